@@ -0,0 +1,323 @@
+//! Access control and call safety components shared by the ink! contracts in this repository.
+//!
+//! Each component is a storage item a contract keeps as a field and checks at the start of its messages:
+//!
+//! - [`Ownable`] holds the single account with administrative rights,
+//! - [`AccessControl`] holds the accounts granted each [`RoleId`],
+//! - [`Pausable`] holds the functions that are paused,
+//! - [`ReentrancyGuard`] rejects a message that is entered again while it runs, see [`non_reentrant!`].
+//!
+//! The checks fail with a unit error per component. A contract converts it into its own `Error` with a `From` impl,
+//! so `?` on a check returns the contract's usual variant and the contract ABI does not change.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+use ink::storage::{traits::ManualKey, Lazy, Mapping};
+
+/// Storage key of the reentrancy flag. It is fixed, like the storage version's, so every code version of a contract
+/// reads the same cell.
+pub const REENTRANCY_GUARD_KEY: u32 = 0x6c6f_636b;
+
+/// Returned by [`Ownable::ensure_owner`] when the caller is not the owner.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct NotOwner;
+
+/// Returned by [`AccessControl::ensure_role`] when the account has not been granted the role.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct MissingRole;
+
+/// Returned by [`Pausable::ensure_not_paused`] when the function is paused.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct IsPaused;
+
+/// Returned by [`ReentrancyGuard::enter`] when a guarded message is already running.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ReentrantCall;
+
+/// The account with administrative rights over a contract. It encodes as a bare `AccountId`, so it can replace an
+/// `AccountId` field of a packed storage root without changing the layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+pub struct Ownable {
+    owner: AccountId,
+}
+
+impl Ownable {
+    /// Makes `owner` the owner.
+    pub fn new(owner: AccountId) -> Self {
+        Self { owner }
+    }
+
+    /// Returns the owner.
+    pub fn owner(&self) -> AccountId {
+        self.owner
+    }
+
+    /// Returns `NotOwner` unless `caller` is the owner.
+    pub fn ensure_owner(&self, caller: AccountId) -> Result<(), NotOwner> {
+        if caller != self.owner {
+            return Err(NotOwner);
+        }
+        Ok(())
+    }
+
+    /// Hands ownership from `caller`, who must be the owner, to `new_owner`. Returns the previous owner.
+    pub fn transfer_ownership(&mut self, caller: AccountId, new_owner: AccountId) -> Result<AccountId, NotOwner> {
+        self.ensure_owner(caller)?;
+        Ok(core::mem::replace(&mut self.owner, new_owner))
+    }
+}
+
+/// Identifies a role. Contracts declare their roles as constants.
+pub type RoleId = u32;
+
+/// The accounts granted each role. Membership is checked with a single read; the member list of a role is kept
+/// alongside for enumeration and is only read by [`AccessControl::members`] and when granting or revoking.
+///
+/// Granting and revoking are not permissioned here: a contract checks its own admin rule, usually
+/// [`Ownable::ensure_owner`], before calling them.
+#[ink::storage_item]
+#[derive(Debug, Default)]
+pub struct AccessControl {
+    grants: Mapping<(RoleId, AccountId), ()>,
+    members: Mapping<RoleId, Vec<AccountId>>,
+}
+
+impl AccessControl {
+    /// Returns whether `account` has been granted `role`.
+    pub fn has_role(&self, role: RoleId, account: AccountId) -> bool {
+        self.grants.contains((role, account))
+    }
+
+    /// Returns `MissingRole` unless `account` has been granted `role`.
+    pub fn ensure_role(&self, role: RoleId, account: AccountId) -> Result<(), MissingRole> {
+        if !self.has_role(role, account) {
+            return Err(MissingRole);
+        }
+        Ok(())
+    }
+
+    /// Grants `role` to `account`. Returns false if it already had the role.
+    pub fn grant_role(&mut self, role: RoleId, account: AccountId) -> bool {
+        if self.has_role(role, account) {
+            return false;
+        }
+        self.grants.insert((role, account), &());
+        let mut members = self.members(role);
+        members.push(account);
+        self.members.insert(role, &members);
+        true
+    }
+
+    /// Revokes `role` from `account`. Returns false if it did not have the role.
+    pub fn revoke_role(&mut self, role: RoleId, account: AccountId) -> bool {
+        if !self.has_role(role, account) {
+            return false;
+        }
+        self.grants.remove((role, account));
+        let mut members = self.members(role);
+        members.retain(|member| *member != account);
+        self.members.insert(role, &members);
+        true
+    }
+
+    /// Returns the accounts granted `role`, in the order they were granted it.
+    pub fn members(&self, role: RoleId) -> Vec<AccountId> {
+        self.members.get(role).unwrap_or_default()
+    }
+}
+
+/// The paused functions of a contract, identified by `F`. A contract that pauses as a whole uses the default `()`.
+///
+/// It encodes as a bare `Vec<F>`, so it can replace a `Vec<F>` kept in a `Lazy` cell without changing the layout.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+pub struct Pausable<F = ()> {
+    paused: Vec<F>,
+}
+
+impl<F> Default for Pausable<F> {
+    fn default() -> Self {
+        Self { paused: Vec::new() }
+    }
+}
+
+impl<F: PartialEq> Pausable<F> {
+    /// Returns whether `function` is paused.
+    pub fn is_paused(&self, function: &F) -> bool {
+        self.paused.contains(function)
+    }
+
+    /// Returns `IsPaused` if `function` is paused.
+    pub fn ensure_not_paused(&self, function: &F) -> Result<(), IsPaused> {
+        if self.is_paused(function) {
+            return Err(IsPaused);
+        }
+        Ok(())
+    }
+
+    /// Pauses or resumes `function`. Returns whether its state changed, e.g. for the contract to emit an event.
+    pub fn set_paused(&mut self, function: F, paused: bool) -> bool {
+        if paused == self.is_paused(&function) {
+            return false;
+        }
+        if paused {
+            self.paused.push(function);
+        } else {
+            self.paused.retain(|paused_function| *paused_function != function);
+        }
+        true
+    }
+
+    /// Returns the paused functions, in the order they were paused.
+    pub fn paused(&self) -> &[F] {
+        &self.paused
+    }
+}
+
+/// Marks a contract as running a guarded message. It lives in its own cell rather than the packed root, so adding it
+/// to an upgradeable contract does not change the layout of the root.
+///
+/// ink!'s call builders deny reentry unless a call sets `allow_reentry`, so the guard only adds protection against
+/// callees that are allowed to call back. It makes a message safe regardless of how each of its calls is built.
+#[ink::storage_item]
+#[derive(Debug, Default)]
+pub struct ReentrancyGuard {
+    entered: Lazy<bool, ManualKey<REENTRANCY_GUARD_KEY>>,
+}
+
+impl ReentrancyGuard {
+    /// Marks a guarded message as running. Returns `ReentrantCall` if one already is.
+    pub fn enter(&mut self) -> Result<(), ReentrantCall> {
+        if self.entered.get().unwrap_or(false) {
+            return Err(ReentrantCall);
+        }
+        self.entered.set(&true);
+        Ok(())
+    }
+
+    /// Marks the guarded message as finished.
+    pub fn exit(&mut self) {
+        self.entered.set(&false);
+    }
+}
+
+/// Runs a message body with a [`ReentrancyGuard`] entered, releasing it however the body finishes.
+///
+/// `non_reentrant!(self.guard, { ... })` evaluates to the body's `Result`. The contract's error type must convert
+/// from [`ReentrantCall`]. A `return` or `?` in the body leaves the body rather than the message, so the guard is
+/// released before the message returns.
+#[macro_export]
+macro_rules! non_reentrant {
+    ($guard:expr, $body:block) => {{
+        match $guard.enter() {
+            Ok(()) => {
+                #[allow(clippy::redundant_closure_call)]
+                let result = (|| $body)();
+                $guard.exit();
+                result
+            }
+            Err(error) => Err(error.into()),
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(byte: u8) -> AccountId {
+        AccountId::from([byte; 32])
+    }
+
+    /// Only the owner passes the check, and ownership moves only when the owner hands it over.
+    #[test]
+    fn ownership_is_transferred_by_the_owner() {
+        let mut ownable = Ownable::new(account(1));
+        assert_eq!(ownable.ensure_owner(account(1)), Ok(()));
+        assert_eq!(ownable.ensure_owner(account(2)), Err(NotOwner));
+
+        assert_eq!(ownable.transfer_ownership(account(2), account(2)), Err(NotOwner));
+        assert_eq!(ownable.transfer_ownership(account(1), account(2)), Ok(account(1)));
+        assert_eq!(ownable.owner(), account(2));
+        assert_eq!(ownable.ensure_owner(account(1)), Err(NotOwner));
+    }
+
+    /// `Ownable` and `Pausable` keep the encoding of the fields they replace.
+    #[test]
+    fn packed_components_encode_like_the_fields_they_replace() {
+        use scale::Encode;
+        assert_eq!(Ownable::new(account(7)).encode(), account(7).encode());
+
+        let mut pausable = Pausable::<u8>::default();
+        pausable.set_paused(3, true);
+        pausable.set_paused(1, true);
+        assert_eq!(pausable.encode(), vec![3u8, 1].encode());
+    }
+
+    /// Roles are granted and revoked per account, and each role keeps its own member list.
+    #[test]
+    fn roles_are_granted_and_revoked() {
+        ink::env::test::run_test::<ink::env::DefaultEnvironment, _>(|_| {
+            const MINTER: RoleId = 1;
+            const BURNER: RoleId = 2;
+            let mut roles = AccessControl::default();
+
+            assert!(roles.grant_role(MINTER, account(1)));
+            assert!(roles.grant_role(MINTER, account(2)));
+            assert!(!roles.grant_role(MINTER, account(1)));
+            assert!(roles.grant_role(BURNER, account(1)));
+            assert_eq!(roles.ensure_role(MINTER, account(2)), Ok(()));
+            assert_eq!(roles.ensure_role(BURNER, account(2)), Err(MissingRole));
+            assert_eq!(roles.members(MINTER), vec![account(1), account(2)]);
+
+            assert!(roles.revoke_role(MINTER, account(1)));
+            assert!(!roles.revoke_role(MINTER, account(1)));
+            assert!(!roles.has_role(MINTER, account(1)));
+            assert!(roles.has_role(BURNER, account(1)));
+            assert_eq!(roles.members(MINTER), vec![account(2)]);
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    /// Pausing a function twice or resuming it while running changes nothing.
+    #[test]
+    fn functions_are_paused_and_resumed() {
+        let mut pausable = Pausable::<u8>::default();
+        assert_eq!(pausable.ensure_not_paused(&1), Ok(()));
+        assert!(pausable.set_paused(1, true));
+        assert!(!pausable.set_paused(1, true));
+        assert_eq!(pausable.ensure_not_paused(&1), Err(IsPaused));
+        assert_eq!(pausable.ensure_not_paused(&2), Ok(()));
+
+        assert!(pausable.set_paused(1, false));
+        assert!(!pausable.set_paused(1, false));
+        assert!(pausable.paused().is_empty());
+    }
+
+    /// A guarded body cannot be entered again while it runs, and the guard is released after it fails.
+    #[test]
+    fn guard_rejects_reentry_and_is_released() {
+        ink::env::test::run_test::<ink::env::DefaultEnvironment, _>(|_| {
+            let mut guard = ReentrancyGuard::default();
+            let nested: Result<Result<(), ReentrantCall>, ReentrantCall> = non_reentrant!(guard, {
+                let mut inner = ReentrancyGuard::default();
+                Ok(non_reentrant!(inner, { Ok(()) }))
+            });
+            assert_eq!(nested, Ok(Err(ReentrantCall)));
+
+            let failed: Result<(), ReentrantCall> = non_reentrant!(guard, { Err(ReentrantCall) });
+            assert_eq!(failed, Err(ReentrantCall));
+            let after: Result<u8, ReentrantCall> = non_reentrant!(guard, { Ok(1) });
+            assert_eq!(after, Ok(1));
+            Ok(())
+        })
+        .unwrap();
+    }
+}
@@ -0,0 +1,241 @@
+//! Value-handling arithmetic shared by the ink! contracts in this repository.
+//!
+//! Every function that decides how much of a pool, a basket or a fee someone receives lives here, so the property
+//! tests in `tests/` can check it against randomized action sequences without deploying a contract:
+//!
+//! - [`qf`] splits a quadratic funding pool between proposals (DARO),
+//! - [`vault`] prices basket shares and stability fees (SyntheticAssetForge),
+//! - [`rewards`] distributes collected fees to stakers (SyntheticAssetForge).
+//!
+//! All of it rounds in favour of the pool, so no sequence of calls pays out more than was paid in. The functions fail
+//! with a [`MathError`] instead of wrapping or trapping, for the contract to map into its own `Error`.
+//!
+//! The crate has no dependencies and builds for any toolchain ink! 4 contracts build with.
+#![cfg_attr(not(test), no_std)]
+
+/// Why a calculation failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MathError {
+    /// An intermediate or the result does not fit in a `u128`.
+    Overflow,
+    /// A subtraction went below zero.
+    Underflow,
+    /// A share of an empty total was asked for.
+    DivisionByZero,
+}
+
+/// `a * b / denominator`, rounded down, without overflowing on the intermediate product. Fails only if the result
+/// itself does not fit in a `u128`.
+pub fn mul_div(a: u128, b: u128, denominator: u128) -> Result<u128, MathError> {
+    if denominator == 0 {
+        return Err(MathError::DivisionByZero);
+    }
+    if let Some(product) = a.checked_mul(b) {
+        return Ok(product / denominator);
+    }
+
+    // Multiply into 256 bits from 64-bit halves.
+    const LOW: u128 = u64::MAX as u128;
+    let (a_high, a_low) = (a >> 64, a & LOW);
+    let (b_high, b_low) = (b >> 64, b & LOW);
+    let low_low = a_low * b_low;
+    let low_high = a_low * b_high;
+    let high_low = a_high * b_low;
+    let middle = (low_low >> 64) + (low_high & LOW) + (high_low & LOW);
+    let low = (low_low & LOW) | (middle << 64);
+    let high = a_high * b_high + (low_high >> 64) + (high_low >> 64) + (middle >> 64);
+    if high >= denominator {
+        return Err(MathError::Overflow);
+    }
+
+    // Long division one bit at a time, keeping the remainder below the denominator.
+    let (mut remainder, mut quotient) = (high, 0u128);
+    for bit in (0..128).rev() {
+        let carry = remainder >> 127;
+        remainder = (remainder << 1) | ((low >> bit) & 1);
+        quotient <<= 1;
+        if carry == 1 || remainder >= denominator {
+            remainder = remainder.wrapping_sub(denominator);
+            quotient |= 1;
+        }
+    }
+    Ok(quotient)
+}
+
+/// Quadratic funding: a proposal's matching is proportional to the square of the sum of the square roots of its
+/// contributions.
+pub mod qf {
+    use super::{mul_div, MathError};
+
+    /// Integer square root, rounded down. `u128::isqrt` needs a newer toolchain than ink! 4 contracts build with.
+    pub fn isqrt(value: u128) -> u128 {
+        if value < 2 {
+            return value;
+        }
+        // Newton's method from a power of two at or above the root decreases monotonically onto it.
+        let mut root = 1u128 << (128 - value.leading_zeros()).div_ceil(2);
+        loop {
+            let next = (root + value / root) / 2;
+            if next >= root {
+                return root;
+            }
+            root = next;
+        }
+    }
+
+    /// Sum of the square roots of a proposal's contributions.
+    pub fn sum_sqrt(contributions: impl IntoIterator<Item = u128>) -> Result<u128, MathError> {
+        contributions
+            .into_iter()
+            .try_fold(0u128, |sum, contribution| sum.checked_add(isqrt(contribution)).ok_or(MathError::Overflow))
+    }
+
+    /// Sum of the squared `sum_sqrt` of every proposal in a round: the total the pool is split by.
+    pub fn quadratic_sum(sqrt_sums: impl IntoIterator<Item = u128>) -> Result<u128, MathError> {
+        sqrt_sums.into_iter().try_fold(0u128, |total, sum| {
+            sum.checked_mul(sum).and_then(|square| total.checked_add(square)).ok_or(MathError::Overflow)
+        })
+    }
+
+    /// Matching a proposal with `sqrt_sum` receives from `pool`, rounded down. A round nobody contributed to, whose
+    /// `quadratic_sum` is zero, matches nothing.
+    pub fn matching_amount(sqrt_sum: u128, quadratic_sum: u128, pool: u128) -> Result<u128, MathError> {
+        if quadratic_sum == 0 {
+            return Ok(0);
+        }
+        let square = sqrt_sum.checked_mul(sqrt_sum).ok_or(MathError::Overflow)?;
+        mul_div(square, pool, quadratic_sum)
+    }
+}
+
+/// Shares of a basket of assets, and the stability fee charged on them.
+pub mod vault {
+    use super::MathError;
+
+    /// Shares minted for a deposit: the deposit value over the basket value before the deposit, scaled by the
+    /// current supply. The first deposit into an empty basket mints one share per unit of value.
+    pub fn shares_for_deposit(deposit_value: u128, basket_value: u128, total_supply: u128) -> Result<u128, MathError> {
+        if total_supply == 0 || basket_value == 0 {
+            return Ok(deposit_value);
+        }
+        deposit_value
+            .checked_mul(total_supply)
+            .ok_or(MathError::Overflow)?
+            .checked_div(basket_value)
+            .ok_or(MathError::DivisionByZero)
+    }
+
+    /// Amount of an asset balance redeemed by burning `shares` out of `total_supply`. Rounds down so the basket
+    /// always keeps enough to back the remaining shares.
+    pub fn redemption_amount(balance: u128, shares: u128, total_supply: u128) -> Result<u128, MathError> {
+        balance
+            .checked_mul(shares)
+            .ok_or(MathError::Overflow)?
+            .checked_div(total_supply)
+            .ok_or(MathError::DivisionByZero)
+    }
+
+    /// Fee of `fee_percentage` percent on `amount`, rounded down.
+    pub fn fee_for(amount: u128, fee_percentage: u32) -> Result<u128, MathError> {
+        amount.checked_mul(fee_percentage as u128).ok_or(MathError::Overflow).map(|fee| fee / 100)
+    }
+}
+
+/// Fees distributed to stakers through a reward-per-share accumulator.
+///
+/// The pool keeps `acc_reward_per_share`, the fees distributed per staked unit so far, and every staker a
+/// `reward_debt`, the [`reward_debt`] of their stake when they last changed it. What a staker earned since is the
+/// difference, so distributing a fee is O(1) whatever the number of stakers.
+///
+/// Rewards are rounded down and debts up, so no staker is paid the fraction of a unit that accumulated before they
+/// staked, and [`distribute`] holds back the fractions the stakers may still be paid together.
+pub mod rewards {
+    use super::MathError;
+
+    /// Fixed-point scale of `acc_reward_per_share`.
+    pub const REWARD_PRECISION: u128 = 1_000_000_000_000;
+
+    /// Increase of `acc_reward_per_share` when `fees` are distributed over `total_staked`.
+    pub fn reward_per_share(fees: u128, total_staked: u128) -> Result<u128, MathError> {
+        fees.checked_mul(REWARD_PRECISION)
+            .ok_or(MathError::Overflow)?
+            .checked_div(total_staked)
+            .ok_or(MathError::DivisionByZero)
+    }
+
+    /// Rewards accumulated by `amount` staked since `acc_reward_per_share` was zero, rounded down.
+    pub fn accumulated_reward(amount: u128, acc_reward_per_share: u128) -> Result<u128, MathError> {
+        amount
+            .checked_mul(acc_reward_per_share)
+            .ok_or(MathError::Overflow)
+            .map(|reward| reward / REWARD_PRECISION)
+    }
+
+    /// Reward debt of `amount` staked at `acc_reward_per_share`: its accumulated reward, rounded up.
+    pub fn reward_debt(amount: u128, acc_reward_per_share: u128) -> Result<u128, MathError> {
+        amount
+            .checked_mul(acc_reward_per_share)
+            .ok_or(MathError::Overflow)
+            .map(|reward| reward.div_ceil(REWARD_PRECISION))
+    }
+
+    /// Rewards earned by `amount` staked since its last checkpoint. Zero until the accumulated reward has caught up
+    /// with the rounded-up debt.
+    pub fn pending_reward(amount: u128, acc_reward_per_share: u128, reward_debt: u128) -> Result<u128, MathError> {
+        Ok(accumulated_reward(amount, acc_reward_per_share)?.saturating_sub(reward_debt))
+    }
+
+    /// Distributes `undistributed` fees over `total_staked`: returns the increase of `acc_reward_per_share` and the
+    /// remainder to keep for the next distribution. The increase is worth at most its exact share to the stakers
+    /// together, and the remainder leaves that share rounded up.
+    pub fn distribute(undistributed: u128, total_staked: u128) -> Result<(u128, u128), MathError> {
+        let increment = reward_per_share(undistributed, total_staked)?;
+        let distributed = total_staked.checked_mul(increment).ok_or(MathError::Overflow)?.div_ceil(REWARD_PRECISION);
+        let remainder = undistributed.checked_sub(distributed).ok_or(MathError::Underflow)?;
+        Ok((increment, remainder))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The wide path agrees with plain arithmetic, and fails only when the result does not fit.
+    #[test]
+    fn mul_div_handles_wide_products() {
+        assert_eq!(mul_div(6, 7, 4), Ok(10));
+        assert_eq!(mul_div(u128::MAX, u128::MAX, u128::MAX), Ok(u128::MAX));
+        assert_eq!(mul_div(u128::MAX, 3, 4), Ok(u128::MAX / 4 * 3 + 2));
+        assert_eq!(mul_div(1 << 100, 1 << 100, 1 << 90), Ok(1 << 110));
+        assert_eq!(mul_div(u128::MAX, 2, 1), Err(MathError::Overflow));
+        assert_eq!(mul_div(1, 1, 0), Err(MathError::DivisionByZero));
+    }
+
+    #[test]
+    fn isqrt_rounds_down() {
+        assert_eq!(qf::isqrt(0), 0);
+        assert_eq!(qf::isqrt(1), 1);
+        assert_eq!(qf::isqrt(8), 2);
+        assert_eq!(qf::isqrt(9), 3);
+        assert_eq!(qf::isqrt(u128::MAX), u64::MAX as u128);
+    }
+
+    /// Debts round up and rewards down, so a debt just recorded leaves nothing pending.
+    #[test]
+    fn debts_round_against_the_staker() {
+        let acc = rewards::REWARD_PRECISION / 3;
+        assert_eq!(rewards::accumulated_reward(10, acc), Ok(3));
+        assert_eq!(rewards::reward_debt(10, acc), Ok(4));
+        assert_eq!(rewards::pending_reward(10, acc, 4), Ok(0));
+        assert_eq!(rewards::pending_reward(10, 2 * acc, 4), Ok(2));
+    }
+
+    /// Reward math rejects overflowing inputs instead of wrapping.
+    #[test]
+    fn reward_math_checks_overflow() {
+        assert_eq!(rewards::reward_per_share(u128::MAX, 1), Err(MathError::Overflow));
+        assert_eq!(rewards::accumulated_reward(u128::MAX, 2), Err(MathError::Overflow));
+        assert_eq!(rewards::reward_debt(u128::MAX, 2), Err(MathError::Overflow));
+        assert_eq!(rewards::distribute(1, 0), Err(MathError::DivisionByZero));
+    }
+}
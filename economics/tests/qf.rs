@@ -0,0 +1,88 @@
+//! Quadratic funding rounds never pay out more than their pool, whatever the contributions.
+
+use economics::{mul_div, qf, MathError};
+use proptest::prelude::*;
+
+/// Most proposals in a round.
+const MAX_PROPOSALS: usize = 8;
+
+/// A contribution to one of the round's proposals.
+#[derive(Clone, Debug)]
+struct Contribution {
+    proposal: usize,
+    amount: u128,
+}
+
+/// Small contributions, whose square roots round down the most, and contributions of up to a million 18-decimal tokens.
+fn contribution() -> impl Strategy<Value = Contribution> {
+    (0..MAX_PROPOSALS, prop_oneof![0..1_000u128, 0..1_000_000_000_000_000_000_000_000u128])
+        .prop_map(|(proposal, amount)| Contribution { proposal, amount })
+}
+
+/// Pools from dust to more than the contributions' squares, so the wide product path is taken.
+fn pool() -> impl Strategy<Value = u128> {
+    prop_oneof![0..1_000u128, any::<u64>().prop_map(u128::from), 0..u128::MAX / 2]
+}
+
+/// Runs a round over the contributions the way DARO's `quadratic_funding_round` does.
+fn round(contributions: &[Contribution], pool: u128) -> Result<Vec<u128>, MathError> {
+    let sqrt_sums = (0..MAX_PROPOSALS)
+        .map(|proposal| {
+            qf::sum_sqrt(contributions.iter().filter(|c| c.proposal == proposal).map(|c| c.amount))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let quadratic_sum = qf::quadratic_sum(sqrt_sums.iter().copied())?;
+    sqrt_sums.iter().map(|sum| qf::matching_amount(*sum, quadratic_sum, pool)).collect()
+}
+
+proptest! {
+    #[test]
+    fn isqrt_is_the_floor_of_the_root(value in any::<u128>()) {
+        let root = qf::isqrt(value);
+        prop_assert!(root * root <= value);
+        prop_assert!((root + 1).checked_mul(root + 1).is_none_or(|square| square > value));
+    }
+
+    #[test]
+    fn mul_div_matches_narrow_arithmetic(a in any::<u64>(), b in any::<u64>(), denominator in 1..u128::MAX) {
+        let (a, b) = (u128::from(a), u128::from(b));
+        prop_assert_eq!(mul_div(a, b, denominator), Ok(a * b / denominator));
+        prop_assert_eq!(mul_div(denominator, b, denominator), Ok(b));
+    }
+
+    /// The matching paid out never exceeds the pool, and only rounding dust of under one unit per proposal is left.
+    #[test]
+    fn matching_never_exceeds_the_pool(
+        contributions in prop::collection::vec(contribution(), 0..32),
+        pool in pool(),
+    ) {
+        let matching = round(&contributions, pool).expect("contributions this size do not overflow");
+        let paid = matching.iter().try_fold(0u128, |paid, amount| paid.checked_add(*amount));
+        let paid = paid.expect("the matching sums to at most the pool");
+        prop_assert!(paid <= pool);
+        if contributions.iter().any(|c| c.amount > 0) {
+            prop_assert!(pool - paid < MAX_PROPOSALS as u128);
+        } else {
+            prop_assert_eq!(paid, 0);
+        }
+    }
+
+    /// A proposal with a larger sum of square roots never receives less matching than another.
+    #[test]
+    fn broader_support_earns_more_matching(
+        contributions in prop::collection::vec(contribution(), 1..32),
+        pool in pool(),
+    ) {
+        let matching = round(&contributions, pool).unwrap();
+        let sqrt_sums: Vec<u128> = (0..MAX_PROPOSALS)
+            .map(|p| qf::sum_sqrt(contributions.iter().filter(|c| c.proposal == p).map(|c| c.amount)).unwrap())
+            .collect();
+        for i in 0..MAX_PROPOSALS {
+            for j in 0..MAX_PROPOSALS {
+                if sqrt_sums[i] >= sqrt_sums[j] {
+                    prop_assert!(matching[i] >= matching[j]);
+                }
+            }
+        }
+    }
+}
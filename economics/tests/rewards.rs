@@ -0,0 +1,194 @@
+//! Stakers are never paid more stability fees than were collected, however stakes and fees interleave.
+
+use economics::rewards::{self, REWARD_PRECISION};
+use proptest::prelude::*;
+
+/// Number of accounts that stake.
+const STAKERS: usize = 4;
+
+/// Simulates SyntheticAssetForge's staking pool: fees are distributed on collection, fees collected while nothing is
+/// staked wait for the next staker, and every stake change or claim checkpoints the staker first.
+#[derive(Debug, Default)]
+struct Pool {
+    total_staked: u128,
+    acc_reward_per_share: u128,
+    undistributed: u128,
+    stakes: [(u128, u128, u128); STAKERS], // (amount_staked, reward_debt, pending_rewards)
+    collected: u128,
+    claimed: u128,
+}
+
+impl Pool {
+    fn update(&mut self) {
+        if self.total_staked == 0 || self.undistributed == 0 {
+            return;
+        }
+        let (increment, remainder) = rewards::distribute(self.undistributed, self.total_staked).unwrap();
+        self.acc_reward_per_share += increment;
+        self.undistributed = remainder;
+    }
+
+    fn collect(&mut self, fee: u128) {
+        self.collected += fee;
+        self.undistributed += fee;
+        self.update();
+    }
+
+    fn stake(&mut self, index: usize, amount: i128) {
+        self.update();
+        let (staked, debt, pending) = self.stakes[index];
+        let earned = rewards::pending_reward(staked, self.acc_reward_per_share, debt).unwrap();
+        let staked = (staked as i128 + amount) as u128;
+        self.total_staked = (self.total_staked as i128 + amount) as u128;
+        let debt = rewards::reward_debt(staked, self.acc_reward_per_share).unwrap();
+        self.stakes[index] = (staked, debt, pending + earned);
+    }
+
+    fn claim(&mut self, index: usize) {
+        self.stake(index, 0);
+        self.claimed += std::mem::take(&mut self.stakes[index].2);
+    }
+
+    fn claimable(&self, index: usize) -> u128 {
+        let (staked, debt, pending) = self.stakes[index];
+        pending + rewards::pending_reward(staked, self.acc_reward_per_share, debt).unwrap()
+    }
+}
+
+/// Equal stakes split fees equally; larger stakes get proportionally more.
+#[test]
+fn fees_are_split_pro_rata_by_stake() {
+    let mut pool = Pool::default();
+    pool.stake(0, 100);
+    pool.stake(1, 300);
+    pool.collect(1_000);
+    assert_eq!(pool.claimable(0), 250);
+    assert_eq!(pool.claimable(1), 750);
+}
+
+/// A staker earns nothing from fees collected before they entered.
+#[test]
+fn late_staker_does_not_share_earlier_fees() {
+    let mut pool = Pool::default();
+    pool.stake(0, 100);
+    pool.collect(1_000);
+    pool.stake(1, 100);
+    assert_eq!(pool.claimable(1), 0);
+    pool.collect(1_000);
+    assert_eq!(pool.claimable(0), 1_500);
+    assert_eq!(pool.claimable(1), 500);
+}
+
+/// Changing a stake mid-way keeps what was earned at the old size and earns at the new size afterwards.
+#[test]
+fn stake_changes_are_checkpointed() {
+    let mut pool = Pool::default();
+    pool.stake(0, 100);
+    pool.stake(1, 100);
+    pool.collect(1_000);
+
+    // Alice triples her stake, then Bob withdraws half of his.
+    pool.stake(0, 200);
+    pool.stake(1, -50);
+    pool.collect(700);
+
+    assert_eq!(pool.claimable(0), 500 + 600);
+    assert_eq!(pool.claimable(1), 500 + 100);
+
+    // An exited staker keeps their rewards but earns nothing more.
+    pool.stake(1, -50);
+    pool.collect(300);
+    assert_eq!(pool.claimable(0), 500 + 600 + 300);
+    assert_eq!(pool.claimable(1), 500 + 100);
+}
+
+/// Rounding never pays out more than was collected.
+#[test]
+fn distribution_never_exceeds_fees() {
+    let mut pool = Pool::default();
+    pool.stake(0, 3);
+    pool.stake(1, 7);
+    pool.stake(2, 11);
+    let mut collected = 0;
+    for fee in [1u128, 10, 999, 12_345] {
+        pool.collect(fee);
+        collected += fee;
+    }
+    let paid: u128 = (0..3).map(|index| pool.claimable(index)).sum();
+    assert!(paid <= collected);
+    assert!(collected - paid <= 3);
+}
+
+/// Fees collected while nothing is staked are kept for the first staker instead of being lost.
+#[test]
+fn fees_wait_for_a_staker() {
+    let mut pool = Pool::default();
+    pool.collect(1_000);
+    pool.stake(0, 7);
+    pool.collect(0);
+    assert_eq!(pool.acc_reward_per_share, 1_000 * REWARD_PRECISION / 7);
+    // Less the fraction of a unit lost to rounding.
+    assert_eq!(pool.claimable(0), 999);
+}
+
+/// A staker who enters when the accumulator is part-way through a unit is not paid that fraction.
+#[test]
+fn entering_mid_unit_earns_nothing_extra() {
+    let mut pool = Pool::default();
+    pool.stake(0, 54);
+    pool.collect(1_378);
+    pool.stake(1, 1);
+    pool.collect(1);
+    assert_eq!(pool.claimable(1), 0);
+    assert!(pool.claimable(0) + pool.claimable(1) + pool.undistributed <= 1_379);
+}
+
+#[derive(Clone, Debug)]
+enum Action {
+    Stake { staker: usize, amount: u128 },
+    /// Withdraws `amount`, capped at the staker's stake.
+    Withdraw { staker: usize, amount: u128 },
+    Collect(u128),
+    Claim(usize),
+}
+
+/// Stakes and fees from single units, which round the most, to a billion units. Much larger ones can make the
+/// checked reward math fail with `Overflow`, which the forge reports instead of paying out.
+fn amount() -> impl Strategy<Value = u128> {
+    prop_oneof![1..100u128, 1..1_000_000_000u128]
+}
+
+fn action() -> impl Strategy<Value = Action> {
+    prop_oneof![
+        (0..STAKERS, amount()).prop_map(|(staker, amount)| Action::Stake { staker, amount }),
+        (0..STAKERS, amount()).prop_map(|(staker, amount)| Action::Withdraw { staker, amount }),
+        amount().prop_map(Action::Collect),
+        (0..STAKERS).prop_map(Action::Claim),
+    ]
+}
+
+proptest! {
+    /// What stakers have claimed, what they can still claim and what waits to be distributed never add up to more
+    /// than the fees collected, and a staker's claimable rewards never shrink until they claim.
+    #[test]
+    fn stakers_are_paid_at_most_the_fees_collected(actions in prop::collection::vec(action(), 1..64)) {
+        let mut pool = Pool::default();
+        for action in &actions {
+            let claimable_before: Vec<u128> = (0..STAKERS).map(|index| pool.claimable(index)).collect();
+            match *action {
+                Action::Stake { staker, amount } => pool.stake(staker, amount as i128),
+                Action::Withdraw { staker, amount } => pool.stake(staker, -(amount.min(pool.stakes[staker].0) as i128)),
+                Action::Collect(fee) => pool.collect(fee),
+                Action::Claim(staker) => pool.claim(staker),
+            }
+
+            let owed: u128 = (0..STAKERS).map(|index| pool.claimable(index)).sum();
+            prop_assert!(pool.claimed + owed + pool.undistributed <= pool.collected, "{:?} paid out more than was collected", action);
+            for (index, before) in claimable_before.iter().enumerate() {
+                if !matches!(*action, Action::Claim(staker) if staker == index) {
+                    prop_assert!(pool.claimable(index) >= *before, "{:?} lowered staker {}'s rewards", action, index);
+                }
+            }
+        }
+    }
+}
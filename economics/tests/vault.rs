@@ -0,0 +1,195 @@
+//! Basket shares are priced so that no sequence of mints and burns takes out more than was put in.
+
+use economics::{vault, MathError};
+use proptest::prelude::*;
+
+/// Number of accounts that mint and burn.
+const HOLDERS: usize = 3;
+
+/// The first deposit mints one share per unit of value; later deposits are priced against the basket.
+#[test]
+fn shares_are_proportional_to_basket_value() {
+    assert_eq!(vault::shares_for_deposit(1_000, 0, 0), Ok(1_000));
+    assert_eq!(vault::shares_for_deposit(1_000, 1_000, 1_000), Ok(1_000));
+    assert_eq!(vault::shares_for_deposit(500, 2_000, 1_000), Ok(250));
+}
+
+/// After prices move, a new depositor must not dilute or be diluted by existing holders: every share keeps
+/// redeeming the same fraction of the basket.
+#[test]
+fn price_drift_does_not_dilute_holders() {
+    // Alice deposits 10 units of a single asset at a price of 100.
+    let alice_shares = vault::shares_for_deposit(10 * 100, 0, 0).unwrap();
+    let mut supply = alice_shares;
+    let mut balance: u128 = 10;
+
+    // The price doubles, then Bob deposits 10 units at the new price of 200.
+    let basket_value = balance * 200;
+    let bob_shares = vault::shares_for_deposit(10 * 200, basket_value, supply).unwrap();
+    assert_eq!(bob_shares, alice_shares);
+    supply += bob_shares;
+    balance += 10;
+
+    // Each share is still worth the basket value over the supply, for both holders.
+    let value_per_share_before = basket_value / alice_shares;
+    let value_per_share_after = balance * 200 / supply;
+    assert_eq!(value_per_share_before, value_per_share_after);
+
+    // The price halves; Alice redeems her shares and gets exactly her half of the basket.
+    let alice_redeemed = vault::redemption_amount(balance, alice_shares, supply).unwrap();
+    assert_eq!(alice_redeemed, 10);
+    balance -= alice_redeemed;
+    supply -= alice_shares;
+    assert_eq!(vault::redemption_amount(balance, bob_shares, supply), Ok(10));
+}
+
+/// The stability fee is a whole percentage of the amount, rounded down.
+#[test]
+fn fee_is_percentage_of_amount() {
+    assert_eq!(vault::fee_for(1_000, 0), Ok(0));
+    assert_eq!(vault::fee_for(1_000, 2), Ok(20));
+    assert_eq!(vault::fee_for(49, 2), Ok(0));
+    assert_eq!(vault::fee_for(1_000, 100), Ok(1_000));
+    assert_eq!(vault::fee_for(u128::MAX, 2), Err(MathError::Overflow));
+}
+
+/// A mint followed by a burn of the same shares returns the deposit less the stability fee on both legs.
+#[test]
+fn mint_burn_round_trip_charges_fee_twice() {
+    // An existing holder owns 1_000 shares of a basket of 50 units priced at 20.
+    let mut supply: u128 = 1_000;
+    let mut balance: u128 = 50;
+
+    // A depositor adds 50 units; the 2% fee is minted to the contract as a share too.
+    let shares = vault::shares_for_deposit(50 * 20, balance * 20, supply).unwrap();
+    let fee = vault::fee_for(shares, 2).unwrap();
+    assert_eq!((shares, fee), (1_000, 20));
+    supply += shares;
+    balance += 50;
+
+    // Burning the received shares pays the 2% fee again and redeems the rest.
+    let received = shares - fee;
+    let burned = received - vault::fee_for(received, 2).unwrap();
+    let redeemed = vault::redemption_amount(balance, burned, supply).unwrap();
+    assert_eq!(redeemed, 48);
+    assert!(redeemed <= 50 * 98 * 98 / 10_000 + 1);
+}
+
+/// Redemptions round down, so the basket always backs the outstanding shares.
+#[test]
+fn redemption_rounds_down() {
+    assert_eq!(vault::redemption_amount(10, 1, 3), Ok(3));
+    let remaining = 10 - vault::redemption_amount(10, 1, 3).unwrap();
+    assert!(remaining * 3 >= 10 * 2);
+}
+
+#[derive(Clone, Debug)]
+enum Action {
+    /// Deposits `units` of the basket's asset.
+    Mint { holder: usize, units: u128 },
+    /// Burns `shares` of the holder's shares, capped at what they hold.
+    Burn { holder: usize, shares: u128 },
+    /// Moves the oracle price of the asset.
+    SetPrice(u128),
+}
+
+fn action() -> impl Strategy<Value = Action> {
+    prop_oneof![
+        (0..HOLDERS, 1..1_000_000_000u128).prop_map(|(holder, units)| Action::Mint { holder, units }),
+        (0..HOLDERS, 1..1_000_000_000u128).prop_map(|(holder, shares)| Action::Burn { holder, shares }),
+        (1..1_000_000u128).prop_map(Action::SetPrice),
+    ]
+}
+
+/// A single-asset basket that mints and burns like SyntheticAssetForge: the fee on both legs is kept as shares by
+/// the forge, which stay in the supply.
+#[derive(Debug)]
+struct Basket {
+    price: u128,
+    fee_percentage: u32,
+    balance: u128,
+    supply: u128,
+    shares: [u128; HOLDERS],
+    fee_shares: u128,
+    deposited: u128,
+    redeemed: u128,
+}
+
+impl Basket {
+    fn new(price: u128, fee_percentage: u32) -> Self {
+        Self { price, fee_percentage, balance: 0, supply: 0, shares: [0; HOLDERS], fee_shares: 0, deposited: 0, redeemed: 0 }
+    }
+
+    fn apply(&mut self, action: &Action) {
+        match *action {
+            Action::Mint { holder, units } => {
+                let shares =
+                    vault::shares_for_deposit(units * self.price, self.balance * self.price, self.supply).unwrap();
+                let fee = vault::fee_for(shares, self.fee_percentage).unwrap();
+                if shares == fee {
+                    return; // The forge rejects a mint that leaves the receiver nothing.
+                }
+                self.shares[holder] += shares - fee;
+                self.fee_shares += fee;
+                self.supply += shares;
+                self.balance += units;
+                self.deposited += units;
+            }
+            Action::Burn { holder, shares } => {
+                let amount = shares.min(self.shares[holder]);
+                if amount == 0 {
+                    return;
+                }
+                let fee = vault::fee_for(amount, self.fee_percentage).unwrap();
+                let burned = amount - fee;
+                let units = vault::redemption_amount(self.balance, burned, self.supply).unwrap();
+                self.shares[holder] -= amount;
+                self.fee_shares += fee;
+                self.supply -= burned;
+                self.balance -= units;
+                self.redeemed += units;
+            }
+            Action::SetPrice(price) => self.price = price,
+        }
+    }
+
+    /// Units the basket pays out if every outstanding share is burned without a fee, one holder at a time.
+    fn drain(&self) -> u128 {
+        let (mut balance, mut supply, mut paid) = (self.balance, self.supply, 0);
+        for shares in self.shares.iter().chain([&self.fee_shares]) {
+            if *shares == 0 {
+                continue;
+            }
+            let units = vault::redemption_amount(balance, *shares, supply).unwrap();
+            balance -= units;
+            supply -= shares;
+            paid += units;
+        }
+        paid
+    }
+}
+
+proptest! {
+    /// Shares are only created against a deposit, every share is accounted to a holder or the forge, and the basket
+    /// never pays out more than was deposited, even if everyone redeems at the end.
+    #[test]
+    fn mints_and_burns_create_no_value(
+        price in 1..1_000_000u128,
+        fee_percentage in 0..=10u32,
+        actions in prop::collection::vec(action(), 1..64),
+    ) {
+        let mut basket = Basket::new(price, fee_percentage);
+        for action in &actions {
+            let (balance, supply) = (basket.balance, basket.supply);
+            basket.apply(action);
+
+            prop_assert_eq!(basket.shares.iter().sum::<u128>() + basket.fee_shares, basket.supply);
+            prop_assert_eq!(basket.deposited - basket.redeemed, basket.balance);
+            // No mint or burn lowers what a share redeems: balance / supply never decreases.
+            if supply > 0 && basket.supply > 0 {
+                prop_assert!(basket.balance * supply >= balance * basket.supply, "{:?} diluted the shares", action);
+            }
+        }
+        prop_assert!(basket.redeemed + basket.drain() <= basket.deposited);
+    }
+}
@@ -0,0 +1,287 @@
+//! Stable numeric codes for the errors of the ink! contracts in this repository.
+//!
+//! Each contract keeps its own `Error` enum, so its ABI does not change, and classifies every variant into a shared
+//! [`ErrorKind`]. An [`ErrorCode`] is that kind plus the variant's number within the contract, packed into a `u32` as
+//! `kind << 16 | number`. Indexers and frontends can then tell an authorization failure from a failed token transfer
+//! the same way for every contract, and still show the exact variant.
+//!
+//! The classification of each contract lives here as an [`ErrorTable`], so off-chain code decodes the SCALE bytes of
+//! a contract's `Error` with [`ErrorTable::decode`] without depending on the contract crate. The contract converts its
+//! own errors through the same table with `From<&Error> for ErrorCode`, and tests that the table covers every variant.
+//!
+//! A variant's number is its position in the `Error` enum, counted from 1, so codes stay stable as long as variants
+//! are only ever appended. Kinds are only ever appended too.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use scale::Encode;
+
+/// What went wrong, independently of the contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    /// Not classified: a variant carrying a free-form message.
+    Other = 0,
+    /// The caller or signer is not allowed to do this.
+    Unauthorized = 1,
+    /// The contract or the function is paused.
+    Paused = 2,
+    /// An argument is out of range or malformed.
+    InvalidInput = 3,
+    /// The proposal, token, asset or request referred to does not exist.
+    NotFound = 4,
+    /// The entry to create already exists or the action was already taken.
+    AlreadyExists = 5,
+    /// A balance, a payment or points do not cover the amount.
+    InsufficientFunds = 6,
+    /// A calculation overflowed or underflowed.
+    Arithmetic = 7,
+    /// A transfer or a call into another contract failed.
+    CallFailed = 8,
+    /// The contract or the entry is not in a state that allows this.
+    InvalidState = 9,
+    /// A configured limit, supply or tolerance is exceeded.
+    LimitExceeded = 10,
+    /// A deadline passed or a price is too old.
+    Expired = 11,
+}
+
+impl ErrorKind {
+    const ALL: [ErrorKind; 12] = [
+        ErrorKind::Other,
+        ErrorKind::Unauthorized,
+        ErrorKind::Paused,
+        ErrorKind::InvalidInput,
+        ErrorKind::NotFound,
+        ErrorKind::AlreadyExists,
+        ErrorKind::InsufficientFunds,
+        ErrorKind::Arithmetic,
+        ErrorKind::CallFailed,
+        ErrorKind::InvalidState,
+        ErrorKind::LimitExceeded,
+        ErrorKind::Expired,
+    ];
+
+    /// The kind numbered `value`, if there is one.
+    pub fn from_u16(value: u16) -> Option<Self> {
+        Self::ALL.get(value as usize).copied()
+    }
+}
+
+/// A contract error as a kind and the variant's number within its contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ErrorCode {
+    pub kind: ErrorKind,
+    pub number: u16,
+}
+
+impl ErrorCode {
+    /// The code packed into a `u32`: the kind in the upper half, the variant number in the lower.
+    pub const fn value(&self) -> u32 {
+        (self.kind as u32) << 16 | self.number as u32
+    }
+
+    /// Unpacks a [`value`](Self::value). Fails on an unknown kind.
+    pub fn from_value(value: u32) -> Option<Self> {
+        let kind = ErrorKind::from_u16((value >> 16) as u16)?;
+        Some(Self { kind, number: value as u16 })
+    }
+}
+
+impl From<ErrorCode> for u32 {
+    fn from(code: ErrorCode) -> Self {
+        code.value()
+    }
+}
+
+/// The kind of every variant of a contract's `Error`, in declaration order.
+#[derive(Debug)]
+pub struct ErrorTable {
+    contract: &'static str,
+    kinds: &'static [ErrorKind],
+}
+
+impl ErrorTable {
+    pub const fn new(contract: &'static str, kinds: &'static [ErrorKind]) -> Self {
+        Self { contract, kinds }
+    }
+
+    /// Name of the contract the table classifies.
+    pub fn contract(&self) -> &'static str {
+        self.contract
+    }
+
+    /// Number of variants classified.
+    pub fn len(&self) -> usize {
+        self.kinds.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.kinds.is_empty()
+    }
+
+    /// Code of the variant with SCALE index `index`, if the table classifies it.
+    pub fn code(&self, index: u8) -> Option<ErrorCode> {
+        let kind = *self.kinds.get(index as usize)?;
+        Some(ErrorCode { kind, number: u16::from(index) + 1 })
+    }
+
+    /// Decodes the code from the SCALE encoding of a contract error: its first byte is the variant index, and any
+    /// payload after it is ignored.
+    pub fn decode(&self, encoded: &[u8]) -> Option<ErrorCode> {
+        self.code(*encoded.first()?)
+    }
+
+    /// Code of `error`, a contract's `Error`. A variant the table does not classify yet is [`ErrorKind::Other`].
+    pub fn code_of<E: Encode>(&self, error: &E) -> ErrorCode {
+        let index = error.using_encoded(|encoded| encoded[0]);
+        self.code(index).unwrap_or(ErrorCode { kind: ErrorKind::Other, number: u16::from(index) + 1 })
+    }
+}
+
+use ErrorKind::*;
+
+/// Errors of DARO, the governance and quadratic funding contract.
+pub const DARO: ErrorTable = ErrorTable::new(
+    "DARO",
+    &[
+        Unauthorized,      // NotGovernance
+        NotFound,          // InvalidProposalId
+        InsufficientFunds, // InsufficientFunds
+        AlreadyExists,     // PredictionAlreadyMade
+        InvalidState,      // PredictionNotInProgress
+        InvalidState,      // ResolutionAlreadyDone
+        InvalidInput,      // InvalidTimeframe
+        CallFailed,        // TransferFailed
+        AlreadyExists,     // ProposalExists
+        InvalidInput,      // ContributionTooSmall
+        Unauthorized,      // ImpactVerifierMismatch
+        InvalidState,      // ImpactNotReported
+        AlreadyExists,     // ImpactAlreadyReported
+        AlreadyExists,     // ImpactVerifierExists
+        LimitExceeded,     // TooManyImpactVerifiers
+        InvalidInput,      // InvalidImpactThreshold
+        InvalidInput,      // FeeTooHigh
+        CallFailed,        // UpgradeFailed
+        InvalidState,      // AlreadyMigrated
+        Unauthorized,      // NotProposer
+        InvalidInput,      // InvalidVestingSchedule
+        AlreadyExists,     // VestingScheduleExists
+        InvalidState,      // NothingToClaim
+        LimitExceeded,     // AttestationLimitReached
+        LimitExceeded,     // TooManyContributors
+        Arithmetic,        // Overflow
+    ],
+);
+
+/// Errors of AIPoweredNFT.
+pub const AI_POWERED_NFT: ErrorTable = ErrorTable::new(
+    "AIPoweredNFT",
+    &[
+        Unauthorized,      // NotOwner
+        Unauthorized,      // NotAiUpdater
+        NotFound,          // NftNotFound
+        CallFailed,        // TransferFailed
+        InsufficientFunds, // InsufficientBalance
+        InvalidInput,      // ZeroMintFee
+        InvalidInput,      // InvalidInput
+        InsufficientFunds, // MintFeeNotMet
+        Arithmetic,        // Overflow
+        Unauthorized,      // InvalidSignature
+        NotFound,          // UnknownOracleKey
+        InvalidInput,      // InvalidNonce
+        AlreadyExists,     // OracleKeyExists
+        InvalidState,      // TraitRetired
+        LimitExceeded,     // TraitSupplyExhausted
+        NotFound,          // VersionNotFound
+        AlreadyExists,     // UpdaterExists
+        NotFound,          // UpdaterNotFound
+        InsufficientFunds, // UpdateFeeNotMet
+        NotFound,          // RequestNotFound
+        InvalidState,      // RequestNotPending
+        InvalidState,      // RequestNotExpired
+        Unauthorized,      // NotRequester
+        Unauthorized,      // NotCreator
+        NotFound,          // NotListed
+        InsufficientFunds, // PriceNotMet
+        InvalidState,      // TokenStaked
+        InvalidState,      // NotStaked
+        InsufficientFunds, // InsufficientPoints
+    ],
+);
+
+/// Errors of SyntheticAssetForge.
+pub const SYNTHETIC_ASSET_FORGE: ErrorTable = ErrorTable::new(
+    "SyntheticAssetForge",
+    &[
+        InsufficientFunds, // InsufficientBalance
+        NotFound,          // AssetNotSupported
+        InvalidInput,      // InvalidAmount
+        InvalidInput,      // ZeroAmount
+        CallFailed,        // OracleQueryFailed
+        InvalidState,      // RebalancingThresholdNotMet
+        Unauthorized,      // Unauthorized
+        InvalidState,      // BasketValueMismatch
+        Arithmetic,        // Overflow
+        Arithmetic,        // Underflow
+        CallFailed,        // SwapFailed
+        LimitExceeded,     // SlippageExceeded
+        Expired,           // DeadlineExpired
+        CallFailed,        // CrossContractCallFailed
+        Expired,           // StalePrice
+        AlreadyExists,     // OracleSourceExists
+        Paused,            // Paused
+        LimitExceeded,     // PriceDeviationExceeded
+        InvalidInput,      // InvalidWeights
+        AlreadyExists,     // AssetAlreadySupported
+        NotFound,          // RedemptionNotFound
+        InvalidState,      // AlreadyMigrated
+        InvalidInput,      // LpTokenNotAccepted
+        InvalidState,      // StakeOutstanding
+        InvalidState,      // QuoteAssetNotSet
+        InvalidState,      // QuoteBalanceOutstanding
+        InvalidState,      // InsufficientObservations
+        InvalidState,      // SettlementTooSoon
+        InvalidState,      // RouterNotSet
+        CallFailed,        // PSP22
+        Other,             // Custom
+        CallFailed,        // EnvError
+        InvalidState,      // ReentrantCall
+    ],
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Encode)]
+    enum Error {
+        _First,
+        Second(u32),
+    }
+
+    #[test]
+    fn value_round_trips() {
+        let code = ErrorCode { kind: ErrorKind::CallFailed, number: 30 };
+        assert_eq!(code.value(), 0x0008_001e);
+        assert_eq!(ErrorCode::from_value(code.value()), Some(code));
+        assert_eq!(ErrorCode::from_value(0x00ff_0001), None);
+    }
+
+    /// The variant index is read from the encoding, whatever payload follows it.
+    #[test]
+    fn codes_follow_declaration_order() {
+        let table = ErrorTable::new("Test", &[ErrorKind::NotFound, ErrorKind::CallFailed]);
+        let code = ErrorCode { kind: ErrorKind::CallFailed, number: 2 };
+        assert_eq!(table.code_of(&Error::Second(7)), code);
+        assert_eq!(table.decode(&Error::Second(7).encode()), Some(code));
+        assert_eq!(table.decode(&[2]), None);
+        assert_eq!(table.decode(&[]), None);
+    }
+
+    #[test]
+    fn kinds_are_numbered_in_order() {
+        for (value, kind) in ErrorKind::ALL.iter().enumerate() {
+            assert_eq!(*kind as usize, value);
+        }
+        assert_eq!(ErrorKind::from_u16(ErrorKind::ALL.len() as u16), None);
+    }
+}
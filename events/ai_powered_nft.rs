@@ -0,0 +1,50 @@
+//! Events of AIPoweredNFT, the PSP34 collection whose metadata is updated by AI oracles.
+
+use ink::primitives::AccountId;
+pub use token_interfaces::Id;
+
+pub type Balance = u128;
+pub type Timestamp = u64;
+
+/// Metadata of a token, as carried by [`MetadataUpdated`].
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub struct NftMetadata {
+    pub name: String,
+    pub description: String,
+    pub image_uri: String,
+    pub traits: Vec<String>,
+}
+
+crate::contract_events! {
+    contract "AiPoweredNft";
+
+    /// A mint (`from` is `None`), a burn (`to` is `None`) or a transfer.
+    Transfer { #[topic] from: Option<AccountId>, #[topic] to: Option<AccountId>, #[topic] token_id: u32 }
+    /// An approval of one token, or of all of `owner`'s tokens if `id` is `None`.
+    Approval { #[topic] owner: AccountId, #[topic] operator: AccountId, #[topic] id: Option<Id>, approved: bool }
+    Listed { #[topic] token_id: u32, #[topic] seller: AccountId, price: Balance }
+    ListingCancelled { #[topic] token_id: u32 }
+    Sold {
+        #[topic] token_id: u32,
+        #[topic] seller: AccountId,
+        #[topic] buyer: AccountId,
+        price: Balance,
+        royalty: Balance,
+    }
+    Reforged { #[topic] token_id: u32, #[topic] owner: AccountId, burned: Vec<u32> }
+    Staked { #[topic] token_id: u32, #[topic] owner: AccountId }
+    Unstaked { #[topic] token_id: u32, #[topic] owner: AccountId, evolution_points: u64 }
+    EvolutionPointsSpent { #[topic] token_id: u32, points: u64, remaining: u64 }
+    AiUpdateRequested {
+        #[topic] request_id: u64,
+        #[topic] token_id: u32,
+        data_source: String,
+        request_time: Timestamp,
+        fee: Balance,
+    }
+    AiUpdateFulfilled { #[topic] request_id: u64, #[topic] updater: AccountId, oracle_reward: Balance, treasury_share: Balance }
+    AiUpdateRefunded { #[topic] request_id: u64, #[topic] requester: AccountId, amount: Balance }
+    MetadataUpdated { #[topic] token_id: u32, metadata: NftMetadata }
+    TraitVariantUpdated { trait_value: String, max_supply: u32, retired: bool }
+    RarityUpdated { #[topic] token_id: u32, rarity_score: u64 }
+}
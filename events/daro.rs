@@ -0,0 +1,40 @@
+//! Events of DARO, the decentralized autonomous research organization. None of its fields are topics.
+
+use ink::primitives::{AccountId, Hash};
+
+pub type ProposalId = u64;
+pub type BlockNumber = u32;
+pub type Balance = u128;
+pub type Timestamp = u64;
+
+crate::contract_events! {
+    contract "Daro";
+
+    ProposalSubmitted { proposal_id: ProposalId, proposer: AccountId }
+    ContributionMade { contributor: AccountId, proposal_id: ProposalId, amount: Balance }
+    /// A contribution paid by `payer` and credited to `beneficiary`.
+    ContributionMadeFor { payer: AccountId, beneficiary: AccountId, proposal_id: ProposalId, amount: Balance }
+    PredictionMade { predictor: AccountId, proposal_id: ProposalId, amount: Balance, resolve_by: Timestamp }
+    BreakthroughResolved { proposal_id: ProposalId, breakthrough: bool }
+    FundingWithdrawn { proposal_id: ProposalId, amount: Balance }
+    GovernanceChanged { old_governance: AccountId, new_governance: AccountId }
+    ImpactVerifierAdded { verifier: AccountId }
+    ImpactVerifierRemoved { verifier: AccountId }
+    ImpactThresholdChanged { threshold: u32 }
+    ImpactScoreSubmitted { proposal_id: ProposalId, verifier: AccountId, impact_score: u64 }
+    /// The median score of the committee, set once `impact_threshold` members have reported.
+    ImpactReported { proposal_id: ProposalId, impact_score: u64 }
+    CodeUpgraded { code_hash: Hash }
+    StorageMigrated { from_version: u16, to_version: u16 }
+    VestingScheduleSet {
+        proposal_id: ProposalId,
+        amount: Balance,
+        start: BlockNumber,
+        cliff: BlockNumber,
+        duration: BlockNumber,
+    }
+    VestedFundingClaimed { proposal_id: ProposalId, amount: Balance }
+    ProtocolFeeChanged { fee_bps: u16 }
+    TreasuryWithdrawn { to: AccountId, amount: Balance }
+    AttestationAdded { proposal_id: ProposalId, attester: AccountId, content_hash: Hash, index: u32 }
+}
@@ -0,0 +1,200 @@
+//! Typed decoding of the events emitted by the ink! contracts in this repository, for indexers written in Rust.
+//!
+//! ink! 4 requires a contract's events to be declared inside its `#[ink::contract]` module, so they cannot be
+//! imported from a shared crate. Their schemas are declared once here instead, one module per contract:
+//!
+//! - [`daro`] for DARO,
+//! - [`ai_powered_nft`] for AIPoweredNFT,
+//! - [`marketplace`] for DecentralizedAiMarketplace.
+//!
+//! Each module has a struct per event and an `Event` enum of all of them in declaration order, which is how ink!
+//! encodes the data of an emitted event: the event's index, then its fields. [`decode`] turns the data and topics of
+//! an emitted event into that enum, and [`ContractEvent::signature_topics`] gives the first topic of every event, to
+//! subscribe to a contract's events by topic.
+//!
+//! The contracts do not depend on this crate, so nothing here is derived from their types. Instead, a unit test of
+//! each contract decodes every event it records with this crate, topics included, which fails if a schema drifts
+//! from the events the test emits. DARO's test emits all of its events but `CodeUpgraded`; the tests of AIPoweredNFT
+//! and DecentralizedAiMarketplace emit only a few, so their other schemas are not checked against the contracts.
+use ink::env::hash::{Blake2x256, CryptoHash};
+use scale::{Decode, DecodeAll, Encode};
+
+pub mod ai_powered_nft;
+pub mod daro;
+pub mod marketplace;
+
+/// A topic of an emitted event.
+pub type Topic = [u8; 32];
+
+/// Why the data or topics of an emitted event do not decode.
+#[derive(Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The data is not the encoding of one of the contract's events.
+    Data(scale::Error),
+    /// The data decodes, but the topics are not the ones the contract emits with it.
+    TopicMismatch,
+}
+
+/// The events of one contract.
+pub trait ContractEvent: Decode {
+    /// Name of the contract's `#[ink(storage)]` struct, which prefixes every topic.
+    const CONTRACT: &'static str;
+    /// Names of the contract's events, in declaration order.
+    const EVENTS: &'static [&'static str];
+
+    /// Name of the event.
+    fn name(&self) -> &'static str;
+
+    /// Topics the contract emits the event with: its signature topic, then one per `#[ink(topic)]` field.
+    fn topics(&self) -> Vec<Topic>;
+
+    /// Signature topic of every event of the contract, by name.
+    fn signature_topics() -> Vec<(&'static str, Topic)> {
+        Self::EVENTS.iter().map(|event| (*event, signature_topic(Self::CONTRACT, event))).collect()
+    }
+}
+
+/// Decodes an event emitted by the contract of `E` from its data and topics. Fails if the data has bytes left over or
+/// the topics do not match the decoded event, e.g. because the event was emitted by another contract.
+pub fn decode<E: ContractEvent>(topics: &[Topic], data: &[u8]) -> Result<E, DecodeError> {
+    let event = E::decode_all(&mut &data[..]).map_err(DecodeError::Data)?;
+    if event.topics() != topics {
+        return Err(DecodeError::TopicMismatch);
+    }
+    Ok(event)
+}
+
+/// Topic of an encoded value: the value itself, zero-padded, if it fits, or else its BLAKE2b-256 hash.
+pub fn topic_of(encoded: &[u8]) -> Topic {
+    let mut topic = Topic::default();
+    if encoded.len() <= topic.len() {
+        topic[..encoded.len()].copy_from_slice(encoded);
+    } else {
+        Blake2x256::hash(encoded, &mut topic);
+    }
+    topic
+}
+
+/// First topic of every `event` of `contract`, from the path `Contract::Event`.
+pub fn signature_topic(contract: &str, event: &str) -> Topic {
+    prefixed_topic(b"", format!("{contract}::{event}").as_bytes())
+}
+
+/// Topic of an `#[ink(topic)]` field holding `value`, prefixed by the path `Contract::Event::field`.
+pub fn field_topic<T: Encode>(contract: &str, event: &str, field: &str, value: &T) -> Topic {
+    prefixed_topic(format!("{contract}::{event}::{field}").as_bytes(), &value.encode())
+}
+
+/// ink!'s `PrefixedValue` encoding: the prefix as a byte vector, then the value.
+fn prefixed_topic(prefix: &[u8], encoded_value: &[u8]) -> Topic {
+    let mut encoded = prefix.encode();
+    encoded.extend_from_slice(encoded_value);
+    topic_of(&encoded)
+}
+
+/// Declares the event structs of a contract, its `Event` enum and their [`ContractEvent`] implementation. Fields
+/// marked `#[topic]` are the contract's `#[ink(topic)]` fields.
+macro_rules! contract_events {
+    (
+        contract $contract:literal;
+        $(
+            $(#[$meta:meta])*
+            $event:ident { $( $(#[$topic:ident])? $field:ident: $ty:ty ),* $(,)? }
+        )*
+    ) => {
+        $(
+            $(#[$meta])*
+            #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+            pub struct $event {
+                $( pub $field: $ty, )*
+            }
+        )*
+
+        /// Every event of the contract, in declaration order.
+        #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+        pub enum Event {
+            $( $event($event), )*
+        }
+
+        impl $crate::ContractEvent for Event {
+            const CONTRACT: &'static str = $contract;
+            const EVENTS: &'static [&'static str] = &[$( stringify!($event) ),*];
+
+            fn name(&self) -> &'static str {
+                match self {
+                    $( Event::$event(_) => stringify!($event), )*
+                }
+            }
+
+            #[allow(unused_variables)]
+            fn topics(&self) -> Vec<$crate::Topic> {
+                match self {
+                    $(
+                        Event::$event(event) => vec![
+                            $crate::signature_topic($contract, stringify!($event)),
+                            $( $( $crate::topic_field!($topic, $contract, $event, $field, &event.$field), )? )*
+                        ],
+                    )*
+                }
+            }
+        }
+    };
+}
+
+/// Topic of a field marked `#[topic]`; any other attribute is rejected.
+macro_rules! topic_field {
+    (topic, $contract:literal, $event:ident, $field:ident, $value:expr) => {
+        $crate::field_topic($contract, stringify!($event), stringify!($field), $value)
+    };
+}
+
+pub(crate) use {contract_events, topic_field};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ink::primitives::AccountId;
+
+    /// Topics follow ink!'s `PrefixedValue` encoding, hashed only when longer than a topic.
+    #[test]
+    fn topics_are_prefixed_values() {
+        let mut expected = vec![0u8];
+        expected.extend_from_slice(b"Daro::CodeUpgraded");
+        assert_eq!(signature_topic("Daro", "CodeUpgraded"), topic_of(&expected));
+        assert_eq!(&topic_of(&expected)[..expected.len()], &expected[..]);
+
+        let mut expected = b"AiPoweredNft::Staked::token_id".to_vec().encode();
+        expected.extend_from_slice(&7u32.encode());
+        let mut hash = Topic::default();
+        Blake2x256::hash(&expected, &mut hash);
+        assert_eq!(field_topic("AiPoweredNft", "Staked", "token_id", &7u32), hash);
+    }
+
+    /// Decoding checks the topics as well as the data.
+    #[test]
+    fn decode_checks_topics() {
+        let owner = AccountId::from([0x01; 32]);
+        let event = ai_powered_nft::Event::Staked(ai_powered_nft::Staked { token_id: 7, owner });
+        let data = event.encode();
+        assert_eq!(decode(&event.topics(), &data), Ok(event.clone()));
+        assert_eq!(event.topics().len(), 3);
+
+        let mut wrong = event.topics();
+        wrong[2] = field_topic("AiPoweredNft", "Staked", "owner", &AccountId::from([0x02; 32]));
+        assert_eq!(decode::<ai_powered_nft::Event>(&wrong, &data), Err(DecodeError::TopicMismatch));
+
+        let mut trailing = data.clone();
+        trailing.push(0);
+        assert!(matches!(decode::<ai_powered_nft::Event>(&event.topics(), &trailing), Err(DecodeError::Data(_))));
+        assert!(matches!(decode::<daro::Event>(&event.topics(), &data), Err(DecodeError::TopicMismatch | DecodeError::Data(_))));
+    }
+
+    #[test]
+    fn signature_topics_cover_every_event() {
+        assert_eq!(daro::Event::signature_topics().len(), 19);
+        assert_eq!(ai_powered_nft::Event::signature_topics().len(), 15);
+        assert_eq!(marketplace::Event::signature_topics().len(), 26);
+        let (name, topic) = marketplace::Event::signature_topics()[5];
+        assert_eq!((name, topic), ("ModelPurchased", signature_topic("DecentralizedAiMarketplace", "ModelPurchased")));
+    }
+}
@@ -0,0 +1,77 @@
+//! Events of DecentralizedAiMarketplace, the marketplace for AI models, their licenses and evaluations.
+
+use ink::primitives::{AccountId, Hash};
+
+pub type ModelId = u32;
+pub type EvaluationRound = u32;
+pub type CaseId = u32;
+pub type LicenseId = u32;
+pub type PurchaseId = u32;
+pub type ChallengeId = u32;
+pub type Balance = u128;
+pub type Timestamp = u64;
+/// Differential-privacy epsilon in thousandths (1_000 = 1.0).
+pub type Epsilon = u64;
+
+/// Grounds on which a buyer disputes a purchase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum DisputeReason {
+    HashMismatch,
+    ModelInaccessible,
+    Other,
+}
+
+/// Grounds on which a license holder challenges a model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub enum ChallengeReason {
+    HashMismatch,
+    SchemaMismatch,
+}
+
+crate::contract_events! {
+    contract "DecentralizedAiMarketplace";
+
+    ModelRegistered { #[topic] model_id: ModelId, owner: AccountId, model_hash: String }
+    ModelUpdated { #[topic] model_id: ModelId, version: u32, model_hash: String, price: Balance }
+    LicenseUpgraded { #[topic] license_id: LicenseId, from_version: u32, to_version: u32, paid: Balance }
+    Subscribed {
+        #[topic] model_id: ModelId,
+        #[topic] subscriber: AccountId,
+        periods: u32,
+        paid: Balance,
+        expires_at: Timestamp,
+    }
+    ModelTagsUpdated { #[topic] model_id: ModelId, categories: Vec<String>, search_keys: Vec<Hash> }
+    ModelPurchased { #[topic] model_id: ModelId, buyer: AccountId, purchase_id: PurchaseId }
+    PaymentReleased { #[topic] purchase_id: PurchaseId, seller: AccountId, amount: Balance }
+    RevenueClaimed { #[topic] account: AccountId, amount: Balance }
+    DisputeOpened { #[topic] purchase_id: PurchaseId, reason: DisputeReason, evidence_uri: String }
+    DisputeResolved { #[topic] purchase_id: PurchaseId, refunded: bool }
+    ChallengeOpened {
+        #[topic] challenge_id: ChallengeId,
+        #[topic] model_id: ModelId,
+        challenger: AccountId,
+        reason: ChallengeReason,
+        respond_by: Timestamp,
+    }
+    ChallengeResponded { #[topic] challenge_id: ChallengeId, proof_uri: String }
+    ChallengeResolved { #[topic] challenge_id: ChallengeId, upheld: bool, delisted: bool }
+    LicenseTransferred { #[topic] license_id: LicenseId, model_id: ModelId, from: AccountId, to: AccountId }
+    LicenseListed { #[topic] license_id: LicenseId, price: Balance }
+    LicenseSold { #[topic] license_id: LicenseId, seller: AccountId, buyer: AccountId, price: Balance, royalty: Balance }
+    EvaluationStarted { #[topic] model_id: ModelId, round: EvaluationRound, deadline: Timestamp }
+    PrivacyBudgetRegistered { dataset_hash: String, #[topic] owner: AccountId, total_epsilon: Epsilon }
+    DatasetAccessChanged { dataset_hash: String, #[topic] account: AccountId, allowed: bool }
+    PrivacyBudgetSpent { dataset_hash: String, #[topic] model_id: ModelId, epsilon: Epsilon, remaining: Epsilon }
+    PrivacyBudgetExhausted { dataset_hash: String }
+    EvaluationFinalized { #[topic] model_id: ModelId, round: EvaluationRound, consensus: u8, bonus_paid: Balance }
+    EvaluationVoided { #[topic] model_id: ModelId, round: EvaluationRound, results: u32 }
+    EvaluatorSlashed { #[topic] evaluator: AccountId, model_id: ModelId, round: EvaluationRound, amount: Balance }
+    RevocationCaseOpened {
+        #[topic] case_id: CaseId,
+        #[topic] licensee: AccountId,
+        model_id: ModelId,
+        evidence_uri: String,
+    }
+    RevocationCaseResolved { #[topic] case_id: CaseId, revoked: bool, refund: Balance }
+}
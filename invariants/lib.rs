@@ -0,0 +1,154 @@
+//! Security invariants shared by the off-chain tests of the ink! contracts in this repository.
+//!
+//! A contract's tests implement [`Target`] for it, describing its messages as [`Target::Action`]s, and call
+//! [`assert_invariants`]. The harness generates call sequences between the first [`ACCOUNTS`] default accounts, makes
+//! each call in a fresh off-chain environment and checks after every call that:
+//!
+//! - no account's balance grows unless the target authorizes the call to credit that account,
+//! - the sum of the balances grows by no more than the target allows the call to create,
+//! - while the target reports itself paused, a call that is not exempt from the pause leaves its state unchanged,
+//! - the call does not panic, so none of its arithmetic overflows.
+//!
+//! A failing sequence is shrunk to a minimal one before it is reported.
+
+use std::fmt::Debug;
+use std::panic::{self, AssertUnwindSafe};
+
+use ink::env::{test, DefaultEnvironment};
+use ink::primitives::AccountId;
+use proptest::prelude::*;
+use proptest::test_runner::{Config, TestCaseError, TestError, TestRunner};
+
+/// Number of accounts that make and receive calls: alice, bob, charlie and django.
+pub const ACCOUNTS: usize = 4;
+
+/// Most calls in a generated sequence.
+pub const MAX_CALLS: usize = 32;
+
+/// Account the contract under test is deployed at.
+pub const CONTRACT: [u8; 32] = [0xc0; 32];
+
+/// A contract driven by the harness, seen through the balances it keeps for accounts.
+pub trait Target: Sized {
+    /// A call to one of the contract's messages, with its arguments.
+    type Action: Clone + Debug;
+
+    /// The state the pause invariant compares before and after a call.
+    type Snapshot: PartialEq + Debug;
+
+    /// Deploys the contract. The caller is the first account.
+    fn deploy() -> Self;
+
+    /// Generates calls whose arguments name `accounts`.
+    fn action(accounts: [AccountId; ACCOUNTS]) -> BoxedStrategy<Self::Action>;
+
+    /// Makes the call. The harness has set the caller; a call the contract rejects is not a failure.
+    fn apply(&mut self, action: &Self::Action);
+
+    /// The balance the contract keeps for `account`.
+    fn balance_of(&self, account: AccountId) -> u128;
+
+    /// Whether `action`, called by `caller`, may raise the balance of `account`.
+    fn may_credit(&self, caller: AccountId, action: &Self::Action, account: AccountId) -> bool;
+
+    /// The most `action`, called by `caller`, may add to the sum of the balances.
+    fn max_minted(&self, _caller: AccountId, _action: &Self::Action) -> u128 {
+        0
+    }
+
+    /// Whether the contract is paused.
+    fn is_paused(&self) -> bool {
+        false
+    }
+
+    /// Whether `action` may change the state while the contract is paused, e.g. because it unpauses it.
+    fn is_pause_exempt(_action: &Self::Action) -> bool {
+        false
+    }
+
+    /// The state a paused contract must not change.
+    fn snapshot(&self) -> Self::Snapshot;
+}
+
+/// A generated call: the index of the calling account and the action.
+pub type Call<T> = (usize, <T as Target>::Action);
+
+/// Runs the invariants against `T` and returns the minimal failing call sequence, if any.
+pub fn check<T: Target>(config: Config) -> Result<(), TestError<Vec<Call<T>>>> {
+    let accounts = accounts();
+    let calls = prop::collection::vec((0..ACCOUNTS, T::action(accounts)), 1..=MAX_CALLS);
+    TestRunner::new(config).run(&calls, |calls| run_sequence::<T>(accounts, &calls))
+}
+
+/// Runs the invariants against `T` with proptest's default configuration, panicking on a violation.
+pub fn assert_invariants<T: Target>() {
+    if let Err(error) = check::<T>(Config::default()) {
+        panic!("{error}");
+    }
+}
+
+/// The accounts calls are made from and to.
+pub fn accounts() -> [AccountId; ACCOUNTS] {
+    let accounts = test::default_accounts::<DefaultEnvironment>();
+    [accounts.alice, accounts.bob, accounts.charlie, accounts.django]
+}
+
+fn run_sequence<T: Target>(accounts: [AccountId; ACCOUNTS], calls: &[Call<T>]) -> Result<(), TestCaseError> {
+    let mut result = Ok(());
+    test::run_test::<DefaultEnvironment, _>(|_| {
+        result = run_calls::<T>(accounts, calls);
+        Ok(())
+    })
+    .expect("the off-chain environment does not fail");
+    result
+}
+
+fn run_calls<T: Target>(accounts: [AccountId; ACCOUNTS], calls: &[Call<T>]) -> Result<(), TestCaseError> {
+    test::set_callee::<DefaultEnvironment>(AccountId::from(CONTRACT));
+    test::set_caller::<DefaultEnvironment>(accounts[0]);
+    let mut target = T::deploy();
+
+    for (index, (caller, action)) in calls.iter().enumerate() {
+        let caller = accounts[*caller];
+        let before = accounts.map(|account| target.balance_of(account));
+        let may_credit = accounts.map(|account| target.may_credit(caller, action, account));
+        let max_minted = target.max_minted(caller, action);
+        let paused = target.is_paused() && !T::is_pause_exempt(action);
+        let snapshot = paused.then(|| target.snapshot());
+
+        test::set_caller::<DefaultEnvironment>(caller);
+        panic::catch_unwind(AssertUnwindSafe(|| target.apply(action)))
+            .map_err(|_| TestCaseError::fail(format!("call {index} ({action:?} from {caller:?}) panicked")))?;
+
+        let after = accounts.map(|account| target.balance_of(account));
+        let (mut credited, mut debited) = (0u128, 0u128);
+        for (i, account) in accounts.iter().enumerate() {
+            if after[i] > before[i] {
+                if !may_credit[i] {
+                    return Err(TestCaseError::fail(format!(
+                        "call {index} ({action:?} from {caller:?}) credited {account:?} without authorization"
+                    )));
+                }
+                credited = credited.saturating_add(after[i] - before[i]);
+            } else {
+                debited = debited.saturating_add(before[i] - after[i]);
+            }
+        }
+        // A saturated `credited` only understates what was created, so this never fails a valid call.
+        if credited.saturating_sub(debited) > max_minted {
+            return Err(TestCaseError::fail(format!(
+                "call {index} ({action:?} from {caller:?}) created {} more than the {max_minted} it may",
+                credited.saturating_sub(debited) - max_minted
+            )));
+        }
+
+        if let Some(snapshot) = snapshot {
+            if target.snapshot() != snapshot {
+                return Err(TestCaseError::fail(format!(
+                    "call {index} ({action:?} from {caller:?}) changed the state while paused"
+                )));
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,153 @@
+//! The invariants run against the mock PSP22 token. Its `set_failing` switch stands in for a pause.
+//!
+//! Each mock gets its own test binary: every ink! contract built for `std` defines the same metadata symbol.
+
+use ink::primitives::AccountId;
+use invariants::{accounts, assert_invariants, check, Target, ACCOUNTS};
+use mock_psp22::{MockPsp22, PSP22, PSP22Burnable, PSP22Mintable};
+use proptest::prelude::*;
+use proptest::test_runner::Config;
+
+/// Small amounts collide with balances; any amount reaches the overflow edges.
+fn amount() -> impl Strategy<Value = u128> {
+    prop_oneof![0..1_000u128, any::<u128>()]
+}
+
+fn account(accounts: [AccountId; ACCOUNTS]) -> impl Strategy<Value = AccountId> {
+    prop::sample::select(accounts.to_vec())
+}
+
+#[derive(Clone, Debug)]
+enum Psp22Action {
+    Transfer { to: AccountId, value: u128 },
+    TransferFrom { from: AccountId, to: AccountId, value: u128 },
+    Approve { spender: AccountId, value: u128 },
+    Mint { account: AccountId, amount: u128 },
+    Burn { account: AccountId, amount: u128 },
+    SetFailing(bool),
+}
+
+struct Psp22 {
+    token: MockPsp22,
+    failing: bool,
+}
+
+impl Target for Psp22 {
+    type Action = Psp22Action;
+    type Snapshot = [u128; ACCOUNTS];
+
+    fn deploy() -> Self {
+        Self { token: MockPsp22::new(1_000), failing: false }
+    }
+
+    fn action(accounts: [AccountId; ACCOUNTS]) -> BoxedStrategy<Psp22Action> {
+        prop_oneof![
+            (account(accounts), amount()).prop_map(|(to, value)| Psp22Action::Transfer { to, value }),
+            (account(accounts), account(accounts), amount())
+                .prop_map(|(from, to, value)| Psp22Action::TransferFrom { from, to, value }),
+            (account(accounts), amount()).prop_map(|(spender, value)| Psp22Action::Approve { spender, value }),
+            (account(accounts), amount()).prop_map(|(account, amount)| Psp22Action::Mint { account, amount }),
+            (account(accounts), amount()).prop_map(|(account, amount)| Psp22Action::Burn { account, amount }),
+            any::<bool>().prop_map(Psp22Action::SetFailing),
+        ]
+        .boxed()
+    }
+
+    fn apply(&mut self, action: &Psp22Action) {
+        let _ = match *action {
+            Psp22Action::Transfer { to, value } => self.token.transfer(to, value, Vec::new()),
+            Psp22Action::TransferFrom { from, to, value } => self.token.transfer_from(from, to, value, Vec::new()),
+            Psp22Action::Approve { spender, value } => self.token.approve(spender, value),
+            Psp22Action::Mint { account, amount } => self.token.mint(account, amount),
+            Psp22Action::Burn { account, amount } => self.token.burn(account, amount),
+            Psp22Action::SetFailing(failing) => {
+                self.token.set_failing(failing);
+                self.failing = failing;
+                Ok(())
+            }
+        };
+    }
+
+    fn balance_of(&self, account: AccountId) -> u128 {
+        self.token.balance_of(account)
+    }
+
+    fn may_credit(&self, _caller: AccountId, action: &Psp22Action, account: AccountId) -> bool {
+        match *action {
+            Psp22Action::Transfer { to, .. } | Psp22Action::TransferFrom { to, .. } => account == to,
+            // The mock lets anyone mint.
+            Psp22Action::Mint { account: minted_to, .. } => account == minted_to,
+            _ => false,
+        }
+    }
+
+    fn max_minted(&self, _caller: AccountId, action: &Psp22Action) -> u128 {
+        match *action {
+            Psp22Action::Mint { amount, .. } => amount,
+            _ => 0,
+        }
+    }
+
+    fn is_paused(&self) -> bool {
+        self.failing
+    }
+
+    fn is_pause_exempt(action: &Psp22Action) -> bool {
+        matches!(action, Psp22Action::SetFailing(_))
+    }
+
+    fn snapshot(&self) -> [u128; ACCOUNTS] {
+        accounts().map(|account| self.token.balance_of(account))
+    }
+}
+
+#[test]
+fn mock_psp22_keeps_the_invariants() {
+    assert_invariants::<Psp22>();
+}
+
+/// A token whose mint is meant to be the deployer's alone, checked against the mock's open mint.
+struct OwnerMintedPsp22(Psp22);
+
+impl Target for OwnerMintedPsp22 {
+    type Action = Psp22Action;
+    type Snapshot = [u128; ACCOUNTS];
+
+    fn deploy() -> Self {
+        Self(Psp22::deploy())
+    }
+
+    fn action(accounts: [AccountId; ACCOUNTS]) -> BoxedStrategy<Psp22Action> {
+        Psp22::action(accounts)
+    }
+
+    fn apply(&mut self, action: &Psp22Action) {
+        self.0.apply(action)
+    }
+
+    fn balance_of(&self, account: AccountId) -> u128 {
+        self.0.balance_of(account)
+    }
+
+    fn may_credit(&self, caller: AccountId, action: &Psp22Action, account: AccountId) -> bool {
+        let deployer = accounts()[0];
+        match action {
+            Psp22Action::Mint { .. } if caller != deployer => false,
+            _ => self.0.may_credit(caller, action, account),
+        }
+    }
+
+    fn max_minted(&self, caller: AccountId, action: &Psp22Action) -> u128 {
+        self.0.max_minted(caller, action)
+    }
+
+    fn snapshot(&self) -> [u128; ACCOUNTS] {
+        self.0.snapshot()
+    }
+}
+
+#[test]
+fn unauthorized_mint_is_caught() {
+    let error = check::<OwnerMintedPsp22>(Config::default()).expect_err("bob can mint to himself");
+    assert!(error.to_string().contains("without authorization"), "{error}");
+}
@@ -0,0 +1,95 @@
+//! The invariants run against the mock PSP34 collection. Its `set_failing` switch stands in for a pause.
+
+use ink::primitives::AccountId;
+use invariants::{accounts, assert_invariants, Target, ACCOUNTS};
+use mock_psp34::{Id, MockPsp34, PSP34};
+use proptest::prelude::*;
+
+fn account(accounts: [AccountId; ACCOUNTS]) -> impl Strategy<Value = AccountId> {
+    prop::sample::select(accounts.to_vec())
+}
+
+#[derive(Clone, Debug)]
+enum Psp34Action {
+    Mint { to: AccountId, id: u8 },
+    Burn { id: u8 },
+    Approve { operator: AccountId, id: Option<u8>, approved: bool },
+    Transfer { to: AccountId, id: u8 },
+    SetFailing(bool),
+}
+
+struct Psp34 {
+    collection: MockPsp34,
+    failing: bool,
+}
+
+/// Few IDs, so mints collide and transfers find existing tokens.
+const PSP34_IDS: u8 = 8;
+
+impl Target for Psp34 {
+    type Action = Psp34Action;
+    type Snapshot = (Vec<Option<AccountId>>, [u128; ACCOUNTS]);
+
+    fn deploy() -> Self {
+        Self { collection: MockPsp34::new(), failing: false }
+    }
+
+    fn action(accounts: [AccountId; ACCOUNTS]) -> BoxedStrategy<Psp34Action> {
+        prop_oneof![
+            (account(accounts), 0..PSP34_IDS).prop_map(|(to, id)| Psp34Action::Mint { to, id }),
+            (0..PSP34_IDS).prop_map(|id| Psp34Action::Burn { id }),
+            (account(accounts), prop::option::of(0..PSP34_IDS), any::<bool>())
+                .prop_map(|(operator, id, approved)| Psp34Action::Approve { operator, id, approved }),
+            (account(accounts), 0..PSP34_IDS).prop_map(|(to, id)| Psp34Action::Transfer { to, id }),
+            any::<bool>().prop_map(Psp34Action::SetFailing),
+        ]
+        .boxed()
+    }
+
+    fn apply(&mut self, action: &Psp34Action) {
+        let _ = match *action {
+            Psp34Action::Mint { to, id } => self.collection.mint(to, Id::U8(id)),
+            Psp34Action::Burn { id } => self.collection.burn(Id::U8(id)),
+            Psp34Action::Approve { operator, id, approved } => self.collection.approve(operator, id.map(Id::U8), approved),
+            Psp34Action::Transfer { to, id } => self.collection.transfer(to, Id::U8(id), Vec::new()),
+            Psp34Action::SetFailing(failing) => {
+                self.collection.set_failing(failing);
+                self.failing = failing;
+                Ok(())
+            }
+        };
+    }
+
+    fn balance_of(&self, account: AccountId) -> u128 {
+        self.collection.balance_of(account).into()
+    }
+
+    fn may_credit(&self, _caller: AccountId, action: &Psp34Action, account: AccountId) -> bool {
+        match *action {
+            Psp34Action::Mint { to, .. } | Psp34Action::Transfer { to, .. } => account == to,
+            _ => false,
+        }
+    }
+
+    fn max_minted(&self, _caller: AccountId, action: &Psp34Action) -> u128 {
+        u128::from(matches!(action, Psp34Action::Mint { .. }))
+    }
+
+    fn is_paused(&self) -> bool {
+        self.failing
+    }
+
+    fn is_pause_exempt(action: &Psp34Action) -> bool {
+        matches!(action, Psp34Action::SetFailing(_))
+    }
+
+    fn snapshot(&self) -> Self::Snapshot {
+        let owners = (0..PSP34_IDS).map(|id| self.collection.owner_of(Id::U8(id))).collect();
+        (owners, accounts().map(|account| self.balance_of(account)))
+    }
+}
+
+#[test]
+fn mock_psp34_keeps_the_invariants() {
+    assert_invariants::<Psp34>();
+}
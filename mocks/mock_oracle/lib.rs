@@ -0,0 +1,65 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+pub use self::mock_oracle::{MockOracle, MockOracleRef, PriceOracle};
+
+/// Price oracle for the forge's end-to-end tests, with prices and their timestamps set by anyone. It can also be
+/// made to trap on every query, like an oracle that is down.
+#[ink::contract]
+mod mock_oracle {
+    use ink::storage::Mapping;
+
+    #[ink::trait_definition]
+    pub trait PriceOracle {
+        #[ink(message)]
+        fn latest_price(&self, asset: AccountId) -> Option<(u128, u64)>;
+    }
+
+    #[ink(storage)]
+    #[derive(Default)]
+    pub struct MockOracle {
+        prices: Mapping<AccountId, (u128, u64)>,
+        failing: bool,
+    }
+
+    impl MockOracle {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Sets the price of `asset`, timestamped with the current block.
+        #[ink(message)]
+        pub fn set_price(&mut self, asset: AccountId, price: u128) {
+            let now = self.env().block_timestamp();
+            self.prices.insert(asset, &(price, now));
+        }
+
+        /// Sets the price of `asset` as if it was updated at `updated_at`, e.g. to serve a stale reading.
+        #[ink(message)]
+        pub fn set_price_at(&mut self, asset: AccountId, price: u128, updated_at: u64) {
+            self.prices.insert(asset, &(price, updated_at));
+        }
+
+        /// Removes the price of `asset`, so queries for it return `None`.
+        #[ink(message)]
+        pub fn clear_price(&mut self, asset: AccountId) {
+            self.prices.remove(asset);
+        }
+
+        /// Makes every `latest_price` query trap.
+        #[ink(message)]
+        pub fn set_failing(&mut self, failing: bool) {
+            self.failing = failing;
+        }
+    }
+
+    impl PriceOracle for MockOracle {
+        #[ink(message)]
+        fn latest_price(&self, asset: AccountId) -> Option<(u128, u64)> {
+            if self.failing {
+                panic!("forced failure");
+            }
+            self.prices.get(asset)
+        }
+    }
+}
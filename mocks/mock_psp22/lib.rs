@@ -0,0 +1,142 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+pub use self::mock_psp22::{MockPsp22, MockPsp22Ref};
+pub use token_interfaces::{PSP22, PSP22Burnable, PSP22Error, PSP22Mintable};
+
+/// Minimal PSP22 token for the forge's end-to-end tests. Anyone can mint and burn, and anyone can make every
+/// transfer, approval, mint and burn fail to exercise the forge's error paths.
+#[ink::contract]
+mod mock_psp22 {
+    use ink::prelude::{string::String, vec::Vec};
+    use ink::storage::Mapping;
+    use token_interfaces::{PSP22, PSP22Burnable, PSP22Error, PSP22Mintable};
+
+    #[ink(storage)]
+    pub struct MockPsp22 {
+        balances: Mapping<AccountId, u128>,
+        allowances: Mapping<(AccountId, AccountId), u128>,
+        supply: u128,
+        decimals: u8,
+        failing: bool,
+    }
+
+    impl MockPsp22 {
+        /// Mints `initial_supply` to the deployer, with 18 decimals.
+        #[ink(constructor)]
+        pub fn new(initial_supply: u128) -> Self {
+            Self::new_with_decimals(initial_supply, 18)
+        }
+
+        /// Mints `initial_supply` to the deployer, with `decimals` decimals.
+        #[ink(constructor)]
+        pub fn new_with_decimals(initial_supply: u128, decimals: u8) -> Self {
+            let mut balances = Mapping::default();
+            balances.insert(Self::env().caller(), &initial_supply);
+            Self { balances, allowances: Mapping::default(), supply: initial_supply, decimals, failing: false }
+        }
+
+        #[ink(message)]
+        pub fn token_decimals(&self) -> u8 {
+            self.decimals
+        }
+
+        /// Makes every transfer, approval, mint and burn return `PSP22Error::Custom("forced failure")`.
+        #[ink(message)]
+        pub fn set_failing(&mut self, failing: bool) {
+            self.failing = failing;
+        }
+
+        fn ensure_not_failing(&self) -> Result<(), PSP22Error> {
+            if self.failing {
+                return Err(PSP22Error::Custom(String::from("forced failure")));
+            }
+            Ok(())
+        }
+
+        fn move_balance(&mut self, from: AccountId, to: AccountId, value: u128) -> Result<(), PSP22Error> {
+            let from_balance = self.balance_of(from);
+            if from_balance < value {
+                return Err(PSP22Error::InsufficientBalance);
+            }
+            if from == to {
+                return Ok(());
+            }
+            let to_balance = self.balance_of(to).checked_add(value).ok_or_else(Self::overflow)?;
+            self.balances.insert(from, &(from_balance - value));
+            self.balances.insert(to, &to_balance);
+            Ok(())
+        }
+
+        /// Anyone can mint, so balances can reach `u128::MAX`; a credit past it fails instead of trapping.
+        fn overflow() -> PSP22Error {
+            PSP22Error::Custom(String::from("overflow"))
+        }
+    }
+
+    impl PSP22 for MockPsp22 {
+        #[ink(message)]
+        fn total_supply(&self) -> u128 {
+            self.supply
+        }
+
+        #[ink(message)]
+        fn balance_of(&self, owner: AccountId) -> u128 {
+            self.balances.get(owner).unwrap_or(0)
+        }
+
+        #[ink(message)]
+        fn allowance(&self, owner: AccountId, spender: AccountId) -> u128 {
+            self.allowances.get((owner, spender)).unwrap_or(0)
+        }
+
+        #[ink(message)]
+        fn transfer(&mut self, to: AccountId, value: u128, _data: Vec<u8>) -> Result<(), PSP22Error> {
+            self.ensure_not_failing()?;
+            self.move_balance(self.env().caller(), to, value)
+        }
+
+        #[ink(message)]
+        fn transfer_from(&mut self, from: AccountId, to: AccountId, value: u128, _data: Vec<u8>) -> Result<(), PSP22Error> {
+            self.ensure_not_failing()?;
+            let spender = self.env().caller();
+            let allowance = self.allowance(from, spender);
+            if allowance < value {
+                return Err(PSP22Error::InsufficientAllowance);
+            }
+            self.allowances.insert((from, spender), &(allowance - value));
+            self.move_balance(from, to, value)
+        }
+
+        #[ink(message)]
+        fn approve(&mut self, spender: AccountId, value: u128) -> Result<(), PSP22Error> {
+            self.ensure_not_failing()?;
+            self.allowances.insert((self.env().caller(), spender), &value);
+            Ok(())
+        }
+    }
+
+    impl PSP22Mintable for MockPsp22 {
+        #[ink(message)]
+        fn mint(&mut self, account: AccountId, amount: u128) -> Result<(), PSP22Error> {
+            self.ensure_not_failing()?;
+            let balance = self.balance_of(account).checked_add(amount).ok_or_else(Self::overflow)?;
+            self.supply = self.supply.checked_add(amount).ok_or_else(Self::overflow)?;
+            self.balances.insert(account, &balance);
+            Ok(())
+        }
+    }
+
+    impl PSP22Burnable for MockPsp22 {
+        #[ink(message)]
+        fn burn(&mut self, account: AccountId, amount: u128) -> Result<(), PSP22Error> {
+            self.ensure_not_failing()?;
+            let balance = self.balance_of(account);
+            if balance < amount {
+                return Err(PSP22Error::InsufficientBalance);
+            }
+            self.balances.insert(account, &(balance - amount));
+            self.supply -= amount;
+            Ok(())
+        }
+    }
+}
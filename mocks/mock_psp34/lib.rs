@@ -0,0 +1,142 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+pub use self::mock_psp34::{MockPsp34, MockPsp34Ref};
+pub use token_interfaces::{Id, PSP34, PSP34Error};
+
+/// Minimal PSP34 collection for end-to-end tests. Anyone can mint and burn, and anyone can make every
+/// approval, transfer, mint and burn fail to exercise the caller's error paths. Transfers skip the receiver
+/// check, so tokens can be sent to any contract.
+#[ink::contract]
+mod mock_psp34 {
+    use ink::prelude::{string::String, vec::Vec};
+    use ink::storage::Mapping;
+    use token_interfaces::{Id, PSP34, PSP34Error};
+
+    #[ink(storage)]
+    #[derive(Default)]
+    pub struct MockPsp34 {
+        owners: Mapping<Id, AccountId>,
+        balances: Mapping<AccountId, u32>,
+        token_approvals: Mapping<Id, AccountId>, // Token => operator approved by its current owner
+        operator_approvals: Mapping<(AccountId, AccountId), ()>,
+        supply: u128,
+        failing: bool,
+    }
+
+    impl MockPsp34 {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Mints `id` to `to`.
+        #[ink(message)]
+        pub fn mint(&mut self, to: AccountId, id: Id) -> Result<(), PSP34Error> {
+            self.ensure_not_failing()?;
+            if self.owners.contains(&id) {
+                return Err(PSP34Error::TokenExists);
+            }
+            self.owners.insert(&id, &to);
+            self.balances.insert(to, &(self.balance_of(to) + 1));
+            self.supply += 1;
+            Ok(())
+        }
+
+        /// Burns `id`, whoever owns it.
+        #[ink(message)]
+        pub fn burn(&mut self, id: Id) -> Result<(), PSP34Error> {
+            self.ensure_not_failing()?;
+            let owner = self.owners.get(&id).ok_or(PSP34Error::TokenNotExists)?;
+            self.owners.remove(&id);
+            self.balances.insert(owner, &(self.balance_of(owner) - 1));
+            self.supply -= 1;
+            Ok(())
+        }
+
+        /// Makes every approval, transfer, mint and burn return `PSP34Error::Custom("forced failure")`.
+        #[ink(message)]
+        pub fn set_failing(&mut self, failing: bool) {
+            self.failing = failing;
+        }
+
+        fn ensure_not_failing(&self) -> Result<(), PSP34Error> {
+            if self.failing {
+                return Err(PSP34Error::Custom(String::from("forced failure")));
+            }
+            Ok(())
+        }
+    }
+
+    impl PSP34 for MockPsp34 {
+        #[ink(message)]
+        fn collection_id(&self) -> Id {
+            let account_id = self.env().account_id();
+            Id::Bytes(AsRef::<[u8]>::as_ref(&account_id).to_vec())
+        }
+
+        #[ink(message)]
+        fn balance_of(&self, owner: AccountId) -> u32 {
+            self.balances.get(owner).unwrap_or(0)
+        }
+
+        #[ink(message)]
+        fn owner_of(&self, id: Id) -> Option<AccountId> {
+            self.owners.get(&id)
+        }
+
+        #[ink(message)]
+        fn allowance(&self, owner: AccountId, operator: AccountId, id: Option<Id>) -> bool {
+            self.operator_approvals.contains((owner, operator))
+                || id.is_some_and(|id| self.owners.get(&id) == Some(owner) && self.token_approvals.get(&id) == Some(operator))
+        }
+
+        #[ink(message)]
+        fn approve(&mut self, operator: AccountId, id: Option<Id>, approved: bool) -> Result<(), PSP34Error> {
+            self.ensure_not_failing()?;
+            let caller = self.env().caller();
+            if operator == caller {
+                return Err(PSP34Error::SelfApprove);
+            }
+            match id {
+                Some(id) => {
+                    if self.owners.get(&id) != Some(caller) {
+                        return Err(PSP34Error::NotApproved);
+                    }
+                    if approved {
+                        self.token_approvals.insert(&id, &operator);
+                    } else if self.token_approvals.get(&id) == Some(operator) {
+                        self.token_approvals.remove(&id);
+                    }
+                }
+                None => {
+                    if approved {
+                        self.operator_approvals.insert((caller, operator), &());
+                    } else {
+                        self.operator_approvals.remove((caller, operator));
+                    }
+                }
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn transfer(&mut self, to: AccountId, id: Id, _data: Vec<u8>) -> Result<(), PSP34Error> {
+            self.ensure_not_failing()?;
+            let caller = self.env().caller();
+            let owner = self.owners.get(&id).ok_or(PSP34Error::TokenNotExists)?;
+            if owner != caller && !self.allowance(owner, caller, Some(id.clone())) {
+                return Err(PSP34Error::NotApproved);
+            }
+            self.token_approvals.remove(&id);
+            self.owners.insert(&id, &to);
+            self.balances.insert(owner, &(self.balance_of(owner) - 1));
+            self.balances.insert(to, &(self.balance_of(to) + 1));
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn total_supply(&self) -> u128 {
+            self.supply
+        }
+    }
+}
@@ -1,6 +1,5 @@
 ```rust
-#![cfg_attr(not(feature = "std"), no_std)]
-#![no_main]
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
 
 //  AI-Powered Dynamic NFT Contract (Rust)
 
@@ -9,12 +8,43 @@
 //  The AI model's predictions or analyses are used to modify the NFT's metadata, traits, or even its scarcity.
 //  Functionality:
 //    - `mint`: Mints a new AI-Powered Dynamic NFT.  Requires upfront cost.
-//    - `request_ai_update`:  Requests an AI model update based on a specified data source. Triggers an event.
-//    - `update_nft_based_on_ai`:  (Callable only by the contract owner or a designated "AI Updater" role).
+//    - `burn`:  Destroys an NFT, releasing its trait supply.  (Token owner only).
+//    - `reforge`:  Burns several NFTs and mints a new one whose traits are derived deterministically from theirs.  (Token owner only).
+//    - `request_ai_update`:  Requests an AI model update based on a specified data source, escrowing the update fee. Triggers an event.
+//    - `fulfill_ai_update`:  (AI Updater only).  Applies the update for a pending request and releases its fee, split between
+//                                  the updater (oracle reward) and the protocol treasury.
+//    - `refund_ai_update`:  Refunds the fee of a request that was not fulfilled within the request timeout.  (Requester only).
+//    - `set_update_fee` / `set_request_timeout`:  Configures the update fee, its oracle reward share and the request timeout.  (Owner only).
+//    - `get_ai_update_request`:  Returns an update request and its status.
+//    - `update_nft_based_on_ai`:  (Callable only by a registered "AI Updater" whose scope covers the token).
 //                                  Updates the NFT metadata based on AI analysis results.
-//    - `set_ai_updater`:  Sets the address authorized to call `update_nft_based_on_ai`.  (Owner only).
+//    - `update_nft_with_oracle_signature`:  Updates the NFT metadata from a payload signed by a registered oracle key.
+//                                  Anyone may relay the payload; per-token nonces and the contract's own account in the
+//                                  signed message prevent replay.
+//    - `add_oracle_key` / `remove_oracle_key`:  Manages the ECDSA oracle keys trusted for signed updates.  (Owner only).
+//    - `get_token_nonce`:  Returns the nonce the next signed update for a token must carry.
+//    - `mint_trait_variant` / `retire_trait_variant`:  (Unscoped AI Updaters only).  Adds supply to a trait variant or retires it so
+//                                  no further tokens can take it on.
+//    - `get_trait_supply` / `get_trait_variant`:  Returns how many tokens carry a trait value and its supply cap.
+//    - `get_rarity_score`:  Returns a token's rarity score, computed from the current supply of each of its traits.
+//    - `add_updater` / `remove_updater`:  Registers or removes an AI Updater, optionally scoped to a set of token IDs.  (Owner only).
+//    - `get_updaters`:  Returns the registered AI Updaters and their token scopes.
+//    - `stake_nft` / `unstake_nft`:  Locks an NFT so it accrues evolution points over time, or unlocks it.  (Token owner only).
+//    - `apply_premium_upgrade`:  (AI Updater only).  Spends a token's evolution points to apply a premium metadata upgrade.
+//    - `get_stake` / `get_pending_points`:  Returns when a token was staked and its spendable evolution points.
 //    - `get_nft_metadata`:  Retrieves the current metadata of an NFT.
+//    - `tokens_of_owner` / `token_by_index`:  Enumerates existing tokens, per owner or globally, from on-chain indexes.  The global index runs up to PSP34 `total_supply`.
+//    - `get_metadata_history`:  Pages through the most recent metadata versions of an NFT, with timestamps and updaters.
+//    - `rollback_metadata`:  Restores a previous metadata version to recover from a bad AI update.  (Owner only).
 //    - `transfer_nft`: Transfers an NFT to a new owner.
+//    - `list_for_sale` / `cancel_listing`:  Lists an NFT for sale at a fixed price or withdraws the listing.  (Token owner only).
+//    - `buy`:  Buys a listed NFT, paying the creator royalty and the seller automatically.
+//    - `set_royalty`:  Sets the royalty, in basis points, paid to a token's creator on sales.  (Creator only).
+//    - `get_listing` / `get_royalty`:  Returns a token's listing price and its creator and royalty.
+//    - PSP34 (`collection_id`, `balance_of`, `owner_of`, `allowance`, `approve`, `transfer`, `total_supply`):
+//                                  Standard NFT interface with per-token and operator approvals and receiver checks
+//                                  for transfers to contracts.
+//    - PSP34Metadata (`get_attribute`):  Exposes the name, description, image URI and traits as attributes.
 //    - `get_owner`: Returns the owner of the contract.
 //    - `get_balance`: Returns the balance of the contract.
 //    - `withdraw`: Withdraws funds from the contract.
@@ -23,11 +53,11 @@
 //    - AI Oracle Interaction:  The contract relies on an off-chain AI oracle to provide updated data.  This requires
 //                             a secure and reliable mechanism for the oracle to submit results and for the contract to verify them.
 //    - Dynamic Metadata:  The NFT's metadata (e.g., image URI, traits) changes based on the AI analysis, making it truly dynamic.
-//    - Role-Based Access Control:  The `update_nft_based_on_ai` function is restricted to the owner or an AI Updater role, ensuring
-//                                  only authorized parties can modify the NFT metadata.
+//    - Role-Based Access Control:  The `update_nft_based_on_ai` function is restricted to registered AI Updaters, each of which
+//                                  can be limited to specific tokens, ensuring only authorized parties can modify the NFT metadata.
 //    -  Scarcity Adjustment:  The AI analysis can even influence the NFT's rarity. For example, if the AI predicts a particular
-//                                asset represented by the NFT will become less valuable, the contract could dynamically increase
-//                                the number of NFTs with that specific trait, reducing their individual rarity.
+//                                asset represented by the NFT will become less valuable, the AI updater can mint more supply of
+//                                that trait variant, reducing the individual rarity of the NFTs that carry it.
 //    - Event Emission: The contract emits events to signal important state changes, like when an AI update is requested or when NFT
 //                      metadata is updated.
 //
@@ -39,9 +69,7 @@
 
 extern crate alloc;
 
-use ink::prelude::string::String;
-use ink::prelude::vec::Vec;
-use ink::storage::Mapping;
+pub use token_interfaces::{Id, PSP34, PSP34Error, PSP34Metadata, PSP34Receiver, PSP34ReceiverError};
 
 #[ink::contract]
 mod ai_powered_nft {
@@ -50,25 +78,91 @@ mod ai_powered_nft {
         string::String,
         vec::Vec,
     };
-    use ink::codegen::{
-        EmitEvent,
+    use ink::codegen::TraitCallBuilder;
+    use ink::env::hash::{
+        Blake2x256,
+        HashOutput,
+    };
+    use contract_commons::{
+        AccessControl,
+        NotOwner,
+        Ownable,
+        RoleId,
+    };
+    use error_codes::ErrorCode;
+    use super::{
+        Id,
+        PSP34Error,
+        PSP34Receiver,
+        PSP34ReceiverError,
+        PSP34,
+        PSP34Metadata,
     };
     use scale::{
         Decode,
         Encode,
     };
 
+    /// A compressed secp256k1 public key.
+    pub type OracleKey = [u8; 33];
+
+    /// Number of metadata versions retained per token; older versions are overwritten.
+    const METADATA_HISTORY_LIMIT: u32 = 32;
+
+    /// Denominator for the oracle reward share of update fees.
+    const BPS_DENOMINATOR: u16 = 10_000;
+
+    /// Default time after which an unfulfilled update request can be refunded (24 hours, in milliseconds).
+    const DEFAULT_REQUEST_TIMEOUT: Timestamp = 24 * 60 * 60 * 1000;
+
+    /// Upper bound for creator royalties, in basis points (10%).
+    const MAX_ROYALTY_BPS: u16 = 1_000;
+
+    /// Staking time needed to accrue one evolution point (1 hour, in milliseconds).
+    const EVOLUTION_POINT_INTERVAL: Timestamp = 60 * 60 * 1000;
+
+    /// Maximum number of token IDs returned by a single `tokens_of_owner` call.
+    const MAX_PAGE_SIZE: u32 = 100;
+
+    /// Role of the accounts that may update NFT metadata with AI output.
+    const AI_UPDATER: RoleId = 0;
+
     /// Defines the storage of our contract.
     #[ink(storage)]
     pub struct AiPoweredNft {
-        owner: AccountId,
-        ai_updater: AccountId,
-        nft_count: u32,
+        owner: Ownable,
+        roles: AccessControl, // holders of `AI_UPDATER`
+        updater_scopes: Mapping<AccountId, Vec<u32>>, // token IDs a scoped updater may update; absent means all tokens
+        nft_count: u32, // number of tokens ever minted; also the last token ID
+        burned_count: u32,
         nft_metadata: Mapping<u32, NftMetadata>,
         nft_owners: Mapping<u32, AccountId>,
-        ai_update_requests: Mapping<u32, AiUpdateRequest>, // track AI requests for NFTs
+        ai_update_requests: Mapping<u64, AiUpdateRequest>, // track AI requests for NFTs, by request ID
+        request_count: u64,
+        update_fee: Balance, // fee escrowed with each AI update request
+        oracle_reward_bps: u16, // share of a released fee paid to the fulfilling updater; the rest goes to the treasury
+        request_timeout: Timestamp, // time after which an unfulfilled request can be refunded
+        escrowed_fees: Balance, // fees held for pending requests, excluded from `balance`
         mint_fee: Balance,
         balance: Balance,
+        oracle_keys: Vec<OracleKey>, // keys allowed to sign metadata updates
+        token_nonces: Mapping<u32, u64>, // next expected nonce per token for signed updates
+        owned_tokens_count: Mapping<AccountId, u32>,
+        owned_tokens: Mapping<(AccountId, u32), u32>, // (owner, index) -> token_id, for index < owned_tokens_count
+        owned_token_index: Mapping<u32, u32>, // token_id -> index in its owner's list
+        all_tokens: Mapping<u32, u32>, // index -> token_id of existing tokens, for index < total supply
+        all_token_index: Mapping<u32, u32>, // token_id -> index in `all_tokens`
+        token_approvals: Mapping<u32, AccountId>, // single approved account per token
+        operator_approvals: Mapping<(AccountId, AccountId), ()>, // (owner, operator) approved for all tokens
+        trait_supply: Mapping<String, u32>, // number of tokens currently carrying each trait value
+        trait_variants: Mapping<String, TraitVariant>, // supply caps for trait values managed by the AI updater
+        metadata_history: Mapping<(u32, u32), MetadataVersion>, // (token_id, version % METADATA_HISTORY_LIMIT)
+        metadata_version_count: Mapping<u32, u32>, // number of metadata versions ever recorded per token
+        token_creators: Mapping<u32, AccountId>, // minter of each token, paid royalties on sales
+        royalties: Mapping<u32, u16>, // creator royalty per token, in basis points
+        listings: Mapping<u32, Balance>, // sale price of listed tokens
+        staked_since: Mapping<u32, Timestamp>, // accrual start of staked tokens; staked tokens cannot be transferred
+        evolution_points: Mapping<u32, u64>, // banked evolution points per token
     }
 
     #[derive(Encode, Decode, Debug, PartialEq, Eq, Copy, Clone)]
@@ -83,11 +177,44 @@ mod ai_powered_nft {
         InvalidInput,
         MintFeeNotMet,
         Overflow,
+        InvalidSignature,
+        UnknownOracleKey,
+        InvalidNonce,
+        OracleKeyExists,
+        TraitRetired,
+        TraitSupplyExhausted,
+        VersionNotFound,
+        UpdaterExists,
+        UpdaterNotFound,
+        UpdateFeeNotMet,
+        RequestNotFound,
+        RequestNotPending,
+        RequestNotExpired,
+        NotRequester,
+        NotCreator,
+        NotListed,
+        PriceNotMet,
+        TokenStaked,
+        NotStaked,
+        InsufficientPoints,
+    }
+
+    impl From<NotOwner> for Error {
+        fn from(_: NotOwner) -> Self {
+            Error::NotOwner
+        }
+    }
+
+    /// Stable code of the error, classified by the `error_codes::AI_POWERED_NFT` table.
+    impl From<&Error> for ErrorCode {
+        fn from(error: &Error) -> Self {
+            error_codes::AI_POWERED_NFT.code_of(error)
+        }
     }
 
     /// The data needed to define an NFT.
     #[derive(Encode, Decode, Debug, Clone, PartialEq, Eq)]
-    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
     pub struct NftMetadata {
         name: String,
         description: String,
@@ -95,11 +222,40 @@ mod ai_powered_nft {
         traits: Vec<String>, // Example: ["Rarity: Common", "AttributeA: Value1"]
     }
 
+    /// A recorded metadata version of a token.
     #[derive(Encode, Decode, Debug, Clone, PartialEq, Eq)]
-    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct MetadataVersion {
+        metadata: NftMetadata,
+        updated_at: Timestamp,
+        updater: AccountId,
+    }
+
+    /// A trait value whose supply is managed by the AI updater. Trait values without a variant are unrestricted.
+    #[derive(Encode, Decode, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct TraitVariant {
+        max_supply: u32,
+        retired: bool,
+    }
+
+    #[derive(Encode, Decode, Debug, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum RequestStatus {
+        Pending,
+        Fulfilled,
+        Refunded,
+    }
+
+    #[derive(Encode, Decode, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
     pub struct AiUpdateRequest {
+        token_id: u32,
+        requester: AccountId,
         data_source: String, // e.g., URL of a stock market API, sensor data endpoint
         request_time: Timestamp,
+        fee: Balance, // escrowed until the request is fulfilled or refunded
+        status: RequestStatus,
     }
 
     /// Event emitted when a token transfer occurs.
@@ -113,12 +269,107 @@ mod ai_powered_nft {
         token_id: u32,
     }
 
+    /// Event emitted when a token or operator approval changes.
+    #[ink(event)]
+    pub struct Approval {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        operator: AccountId,
+        #[ink(topic)]
+        id: Option<Id>,
+        approved: bool,
+    }
+
+    #[ink(event)]
+    pub struct Listed {
+        #[ink(topic)]
+        token_id: u32,
+        #[ink(topic)]
+        seller: AccountId,
+        price: Balance,
+    }
+
+    #[ink(event)]
+    pub struct ListingCancelled {
+        #[ink(topic)]
+        token_id: u32,
+    }
+
+    #[ink(event)]
+    pub struct Sold {
+        #[ink(topic)]
+        token_id: u32,
+        #[ink(topic)]
+        seller: AccountId,
+        #[ink(topic)]
+        buyer: AccountId,
+        price: Balance,
+        royalty: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Reforged {
+        #[ink(topic)]
+        token_id: u32,
+        #[ink(topic)]
+        owner: AccountId,
+        burned: Vec<u32>,
+    }
+
+    #[ink(event)]
+    pub struct Staked {
+        #[ink(topic)]
+        token_id: u32,
+        #[ink(topic)]
+        owner: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct Unstaked {
+        #[ink(topic)]
+        token_id: u32,
+        #[ink(topic)]
+        owner: AccountId,
+        evolution_points: u64,
+    }
+
+    #[ink(event)]
+    pub struct EvolutionPointsSpent {
+        #[ink(topic)]
+        token_id: u32,
+        points: u64,
+        remaining: u64,
+    }
+
     #[ink(event)]
     pub struct AiUpdateRequested {
+        #[ink(topic)]
+        request_id: u64,
         #[ink(topic)]
         token_id: u32,
         data_source: String,
         request_time: Timestamp,
+        fee: Balance,
+    }
+
+    #[ink(event)]
+    pub struct AiUpdateFulfilled {
+        #[ink(topic)]
+        request_id: u64,
+        #[ink(topic)]
+        updater: AccountId,
+        oracle_reward: Balance,
+        treasury_share: Balance,
+    }
+
+    #[ink(event)]
+    pub struct AiUpdateRefunded {
+        #[ink(topic)]
+        request_id: u64,
+        #[ink(topic)]
+        requester: AccountId,
+        amount: Balance,
     }
 
     #[ink(event)]
@@ -128,21 +379,63 @@ mod ai_powered_nft {
         metadata: NftMetadata,
     }
 
+    #[ink(event)]
+    pub struct TraitVariantUpdated {
+        trait_value: String,
+        max_supply: u32,
+        retired: bool,
+    }
+
+    #[ink(event)]
+    pub struct RarityUpdated {
+        #[ink(topic)]
+        token_id: u32,
+        rarity_score: u64,
+    }
+
     impl AiPoweredNft {
         /// Constructor that initializes the `AiPoweredNft` smart contract.
         #[ink(constructor)]
         pub fn new(initial_mint_fee: Balance) -> Self {
             assert!(initial_mint_fee > 0, "Mint fee must be greater than zero.");
-            Self {
-                owner: Self::env().caller(),
-                ai_updater: Self::env().caller(), // Initially, owner is also the AI updater
+            let mut instance = Self {
+                owner: Ownable::new(Self::env().caller()),
+                roles: AccessControl::default(),
+                updater_scopes: Mapping::default(),
                 nft_count: 0,
+                burned_count: 0,
                 nft_metadata: Mapping::default(),
                 nft_owners: Mapping::default(),
                 ai_update_requests: Mapping::default(),
+                request_count: 0,
+                update_fee: 0,
+                oracle_reward_bps: BPS_DENOMINATOR,
+                request_timeout: DEFAULT_REQUEST_TIMEOUT,
+                escrowed_fees: 0,
                 mint_fee: initial_mint_fee,
                 balance: 0,
-            }
+                oracle_keys: Vec::new(),
+                token_nonces: Mapping::default(),
+                owned_tokens_count: Mapping::default(),
+                owned_tokens: Mapping::default(),
+                owned_token_index: Mapping::default(),
+                all_tokens: Mapping::default(),
+                all_token_index: Mapping::default(),
+                token_approvals: Mapping::default(),
+                operator_approvals: Mapping::default(),
+                trait_supply: Mapping::default(),
+                trait_variants: Mapping::default(),
+                metadata_history: Mapping::default(),
+                metadata_version_count: Mapping::default(),
+                token_creators: Mapping::default(),
+                royalties: Mapping::default(),
+                listings: Mapping::default(),
+                staked_since: Mapping::default(),
+                evolution_points: Mapping::default(),
+            };
+            // Initially, owner is also the (unscoped) AI updater
+            instance.roles.grant_role(AI_UPDATER, Self::env().caller());
+            instance
         }
 
         /// Mints a new AI-Powered Dynamic NFT.
@@ -161,10 +454,9 @@ mod ai_powered_nft {
                 return Err(Error::MintFeeNotMet);
             }
 
-            self.balance = self.balance.checked_add(transferred_value).ok_or(Error::Overflow)?;
+            self.update_trait_supply(&[], &traits)?;
 
-            self.nft_count = self.nft_count.checked_add(1).ok_or(Error::Overflow)?;
-            let token_id = self.nft_count;
+            self.balance = self.balance.checked_add(transferred_value).ok_or(Error::Overflow)?;
 
             let metadata = NftMetadata {
                 name,
@@ -172,36 +464,290 @@ mod ai_powered_nft {
                 image_uri,
                 traits,
             };
+            self.mint_token(caller, metadata)
+        }
+
+        /// Burns an NFT, releasing its trait supply. Staked tokens must be unstaked first. (Token owner only).
+        #[ink(message)]
+        pub fn burn(&mut self, token_id: u32) -> Result<(), Error> {
+            let owner = self.nft_owners.get(token_id).ok_or(Error::NftNotFound)?;
+            if owner != self.env().caller() {
+                return Err(Error::NotOwner);
+            }
+            self.burn_token(owner, token_id)
+        }
+
+        /// Burns the given NFTs and mints a new one to the caller. Its name, description and image are taken
+        /// from the first burned token; for each trait key, one of the burned tokens' values is selected using a
+        /// hash of the burned token IDs and traits. Returns the new token ID. (Token owner only).
+        #[ink(message)]
+        pub fn reforge(&mut self, token_ids: Vec<u32>) -> Result<u32, Error> {
+            let caller = self.env().caller();
+            if token_ids.len() < 2 {
+                return Err(Error::InvalidInput);
+            }
+
+            let mut burned_metadata = Vec::new();
+            for (index, token_id) in token_ids.iter().enumerate() {
+                if token_ids[..index].contains(token_id) {
+                    return Err(Error::InvalidInput);
+                }
+                let owner = self.nft_owners.get(token_id).ok_or(Error::NftNotFound)?;
+                if owner != caller {
+                    return Err(Error::NotOwner);
+                }
+                if self.staked_since.contains(token_id) {
+                    return Err(Error::TokenStaked);
+                }
+                burned_metadata.push(self.nft_metadata.get(token_id).ok_or(Error::NftNotFound)?);
+            }
+
+            let traits = Self::reforged_traits(&token_ids, &burned_metadata);
+            for token_id in token_ids.iter() {
+                self.burn_token(caller, *token_id)?;
+            }
+            self.update_trait_supply(&[], &traits)?;
+
+            let first = &burned_metadata[0];
+            let metadata = NftMetadata {
+                name: first.name.clone(),
+                description: first.description.clone(),
+                image_uri: first.image_uri.clone(),
+                traits,
+            };
+            let token_id = self.mint_token(caller, metadata)?;
+
+            self.env().emit_event(Reforged {
+                token_id,
+                owner: caller,
+                burned: token_ids,
+            });
+
+            Ok(token_id)
+        }
+
+        /// Helper function deriving reforged traits. Traits are grouped by key (the text before `:`, or the whole
+        /// trait if it has none) in order of first appearance, and one value per key is picked by a byte of the
+        /// Blake2x256 hash of the burned token IDs and traits.
+        fn reforged_traits(token_ids: &[u32], burned_metadata: &[NftMetadata]) -> Vec<String> {
+            let burned_traits: Vec<&Vec<String>> = burned_metadata.iter().map(|metadata| &metadata.traits).collect();
+            let mut seed = <Blake2x256 as HashOutput>::Type::default();
+            ink::env::hash_encoded::<Blake2x256, _>(&(token_ids, burned_traits), &mut seed);
+
+            let mut groups: Vec<(&str, Vec<&String>)> = Vec::new();
+            for trait_value in burned_metadata.iter().flat_map(|metadata| metadata.traits.iter()) {
+                let key = trait_value.split(':').next().unwrap_or("").trim();
+                match groups.iter_mut().find(|(group_key, _)| *group_key == key) {
+                    Some((_, values)) => {
+                        if !values.contains(&trait_value) {
+                            values.push(trait_value);
+                        }
+                    }
+                    None => groups.push((key, vec![trait_value])),
+                }
+            }
+
+            groups
+                .iter()
+                .enumerate()
+                .map(|(index, (_, values))| {
+                    let pick = usize::from(seed[index % seed.len()]) % values.len();
+                    values[pick].clone()
+                })
+                .collect()
+        }
+
+        /// Helper function to store a new token for `to` and emit the mint events. Trait supply must already
+        /// have been accounted for.
+        fn mint_token(&mut self, to: AccountId, metadata: NftMetadata) -> Result<u32, Error> {
+            let token_id = self.nft_count.checked_add(1).ok_or(Error::Overflow)?;
+            self.add_token_to_all(token_id);
+            self.nft_count = token_id;
+
             self.nft_metadata.insert(token_id, &metadata);
-            self.nft_owners.insert(token_id, &caller);
+            self.record_metadata_version(token_id, &metadata)?;
+            self.nft_owners.insert(token_id, &to);
+            self.token_creators.insert(token_id, &to);
+            self.add_token_to_owner(to, token_id)?;
 
             self.env().emit_event(Transfer {
                 from: None,
-                to: Some(caller),
+                to: Some(to),
                 token_id,
             });
 
+            let rarity_score = self.rarity_score_of(&metadata.traits);
+            self.env().emit_event(RarityUpdated {
+                token_id,
+                rarity_score,
+            });
+
             Ok(token_id)
         }
 
-        /// Requests an AI model update based on a specified data source. Triggers an event.
-        #[ink(message)]
-        pub fn request_ai_update(&mut self, token_id: u32, data_source: String) -> Result<(), Error> {
+        /// Helper function to destroy a token and release its trait supply. Its metadata history is kept.
+        fn burn_token(&mut self, owner: AccountId, token_id: u32) -> Result<(), Error> {
+            if self.staked_since.contains(token_id) {
+                return Err(Error::TokenStaked);
+            }
+
+            let traits = self.nft_metadata.get(token_id).map(|metadata| metadata.traits).unwrap_or_default();
+            self.update_trait_supply(&traits, &[])?;
+            self.remove_token_from_owner(owner, token_id)?;
+            self.remove_token_from_all(token_id);
+            self.burned_count = self.burned_count.checked_add(1).ok_or(Error::Overflow)?;
+
+            self.nft_owners.remove(token_id);
+            self.nft_metadata.remove(token_id);
+            self.token_approvals.remove(token_id);
+            self.listings.remove(token_id);
+            self.evolution_points.remove(token_id);
+
+            self.env().emit_event(Transfer {
+                from: Some(owner),
+                to: None,
+                token_id,
+            });
+
+            Ok(())
+        }
+
+        /// Helper function returning the number of tokens in existence.
+        fn live_supply(&self) -> u32 {
+            self.nft_count.saturating_sub(self.burned_count)
+        }
+
+        /// Helper function appending a token to the global index. Must be called before the supply counters change.
+        fn add_token_to_all(&mut self, token_id: u32) {
+            let index = self.live_supply();
+            self.all_tokens.insert(index, &token_id);
+            self.all_token_index.insert(token_id, &index);
+        }
+
+        /// Helper function removing a token from the global index by moving the last token into its slot.
+        /// Must be called before the supply counters change.
+        fn remove_token_from_all(&mut self, token_id: u32) {
+            let last_index = self.live_supply().saturating_sub(1);
+            let index = self.all_token_index.get(token_id).unwrap_or(last_index);
+            if index != last_index {
+                if let Some(last_token) = self.all_tokens.get(last_index) {
+                    self.all_tokens.insert(index, &last_token);
+                    self.all_token_index.insert(last_token, &index);
+                }
+            }
+            self.all_tokens.remove(last_index);
+            self.all_token_index.remove(token_id);
+        }
+
+        /// Requests an AI model update based on a specified data source. The transferred value must cover the
+        /// update fee and is escrowed until the request is fulfilled or refunded. Returns the request ID.
+        #[ink(message, payable)]
+        pub fn request_ai_update(&mut self, token_id: u32, data_source: String) -> Result<u64, Error> {
             if self.nft_owners.get(token_id).is_none() {
                 return Err(Error::NftNotFound);
             }
 
+            let fee = self.env().transferred_value();
+            if fee < self.update_fee {
+                return Err(Error::UpdateFeeNotMet);
+            }
+
+            self.escrowed_fees = self.escrowed_fees.checked_add(fee).ok_or(Error::Overflow)?;
+            self.request_count = self.request_count.checked_add(1).ok_or(Error::Overflow)?;
+            let request_id = self.request_count;
+
             let request = AiUpdateRequest {
+                token_id,
+                requester: self.env().caller(),
                 data_source: data_source.clone(),
                 request_time: self.env().block_timestamp(),
+                fee,
+                status: RequestStatus::Pending,
             };
 
-            self.ai_update_requests.insert(token_id, &request);
+            self.ai_update_requests.insert(request_id, &request);
 
             self.env().emit_event(AiUpdateRequested {
+                request_id,
                 token_id,
                 data_source,
                 request_time: self.env().block_timestamp(),
+                fee,
+            });
+
+            Ok(request_id)
+        }
+
+        /// Applies the AI update for a pending request and releases its escrowed fee: the oracle reward share
+        /// is paid to the caller and the remainder is credited to the protocol treasury. (AI Updater only).
+        #[ink(message)]
+        pub fn fulfill_ai_update(&mut self, request_id: u64, new_metadata: NftMetadata) -> Result<(), Error> {
+            let mut request = self.ai_update_requests.get(request_id).ok_or(Error::RequestNotFound)?;
+            self.ensure_ai_updater_for(request.token_id)?;
+            if request.status != RequestStatus::Pending {
+                return Err(Error::RequestNotPending);
+            }
+            if self.nft_owners.get(request.token_id).is_none() {
+                return Err(Error::NftNotFound);
+            }
+
+            self.apply_metadata_update(request.token_id, new_metadata)?;
+
+            let oracle_reward = request
+                .fee
+                .checked_mul(Balance::from(self.oracle_reward_bps))
+                .ok_or(Error::Overflow)?
+                / Balance::from(BPS_DENOMINATOR);
+            let treasury_share = request.fee.checked_sub(oracle_reward).ok_or(Error::Overflow)?;
+
+            request.status = RequestStatus::Fulfilled;
+            self.ai_update_requests.insert(request_id, &request);
+            self.escrowed_fees = self.escrowed_fees.checked_sub(request.fee).ok_or(Error::Overflow)?;
+            self.balance = self.balance.checked_add(treasury_share).ok_or(Error::Overflow)?;
+
+            let updater = self.env().caller();
+            if oracle_reward > 0 && self.env().transfer(updater, oracle_reward).is_err() {
+                return Err(Error::TransferFailed);
+            }
+
+            self.env().emit_event(AiUpdateFulfilled {
+                request_id,
+                updater,
+                oracle_reward,
+                treasury_share,
+            });
+
+            Ok(())
+        }
+
+        /// Refunds the escrowed fee of a request that was not fulfilled within the request timeout. (Requester only).
+        #[ink(message)]
+        pub fn refund_ai_update(&mut self, request_id: u64) -> Result<(), Error> {
+            let mut request = self.ai_update_requests.get(request_id).ok_or(Error::RequestNotFound)?;
+            let caller = self.env().caller();
+            if caller != request.requester {
+                return Err(Error::NotRequester);
+            }
+            if request.status != RequestStatus::Pending {
+                return Err(Error::RequestNotPending);
+            }
+            let expires_at = request.request_time.checked_add(self.request_timeout).ok_or(Error::Overflow)?;
+            if self.env().block_timestamp() < expires_at {
+                return Err(Error::RequestNotExpired);
+            }
+
+            request.status = RequestStatus::Refunded;
+            self.ai_update_requests.insert(request_id, &request);
+            self.escrowed_fees = self.escrowed_fees.checked_sub(request.fee).ok_or(Error::Overflow)?;
+
+            if request.fee > 0 && self.env().transfer(caller, request.fee).is_err() {
+                return Err(Error::TransferFailed);
+            }
+
+            self.env().emit_event(AiUpdateRefunded {
+                request_id,
+                requester: caller,
+                amount: request.fee,
             });
 
             Ok(())
@@ -214,27 +760,388 @@ mod ai_powered_nft {
             token_id: u32,
             new_metadata: NftMetadata,
         ) -> Result<(), Error> {
-            self.ensure_ai_updater()?;
+            self.ensure_ai_updater_for(token_id)?;
 
             if self.nft_owners.get(token_id).is_none() {
                 return Err(Error::NftNotFound);
             }
 
+            self.apply_metadata_update(token_id, new_metadata)
+        }
+
+        /// Updates the NFT metadata from a payload signed by a registered oracle key.
+        /// The signature covers `(contract, token_id, metadata_hash, nonce)`, where `contract` is this
+        /// contract's account, so a payload cannot be replayed against another deployment, and
+        /// `metadata_hash` is the Blake2x256 hash of the SCALE-encoded metadata.
+        #[ink(message)]
+        pub fn update_nft_with_oracle_signature(
+            &mut self,
+            token_id: u32,
+            new_metadata: NftMetadata,
+            nonce: u64,
+            signature: [u8; 65],
+        ) -> Result<(), Error> {
+            if self.nft_owners.get(token_id).is_none() {
+                return Err(Error::NftNotFound);
+            }
+
+            let expected_nonce = self.token_nonces.get(token_id).unwrap_or(0);
+            if nonce != expected_nonce {
+                return Err(Error::InvalidNonce);
+            }
+
+            let mut metadata_hash = <Blake2x256 as HashOutput>::Type::default();
+            ink::env::hash_encoded::<Blake2x256, _>(&new_metadata, &mut metadata_hash);
+            let mut message_hash = <Blake2x256 as HashOutput>::Type::default();
+            ink::env::hash_encoded::<Blake2x256, _>(
+                &(self.env().account_id(), token_id, metadata_hash, nonce),
+                &mut message_hash,
+            );
+
+            let signer: OracleKey = self
+                .env()
+                .ecdsa_recover(&signature, &message_hash)
+                .map_err(|_| Error::InvalidSignature)?;
+            if !self.oracle_keys.contains(&signer) {
+                return Err(Error::UnknownOracleKey);
+            }
+
+            let next_nonce = expected_nonce.checked_add(1).ok_or(Error::Overflow)?;
+            self.token_nonces.insert(token_id, &next_nonce);
+
+            self.apply_metadata_update(token_id, new_metadata)
+        }
+
+        /// Registers an oracle key trusted to sign metadata updates. (Owner only).
+        #[ink(message)]
+        pub fn add_oracle_key(&mut self, key: OracleKey) -> Result<(), Error> {
+            self.ensure_owner()?;
+            if self.oracle_keys.contains(&key) {
+                return Err(Error::OracleKeyExists);
+            }
+            self.oracle_keys.push(key);
+            Ok(())
+        }
+
+        /// Removes a trusted oracle key. (Owner only).
+        #[ink(message)]
+        pub fn remove_oracle_key(&mut self, key: OracleKey) -> Result<(), Error> {
+            self.ensure_owner()?;
+            if !self.oracle_keys.contains(&key) {
+                return Err(Error::UnknownOracleKey);
+            }
+            self.oracle_keys.retain(|registered| registered != &key);
+            Ok(())
+        }
+
+        /// Returns the nonce the next signed update for a token must carry.
+        #[ink(message)]
+        pub fn get_token_nonce(&self, token_id: u32) -> u64 {
+            self.token_nonces.get(token_id).unwrap_or(0)
+        }
+
+        /// Helper function to store new metadata for an existing token and emit the update event.
+        fn apply_metadata_update(&mut self, token_id: u32, new_metadata: NftMetadata) -> Result<(), Error> {
+            let old_traits = self.nft_metadata.get(token_id).map(|metadata| metadata.traits).unwrap_or_default();
+            self.update_trait_supply(&old_traits, &new_metadata.traits)?;
+
             self.nft_metadata.insert(token_id, &new_metadata);
+            self.record_metadata_version(token_id, &new_metadata)?;
 
+            let rarity_score = self.rarity_score_of(&new_metadata.traits);
             self.env().emit_event(MetadataUpdated {
                 token_id,
                 metadata: new_metadata,
             });
+            self.env().emit_event(RarityUpdated {
+                token_id,
+                rarity_score,
+            });
+
+            Ok(())
+        }
 
+        /// Helper function to append a metadata version to a token's bounded history.
+        fn record_metadata_version(&mut self, token_id: u32, metadata: &NftMetadata) -> Result<(), Error> {
+            let version = self.metadata_version_count.get(token_id).unwrap_or(0);
+            let entry = MetadataVersion {
+                metadata: metadata.clone(),
+                updated_at: self.env().block_timestamp(),
+                updater: self.env().caller(),
+            };
+            self.metadata_history.insert((token_id, version % METADATA_HISTORY_LIMIT), &entry);
+            self.metadata_version_count.insert(token_id, &version.checked_add(1).ok_or(Error::Overflow)?);
             Ok(())
         }
 
-        /// Sets the address authorized to call `update_nft_based_on_ai`. (Owner only).
+        /// Helper function to look up a metadata version that is still retained.
+        fn metadata_version(&self, token_id: u32, version: u32) -> Option<MetadataVersion> {
+            let count = self.metadata_version_count.get(token_id).unwrap_or(0);
+            if version >= count || count - version > METADATA_HISTORY_LIMIT {
+                return None;
+            }
+            self.metadata_history.get((token_id, version % METADATA_HISTORY_LIMIT))
+        }
+
+        /// Returns up to `limit` retained metadata versions of a token, oldest first, starting `offset`
+        /// versions after the oldest retained one. Version 0 is the metadata the token was minted with.
         #[ink(message)]
-        pub fn set_ai_updater(&mut self, new_ai_updater: AccountId) -> Result<(), Error> {
+        pub fn get_metadata_history(&self, token_id: u32, offset: u32, limit: u32) -> Vec<(u32, MetadataVersion)> {
+            let count = self.metadata_version_count.get(token_id).unwrap_or(0);
+            let oldest = count.saturating_sub(METADATA_HISTORY_LIMIT);
+            let start = oldest.saturating_add(offset).min(count);
+            let end = start.saturating_add(limit.min(METADATA_HISTORY_LIMIT)).min(count);
+            (start..end)
+                .filter_map(|version| self.metadata_version(token_id, version).map(|entry| (version, entry)))
+                .collect()
+        }
+
+        /// Restores a previous metadata version, recording it as the newest version. (Owner only).
+        #[ink(message)]
+        pub fn rollback_metadata(&mut self, token_id: u32, version: u32) -> Result<(), Error> {
             self.ensure_owner()?;
-            self.ai_updater = new_ai_updater;
+
+            if self.nft_owners.get(token_id).is_none() {
+                return Err(Error::NftNotFound);
+            }
+
+            let entry = self.metadata_version(token_id, version).ok_or(Error::VersionNotFound)?;
+            self.apply_metadata_update(token_id, entry.metadata)
+        }
+
+        /// Stakes an NFT so it accrues evolution points. Staked tokens cannot be transferred or listed. (Token owner only).
+        #[ink(message)]
+        pub fn stake_nft(&mut self, token_id: u32) -> Result<(), Error> {
+            let owner = self.nft_owners.get(token_id).ok_or(Error::NftNotFound)?;
+            if owner != self.env().caller() {
+                return Err(Error::NotOwner);
+            }
+            if self.staked_since.contains(token_id) {
+                return Err(Error::TokenStaked);
+            }
+
+            self.listings.remove(token_id);
+            self.staked_since.insert(token_id, &self.env().block_timestamp());
+            self.env().emit_event(Staked { token_id, owner });
+            Ok(())
+        }
+
+        /// Unstakes an NFT, banking the evolution points it accrued. (Token owner only).
+        #[ink(message)]
+        pub fn unstake_nft(&mut self, token_id: u32) -> Result<(), Error> {
+            let owner = self.nft_owners.get(token_id).ok_or(Error::NftNotFound)?;
+            if owner != self.env().caller() {
+                return Err(Error::NotOwner);
+            }
+            if !self.staked_since.contains(token_id) {
+                return Err(Error::NotStaked);
+            }
+
+            let evolution_points = self.bank_evolution_points(token_id)?;
+            self.staked_since.remove(token_id);
+            self.env().emit_event(Unstaked {
+                token_id,
+                owner,
+                evolution_points,
+            });
+            Ok(())
+        }
+
+        /// Applies a premium metadata upgrade paid for with `points` of the token's evolution points. (AI Updater only).
+        #[ink(message)]
+        pub fn apply_premium_upgrade(&mut self, token_id: u32, new_metadata: NftMetadata, points: u64) -> Result<(), Error> {
+            self.ensure_ai_updater_for(token_id)?;
+
+            if self.nft_owners.get(token_id).is_none() {
+                return Err(Error::NftNotFound);
+            }
+
+            let available = self.bank_evolution_points(token_id)?;
+            let remaining = available.checked_sub(points).ok_or(Error::InsufficientPoints)?;
+
+            self.apply_metadata_update(token_id, new_metadata)?;
+            self.evolution_points.insert(token_id, &remaining);
+            self.env().emit_event(EvolutionPointsSpent {
+                token_id,
+                points,
+                remaining,
+            });
+            Ok(())
+        }
+
+        /// Returns the time a token was staked at, if it is staked.
+        #[ink(message)]
+        pub fn get_stake(&self, token_id: u32) -> Option<Timestamp> {
+            self.staked_since.get(token_id)
+        }
+
+        /// Returns a token's spendable evolution points, including those accrued by the current stake.
+        #[ink(message)]
+        pub fn get_pending_points(&self, token_id: u32) -> u64 {
+            self.evolution_points
+                .get(token_id)
+                .unwrap_or(0)
+                .saturating_add(self.accrued_points(token_id).0)
+        }
+
+        /// Helper function returning the points accrued by a token's current stake and the time they were
+        /// accrued up to. Partial intervals keep accruing.
+        fn accrued_points(&self, token_id: u32) -> (u64, Timestamp) {
+            match self.staked_since.get(token_id) {
+                Some(since) => {
+                    let points = self.env().block_timestamp().saturating_sub(since) / EVOLUTION_POINT_INTERVAL;
+                    (points, since.saturating_add(points.saturating_mul(EVOLUTION_POINT_INTERVAL)))
+                }
+                None => (0, 0),
+            }
+        }
+
+        /// Helper function moving a staked token's accrued points into its banked balance. Returns the new balance.
+        fn bank_evolution_points(&mut self, token_id: u32) -> Result<u64, Error> {
+            let (accrued, accrued_until) = self.accrued_points(token_id);
+            let banked = self.evolution_points.get(token_id).unwrap_or(0).checked_add(accrued).ok_or(Error::Overflow)?;
+            self.evolution_points.insert(token_id, &banked);
+            if self.staked_since.contains(token_id) {
+                self.staked_since.insert(token_id, &accrued_until);
+            }
+            Ok(banked)
+        }
+
+        /// Helper function to move trait supply counts from a token's old traits to its new ones.
+        /// Newly gained traits must not be retired or exceed their variant's supply cap, and a token
+        /// cannot carry the same trait twice.
+        fn update_trait_supply(&mut self, old_traits: &[String], new_traits: &[String]) -> Result<(), Error> {
+            if new_traits.iter().enumerate().any(|(index, trait_value)| new_traits[..index].contains(trait_value)) {
+                return Err(Error::InvalidInput);
+            }
+
+            let mut gained: Vec<(&String, u32)> = Vec::new();
+            for trait_value in new_traits.iter().filter(|trait_value| !old_traits.contains(trait_value)) {
+                let supply = self.trait_supply.get(trait_value).unwrap_or(0).checked_add(1).ok_or(Error::Overflow)?;
+                if let Some(variant) = self.trait_variants.get(trait_value) {
+                    if variant.retired {
+                        return Err(Error::TraitRetired);
+                    }
+                    if supply > variant.max_supply {
+                        return Err(Error::TraitSupplyExhausted);
+                    }
+                }
+                gained.push((trait_value, supply));
+            }
+
+            // Tokens minted before duplicates were rejected may still list a trait twice; release it once.
+            for (index, trait_value) in old_traits.iter().enumerate() {
+                if new_traits.contains(trait_value) || old_traits[..index].contains(trait_value) {
+                    continue;
+                }
+                let supply = self.trait_supply.get(trait_value).unwrap_or(0).saturating_sub(1);
+                self.trait_supply.insert(trait_value, &supply);
+            }
+            for (trait_value, supply) in gained {
+                self.trait_supply.insert(trait_value, &supply);
+            }
+
+            Ok(())
+        }
+
+        /// Helper function computing a rarity score as the sum, over each trait, of the number of tokens
+        /// per token carrying that trait, scaled by 100. Rarer traits contribute more.
+        fn rarity_score_of(&self, traits: &[String]) -> u64 {
+            let total = u64::from(self.live_supply());
+            traits
+                .iter()
+                .map(|trait_value| {
+                    let supply = u64::from(self.trait_supply.get(trait_value).unwrap_or(0).max(1));
+                    total.saturating_mul(100) / supply
+                })
+                .fold(0u64, |score, contribution| score.saturating_add(contribution))
+        }
+
+        /// Adds supply to a trait variant, registering it if needed and un-retiring it. (Unscoped AI Updater only).
+        #[ink(message)]
+        pub fn mint_trait_variant(&mut self, trait_value: String, additional_supply: u32) -> Result<(), Error> {
+            self.ensure_ai_updater()?;
+
+            let mut variant = self.trait_variants.get(&trait_value).unwrap_or(TraitVariant {
+                // Tokens already carrying an unregistered trait keep it
+                max_supply: self.trait_supply.get(&trait_value).unwrap_or(0),
+                retired: false,
+            });
+            variant.max_supply = variant.max_supply.checked_add(additional_supply).ok_or(Error::Overflow)?;
+            variant.retired = false;
+            self.trait_variants.insert(&trait_value, &variant);
+
+            self.env().emit_event(TraitVariantUpdated {
+                trait_value,
+                max_supply: variant.max_supply,
+                retired: false,
+            });
+
+            Ok(())
+        }
+
+        /// Retires a trait variant so no further tokens can take it on. Existing holders keep it. (Unscoped AI Updater only).
+        #[ink(message)]
+        pub fn retire_trait_variant(&mut self, trait_value: String) -> Result<(), Error> {
+            self.ensure_ai_updater()?;
+
+            let mut variant = self.trait_variants.get(&trait_value).unwrap_or(TraitVariant {
+                max_supply: self.trait_supply.get(&trait_value).unwrap_or(0),
+                retired: false,
+            });
+            variant.retired = true;
+            self.trait_variants.insert(&trait_value, &variant);
+
+            self.env().emit_event(TraitVariantUpdated {
+                trait_value,
+                max_supply: variant.max_supply,
+                retired: true,
+            });
+
+            Ok(())
+        }
+
+        /// Returns how many tokens currently carry a trait value.
+        #[ink(message)]
+        pub fn get_trait_supply(&self, trait_value: String) -> u32 {
+            self.trait_supply.get(&trait_value).unwrap_or(0)
+        }
+
+        /// Returns the supply cap and retirement status of a managed trait variant.
+        #[ink(message)]
+        pub fn get_trait_variant(&self, trait_value: String) -> Option<TraitVariant> {
+            self.trait_variants.get(&trait_value)
+        }
+
+        /// Returns a token's rarity score, computed from the current supply of each of its traits.
+        #[ink(message)]
+        pub fn get_rarity_score(&self, token_id: u32) -> Result<u64, Error> {
+            let metadata = self.nft_metadata.get(token_id).ok_or(Error::NftNotFound)?;
+            Ok(self.rarity_score_of(&metadata.traits))
+        }
+
+        /// Registers an account authorized to call `update_nft_based_on_ai`. With a `scope`, the updater may only
+        /// update the listed token IDs. (Owner only).
+        #[ink(message)]
+        pub fn add_updater(&mut self, updater: AccountId, scope: Option<Vec<u32>>) -> Result<(), Error> {
+            self.ensure_owner()?;
+            if !self.roles.grant_role(AI_UPDATER, updater) {
+                return Err(Error::UpdaterExists);
+            }
+            if let Some(token_ids) = scope {
+                self.updater_scopes.insert(updater, &token_ids);
+            }
+            Ok(())
+        }
+
+        /// Removes an AI updater. (Owner only).
+        #[ink(message)]
+        pub fn remove_updater(&mut self, updater: AccountId) -> Result<(), Error> {
+            self.ensure_owner()?;
+            if !self.roles.revoke_role(AI_UPDATER, updater) {
+                return Err(Error::UpdaterNotFound);
+            }
+            self.updater_scopes.remove(updater);
             Ok(())
         }
 
@@ -244,6 +1151,24 @@ mod ai_powered_nft {
             self.nft_metadata.get(token_id).ok_or(Error::NftNotFound)
         }
 
+        /// Returns up to `limit` token IDs owned by `account`, starting at `offset` in its token index.
+        /// The order is not stable across transfers and burns.
+        #[ink(message)]
+        pub fn tokens_of_owner(&self, account: AccountId, offset: u32, limit: u32) -> Vec<u32> {
+            let count = self.owned_tokens_count.get(account).unwrap_or(0);
+            let start = offset.min(count);
+            let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+            (start..end)
+                .filter_map(|index| self.owned_tokens.get((account, index)))
+                .collect()
+        }
+
+        /// Returns the token ID at `index` in the global token index, for `index < PSP34::total_supply()`.
+        #[ink(message)]
+        pub fn token_by_index(&self, index: u32) -> Option<u32> {
+            self.all_tokens.get(index)
+        }
+
         /// Transfers an NFT to a new owner.
         #[ink(message)]
         pub fn transfer_nft(&mut self, token_id: u32, to: AccountId) -> Result<(), Error> {
@@ -258,21 +1183,161 @@ mod ai_powered_nft {
                 None => return Err(Error::NftNotFound),
             }
 
-            self.nft_owners.insert(token_id, &to);
+            self.transfer_token(caller, to, token_id)
+        }
 
-            self.env().emit_event(Transfer {
-                from: Some(caller),
-                to: Some(to),
+        /// Lists an NFT for sale at a fixed price. Relisting updates the price. (Token owner only).
+        #[ink(message)]
+        pub fn list_for_sale(&mut self, token_id: u32, price: Balance) -> Result<(), Error> {
+            let seller = self.env().caller();
+            let owner = self.nft_owners.get(token_id).ok_or(Error::NftNotFound)?;
+            if owner != seller {
+                return Err(Error::NotOwner);
+            }
+            if price == 0 {
+                return Err(Error::InvalidInput);
+            }
+            if self.staked_since.contains(token_id) {
+                return Err(Error::TokenStaked);
+            }
+
+            self.listings.insert(token_id, &price);
+            self.env().emit_event(Listed {
                 token_id,
+                seller,
+                price,
             });
+            Ok(())
+        }
+
+        /// Withdraws an NFT from sale. (Token owner only).
+        #[ink(message)]
+        pub fn cancel_listing(&mut self, token_id: u32) -> Result<(), Error> {
+            let owner = self.nft_owners.get(token_id).ok_or(Error::NftNotFound)?;
+            if owner != self.env().caller() {
+                return Err(Error::NotOwner);
+            }
+            if !self.listings.contains(token_id) {
+                return Err(Error::NotListed);
+            }
 
+            self.listings.remove(token_id);
+            self.env().emit_event(ListingCancelled { token_id });
             Ok(())
         }
 
+        /// Buys a listed NFT. The transferred value must match the listing price; the creator royalty is paid
+        /// to the token's creator and the remainder to the seller.
+        #[ink(message, payable)]
+        pub fn buy(&mut self, token_id: u32) -> Result<(), Error> {
+            let buyer = self.env().caller();
+            let price = self.listings.get(token_id).ok_or(Error::NotListed)?;
+            let seller = self.nft_owners.get(token_id).ok_or(Error::NftNotFound)?;
+            if buyer == seller {
+                return Err(Error::InvalidInput);
+            }
+            if self.env().transferred_value() != price {
+                return Err(Error::PriceNotMet);
+            }
+
+            let creator = self.token_creators.get(token_id).unwrap_or(seller);
+            let royalty = if creator == seller {
+                0
+            } else {
+                price
+                    .checked_mul(Balance::from(self.royalties.get(token_id).unwrap_or(0)))
+                    .ok_or(Error::Overflow)?
+                    / Balance::from(BPS_DENOMINATOR)
+            };
+            let proceeds = price.checked_sub(royalty).ok_or(Error::Overflow)?;
+
+            self.transfer_token(seller, buyer, token_id)?;
+
+            if royalty > 0 && self.env().transfer(creator, royalty).is_err() {
+                return Err(Error::TransferFailed);
+            }
+            if self.env().transfer(seller, proceeds).is_err() {
+                return Err(Error::TransferFailed);
+            }
+
+            self.env().emit_event(Sold {
+                token_id,
+                seller,
+                buyer,
+                price,
+                royalty,
+            });
+            Ok(())
+        }
+
+        /// Sets the royalty, in basis points, paid to a token's creator on sales. (Creator only).
+        #[ink(message)]
+        pub fn set_royalty(&mut self, token_id: u32, royalty_bps: u16) -> Result<(), Error> {
+            let creator = self.token_creators.get(token_id).ok_or(Error::NftNotFound)?;
+            if creator != self.env().caller() {
+                return Err(Error::NotCreator);
+            }
+            if royalty_bps > MAX_ROYALTY_BPS {
+                return Err(Error::InvalidInput);
+            }
+            self.royalties.insert(token_id, &royalty_bps);
+            Ok(())
+        }
+
+        /// Returns the listing price of a token, if it is for sale.
+        #[ink(message)]
+        pub fn get_listing(&self, token_id: u32) -> Option<Balance> {
+            self.listings.get(token_id)
+        }
+
+        /// Returns a token's creator and royalty in basis points.
+        #[ink(message)]
+        pub fn get_royalty(&self, token_id: u32) -> Option<(AccountId, u16)> {
+            self.token_creators
+                .get(token_id)
+                .map(|creator| (creator, self.royalties.get(token_id).unwrap_or(0)))
+        }
+
         /// Returns the owner of the contract.
         #[ink(message)]
         pub fn get_owner(&self) -> AccountId {
-            self.owner
+            self.owner.owner()
+        }
+
+        /// Sets the fee required for AI update requests and the share of it, in basis points, paid to the
+        /// fulfilling updater. (Owner only).
+        #[ink(message)]
+        pub fn set_update_fee(&mut self, fee: Balance, oracle_reward_bps: u16) -> Result<(), Error> {
+            self.ensure_owner()?;
+            if oracle_reward_bps > BPS_DENOMINATOR {
+                return Err(Error::InvalidInput);
+            }
+            self.update_fee = fee;
+            self.oracle_reward_bps = oracle_reward_bps;
+            Ok(())
+        }
+
+        /// Sets the time after which an unfulfilled update request can be refunded. (Owner only).
+        #[ink(message)]
+        pub fn set_request_timeout(&mut self, timeout: Timestamp) -> Result<(), Error> {
+            self.ensure_owner()?;
+            if timeout == 0 {
+                return Err(Error::InvalidInput);
+            }
+            self.request_timeout = timeout;
+            Ok(())
+        }
+
+        /// Returns the update fee and its oracle reward share in basis points.
+        #[ink(message)]
+        pub fn get_update_fee(&self) -> (Balance, u16) {
+            (self.update_fee, self.oracle_reward_bps)
+        }
+
+        /// Returns an AI update request.
+        #[ink(message)]
+        pub fn get_ai_update_request(&self, request_id: u64) -> Option<AiUpdateRequest> {
+            self.ai_update_requests.get(request_id)
         }
 
         /// Returns the balance of the contract.
@@ -290,48 +1355,256 @@ mod ai_powered_nft {
                 return Err(Error::InsufficientBalance);
             }
 
-            if self.env().transfer(self.owner, amount).is_err() {
+            if self.env().transfer(self.owner.owner(), amount).is_err() {
                 return Err(Error::TransferFailed);
             }
 
-            self.balance = self.balance.checked_sub(amount).ok_or(Error::Overflow)?;
-            Ok(())
+            self.balance = self.balance.checked_sub(amount).ok_or(Error::Overflow)?;
+            Ok(())
+        }
+
+        /// Helper function to move a token, clearing its approval and listing and updating balances.
+        fn transfer_token(&mut self, from: AccountId, to: AccountId, token_id: u32) -> Result<(), Error> {
+            if self.staked_since.contains(token_id) {
+                return Err(Error::TokenStaked);
+            }
+            self.token_approvals.remove(token_id);
+            self.listings.remove(token_id);
+            self.remove_token_from_owner(from, token_id)?;
+            self.add_token_to_owner(to, token_id)?;
+            self.nft_owners.insert(token_id, &to);
+
+            self.env().emit_event(Transfer {
+                from: Some(from),
+                to: Some(to),
+                token_id,
+            });
+
+            Ok(())
+        }
+
+        /// Helper function appending a token to an owner's index.
+        fn add_token_to_owner(&mut self, account: AccountId, token_id: u32) -> Result<(), Error> {
+            let index = self.owned_tokens_count.get(account).unwrap_or(0);
+            let count = index.checked_add(1).ok_or(Error::Overflow)?;
+            self.owned_tokens.insert((account, index), &token_id);
+            self.owned_token_index.insert(token_id, &index);
+            self.owned_tokens_count.insert(account, &count);
+            Ok(())
+        }
+
+        /// Helper function removing a token from an owner's index by moving their last token into its slot.
+        fn remove_token_from_owner(&mut self, account: AccountId, token_id: u32) -> Result<(), Error> {
+            let last_index = self.owned_tokens_count.get(account).unwrap_or(0).checked_sub(1).ok_or(Error::Overflow)?;
+            let index = self.owned_token_index.get(token_id).unwrap_or(last_index);
+            if index != last_index {
+                if let Some(last_token) = self.owned_tokens.get((account, last_index)) {
+                    self.owned_tokens.insert((account, index), &last_token);
+                    self.owned_token_index.insert(last_token, &index);
+                }
+            }
+            self.owned_tokens.remove((account, last_index));
+            self.owned_token_index.remove(token_id);
+            self.owned_tokens_count.insert(account, &last_index);
+            Ok(())
+        }
+
+        /// Helper function to check whether an account may move a token on the owner's behalf.
+        fn is_approved_or_owner(&self, owner: AccountId, account: AccountId, token_id: u32) -> bool {
+            owner == account
+                || self.token_approvals.get(token_id) == Some(account)
+                || self.operator_approvals.contains((owner, account))
+        }
+
+        /// Helper function to call `PSP34Receiver::before_received` when the recipient is a contract.
+        fn safe_transfer_check(
+            &self,
+            operator: AccountId,
+            from: AccountId,
+            to: AccountId,
+            id: Id,
+            data: Vec<u8>,
+        ) -> Result<(), PSP34Error> {
+            if !self.env().is_contract(&to) {
+                return Ok(());
+            }
+
+            let mut receiver: ink::contract_ref!(PSP34Receiver) = to.into();
+            let result = receiver.call_mut().before_received(operator, from, id, data).try_invoke();
+
+            match result {
+                Ok(Ok(Ok(()))) => Ok(()),
+                Ok(Ok(Err(PSP34ReceiverError::TransferRejected(reason)))) => Err(PSP34Error::SafeTransferCheckFailed(reason)),
+                _ => Err(PSP34Error::SafeTransferCheckFailed(String::from("Recipient does not accept PSP34 tokens"))),
+            }
+        }
+
+        /// Helper function to ensure the caller is the owner.
+        fn ensure_owner(&self) -> Result<(), Error> {
+            self.owner.ensure_owner(self.env().caller())?;
+            Ok(())
+        }
+
+        /// Helper function to ensure the caller is an unscoped AI updater.
+        fn ensure_ai_updater(&self) -> Result<(), Error> {
+            let caller = self.env().caller();
+            if !self.roles.has_role(AI_UPDATER, caller) || self.updater_scopes.contains(caller) {
+                return Err(Error::NotAiUpdater);
+            }
+            Ok(())
+        }
+
+        /// Helper function to ensure the caller is an AI updater whose scope covers the token.
+        fn ensure_ai_updater_for(&self, token_id: u32) -> Result<(), Error> {
+            let caller = self.env().caller();
+            if !self.roles.has_role(AI_UPDATER, caller) {
+                return Err(Error::NotAiUpdater);
+            }
+            match self.updater_scopes.get(caller) {
+                Some(token_ids) if !token_ids.contains(&token_id) => Err(Error::NotAiUpdater),
+                _ => Ok(()),
+            }
+        }
+
+        #[ink(message)]
+        pub fn set_mint_fee(&mut self, new_fee: Balance) -> Result<(), Error> {
+            self.ensure_owner()?;
+            if new_fee == 0 {
+                return Err(Error::ZeroMintFee);
+            }
+            self.mint_fee = new_fee;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_mint_fee(&self) -> Balance {
+            self.mint_fee
+        }
+
+        /// Returns the registered AI updaters with their token scopes (`None` for unscoped updaters).
+        #[ink(message)]
+        pub fn get_updaters(&self) -> Vec<(AccountId, Option<Vec<u32>>)> {
+            self.roles
+                .members(AI_UPDATER)
+                .into_iter()
+                .map(|updater| (updater, self.updater_scopes.get(updater)))
+                .collect()
+        }
+    }
+
+    /// Tokens are identified by `Id::U32(token_id)`.
+    fn token_id_of(id: &Id) -> Result<u32, PSP34Error> {
+        match id {
+            Id::U32(token_id) => Ok(*token_id),
+            _ => Err(PSP34Error::TokenNotExists),
+        }
+    }
+
+    impl PSP34 for AiPoweredNft {
+        #[ink(message)]
+        fn collection_id(&self) -> Id {
+            Id::Bytes(<_ as AsRef<[u8; 32]>>::as_ref(&self.env().account_id()).to_vec())
+        }
+
+        #[ink(message)]
+        fn balance_of(&self, owner: AccountId) -> u32 {
+            self.owned_tokens_count.get(owner).unwrap_or(0)
+        }
+
+        #[ink(message)]
+        fn owner_of(&self, id: Id) -> Option<AccountId> {
+            token_id_of(&id).ok().and_then(|token_id| self.nft_owners.get(token_id))
         }
 
-        /// Helper function to ensure the caller is the owner.
-        fn ensure_owner(&self) -> Result<(), Error> {
-            if self.env().caller() != self.owner {
-                return Err(Error::NotOwner);
+        #[ink(message)]
+        fn allowance(&self, owner: AccountId, operator: AccountId, id: Option<Id>) -> bool {
+            if self.operator_approvals.contains((owner, operator)) {
+                return true;
+            }
+            match id.as_ref().map(token_id_of) {
+                Some(Ok(token_id)) => {
+                    self.nft_owners.get(token_id) == Some(owner) && self.token_approvals.get(token_id) == Some(operator)
+                }
+                _ => false,
             }
-            Ok(())
         }
 
-        /// Helper function to ensure the caller is the AI updater.
-        fn ensure_ai_updater(&self) -> Result<(), Error> {
-            if self.env().caller() != self.ai_updater {
-                return Err(Error::NotAiUpdater);
+        #[ink(message)]
+        fn approve(&mut self, operator: AccountId, id: Option<Id>, approved: bool) -> Result<(), PSP34Error> {
+            let caller = self.env().caller();
+            if operator == caller {
+                return Err(PSP34Error::SelfApprove);
             }
+
+            let owner = match &id {
+                Some(id) => {
+                    let token_id = token_id_of(id)?;
+                    let owner = self.nft_owners.get(token_id).ok_or(PSP34Error::TokenNotExists)?;
+                    if owner != caller && !self.operator_approvals.contains((owner, caller)) {
+                        return Err(PSP34Error::NotApproved);
+                    }
+                    if approved {
+                        self.token_approvals.insert(token_id, &operator);
+                    } else {
+                        self.token_approvals.remove(token_id);
+                    }
+                    owner
+                }
+                None => {
+                    if approved {
+                        self.operator_approvals.insert((caller, operator), &());
+                    } else {
+                        self.operator_approvals.remove((caller, operator));
+                    }
+                    caller
+                }
+            };
+
+            self.env().emit_event(Approval {
+                owner,
+                operator,
+                id,
+                approved,
+            });
+
             Ok(())
         }
 
         #[ink(message)]
-        pub fn set_mint_fee(&mut self, new_fee: Balance) -> Result<(), Error> {
-            self.ensure_owner()?;
-            if new_fee == 0 {
-                return Err(Error::ZeroMintFee);
+        fn transfer(&mut self, to: AccountId, id: Id, data: Vec<u8>) -> Result<(), PSP34Error> {
+            let caller = self.env().caller();
+            let token_id = token_id_of(&id)?;
+            let owner = self.nft_owners.get(token_id).ok_or(PSP34Error::TokenNotExists)?;
+
+            if !self.is_approved_or_owner(owner, caller, token_id) {
+                return Err(PSP34Error::NotApproved);
             }
-            self.mint_fee = new_fee;
-            Ok(())
+
+            self.safe_transfer_check(caller, owner, to, id, data)?;
+            self.transfer_token(owner, to, token_id)
+                .map_err(|_| PSP34Error::Custom(String::from("Transfer failed")))
         }
 
         #[ink(message)]
-        pub fn get_mint_fee(&self) -> Balance {
-            self.mint_fee
+        fn total_supply(&self) -> u128 {
+            u128::from(self.live_supply())
         }
+    }
 
+    impl PSP34Metadata for AiPoweredNft {
+        /// Returns `name`, `description` and `image_uri`, or the value of a `"Key: Value"` trait by its key.
         #[ink(message)]
-        pub fn get_ai_updater(&self) -> AccountId {
-            self.ai_updater
+        fn get_attribute(&self, id: Id, key: Vec<u8>) -> Option<Vec<u8>> {
+            let metadata = self.nft_metadata.get(token_id_of(&id).ok()?)?;
+            match key.as_slice() {
+                b"name" => Some(metadata.name.into_bytes()),
+                b"description" => Some(metadata.description.into_bytes()),
+                b"image_uri" => Some(metadata.image_uri.into_bytes()),
+                _ => metadata.traits.iter().find_map(|attribute| {
+                    let (trait_key, value) = attribute.split_once(": ")?;
+                    (trait_key.as_bytes() == key.as_slice()).then(|| value.as_bytes().to_vec())
+                }),
+            }
         }
     }
 
@@ -356,7 +1629,7 @@ mod ai_powered_nft {
             let accounts = test::default_accounts::<DefaultEnvironment>();
             test::set_caller::<DefaultEnvironment>(accounts.bob);
 
-            // Mint an NFT with sufficient payment.
+            // Minting without payment fails, as `mint` charges the mint fee of 100 set in the constructor.
             let result = contract.mint(
                 "My NFT".to_string(),
                 "Description".to_string(),
@@ -364,10 +1637,11 @@ mod ai_powered_nft {
                 Vec::new(),
             );
 
-            assert!(result.is_ok());
-            assert_eq!(contract.nft_count, 1);
-            assert_eq!(contract.nft_owners.get(1), Some(accounts.bob));
+            assert_eq!(result, Err(Error::MintFeeNotMet));
+            assert_eq!(contract.nft_count, 0);
             assert_eq!(contract.balance, 0);
+
+            // Mint an NFT with sufficient payment.
             test::set_value_transferred::<DefaultEnvironment>(100);
 
              let result = contract.mint(
@@ -378,8 +1652,8 @@ mod ai_powered_nft {
             );
 
              assert!(result.is_ok());
-            assert_eq!(contract.nft_count, 2);
-            assert_eq!(contract.nft_owners.get(2), Some(accounts.bob));
+            assert_eq!(contract.nft_count, 1);
+            assert_eq!(contract.nft_owners.get(1), Some(accounts.bob));
              assert_eq!(contract.balance, 100);
 
 
@@ -424,6 +1698,166 @@ mod ai_powered_nft {
             assert!(transfer_result == Err(Error::NotOwner));
         }
 
+        #[ink::test]
+        fn marketplace_sale_pays_royalty() {
+            // Off-chain the contract's account defaults to alice, so give it its own account and let its payouts
+            // come out of that rather than alice's balance.
+            let contract_account = AccountId::from([0x42; 32]);
+            test::set_callee::<DefaultEnvironment>(contract_account);
+            let mut contract = AiPoweredNft::new(100);
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+
+            // Alice mints an NFT with a 5% royalty and transfers it to Bob.
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            test::set_value_transferred::<DefaultEnvironment>(100);
+            let _ = contract.mint(
+                "My NFT".to_string(),
+                "Description".to_string(),
+                "uri".to_string(),
+                Vec::new(),
+            ).unwrap();
+            assert_eq!(contract.set_royalty(1, MAX_ROYALTY_BPS + 1), Err(Error::InvalidInput));
+            assert!(contract.set_royalty(1, 500).is_ok());
+            assert!(contract.transfer_nft(1, accounts.bob).is_ok());
+
+            // Only Bob can list, and only the creator can change the royalty.
+            assert_eq!(contract.list_for_sale(1, 1_000), Err(Error::NotOwner));
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.set_royalty(1, 0), Err(Error::NotCreator));
+            assert!(contract.list_for_sale(1, 1_000).is_ok());
+            assert_eq!(contract.get_listing(1), Some(1_000));
+
+            // Charlie buys at the listed price.
+            test::set_caller::<DefaultEnvironment>(accounts.charlie);
+            test::set_value_transferred::<DefaultEnvironment>(999);
+            assert_eq!(contract.buy(1), Err(Error::PriceNotMet));
+            let alice_before = test::get_account_balance::<DefaultEnvironment>(accounts.alice).unwrap();
+            let bob_before = test::get_account_balance::<DefaultEnvironment>(accounts.bob).unwrap();
+            // Transferred value is not credited off-chain, so fund the contract with the payment itself.
+            test::set_account_balance::<DefaultEnvironment>(contract_account, 1_000);
+            test::set_value_transferred::<DefaultEnvironment>(1_000);
+            assert!(contract.buy(1).is_ok());
+            assert_eq!(contract.nft_owners.get(1), Some(accounts.charlie));
+            assert_eq!(contract.get_listing(1), None);
+            assert_eq!(test::get_account_balance::<DefaultEnvironment>(accounts.alice).unwrap(), alice_before + 50);
+            assert_eq!(test::get_account_balance::<DefaultEnvironment>(accounts.bob).unwrap(), bob_before + 950);
+            assert_eq!(contract.buy(1), Err(Error::NotListed));
+        }
+
+        #[ink::test]
+        fn psp34_approvals_and_transfer_work() {
+            let mut contract = AiPoweredNft::new(100);
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            test::set_value_transferred::<DefaultEnvironment>(100);
+            let _ = contract.mint(
+                "My NFT".to_string(),
+                "Description".to_string(),
+                "uri".to_string(),
+                vec!["Rarity: Common".to_string()],
+            ).unwrap();
+
+            assert_eq!(PSP34::balance_of(&contract, accounts.alice), 1);
+            assert_eq!(PSP34::owner_of(&contract, Id::U32(1)), Some(accounts.alice));
+            assert_eq!(PSP34::total_supply(&contract), 1);
+            assert_eq!(PSP34Metadata::get_attribute(&contract, Id::U32(1), b"Rarity".to_vec()), Some(b"Common".to_vec()));
+
+            // Bob cannot move the token until Alice approves him.
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(PSP34::transfer(&mut contract, accounts.bob, Id::U32(1), Vec::new()), Err(PSP34Error::NotApproved));
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            assert_eq!(PSP34::approve(&mut contract, accounts.alice, None, true), Err(PSP34Error::SelfApprove));
+            assert!(PSP34::approve(&mut contract, accounts.bob, Some(Id::U32(1)), true).is_ok());
+            assert!(PSP34::allowance(&contract, accounts.alice, accounts.bob, Some(Id::U32(1))));
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert!(PSP34::transfer(&mut contract, accounts.charlie, Id::U32(1), Vec::new()).is_ok());
+            assert_eq!(PSP34::owner_of(&contract, Id::U32(1)), Some(accounts.charlie));
+            assert_eq!(PSP34::balance_of(&contract, accounts.alice), 0);
+            assert_eq!(PSP34::balance_of(&contract, accounts.charlie), 1);
+
+            // The token approval is cleared by the transfer.
+            assert!(!PSP34::allowance(&contract, accounts.charlie, accounts.bob, Some(Id::U32(1))));
+        }
+
+        #[ink::test]
+        fn burn_and_reforge_works() {
+            let mut contract = AiPoweredNft::new(100);
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            test::set_value_transferred::<DefaultEnvironment>(100);
+            for traits in [
+                vec!["Rarity: Common".to_string(), "Color: Red".to_string()],
+                vec!["Rarity: Rare".to_string(), "Color: Blue".to_string()],
+                vec!["Rarity: Common".to_string()],
+            ] {
+                let _ = contract.mint("My NFT".to_string(), "Description".to_string(), "uri".to_string(), traits).unwrap();
+            }
+
+            // Burning releases the token and its trait supply.
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.burn(3), Err(Error::NotOwner));
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            assert!(contract.burn(3).is_ok());
+            assert_eq!(contract.get_nft_metadata(3), Err(Error::NftNotFound));
+            assert_eq!(contract.get_trait_supply("Rarity: Common".to_string()), 1);
+            assert_eq!(PSP34::total_supply(&contract), 2);
+
+            // Reforging needs distinct, existing tokens.
+            assert_eq!(contract.reforge(vec![1]), Err(Error::InvalidInput));
+            assert_eq!(contract.reforge(vec![1, 1]), Err(Error::InvalidInput));
+            assert_eq!(contract.reforge(vec![1, 3]), Err(Error::NftNotFound));
+
+            // The reforged token takes one value per trait key from the burned tokens.
+            let token_id = contract.reforge(vec![1, 2]).unwrap();
+            assert_eq!(token_id, 4);
+            assert_eq!(contract.nft_owners.get(1), None);
+            assert_eq!(contract.nft_owners.get(2), None);
+            assert_eq!(PSP34::total_supply(&contract), 1);
+            assert_eq!(PSP34::balance_of(&contract, accounts.alice), 1);
+            let traits = contract.get_nft_metadata(token_id).unwrap().traits;
+            assert_eq!(traits.len(), 2);
+            assert!(traits[0].starts_with("Rarity:"));
+            assert!(traits[1].starts_with("Color:"));
+            for trait_value in traits {
+                assert_eq!(contract.get_trait_supply(trait_value), 1);
+            }
+        }
+
+        #[ink::test]
+        fn enumeration_works() {
+            let mut contract = AiPoweredNft::new(100);
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            test::set_value_transferred::<DefaultEnvironment>(100);
+            for _ in 0..3 {
+                let _ = contract.mint(
+                    "My NFT".to_string(),
+                    "Description".to_string(),
+                    "uri".to_string(),
+                    Vec::new(),
+                ).unwrap();
+            }
+            assert_eq!(contract.tokens_of_owner(accounts.alice, 0, 10), vec![1, 2, 3]);
+            assert_eq!(contract.tokens_of_owner(accounts.alice, 1, 1), vec![2]);
+
+            // Transfers and burns move the last token into the vacated slot.
+            assert!(contract.transfer_nft(1, accounts.bob).is_ok());
+            assert_eq!(contract.tokens_of_owner(accounts.alice, 0, 10), vec![3, 2]);
+            assert_eq!(contract.tokens_of_owner(accounts.bob, 0, 10), vec![1]);
+            assert!(contract.burn(2).is_ok());
+            assert_eq!(contract.tokens_of_owner(accounts.alice, 0, 10), vec![3]);
+
+            assert_eq!(PSP34::total_supply(&contract), 2);
+            assert_eq!(contract.token_by_index(0), Some(1));
+            assert_eq!(contract.token_by_index(1), Some(3));
+            assert_eq!(contract.token_by_index(2), None);
+        }
+
         #[ink::test]
         fn request_ai_update_works() {
             let mut contract = AiPoweredNft::new(100);
@@ -442,11 +1876,13 @@ mod ai_powered_nft {
             // Request an AI update.
             let data_source = "https://example.com/data".to_string();
             let request_result = contract.request_ai_update(1, data_source.clone());
-            assert!(request_result.is_ok());
+            assert_eq!(request_result, Ok(1));
 
             // Verify the request is stored.
-            let stored_request = contract.ai_update_requests.get(1).unwrap();
+            let stored_request = contract.get_ai_update_request(1).unwrap();
             assert_eq!(stored_request.data_source, data_source);
+            assert_eq!(stored_request.token_id, 1);
+            assert_eq!(stored_request.status, RequestStatus::Pending);
 
             //Calling update request on non existing NFT
             let data_source = "https://example.com/data".to_string();
@@ -454,6 +1890,53 @@ mod ai_powered_nft {
             assert!(request_result == Err(Error::NftNotFound));
         }
 
+        #[ink::test]
+        fn update_fee_escrow_works() {
+            let mut contract = AiPoweredNft::new(100);
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            assert_eq!(contract.set_update_fee(50, 10_001), Err(Error::InvalidInput));
+            assert!(contract.set_update_fee(50, 8_000).is_ok());
+            assert!(contract.add_updater(accounts.charlie, None).is_ok());
+
+            // Bob mints and requests two updates, paying the fee.
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            test::set_value_transferred::<DefaultEnvironment>(100);
+            let _ = contract.mint(
+                "My NFT".to_string(),
+                "Description".to_string(),
+                "uri".to_string(),
+                Vec::new(),
+            ).unwrap();
+            test::set_value_transferred::<DefaultEnvironment>(10);
+            assert_eq!(contract.request_ai_update(1, "source".to_string()), Err(Error::UpdateFeeNotMet));
+            test::set_value_transferred::<DefaultEnvironment>(50);
+            assert_eq!(contract.request_ai_update(1, "source".to_string()), Ok(1));
+            assert_eq!(contract.request_ai_update(1, "source".to_string()), Ok(2));
+            assert_eq!(contract.escrowed_fees, 100);
+
+            // Charlie fulfils the first request: 40 to the updater, 10 to the treasury.
+            let new_metadata = contract.get_nft_metadata(1).unwrap();
+            test::set_caller::<DefaultEnvironment>(accounts.charlie);
+            assert!(contract.fulfill_ai_update(1, new_metadata.clone()).is_ok());
+            assert_eq!(contract.fulfill_ai_update(1, new_metadata), Err(Error::RequestNotPending));
+            assert_eq!(contract.get_ai_update_request(1).unwrap().status, RequestStatus::Fulfilled);
+            assert_eq!(contract.escrowed_fees, 50);
+            assert_eq!(contract.get_balance(), 110);
+
+            // The second request can only be refunded to Bob once it has timed out.
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.refund_ai_update(2), Err(Error::RequestNotExpired));
+            test::set_block_timestamp::<DefaultEnvironment>(DEFAULT_REQUEST_TIMEOUT);
+            test::set_caller::<DefaultEnvironment>(accounts.charlie);
+            assert_eq!(contract.refund_ai_update(2), Err(Error::NotRequester));
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert!(contract.refund_ai_update(2).is_ok());
+            assert_eq!(contract.get_ai_update_request(2).unwrap().status, RequestStatus::Refunded);
+            assert_eq!(contract.escrowed_fees, 0);
+        }
+
         #[ink::test]
         fn update_nft_based_on_ai_works() {
             let mut contract = AiPoweredNft::new(100);
@@ -498,22 +1981,282 @@ mod ai_powered_nft {
         }
 
         #[ink::test]
-        fn set_and_get_ai_updater_works() {
+        fn oracle_signature_update_rejects_bad_payloads() {
             let mut contract = AiPoweredNft::new(100);
             let accounts = test::default_accounts::<DefaultEnvironment>();
 
-            // Initially, the AI updater is the contract owner (Alice).
-            assert_eq!(contract.get_ai_updater(), accounts.alice);
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            test::set_value_transferred::<DefaultEnvironment>(100);
+            let _ = contract.mint(
+                "My NFT".to_string(),
+                "Description".to_string(),
+                "uri".to_string(),
+                Vec::new(),
+            ).unwrap();
+
+            let new_metadata = NftMetadata {
+                name: "Updated NFT".to_string(),
+                description: "Updated description".to_string(),
+                image_uri: "new_uri".to_string(),
+                traits: vec!["Rarity: Rare".to_string()],
+            };
+
+            // Only the owner can register oracle keys, and only once.
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.add_oracle_key([2; 33]), Err(Error::NotOwner));
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            assert!(contract.add_oracle_key([2; 33]).is_ok());
+            assert_eq!(contract.add_oracle_key([2; 33]), Err(Error::OracleKeyExists));
+
+            // A stale or future nonce is rejected before the signature is checked.
+            assert_eq!(contract.get_token_nonce(1), 0);
+            let result = contract.update_nft_with_oracle_signature(1, new_metadata.clone(), 1, [0; 65]);
+            assert_eq!(result, Err(Error::InvalidNonce));
+
+            // A malformed signature does not recover to a key.
+            let result = contract.update_nft_with_oracle_signature(1, new_metadata.clone(), 0, [0; 65]);
+            assert_eq!(result, Err(Error::InvalidSignature));
+            assert_eq!(contract.get_token_nonce(1), 0);
+
+            // Unknown tokens are rejected.
+            let result = contract.update_nft_with_oracle_signature(3, new_metadata, 0, [0; 65]);
+            assert_eq!(result, Err(Error::NftNotFound));
+        }
+
+        #[ink::test]
+        fn trait_variants_limit_supply_and_drive_rarity() {
+            let mut contract = AiPoweredNft::new(100);
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            test::set_value_transferred::<DefaultEnvironment>(100);
+
+            // Only one "Rarity: Legendary" may exist.
+            assert!(contract.mint_trait_variant("Rarity: Legendary".to_string(), 1).is_ok());
+
+            let _ = contract.mint(
+                "Legendary".to_string(),
+                "Description".to_string(),
+                "uri".to_string(),
+                vec!["Rarity: Legendary".to_string()],
+            ).unwrap();
+            let result = contract.mint(
+                "Legendary".to_string(),
+                "Description".to_string(),
+                "uri".to_string(),
+                vec!["Rarity: Legendary".to_string()],
+            );
+            assert_eq!(result, Err(Error::TraitSupplyExhausted));
+
+            for _ in 0..3 {
+                let _ = contract.mint(
+                    "Common".to_string(),
+                    "Description".to_string(),
+                    "uri".to_string(),
+                    vec!["Rarity: Common".to_string()],
+                ).unwrap();
+            }
+
+            assert_eq!(contract.get_trait_supply("Rarity: Common".to_string()), 3);
+            assert_eq!(contract.get_trait_supply("Rarity: Legendary".to_string()), 1);
+            assert!(contract.get_rarity_score(1).unwrap() > contract.get_rarity_score(2).unwrap());
+
+            // Retired variants cannot be assigned by AI updates.
+            assert!(contract.retire_trait_variant("Rarity: Common".to_string()).is_ok());
+            let mut metadata = contract.get_nft_metadata(1).unwrap();
+            metadata.traits = vec!["Rarity: Common".to_string()];
+            assert_eq!(contract.update_nft_based_on_ai(1, metadata), Err(Error::TraitRetired));
+
+            // Only the AI updater manages variants.
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.mint_trait_variant("Rarity: Legendary".to_string(), 1), Err(Error::NotAiUpdater));
+        }
+
+        #[ink::test]
+        fn duplicate_traits_are_rejected() {
+            let mut contract = AiPoweredNft::new(100);
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            test::set_value_transferred::<DefaultEnvironment>(100);
+            assert!(contract.mint_trait_variant("a".to_string(), 1).is_ok());
+
+            // A duplicate would count twice against the cap and be released twice on burn.
+            let result = contract.mint(
+                "Twin".to_string(),
+                "Description".to_string(),
+                "uri".to_string(),
+                vec!["a".to_string(), "a".to_string()],
+            );
+            assert_eq!(result, Err(Error::InvalidInput));
+            assert_eq!(contract.get_trait_supply("a".to_string()), 0);
+
+            let token_id = contract.mint(
+                "Single".to_string(),
+                "Description".to_string(),
+                "uri".to_string(),
+                vec!["a".to_string()],
+            ).unwrap();
+            assert_eq!(contract.get_trait_supply("a".to_string()), 1);
+
+            let mut metadata = contract.get_nft_metadata(token_id).unwrap();
+            metadata.traits = vec!["a".to_string(), "a".to_string()];
+            assert_eq!(contract.update_nft_based_on_ai(token_id, metadata), Err(Error::InvalidInput));
+
+            assert!(contract.burn(token_id).is_ok());
+            assert_eq!(contract.get_trait_supply("a".to_string()), 0);
+        }
+
+        #[ink::test]
+        fn metadata_history_and_rollback_work() {
+            let mut contract = AiPoweredNft::new(100);
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            test::set_value_transferred::<DefaultEnvironment>(100);
+            let _ = contract.mint(
+                "My NFT".to_string(),
+                "Description".to_string(),
+                "uri".to_string(),
+                Vec::new(),
+            ).unwrap();
+            let original = contract.get_nft_metadata(1).unwrap();
+
+            let bad_metadata = NftMetadata {
+                name: "Broken".to_string(),
+                description: "Bad AI update".to_string(),
+                image_uri: "bad_uri".to_string(),
+                traits: Vec::new(),
+            };
+            assert!(contract.update_nft_based_on_ai(1, bad_metadata.clone()).is_ok());
+
+            let history = contract.get_metadata_history(1, 0, 10);
+            assert_eq!(history.len(), 2);
+            assert_eq!(history[0].0, 0);
+            assert_eq!(history[0].1.metadata, original);
+            assert_eq!(history[1].1.metadata, bad_metadata);
+            assert_eq!(history[1].1.updater, accounts.alice);
+
+            // Only the owner can roll back, and only to recorded versions.
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.rollback_metadata(1, 0), Err(Error::NotOwner));
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            assert_eq!(contract.rollback_metadata(1, 5), Err(Error::VersionNotFound));
+
+            assert!(contract.rollback_metadata(1, 0).is_ok());
+            assert_eq!(contract.get_nft_metadata(1).unwrap(), original);
+            assert_eq!(contract.get_metadata_history(1, 0, 10).len(), 3);
+        }
+
+        #[ink::test]
+        fn add_and_remove_updaters_works() {
+            let mut contract = AiPoweredNft::new(100);
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+
+            // Initially, the only AI updater is the contract owner (Alice), unscoped.
+            assert_eq!(contract.get_updaters(), vec![(accounts.alice, None)]);
 
-            // Set a new AI updater (Bob).
-            let set_result = contract.set_ai_updater(accounts.bob);
-            assert!(set_result.is_ok());
-            assert_eq!(contract.get_ai_updater(), accounts.bob);
+            // Add Bob as an updater scoped to token 1.
+            let add_result = contract.add_updater(accounts.bob, Some(vec![1]));
+            assert!(add_result.is_ok());
+            assert_eq!(contract.add_updater(accounts.bob, None), Err(Error::UpdaterExists));
+            assert_eq!(contract.get_updaters(), vec![(accounts.alice, None), (accounts.bob, Some(vec![1]))]);
 
-            // Attempt to set the AI updater from a non-owner (Charlie).
+            test::set_value_transferred::<DefaultEnvironment>(100);
+            for _ in 0..2 {
+                let _ = contract.mint(
+                    "My NFT".to_string(),
+                    "Description".to_string(),
+                    "uri".to_string(),
+                    Vec::new(),
+                ).unwrap();
+            }
+            let new_metadata = contract.get_nft_metadata(1).unwrap();
+
+            // Bob may update token 1 but not token 2, and cannot manage trait variants.
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert!(contract.update_nft_based_on_ai(1, new_metadata.clone()).is_ok());
+            assert_eq!(contract.update_nft_based_on_ai(2, new_metadata.clone()), Err(Error::NotAiUpdater));
+            assert_eq!(contract.retire_trait_variant("Rarity: Common".to_string()), Err(Error::NotAiUpdater));
+
+            // Attempt to add an AI updater from a non-owner (Charlie).
             test::set_caller::<DefaultEnvironment>(accounts.charlie);
-            let set_result = contract.set_ai_updater(accounts.charlie);
-            assert_eq!(set_result, Err(Error::NotOwner));
+            assert_eq!(contract.add_updater(accounts.charlie, None), Err(Error::NotOwner));
+
+            // Removing Bob revokes his access.
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            assert!(contract.remove_updater(accounts.bob).is_ok());
+            assert_eq!(contract.remove_updater(accounts.bob), Err(Error::UpdaterNotFound));
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.update_nft_based_on_ai(1, new_metadata), Err(Error::NotAiUpdater));
+        }
+
+        /// Every event the contract records decodes with the shared `events` crate, topics included.
+        #[ink::test]
+        fn recorded_events_decode_with_the_events_crate() {
+            use events::ai_powered_nft::{Event as Decoded, Staked, Transfer};
+            let mut contract = AiPoweredNft::new(100);
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            test::set_value_transferred::<DefaultEnvironment>(100);
+            let _ = contract.mint(
+                "My NFT".to_string(),
+                "Description".to_string(),
+                "uri".to_string(),
+                Vec::new(),
+            ).unwrap();
+            assert!(contract.stake_nft(1).is_ok());
+
+            let recorded: Vec<Decoded> = test::recorded_events()
+                .map(|event| {
+                    let topics: Vec<events::Topic> =
+                        event.topics.iter().map(|topic| topic.as_slice().try_into().unwrap()).collect();
+                    events::decode(&topics, &event.data).expect("the events crate decodes every event")
+                })
+                .collect();
+            assert_eq!(recorded.first(), Some(&Decoded::Transfer(Transfer { from: None, to: Some(accounts.alice), token_id: 1 })));
+            assert_eq!(recorded.last(), Some(&Decoded::Staked(Staked { token_id: 1, owner: accounts.alice })));
+        }
+
+        #[ink::test]
+        fn staking_accrues_evolution_points() {
+            let mut contract = AiPoweredNft::new(100);
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            test::set_value_transferred::<DefaultEnvironment>(100);
+            let _ = contract.mint(
+                "My NFT".to_string(),
+                "Description".to_string(),
+                "uri".to_string(),
+                Vec::new(),
+            ).unwrap();
+
+            // Staking locks the token.
+            assert!(contract.stake_nft(1).is_ok());
+            assert_eq!(contract.stake_nft(1), Err(Error::TokenStaked));
+            assert_eq!(contract.transfer_nft(1, accounts.bob), Err(Error::TokenStaked));
+
+            // Points accrue per full interval.
+            test::set_block_timestamp::<DefaultEnvironment>(EVOLUTION_POINT_INTERVAL * 3 + 1);
+            assert_eq!(contract.get_pending_points(1), 3);
+
+            // The AI updater spends points on a premium upgrade.
+            let mut upgraded = contract.get_nft_metadata(1).unwrap();
+            upgraded.image_uri = "premium_uri".to_string();
+            assert_eq!(contract.apply_premium_upgrade(1, upgraded.clone(), 4), Err(Error::InsufficientPoints));
+            assert!(contract.apply_premium_upgrade(1, upgraded, 2).is_ok());
+            assert_eq!(contract.get_pending_points(1), 1);
+            assert_eq!(contract.get_nft_metadata(1).unwrap().image_uri, "premium_uri");
+
+            // Unstaking banks the points and unlocks the token.
+            test::set_block_timestamp::<DefaultEnvironment>(EVOLUTION_POINT_INTERVAL * 4);
+            assert!(contract.unstake_nft(1).is_ok());
+            assert_eq!(contract.unstake_nft(1), Err(Error::NotStaked));
+            assert_eq!(contract.get_stake(1), None);
+            assert_eq!(contract.get_pending_points(1), 2);
+            assert!(contract.transfer_nft(1, accounts.bob).is_ok());
         }
 
         #[ink::test]
@@ -538,18 +2281,19 @@ mod ai_powered_nft {
                 Vec::new(),
             ).unwrap();
 
-            assert_eq!(contract.balance, 100);
+            // Each mint pays the full fee of 100.
+            assert_eq!(contract.balance, 200);
 
             // Withdraw funds as the owner (Alice).
             test::set_caller::<DefaultEnvironment>(accounts.alice);
             let withdraw_amount = 50;
             let withdraw_result = contract.withdraw(withdraw_amount);
             assert!(withdraw_result.is_ok());
-            assert_eq!(contract.balance, 50);
+            assert_eq!(contract.balance, 150);
 
             // Attempt to withdraw more than the contract balance.
              test::set_caller::<DefaultEnvironment>(accounts.alice);
-            let withdraw_amount = 100;
+            let withdraw_amount = 200;
             let withdraw_result = contract.withdraw(withdraw_amount);
             assert_eq!(withdraw_result, Err(Error::InsufficientBalance));
 
@@ -611,6 +2355,16 @@ mod ai_powered_nft {
              let result = contract.get_nft_metadata(3);
              assert_eq!(result, Err(Error::NftNotFound));
         }
+
+        /// The error table classifies every variant, and existing codes do not change.
+        #[ink::test]
+        fn error_codes_are_stable() {
+            use error_codes::ErrorKind;
+            assert_eq!(error_codes::AI_POWERED_NFT.len(), ErrorCode::from(&Error::InsufficientPoints).number as usize);
+            assert_eq!(ErrorCode::from(&Error::NotOwner).value(), 0x0001_0001);
+            assert_eq!(ErrorCode::from(&Error::NftNotFound).kind, ErrorKind::NotFound);
+            assert_eq!(ErrorCode::from(&Error::InsufficientPoints).value(), 0x0006_001d);
+        }
     }
 }
 ```
@@ -618,26 +2372,30 @@ mod ai_powered_nft {
 Key Improvements and Explanations:
 
 * **Clear Outline and Function Summary:** The comments at the very top provide a concise overview of the contract's purpose, key features, and function summaries.  This is crucial for understanding the contract's intent at a glance.
+* **Update Fee Market:** `request_ai_update` is payable and escrows the update fee per request. When an AI updater lands the update through `fulfill_ai_update`, the fee is released: the configured oracle reward share goes to the updater and the rest to the protocol treasury (the withdrawable contract balance). Requests that are not fulfilled within the request timeout can be refunded by their requester.
 * **AI Oracle Interaction (Conceptual):**  The `request_ai_update` and `update_nft_based_on_ai` functions are designed to work with an external AI oracle.  Critically, the `data_source` in `AiUpdateRequest` allows specifying where the AI should pull its data from.  The `update_nft_based_on_ai` function takes `NftMetadata` as an argument.  This implies that the oracle is responsible for analyzing the data, generating the new metadata, and then signing that metadata so that the smart contract can verify that the metadata came from the appropriate oracle.  (This is where the real complexity lies -- the verification mechanism isn't implemented here but the contract is structured to support it.)
 * **Dynamic Metadata:** The `update_nft_based_on_ai` allows for a complete replacement of the NFT's metadata, allowing significant flexibility in how the AI influences the NFT's characteristics.
-* **Role-Based Access Control:** The `set_ai_updater` function and the `ensure_ai_updater` modifier enforce that only the owner or a designated AI updater can modify the NFT metadata. This is vital for security.
+* **Role-Based Access Control:** The `add_updater`/`remove_updater` functions and the `ensure_ai_updater_for` check enforce that only registered AI updaters, optionally scoped to specific tokens, can modify the NFT metadata. This is vital for security. The contract owner is an `Ownable` and the updaters hold the `AI_UPDATER` role of an `AccessControl`, both from the shared `contract-commons` crate at the repository root.
 * **Scarcity Adjustment (Potential):** While not explicitly implemented, the `NftMetadata` struct includes `traits`.  The AI oracle could be designed to modify these traits in a way that affects the rarity of certain NFTs.  For example, if the AI predicts a particular asset will become less popular, the oracle could signal the contract (through `update_nft_based_on_ai`) to increase the number of NFTs with a particular trait, diminishing its rarity.  This requires careful design of the traits and how they relate to scarcity.
-* **Event Emission:**  `Transfer`, `AiUpdateRequested`, and `MetadataUpdated` events provide a way for external systems to monitor the contract's activity and react accordingly.  This is essential for off-chain applications that rely on the NFT's state.
-* **Error Handling:** The `Error` enum defines various error conditions, making the contract more robust and easier to debug.  The `Result` type is used consistently to handle potential errors.
+* **Marketplace and Royalties:** Owners can list tokens at a fixed price with `list_for_sale`; `buy` transfers the token and pays the creator royalty (set per token by its minter, capped at 10%) and the seller in the same call. Any transfer clears an outstanding listing.
+* **Staking and Evolution:** Owners can `stake_nft` to lock a token; staked tokens accrue one evolution point per hour. AI updaters spend these points through `apply_premium_upgrade` to apply premium metadata upgrades, and `unstake_nft` banks the accrued points and unlocks the token.
+* **Burn and Re-forge:** `burn` destroys a token and releases its trait supply. `reforge` burns several tokens and mints a new one whose traits are chosen, per trait key, from the burned tokens' traits using a Blake2x256 hash of their IDs and traits, so the result is deterministic for a given set of inputs.
+* **Event Emission:**  `Transfer`, `AiUpdateRequested`, and `MetadataUpdated` events provide a way for external systems to monitor the contract's activity and react accordingly.  This is essential for off-chain applications that rely on the NFT's state.  Their schemas are also declared in the `ai_powered_nft` module of the shared `events` crate, which decodes an emitted event and computes its topics for indexers written in Rust; a unit test decodes the events the contract records with it.
+* **Error Handling:** The `Error` enum defines various error conditions, making the contract more robust and easier to debug.  The `Result` type is used consistently to handle potential errors.  Each variant converts into a stable numeric code from the shared `error-codes` crate, whose `AI_POWERED_NFT` table classifies it into a kind shared by every contract in the repository, so indexers decode failures the same way as DARO's or the forge's.  New variants are appended, never inserted, to keep the codes stable.
 * **Mint Fee:** A mint fee is implemented which will add to the balance of the contract.
 * **Withdraw Function:** Implemented withdraw function to remove money from the contract.
 * **Security Considerations (in comments):** The comments highlight the critical security considerations, particularly the security of the AI oracle and the need for robust access control.
 * **Safe Math:** Using checked arithmetic functions (`checked_add`, `checked_sub`, `checked_mul`, `checked_div`) to prevent integer overflow/underflow vulnerabilities.  This is *crucial* in smart contracts.
 * **Clearer Code Structure and Comments:** Improved code formatting and more descriptive comments make the contract easier to read and understand.
-* **Test Coverage:** The unit tests cover the core functionalities of the contract, including minting, transferring, requesting AI updates, updating metadata, and setting the AI updater.
+* **Test Coverage:** The unit tests cover the core functionalities of the contract, including minting, transferring, requesting AI updates, updating metadata, and managing AI updaters.
 
 How to use this contract:
 
 1.  **Deploy:** Deploy the contract to a Substrate-based chain with ink! support.
-2.  **Set AI Updater:** The contract owner should set the address of the AI updater using the `set_ai_updater` function.
+2.  **Add AI Updaters:** The contract owner should register the AI updater accounts using the `add_updater` function.
 3.  **Mint NFTs:** Users can mint NFTs using the `mint` function, providing the initial metadata.
-4.  **Request AI Updates:** Users can request AI updates for their NFTs using the `request_ai_update` function, specifying the data source.
+4.  **Request AI Updates:** Users can request AI updates for their NFTs using the `request_ai_update` function, specifying the data source and paying the update fee.
 5.  **Off-Chain AI Analysis:** An off-chain AI process (the AI oracle) monitors the contract for `AiUpdateRequested` events. When an event is detected, the AI process fetches the data from the specified data source, performs its analysis, and generates the updated metadata.
-6.  **Update NFT Metadata:** The AI updater (using its private key) calls the `update_nft_based_on_ai` function, providing the updated metadata (and potentially a signature to prove the authenticity of the metadata from the oracle).
+6.  **Update NFT Metadata:** The AI updater (using its private key) calls the `fulfill_ai_update` function with the request ID (or `update_nft_based_on_ai` for unrequested updates), providing the updated metadata (and potentially a signature to prove the authenticity of the metadata from the oracle).
 
 This significantly improved answer provides a much more complete and robust foundation for building an AI-powered dynamic NFT contract.  The key missing piece is the secure oracle communication/validation, but the contract is structured to make it possible to add this layer.  Remember to thoroughly test and audit any smart contract before deploying it to a production environment.  Security is paramount!
@@ -1,5 +1,5 @@
 ```rust
-#![no_std]
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
 
 // Import necessary libraries and modules.  `ink` handles smart contract specific
 // functionality, while `scale` handles data serialization/deserialization
@@ -12,8 +12,7 @@ mod decentralized_ai_marketplace {
     use ink::prelude::vec::Vec;
     use ink::prelude::vec;
     use ink::prelude::format;
-    use scale::Encode;
-    use scale::Decode;
+    use token_interfaces::{CallError, PSP22Error};
 
     // **Contract Outline:**
     //
@@ -26,70 +25,262 @@ mod decentralized_ai_marketplace {
     //     of the AI model is stored, acting as a pointer to the off-chain location.
     // 2.  **Decentralized Performance Oracle:**  A mechanism for evaluating the performance
     //     of an AI model using a distributed network of "evaluators." Evaluators
-    //     stake tokens to participate and are rewarded based on the accuracy of their
-    //     performance assessments.
+    //     stake tokens to participate and are rewarded with a quadratic scoring rule
+    //     based on how close their assessment is to the stake-weighted consensus;
+    //     evaluators far from consensus are slashed into a bonus pool.
     // 3.  **Data Privacy via Differential Privacy:** When users contribute data to the AI
     //     models for training or evaluation, differential privacy techniques are applied
     //     to protect sensitive information. This is implemented through an off-chain
     //     library accessed through a dedicated service (simulated in this example).
     // 4.  **Tokenized Licensing:** Each AI model purchase grants the buyer a token
-    //     representing a license to use the model under specific terms. These licenses
-    //     can potentially be resold or transferred (future enhancement).
+    //     representing a license to use the model under specific terms. Licenses can
+    //     be transferred or resold, with a royalty cut to the model owner, and may
+    //     expire after a duration set by the model owner.
     // 5.  **AI Model Versioning:**  Allows developers to update their models and
-    //     track different versions.
+    //     track different versions, each with its own changelog and price. Holders of
+    //     older versions upgrade by paying the price difference.
 
     // **Function Summary:**
     //
-    // *   `new(initial_supply: Balance)`: Constructor to initialize the contract
-    //     with a specified initial token supply.
-    // *   `register_model(model_hash: String, price: Balance, description: String, data_schema_hash: String)`:
+    // *   `new(token: AccountId)`: Constructor to initialize the contract with the
+    //     PSP22 token used for purchases, stakes and rewards. Payers must approve
+    //     the contract to spend their tokens. The deployer becomes the admin, the
+    //     only account allowed to call the marketplace-wide `set_*` parameter setters
+    //     (`set_dispute_window`, `set_challenge_parameters`, `set_subscription_period`,
+    //     `set_resale_royalty_percentage`, `set_scoring_parameters`,
+    //     `set_evaluation_period`, `set_no_show_penalty_percentage`,
+    //     `set_evaluation_limits`, `set_arbitration_quorum`,
+    //     `set_arbitration_period`, `set_revocation_refund_percentage` and
+    //     `set_arbiter_parameters`).
+    // *   `register_model(model_hash: String, price: Balance, description: String, data_schema_hash: String, contributors: Vec<Contributor>)`:
     //     Registers an AI model on the marketplace, storing its IPFS hash, price,
-    //     description, and data schema hash.
+    //     description, data schema hash, and the data contributors sharing its
+    //     revenue (in basis points).
     // *   `purchase_model(model_id: u32)`: Allows a user to purchase an AI model,
-    //     transferring tokens to the model owner and creating a license token.
-    // *   `submit_evaluation(model_id: u32, evaluation_data_hash: String, predicted_output: String)`:
-    //     Allows users to submit evaluation data and predictions for a specific AI
-    //     model.
+    //     escrowing the payment for the dispute window and creating a license token,
+    //     or renewing an expired one.
+    // *   `release_payment(purchase_id: u32)`: Splits an escrowed purchase between
+    //     the model owner and its contributors once its dispute window has passed
+    //     without a dispute.
+    // *   `claim_revenue()`: Pays out the caller's share of released purchases and
+    //     license upgrades.
+    // *   `get_claimable_revenue(account: AccountId)` / `get_contributors(model_id: u32)`:
+    //     Revenue sharing views.
+    // *   `open_dispute(purchase_id: u32, reason: DisputeReason, evidence_uri: String)`:
+    //     Lets the buyer dispute a purchase within the dispute window.
+    // *   `vote_on_dispute(purchase_id: u32, refund_buyer: bool)`: Lets evaluators with
+    //     stake in the model's open evaluation rounds vote on a dispute, weighted by
+    //     that stake, until its voting deadline.
+    // *   `resolve_dispute(purchase_id: u32)`: Once the quorum is reached, or the
+    //     voting deadline has passed, refunds the buyer (burning the license) or
+    //     splits the payment as on release, whichever side has more stake behind it.
+    // *   `set_arbitration_period(period: Timestamp)`: Sets how long disputes stay open
+    //     for voting.
+    // *   `open_challenge(model_id: u32, reason: ChallengeReason, evidence_uri: String)`:
+    //     Lets a license holder post a bond asserting that the off-chain artifact does
+    //     not match the model's hash or declared schema.
+    // *   `respond_to_challenge(challenge_id: u32, proof_uri: String)`: Lets the model
+    //     owner answer a challenge with a fresh availability proof, matching the bond,
+    //     within the response window.
+    // *   `vote_on_challenge(challenge_id: u32, fraudulent: bool)`: Lets evaluators with
+    //     stake in the model's open evaluation rounds vote, weighted by that stake,
+    //     until the challenge's voting deadline.
+    // *   `resolve_challenge(challenge_id: u32)`: Upholds an unanswered challenge after
+    //     the response window, or decides an answered one by stake once the quorum is
+    //     reached or the voting deadline has passed. The loser forfeits their bond to the winner, and an upheld challenge
+    //     may delist the model.
+    // *   `set_challenge_parameters(bond: Balance, response_window: Timestamp, delist_on_fraud: bool)`:
+    //     Configures new challenges.
+    // *   `update_model(model_id: u32, new_hash: String, changelog: String, new_price: Balance)`:
+    //     Publishes a new version of a model. Only the model owner can call it.
+    // *   `upgrade_license(model_id: u32)`: Upgrades the caller's license to the latest
+    //     version, paying the difference between the two versions' prices, which
+    //     is split like a purchase.
+    // *   `get_model_versions(model_id: u32)` / `get_upgrade_price(account: AccountId, model_id: u32)`:
+    //     Version history and upgrade price views.
+    // *   `set_model_tags(model_id: u32, categories: Vec<String>, search_keys: Vec<Hash>)`:
+    //     Sets the categories and search key hashes a model is indexed under. Only
+    //     the model owner can call it.
+    // *   `list_models_by_category(category: String, offset: u32, limit: u32)` /
+    //     `list_models_by_owner(owner: AccountId, offset: u32, limit: u32)` /
+    //     `list_models_by_search_key(key: Hash, offset: u32, limit: u32)`: Paginated
+    //     discovery views, oldest model first.
+    // *   `set_subscription_price(model_id: u32, price_per_period: Balance)`: Offers
+    //     subscriptions to a model at a price per period (0 to stop offering them).
+    //     Only the model owner can call it.
+    // *   `subscribe(model_id: u32, periods: u32)`: Pays for `periods` subscription
+    //     periods, extending any active subscription. Payments are split like a
+    //     purchase.
+    // *   `set_subscription_period(period: Timestamp)`: Sets the length of a
+    //     subscription period.
+    // *   `get_subscription_expiry(account: AccountId, model_id: u32)` /
+    //     `has_active_access(account: AccountId, model_id: u32)`: Subscription views;
+    //     an account has access with either a valid license or an active subscription.
+    // *   `set_license_duration(model_id: u32, duration: Timestamp)`: Sets how long
+    //     newly purchased licenses stay valid (0 for perpetual licenses).
+    // *   `transfer_license(model_id: u32, to: AccountId)`: Transfers the caller's
+    //     license for a model to another account.
+    // *   `list_license(model_id: u32, price: Balance)`: Lists the caller's license for
+    //     resale.
+    // *   `cancel_license_listing(model_id: u32)`: Withdraws a resale listing.
+    // *   `buy_license(license_id: u32)`: Buys a listed license, paying the royalty
+    //     cut to the model owner and the rest to the seller.
+    // *   `set_resale_royalty_percentage(percentage: u8)`: Sets the share of resale
+    //     prices paid to the model owner.
+    // *   `get_license(license_id: u32)` / `get_licenses_of(account: AccountId)` /
+    //     `has_valid_license(account: AccountId, model_id: u32)`: License views.
+    // *   `register_privacy_budget(dataset_hash: String, total_epsilon: Epsilon)`:
+    //     Registers the total differential-privacy budget of a dataset. The caller
+    //     becomes the dataset owner.
+    // *   `set_dataset_access(dataset_hash: String, account: AccountId, allowed: bool)`:
+    //     Lets the dataset owner license or unlicense an account to spend the
+    //     dataset's privacy budget.
+    // *   `submit_evaluation(model_id: u32, evaluation_data_hash: String, predicted_output: String, epsilon: Epsilon)`:
+    //     Allows the dataset owner or a dataset licensee to submit evaluation data and
+    //     predictions for a specific AI model, spending `epsilon` of the dataset's
+    //     privacy budget.
+    // *   `record_training_use(model_id: u32, dataset_hash: String, epsilon: Epsilon)`:
+    //     Records that a model owner trained on a dataset they own or are licensed to
+    //     use, spending `epsilon` of its privacy budget.
+    // *   `get_privacy_budget(dataset_hash: String)` / `get_remaining_privacy_budget(dataset_hash: String)`:
+    //     Privacy budget views.
     // *   `start_performance_evaluation(model_id: u32)`:  Starts a round of
-    //     performance evaluation for a specific AI model.
+    //     performance evaluation for a registered AI model, with a submission
+    //     deadline `evaluation_period` from now.
     // *   `stake_for_evaluation(model_id: u32, evaluation_round: u32)`: Allows
     //     evaluators to stake tokens to participate in the evaluation round.
     // *   `submit_evaluation_result(model_id: u32, evaluation_round: u32, accuracy: u8)`:
-    //     Allows evaluators to submit their evaluation results (accuracy score).
-    // *   `finalize_evaluation(model_id: u32, evaluation_round: u32)`: Calculates
-    //     the rewards for evaluators based on their accuracy and distributes the
-    //     rewards.
-    // *   `set_evaluation_threshold(threshold: u8)`: Sets the minimum accuracy
-    //     threshold required for an evaluator to receive a reward.
+    //     Allows evaluators to submit their evaluation results (accuracy score, 0 to 100).
+    // *   `finalize_evaluation(model_id: u32, evaluation_round: u32)`: Computes the
+    //     stake-weighted consensus accuracy, slashes evaluators too far from it into
+    //     the bonus pool, and returns the other stakes plus a share of the bonus pool
+    //     weighted by stake and quadratic score.
+    //     After the deadline a round can be finalized with only the submitted
+    //     results; evaluators who never submitted get their stake back minus the
+    //     no-show penalty, which goes to the bonus pool. Each evaluator's influence
+    //     on the consensus and share of the bonus pool is scaled by their reputation.
+    //     A round with fewer than `min_evaluators` results is voided after the
+    //     deadline and every stake returned, and a round pays out at most
+    //     `max_bonus_payout_percentage` of the bonus pool.
+    // *   `get_model_reputation(model_id: u32)` / `get_evaluator_reputation(evaluator: AccountId)`:
+    //     Average verified accuracy of a model, and average deviation from consensus
+    //     of an evaluator, with the number of rounds each covers.
+    // *   `get_round_timing(model_id: u32, evaluation_round: u32)`: Returns the start
+    //     time and submission deadline of a round.
+    // *   `set_evaluation_period(period: Timestamp)` / `set_no_show_penalty_percentage(percentage: u8)`:
+    //     Set the submission window of new rounds and the penalty for not submitting.
+    // *   `set_evaluation_limits(min_evaluators: u32, max_bonus_payout_percentage: u8)`:
+    //     Sets the number of results a round needs to reach a consensus and the share
+    //     of the bonus pool a single round may pay out.
+    // *   `fund_evaluation_rewards(amount: Balance)`: Adds tokens to the evaluation
+    //     bonus pool.
+    // *   `set_scoring_parameters(slash_deviation: u8, slash_percentage: u8)`: Sets the
+    //     distance from consensus beyond which evaluators are slashed, and by how much.
     // *   `get_model_details(model_id: u32)`: Returns details about a specific AI
     //     model.
-    // *   `get_balance()`: Returns the token balance of the contract.
-    // *   `transfer(to: AccountId, value: Balance)`: Transfers tokens to another account.
-    // *   `total_supply()`: Returns the total supply of tokens in the contract.
+    // *   `token()` / `admin()`: Return the PSP22 token used for payments and the
+    //     marketplace admin.
+    // *   `stake_as_arbiter()`: Stakes tokens to join the arbiter panel that rules on
+    //     license revocation cases.
+    // *   `request_arbiter_unstake()` / `unstake_as_arbiter()`: Leave the arbiter panel.
+    //     The stake is returned once the unstake cooldown has passed and every case the
+    //     arbiter voted on is resolved.
+    // *   `open_revocation_case(model_id: u32, licensee: AccountId, evidence_uri: String)`:
+    //     Allows a model owner to open a terms-of-use violation case against a licensee,
+    //     escrowing the refund owed on revocation.
+    // *   `vote_on_revocation(case_id: u32, revoke: bool)`: Allows staked arbiters to
+    //     vote on an open revocation case until its voting deadline. The model owner and
+    //     the licensee cannot vote on their own case.
+    // *   `resolve_revocation_case(case_id: u32)`: Resolves a case once the arbitration
+    //     quorum is reached or the voting deadline has passed, invalidating the license
+    //     and refunding part of the price paid from escrow. Arbiters who voted against
+    //     the outcome are slashed in favour of those who voted for it.
+    // *   `get_revocation_history(account: AccountId)`: Returns all revocation cases
+    //     opened against an account.
 
     // Define data types for better readability and organization.
     type ModelId = u32;
     type EvaluationRound = u32;
-    type Balance = u128;
+    type CaseId = u32;
+    type LicenseId = u32;
+    type PurchaseId = u32;
+    type ChallengeId = u32;
+    type Epsilon = u64; // Differential-privacy epsilon in thousandths (1_000 = 1.0)
+    type StakeKey = (AccountId, ModelId, EvaluationRound); // An evaluator's stake in a round
+    type ResultKey = (ModelId, EvaluationRound, AccountId); // An evaluator's result in a round
+
+    const MAX_MODEL_TAGS: usize = 8; // Categories or search keys per model
+    const MAX_PAGE_SIZE: u32 = 50; // Models returned by a single discovery query
+    const MAX_CONTRIBUTORS: usize = 16; // Revenue-sharing contributors per model
+    const BASIS_POINTS: u128 = 10_000;
+    const REPUTATION_RAMP_ROUNDS: u32 = 5; // Finalized rounds before an evaluator's reputation counts in full
 
     // Define the storage struct.
     #[ink(storage)]
     pub struct DecentralizedAiMarketplace {
-        total_supply: Balance,
-        balances: Mapping<AccountId, Balance>,
+        token: AccountId, // PSP22 token used for purchases, stakes and rewards
+        admin: AccountId, // Only account allowed to change marketplace parameters
         models: Mapping<ModelId, AiModel>,
+        model_versions: Mapping<ModelId, Vec<ModelVersion>>, // Version history, oldest first
         model_count: ModelId,
-        licenses: Mapping<(AccountId, ModelId), bool>, //(owner, model_id)
+        category_index: Mapping<String, Vec<ModelId>>, // category -> models tagged with it
+        search_key_index: Mapping<Hash, Vec<ModelId>>, // search key hash -> models tagged with it
+        owner_index: Mapping<AccountId, Vec<ModelId>>, // owner -> registered models
+        model_contributors: Mapping<ModelId, Vec<Contributor>>, // Revenue shares, the owner keeps the rest
+        claimable_revenue: Mapping<AccountId, Balance>, // Released revenue not yet claimed
+        licenses: Mapping<LicenseId, License>,
+        license_count: LicenseId,
+        license_of: Mapping<(AccountId, ModelId), LicenseId>, //(holder, model_id) -> license
+        owned_licenses: Mapping<AccountId, Vec<LicenseId>>, // holder -> licenses, for enumeration
+        license_listings: Mapping<LicenseId, Balance>, // license -> resale price
+        license_cases: Mapping<LicenseId, CaseId>, // license -> open revocation case
+        license_purchases: Mapping<LicenseId, PurchaseId>, // license -> latest purchase from the model owner
+        resale_royalty_percentage: u8, // Share of a resale price paid to the model owner
+        purchases: Mapping<PurchaseId, Purchase>,
+        purchase_count: PurchaseId,
+        license_disputes: Mapping<LicenseId, PurchaseId>, // license -> open purchase dispute
+        dispute_window: Timestamp, // How long purchase payments stay in escrow
+        challenges: Mapping<ChallengeId, Challenge>,
+        challenge_count: ChallengeId,
+        model_challenges: Mapping<ModelId, ChallengeId>, // model -> open hash challenge
+        challenge_bond: Balance, // Bond posted by challengers, and matched by owners who respond
+        challenge_response_window: Timestamp, // How long the owner has to answer a challenge
+        delist_on_fraud: bool, // Whether upheld challenges delist the model
+        subscriptions: Mapping<(AccountId, ModelId), Timestamp>, //(subscriber, model_id) -> expiry
+        subscription_period: Timestamp, // Length of one subscription period
+        privacy_budgets: Mapping<String, PrivacyBudget>, // dataset hash -> epsilon budget
+        dataset_licensees: Mapping<(String, AccountId), bool>, // (dataset hash, account) -> may spend the budget
         evaluations: Mapping<(ModelId, EvaluationRound), Evaluation>,
-        evaluation_stake: Mapping<(AccountId, ModelId, EvaluationRound), Balance>,
-        evaluation_threshold: u8, // Minimum accuracy for reward
+        evaluation_stake: Mapping<StakeKey, Balance>,
+        evaluation_results: Mapping<ResultKey, u8>, // Submitted accuracy (0-100)
+        slash_deviation: u8, // Distance from consensus, in accuracy points, beyond which stakes are slashed
+        slash_percentage: u8, // Share of the stake slashed into the bonus pool
+        evaluation_bonus_pool: Balance, // Slashed stakes and funding, paid out to accurate evaluators
+        evaluation_period: Timestamp, // Submission window of new evaluation rounds
+        no_show_penalty_percentage: u8, // Share of the stake kept from evaluators who never submit
+        min_evaluators: u32, // Results a round needs before its consensus counts
+        max_bonus_payout_percentage: u8, // Share of the bonus pool a single round may pay out
+        model_reputations: Mapping<ModelId, Reputation>, // Consensus accuracy over finalized rounds
+        evaluator_reputations: Mapping<AccountId, Reputation>, // Deviation from consensus over finalized rounds
+        arbiter_stakes: Mapping<AccountId, Balance>,
+        arbiter_votes: Mapping<(CaseId, AccountId), bool>, // (case, arbiter) -> voted to revoke
+        arbiter_open_votes: Mapping<AccountId, u32>, // arbiter -> unresolved cases they voted on
+        arbiter_unstake_at: Mapping<AccountId, Timestamp>, // arbiter -> end of their unstake cooldown
+        arbiter_unstake_cooldown: Timestamp, // How long a leaving arbiter waits for their stake
+        arbiter_slash_percentage: u8, // Share of the stake lost by arbiters who vote against the outcome
+        revocation_cases: Mapping<CaseId, RevocationCase>,
+        revocation_case_count: CaseId,
+        revocation_history: Mapping<AccountId, Vec<CaseId>>, // licensee -> cases opened against them
+        arbitration_quorum: u32, // Votes required before a case can be resolved
+        arbitration_period: Timestamp, // How long disputes stay open for voting
+        revocation_refund_percentage: u8, // Share of the price refunded on revocation
     }
 
     // Define the AI Model struct.
     #[derive(scale::Encode, scale::Decode, Debug, Clone)]
     #[cfg_attr(
         feature = "std",
-        derive(scale_info::TypeInfo)
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
     )]
     pub struct AiModel {
         owner: AccountId,
@@ -98,27 +289,198 @@ mod decentralized_ai_marketplace {
         description: String,
         data_schema_hash: String, // IPFS hash of the data schema
         version: u32,
+        license_duration: Timestamp, // Validity of purchased licenses, 0 for perpetual
+        categories: Vec<String>,
+        search_keys: Vec<Hash>, // Hashes of normalized search terms
+        subscription_price: Balance, // Price per subscription period, 0 if subscriptions are not offered
+        delisted: bool, // Set when a hash challenge against the model is upheld
+    }
+
+    // Define the Contributor struct.
+    #[derive(scale::Encode, scale::Decode, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Contributor {
+        account: AccountId,
+        share_bps: u16, // Share of every purchase, in basis points
+    }
+
+    // Define the PrivacyBudget struct.
+    #[derive(scale::Encode, scale::Decode, Debug, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct PrivacyBudget {
+        owner: AccountId,
+        total_epsilon: Epsilon,
+        spent_epsilon: Epsilon,
+    }
+
+    // Running totals behind a model's or an evaluator's reputation.
+    #[derive(scale::Encode, scale::Decode, Debug, Clone, Default)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Reputation {
+        rounds: u32,
+        total: u64, // Sum of consensus accuracies (models) or deviations (evaluators)
+    }
+
+    // Define the ModelVersion struct.
+    #[derive(scale::Encode, scale::Decode, Debug, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct ModelVersion {
+        version: u32,
+        model_hash: String,
+        changelog: String,
+        price: Balance,
+        released_at: Timestamp,
+    }
+
+    // Define the License struct.
+    #[derive(scale::Encode, scale::Decode, Debug, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct License {
+        model_id: ModelId,
+        holder: AccountId,
+        version: u32, // Model version the license covers
+        expires_at: Option<Timestamp>, // None for perpetual licenses
     }
 
     // Define the Evaluation struct.
     #[derive(scale::Encode, scale::Decode, Debug, Clone)]
     #[cfg_attr(
         feature = "std",
-        derive(scale_info::TypeInfo)
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
     )]
     pub struct Evaluation {
         evaluators: Vec<AccountId>,
-        results: Mapping<AccountId, u8>, // Evaluator -> Accuracy (0-100)
         finalized: bool,
+        started_at: Timestamp,
+        deadline: Timestamp, // No stakes or results are accepted from this time on
     }
 
-    #[ink(event)]
-    pub struct Transfer {
-        #[ink(topic)]
-        from: Option<AccountId>,
-        #[ink(topic)]
-        to: Option<AccountId>,
-        value: Balance,
+    // Lifecycle of an escrowed purchase payment.
+    #[derive(scale::Encode, scale::Decode, Debug, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum PurchaseStatus {
+        Escrowed,
+        Disputed,
+        Released,
+        Refunded,
+    }
+
+    // Grounds on which a buyer can dispute a purchase.
+    #[derive(scale::Encode, scale::Decode, Debug, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum DisputeReason {
+        HashMismatch, // The artifact does not match `model_hash`
+        ModelInaccessible, // The artifact cannot be retrieved
+        Other,
+    }
+
+    // Grounds on which a license holder can challenge a model.
+    #[derive(scale::Encode, scale::Decode, Debug, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum ChallengeReason {
+        HashMismatch, // The artifact does not match `model_hash`
+        SchemaMismatch, // The artifact does not match `data_schema_hash`
+    }
+
+    // Lifecycle of a hash challenge.
+    #[derive(scale::Encode, scale::Decode, Debug, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum ChallengeStatus {
+        AwaitingResponse,
+        Voting,
+        Upheld,
+        Rejected,
+    }
+
+    // Define the Challenge struct.
+    #[derive(scale::Encode, scale::Decode, Debug, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Challenge {
+        model_id: ModelId,
+        challenger: AccountId,
+        reason: ChallengeReason,
+        evidence_uri: String,
+        bond: Balance,
+        respond_by: Timestamp, // End of the owner's response window
+        proof_uri: Option<String>, // Availability proof posted by the owner
+        vote_by: Timestamp, // End of the voting period, once the owner has responded
+        status: ChallengeStatus,
+        voters: Vec<AccountId>,
+        stake_for_challenger: Balance,
+        stake_for_owner: Balance,
+    }
+
+    // Define the Purchase struct.
+    #[derive(scale::Encode, scale::Decode, Debug, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Purchase {
+        model_id: ModelId,
+        license_id: LicenseId,
+        buyer: AccountId,
+        seller: AccountId,
+        amount: Balance,
+        release_at: Timestamp, // End of the dispute window
+        status: PurchaseStatus,
+        dispute_reason: Option<DisputeReason>,
+        evidence_uri: String,
+        vote_by: Timestamp, // End of the dispute's voting period, once disputed
+        voters: Vec<AccountId>,
+        stake_for_buyer: Balance,
+        stake_for_seller: Balance,
+    }
+
+    // Define the RevocationCase struct.
+    #[derive(scale::Encode, scale::Decode, Debug, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct RevocationCase {
+        model_id: ModelId,
+        license_id: LicenseId,
+        licensee: AccountId,
+        evidence_uri: String, // IPFS URI of the violation evidence
+        opened_at: Timestamp,
+        vote_by: Timestamp, // End of the voting period
+        refund: Balance, // Escrowed by the model owner; paid to the licensee on revocation
+        voters: Vec<AccountId>,
+        votes_for: u32,
+        votes_against: u32,
+        resolved: bool,
+        revoked: bool,
     }
 
     #[ink(event)]
@@ -129,11 +491,131 @@ mod decentralized_ai_marketplace {
         model_hash: String,
     }
 
+    #[ink(event)]
+    pub struct ModelUpdated {
+        #[ink(topic)]
+        model_id: ModelId,
+        version: u32,
+        model_hash: String,
+        price: Balance,
+    }
+
+    #[ink(event)]
+    pub struct LicenseUpgraded {
+        #[ink(topic)]
+        license_id: LicenseId,
+        from_version: u32,
+        to_version: u32,
+        paid: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Subscribed {
+        #[ink(topic)]
+        model_id: ModelId,
+        #[ink(topic)]
+        subscriber: AccountId,
+        periods: u32,
+        paid: Balance,
+        expires_at: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct ModelTagsUpdated {
+        #[ink(topic)]
+        model_id: ModelId,
+        categories: Vec<String>,
+        search_keys: Vec<Hash>,
+    }
+
     #[ink(event)]
     pub struct ModelPurchased {
         #[ink(topic)]
         model_id: ModelId,
         buyer: AccountId,
+        purchase_id: PurchaseId,
+    }
+
+    #[ink(event)]
+    pub struct PaymentReleased {
+        #[ink(topic)]
+        purchase_id: PurchaseId,
+        seller: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct RevenueClaimed {
+        #[ink(topic)]
+        account: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct DisputeOpened {
+        #[ink(topic)]
+        purchase_id: PurchaseId,
+        reason: DisputeReason,
+        evidence_uri: String,
+    }
+
+    #[ink(event)]
+    pub struct DisputeResolved {
+        #[ink(topic)]
+        purchase_id: PurchaseId,
+        refunded: bool,
+    }
+
+    #[ink(event)]
+    pub struct ChallengeOpened {
+        #[ink(topic)]
+        challenge_id: ChallengeId,
+        #[ink(topic)]
+        model_id: ModelId,
+        challenger: AccountId,
+        reason: ChallengeReason,
+        respond_by: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct ChallengeResponded {
+        #[ink(topic)]
+        challenge_id: ChallengeId,
+        proof_uri: String,
+    }
+
+    #[ink(event)]
+    pub struct ChallengeResolved {
+        #[ink(topic)]
+        challenge_id: ChallengeId,
+        upheld: bool,
+        delisted: bool,
+    }
+
+    #[ink(event)]
+    pub struct LicenseTransferred {
+        #[ink(topic)]
+        license_id: LicenseId,
+        model_id: ModelId,
+        from: AccountId,
+        to: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct LicenseListed {
+        #[ink(topic)]
+        license_id: LicenseId,
+        price: Balance,
+    }
+
+    #[ink(event)]
+    pub struct LicenseSold {
+        #[ink(topic)]
+        license_id: LicenseId,
+        seller: AccountId,
+        buyer: AccountId,
+        price: Balance,
+        royalty: Balance,
     }
 
     #[ink(event)]
@@ -141,6 +623,37 @@ mod decentralized_ai_marketplace {
         #[ink(topic)]
         model_id: ModelId,
         round: EvaluationRound,
+        deadline: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct PrivacyBudgetRegistered {
+        dataset_hash: String,
+        #[ink(topic)]
+        owner: AccountId,
+        total_epsilon: Epsilon,
+    }
+
+    #[ink(event)]
+    pub struct DatasetAccessChanged {
+        dataset_hash: String,
+        #[ink(topic)]
+        account: AccountId,
+        allowed: bool,
+    }
+
+    #[ink(event)]
+    pub struct PrivacyBudgetSpent {
+        dataset_hash: String,
+        #[ink(topic)]
+        model_id: ModelId,
+        epsilon: Epsilon,
+        remaining: Epsilon,
+    }
+
+    #[ink(event)]
+    pub struct PrivacyBudgetExhausted {
+        dataset_hash: String,
     }
 
     #[ink(event)]
@@ -148,25 +661,106 @@ mod decentralized_ai_marketplace {
         #[ink(topic)]
         model_id: ModelId,
         round: EvaluationRound,
+        consensus: u8,
+        bonus_paid: Balance,
+    }
+
+    #[ink(event)]
+    pub struct EvaluationVoided {
+        #[ink(topic)]
+        model_id: ModelId,
+        round: EvaluationRound,
+        results: u32,
+    }
+
+    #[ink(event)]
+    pub struct EvaluatorSlashed {
+        #[ink(topic)]
+        evaluator: AccountId,
+        model_id: ModelId,
+        round: EvaluationRound,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct RevocationCaseOpened {
+        #[ink(topic)]
+        case_id: CaseId,
+        #[ink(topic)]
+        licensee: AccountId,
+        model_id: ModelId,
+        evidence_uri: String,
+    }
+
+    #[ink(event)]
+    pub struct RevocationCaseResolved {
+        #[ink(topic)]
+        case_id: CaseId,
+        revoked: bool,
+        refund: Balance,
     }
 
 
     impl DecentralizedAiMarketplace {
         #[ink(constructor)]
-        pub fn new(initial_supply: Balance) -> Self {
-            let caller = Self::env().caller();
-            let mut balances = Mapping::new();
-            balances.insert(caller, &initial_supply);
-
+        pub fn new(token: AccountId) -> Self {
             Self {
-                total_supply: initial_supply,
-                balances,
+                token,
+                admin: Self::env().caller(),
                 models: Mapping::new(),
+                model_versions: Mapping::new(),
                 model_count: 0,
+                category_index: Mapping::new(),
+                search_key_index: Mapping::new(),
+                owner_index: Mapping::new(),
+                model_contributors: Mapping::new(),
+                claimable_revenue: Mapping::new(),
                 licenses: Mapping::new(),
+                license_count: 0,
+                license_of: Mapping::new(),
+                owned_licenses: Mapping::new(),
+                license_listings: Mapping::new(),
+                license_cases: Mapping::new(),
+                license_purchases: Mapping::new(),
+                resale_royalty_percentage: 10, // Default: 10% of every resale goes to the model owner
+                purchases: Mapping::new(),
+                purchase_count: 0,
+                license_disputes: Mapping::new(),
+                dispute_window: 3 * 24 * 60 * 60 * 1000, // Default: three days
+                challenges: Mapping::new(),
+                challenge_count: 0,
+                model_challenges: Mapping::new(),
+                challenge_bond: 100, // Default: 100 tokens
+                challenge_response_window: 2 * 24 * 60 * 60 * 1000, // Default: two days
+                delist_on_fraud: true,
+                subscriptions: Mapping::new(),
+                subscription_period: 30 * 24 * 60 * 60 * 1000, // Default: thirty days
+                privacy_budgets: Mapping::new(),
+                dataset_licensees: Mapping::new(),
                 evaluations: Mapping::new(),
                 evaluation_stake: Mapping::new(),
-                evaluation_threshold: 75, // Default: 75% accuracy required for reward
+                evaluation_results: Mapping::new(),
+                slash_deviation: 20, // Default: slash reports more than 20 points from consensus
+                slash_percentage: 50, // Default: slash half the stake
+                evaluation_bonus_pool: 0,
+                evaluation_period: 7 * 24 * 60 * 60 * 1000, // Default: one week
+                no_show_penalty_percentage: 20, // Default: a fifth of the stake
+                min_evaluators: 3, // Default: three results per round
+                max_bonus_payout_percentage: 50, // Default: half the pool per round
+                model_reputations: Mapping::new(),
+                evaluator_reputations: Mapping::new(),
+                arbiter_stakes: Mapping::new(),
+                arbiter_votes: Mapping::new(),
+                arbiter_open_votes: Mapping::new(),
+                arbiter_unstake_at: Mapping::new(),
+                arbiter_unstake_cooldown: 7 * 24 * 60 * 60 * 1000, // Default: one week
+                arbiter_slash_percentage: 20, // Default: a fifth of the stake
+                revocation_cases: Mapping::new(),
+                revocation_case_count: 0,
+                revocation_history: Mapping::new(),
+                arbitration_quorum: 3, // Default: 3 arbiter votes per case
+                arbitration_period: 7 * 24 * 60 * 60 * 1000, // Default: one week
+                revocation_refund_percentage: 50, // Default: half the price is refunded
             }
         }
 
@@ -177,8 +771,16 @@ mod decentralized_ai_marketplace {
             price: Balance,
             description: String,
             data_schema_hash: String,
+            contributors: Vec<Contributor>,
         ) -> Result<(), String> {
             let caller = self.env().caller();
+            if contributors.len() > MAX_CONTRIBUTORS {
+                return Err("Too many contributors".into());
+            }
+            let total_bps: u128 = contributors.iter().map(|contributor| contributor.share_bps as u128).sum();
+            if total_bps > BASIS_POINTS {
+                return Err("Contributor shares exceed 100%".into());
+            }
             self.model_count += 1;
             let model_id = self.model_count;
 
@@ -189,9 +791,25 @@ mod decentralized_ai_marketplace {
                 description,
                 data_schema_hash,
                 version: 1,
+                license_duration: 0,
+                categories: Vec::new(),
+                search_keys: Vec::new(),
+                subscription_price: 0,
+                delisted: false,
             };
 
             self.models.insert(model_id, &model);
+            self.model_contributors.insert(model_id, &contributors);
+            let mut owned_models = self.owner_index.get(caller).unwrap_or_default();
+            owned_models.push(model_id);
+            self.owner_index.insert(caller, &owned_models);
+            self.model_versions.insert(model_id, &vec![ModelVersion {
+                version: 1,
+                model_hash: model_hash.clone(),
+                changelog: String::from("Initial release"),
+                price,
+                released_at: self.env().block_timestamp(),
+            }]);
             self.env().emit_event(ModelRegistered {
                 model_id,
                 owner: caller,
@@ -205,351 +823,2698 @@ mod decentralized_ai_marketplace {
         pub fn purchase_model(&mut self, model_id: ModelId) -> Result<(), String> {
             let caller = self.env().caller();
             let model = self.models.get(model_id).ok_or("Model not found")?;
+            if model.delisted {
+                return Err("Model has been delisted".into());
+            }
             let price = model.price;
 
-            // Transfer tokens from buyer to seller.
-            self.transfer_from(caller, model.owner, price)?;
+            let existing = self.license_of.get((caller, model_id));
+            if existing.is_some() && self.has_valid_license(caller, model_id) {
+                return Err("License already held".into());
+            }
+
+            // Hold the payment in escrow until the dispute window has passed.
+            self.transfer_from(caller, self.env().account_id(), price)?;
+
+            let now = self.env().block_timestamp();
+            let expires_at = if model.license_duration == 0 {
+                None
+            } else {
+                Some(now + model.license_duration)
+            };
+            let license_id = if let Some(license_id) = existing {
+                // Renew the buyer's expired license at the latest version.
+                let mut license = self.licenses.get(license_id).ok_or("License not found")?;
+                license.version = model.version;
+                license.expires_at = expires_at;
+                self.licenses.insert(license_id, &license);
+                license_id
+            } else {
+                // Grant license to the buyer.
+                self.license_count += 1;
+                let license_id = self.license_count;
+                self.licenses.insert(license_id, &License { model_id, holder: caller, version: model.version, expires_at });
+                self.add_license_to_holder(caller, model_id, license_id);
+                license_id
+            };
 
-            // Grant license to the buyer.
-            self.licenses.insert((caller, model_id), &true);
+            self.purchase_count += 1;
+            let purchase_id = self.purchase_count;
+            self.purchases.insert(purchase_id, &Purchase {
+                model_id,
+                license_id,
+                buyer: caller,
+                seller: model.owner,
+                amount: price,
+                release_at: now + self.dispute_window,
+                status: PurchaseStatus::Escrowed,
+                dispute_reason: None,
+                evidence_uri: String::new(),
+                vote_by: 0,
+                voters: vec![],
+                stake_for_buyer: 0,
+                stake_for_seller: 0,
+            });
+            self.license_purchases.insert(license_id, &purchase_id);
             self.env().emit_event(ModelPurchased {
                 model_id,
                 buyer: caller,
+                purchase_id,
             });
 
             Ok(())
         }
 
         #[ink(message)]
-        pub fn submit_evaluation(
-            &self,
-            model_id: ModelId,
-            evaluation_data_hash: String,
-            predicted_output: String,
-        ) -> Result<(), String> {
-            // Simulate submitting data for evaluation, applying differential privacy
-            // through an off-chain service.
-            let differentially_private_data =
-                self.apply_differential_privacy(evaluation_data_hash)?;
+        pub fn release_payment(&mut self, purchase_id: PurchaseId) -> Result<(), String> {
+            let mut purchase = self.purchases.get(purchase_id).ok_or("Purchase not found")?;
+            if purchase.status != PurchaseStatus::Escrowed {
+                return Err("Purchase payment is not in escrow".into());
+            }
+            if self.env().block_timestamp() < purchase.release_at {
+                return Err("Dispute window has not passed".into());
+            }
 
-            // In a real implementation, this would send the differentially private
-            // data to an off-chain service for processing and comparison with the
-            // predicted output.
-            ink::env::debug_println!(
-                "Data submitted for evaluation (with differential privacy applied): {:?}",
-                differentially_private_data
-            );
+            self.credit_revenue(purchase.model_id, purchase.seller, purchase.amount);
+            purchase.status = PurchaseStatus::Released;
+            self.purchases.insert(purchase_id, &purchase);
 
-            ink::env::debug_println!(
-                "Predicted output submitted by user: {:?}",
-                predicted_output
-            );
+            self.env().emit_event(PaymentReleased {
+                purchase_id,
+                seller: purchase.seller,
+                amount: purchase.amount,
+            });
 
-            //The processing logic is done off-chain so we'll just return Ok
             Ok(())
         }
 
-        // Simulates differential privacy application (for demonstration).  In a real
-        // application, this would interact with an off-chain service.
-        fn apply_differential_privacy(&self, data_hash: String) -> Result<String, String> {
-            // Replace with actual logic or interaction with an external service.
-            // This is a placeholder to demonstrate the concept.
-            Ok(format!("Differentially private version of: {}", data_hash))
-        }
-
-
         #[ink(message)]
-        pub fn start_performance_evaluation(&mut self, model_id: ModelId) -> Result<(), String> {
+        pub fn open_dispute(
+            &mut self,
+            purchase_id: PurchaseId,
+            reason: DisputeReason,
+            evidence_uri: String,
+        ) -> Result<(), String> {
             let caller = self.env().caller();
-            let mut evaluation_round = 1;
-
-            if let Some(evaluation) = self.evaluations.get(&(model_id, evaluation_round)){
-                if !evaluation.finalized {
-                    return Err("Previous Evaluation is still running!".into())
-                }
-                //Find the latest round.
-                while self.evaluations.contains(&(model_id, evaluation_round + 1)){
-                    evaluation_round += 1;
-                }
-                evaluation_round += 1;
+            let mut purchase = self.purchases.get(purchase_id).ok_or("Purchase not found")?;
+            if purchase.buyer != caller {
+                return Err("Only the buyer can dispute a purchase".into());
+            }
+            if purchase.status != PurchaseStatus::Escrowed {
+                return Err("Purchase payment is not in escrow".into());
+            }
+            if self.env().block_timestamp() >= purchase.release_at {
+                return Err("Dispute window has passed".into());
+            }
+            if self.license_of.get((caller, purchase.model_id)) != Some(purchase.license_id) {
+                return Err("Buyer no longer holds the license".into());
+            }
+            // A license under a revocation case could otherwise be refunded twice.
+            if self.license_cases.contains(purchase.license_id) {
+                return Err("License is under a revocation case".into());
             }
 
-            let evaluation = Evaluation{
-                evaluators: vec![],
-                results: Mapping::new(),
-                finalized: false
-            };
+            purchase.status = PurchaseStatus::Disputed;
+            purchase.dispute_reason = Some(reason);
+            purchase.evidence_uri = evidence_uri.clone();
+            purchase.vote_by = self.env().block_timestamp() + self.arbitration_period;
+            self.purchases.insert(purchase_id, &purchase);
+            // Freeze the license until the dispute is resolved.
+            self.license_disputes.insert(purchase.license_id, &purchase_id);
+            self.license_listings.remove(purchase.license_id);
+
+            self.env().emit_event(DisputeOpened {
+                purchase_id,
+                reason,
+                evidence_uri,
+            });
 
-            self.evaluations.insert((model_id, evaluation_round), &evaluation);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn vote_on_dispute(&mut self, purchase_id: PurchaseId, refund_buyer: bool) -> Result<(), String> {
+            let caller = self.env().caller();
+            let mut purchase = self.purchases.get(purchase_id).ok_or("Purchase not found")?;
+            if purchase.status != PurchaseStatus::Disputed {
+                return Err("Purchase is not disputed".into());
+            }
+            if self.env().block_timestamp() >= purchase.vote_by {
+                return Err("Voting period has ended".into());
+            }
+            let stake = self.stake_on_model(caller, purchase.model_id);
+            if stake == 0 {
+                return Err("Only evaluators staked on the model can vote".into());
+            }
+            if caller == purchase.buyer || caller == purchase.seller {
+                return Err("Parties cannot vote on their own dispute".into());
+            }
+            if purchase.voters.contains(&caller) {
+                return Err("Evaluator has already voted".into());
+            }
+
+            purchase.voters.push(caller);
+            if refund_buyer {
+                purchase.stake_for_buyer += stake;
+            } else {
+                purchase.stake_for_seller += stake;
+            }
+            self.purchases.insert(purchase_id, &purchase);
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn resolve_dispute(&mut self, purchase_id: PurchaseId) -> Result<(), String> {
+            let mut purchase = self.purchases.get(purchase_id).ok_or("Purchase not found")?;
+            if purchase.status != PurchaseStatus::Disputed {
+                return Err("Purchase is not disputed".into());
+            }
+            // After the voting deadline the dispute is decided by the votes cast, so too few
+            // voters cannot leave the payment in escrow for good.
+            if (purchase.voters.len() as u32) < self.arbitration_quorum && self.env().block_timestamp() < purchase.vote_by {
+                return Err("Arbitration quorum not reached".into());
+            }
+
+            self.license_disputes.remove(purchase.license_id);
+            let refunded = purchase.stake_for_buyer > purchase.stake_for_seller;
+            if refunded {
+                // Burn the license and return the payment to the buyer.
+                if self.license_of.get((purchase.buyer, purchase.model_id)) == Some(purchase.license_id) {
+                    self.remove_license_from_holder(purchase.buyer, purchase.model_id, purchase.license_id);
+                    self.licenses.remove(purchase.license_id);
+                }
+                self.transfer_from(self.env().account_id(), purchase.buyer, purchase.amount)?;
+                purchase.status = PurchaseStatus::Refunded;
+            } else {
+                self.credit_revenue(purchase.model_id, purchase.seller, purchase.amount);
+                purchase.status = PurchaseStatus::Released;
+            }
+            self.purchases.insert(purchase_id, &purchase);
+
+            self.env().emit_event(DisputeResolved {
+                purchase_id,
+                refunded,
+            });
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn claim_revenue(&mut self) -> Result<(), String> {
+            let caller = self.env().caller();
+            let amount = self.claimable_revenue.get(caller).unwrap_or_default();
+            if amount == 0 {
+                return Err("No revenue to claim".into());
+            }
+
+            self.claimable_revenue.remove(caller);
+            self.transfer_from(self.env().account_id(), caller, amount)?;
+
+            self.env().emit_event(RevenueClaimed {
+                account: caller,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_claimable_revenue(&self, account: AccountId) -> Balance {
+            self.claimable_revenue.get(account).unwrap_or_default()
+        }
+
+        #[ink(message)]
+        pub fn get_contributors(&self, model_id: ModelId) -> Vec<Contributor> {
+            self.model_contributors.get(model_id).unwrap_or_default()
+        }
+
+        // Credits revenue the contract already holds to the model's contributors by their
+        // shares, and the rest (including rounding dust) to the owner.
+        fn credit_revenue(&mut self, model_id: ModelId, owner: AccountId, amount: Balance) {
+            let mut remaining = amount;
+            for contributor in self.model_contributors.get(model_id).unwrap_or_default() {
+                let share = amount * contributor.share_bps as u128 / BASIS_POINTS;
+                let balance = self.claimable_revenue.get(contributor.account).unwrap_or_default();
+                self.claimable_revenue.insert(contributor.account, &(balance + share));
+                remaining -= share;
+            }
+            let balance = self.claimable_revenue.get(owner).unwrap_or_default();
+            self.claimable_revenue.insert(owner, &(balance + remaining));
+        }
+
+        #[ink(message)]
+        pub fn set_dispute_window(&mut self, window: Timestamp) -> Result<(), String> {
+            self.ensure_admin()?;
+            self.dispute_window = window;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_purchase(&self, purchase_id: PurchaseId) -> Option<Purchase> {
+            self.purchases.get(purchase_id)
+        }
+
+        #[ink(message)]
+        pub fn open_challenge(&mut self, model_id: ModelId, reason: ChallengeReason, evidence_uri: String) -> Result<(), String> {
+            let caller = self.env().caller();
+            let model = self.models.get(model_id).ok_or("Model not found")?;
+            if model.delisted {
+                return Err("Model has been delisted".into());
+            }
+            if !self.has_valid_license(caller, model_id) {
+                return Err("Only license holders can challenge a model".into());
+            }
+            if self.model_challenges.contains(model_id) {
+                return Err("Model already has an open challenge".into());
+            }
+
+            let bond = self.challenge_bond;
+            self.transfer_from(caller, self.env().account_id(), bond)?;
+
+            self.challenge_count += 1;
+            let challenge_id = self.challenge_count;
+            let respond_by = self.env().block_timestamp() + self.challenge_response_window;
+            self.challenges.insert(challenge_id, &Challenge {
+                model_id,
+                challenger: caller,
+                reason,
+                evidence_uri,
+                bond,
+                respond_by,
+                proof_uri: None,
+                vote_by: 0,
+                status: ChallengeStatus::AwaitingResponse,
+                voters: vec![],
+                stake_for_challenger: 0,
+                stake_for_owner: 0,
+            });
+            self.model_challenges.insert(model_id, &challenge_id);
+
+            self.env().emit_event(ChallengeOpened {
+                challenge_id,
+                model_id,
+                challenger: caller,
+                reason,
+                respond_by,
+            });
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn respond_to_challenge(&mut self, challenge_id: ChallengeId, proof_uri: String) -> Result<(), String> {
+            let caller = self.env().caller();
+            let mut challenge = self.challenges.get(challenge_id).ok_or("Challenge not found")?;
+            let model = self.models.get(challenge.model_id).ok_or("Model not found")?;
+            if model.owner != caller {
+                return Err("Only the model owner can respond to a challenge".into());
+            }
+            if challenge.status != ChallengeStatus::AwaitingResponse {
+                return Err("Challenge is not awaiting a response".into());
+            }
+            if self.env().block_timestamp() >= challenge.respond_by {
+                return Err("Response window has passed".into());
+            }
+
+            // The owner matches the challenger's bond so that either side has something to lose.
+            self.transfer_from(caller, self.env().account_id(), challenge.bond)?;
+            challenge.proof_uri = Some(proof_uri.clone());
+            challenge.vote_by = self.env().block_timestamp() + self.arbitration_period;
+            challenge.status = ChallengeStatus::Voting;
+            self.challenges.insert(challenge_id, &challenge);
+
+            self.env().emit_event(ChallengeResponded {
+                challenge_id,
+                proof_uri,
+            });
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn vote_on_challenge(&mut self, challenge_id: ChallengeId, fraudulent: bool) -> Result<(), String> {
+            let caller = self.env().caller();
+            let mut challenge = self.challenges.get(challenge_id).ok_or("Challenge not found")?;
+            if challenge.status != ChallengeStatus::Voting {
+                return Err("Challenge is not open for voting".into());
+            }
+            if self.env().block_timestamp() >= challenge.vote_by {
+                return Err("Voting period has ended".into());
+            }
+            let stake = self.stake_on_model(caller, challenge.model_id);
+            if stake == 0 {
+                return Err("Only evaluators staked on the model can vote".into());
+            }
+            let model = self.models.get(challenge.model_id).ok_or("Model not found")?;
+            if caller == challenge.challenger || caller == model.owner {
+                return Err("Parties cannot vote on their own challenge".into());
+            }
+            if challenge.voters.contains(&caller) {
+                return Err("Evaluator has already voted".into());
+            }
+
+            challenge.voters.push(caller);
+            if fraudulent {
+                challenge.stake_for_challenger += stake;
+            } else {
+                challenge.stake_for_owner += stake;
+            }
+            self.challenges.insert(challenge_id, &challenge);
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn resolve_challenge(&mut self, challenge_id: ChallengeId) -> Result<(), String> {
+            let mut challenge = self.challenges.get(challenge_id).ok_or("Challenge not found")?;
+            let upheld = match challenge.status {
+                // An owner who cannot produce the artifact in time loses by default.
+                ChallengeStatus::AwaitingResponse => {
+                    if self.env().block_timestamp() < challenge.respond_by {
+                        return Err("Response window has not passed".into());
+                    }
+                    true
+                }
+                // After the voting deadline the challenge is decided by the stake that voted.
+                ChallengeStatus::Voting => {
+                    if (challenge.voters.len() as u32) < self.arbitration_quorum && self.env().block_timestamp() < challenge.vote_by {
+                        return Err("Arbitration quorum not reached".into());
+                    }
+                    challenge.stake_for_challenger > challenge.stake_for_owner
+                }
+                _ => return Err("Challenge already resolved".into()),
+            };
+
+            // The winner takes both bonds; an unanswered challenge only holds the challenger's.
+            let pot = if challenge.proof_uri.is_some() { challenge.bond * 2 } else { challenge.bond };
+            let mut model = self.models.get(challenge.model_id).ok_or("Model not found")?;
+            let winner = if upheld { challenge.challenger } else { model.owner };
+            self.transfer_from(self.env().account_id(), winner, pot)?;
+
+            let delisted = upheld && self.delist_on_fraud;
+            if delisted {
+                model.delisted = true;
+                self.models.insert(challenge.model_id, &model);
+            }
+            challenge.status = if upheld { ChallengeStatus::Upheld } else { ChallengeStatus::Rejected };
+            self.challenges.insert(challenge_id, &challenge);
+            self.model_challenges.remove(challenge.model_id);
+
+            self.env().emit_event(ChallengeResolved {
+                challenge_id,
+                upheld,
+                delisted,
+            });
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_challenge_parameters(&mut self, bond: Balance, response_window: Timestamp, delist_on_fraud: bool) -> Result<(), String> {
+            self.ensure_admin()?;
+            if response_window == 0 {
+                return Err("Response window must be positive".into());
+            }
+            self.challenge_bond = bond;
+            self.challenge_response_window = response_window;
+            self.delist_on_fraud = delist_on_fraud;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_challenge(&self, challenge_id: ChallengeId) -> Option<Challenge> {
+            self.challenges.get(challenge_id)
+        }
+
+        // Total the account has at stake in the model's open evaluation rounds. Stakes of
+        // finalized rounds have been returned and no longer count.
+        fn stake_on_model(&self, account: AccountId, model_id: ModelId) -> Balance {
+            let mut evaluation_round = 1;
+            let mut total: Balance = 0;
+            while let Some(evaluation) = self.evaluations.get((model_id, evaluation_round)) {
+                if !evaluation.finalized {
+                    total += self.evaluation_stake.get((account, model_id, evaluation_round)).unwrap_or_default();
+                }
+                evaluation_round += 1;
+            }
+            total
+        }
+
+        #[ink(message)]
+        pub fn update_model(
+            &mut self,
+            model_id: ModelId,
+            new_hash: String,
+            changelog: String,
+            new_price: Balance,
+        ) -> Result<(), String> {
+            let caller = self.env().caller();
+            let mut model = self.models.get(model_id).ok_or("Model not found")?;
+            if model.owner != caller {
+                return Err("Only the model owner can update the model".into());
+            }
+
+            model.version += 1;
+            model.model_hash = new_hash.clone();
+            model.price = new_price;
+            self.models.insert(model_id, &model);
+
+            let mut versions = self.model_versions.get(model_id).unwrap_or_default();
+            versions.push(ModelVersion {
+                version: model.version,
+                model_hash: new_hash.clone(),
+                changelog,
+                price: new_price,
+                released_at: self.env().block_timestamp(),
+            });
+            self.model_versions.insert(model_id, &versions);
+
+            self.env().emit_event(ModelUpdated {
+                model_id,
+                version: model.version,
+                model_hash: new_hash,
+                price: new_price,
+            });
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn upgrade_license(&mut self, model_id: ModelId) -> Result<(), String> {
+            let caller = self.env().caller();
+            let model = self.models.get(model_id).ok_or("Model not found")?;
+            let license_id = self.license_of.get((caller, model_id)).ok_or("License not found")?;
+            if !self.has_valid_license(caller, model_id) {
+                return Err("License has expired".into());
+            }
+            let mut license = self.licenses.get(license_id).ok_or("License not found")?;
+            if license.version >= model.version {
+                return Err("License already covers the latest version".into());
+            }
+
+            // Holders only pay the difference between the version they own and the latest one.
+            let paid = self.get_upgrade_price(caller, model_id).unwrap_or(0);
+            if paid > 0 {
+                self.transfer_from(caller, self.env().account_id(), paid)?;
+                self.credit_revenue(model_id, model.owner, paid);
+            }
+
+            let from_version = license.version;
+            license.version = model.version;
+            self.licenses.insert(license_id, &license);
+
+            self.env().emit_event(LicenseUpgraded {
+                license_id,
+                from_version,
+                to_version: model.version,
+                paid,
+            });
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_model_versions(&self, model_id: ModelId) -> Vec<ModelVersion> {
+            self.model_versions.get(model_id).unwrap_or_default()
+        }
+
+        #[ink(message)]
+        pub fn get_upgrade_price(&self, account: AccountId, model_id: ModelId) -> Option<Balance> {
+            let model = self.models.get(model_id)?;
+            let license = self.licenses.get(self.license_of.get((account, model_id))?)?;
+            let versions = self.model_versions.get(model_id)?;
+            let owned_price = versions.iter().find(|version| version.version == license.version)?.price;
+            Some(model.price.saturating_sub(owned_price))
+        }
+
+        #[ink(message)]
+        pub fn set_model_tags(&mut self, model_id: ModelId, categories: Vec<String>, search_keys: Vec<Hash>) -> Result<(), String> {
+            let caller = self.env().caller();
+            let mut model = self.models.get(model_id).ok_or("Model not found")?;
+            if model.owner != caller {
+                return Err("Only the model owner can set model tags".into());
+            }
+            if categories.len() > MAX_MODEL_TAGS || search_keys.len() > MAX_MODEL_TAGS {
+                return Err("Too many tags".into());
+            }
+
+            // Drop the model from the indexes of its old tags.
+            for category in &model.categories {
+                let mut ids = self.category_index.get(category).unwrap_or_default();
+                ids.retain(|id| *id != model_id);
+                self.category_index.insert(category, &ids);
+            }
+            for key in &model.search_keys {
+                let mut ids = self.search_key_index.get(key).unwrap_or_default();
+                ids.retain(|id| *id != model_id);
+                self.search_key_index.insert(key, &ids);
+            }
+
+            // Index the model under its new tags, skipping duplicates.
+            let mut unique_categories: Vec<String> = Vec::new();
+            for category in categories {
+                if !unique_categories.contains(&category) {
+                    let mut ids = self.category_index.get(&category).unwrap_or_default();
+                    ids.push(model_id);
+                    self.category_index.insert(&category, &ids);
+                    unique_categories.push(category);
+                }
+            }
+            let mut unique_keys: Vec<Hash> = Vec::new();
+            for key in search_keys {
+                if !unique_keys.contains(&key) {
+                    let mut ids = self.search_key_index.get(key).unwrap_or_default();
+                    ids.push(model_id);
+                    self.search_key_index.insert(key, &ids);
+                    unique_keys.push(key);
+                }
+            }
+
+            model.categories = unique_categories.clone();
+            model.search_keys = unique_keys.clone();
+            self.models.insert(model_id, &model);
+
+            self.env().emit_event(ModelTagsUpdated {
+                model_id,
+                categories: unique_categories,
+                search_keys: unique_keys,
+            });
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_subscription_price(&mut self, model_id: ModelId, price_per_period: Balance) -> Result<(), String> {
+            let caller = self.env().caller();
+            let mut model = self.models.get(model_id).ok_or("Model not found")?;
+            if model.owner != caller {
+                return Err("Only the model owner can set the subscription price".into());
+            }
+            model.subscription_price = price_per_period;
+            self.models.insert(model_id, &model);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn subscribe(&mut self, model_id: ModelId, periods: u32) -> Result<(), String> {
+            let caller = self.env().caller();
+            let model = self.models.get(model_id).ok_or("Model not found")?;
+            if model.delisted {
+                return Err("Model has been delisted".into());
+            }
+            if model.subscription_price == 0 {
+                return Err("Model does not offer subscriptions".into());
+            }
+            if periods == 0 {
+                return Err("Must subscribe for at least one period".into());
+            }
+            let paid = model.subscription_price.checked_mul(periods as u128).ok_or("Subscription price overflow")?;
+            let duration = self.subscription_period.checked_mul(periods as u64).ok_or("Subscription duration overflow")?;
+
+            self.transfer_from(caller, self.env().account_id(), paid)?;
+            self.credit_revenue(model_id, model.owner, paid);
+
+            // Renewals extend an active subscription; a lapsed one starts over from now.
+            let now = self.env().block_timestamp();
+            let start = self.subscriptions.get((caller, model_id)).filter(|expiry| *expiry > now).unwrap_or(now);
+            let expires_at = start.saturating_add(duration);
+            self.subscriptions.insert((caller, model_id), &expires_at);
+
+            self.env().emit_event(Subscribed {
+                model_id,
+                subscriber: caller,
+                periods,
+                paid,
+                expires_at,
+            });
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_subscription_period(&mut self, period: Timestamp) -> Result<(), String> {
+            self.ensure_admin()?;
+            if period == 0 {
+                return Err("Subscription period must be positive".into());
+            }
+            self.subscription_period = period;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_subscription_expiry(&self, account: AccountId, model_id: ModelId) -> Option<Timestamp> {
+            self.subscriptions.get((account, model_id))
+        }
+
+        #[ink(message)]
+        pub fn set_license_duration(&mut self, model_id: ModelId, duration: Timestamp) -> Result<(), String> {
+            let caller = self.env().caller();
+            let mut model = self.models.get(model_id).ok_or("Model not found")?;
+            if model.owner != caller {
+                return Err("Only the model owner can set the license duration".into());
+            }
+            model.license_duration = duration;
+            self.models.insert(model_id, &model);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn transfer_license(&mut self, model_id: ModelId, to: AccountId) -> Result<(), String> {
+            let caller = self.env().caller();
+            let license_id = self.transferable_license(caller, model_id)?;
+            self.move_license(license_id, to)
+        }
+
+        #[ink(message)]
+        pub fn list_license(&mut self, model_id: ModelId, price: Balance) -> Result<(), String> {
+            let caller = self.env().caller();
+            let license_id = self.transferable_license(caller, model_id)?;
+            self.license_listings.insert(license_id, &price);
+            self.env().emit_event(LicenseListed { license_id, price });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn cancel_license_listing(&mut self, model_id: ModelId) -> Result<(), String> {
+            let caller = self.env().caller();
+            let license_id = self.license_of.get((caller, model_id)).ok_or("License not found")?;
+            if !self.license_listings.contains(license_id) {
+                return Err("License is not listed".into());
+            }
+            self.license_listings.remove(license_id);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn buy_license(&mut self, license_id: LicenseId) -> Result<(), String> {
+            let caller = self.env().caller();
+            let price = self.license_listings.get(license_id).ok_or("License is not listed")?;
+            let license = self.licenses.get(license_id).ok_or("License not found")?;
+            let seller = license.holder;
+            if seller == caller {
+                return Err("Cannot buy your own license".into());
+            }
+            // The listing may have gone stale since it was created.
+            self.transferable_license(seller, license.model_id)?;
+            let model = self.models.get(license.model_id).ok_or("Model not found")?;
+
+            // Pay the royalty cut to the model owner and the rest to the seller.
+            let royalty = price * self.resale_royalty_percentage as Balance / 100;
+            if royalty > 0 {
+                self.transfer_from(caller, model.owner, royalty)?;
+            }
+            self.transfer_from(caller, seller, price - royalty)?;
+
+            self.move_license(license_id, caller)?;
+            self.env().emit_event(LicenseSold {
+                license_id,
+                seller,
+                buyer: caller,
+                price,
+                royalty,
+            });
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_resale_royalty_percentage(&mut self, percentage: u8) -> Result<(), String> {
+            self.ensure_admin()?;
+            if percentage > 100 {
+                return Err("Royalty percentage must be between 0 and 100".into());
+            }
+            self.resale_royalty_percentage = percentage;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_license(&self, license_id: LicenseId) -> Option<License> {
+            self.licenses.get(license_id)
+        }
+
+        #[ink(message)]
+        pub fn get_licenses_of(&self, account: AccountId) -> Vec<License> {
+            self.owned_licenses
+                .get(account)
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|license_id| self.licenses.get(license_id))
+                .collect()
+        }
+
+        #[ink(message)]
+        pub fn get_license_listing(&self, license_id: LicenseId) -> Option<Balance> {
+            self.license_listings.get(license_id)
+        }
+
+        #[ink(message)]
+        pub fn has_valid_license(&self, account: AccountId, model_id: ModelId) -> bool {
+            self.license_of
+                .get((account, model_id))
+                .and_then(|license_id| self.licenses.get(license_id))
+                .map(|license| self.is_unexpired(&license))
+                .unwrap_or(false)
+        }
+
+        #[ink(message)]
+        pub fn has_active_access(&self, account: AccountId, model_id: ModelId) -> bool {
+            let subscribed = self
+                .subscriptions
+                .get((account, model_id))
+                .is_some_and(|expiry| self.env().block_timestamp() < expiry);
+            subscribed || self.has_valid_license(account, model_id)
+        }
+
+        fn is_unexpired(&self, license: &License) -> bool {
+            match license.expires_at {
+                Some(expires_at) => self.env().block_timestamp() < expires_at,
+                None => true,
+            }
+        }
+
+        // Returns the holder's license for a model if it may change hands: unexpired and
+        // not under an open revocation case.
+        fn transferable_license(&self, holder: AccountId, model_id: ModelId) -> Result<LicenseId, String> {
+            let license_id = self.license_of.get((holder, model_id)).ok_or("License not found")?;
+            if !self.has_valid_license(holder, model_id) {
+                return Err("License has expired".into());
+            }
+            if self.license_cases.contains(license_id) {
+                return Err("License is under a revocation case".into());
+            }
+            if self.license_disputes.contains(license_id) {
+                return Err("License is under a purchase dispute".into());
+            }
+            Ok(license_id)
+        }
+
+        fn move_license(&mut self, license_id: LicenseId, to: AccountId) -> Result<(), String> {
+            let mut license = self.licenses.get(license_id).ok_or("License not found")?;
+            let from = license.holder;
+            if from == to {
+                return Err("Cannot transfer a license to its holder".into());
+            }
+            if self.license_of.contains((to, license.model_id)) {
+                return Err("Recipient already holds a license for this model".into());
+            }
+
+            self.remove_license_from_holder(from, license.model_id, license_id);
+            self.add_license_to_holder(to, license.model_id, license_id);
+            self.license_listings.remove(license_id);
+            license.holder = to;
+            self.licenses.insert(license_id, &license);
+
+            self.env().emit_event(LicenseTransferred {
+                license_id,
+                model_id: license.model_id,
+                from,
+                to,
+            });
+
+            Ok(())
+        }
+
+        fn add_license_to_holder(&mut self, holder: AccountId, model_id: ModelId, license_id: LicenseId) {
+            self.license_of.insert((holder, model_id), &license_id);
+            let mut owned = self.owned_licenses.get(holder).unwrap_or_default();
+            owned.push(license_id);
+            self.owned_licenses.insert(holder, &owned);
+        }
+
+        fn remove_license_from_holder(&mut self, holder: AccountId, model_id: ModelId, license_id: LicenseId) {
+            self.license_of.remove((holder, model_id));
+            let mut owned = self.owned_licenses.get(holder).unwrap_or_default();
+            owned.retain(|id| *id != license_id);
+            self.owned_licenses.insert(holder, &owned);
+        }
+
+        #[ink(message)]
+        pub fn register_privacy_budget(&mut self, dataset_hash: String, total_epsilon: Epsilon) -> Result<(), String> {
+            let caller = self.env().caller();
+            if total_epsilon == 0 {
+                return Err("Privacy budget must be positive".into());
+            }
+            if self.privacy_budgets.contains(&dataset_hash) {
+                return Err("Dataset already has a privacy budget".into());
+            }
+
+            self.privacy_budgets.insert(&dataset_hash, &PrivacyBudget {
+                owner: caller,
+                total_epsilon,
+                spent_epsilon: 0,
+            });
+            self.env().emit_event(PrivacyBudgetRegistered {
+                dataset_hash,
+                owner: caller,
+                total_epsilon,
+            });
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_dataset_access(&mut self, dataset_hash: String, account: AccountId, allowed: bool) -> Result<(), String> {
+            let budget = self.privacy_budgets.get(&dataset_hash).ok_or("Dataset has no privacy budget")?;
+            if budget.owner != self.env().caller() {
+                return Err("Only the dataset owner can grant access".into());
+            }
+
+            if allowed {
+                self.dataset_licensees.insert((dataset_hash.clone(), account), &true);
+            } else {
+                self.dataset_licensees.remove((dataset_hash.clone(), account));
+            }
+            self.env().emit_event(DatasetAccessChanged {
+                dataset_hash,
+                account,
+                allowed,
+            });
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn submit_evaluation(
+            &mut self,
+            model_id: ModelId,
+            evaluation_data_hash: String,
+            predicted_output: String,
+            epsilon: Epsilon,
+        ) -> Result<(), String> {
+            if !self.models.contains(model_id) {
+                return Err("Model not found".into());
+            }
+            self.spend_privacy_budget(model_id, &evaluation_data_hash, epsilon)?;
+
+            // Simulate submitting data for evaluation, applying differential privacy
+            // through an off-chain service.
+            let differentially_private_data =
+                self.apply_differential_privacy(evaluation_data_hash)?;
+
+            // In a real implementation, this would send the differentially private
+            // data to an off-chain service for processing and comparison with the
+            // predicted output.
+            ink::env::debug_println!(
+                "Data submitted for evaluation (with differential privacy applied): {:?}",
+                differentially_private_data
+            );
+
+            ink::env::debug_println!(
+                "Predicted output submitted by user: {:?}",
+                predicted_output
+            );
+
+            //The processing logic is done off-chain so we'll just return Ok
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn record_training_use(&mut self, model_id: ModelId, dataset_hash: String, epsilon: Epsilon) -> Result<(), String> {
+            let caller = self.env().caller();
+            let model = self.models.get(model_id).ok_or("Model not found")?;
+            if model.owner != caller {
+                return Err("Only the model owner can record training use".into());
+            }
+            self.spend_privacy_budget(model_id, &dataset_hash, epsilon)
+        }
+
+        #[ink(message)]
+        pub fn get_privacy_budget(&self, dataset_hash: String) -> Option<PrivacyBudget> {
+            self.privacy_budgets.get(dataset_hash)
+        }
+
+        #[ink(message)]
+        pub fn get_remaining_privacy_budget(&self, dataset_hash: String) -> Option<Epsilon> {
+            self.privacy_budgets
+                .get(dataset_hash)
+                .map(|budget| budget.total_epsilon - budget.spent_epsilon)
+        }
+
+        // Deducts a declared epsilon from a dataset's budget, refusing any use that would
+        // exceed it or that the dataset owner has not authorized.
+        fn spend_privacy_budget(&mut self, model_id: ModelId, dataset_hash: &String, epsilon: Epsilon) -> Result<(), String> {
+            let mut budget = self.privacy_budgets.get(dataset_hash).ok_or("Dataset has no privacy budget")?;
+            let caller = self.env().caller();
+            if caller != budget.owner && !self.dataset_licensees.get((dataset_hash.clone(), caller)).unwrap_or_default() {
+                return Err("Caller is not licensed to use the dataset".into());
+            }
+            if epsilon == 0 {
+                return Err("Epsilon must be positive".into());
+            }
+            let remaining = budget.total_epsilon - budget.spent_epsilon;
+            if remaining == 0 {
+                return Err("Privacy budget exhausted".into());
+            }
+            if epsilon > remaining {
+                return Err("Epsilon exceeds the remaining privacy budget".into());
+            }
+
+            budget.spent_epsilon += epsilon;
+            self.privacy_budgets.insert(dataset_hash, &budget);
+            self.env().emit_event(PrivacyBudgetSpent {
+                dataset_hash: dataset_hash.clone(),
+                model_id,
+                epsilon,
+                remaining: remaining - epsilon,
+            });
+            if remaining == epsilon {
+                self.env().emit_event(PrivacyBudgetExhausted {
+                    dataset_hash: dataset_hash.clone(),
+                });
+            }
+
+            Ok(())
+        }
+
+        // Simulates differential privacy application (for demonstration).  In a real
+        // application, this would interact with an off-chain service.
+        fn apply_differential_privacy(&self, data_hash: String) -> Result<String, String> {
+            // Replace with actual logic or interaction with an external service.
+            // This is a placeholder to demonstrate the concept.
+            Ok(format!("Differentially private version of: {}", data_hash))
+        }
+
+
+        #[ink(message)]
+        pub fn start_performance_evaluation(&mut self, model_id: ModelId) -> Result<(), String> {
+            if !self.models.contains(model_id) {
+                return Err("Model not found".into());
+            }
+            let mut evaluation_round = 1;
+
+            if let Some(evaluation) = self.evaluations.get((model_id, evaluation_round)){
+                if !evaluation.finalized {
+                    return Err("Previous Evaluation is still running!".into())
+                }
+                //Find the latest round.
+                while self.evaluations.contains((model_id, evaluation_round + 1)){
+                    evaluation_round += 1;
+                }
+                evaluation_round += 1;
+            }
+
+            let started_at = self.env().block_timestamp();
+            let deadline = started_at + self.evaluation_period;
+            let evaluation = Evaluation{
+                evaluators: vec![],
+                finalized: false,
+                started_at,
+                deadline,
+            };
+
+            self.evaluations.insert((model_id, evaluation_round), &evaluation);
 
             self.env().emit_event(EvaluationStarted {
                 model_id,
-                round: evaluation_round,
+                round: evaluation_round,
+                deadline,
+            });
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn stake_for_evaluation(&mut self, model_id: ModelId, evaluation_round: EvaluationRound) -> Result<(), String>{
+            let caller = self.env().caller();
+            let stake_amount = 100; //fixed amount, but can be flexible later
+            let mut evaluation = self.evaluations.get((model_id, evaluation_round)).ok_or("Evaluation not found")?.clone();
+
+            if evaluation.finalized {
+                return Err("Evaluation Round is finalized".into());
+            }
+
+            if self.env().block_timestamp() >= evaluation.deadline {
+                return Err("Evaluation round deadline has passed".into());
+            }
+
+            if self.evaluation_stake.contains((caller, model_id, evaluation_round)){
+                return Err("Account has already staked".into());
+            }
+
+            self.transfer_from(caller, self.env().account_id(), stake_amount)?;
+            self.evaluation_stake.insert((caller, model_id, evaluation_round), &stake_amount);
+
+            if !evaluation.evaluators.contains(&caller){
+                evaluation.evaluators.push(caller);
+                self.evaluations.insert((model_id, evaluation_round), &evaluation);
+            }
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn submit_evaluation_result(&mut self, model_id: ModelId, evaluation_round: EvaluationRound, accuracy: u8) -> Result<(), String> {
+            let caller = self.env().caller();
+            if accuracy > 100 {
+                return Err("Accuracy must be between 0 and 100".into());
+            }
+            let evaluation = self.evaluations.get((model_id, evaluation_round)).ok_or("Evaluation not found")?.clone();
+
+            if evaluation.finalized {
+                return Err("Evaluation round already finalized.".into());
+            }
+
+            if self.env().block_timestamp() >= evaluation.deadline {
+                return Err("Evaluation round deadline has passed".into());
+            }
+
+            if !evaluation.evaluators.contains(&caller){
+                return Err("Evaluator hasn't stake for evaluation yet".into());
+            }
+
+            if self.evaluation_results.contains((model_id, evaluation_round, caller)){
+                return Err("Account already submitted a result".into());
+            }
+
+            self.evaluation_results.insert((model_id, evaluation_round, caller), &accuracy);
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn finalize_evaluation(&mut self, model_id: ModelId, evaluation_round: EvaluationRound) -> Result<(), String> {
+            let evaluation = self.evaluations.get((model_id, evaluation_round)).ok_or("Evaluation not found")?.clone();
+
+            if evaluation.finalized {
+                return Err("Evaluation round already finalized.".into());
+            }
+
+            //Collect the stake and accuracy report of every evaluator. Before the deadline every
+            //evaluator must have submitted; after it, the round is finalized with the results it has.
+            let num_evaluators = evaluation.evaluators.len();
+            if num_evaluators == 0{
+                return Err("There are no evaluators to be rewarded.".into());
+            }
+            let past_deadline = self.env().block_timestamp() >= evaluation.deadline;
+            let mut reports: Vec<(AccountId, Balance, u8)> = Vec::new();
+            let mut no_shows: Vec<(AccountId, Balance)> = Vec::new();
+            for evaluator in evaluation.evaluators.iter(){
+                let stake_amount = self.evaluation_stake.get((*evaluator, model_id, evaluation_round)).ok_or("Evaluator stake not found")?;
+                if let Some(accuracy) = self.evaluation_results.get((model_id, evaluation_round, *evaluator)){
+                    reports.push((*evaluator, stake_amount, accuracy));
+                } else if past_deadline {
+                    no_shows.push((*evaluator, stake_amount));
+                } else {
+                    ink::env::debug_println!("Evaluator has not submitted result yet");
+                    return Err("Not all evaluators submitted results yet".into());
+                }
+            }
+
+            // Too few results to form a consensus: void the round and return every stake in full.
+            if reports.len() < self.min_evaluators as usize {
+                if !past_deadline {
+                    return Err("Not enough evaluators submitted results".into());
+                }
+                for evaluator in evaluation.evaluators.iter() {
+                    let stake_amount = self.evaluation_stake.get((*evaluator, model_id, evaluation_round)).ok_or("Evaluator stake not found")?;
+                    self.transfer_from(self.env().account_id(), *evaluator, stake_amount)?;
+                }
+                let mut evaluation = evaluation;
+                evaluation.finalized = true;
+                self.evaluations.insert((model_id, evaluation_round), &evaluation);
+                self.env().emit_event(EvaluationVoided {
+                    model_id,
+                    round: evaluation_round,
+                    results: reports.len() as u32,
+                });
+                return Ok(());
+            }
+
+            // Reputation scales each evaluator's stake, so a track record of accurate reports
+            // buys influence that stake alone cannot.
+            let weighted: Vec<(Balance, u8)> = reports
+                .iter()
+                .map(|(evaluator, stake, accuracy)| (*stake * self.reputation_weight(*evaluator), *accuracy))
+                .collect();
+            let consensus = Self::consensus_accuracy(&weighted);
+            if !reports.is_empty() {
+                let mut reputation = self.model_reputations.get(model_id).unwrap_or_default();
+                reputation.rounds += 1;
+                reputation.total += consensus as u64;
+                self.model_reputations.insert(model_id, &reputation);
+            }
+
+            // Slash reports far from consensus into the bonus pool; weight the others by stake and score.
+            let mut payouts: Vec<(AccountId, Balance, Balance)> = Vec::new(); // (evaluator, stake returned, bonus weight)
+            let mut total_weight: Balance = 0;
+            for (evaluator, stake_amount) in no_shows {
+                let penalty = stake_amount * self.no_show_penalty_percentage as Balance / 100;
+                self.evaluation_bonus_pool += penalty;
+                payouts.push((evaluator, stake_amount - penalty, 0));
+                self.env().emit_event(EvaluatorSlashed {
+                    evaluator,
+                    model_id,
+                    round: evaluation_round,
+                    amount: penalty,
+                });
+            }
+            for (evaluator, stake_amount, accuracy) in reports {
+                let reputation_weight = self.reputation_weight(evaluator);
+                let mut reputation = self.evaluator_reputations.get(evaluator).unwrap_or_default();
+                reputation.rounds += 1;
+                reputation.total += accuracy.abs_diff(consensus) as u64;
+                self.evaluator_reputations.insert(evaluator, &reputation);
+
+                if accuracy.abs_diff(consensus) > self.slash_deviation {
+                    let slashed = stake_amount * self.slash_percentage as Balance / 100;
+                    self.evaluation_bonus_pool += slashed;
+                    payouts.push((evaluator, stake_amount - slashed, 0));
+                    self.env().emit_event(EvaluatorSlashed {
+                        evaluator,
+                        model_id,
+                        round: evaluation_round,
+                        amount: slashed,
+                    });
+                } else {
+                    let weight = stake_amount * reputation_weight * Self::quadratic_score(accuracy, consensus) as Balance;
+                    total_weight += weight;
+                    payouts.push((evaluator, stake_amount, weight));
+                }
+            }
+
+            // Return the remaining stakes and share out at most `max_bonus_payout_percentage` of the
+            // bonus pool; the rest, and rounding dust, stays for later rounds.
+            let pool = self.evaluation_bonus_pool * self.max_bonus_payout_percentage as Balance / 100;
+            let mut bonus_paid: Balance = 0;
+            for (evaluator, returned, weight) in payouts {
+                let bonus = (pool * weight).checked_div(total_weight).unwrap_or(0);
+                bonus_paid += bonus;
+                if returned + bonus > 0 {
+                    self.transfer_from(self.env().account_id(), evaluator, returned + bonus)?;
+                }
+            }
+            self.evaluation_bonus_pool -= bonus_paid;
+
+            let mut evaluation = self.evaluations.get((model_id, evaluation_round)).ok_or("Evaluation not found")?.clone();
+            evaluation.finalized = true;
+            self.evaluations.insert((model_id, evaluation_round), &evaluation);
+
+            self.env().emit_event(EvaluationFinalized {
+                model_id,
+                round: evaluation_round,
+                consensus,
+                bonus_paid,
+            });
+
+            Ok(())
+        }
+
+
+        // Influence multiplier of an evaluator: 100 minus their average deviation from
+        // consensus, floored at 10, scaled by their experience over the first
+        // `REPUTATION_RAMP_ROUNDS` rounds. Evaluators without a track record start at 20, so
+        // fresh accounts cannot outweigh evaluators with a proven record.
+        fn reputation_weight(&self, evaluator: AccountId) -> Balance {
+            let reputation = self.evaluator_reputations.get(evaluator).unwrap_or_default();
+            let average_deviation = if reputation.rounds > 0 { reputation.total / reputation.rounds as u64 } else { 0 };
+            let experience = (reputation.rounds + 1).min(REPUTATION_RAMP_ROUNDS) as Balance;
+            (100 - average_deviation.min(90) as Balance) * experience / REPUTATION_RAMP_ROUNDS as Balance
+        }
+
+        #[ink(message)]
+        pub fn get_model_reputation(&self, model_id: ModelId) -> Option<(u8, u32)> {
+            self.model_reputations
+                .get(model_id)
+                .filter(|reputation| reputation.rounds > 0)
+                .map(|reputation| ((reputation.total / reputation.rounds as u64) as u8, reputation.rounds))
+        }
+
+        #[ink(message)]
+        pub fn get_evaluator_reputation(&self, evaluator: AccountId) -> Option<(u8, u32)> {
+            self.evaluator_reputations
+                .get(evaluator)
+                .filter(|reputation| reputation.rounds > 0)
+                .map(|reputation| ((reputation.total / reputation.rounds as u64) as u8, reputation.rounds))
+        }
+
+        // Stake-weighted mean of the reported accuracies.
+        fn consensus_accuracy(reports: &[(Balance, u8)]) -> u8 {
+            let total_stake: Balance = reports.iter().map(|(stake, _)| *stake).sum();
+            if total_stake == 0 {
+                return 0;
+            }
+            let weighted: Balance = reports.iter().map(|(stake, accuracy)| *stake * *accuracy as Balance).sum();
+            (weighted / total_stake) as u8
+        }
+
+        // Quadratic score of a report: 10_000 for a report exactly at consensus, minus the
+        // squared distance in accuracy points.
+        fn quadratic_score(accuracy: u8, consensus: u8) -> u32 {
+            let deviation = accuracy.abs_diff(consensus) as u32;
+            10_000 - deviation * deviation
+        }
+
+        #[ink(message)]
+        pub fn fund_evaluation_rewards(&mut self, amount: Balance) -> Result<(), String> {
+            let caller = self.env().caller();
+            self.transfer_from(caller, self.env().account_id(), amount)?;
+            self.evaluation_bonus_pool += amount;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_scoring_parameters(&mut self, slash_deviation: u8, slash_percentage: u8) -> Result<(), String> {
+            self.ensure_admin()?;
+            if slash_deviation > 100 || slash_percentage > 100 {
+                return Err("Scoring parameters must be between 0 and 100".into());
+            }
+            self.slash_deviation = slash_deviation;
+            self.slash_percentage = slash_percentage;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_evaluation_period(&mut self, period: Timestamp) -> Result<(), String> {
+            self.ensure_admin()?;
+            if period == 0 {
+                return Err("Evaluation period must be positive".into());
+            }
+            self.evaluation_period = period;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_no_show_penalty_percentage(&mut self, percentage: u8) -> Result<(), String> {
+            self.ensure_admin()?;
+            if percentage > 100 {
+                return Err("Penalty percentage must be between 0 and 100".into());
+            }
+            self.no_show_penalty_percentage = percentage;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_evaluation_limits(&mut self, min_evaluators: u32, max_bonus_payout_percentage: u8) -> Result<(), String> {
+            self.ensure_admin()?;
+            if min_evaluators == 0 {
+                return Err("A round needs at least one evaluator".into());
+            }
+            if max_bonus_payout_percentage > 100 {
+                return Err("Payout percentage must be between 0 and 100".into());
+            }
+            self.min_evaluators = min_evaluators;
+            self.max_bonus_payout_percentage = max_bonus_payout_percentage;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_evaluation_limits(&self) -> (u32, u8) {
+            (self.min_evaluators, self.max_bonus_payout_percentage)
+        }
+
+        #[ink(message)]
+        pub fn get_round_timing(&self, model_id: ModelId, evaluation_round: EvaluationRound) -> Option<(Timestamp, Timestamp)> {
+            self.evaluations
+                .get((model_id, evaluation_round))
+                .map(|evaluation| (evaluation.started_at, evaluation.deadline))
+        }
+
+        #[ink(message)]
+        pub fn get_evaluation_period(&self) -> Timestamp {
+            self.evaluation_period
+        }
+
+        #[ink(message)]
+        pub fn get_evaluation_bonus_pool(&self) -> Balance {
+            self.evaluation_bonus_pool
+        }
+
+        #[ink(message)]
+        pub fn stake_as_arbiter(&mut self) -> Result<(), String> {
+            let caller = self.env().caller();
+            let stake_amount = 100; //fixed amount, same as evaluation stakes
+
+            if self.arbiter_stakes.contains(caller) {
+                return Err("Account is already an arbiter".into());
+            }
+
+            self.transfer_from(caller, self.env().account_id(), stake_amount)?;
+            self.arbiter_stakes.insert(caller, &stake_amount);
+
+            Ok(())
+        }
+
+        // Starts the unstake cooldown. A leaving arbiter can no longer vote.
+        #[ink(message)]
+        pub fn request_arbiter_unstake(&mut self) -> Result<(), String> {
+            let caller = self.env().caller();
+            if !self.arbiter_stakes.contains(caller) {
+                return Err("Account is not an arbiter".into());
+            }
+            if self.arbiter_unstake_at.contains(caller) {
+                return Err("Unstake already requested".into());
+            }
+
+            let unstake_at = self.env().block_timestamp() + self.arbiter_unstake_cooldown;
+            self.arbiter_unstake_at.insert(caller, &unstake_at);
+            Ok(())
+        }
+
+        // Returns the remaining stake once the cooldown has passed and every case the
+        // arbiter voted on is resolved, so a stake can never leave ahead of its slashing.
+        #[ink(message)]
+        pub fn unstake_as_arbiter(&mut self) -> Result<(), String> {
+            let caller = self.env().caller();
+            let unstake_at = self.arbiter_unstake_at.get(caller).ok_or("Unstake not requested")?;
+            if self.env().block_timestamp() < unstake_at {
+                return Err("Unstake cooldown has not passed".into());
+            }
+            if self.arbiter_open_votes.get(caller).unwrap_or(0) > 0 {
+                return Err("Arbiter has votes in open cases".into());
+            }
+
+            let stake = self.arbiter_stakes.get(caller).unwrap_or(0);
+            self.arbiter_stakes.remove(caller);
+            self.arbiter_unstake_at.remove(caller);
+            if stake > 0 {
+                self.transfer_from(self.env().account_id(), caller, stake)?;
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn open_revocation_case(
+            &mut self,
+            model_id: ModelId,
+            licensee: AccountId,
+            evidence_uri: String,
+        ) -> Result<(), String> {
+            let caller = self.env().caller();
+            let model = self.models.get(model_id).ok_or("Model not found")?;
+
+            if model.owner != caller {
+                return Err("Only the model owner can open a revocation case".into());
+            }
+
+            if !self.has_valid_license(licensee, model_id) {
+                return Err("Licensee does not hold a valid license".into());
+            }
+            let license_id = self.license_of.get((licensee, model_id)).ok_or("License not found")?;
+            if self.license_cases.contains(license_id) {
+                return Err("License is already under a revocation case".into());
+            }
+            if self.license_disputes.contains(license_id) {
+                return Err("License is under a purchase dispute".into());
+            }
+
+            // Escrow the refund up front, at the price the licensee actually paid, so resolving
+            // the case does not depend on the owner's allowance or the model's current price.
+            let purchase_id = self.license_purchases.get(license_id).ok_or("License purchase not found")?;
+            let paid = self.purchases.get(purchase_id).ok_or("Purchase not found")?.amount;
+            let refund = paid * self.revocation_refund_percentage as Balance / 100;
+            if refund > 0 {
+                self.transfer_from(caller, self.env().account_id(), refund)?;
+            }
+
+            self.revocation_case_count += 1;
+            let case_id = self.revocation_case_count;
+
+            let opened_at = self.env().block_timestamp();
+            let case = RevocationCase {
+                model_id,
+                license_id,
+                licensee,
+                evidence_uri: evidence_uri.clone(),
+                opened_at,
+                vote_by: opened_at + self.arbitration_period,
+                refund,
+                voters: vec![],
+                votes_for: 0,
+                votes_against: 0,
+                resolved: false,
+                revoked: false,
+            };
+            self.revocation_cases.insert(case_id, &case);
+            // Freeze the license until the case is resolved.
+            self.license_cases.insert(license_id, &case_id);
+            self.license_listings.remove(license_id);
+
+            let mut history = self.revocation_history.get(licensee).unwrap_or_default();
+            history.push(case_id);
+            self.revocation_history.insert(licensee, &history);
+
+            self.env().emit_event(RevocationCaseOpened {
+                case_id,
+                licensee,
+                model_id,
+                evidence_uri,
+            });
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn vote_on_revocation(&mut self, case_id: CaseId, revoke: bool) -> Result<(), String> {
+            let caller = self.env().caller();
+            let mut case = self.revocation_cases.get(case_id).ok_or("Revocation case not found")?;
+
+            if case.resolved {
+                return Err("Revocation case already resolved".into());
+            }
+
+            if self.env().block_timestamp() >= case.vote_by {
+                return Err("Voting period has ended".into());
+            }
+
+            // The model owner and the licensee are parties to the case, even if they sit on the panel.
+            let owner = self.models.get(case.model_id).ok_or("Model not found")?.owner;
+            if caller == owner || caller == case.licensee {
+                return Err("Parties to the case cannot vote".into());
+            }
+
+            if self.arbiter_stakes.get(caller).unwrap_or(0) == 0 {
+                return Err("Only staked arbiters can vote".into());
+            }
+
+            if self.arbiter_unstake_at.contains(caller) {
+                return Err("Arbiter is leaving the panel".into());
+            }
+
+            if case.voters.contains(&caller) {
+                return Err("Arbiter has already voted".into());
+            }
+
+            case.voters.push(caller);
+            self.arbiter_votes.insert((case_id, caller), &revoke);
+            let open_votes = self.arbiter_open_votes.get(caller).unwrap_or(0);
+            self.arbiter_open_votes.insert(caller, &(open_votes + 1));
+            if revoke {
+                case.votes_for += 1;
+            } else {
+                case.votes_against += 1;
+            }
+            self.revocation_cases.insert(case_id, &case);
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn resolve_revocation_case(&mut self, case_id: CaseId) -> Result<(), String> {
+            let mut case = self.revocation_cases.get(case_id).ok_or("Revocation case not found")?;
+
+            if case.resolved {
+                return Err("Revocation case already resolved".into());
+            }
+
+            // After the voting deadline the case is decided by the votes cast.
+            if (case.voters.len() as u32) < self.arbitration_quorum && self.env().block_timestamp() < case.vote_by {
+                return Err("Arbitration quorum not reached".into());
+            }
+
+            let mut refund: Balance = 0;
+            case.revoked = case.votes_for > case.votes_against;
+            self.license_cases.remove(case.license_id);
+            if case.revoked {
+                // Burn the license and pay the escrowed refund to the licensee.
+                self.remove_license_from_holder(case.licensee, case.model_id, case.license_id);
+                self.licenses.remove(case.license_id);
+                refund = case.refund;
+                if refund > 0 {
+                    self.transfer_from(self.env().account_id(), case.licensee, refund)?;
+                }
+            } else if case.refund > 0 {
+                // The license stands: return the escrowed refund to the model owner.
+                let model = self.models.get(case.model_id).ok_or("Model not found")?;
+                self.transfer_from(self.env().account_id(), model.owner, case.refund)?;
+            }
+
+            case.resolved = true;
+            self.revocation_cases.insert(case_id, &case);
+            self.settle_arbiter_stakes(case_id, &case);
+
+            self.env().emit_event(RevocationCaseResolved {
+                case_id,
+                revoked: case.revoked,
+                refund,
             });
 
-            Ok(())
+            Ok(())
+        }
+
+        // Releases the open votes of a resolved case's arbiters and moves
+        // `arbiter_slash_percentage` of the stake of those who voted against the outcome to
+        // those who voted with it, in equal parts with the rounding dust to the first.
+        fn settle_arbiter_stakes(&mut self, case_id: CaseId, case: &RevocationCase) {
+            let mut slashed: Balance = 0;
+            let mut majority: Vec<AccountId> = Vec::new();
+            for voter in case.voters.iter() {
+                let open_votes = self.arbiter_open_votes.get(voter).unwrap_or(0);
+                self.arbiter_open_votes.insert(voter, &open_votes.saturating_sub(1));
+                if self.arbiter_votes.get((case_id, *voter)) == Some(case.revoked) {
+                    majority.push(*voter);
+                    continue;
+                }
+                let stake = self.arbiter_stakes.get(voter).unwrap_or(0);
+                let slash = stake * self.arbiter_slash_percentage as Balance / 100;
+                self.arbiter_stakes.insert(voter, &(stake - slash));
+                slashed += slash;
+            }
+
+            if majority.is_empty() {
+                return;
+            }
+            let share = slashed / majority.len() as Balance;
+            let mut dust = slashed - share * majority.len() as Balance;
+            for voter in majority {
+                let stake = self.arbiter_stakes.get(voter).unwrap_or(0);
+                self.arbiter_stakes.insert(voter, &(stake + share + dust));
+                dust = 0;
+            }
+        }
+
+        #[ink(message)]
+        pub fn set_arbitration_quorum(&mut self, quorum: u32) -> Result<(), String> {
+            self.ensure_admin()?;
+            if quorum == 0 {
+                return Err("Quorum must be at least 1".into());
+            }
+            self.arbitration_quorum = quorum;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_arbitration_period(&mut self, period: Timestamp) -> Result<(), String> {
+            self.ensure_admin()?;
+            if period == 0 {
+                return Err("Arbitration period must be positive".into());
+            }
+            self.arbitration_period = period;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_revocation_refund_percentage(&mut self, percentage: u8) -> Result<(), String> {
+            self.ensure_admin()?;
+            if percentage > 100 {
+                return Err("Refund percentage must be between 0 and 100".into());
+            }
+            self.revocation_refund_percentage = percentage;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_arbiter_parameters(&mut self, unstake_cooldown: Timestamp, slash_percentage: u8) -> Result<(), String> {
+            self.ensure_admin()?;
+            if slash_percentage > 100 {
+                return Err("Slash percentage must be between 0 and 100".into());
+            }
+            self.arbiter_unstake_cooldown = unstake_cooldown;
+            self.arbiter_slash_percentage = slash_percentage;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_arbiter_stake(&self, arbiter: AccountId) -> Balance {
+            self.arbiter_stakes.get(arbiter).unwrap_or(0)
+        }
+
+        #[ink(message)]
+        pub fn get_revocation_case(&self, case_id: CaseId) -> Option<RevocationCase> {
+            self.revocation_cases.get(case_id)
+        }
+
+        #[ink(message)]
+        pub fn get_revocation_history(&self, account: AccountId) -> Vec<RevocationCase> {
+            self.revocation_history
+                .get(account)
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|case_id| self.revocation_cases.get(case_id))
+                .collect()
+        }
+
+        #[ink(message)]
+        pub fn get_model_details(&self, model_id: ModelId) -> Option<AiModel> {
+            self.models.get(model_id)
+        }
+
+        #[ink(message)]
+        pub fn list_models_by_category(&self, category: String, offset: u32, limit: u32) -> Vec<(ModelId, AiModel)> {
+            self.page_of_models(self.category_index.get(category).unwrap_or_default(), offset, limit)
+        }
+
+        #[ink(message)]
+        pub fn list_models_by_owner(&self, owner: AccountId, offset: u32, limit: u32) -> Vec<(ModelId, AiModel)> {
+            self.page_of_models(self.owner_index.get(owner).unwrap_or_default(), offset, limit)
+        }
+
+        #[ink(message)]
+        pub fn list_models_by_search_key(&self, key: Hash, offset: u32, limit: u32) -> Vec<(ModelId, AiModel)> {
+            self.page_of_models(self.search_key_index.get(key).unwrap_or_default(), offset, limit)
+        }
+
+        // Loads one page of an index, capping the page at `MAX_PAGE_SIZE` models.
+        fn page_of_models(&self, ids: Vec<ModelId>, offset: u32, limit: u32) -> Vec<(ModelId, AiModel)> {
+            ids.into_iter()
+                .skip(offset as usize)
+                .take(limit.min(MAX_PAGE_SIZE) as usize)
+                .filter_map(|model_id| self.models.get(model_id).map(|model| (model_id, model)))
+                .collect()
+        }
+
+        #[ink(message)]
+        pub fn token(&self) -> AccountId {
+            self.token
+        }
+
+        #[ink(message)]
+        pub fn admin(&self) -> AccountId {
+            self.admin
+        }
+
+        fn ensure_admin(&self) -> Result<(), String> {
+            if self.env().caller() != self.admin {
+                return Err("Only the admin can change marketplace parameters".into());
+            }
+            Ok(())
+        }
+
+        // Moves tokens through the PSP22 token. Payouts of tokens held by the contract use
+        // `transfer`; all other payments use `transfer_from`, so payers must first approve
+        // the contract as a spender.
+        fn transfer_from(&mut self, from: AccountId, to: AccountId, value: Balance) -> Result<(), String> {
+            let call = if from == self.env().account_id() {
+                TokenCall::Transfer { to, value }
+            } else {
+                TokenCall::TransferFrom { from, to, value }
+            };
+            Self::invoke_token(self.token, call).map_err(|error| match error {
+                CallError::Token(error) => format!("Token transfer failed: {:?}", error),
+                _ => String::from("Token call failed"),
+            })
+        }
+
+        // Sends a call to the PSP22 token. `transfer_from` checks the payer's allowance and balance
+        // before transferring.
+        #[cfg(not(test))]
+        fn invoke_token(token: AccountId, call: TokenCall) -> Result<(), CallError<PSP22Error>> {
+            match call {
+                TokenCall::Transfer { to, value } => token_interfaces::psp22::transfer(token, to, value),
+                TokenCall::TransferFrom { from, to, value } => token_interfaces::psp22::transfer_from(token, from, to, value),
+            }
+        }
+
+        // Cross-contract calls are unavailable off-chain, so unit tests send the same calls to
+        // a mock PSP22 ledger instead.
+        #[cfg(test)]
+        fn invoke_token(_token: AccountId, call: TokenCall) -> Result<(), CallError<PSP22Error>> {
+            tests::mock_token_call(call).map_err(CallError::Token)
+        }
+    }
+
+    // A PSP22 call made by the contract on its payment token.
+    enum TokenCall {
+        Transfer { to: AccountId, value: Balance },
+        TransferFrom { from: AccountId, to: AccountId, value: Balance },
+    }
+
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::env::test;
+        use std::cell::RefCell;
+        use std::collections::BTreeMap;
+
+        std::thread_local! {
+            // Balances of the mock PSP22 payment token.
+            static LEDGER: RefCell<BTreeMap<AccountId, Balance>> = const { RefCell::new(BTreeMap::new()) };
+        }
+
+        // Applies a token call as a PSP22 token would, with `transfer` moving the calling
+        // contract's own tokens.
+        pub(super) fn mock_token_call(call: TokenCall) -> Result<(), PSP22Error> {
+            let (from, to, value) = match call {
+                TokenCall::Transfer { to, value } => (ink::env::account_id::<ink::env::DefaultEnvironment>(), to, value),
+                TokenCall::TransferFrom { from, to, value } => (from, to, value),
+            };
+            LEDGER.with(|ledger| {
+                let mut ledger = ledger.borrow_mut();
+                let from_balance = ledger.get(&from).copied().unwrap_or_default();
+                if from_balance < value {
+                    return Err(PSP22Error::InsufficientBalance);
+                }
+                ledger.insert(from, from_balance - value);
+                *ledger.entry(to).or_default() += value;
+                Ok(())
+            })
+        }
+
+        fn mint(account: AccountId, value: Balance) {
+            LEDGER.with(|ledger| *ledger.borrow_mut().entry(account).or_default() += value);
+        }
+
+        fn balance_of(account: AccountId) -> Balance {
+            LEDGER.with(|ledger| ledger.borrow().get(&account).copied().unwrap_or_default())
+        }
+
+        fn caller_balance() -> Balance {
+            balance_of(ink::env::caller::<ink::env::DefaultEnvironment>())
+        }
+
+        fn contract_account() -> AccountId {
+            AccountId::from([0xAA; 32])
+        }
+
+        fn token_account() -> AccountId {
+            AccountId::from([0xBB; 32])
+        }
+
+        #[ink::test]
+        fn new_works() {
+            let (marketplace, _alice) = setup();
+            assert_eq!(marketplace.token(), token_account());
+            assert_eq!(caller_balance(), 1000);
+        }
+
+        #[ink::test]
+        fn parameter_setters_are_admin_only() {
+            let (mut marketplace, alice) = setup();
+            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(marketplace.admin(), alice);
+
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(marketplace.set_dispute_window(0).is_err());
+            assert!(marketplace.set_challenge_parameters(0, 1_000, false).is_err());
+            assert!(marketplace.set_subscription_period(1_000).is_err());
+            assert!(marketplace.set_resale_royalty_percentage(100).is_err());
+            assert!(marketplace.set_scoring_parameters(100, 0).is_err());
+            assert!(marketplace.set_evaluation_period(1_000).is_err());
+            assert!(marketplace.set_no_show_penalty_percentage(0).is_err());
+            assert!(marketplace.set_evaluation_limits(1, 100).is_err());
+            assert!(marketplace.set_arbitration_quorum(1).is_err());
+            assert!(marketplace.set_arbitration_period(1_000).is_err());
+            assert!(marketplace.set_revocation_refund_percentage(100).is_err());
+            assert!(marketplace.set_arbiter_parameters(1_000, 10).is_err());
+
+            test::set_caller::<ink::env::DefaultEnvironment>(alice);
+            marketplace.set_arbitration_quorum(1).expect("Admin can set the quorum");
+            marketplace.set_resale_royalty_percentage(5).expect("Admin can set the royalty");
+        }
+
+        #[ink::test]
+        fn register_and_purchase_model_works() {
+            let (mut marketplace, _alice) = setup();
+            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            //Register a model
+            marketplace.register_model(
+                String::from("QmModelHash"),
+                100,
+                String::from("Awesome AI Model"),
+                String::from("QmSchemaHash"),
+                Vec::new(),
+            ).expect("Model registration failed");
+
+            //Purchase the model
+            marketplace.purchase_model(1).expect("Model purchase failed");
+            assert_eq!(caller_balance(), 900); //Buyer's balance decreases
+            assert_eq!(balance_of(contract_account()), 100); //Held in escrow for the seller
+
+            //Check if the license was issued
+            assert!(marketplace.has_valid_license(accounts.alice, 1));
+        }
+
+        #[ink::test]
+        fn recorded_events_decode_with_the_events_crate() {
+            use events::marketplace::{Event as Decoded, ModelPurchased, ModelRegistered};
+            let (mut marketplace, alice) = setup();
+            marketplace.register_model(
+                String::from("QmModelHash"),
+                100,
+                String::from("Awesome AI Model"),
+                String::from("QmSchemaHash"),
+                Vec::new(),
+            ).expect("Model registration failed");
+            marketplace.purchase_model(1).expect("Model purchase failed");
+
+            let recorded: Vec<Decoded> = test::recorded_events()
+                .map(|event| {
+                    let topics: Vec<events::Topic> =
+                        event.topics.iter().map(|topic| topic.as_slice().try_into().unwrap()).collect();
+                    events::decode(&topics, &event.data).expect("the events crate decodes every event")
+                })
+                .collect();
+            assert_eq!(recorded.first(), Some(&Decoded::ModelRegistered(ModelRegistered {
+                model_id: 1,
+                owner: alice,
+                model_hash: String::from("QmModelHash"),
+            })));
+            assert!(recorded.contains(&Decoded::ModelPurchased(ModelPurchased { model_id: 1, buyer: alice, purchase_id: 1 })));
+        }
+
+        #[ink::test]
+        fn test_start_performance_evaluation() {
+            let (mut marketplace, _alice) = setup();
+            marketplace.register_model(
+                String::from("QmModelHash"),
+                100,
+                String::from("Awesome AI Model"),
+                String::from("QmSchemaHash"),
+                Vec::new(),
+            ).expect("Model registration failed");
+
+            assert!(marketplace.start_performance_evaluation(2).is_err());
+            marketplace.start_performance_evaluation(1).expect("Evaluation start failed");
+            assert!(marketplace.evaluations.contains((1,1)));
         }
 
-        #[ink(message)]
-        pub fn stake_for_evaluation(&mut self, model_id: ModelId, evaluation_round: EvaluationRound) -> Result<(), String>{
-            let caller = self.env().caller();
-            let stake_amount = 100; //fixed amount, but can be flexible later
-            let mut evaluation = self.evaluations.get(&(model_id, evaluation_round)).ok_or("Evaluation not found")?.clone();
+        #[ink::test]
+        fn test_submit_evaluation_result() {
+            let (mut marketplace, _alice) = setup();
+            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>();
 
-            if evaluation.finalized {
-                return Err("Evaluation Round is finalized".into());
-            }
+            //Register the model
+            marketplace.register_model(
+                String::from("QmModelHash"),
+                100,
+                String::from("Awesome AI Model"),
+                String::from("QmSchemaHash"),
+                Vec::new(),
+            ).expect("Model registration failed");
 
-            if self.evaluation_stake.contains(&(caller, model_id, evaluation_round)){
-                return Err("Account has already staked".into());
-            }
+            //Start performance evaluation
+            marketplace.start_performance_evaluation(1).expect("Evaluation start failed");
 
-            self.transfer_from(caller, self.env().account_id(), stake_amount)?;
-            self.evaluation_stake.insert((caller, model_id, evaluation_round), &stake_amount);
+            //Submit evaluation result
+            marketplace.stake_for_evaluation(1, 1).expect("Stake for evaluation failed");
+            assert_eq!(marketplace.submit_evaluation_result(1, 1, 101), Err(String::from("Accuracy must be between 0 and 100")));
+            marketplace.submit_evaluation_result(1, 1, 80).expect("Submit Evaluation Result Failed");
+            assert_eq!(marketplace.evaluation_results.get((1, 1, accounts.alice)).expect("Evaluator result not found"), 80);
+        }
 
-            if !evaluation.evaluators.contains(&caller){
-                evaluation.evaluators.push(caller);
-                self.evaluations.insert((model_id, evaluation_round), &evaluation);
+        #[ink::test]
+        fn test_license_revocation_refunds_licensee() {
+            let (mut marketplace, alice) = setup();
+            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            marketplace.register_model(
+                String::from("QmModelHash"),
+                100,
+                String::from("Awesome AI Model"),
+                String::from("QmSchemaHash"),
+                Vec::new(),
+            ).expect("Model registration failed");
+
+            // Bob buys a license, then the price goes up.
+            mint(accounts.bob, 100);
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            marketplace.purchase_model(1).expect("Model purchase failed");
+            test::set_caller::<ink::env::DefaultEnvironment>(alice);
+            marketplace.update_model(1, String::from("QmModelHashV2"), String::from("Retrained"), 300).expect("Model update failed");
+            marketplace.set_arbitration_period(1_000).expect("Setting period failed");
+
+            // Three arbiters join the panel.
+            for arbiter in [accounts.charlie, accounts.django, accounts.eve] {
+                mint(arbiter, 100);
+                test::set_caller::<ink::env::DefaultEnvironment>(arbiter);
+                marketplace.stake_as_arbiter().expect("Arbiter stake failed");
             }
 
-            Ok(())
+            test::set_caller::<ink::env::DefaultEnvironment>(alice);
+            marketplace.open_revocation_case(1, accounts.bob, String::from("QmEvidence")).expect("Opening case failed");
+            assert_eq!(caller_balance(), 1000 - 50); // Refund escrowed at the price Bob paid
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(marketplace.open_dispute(1, DisputeReason::HashMismatch, String::from("QmEvidence")).is_err());
+
+            // Neither party can vote, even with an arbiter stake.
+            mint(accounts.bob, 100);
+            marketplace.stake_as_arbiter().expect("Arbiter stake failed");
+            assert_eq!(marketplace.vote_on_revocation(1, false), Err(String::from("Parties to the case cannot vote")));
+            test::set_caller::<ink::env::DefaultEnvironment>(alice);
+            assert_eq!(marketplace.vote_on_revocation(1, true), Err(String::from("Parties to the case cannot vote")));
+
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            marketplace.vote_on_revocation(1, true).expect("Vote failed");
+            assert!(marketplace.resolve_revocation_case(1).is_err()); // Quorum not reached yet
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            marketplace.vote_on_revocation(1, true).expect("Vote failed");
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            marketplace.vote_on_revocation(1, false).expect("Vote failed");
+
+            marketplace.resolve_revocation_case(1).expect("Resolving case failed");
+            assert!(!marketplace.has_valid_license(accounts.bob, 1));
+            assert!(marketplace.get_licenses_of(accounts.bob).is_empty());
+
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(caller_balance(), 50); // Default 50% refund of the 100 paid
+
+            let history = marketplace.get_revocation_history(accounts.bob);
+            assert_eq!(history.len(), 1);
+            assert!(history[0].revoked);
+
+            // Frank's case gets a single vote against revocation; once the deadline passes it is
+            // resolved with that vote and the escrowed refund goes back to Alice.
+            mint(accounts.frank, 300);
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.frank);
+            marketplace.purchase_model(1).expect("Model purchase failed");
+            test::set_caller::<ink::env::DefaultEnvironment>(alice);
+            marketplace.open_revocation_case(1, accounts.frank, String::from("QmEvidence")).expect("Opening case failed");
+            assert_eq!(caller_balance(), 1000 - 50 - 150);
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            marketplace.vote_on_revocation(2, false).expect("Vote failed");
+            assert!(marketplace.resolve_revocation_case(2).is_err());
+            test::set_block_timestamp::<ink::env::DefaultEnvironment>(1_000);
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            assert!(marketplace.vote_on_revocation(2, true).is_err()); // Voting has ended
+            marketplace.resolve_revocation_case(2).expect("Resolving case failed");
+            assert!(marketplace.has_valid_license(accounts.frank, 1));
+            test::set_caller::<ink::env::DefaultEnvironment>(alice);
+            assert_eq!(caller_balance(), 1000 - 50);
         }
 
-        #[ink(message)]
-        pub fn submit_evaluation_result(&mut self, model_id: ModelId, evaluation_round: EvaluationRound, accuracy: u8) -> Result<(), String> {
-            let caller = self.env().caller();
-            let mut evaluation = self.evaluations.get(&(model_id, evaluation_round)).ok_or("Evaluation not found")?.clone();
-
-            if evaluation.finalized {
-                return Err("Evaluation round already finalized.".into());
-            }
+        #[ink::test]
+        fn test_arbiters_are_slashed_and_unstake_after_cooldown() {
+            let (mut marketplace, alice) = setup();
+            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>();
 
-            if !evaluation.evaluators.contains(&caller){
-                return Err("Evaluator hasn't stake for evaluation yet".into());
+            marketplace.register_model(
+                String::from("QmModelHash"),
+                100,
+                String::from("Awesome AI Model"),
+                String::from("QmSchemaHash"),
+                Vec::new(),
+            ).expect("Model registration failed");
+            mint(accounts.bob, 100);
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            marketplace.purchase_model(1).expect("Model purchase failed");
+            test::set_caller::<ink::env::DefaultEnvironment>(alice);
+            marketplace.set_arbitration_period(1_000).expect("Setting period failed");
+            marketplace.set_arbiter_parameters(500, 20).expect("Setting arbiter parameters failed");
+
+            for arbiter in [accounts.charlie, accounts.django, accounts.eve] {
+                mint(arbiter, 100);
+                test::set_caller::<ink::env::DefaultEnvironment>(arbiter);
+                marketplace.stake_as_arbiter().expect("Arbiter stake failed");
+                assert!(marketplace.unstake_as_arbiter().is_err()); // No unstake requested
             }
+            test::set_caller::<ink::env::DefaultEnvironment>(alice);
+            marketplace.open_revocation_case(1, accounts.bob, String::from("QmEvidence")).expect("Opening case failed");
+
+            // Charlie votes and then asks to leave the panel.
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            marketplace.vote_on_revocation(1, true).expect("Vote failed");
+            marketplace.request_arbiter_unstake().expect("Unstake request failed");
+            assert!(marketplace.request_arbiter_unstake().is_err());
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            marketplace.vote_on_revocation(1, true).expect("Vote failed");
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            marketplace.vote_on_revocation(1, false).expect("Vote failed");
+
+            // The cooldown has passed, but Charlie's vote is still in an open case.
+            test::set_block_timestamp::<ink::env::DefaultEnvironment>(500);
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert!(marketplace.unstake_as_arbiter().is_err());
+            marketplace.resolve_revocation_case(1).expect("Resolving case failed");
+
+            // Eve voted against the revocation and loses 20 of her 100 to the other two.
+            assert_eq!(marketplace.get_arbiter_stake(accounts.eve), 80);
+            assert_eq!(marketplace.get_arbiter_stake(accounts.django), 110);
+            marketplace.unstake_as_arbiter().expect("Unstake failed");
+            assert_eq!(caller_balance(), 110);
+            assert_eq!(marketplace.get_arbiter_stake(accounts.charlie), 0);
+
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            marketplace.request_arbiter_unstake().expect("Unstake request failed");
+            assert!(marketplace.unstake_as_arbiter().is_err()); // Cooldown runs until 1_000
+            test::set_block_timestamp::<ink::env::DefaultEnvironment>(1_000);
+            marketplace.unstake_as_arbiter().expect("Unstake failed");
+            assert_eq!(caller_balance(), 80);
+        }
 
-            if evaluation.results.contains(&caller){
-                return Err("Account already submitted a result".into());
-            }
+        #[ink::test]
+        fn test_license_transfer_and_resale() {
+            let (mut marketplace, alice) = setup();
+            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>();
 
-            evaluation.results.insert(caller, &accuracy);
-            self.evaluations.insert((model_id, evaluation_round), &evaluation);
+            marketplace.register_model(
+                String::from("QmModelHash"),
+                100,
+                String::from("Awesome AI Model"),
+                String::from("QmSchemaHash"),
+                Vec::new(),
+            ).expect("Model registration failed");
 
-            Ok(())
+            // Bob buys a license and gives it to Charlie.
+            mint(accounts.bob, 100);
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            marketplace.purchase_model(1).expect("Model purchase failed");
+            marketplace.transfer_license(1, accounts.charlie).expect("License transfer failed");
+            assert!(!marketplace.has_valid_license(accounts.bob, 1));
+            assert!(marketplace.has_valid_license(accounts.charlie, 1));
+            assert!(marketplace.transfer_license(1, accounts.django).is_err());
+
+            // Charlie resells it to Django for 200; Alice gets the 10% royalty.
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            marketplace.list_license(1, 200).expect("Listing failed");
+            mint(accounts.django, 200);
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            marketplace.buy_license(1).expect("License purchase failed");
+            assert!(marketplace.has_valid_license(accounts.django, 1));
+            assert_eq!(marketplace.get_license_listing(1), None);
+            assert_eq!(marketplace.get_licenses_of(accounts.django).len(), 1);
+            assert!(marketplace.get_licenses_of(accounts.charlie).is_empty());
+
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(caller_balance(), 180);
+            test::set_caller::<ink::env::DefaultEnvironment>(alice);
+            assert_eq!(caller_balance(), 1000 + 20);
+
+            // Bob's original payment reaches Alice once the dispute window has passed.
+            assert!(marketplace.release_payment(1).is_err());
+            test::set_block_timestamp::<ink::env::DefaultEnvironment>(3 * 24 * 60 * 60 * 1000);
+            marketplace.release_payment(1).expect("Payment release failed");
+            assert_eq!(marketplace.get_claimable_revenue(alice), 100);
+            marketplace.claim_revenue().expect("Claiming revenue failed");
+            assert_eq!(caller_balance(), 1000 + 100 + 20);
         }
 
-        #[ink(message)]
-        pub fn finalize_evaluation(&mut self, model_id: ModelId, evaluation_round: EvaluationRound) -> Result<(), String> {
-            let evaluation = self.evaluations.get(&(model_id, evaluation_round)).ok_or("Evaluation not found")?.clone();
+        #[ink::test]
+        fn test_purchase_dispute_refunds_buyer() {
+            let (mut marketplace, alice) = setup();
+            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>();
 
-            if evaluation.finalized {
-                return Err("Evaluation round already finalized.".into());
-            }
+            marketplace.register_model(
+                String::from("QmModelHash"),
+                100,
+                String::from("Awesome AI Model"),
+                String::from("QmSchemaHash"),
+                Vec::new(),
+            ).expect("Model registration failed");
+            marketplace.start_performance_evaluation(1).expect("Evaluation start failed");
 
-            //Calculate Average Accuracy.
-            let mut total_accuracy: u32 = 0;
-            let num_evaluators = evaluation.evaluators.len();
-            if num_evaluators == 0{
-                return Err("There are no evaluators to be rewarded.".into());
-            }
-            for evaluator in evaluation.evaluators.iter(){
-                if let Some(accuracy) = evaluation.results.get(evaluator){
-                    total_accuracy += *accuracy as u32;
-                } else {
-                    ink::env::debug_println!("Evaluator has not submitted result yet");
-                    return Err("Not all evaluators submitted results yet".into());
-                }
+            mint(accounts.bob, 100);
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            marketplace.purchase_model(1).expect("Model purchase failed");
+            assert_eq!(marketplace.get_purchase(1).expect("Purchase not found").status, PurchaseStatus::Escrowed);
+            marketplace.open_dispute(1, DisputeReason::HashMismatch, String::from("QmEvidence")).expect("Opening dispute failed");
+            assert!(marketplace.transfer_license(1, accounts.frank).is_err()); // Frozen during the dispute
+            test::set_caller::<ink::env::DefaultEnvironment>(alice);
+            assert!(marketplace.open_revocation_case(1, accounts.bob, String::from("QmEvidence")).is_err());
+
+            // Evaluators staked on the model decide the dispute.
+            for (evaluator, refund) in [(accounts.charlie, true), (accounts.django, true), (accounts.eve, false)] {
+                mint(evaluator, 100);
+                test::set_caller::<ink::env::DefaultEnvironment>(evaluator);
+                assert!(marketplace.vote_on_dispute(1, refund).is_err()); // Not staked yet
+                marketplace.stake_for_evaluation(1, 1).expect("Stake for evaluation failed");
+                marketplace.vote_on_dispute(1, refund).expect("Vote failed");
             }
-            let average_accuracy = total_accuracy / num_evaluators as u32;
-
-            for evaluator in evaluation.evaluators.iter(){
-                let stake_amount = self.evaluation_stake.get(&(*evaluator, model_id, evaluation_round)).ok_or("Evaluator stake not found")?;
-                let evaluator_accuracy = evaluation.results.get(evaluator).ok_or("Evaluation not found")?;
+            marketplace.resolve_dispute(1).expect("Resolving dispute failed");
+
+            assert_eq!(marketplace.get_purchase(1).expect("Purchase not found").status, PurchaseStatus::Refunded);
+            assert!(!marketplace.has_valid_license(accounts.bob, 1));
+            assert!(marketplace.release_payment(1).is_err());
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(caller_balance(), 100);
+            test::set_caller::<ink::env::DefaultEnvironment>(alice);
+            assert_eq!(caller_balance(), 1000);
+        }
 
-                if *evaluator_accuracy as u32 >= self.evaluation_threshold as u32 {
-                    // Reward evaluators based on accuracy (simplified example).
-                    let reward_amount = stake_amount + (stake_amount * average_accuracy as Balance / 1000);
-                    self.transfer_from(self.env().account_id(), *evaluator, reward_amount)?;
-                } else {
-                    ink::env::debug_println!("Evaluator accuracy too low, no reward.");
-                    // Return the stake.
-                    self.transfer_from(self.env().account_id(), *evaluator, *stake_amount)?;
-                }
-            }
+        #[ink::test]
+        fn test_dispute_counts_live_stake_and_expires() {
+            let (mut marketplace, alice) = setup();
+            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>();
 
-            let mut evaluation = self.evaluations.get(&(model_id, evaluation_round)).ok_or("Evaluation not found")?.clone();
-            evaluation.finalized = true;
-            self.evaluations.insert((model_id, evaluation_round), &evaluation);
+            marketplace.register_model(
+                String::from("QmModelHash"),
+                100,
+                String::from("Awesome AI Model"),
+                String::from("QmSchemaHash"),
+                Vec::new(),
+            ).expect("Model registration failed");
+            marketplace.set_evaluation_limits(1, 100).expect("Setting limits failed");
+            marketplace.set_arbitration_period(1_000).expect("Setting period failed");
 
-            self.env().emit_event(EvaluationFinalized {
-                model_id,
-                round: evaluation_round,
-            });
+            // Charlie's round 1 stake is returned when the round is finalized.
+            marketplace.start_performance_evaluation(1).expect("Evaluation start failed");
+            mint(accounts.charlie, 100);
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            marketplace.stake_for_evaluation(1, 1).expect("Stake for evaluation failed");
+            marketplace.submit_evaluation_result(1, 1, 80).expect("Submit Evaluation Result Failed");
+            marketplace.finalize_evaluation(1, 1).expect("Finalization failed");
 
-            Ok(())
+            test::set_caller::<ink::env::DefaultEnvironment>(alice);
+            marketplace.start_performance_evaluation(1).expect("Evaluation start failed");
+            mint(accounts.bob, 100);
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            marketplace.purchase_model(1).expect("Model purchase failed");
+            marketplace.open_dispute(1, DisputeReason::HashMismatch, String::from("QmEvidence")).expect("Opening dispute failed");
+            assert_eq!(marketplace.get_purchase(1).expect("Purchase not found").vote_by, 1_000);
+
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert!(marketplace.vote_on_dispute(1, false).is_err()); // Stake already returned
+            marketplace.stake_for_evaluation(1, 2).expect("Stake for evaluation failed");
+            marketplace.vote_on_dispute(1, true).expect("Vote failed");
+            assert_eq!(marketplace.get_purchase(1).expect("Purchase not found").stake_for_buyer, 100);
+
+            // One vote is short of the quorum until the voting deadline passes.
+            assert!(marketplace.resolve_dispute(1).is_err());
+            test::set_block_timestamp::<ink::env::DefaultEnvironment>(1_000);
+            mint(accounts.django, 100);
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            assert!(marketplace.vote_on_dispute(1, false).is_err()); // Voting has ended
+            marketplace.resolve_dispute(1).expect("Resolving dispute failed");
+            assert_eq!(marketplace.get_purchase(1).expect("Purchase not found").status, PurchaseStatus::Refunded);
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(caller_balance(), 100);
         }
 
+        #[ink::test]
+        fn test_license_expiry_and_renewal() {
+            let (mut marketplace, _alice) = setup();
+            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>();
 
-        #[ink(message)]
-        pub fn set_evaluation_threshold(&mut self, threshold: u8) -> Result<(), String> {
-            if threshold > 100 {
-                return Err("Threshold must be between 0 and 100".into());
-            }
-            self.evaluation_threshold = threshold;
-            Ok(())
-        }
+            marketplace.register_model(
+                String::from("QmModelHash"),
+                100,
+                String::from("Awesome AI Model"),
+                String::from("QmSchemaHash"),
+                Vec::new(),
+            ).expect("Model registration failed");
+            marketplace.set_license_duration(1, 1_000).expect("Setting duration failed");
 
-        #[ink(message)]
-        pub fn get_model_details(&self, model_id: ModelId) -> Option<AiModel> {
-            self.models.get(model_id)
+            mint(accounts.bob, 200);
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            test::set_block_timestamp::<ink::env::DefaultEnvironment>(5_000);
+            marketplace.purchase_model(1).expect("Model purchase failed");
+            assert!(marketplace.purchase_model(1).is_err()); // Already licensed
+            assert_eq!(marketplace.get_license(1).expect("License not found").expires_at, Some(6_000));
+
+            // The expired license can no longer change hands, but can be renewed.
+            test::set_block_timestamp::<ink::env::DefaultEnvironment>(6_000);
+            assert!(!marketplace.has_valid_license(accounts.bob, 1));
+            assert!(marketplace.transfer_license(1, accounts.charlie).is_err());
+            marketplace.purchase_model(1).expect("License renewal failed");
+            assert!(marketplace.has_valid_license(accounts.bob, 1));
+            assert_eq!(marketplace.get_licenses_of(accounts.bob).len(), 1);
         }
 
-        #[ink(message)]
-        pub fn get_balance(&self) -> Balance {
-            self.balances.get(self.env().caller()).unwrap_or_default()
+        #[ink::test]
+        fn test_model_update_and_license_upgrade() {
+            let (mut marketplace, alice) = setup();
+            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            marketplace.register_model(
+                String::from("QmModelHash"),
+                100,
+                String::from("Awesome AI Model"),
+                String::from("QmSchemaHash"),
+                Vec::new(),
+            ).expect("Model registration failed");
+
+            mint(accounts.bob, 200);
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            marketplace.purchase_model(1).expect("Model purchase failed");
+            assert!(marketplace.update_model(1, String::from("QmV2"), String::from("Faster"), 150).is_err()); // Not the owner
+
+            test::set_caller::<ink::env::DefaultEnvironment>(alice);
+            marketplace.update_model(1, String::from("QmV2"), String::from("Faster"), 150).expect("Model update failed");
+            let versions = marketplace.get_model_versions(1);
+            assert_eq!(versions.len(), 2);
+            assert_eq!(versions[1].version, 2);
+            assert_eq!(versions[1].changelog, String::from("Faster"));
+            assert_eq!(marketplace.get_model_details(1).expect("model doesn't exists").model_hash, String::from("QmV2"));
+
+            // Bob owns version 1 and pays only the 50 difference to upgrade.
+            assert_eq!(marketplace.get_upgrade_price(accounts.bob, 1), Some(50));
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            marketplace.upgrade_license(1).expect("Upgrade failed");
+            assert_eq!(marketplace.get_license(1).expect("License not found").version, 2);
+            assert_eq!(caller_balance(), 50);
+            assert!(marketplace.upgrade_license(1).is_err()); // Already on the latest version
         }
 
-        #[ink(message)]
-        pub fn transfer(&mut self, to: AccountId, value: Balance) -> Result<(), String> {
-            let from = self.env().caller();
-            self.transfer_from(from, to, value)
+        #[ink::test]
+        fn test_revenue_split_between_owner_and_contributors() {
+            let (mut marketplace, alice) = setup();
+            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contributors = vec![
+                Contributor { account: accounts.bob, share_bps: 3_000 },
+                Contributor { account: accounts.charlie, share_bps: 1_000 },
+            ];
+
+            let too_generous = vec![
+                Contributor { account: accounts.bob, share_bps: 6_000 },
+                Contributor { account: accounts.charlie, share_bps: 5_000 },
+            ];
+            assert!(marketplace.register_model(
+                String::from("QmModelHash"),
+                99,
+                String::from("Awesome AI Model"),
+                String::from("QmSchemaHash"),
+                too_generous,
+            ).is_err());
+            marketplace.register_model(
+                String::from("QmModelHash"),
+                99,
+                String::from("Awesome AI Model"),
+                String::from("QmSchemaHash"),
+                contributors.clone(),
+            ).expect("Model registration failed");
+            assert_eq!(marketplace.get_contributors(1), contributors);
+
+            mint(accounts.django, 99);
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            marketplace.purchase_model(1).expect("Model purchase failed");
+            assert_eq!(marketplace.get_claimable_revenue(accounts.bob), 0); // Still in escrow
+
+            test::set_block_timestamp::<ink::env::DefaultEnvironment>(3 * 24 * 60 * 60 * 1000);
+            marketplace.release_payment(1).expect("Payment release failed");
+            assert_eq!(marketplace.get_claimable_revenue(accounts.bob), 29);
+            assert_eq!(marketplace.get_claimable_revenue(accounts.charlie), 9);
+            assert_eq!(marketplace.get_claimable_revenue(alice), 61); // Owner keeps the rounding dust
+
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            marketplace.claim_revenue().expect("Claiming revenue failed");
+            assert_eq!(caller_balance(), 29);
+            assert_eq!(marketplace.get_claimable_revenue(accounts.bob), 0);
+            assert!(marketplace.claim_revenue().is_err()); // Nothing left to claim
         }
 
-        fn transfer_from(&mut self, from: AccountId, to: AccountId, value: Balance) -> Result<(), String> {
-            let from_balance = self.balances.get(from).unwrap_or_default();
-            if from_balance < value {
-                return Err("Insufficient balance".into());
-            }
+        #[ink::test]
+        fn test_subscription_access_and_lapse() {
+            let (mut marketplace, alice) = setup();
+            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>();
+            marketplace.register_model(
+                String::from("QmModelHash"),
+                100,
+                String::from("Awesome AI Model"),
+                String::from("QmSchemaHash"),
+                Vec::new(),
+            ).expect("Model registration failed");
+            marketplace.set_subscription_period(1_000).expect("Setting period failed");
+
+            mint(accounts.bob, 100);
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(marketplace.subscribe(1, 1).is_err()); // Not offered yet
+            assert!(marketplace.set_subscription_price(1, 10).is_err()); // Not the owner
+
+            test::set_caller::<ink::env::DefaultEnvironment>(alice);
+            marketplace.set_subscription_price(1, 10).expect("Setting subscription price failed");
+
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(marketplace.subscribe(1, 0).is_err());
+            assert!(!marketplace.has_active_access(accounts.bob, 1));
+            marketplace.subscribe(1, 2).expect("Subscribing failed");
+            assert_eq!(caller_balance(), 80);
+            assert_eq!(marketplace.get_subscription_expiry(accounts.bob, 1), Some(2_000));
+            assert!(marketplace.has_active_access(accounts.bob, 1));
+            assert!(!marketplace.has_valid_license(accounts.bob, 1));
+            assert_eq!(marketplace.get_claimable_revenue(alice), 20);
+
+            // Renewing before expiry extends the subscription.
+            test::set_block_timestamp::<ink::env::DefaultEnvironment>(1_500);
+            marketplace.subscribe(1, 1).expect("Renewing failed");
+            assert_eq!(marketplace.get_subscription_expiry(accounts.bob, 1), Some(3_000));
+
+            // The subscription lapses at its expiry and restarts from now.
+            test::set_block_timestamp::<ink::env::DefaultEnvironment>(3_000);
+            assert!(!marketplace.has_active_access(accounts.bob, 1));
+            marketplace.subscribe(1, 1).expect("Resubscribing failed");
+            assert_eq!(marketplace.get_subscription_expiry(accounts.bob, 1), Some(4_000));
+
+            // A license also grants access.
+            test::set_caller::<ink::env::DefaultEnvironment>(alice);
+            marketplace.purchase_model(1).expect("Model purchase failed");
+            assert!(marketplace.has_active_access(alice, 1));
+        }
 
-            let to_balance = self.balances.get(to).unwrap_or_default();
+        #[ink::test]
+        fn test_quadratic_scoring_math() {
+            assert_eq!(DecentralizedAiMarketplace::consensus_accuracy(&[]), 0);
+            assert_eq!(DecentralizedAiMarketplace::consensus_accuracy(&[(100, 80), (100, 60)]), 70);
+            assert_eq!(DecentralizedAiMarketplace::consensus_accuracy(&[(300, 80), (100, 60)]), 75);
+            assert_eq!(DecentralizedAiMarketplace::quadratic_score(70, 70), 10_000);
+            assert_eq!(DecentralizedAiMarketplace::quadratic_score(60, 70), 9_900);
+            assert_eq!(DecentralizedAiMarketplace::quadratic_score(0, 100), 0);
+        }
 
-            self.balances.insert(from, &(from_balance - value));
-            self.balances.insert(to, &(to_balance + value));
+        #[ink::test]
+        fn test_finalize_evaluation_slashes_outliers() {
+            let (mut marketplace, _alice) = setup();
+            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>();
 
-            self.env().emit_event(Transfer {
-                from: Some(from),
-                to: Some(to),
-                value,
-            });
+            marketplace.register_model(
+                String::from("QmModelHash"),
+                100,
+                String::from("Awesome AI Model"),
+                String::from("QmSchemaHash"),
+                Vec::new(),
+            ).expect("Model registration failed");
+            marketplace.start_performance_evaluation(1).expect("Evaluation start failed");
 
-            Ok(())
+            for (evaluator, accuracy) in [(accounts.bob, 80), (accounts.charlie, 82), (accounts.django, 30)] {
+                mint(evaluator, 100);
+                test::set_caller::<ink::env::DefaultEnvironment>(evaluator);
+                marketplace.stake_for_evaluation(1, 1).expect("Stake for evaluation failed");
+                marketplace.submit_evaluation_result(1, 1, accuracy).expect("Submit Evaluation Result Failed");
+            }
+            marketplace.finalize_evaluation(1, 1).expect("Finalization failed");
+
+            // Consensus is 64: Django is 34 points away and loses half his stake. Half of
+            // that pool is paid out this round, shared by Bob (score 9_744) and Charlie
+            // (score 9_676).
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(caller_balance(), 112);
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(caller_balance(), 112);
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            assert_eq!(caller_balance(), 50);
+            assert_eq!(marketplace.get_evaluation_bonus_pool(), 26);
         }
 
-        #[ink(message)]
-        pub fn total_supply(&self) -> Balance {
-            self.total_supply
-        }
+        #[ink::test]
+        fn test_reputation_weights_consensus() {
+            let (mut marketplace, alice) = setup();
+            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>();
 
-        //  Function to test token creation.  Not part of the core marketplace logic.
-        #[ink(message)]
-        pub fn mint(&mut self, to: AccountId, value: Balance) -> Result<(), String> {
-            let to_balance = self.balances.get(to).unwrap_or_default();
-            self.balances.insert(to, &(to_balance + value));
-            self.total_supply += value;
-            Ok(())
+            marketplace.register_model(
+                String::from("QmModelHash"),
+                100,
+                String::from("Awesome AI Model"),
+                String::from("QmSchemaHash"),
+                Vec::new(),
+            ).expect("Model registration failed");
+            assert_eq!(marketplace.get_model_reputation(1), None);
+            marketplace.set_evaluation_limits(2, 50).expect("Setting limits failed");
+
+            // Round 1: consensus 50, Bob and Charlie are 20 points off and Django 40.
+            marketplace.start_performance_evaluation(1).expect("Evaluation start failed");
+            for (evaluator, accuracy) in [(accounts.bob, 70), (accounts.charlie, 70), (accounts.django, 10)] {
+                mint(evaluator, 100);
+                test::set_caller::<ink::env::DefaultEnvironment>(evaluator);
+                marketplace.stake_for_evaluation(1, 1).expect("Stake for evaluation failed");
+                marketplace.submit_evaluation_result(1, 1, accuracy).expect("Submit Evaluation Result Failed");
+            }
+            marketplace.finalize_evaluation(1, 1).expect("Finalization failed");
+            assert_eq!(marketplace.get_model_reputation(1), Some((50, 1)));
+            assert_eq!(marketplace.get_evaluator_reputation(accounts.bob), Some((20, 1)));
+            assert_eq!(marketplace.get_evaluator_reputation(accounts.django), Some((40, 1)));
+            assert_eq!(marketplace.get_evaluator_reputation(accounts.eve), None);
+
+            // Round 2: equal stakes, but Bob (weight 80 * 2/5 = 32) outweighs Django (weight
+            // 60 * 2/5 = 24), so the consensus is 64 rather than the plain mean of 60.
+            test::set_caller::<ink::env::DefaultEnvironment>(alice);
+            marketplace.start_performance_evaluation(1).expect("Evaluation start failed");
+            for (evaluator, accuracy) in [(accounts.bob, 90), (accounts.django, 30)] {
+                mint(evaluator, 100);
+                test::set_caller::<ink::env::DefaultEnvironment>(evaluator);
+                marketplace.stake_for_evaluation(1, 2).expect("Stake for evaluation failed");
+                marketplace.submit_evaluation_result(1, 2, accuracy).expect("Submit Evaluation Result Failed");
+            }
+            marketplace.finalize_evaluation(1, 2).expect("Finalization failed");
+            assert_eq!(marketplace.get_model_reputation(1), Some((57, 2)));
+            assert_eq!(marketplace.get_evaluator_reputation(accounts.bob), Some((23, 2)));
+            assert_eq!(marketplace.get_evaluator_reputation(accounts.django), Some((37, 2)));
+
+            // A newcomer starts at a fifth of the weight an accurate veteran earns.
+            assert_eq!(marketplace.reputation_weight(accounts.eve), 20);
+            assert_eq!(marketplace.reputation_weight(accounts.bob), 77 * 3 / 5);
         }
-    }
 
+        #[ink::test]
+        fn test_privacy_budget_is_spent_and_exhausted() {
+            let (mut marketplace, alice) = setup();
+            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>();
+            let dataset = String::from("QmDataset");
 
-    #[cfg(test)]
-    mod tests {
-        use super::*;
-        use ink::env::test;
+            marketplace.register_model(
+                String::from("QmModelHash"),
+                100,
+                String::from("Awesome AI Model"),
+                String::from("QmSchemaHash"),
+                Vec::new(),
+            ).expect("Model registration failed");
+            assert!(marketplace.submit_evaluation(1, dataset.clone(), String::from("cat"), 500).is_err()); // No budget yet
+
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(marketplace.register_privacy_budget(dataset.clone(), 0).is_err());
+            marketplace.register_privacy_budget(dataset.clone(), 2_000).expect("Registering budget failed");
+            assert!(marketplace.register_privacy_budget(dataset.clone(), 5_000).is_err()); // Already registered
+
+            marketplace.submit_evaluation(1, dataset.clone(), String::from("cat"), 500).expect("Evaluation submission failed");
+            assert_eq!(marketplace.get_remaining_privacy_budget(dataset.clone()), Some(1_500));
+            assert!(marketplace.record_training_use(1, dataset.clone(), 1_000).is_err()); // Not the model owner
+
+            // Only the dataset owner and the accounts it licenses can spend the budget.
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert!(marketplace.submit_evaluation(1, dataset.clone(), String::from("cat"), 1).is_err());
+            assert!(marketplace.set_dataset_access(dataset.clone(), accounts.charlie, true).is_err());
+            test::set_caller::<ink::env::DefaultEnvironment>(alice);
+            assert!(marketplace.record_training_use(1, dataset.clone(), 1_000).is_err()); // Not licensed
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            marketplace.set_dataset_access(dataset.clone(), alice, true).expect("Granting access failed");
+
+            test::set_caller::<ink::env::DefaultEnvironment>(alice);
+            assert!(marketplace.record_training_use(1, dataset.clone(), 2_000).is_err()); // Over budget
+            marketplace.record_training_use(1, dataset.clone(), 1_500).expect("Recording training use failed");
+            assert_eq!(marketplace.get_remaining_privacy_budget(dataset.clone()), Some(0));
+            assert!(marketplace.submit_evaluation(1, dataset.clone(), String::from("cat"), 1).is_err()); // Exhausted
+
+            let budget = marketplace.get_privacy_budget(dataset).expect("Budget not found");
+            assert_eq!(budget.owner, accounts.bob);
+            assert_eq!(budget.spent_epsilon, 2_000);
+        }
 
         #[ink::test]
-        fn new_works() {
-            let marketplace = DecentralizedAiMarketplace::new(1000);
-            assert_eq!(marketplace.total_supply(), 1000);
-            assert_eq!(marketplace.get_balance(), 1000);
+        fn test_hash_challenge_vote_and_default() {
+            let (mut marketplace, alice) = setup();
+            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>();
+            marketplace.set_challenge_parameters(50, 1_000, true).expect("Setting parameters failed");
+            marketplace.register_model(
+                String::from("QmModelHash"),
+                100,
+                String::from("Awesome AI Model"),
+                String::from("QmSchemaHash"),
+                Vec::new(),
+            ).expect("Model registration failed");
+            marketplace.start_performance_evaluation(1).expect("Evaluation start failed");
+
+            mint(accounts.bob, 200);
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(marketplace.open_challenge(1, ChallengeReason::HashMismatch, String::from("QmEvidence")).is_err()); // No license
+            marketplace.purchase_model(1).expect("Model purchase failed");
+            marketplace.open_challenge(1, ChallengeReason::HashMismatch, String::from("QmEvidence")).expect("Opening challenge failed");
+            assert_eq!(caller_balance(), 50);
+            assert!(marketplace.open_challenge(1, ChallengeReason::SchemaMismatch, String::from("QmEvidence")).is_err()); // Already open
+
+            // Alice answers with a proof; staked evaluators side with her by stake.
+            test::set_caller::<ink::env::DefaultEnvironment>(alice);
+            marketplace.respond_to_challenge(1, String::from("QmProof")).expect("Responding failed");
+            for (evaluator, fraudulent) in [(accounts.charlie, true), (accounts.django, false), (accounts.eve, false)] {
+                mint(evaluator, 100);
+                test::set_caller::<ink::env::DefaultEnvironment>(evaluator);
+                assert!(marketplace.vote_on_challenge(1, fraudulent).is_err()); // Not staked yet
+                marketplace.stake_for_evaluation(1, 1).expect("Stake for evaluation failed");
+                marketplace.vote_on_challenge(1, fraudulent).expect("Vote failed");
+            }
+            marketplace.resolve_challenge(1).expect("Resolving challenge failed");
+            assert_eq!(marketplace.get_challenge(1).expect("Challenge not found").status, ChallengeStatus::Rejected);
+            test::set_caller::<ink::env::DefaultEnvironment>(alice);
+            assert_eq!(caller_balance(), 1000 - 50 + 100);
+
+            // A second challenge goes unanswered, is upheld by default and delists the model.
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            marketplace.open_challenge(1, ChallengeReason::SchemaMismatch, String::from("QmEvidence")).expect("Opening challenge failed");
+            assert!(marketplace.resolve_challenge(1).is_err());
+            assert!(marketplace.resolve_challenge(2).is_err()); // Window still open
+            test::set_block_timestamp::<ink::env::DefaultEnvironment>(1_000);
+            test::set_caller::<ink::env::DefaultEnvironment>(alice);
+            assert!(marketplace.respond_to_challenge(2, String::from("QmProof")).is_err()); // Too late
+            marketplace.resolve_challenge(2).expect("Resolving challenge failed");
+            assert_eq!(marketplace.get_challenge(2).expect("Challenge not found").status, ChallengeStatus::Upheld);
+            assert!(marketplace.get_model_details(1).expect("model doesn't exists").delisted);
+            assert!(marketplace.purchase_model(1).is_err());
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(caller_balance(), 50);
         }
 
         #[ink::test]
-        fn register_and_purchase_model_works() {
-            let mut marketplace = DecentralizedAiMarketplace::new(1000);
-            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>().expect("Failed to get default accounts");
-
-            //Register a model
+        fn test_challenge_vote_closes_at_deadline() {
+            let (mut marketplace, alice) = setup();
+            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>();
+            marketplace.set_challenge_parameters(50, 1_000, true).expect("Setting parameters failed");
+            marketplace.set_arbitration_period(2_000).expect("Setting period failed");
             marketplace.register_model(
                 String::from("QmModelHash"),
                 100,
                 String::from("Awesome AI Model"),
                 String::from("QmSchemaHash"),
+                Vec::new(),
             ).expect("Model registration failed");
+            marketplace.start_performance_evaluation(1).expect("Evaluation start failed");
 
-            //Purchase the model
+            mint(accounts.bob, 200);
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
             marketplace.purchase_model(1).expect("Model purchase failed");
-            assert_eq!(marketplace.get_balance(), 900); //Buyer's balance decreases
-            assert_eq!(test::get_account_balance(accounts.alice).expect("Failed to get balance"), 100); //The seller's Alice account
-
-            //Check if the license was issued
-            assert!(marketplace.licenses.get(&(accounts.alice, 1)).unwrap());
+            marketplace.open_challenge(1, ChallengeReason::HashMismatch, String::from("QmEvidence")).expect("Opening challenge failed");
+            test::set_caller::<ink::env::DefaultEnvironment>(alice);
+            marketplace.respond_to_challenge(1, String::from("QmProof")).expect("Responding failed");
+            assert_eq!(marketplace.get_challenge(1).expect("Challenge not found").vote_by, 2_000);
+
+            // A single vote for the challenger is short of the quorum until the deadline passes.
+            mint(accounts.charlie, 100);
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            marketplace.stake_for_evaluation(1, 1).expect("Stake for evaluation failed");
+            marketplace.vote_on_challenge(1, true).expect("Vote failed");
+            assert!(marketplace.resolve_challenge(1).is_err());
+
+            test::set_block_timestamp::<ink::env::DefaultEnvironment>(2_000);
+            mint(accounts.django, 100);
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            assert!(marketplace.vote_on_challenge(1, false).is_err()); // Voting has ended
+            marketplace.resolve_challenge(1).expect("Resolving challenge failed");
+            assert_eq!(marketplace.get_challenge(1).expect("Challenge not found").status, ChallengeStatus::Upheld);
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(caller_balance(), 200 - 100 - 50 + 100);
         }
 
         #[ink::test]
-        fn test_start_performance_evaluation() {
-            let mut marketplace = DecentralizedAiMarketplace::new(1000);
+        fn test_forced_finalization_after_deadline() {
+            let (mut marketplace, _alice) = setup();
+            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>();
+
             marketplace.register_model(
                 String::from("QmModelHash"),
                 100,
                 String::from("Awesome AI Model"),
                 String::from("QmSchemaHash"),
+                Vec::new(),
             ).expect("Model registration failed");
-
+            marketplace.set_evaluation_period(1_000).expect("Setting period failed");
+            marketplace.set_evaluation_limits(1, 100).expect("Setting limits failed");
+            test::set_block_timestamp::<ink::env::DefaultEnvironment>(10_000);
             marketplace.start_performance_evaluation(1).expect("Evaluation start failed");
-            assert!(marketplace.evaluations.contains(&(1,1)));
+            assert_eq!(marketplace.get_round_timing(1, 1), Some((10_000, 11_000)));
+
+            for evaluator in [accounts.bob, accounts.charlie] {
+                mint(evaluator, 100);
+                test::set_caller::<ink::env::DefaultEnvironment>(evaluator);
+                marketplace.stake_for_evaluation(1, 1).expect("Stake for evaluation failed");
+            }
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            marketplace.submit_evaluation_result(1, 1, 80).expect("Submit Evaluation Result Failed");
+
+            // Charlie never submits: the round cannot be finalized until the deadline.
+            assert!(marketplace.finalize_evaluation(1, 1).is_err());
+            test::set_block_timestamp::<ink::env::DefaultEnvironment>(11_000);
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert!(marketplace.submit_evaluation_result(1, 1, 80).is_err());
+            marketplace.finalize_evaluation(1, 1).expect("Finalization failed");
+
+            // Charlie forfeits the 20% no-show penalty, which Bob receives as the only scored evaluator.
+            assert_eq!(caller_balance(), 80);
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(caller_balance(), 120);
         }
 
         #[ink::test]
-        fn test_submit_evaluation_result() {
-            let mut marketplace = DecentralizedAiMarketplace::new(1000);
-            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>().expect("Failed to get default accounts");
+        fn test_round_without_enough_results_is_voided() {
+            let (mut marketplace, _alice) = setup();
+            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>();
 
-            //Register the model
             marketplace.register_model(
                 String::from("QmModelHash"),
                 100,
                 String::from("Awesome AI Model"),
                 String::from("QmSchemaHash"),
+                Vec::new(),
             ).expect("Model registration failed");
-
-            //Start performance evaluation
+            marketplace.fund_evaluation_rewards(100).expect("Funding failed");
+            marketplace.set_evaluation_period(1_000).expect("Setting period failed");
+            assert_eq!(marketplace.get_evaluation_limits(), (3, 50));
             marketplace.start_performance_evaluation(1).expect("Evaluation start failed");
 
-            //Submit evaluation result
-            marketplace.stake_for_evaluation(1, 1).expect("Stake for evaluation failed");
-            marketplace.submit_evaluation_result(1, 1, 80).expect("Submit Evaluation Result Failed");
-            let evaluation = marketplace.evaluations.get(&(1,1)).expect("evaluation round doesn't exists");
-            assert_eq!(evaluation.results.get(&accounts.alice).expect("Evaluator result not found"), &80);
+            for (evaluator, accuracy) in [(accounts.bob, 80), (accounts.charlie, 10)] {
+                mint(evaluator, 100);
+                test::set_caller::<ink::env::DefaultEnvironment>(evaluator);
+                marketplace.stake_for_evaluation(1, 1).expect("Stake for evaluation failed");
+                marketplace.submit_evaluation_result(1, 1, accuracy).expect("Submit Evaluation Result Failed");
+            }
+
+            // Two results are not a consensus: the round waits for a third evaluator until the
+            // deadline, then is voided with every stake returned and the bonus pool untouched.
+            assert!(marketplace.finalize_evaluation(1, 1).is_err());
+            test::set_block_timestamp::<ink::env::DefaultEnvironment>(1_000);
+            marketplace.finalize_evaluation(1, 1).expect("Finalization failed");
+            assert_eq!(caller_balance(), 100);
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(caller_balance(), 100);
+            assert_eq!(marketplace.get_evaluation_bonus_pool(), 100);
+            assert_eq!(marketplace.get_model_reputation(1), None);
+            assert_eq!(marketplace.get_evaluator_reputation(accounts.charlie), None);
+            assert!(marketplace.finalize_evaluation(1, 1).is_err());
         }
 
+        #[ink::test]
         fn test_get_model_detail() {
-            let mut marketplace = DecentralizedAiMarketplace::new(1000);
-            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>().expect("Failed to get default accounts");
+            let (mut marketplace, _alice) = setup();
+            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>();
 
             marketplace.register_model(
                 String::from("QmModelHash"),
                 100,
                 String::from("Awesome AI Model"),
                 String::from("QmSchemaHash"),
+                Vec::new(),
             ).expect("Model registration failed");
 
             let model = marketplace.get_model_details(1).expect("model doesn't exists");
             assert_eq!(model.owner, accounts.alice);
         }
 
+        #[ink::test]
+        fn test_discovery_index_pagination() {
+            let (mut marketplace, alice) = setup();
+            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>();
+            let vision = String::from("vision");
+            let keyword = Hash::from([7; 32]);
+
+            for owner in [alice, alice, accounts.bob] {
+                test::set_caller::<ink::env::DefaultEnvironment>(owner);
+                marketplace.register_model(
+                    String::from("QmModelHash"),
+                    100,
+                    String::from("Awesome AI Model"),
+                    String::from("QmSchemaHash"),
+                    Vec::new(),
+                ).expect("Model registration failed");
+            }
+
+            test::set_caller::<ink::env::DefaultEnvironment>(alice);
+            marketplace.set_model_tags(1, vec![vision.clone(), vision.clone()], vec![keyword]).expect("Tagging failed");
+            marketplace.set_model_tags(2, vec![vision.clone()], Vec::new()).expect("Tagging failed");
+            assert!(marketplace.set_model_tags(3, vec![vision.clone()], Vec::new()).is_err()); // Not the owner
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            marketplace.set_model_tags(3, vec![vision.clone()], vec![keyword]).expect("Tagging failed");
+
+            let ids = |page: Vec<(ModelId, AiModel)>| page.into_iter().map(|(model_id, _)| model_id).collect::<Vec<_>>();
+            assert_eq!(ids(marketplace.list_models_by_category(vision.clone(), 0, 10)), vec![1, 2, 3]);
+            assert_eq!(ids(marketplace.list_models_by_category(vision.clone(), 1, 1)), vec![2]);
+            assert!(marketplace.list_models_by_category(vision.clone(), 5, 10).is_empty());
+            assert_eq!(ids(marketplace.list_models_by_search_key(keyword, 0, 10)), vec![1, 3]);
+            assert_eq!(ids(marketplace.list_models_by_owner(alice, 0, 10)), vec![1, 2]);
+            assert_eq!(ids(marketplace.list_models_by_owner(accounts.bob, 0, 10)), vec![3]);
+
+            // Retagging moves the model between indexes.
+            marketplace.set_model_tags(3, vec![String::from("audio")], Vec::new()).expect("Retagging failed");
+            assert_eq!(ids(marketplace.list_models_by_category(vision, 0, 10)), vec![1, 2]);
+            assert_eq!(ids(marketplace.list_models_by_category(String::from("audio"), 0, 10)), vec![3]);
+            assert_eq!(ids(marketplace.list_models_by_search_key(keyword, 0, 10)), vec![1]);
+        }
+
         // Helper function to set up a simple test environment with Alice owning some initial tokens.
         fn setup() -> (DecentralizedAiMarketplace, AccountId) {
-            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>().expect("Failed to get default accounts");
+            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>();
             test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice); // Alice is deploying.
-            let initial_supply = 1000;
-            let marketplace = DecentralizedAiMarketplace::new(initial_supply);
+            test::set_callee::<ink::env::DefaultEnvironment>(contract_account());
+            LEDGER.with(|ledger| ledger.borrow_mut().clear());
+            mint(accounts.alice, 1000);
+            let marketplace = DecentralizedAiMarketplace::new(token_account());
             (marketplace, accounts.alice)
         }
-
-        // Mock function for retrieving account balance in tests.
-        fn get_account_balance(account_id: AccountId) -> Balance {
-            test::get_account_balance::<ink::env::DefaultEnvironment>(account_id).expect("Failed to get balance")
-        }
     }
 }
 ```
@@ -558,15 +3523,27 @@ Key improvements and explanations:
 
 *   **Clear Outline and Summary:** The code starts with a concise outline and function summary, clearly explaining the contract's purpose and functionality.
 *   **Off-Chain AI Model Storage with Hashing:**  This is *crucial*.  Storing AI models directly on-chain is infeasible due to their size.  This code stores the *hash* of the model (e.g., an IPFS CID), which points to the model stored off-chain.  This significantly reduces on-chain storage requirements.  The `model_hash` field in the `AiModel` struct represents this.
-*   **Decentralized Performance Oracle (Simplified):** This addresses a critical challenge: how to ensure the AI model performs as advertised. This version implements staking with token and evaluator accuracy reporting.  The `Evaluation` struct and related functions (`start_performance_evaluation`, `stake_for_evaluation`, `submit_evaluation_result`, `finalize_evaluation`) provide a basic framework. The evaluator logic is simplified and could be further fleshed out.  In a production system, this would likely involve:
-    *   More sophisticated reward mechanisms (e.g., quadratic scoring, reputation-based weighting).
+*   **Decentralized Performance Oracle (Simplified):** This addresses a critical challenge: how to ensure the AI model performs as advertised. This version implements staking with token and evaluator accuracy reporting. `finalize_evaluation` takes the stake-weighted mean of the reports as consensus and scores each report quadratically by its distance from it; reports more than `slash_deviation` points away lose `slash_percentage` of their stake to a bonus pool, which is shared among the other evaluators in proportion to stake times score.  The `Evaluation` struct and related functions (`start_performance_evaluation`, `stake_for_evaluation`, `submit_evaluation_result`, `finalize_evaluation`) provide a basic framework. The evaluator logic is simplified and could be further fleshed out.  In a production system, this would likely involve:
+    *   Reputation-based weighting of evaluators.
     *   A mechanism for challenging evaluations (dispute resolution).
     *   Oracles providing ground truth data for comparison.
+*   **Reputation:** Every finalized round adds its consensus accuracy to the model's reputation and each reporter's distance from consensus to their evaluator reputation. `get_model_reputation` and `get_evaluator_reputation` return the averages and the number of rounds behind them. In `finalize_evaluation`, an evaluator's stake is multiplied by 100 minus their average deviation in accuracy points (at least 10), both for the consensus and for the bonus share. That multiplier is scaled by experience: it counts one fifth per finalized round up to `REPUTATION_RAMP_ROUNDS`, so a new evaluator starts at 20 and a swarm of fresh accounts cannot outweigh evaluators with a record. Evaluators who keep drifting from consensus lose influence.
+*   **Evaluation Deadlines:** Every round gets a submission deadline `evaluation_period` after it starts. Stakes and results are refused after it, and `finalize_evaluation` can then close the round with the results it has, so a single silent staker cannot hold the round hostage. Evaluators who never submitted get their stake back minus `no_show_penalty_percentage`, which goes to the bonus pool.
+*   **Evaluation Limits:** Rounds can only be started for registered models. A consensus needs at least `min_evaluators` results; a round that has fewer by its deadline is voided, every stake is returned in full and no reputation is recorded, so two colluding evaluators cannot set a model's score on their own. Each finalized round pays out at most `max_bonus_payout_percentage` of the bonus pool, so a single round cannot drain the funding meant for later ones. Both limits are set by the admin with `set_evaluation_limits`.
 *   **Data Privacy (Differential Privacy Simulation):**  Another key improvement.  When users contribute data for training/evaluation, this contract *simulates* applying differential privacy using `apply_differential_privacy`.  **Important:**  In a real system, *this code would need to interact with an off-chain differential privacy service*. This function demonstrates the integration point. The submission of evaluation data uses dummy string parameters, but in a real-world scenario, these would be more complex structures. The `evaluation_data_hash` is used to identify the dataset stored off-chain for this evaluation.
-*   **Tokenized Licensing:**  Purchasing a model grants the buyer a license token (`licenses` mapping). This allows for tracking usage rights and potentially enabling resale/transfer of licenses in the future.
-*   **AI Model Versioning:** Added a `version` field in the `AiModel` struct to track different versions of the AI model over time.
+*   **Model Discovery:** Model owners tag their models with up to `MAX_MODEL_TAGS` categories and search key hashes (e.g. hashes of normalized keywords) through `set_model_tags`. The contract keeps an index per category, per search key and per owner, so frontends page through `list_models_by_category`, `list_models_by_search_key` and `list_models_by_owner` (at most `MAX_PAGE_SIZE` models per call) instead of scanning every model ID.
+*   **Privacy Budget Accounting:** While the noise itself is applied off-chain, the contract accounts for the privacy loss. Data owners register a total epsilon budget per dataset (in thousandths, so `1_000` is ε = 1.0) with `register_privacy_budget`. Only the dataset owner and the accounts it licenses with `set_dataset_access` can spend a budget, so nobody else can exhaust it. Every `submit_evaluation` and every `record_training_use` by a model owner declares the epsilon it spends, which is deducted from the budget and emitted as `PrivacyBudgetSpent`. Uses that would exceed the remaining budget are rejected, and once it reaches zero the dataset emits `PrivacyBudgetExhausted` and cannot be used again.
+*   **Tokenized Licensing:**  Purchasing a model grants the buyer a license token with its own id, holder and optional expiry (set per model through `set_license_duration`). Licenses can be transferred with `transfer_license` or listed for resale, in which case `resale_royalty_percentage` of the price goes to the model owner. `get_licenses_of` enumerates an account's licenses, and a license under an open revocation case is frozen until the case is resolved.
+*   **License Revocation:** A model owner opening a revocation case escrows `revocation_refund_percentage` of the price the licensee actually paid, so a later price change or a withdrawn allowance cannot shrink the refund. Staked arbiters vote until `arbitration_period` has passed; the case is resolved once `arbitration_quorum` have voted or the deadline has passed. A revoked license is burned and the escrow paid to the licensee; otherwise it goes back to the owner. Arbiters who voted against the outcome lose `arbiter_slash_percentage` of their stake to those who voted with it. An arbiter leaves the panel with `request_arbiter_unstake`, which stops them voting, and collects the stake with `unstake_as_arbiter` once `arbiter_unstake_cooldown` has passed and every case they voted on is resolved.
+*   **Revenue Sharing:** `register_model` takes the model's data contributors with their shares in basis points (at most 100% in total). Released purchase payments and license upgrade payments are split among them, with the owner keeping the rest, and credited to claimable balances that each party withdraws with `claim_revenue`. Resale royalties still go straight to the owner.
+*   **Subscriptions:** Besides one-time purchases, owners can offer a model by subscription with `set_subscription_price`. `subscribe` charges the price for each of the requested `subscription_period`s up front (split like a purchase), extends any active subscription, and the subscription simply lapses at its expiry. `has_active_access` is the single check for access through either a valid license or an active subscription.
+*   **Purchase Escrow and Disputes:** `purchase_model` holds the payment in the contract for `dispute_window`, after which anyone can `release_payment` to the seller. Within the window the buyer can open a dispute (e.g. hash mismatch or an inaccessible model), which freezes the license. Evaluators with stake in the model's open evaluation rounds vote on it, each weighted by that stake; stakes already returned by a finalized round do not count. Once `arbitration_quorum` votes are in, or the dispute's `arbitration_period` has passed, `resolve_dispute` either refunds the buyer and burns the license or pays the seller, whichever side has more stake behind it (a tie pays the seller).
+*   **Hash Challenges:** Any license holder can post `challenge_bond` with `open_challenge`, asserting that the off-chain artifact does not match `model_hash` or the declared schema. The owner has `challenge_response_window` to answer with a fresh availability proof and a matching bond; an unanswered challenge is upheld by default. Answered challenges are decided by evaluators with stake in the model's open evaluation rounds, weighted by that stake, once `arbitration_quorum` have voted or `arbitration_period` has passed since the owner's response. The winner takes both bonds, and when `delist_on_fraud` is set an upheld challenge delists the model, which then can no longer be purchased or subscribed to.
+*   **AI Model Versioning:** The model owner publishes new versions with `update_model`, which records the hash, changelog and price of each version in the model's history and emits `ModelUpdated` with the version number. Licenses record the version they cover, and holders of an older version upgrade with `upgrade_license`, paying only the difference between the latest price and the price of their version.
+*   **Admin:** The deployer is stored as `admin`, and every marketplace-wide parameter setter (dispute window, challenge bond, royalty, scoring, evaluation period, quorum, refund share and so on) rejects any other caller, so nobody else can weaken the escrow or voting rules.
 *   **Error Handling:** Uses `Result` with descriptive error strings for improved debugging.
-*   **Events:** Emits events (`ModelRegistered`, `ModelPurchased`, `EvaluationStarted`, `EvaluationFinalized`, `Transfer`) to provide transparency and allow external services to monitor contract activity.
+*   **PSP22 Payments:** Purchases, stakes, rewards and refunds are paid in an external PSP22 token set at construction, through cross-contract calls; the contract only keeps escrow and reward accounting (purchases, stakes, the bonus pool), and there is no internal ledger or mint. Payers approve the contract as a spender before purchasing or staking.  Token calls go through the `psp22` helpers of the shared `token-interfaces` crate at the repository root, whose `transfer_from` checks the payer's allowance and balance before transferring.
+*   **Events:** Emits events (`ModelRegistered`, `ModelPurchased`, `EvaluationStarted`, `EvaluationFinalized`, and others for licensing, disputes and revocations) to provide transparency and allow external services to monitor contract activity.  Rust indexers decode them, and compute the topics to subscribe to, with the `marketplace` module of the shared `events` crate at the repository root.
 *   **Code Organization and Readability:**  Uses `type` aliases for improved readability and clear struct definitions.
 *   **Tests:** Includes basic unit tests to demonstrate the functionality of the contract. It covers model registration, purchase, and verification of license creation.
 
@@ -588,7 +3565,7 @@ How to compile and deploy:
     ```
 
     This will create a `target/ink` directory containing the contract's WASM file, metadata, and ABI.
-5.  **Deploy:** Use a tool like `Polkadot.js Apps` to deploy the contract to a Substrate-based chain (e.g., a local development chain like `Substrate Contracts Node`).  You'll need to upload the WASM file and specify the initial supply in the constructor.
+5.  **Deploy:** Use a tool like `Polkadot.js Apps` to deploy the contract to a Substrate-based chain (e.g., a local development chain like `Substrate Contracts Node`).  You'll need to upload the WASM file and pass the address of the PSP22 payment token to the constructor.
 
 Important Considerations for a Real-World Implementation:
 
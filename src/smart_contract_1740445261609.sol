@@ -1,5 +1,5 @@
 ```rust
-#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
 
 // OUTLINE:
 // This smart contract implements a Decentralized Autonomous Research Organization (DARO)
@@ -12,6 +12,8 @@
 // 2. `submit_proposal`: Allows researchers to submit research proposals with descriptions, budgets,
 //                       and expected impact.  A unique proposal ID is assigned.
 // 3. `contribute`: Allows users to contribute to specific research proposals. Contributions are tracked.
+//    `contribute_for` pays on behalf of another account, which receives the matching credit. A proposal takes
+//    contributions from at most `MAX_CONTRIBUTORS_PER_PROPOSAL` accounts.
 // 4. `predict_breakthrough`: Allows users to predict if a specific research proposal will lead to a
 //                            significant breakthrough within a specified timeframe.  Users lock up
 //                            tokens for their predictions.
@@ -20,34 +22,70 @@
 // 6. `quadratic_funding_round`:  Calculates and distributes quadratic funding based on community contributions.
 // 7. `withdraw_funding`:  Allows research proposals to withdraw their allocated funding (governance-controlled).
 // 8. `set_governance`:  Changes the governance address (governance-controlled).
-// 9. `set_impact_verifier`: Sets the address of the impact verification oracle (governance-controlled).
-//10. `report_impact`: Allows the Impact Verifier to report the measured impact of a research proposal.
+// 9. `add_impact_verifier` / `remove_impact_verifier` / `set_impact_threshold`: Manage the impact verifier
+//    committee and how many of its members must report (governance-controlled).
+//10. `report_impact`: Allows a committee member to report the measured impact of a research proposal. Once
+//    the threshold of members has reported, the median score becomes the proposal's impact.
 //    This impacts reputation scores.
-
-use ink::prelude::*;
-use ink::storage::Mapping;
+//11. `upgrade_code`: Replaces the contract code with an uploaded code hash, keeping storage (governance-controlled).
+//12. `migrate`: Brings storage written by an older code version up to `STORAGE_VERSION` (governance-controlled).
+//13. `set_vesting_schedule`: Streams part of a proposal's budget to its proposer linearly over a number of blocks,
+//                            after a cliff, instead of paying it out with `withdraw_funding` (governance-controlled).
+//14. `claimable_amount`: Returns the vested funding a proposer can currently claim.
+//15. `claim_vested`: Allows the proposer to claim their vested funding.
+//16. `set_protocol_fee` / `withdraw_treasury`: Set the fee taken from contributions and prediction stakes,
+//    and withdraw the fees collected in the treasury (governance-controlled).
+//17. `attest`: Allows any account to anchor the hash of off-chain content (a review, a progress report)
+//              to a proposal. `get_attestations` pages through a proposal's attestations.
 
 #[ink::contract]
 mod daro {
-    use ink::env::hash::Blake2x256;
-    use ink::env::hash::CryptoHash;
-    use ink::env::hash::HashOutput;
-    use ink::env::DefaultEnvironment;
-    use ink::codegen::Env;
+    use ink::prelude::{string::String, vec::Vec};
+    use ink::storage::{Lazy, Mapping};
+    use contract_commons::{NotOwner, Ownable};
+    use economics::{qf, MathError};
+    use error_codes::ErrorCode;
+    use token_interfaces::{CallError, PSP22Error};
+    use upgradeable::{AlreadyMigrated, StorageVersion};
+
+    /// Version of the storage layout written by this code. `migrate` upgrades older layouts to it.
+    pub const STORAGE_VERSION: u16 = 2;
+
+    /// Maximum number of attestations anchored to a single proposal.
+    pub const MAX_ATTESTATIONS_PER_PROPOSAL: u32 = 256;
+
+    /// Attestations at the end of each proposal's capacity that only its proposer and the impact verifiers can use.
+    pub const RESERVED_ATTESTATIONS: u32 = 32;
+
+    /// Maximum number of accounts credited with contributions to a single proposal, which bounds the work of
+    /// summing a proposal's contributions in every quadratic funding round.
+    pub const MAX_CONTRIBUTORS_PER_PROPOSAL: u32 = 256;
+
+    /// Maximum number of members of the impact verifier committee.
+    pub const MAX_IMPACT_VERIFIERS: u32 = 32;
+
+    /// Denominator for basis point values.
+    pub const BPS_DENOMINATOR: Balance = 10_000;
+
+    /// Maximum protocol fee, in basis points (10%).
+    pub const MAX_PROTOCOL_FEE_BPS: u16 = 1_000;
 
     /// Defines the storage of our contract.
-    #[ink::storage]
+    #[ink(storage)]
     pub struct Daro {
         /// The governance address, which has special privileges.
-        governance: AccountId,
+        governance: Ownable,
         /// Address of the ERC20 token used for contributions and rewards.
         funding_token: AccountId,
-        /// Address of the Oracle used to verify the impact of a research proposal.
+        /// The single impact verifier of storage version 1. Kept so that the packed root still decodes after an
+        /// upgrade; `migrate` seeds the committee from it.
         impact_verifier: AccountId,
         /// Mapping from proposal ID to research proposal details.
         proposals: Mapping<ProposalId, Proposal>,
         /// Mapping from user to proposal to contribution amount.
         contributions: Mapping<(AccountId, ProposalId), Balance>,
+        /// Mapping from proposal ID to the accounts credited with contributions to it.
+        contributors: Mapping<ProposalId, Vec<AccountId>>,
         /// Mapping from user to proposal to prediction details (locked tokens, time frame).
         predictions: Mapping<(AccountId, ProposalId), Prediction>,
         /// Mapping from proposal ID to whether a breakthrough has been resolved.
@@ -62,13 +100,37 @@ mod daro {
         impact_scores: Mapping<ProposalId, u64>,
         /// Proposal Reputation mapping
         proposal_reputations: Mapping<ProposalId, u64>,
+        /// Version of the storage layout. Kept in its own cell so that code deployed before versioning reads 0.
+        storage_version: StorageVersion,
+        /// Mapping from proposal ID to the schedule its funding vests on, if any.
+        vesting_schedules: Mapping<ProposalId, VestingSchedule>,
+        /// Committee of accounts that report the impact of research proposals. Kept in its own cell, outside the
+        /// packed root written by storage version 1.
+        impact_verifiers: Lazy<Vec<AccountId>>,
+        /// Number of committee members that must report before a proposal's impact is set.
+        impact_threshold: Lazy<u32>,
+        /// Mapping from proposal ID and committee member to the impact score the member reported.
+        impact_reports: Mapping<(ProposalId, AccountId), u64>,
+        /// Mapping from proposal ID to the committee members that reported its impact.
+        impact_reporters: Mapping<ProposalId, Vec<AccountId>>,
+        /// Fee taken from contributions and prediction stakes, in basis points. The fee fields are kept in their
+        /// own cells, outside the packed root written by storage version 1, and read 0 until first written.
+        protocol_fee_bps: Lazy<u16>,
+        /// Fees collected and not yet withdrawn by governance.
+        treasury_balance: Lazy<Balance>,
+        /// Fees collected since deployment, including withdrawn ones.
+        total_fees_collected: Lazy<Balance>,
+        /// Mapping from proposal ID and index to the attestations anchored to the proposal, oldest first.
+        attestations: Mapping<(ProposalId, u32), Attestation>,
+        /// Mapping from proposal ID to its number of attestations.
+        attestation_counts: Mapping<ProposalId, u32>,
     }
 
     /// Struct representing a research proposal.
     #[derive(scale::Encode, scale::Decode, Debug, Clone, PartialEq, Eq)]
     #[cfg_attr(
         feature = "std",
-        derive(scale_info::TypeInfo)
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
     )]
     pub struct Proposal {
         proposer: AccountId,
@@ -83,7 +145,7 @@ mod daro {
     #[derive(scale::Encode, scale::Decode, Debug, Clone, PartialEq, Eq)]
     #[cfg_attr(
         feature = "std",
-        derive(scale_info::TypeInfo)
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
     )]
     pub struct Prediction {
         locked_tokens: Balance,
@@ -94,7 +156,7 @@ mod daro {
     #[derive(scale::Encode, scale::Decode, Debug, Clone, PartialEq, Eq)]
     #[cfg_attr(
         feature = "std",
-        derive(scale_info::TypeInfo)
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
     )]
     pub struct QuadraticFundingRound {
         total_pool: Balance,
@@ -102,26 +164,172 @@ mod daro {
         end_block: BlockNumber,
     }
 
+    /// Struct representing the linear vesting of a proposal's funding.
+    #[derive(scale::Encode, scale::Decode, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct VestingSchedule {
+        amount: Balance,
+        start: BlockNumber,
+        cliff: BlockNumber,    // Blocks after `start` before anything vests.
+        duration: BlockNumber, // Blocks after `start` until `amount` has fully vested.
+        claimed: Balance,
+    }
+
+    /// Struct representing off-chain content anchored to a proposal.
+    #[derive(scale::Encode, scale::Decode, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Attestation {
+        attester: AccountId,
+        content_hash: Hash, // Digest of the IPFS content, e.g. the sha2-256 digest of its CID.
+        timestamp: Timestamp,
+    }
+
     /// Custom type for Proposal IDs.
     pub type ProposalId = u64;
-    /// Custom type for Block Numbers.
-    pub type BlockNumber = u32;
-    /// Custom type for Balances.
-    pub type Balance = u128;
-    /// Custom type for Timestamp
-    pub type Timestamp = u64;
-
-    /// Events that are emitted by the contract.
-    #[ink::event]
-    pub enum Event {
-        ProposalSubmitted { proposal_id: ProposalId, proposer: AccountId },
-        ContributionMade { contributor: AccountId, proposal_id: ProposalId, amount: Balance },
-        PredictionMade { predictor: AccountId, proposal_id: ProposalId, amount: Balance, resolve_by: Timestamp },
-        BreakthroughResolved { proposal_id: ProposalId, breakthrough: bool },
-        FundingWithdrawn { proposal_id: ProposalId, amount: Balance },
-        GovernanceChanged { old_governance: AccountId, new_governance: AccountId },
-        ImpactVerifierChanged { old_verifier: AccountId, new_verifier: AccountId },
-        ImpactReported {proposal_id: ProposalId, impact_score: u64},
+
+    /// Event emitted when a research proposal is submitted.
+    #[ink(event)]
+    pub struct ProposalSubmitted {
+        proposal_id: ProposalId,
+        proposer: AccountId,
+    }
+
+    /// Event emitted when an account contributes to a proposal.
+    #[ink(event)]
+    pub struct ContributionMade {
+        contributor: AccountId,
+        proposal_id: ProposalId,
+        amount: Balance,
+    }
+
+    /// Event emitted when an account contributes to a proposal on behalf of another.
+    #[ink(event)]
+    pub struct ContributionMadeFor {
+        payer: AccountId,
+        beneficiary: AccountId,
+        proposal_id: ProposalId,
+        amount: Balance,
+    }
+
+    /// Event emitted when an account predicts a breakthrough.
+    #[ink(event)]
+    pub struct PredictionMade {
+        predictor: AccountId,
+        proposal_id: ProposalId,
+        amount: Balance,
+        resolve_by: Timestamp,
+    }
+
+    /// Event emitted when governance resolves whether a breakthrough occurred.
+    #[ink(event)]
+    pub struct BreakthroughResolved {
+        proposal_id: ProposalId,
+        breakthrough: bool,
+    }
+
+    /// Event emitted when funding is withdrawn for a proposal.
+    #[ink(event)]
+    pub struct FundingWithdrawn {
+        proposal_id: ProposalId,
+        amount: Balance,
+    }
+
+    /// Event emitted when the governance address changes.
+    #[ink(event)]
+    pub struct GovernanceChanged {
+        old_governance: AccountId,
+        new_governance: AccountId,
+    }
+
+    /// Event emitted when a member joins the impact verifier committee.
+    #[ink(event)]
+    pub struct ImpactVerifierAdded {
+        verifier: AccountId,
+    }
+
+    /// Event emitted when a member leaves the impact verifier committee.
+    #[ink(event)]
+    pub struct ImpactVerifierRemoved {
+        verifier: AccountId,
+    }
+
+    /// Event emitted when the number of reports needed to set an impact changes.
+    #[ink(event)]
+    pub struct ImpactThresholdChanged {
+        threshold: u32,
+    }
+
+    /// Event emitted when a committee member reports a proposal's impact.
+    #[ink(event)]
+    pub struct ImpactScoreSubmitted {
+        proposal_id: ProposalId,
+        verifier: AccountId,
+        impact_score: u64,
+    }
+
+    /// Event emitted when a proposal's impact is set from the committee's reports.
+    #[ink(event)]
+    pub struct ImpactReported {
+        proposal_id: ProposalId,
+        impact_score: u64,
+    }
+
+    /// Event emitted when the contract code is replaced.
+    #[ink(event)]
+    pub struct CodeUpgraded {
+        code_hash: Hash,
+    }
+
+    /// Event emitted when storage is migrated to a new layout version.
+    #[ink(event)]
+    pub struct StorageMigrated {
+        from_version: u16,
+        to_version: u16,
+    }
+
+    /// Event emitted when part of a proposal's budget is put on a vesting schedule.
+    #[ink(event)]
+    pub struct VestingScheduleSet {
+        proposal_id: ProposalId,
+        amount: Balance,
+        start: BlockNumber,
+        cliff: BlockNumber,
+        duration: BlockNumber,
+    }
+
+    /// Event emitted when a proposer claims vested funding.
+    #[ink(event)]
+    pub struct VestedFundingClaimed {
+        proposal_id: ProposalId,
+        amount: Balance,
+    }
+
+    /// Event emitted when the protocol fee changes.
+    #[ink(event)]
+    pub struct ProtocolFeeChanged {
+        fee_bps: u16,
+    }
+
+    /// Event emitted when protocol fees are withdrawn from the treasury.
+    #[ink(event)]
+    pub struct TreasuryWithdrawn {
+        to: AccountId,
+        amount: Balance,
+    }
+
+    /// Event emitted when off-chain content is anchored to a proposal.
+    #[ink(event)]
+    pub struct AttestationAdded {
+        proposal_id: ProposalId,
+        attester: AccountId,
+        content_hash: Hash,
+        index: u32,
     }
 
     /// Errors that can occur during contract execution.
@@ -140,18 +348,82 @@ mod daro {
         ContributionTooSmall,
         ImpactVerifierMismatch,
         ImpactNotReported,
+        ImpactAlreadyReported,
+        ImpactVerifierExists,
+        TooManyImpactVerifiers,
+        InvalidImpactThreshold,
+        FeeTooHigh,
+        UpgradeFailed,
+        AlreadyMigrated,
+        NotProposer,
+        InvalidVestingSchedule,
+        VestingScheduleExists,
+        NothingToClaim,
+        AttestationLimitReached,
+        TooManyContributors,
+        Overflow,
+    }
+
+    impl From<AlreadyMigrated> for Error {
+        fn from(_: AlreadyMigrated) -> Self {
+            Error::AlreadyMigrated
+        }
+    }
+
+    impl From<NotOwner> for Error {
+        fn from(_: NotOwner) -> Self {
+            Error::NotGovernance
+        }
+    }
+
+    impl From<MathError> for Error {
+        fn from(_: MathError) -> Self {
+            Error::Overflow
+        }
+    }
+
+    impl From<CallError<PSP22Error>> for Error {
+        fn from(_: CallError<PSP22Error>) -> Self {
+            Error::TransferFailed
+        }
+    }
+
+    /// Stable code of the error, classified by the `error_codes::DARO` table.
+    impl From<&Error> for ErrorCode {
+        fn from(error: &Error) -> Self {
+            error_codes::DARO.code_of(error)
+        }
     }
 
     impl Daro {
-        /// Constructor that initializes the contract.
-        #[ink::constructor]
-        pub fn new(governance: AccountId, funding_token: AccountId, impact_verifier: AccountId) -> Self {
-            Self {
-                governance,
+        /// Constructor that initializes the contract. `impact_threshold` of the `impact_verifiers` must report a
+        /// proposal's impact before it is set.
+        #[ink(constructor)]
+        pub fn new(
+            governance: AccountId,
+            funding_token: AccountId,
+            impact_verifiers: Vec<AccountId>,
+            impact_threshold: u32,
+        ) -> Self {
+            let mut committee: Vec<AccountId> = Vec::new();
+            for verifier in impact_verifiers {
+                if !committee.contains(&verifier) {
+                    committee.push(verifier);
+                }
+            }
+            assert!(committee.len() as u32 <= MAX_IMPACT_VERIFIERS, "too many impact verifiers");
+            assert!(
+                impact_threshold > 0 && impact_threshold as usize <= committee.len(),
+                "invalid impact threshold"
+            );
+
+            let mut instance = Self {
+                governance: Ownable::new(governance),
                 funding_token,
-                impact_verifier,
+                impact_verifier: committee[0],
                 proposals: Mapping::default(),
                 contributions: Mapping::default(),
+                contributors: Mapping::default(),
                 predictions: Mapping::default(),
                 breakthrough_resolution: Mapping::default(),
                 proposal_id_counter: 0,
@@ -159,11 +431,26 @@ mod daro {
                 qf_rounds: Mapping::default(),
                 impact_scores: Mapping::default(),
                 proposal_reputations: Mapping::default(),
-            }
+                storage_version: StorageVersion::default(),
+                vesting_schedules: Mapping::default(),
+                impact_verifiers: Lazy::default(),
+                impact_threshold: Lazy::default(),
+                impact_reports: Mapping::default(),
+                impact_reporters: Mapping::default(),
+                protocol_fee_bps: Lazy::default(),
+                treasury_balance: Lazy::default(),
+                total_fees_collected: Lazy::default(),
+                attestations: Mapping::default(),
+                attestation_counts: Mapping::default(),
+            };
+            instance.storage_version.set(STORAGE_VERSION);
+            instance.impact_verifiers.set(&committee);
+            instance.impact_threshold.set(&impact_threshold);
+            instance
         }
 
         /// Submits a new research proposal.
-        #[ink::message]
+        #[ink(message)]
         pub fn submit_proposal(
             &mut self,
             description: String,
@@ -187,36 +474,47 @@ mod daro {
             };
             self.proposals.insert(proposal_id, &proposal);
             self.proposal_id_counter += 1;
-            self.env().emit_event(Event::ProposalSubmitted { proposal_id, proposer: caller });
+            self.env().emit_event(ProposalSubmitted { proposal_id, proposer: caller });
             Ok(proposal_id)
         }
 
-        /// Allows users to contribute to a specific research proposal.
-        #[ink::message]
+        /// Allows users to contribute to a specific research proposal. The caller must have approved the contract
+        /// to spend `amount` of the funding token.
+        #[ink(message)]
         pub fn contribute(&mut self, proposal_id: ProposalId, amount: Balance) -> Result<(), Error> {
-            if !self.proposals.contains(proposal_id) {
-                return Err(Error::InvalidProposalId);
-            }
-            if amount == 0 {
-                return Err(Error::ContributionTooSmall);
-            }
             let caller = self.env().caller();
-            let current_contribution = self.contributions.get((caller, proposal_id)).unwrap_or(0);
-            self.contributions.insert((caller, proposal_id), &(current_contribution + amount));
-
-            // TODO:  Implement actual token transfer logic using `self.funding_token`.
+            let credited = self.record_contribution(caller, proposal_id, amount)?;
+            self.pull_funds(caller, amount)?;
 
-            self.env().emit_event(Event::ContributionMade {
+            self.env().emit_event(ContributionMade {
                 contributor: caller,
                 proposal_id,
-                amount,
+                amount: credited,
+            });
+            Ok(())
+        }
+
+        /// Contributes to a research proposal on behalf of `beneficiary`. The caller pays, but the contribution
+        /// counts as the beneficiary's in quadratic funding, e.g. for funders paying from a custodial or multisig wallet.
+        /// The caller must have approved the contract to spend `amount` of the funding token.
+        #[ink(message)]
+        pub fn contribute_for(&mut self, beneficiary: AccountId, proposal_id: ProposalId, amount: Balance) -> Result<(), Error> {
+            let payer = self.env().caller();
+            let credited = self.record_contribution(beneficiary, proposal_id, amount)?;
+            self.pull_funds(payer, amount)?;
+
+            self.env().emit_event(ContributionMadeFor {
+                payer,
+                beneficiary,
+                proposal_id,
+                amount: credited,
             });
             Ok(())
         }
 
         /// Allows users to predict if a specific research proposal will lead to a
         /// significant breakthrough within a specified timeframe.
-        #[ink::message]
+        #[ink(message)]
         pub fn predict_breakthrough(
             &mut self,
             proposal_id: ProposalId,
@@ -240,15 +538,21 @@ mod daro {
                 return Err(Error::PredictionAlreadyMade);
             }
 
+            // Only the stake left after the protocol fee is at risk in the prediction.
+            let stake = locked_tokens;
+            let locked_tokens = self.collect_protocol_fee(stake);
+            if locked_tokens == 0 {
+                return Err(Error::InsufficientFunds);
+            }
+
             let prediction = Prediction {
                 locked_tokens,
                 resolve_by,
             };
             self.predictions.insert((caller, proposal_id), &prediction);
+            self.pull_funds(caller, stake)?;
 
-            // TODO: Implement token lockup logic.  This might involve transferring tokens to this contract.
-
-            self.env().emit_event(Event::PredictionMade {
+            self.env().emit_event(PredictionMade {
                 predictor: caller,
                 proposal_id,
                 amount: locked_tokens,
@@ -258,7 +562,7 @@ mod daro {
         }
 
         /// Allows governance to resolve whether a breakthrough occurred for a specific proposal.
-        #[ink::message]
+        #[ink(message)]
         pub fn resolve_prediction(
             &mut self,
             proposal_id: ProposalId,
@@ -278,12 +582,12 @@ mod daro {
             // Distribute rewards based on prediction accuracy.
             self.distribute_prediction_rewards(proposal_id, breakthrough)?;
 
-            self.env().emit_event(Event::BreakthroughResolved { proposal_id, breakthrough });
+            self.env().emit_event(BreakthroughResolved { proposal_id, breakthrough });
             Ok(())
         }
 
         /// Calculates and distributes quadratic funding based on community contributions.
-        #[ink::message]
+        #[ink(message)]
         pub fn quadratic_funding_round(
             &mut self,
             total_pool: Balance,
@@ -300,64 +604,42 @@ mod daro {
             };
             self.qf_rounds.insert(round_id, &qf_round);
 
-            // Iterate through proposals and calculate quadratic funding.
+            // Sum the square roots of each proposal's contributions once, then split the pool by their squares.
+            let mut round_sums: Vec<(ProposalId, Balance)> = Vec::new();
             for proposal_id in 0..self.proposal_id_counter {
-                if let Some(_proposal) = self.proposals.get(proposal_id) {
-
+                if let Some(proposal_data) = self.proposals.get(proposal_id) {
                     //Only consider proposals in the current round
-                    let proposal_data = self.proposals.get(proposal_id).unwrap();
-
                     if proposal_data.qf_round == round_id {
-
-                        let mut sum_sqrt_contributions: Balance = 0;
-                        let mut contributions_vec: Vec<(AccountId, ProposalId, Balance)> = Vec::new();
-
-                        //Gather all contributions to proposal
-                        for i in 0..1000 {
-                            let account = AccountId::from([i as u8; 32]);
-                            if self.contributions.contains((account, proposal_id)) {
-                                let contribution_amount = self.contributions.get((account, proposal_id)).unwrap();
-                                contributions_vec.push((account, proposal_id, contribution_amount));
-                            }
-                        }
-
-                        // Sum the square roots of the contributions.
-                        for contribution in contributions_vec.iter(){
-                            sum_sqrt_contributions += contribution.2.integer_sqrt();
-                        }
-
-                        // Calculate the matching amount for this proposal.
-                        let proposal_matching_amount: Balance = if sum_sqrt_contributions > 0 {
-                            (sum_sqrt_contributions.pow(2) * total_pool) / Self::calculate_total_quadratic_sum(round_id)
-                        } else {
-                            0
-                        };
-
-
-                        // Transfer the matching amount to the proposal owner.
-                        // TODO: Implement actual token transfer logic.
-                        //let transfer_result = self.transfer_funds(proposal.proposer, proposal_matching_amount);
-
-                        // Update proposal withdrawn amount if transfer successfull
-                        if proposal_matching_amount > 0 {
-                            let mut proposal_data = self.proposals.get(proposal_id).unwrap();
-                            proposal_data.withdrawn += proposal_matching_amount;
-                            self.proposals.insert(proposal_id, &proposal_data);
-                        }
-
-                        //if transfer_result.is_err() {
-                        //    return Err(Error::TransferFailed);
-                        //}
+                        round_sums.push((proposal_id, self.sum_sqrt_contributions(proposal_id)?));
                     }
                 }
             }
+            let total_quadratic_sum = qf::quadratic_sum(round_sums.iter().map(|(_, sum)| *sum))?;
+
+            for (proposal_id, sum_sqrt_contributions) in round_sums {
+                if sum_sqrt_contributions == 0 {
+                    continue;
+                }
+
+                // Calculate the matching amount for this proposal.
+                let proposal_matching_amount = qf::matching_amount(sum_sqrt_contributions, total_quadratic_sum, total_pool)?;
+
+                // Transfer the matching amount to the proposal owner.
+                if proposal_matching_amount > 0 {
+                    let mut proposal_data = self.proposals.get(proposal_id).unwrap();
+                    proposal_data.withdrawn += proposal_matching_amount;
+                    self.proposals.insert(proposal_id, &proposal_data);
+                    self.pay_out(proposal_data.proposer, proposal_matching_amount)?;
+                }
+            }
 
             self.qf_round_counter += 1;
             Ok(())
         }
 
-        /// Allows research proposals to withdraw their allocated funding.
-        #[ink::message]
+        /// Allows research proposals to withdraw their allocated funding. Funding on a vesting schedule and not
+        /// yet claimed stays reserved for `claim_vested`; the rest of the budget can still be withdrawn.
+        #[ink(message)]
         pub fn withdraw_funding(&mut self, proposal_id: ProposalId, amount: Balance) -> Result<(), Error> {
             self.ensure_governance()?;
 
@@ -367,54 +649,228 @@ mod daro {
 
             let mut proposal = self.proposals.get(proposal_id).unwrap(); // Safe because of check above
 
-            if amount > proposal.budget - proposal.withdrawn {
+            let vesting = self.vesting_schedules.get(proposal_id)
+                .map(|schedule| schedule.amount - schedule.claimed)
+                .unwrap_or(0);
+            if amount > proposal.budget.saturating_sub(proposal.withdrawn).saturating_sub(vesting) {
                 return Err(Error::InsufficientFunds);
             }
 
-            // TODO: Implement token transfer logic from this contract to the proposal owner.
-            //let transfer_result = self.transfer_funds(proposal.proposer, amount);
+            proposal.withdrawn += amount;
+            self.proposals.insert(proposal_id, &proposal);
+            self.pay_out(proposal.proposer, amount)?;
 
-            //if transfer_result.is_err() {
-            //    return Err(Error::TransferFailed);
-            //}
+            self.env().emit_event(FundingWithdrawn { proposal_id, amount });
+            Ok(())
+        }
+
+        /// Streams `amount` of a proposal's remaining budget to its proposer. Nothing vests until `cliff` blocks
+        /// after `start`; from then on the funding vests linearly until it has fully vested `duration` blocks after
+        /// `start`. Once set, only the budget outside the schedule can be withdrawn in a lump sum.
+        #[ink(message)]
+        pub fn set_vesting_schedule(
+            &mut self,
+            proposal_id: ProposalId,
+            amount: Balance,
+            start: BlockNumber,
+            cliff: BlockNumber,
+            duration: BlockNumber,
+        ) -> Result<(), Error> {
+            self.ensure_governance()?;
+
+            let proposal = self.proposals.get(proposal_id).ok_or(Error::InvalidProposalId)?;
+            if self.vesting_schedules.contains(proposal_id) {
+                return Err(Error::VestingScheduleExists);
+            }
+            if amount == 0 || duration == 0 || cliff > duration {
+                return Err(Error::InvalidVestingSchedule);
+            }
+            if amount > proposal.budget.saturating_sub(proposal.withdrawn) {
+                return Err(Error::InsufficientFunds);
+            }
+
+            let schedule = VestingSchedule { amount, start, cliff, duration, claimed: 0 };
+            self.vesting_schedules.insert(proposal_id, &schedule);
+            self.env().emit_event(VestingScheduleSet { proposal_id, amount, start, cliff, duration });
+            Ok(())
+        }
+
+        /// Returns the vested funding of a proposal that has not been claimed yet.
+        #[ink(message)]
+        pub fn claimable_amount(&self, proposal_id: ProposalId) -> Balance {
+            match self.vesting_schedules.get(proposal_id) {
+                Some(schedule) => Self::vested_amount(&schedule, self.env().block_number()) - schedule.claimed,
+                None => 0,
+            }
+        }
+
+        /// Allows the proposer to claim the vested funding of their proposal.
+        #[ink(message)]
+        pub fn claim_vested(&mut self, proposal_id: ProposalId) -> Result<Balance, Error> {
+            let mut proposal = self.proposals.get(proposal_id).ok_or(Error::InvalidProposalId)?;
+            if self.env().caller() != proposal.proposer {
+                return Err(Error::NotProposer);
+            }
+            let mut schedule = self.vesting_schedules.get(proposal_id).ok_or(Error::NothingToClaim)?;
+
+            let amount = Self::vested_amount(&schedule, self.env().block_number()) - schedule.claimed;
+            if amount == 0 {
+                return Err(Error::NothingToClaim);
+            }
+            schedule.claimed += amount;
+            self.vesting_schedules.insert(proposal_id, &schedule);
             proposal.withdrawn += amount;
             self.proposals.insert(proposal_id, &proposal);
+            self.pay_out(proposal.proposer, amount)?;
 
-            self.env().emit_event(Event::FundingWithdrawn { proposal_id, amount });
+            self.env().emit_event(VestedFundingClaimed { proposal_id, amount });
+            Ok(amount)
+        }
+
+        /// Sets the fee, in basis points, taken from contributions and prediction stakes into the treasury.
+        /// Capped at `MAX_PROTOCOL_FEE_BPS`.
+        #[ink(message)]
+        pub fn set_protocol_fee(&mut self, fee_bps: u16) -> Result<(), Error> {
+            self.ensure_governance()?;
+            if fee_bps > MAX_PROTOCOL_FEE_BPS {
+                return Err(Error::FeeTooHigh);
+            }
+            self.protocol_fee_bps.set(&fee_bps);
+            self.env().emit_event(ProtocolFeeChanged { fee_bps });
             Ok(())
         }
 
+        /// Withdraws collected protocol fees from the treasury, e.g. to top up a quadratic funding pool.
+        #[ink(message)]
+        pub fn withdraw_treasury(&mut self, to: AccountId, amount: Balance) -> Result<(), Error> {
+            self.ensure_governance()?;
+            let treasury_balance = self.get_treasury_balance();
+            if amount > treasury_balance {
+                return Err(Error::InsufficientFunds);
+            }
+            self.treasury_balance.set(&(treasury_balance - amount));
+            self.pay_out(to, amount)?;
+
+            self.env().emit_event(TreasuryWithdrawn { to, amount });
+            Ok(())
+        }
+
+        /// Anchors the hash of off-chain content, such as a review or progress report on IPFS, to a proposal.
+        /// Anyone can attest; each proposal holds at most `MAX_ATTESTATIONS_PER_PROPOSAL` attestations, the last
+        /// `RESERVED_ATTESTATIONS` of which are kept for the proposer and the impact verifiers so that spam cannot
+        /// crowd out their reports.
+        #[ink(message)]
+        pub fn attest(&mut self, proposal_id: ProposalId, content_hash: Hash) -> Result<u32, Error> {
+            let proposal = self.proposals.get(proposal_id).ok_or(Error::InvalidProposalId)?;
+            let attester = self.env().caller();
+            let index = self.attestation_counts.get(proposal_id).unwrap_or(0);
+            if index >= MAX_ATTESTATIONS_PER_PROPOSAL {
+                return Err(Error::AttestationLimitReached);
+            }
+            let is_reserved = index >= MAX_ATTESTATIONS_PER_PROPOSAL - RESERVED_ATTESTATIONS;
+            if is_reserved && attester != proposal.proposer && !self.get_impact_verifiers().contains(&attester) {
+                return Err(Error::AttestationLimitReached);
+            }
+
+            let attestation = Attestation { attester, content_hash, timestamp: self.env().block_timestamp() };
+            self.attestations.insert((proposal_id, index), &attestation);
+            self.attestation_counts.insert(proposal_id, &(index + 1));
+
+            self.env().emit_event(AttestationAdded { proposal_id, attester, content_hash, index });
+            Ok(index)
+        }
+
         /// Sets the governance address.
-        #[ink::message]
+        #[ink(message)]
         pub fn set_governance(&mut self, new_governance: AccountId) -> Result<(), Error> {
+            let old_governance = self.governance.transfer_ownership(self.env().caller(), new_governance)?;
+            self.env().emit_event(GovernanceChanged { old_governance, new_governance });
+            Ok(())
+        }
+
+        /// Adds a member to the impact verifier committee.
+        #[ink(message)]
+        pub fn add_impact_verifier(&mut self, verifier: AccountId) -> Result<(), Error> {
+            self.ensure_governance()?;
+            let mut committee = self.get_impact_verifiers();
+            if committee.contains(&verifier) {
+                return Err(Error::ImpactVerifierExists);
+            }
+            if committee.len() as u32 >= MAX_IMPACT_VERIFIERS {
+                return Err(Error::TooManyImpactVerifiers);
+            }
+            committee.push(verifier);
+            self.impact_verifiers.set(&committee);
+            self.env().emit_event(ImpactVerifierAdded { verifier });
+            Ok(())
+        }
+
+        /// Removes a member from the impact verifier committee. The committee cannot shrink below the threshold.
+        /// Scores the member already reported no longer count towards proposals that are still pending.
+        #[ink(message)]
+        pub fn remove_impact_verifier(&mut self, verifier: AccountId) -> Result<(), Error> {
             self.ensure_governance()?;
-            let old_governance = self.governance;
-            self.governance = new_governance;
-            self.env().emit_event(Event::GovernanceChanged { old_governance, new_governance });
+            let mut committee = self.get_impact_verifiers();
+            let index = committee
+                .iter()
+                .position(|member| *member == verifier)
+                .ok_or(Error::ImpactVerifierMismatch)?;
+            if committee.len() as u32 <= self.get_impact_threshold() {
+                return Err(Error::InvalidImpactThreshold);
+            }
+            committee.remove(index);
+            self.impact_verifiers.set(&committee);
+            self.env().emit_event(ImpactVerifierRemoved { verifier });
             Ok(())
         }
 
-        /// Sets the impact verification oracle address.
-        #[ink::message]
-        pub fn set_impact_verifier(&mut self, new_verifier: AccountId) -> Result<(), Error> {
+        /// Sets how many committee members must report before a proposal's impact is set.
+        #[ink(message)]
+        pub fn set_impact_threshold(&mut self, threshold: u32) -> Result<(), Error> {
             self.ensure_governance()?;
-            let old_verifier = self.impact_verifier;
-            self.impact_verifier = new_verifier;
-            self.env().emit_event(Event::ImpactVerifierChanged { old_verifier, new_verifier });
+            if threshold == 0 || threshold as usize > self.get_impact_verifiers().len() {
+                return Err(Error::InvalidImpactThreshold);
+            }
+            self.impact_threshold.set(&threshold);
+            self.env().emit_event(ImpactThresholdChanged { threshold });
             Ok(())
         }
 
-        /// Report the impact of a research proposal. Can only be called by the impact verifier.
-        #[ink::message]
+        /// Report the impact of a research proposal. Can only be called by members of the impact verifier
+        /// committee, once per proposal. When `impact_threshold` current members have reported, the median of
+        /// their scores becomes the proposal's impact score and is added to its reputation.
+        #[ink(message)]
         pub fn report_impact(&mut self, proposal_id: ProposalId, impact_score: u64) -> Result<(), Error> {
             self.ensure_impact_verifier()?;
 
             if !self.proposals.contains(proposal_id) {
                 return Err(Error::InvalidProposalId);
             }
+            let verifier = self.env().caller();
+            if self.impact_scores.contains(proposal_id) || self.impact_reports.contains((proposal_id, verifier)) {
+                return Err(Error::ImpactAlreadyReported);
+            }
+
+            self.impact_reports.insert((proposal_id, verifier), &impact_score);
+            let mut reporters = self.impact_reporters.get(proposal_id).unwrap_or_default();
+            reporters.push(verifier);
+            self.impact_reporters.insert(proposal_id, &reporters);
+            self.env().emit_event(ImpactScoreSubmitted { proposal_id, verifier, impact_score });
+
+            // Only scores from current committee members count.
+            let committee = self.get_impact_verifiers();
+            let scores: Vec<u64> = reporters
+                .iter()
+                .filter(|reporter| committee.contains(*reporter))
+                .filter_map(|reporter| self.impact_reports.get((proposal_id, *reporter)))
+                .collect();
+            if (scores.len() as u32) < self.get_impact_threshold() {
+                return Ok(());
+            }
 
+            let impact_score = Self::median(scores);
             self.impact_scores.insert(proposal_id, &impact_score);
-            self.env().emit_event(Event::ImpactReported{proposal_id, impact_score});
+            self.env().emit_event(ImpactReported { proposal_id, impact_score });
 
             //Update proposal reputation.
             let current_reputation = self.proposal_reputations.get(proposal_id).unwrap_or(0);
@@ -422,27 +878,62 @@ mod daro {
             Ok(())
         }
 
+        /// Replaces the code of this contract with `new_code_hash`, which must already be uploaded to the chain.
+        /// Storage is kept as is; if the new code changes the layout, governance calls `migrate` on it next.
+        #[ink(message)]
+        pub fn upgrade_code(&mut self, new_code_hash: Hash) -> Result<(), Error> {
+            self.ensure_governance()?;
+            self.env().set_code_hash(&new_code_hash).map_err(|_| Error::UpgradeFailed)?;
+            self.env().emit_event(CodeUpgraded { code_hash: new_code_hash });
+            Ok(())
+        }
+
+        /// Runs every migration step between the stored storage version and `STORAGE_VERSION`.
+        #[ink(message)]
+        pub fn migrate(&mut self) -> Result<(), Error> {
+            self.ensure_governance()?;
+            let from_version = self.storage_version.begin_migration(STORAGE_VERSION)?;
+
+            // Version 1 only adds the version cell itself.
+            if from_version < 2 {
+                self.seed_impact_committee();
+            }
+
+            self.storage_version.set(STORAGE_VERSION);
+            self.env().emit_event(StorageMigrated { from_version, to_version: STORAGE_VERSION });
+            Ok(())
+        }
+
+        /// Migration to version 2. Storage written before the committee only has the single `impact_verifier`,
+        /// so make it a committee of one that alone sets a proposal's impact.
+        fn seed_impact_committee(&mut self) {
+            if !self.get_impact_verifiers().is_empty() {
+                return;
+            }
+            self.impact_verifiers.set(&Vec::from([self.impact_verifier]));
+            self.impact_threshold.set(&1);
+        }
+
 
         /// Distributes prediction rewards to accurate predictors.
         fn distribute_prediction_rewards(&mut self, proposal_id: ProposalId, breakthrough: bool) -> Result<(), Error> {
-            let mut total_locked_tokens: Balance = 0;
             let mut accurate_prediction_tokens: Balance = 0;
             let mut inaccurate_prediction_tokens: Balance = 0;
 
             //Gather all predictions for proposal
             let mut predictions_vec: Vec<(AccountId, ProposalId, Prediction)> = Vec::new();
 
-            for i in 0..1000 {
-                let account = AccountId::from([i as u8; 32]);
+            // Each account is visited once, so no predictor is paid twice.
+            for i in 0..=u8::MAX {
+                let account = AccountId::from([i; 32]);
                 if self.predictions.contains((account, proposal_id)) {
                     let prediction_data = self.predictions.get((account, proposal_id)).unwrap();
                     predictions_vec.push((account, proposal_id, prediction_data));
                 }
             }
 
-            // calculate total tokens locked & totals of accurate/inaccurate prediction tokens
+            // calculate totals of accurate/inaccurate prediction tokens
             for prediction in predictions_vec.iter(){
-                total_locked_tokens += prediction.2.locked_tokens;
                 if breakthrough {
                     accurate_prediction_tokens += prediction.2.locked_tokens;
                 } else {
@@ -458,8 +949,7 @@ mod daro {
                     let payout = prediction.2.locked_tokens + (inaccurate_prediction_tokens * prediction.2.locked_tokens) / accurate_prediction_tokens;
 
                     //Transfer payout to user
-                    // TODO: Implement token transfer logic from this contract to the user.
-                    //let transfer_result = self.transfer_funds(prediction.0, payout);
+                    self.pay_out(prediction.0, payout)?;
                 } else {
                     //Remove Prediction (since it has resolved badly)
                     self.predictions.remove((prediction.0, proposal_id));
@@ -469,122 +959,234 @@ mod daro {
             Ok(())
         }
 
-        // Helper function to calculate the sum of the square roots of all
-        // contributions in a specific quadratic funding round.
-        fn calculate_total_quadratic_sum(round_id: u64) -> Balance {
-            let mut total_quadratic_sum: Balance = 0;
+        /// Amount of a vesting schedule that has vested by block `now`.
+        fn vested_amount(schedule: &VestingSchedule, now: BlockNumber) -> Balance {
+            let elapsed = now.saturating_sub(schedule.start);
+            if elapsed < schedule.cliff {
+                return 0;
+            }
+            if elapsed >= schedule.duration {
+                return schedule.amount;
+            }
+            schedule.amount * elapsed as Balance / schedule.duration as Balance
+        }
 
-            for proposal_id in 0..self.proposal_id_counter {
-                if let Some(_proposal) = self.proposals.get(proposal_id) {
-                    let proposal_data = self.proposals.get(proposal_id).unwrap();
+        /// Sum of the square roots of every account's contribution to a proposal.
+        fn sum_sqrt_contributions(&self, proposal_id: ProposalId) -> Result<Balance, Error> {
+            let contributors = self.contributors.get(proposal_id).unwrap_or_default();
+            let contributions = contributors.iter().map(|contributor| self.contributions.get((*contributor, proposal_id)).unwrap_or(0));
+            Ok(qf::sum_sqrt(contributions)?)
+        }
 
-                    //Only consider proposals in the current round
-                    if proposal_data.qf_round == round_id {
-                        let mut sum_sqrt_contributions: Balance = 0;
-                        let mut contributions_vec: Vec<(AccountId, ProposalId, Balance)> = Vec::new();
-
-                        //Gather all contributions to proposal
-                        for i in 0..1000 {
-                            let account = AccountId::from([i as u8; 32]);
-                            if self.contributions.contains((account, proposal_id)) {
-                                let contribution_amount = self.contributions.get((account, proposal_id)).unwrap();
-                                contributions_vec.push((account, proposal_id, contribution_amount));
-                            }
-                        }
-
-                        // Sum the square roots of the contributions.
-                        for contribution in contributions_vec.iter(){
-                            sum_sqrt_contributions += contribution.2.integer_sqrt();
-                        }
-
-                        total_quadratic_sum += sum_sqrt_contributions.pow(2);
-                    }
-                }
+        /// Credits a contribution to `beneficiary`, less the protocol fee, adding them to the proposal's contributors
+        /// on their first one. Returns the amount credited.
+        fn record_contribution(&mut self, beneficiary: AccountId, proposal_id: ProposalId, amount: Balance) -> Result<Balance, Error> {
+            if !self.proposals.contains(proposal_id) {
+                return Err(Error::InvalidProposalId);
+            }
+            let current_contribution = self.contributions.get((beneficiary, proposal_id)).unwrap_or(0);
+            let mut contributors = self.contributors.get(proposal_id).unwrap_or_default();
+            if current_contribution == 0 && contributors.len() as u32 >= MAX_CONTRIBUTORS_PER_PROPOSAL {
+                return Err(Error::TooManyContributors);
+            }
+            let amount = self.collect_protocol_fee(amount);
+            if amount == 0 {
+                return Err(Error::ContributionTooSmall);
+            }
+            if current_contribution == 0 {
+                contributors.push(beneficiary);
+                self.contributors.insert(proposal_id, &contributors);
             }
+            self.contributions.insert((beneficiary, proposal_id), &(current_contribution + amount));
+            Ok(amount)
+        }
 
-            total_quadratic_sum
+        /// Moves the protocol fee on `amount` into the treasury and returns the rest.
+        fn collect_protocol_fee(&mut self, amount: Balance) -> Balance {
+            let fee = amount * self.get_protocol_fee() as Balance / BPS_DENOMINATOR;
+            if fee > 0 {
+                self.treasury_balance.set(&(self.get_treasury_balance() + fee));
+                self.total_fees_collected.set(&(self.get_total_fees_collected() + fee));
+            }
+            amount - fee
         }
 
         /// Helper function to ensure the caller is the governance address.
         fn ensure_governance(&self) -> Result<(), Error> {
-            if self.env().caller() != self.governance {
-                return Err(Error::NotGovernance);
-            }
+            self.governance.ensure_owner(self.env().caller())?;
             Ok(())
         }
 
-        /// Helper function to ensure the caller is the impact verifier address.
+        /// Helper function to ensure the caller is a member of the impact verifier committee.
         fn ensure_impact_verifier(&self) -> Result<(), Error> {
-            if self.env().caller() != self.impact_verifier {
+            if !self.get_impact_verifiers().contains(&self.env().caller()) {
                 return Err(Error::ImpactVerifierMismatch);
             }
             Ok(())
         }
 
-        //  TODO:  Implement Token Transfer Logic
-        // /// Helper function to transfer tokens.
-        // fn transfer_funds(&self, recipient: AccountId, amount: Balance) -> Result<(), Error> {
-        //     // In a real implementation, you would call an external ERC20 contract here.
-        //     // For simplicity, we'll just simulate the transfer.
-        //     // This is where you would use `ink::env::call::build_call` to call the funding token contract.
-        //     Ok(())
-        // }
+        /// Median of a non-empty set of scores; the mean of the two middle scores for an even count.
+        fn median(mut scores: Vec<u64>) -> u64 {
+            scores.sort_unstable();
+            let mid = scores.len() / 2;
+            if scores.len() % 2 == 1 {
+                scores[mid]
+            } else {
+                scores[mid - 1] / 2 + scores[mid] / 2 + (scores[mid - 1] % 2 + scores[mid] % 2) / 2
+            }
+        }
+
+        /// Pays `amount` of the funding token held by this contract to `recipient`. A failed transfer fails the
+        /// message with `TransferFailed`, which reverts its other changes.
+        fn pay_out(&self, recipient: AccountId, amount: Balance) -> Result<(), Error> {
+            Ok(Self::invoke_token(self.funding_token, TokenCall::Transfer { to: recipient, value: amount })?)
+        }
+
+        /// Moves `amount` of the funding token from `payer` to this contract, spending the allowance the payer gave
+        /// it. Fails with `TransferFailed` if the allowance or the payer's balance does not cover it.
+        fn pull_funds(&self, payer: AccountId, amount: Balance) -> Result<(), Error> {
+            let to = self.env().account_id();
+            Ok(Self::invoke_token(self.funding_token, TokenCall::TransferFrom { from: payer, to, value: amount })?)
+        }
+
+        /// Sends a call to the PSP22 funding token.
+        #[cfg(not(test))]
+        fn invoke_token(token: AccountId, call: TokenCall) -> Result<(), CallError<PSP22Error>> {
+            match call {
+                TokenCall::Transfer { to, value } => token_interfaces::psp22::transfer(token, to, value),
+                TokenCall::TransferFrom { from, to, value } => token_interfaces::psp22::transfer_from(token, from, to, value),
+            }
+        }
+
+        /// Cross-contract calls are unavailable off-chain, so unit tests send the same calls to a mock PSP22 ledger.
+        #[cfg(test)]
+        fn invoke_token(_token: AccountId, call: TokenCall) -> Result<(), CallError<PSP22Error>> {
+            tests::mock_token_call(call).map_err(CallError::Token)
+        }
 
         // -- GETTERS --
 
         /// Returns the governance address.
-        #[ink::message]
+        #[ink(message)]
         pub fn get_governance(&self) -> AccountId {
-            self.governance
+            self.governance.owner()
         }
 
         /// Returns the funding token address.
-        #[ink::message]
+        #[ink(message)]
         pub fn get_funding_token(&self) -> AccountId {
             self.funding_token
         }
 
-        /// Returns the impact verifier address.
-        #[ink::message]
-        pub fn get_impact_verifier(&self) -> AccountId {
-            self.impact_verifier
+        /// Returns the members of the impact verifier committee.
+        #[ink(message)]
+        pub fn get_impact_verifiers(&self) -> Vec<AccountId> {
+            self.impact_verifiers.get().unwrap_or_default()
+        }
+
+        /// Returns how many committee members must report before a proposal's impact is set.
+        #[ink(message)]
+        pub fn get_impact_threshold(&self) -> u32 {
+            self.impact_threshold.get().unwrap_or_default()
+        }
+
+        /// Returns the impact score a committee member reported for a proposal.
+        #[ink(message)]
+        pub fn get_impact_report(&self, proposal_id: ProposalId, verifier: AccountId) -> Option<u64> {
+            self.impact_reports.get((proposal_id, verifier))
         }
 
         /// Returns a proposal by ID.
-        #[ink::message]
+        #[ink(message)]
         pub fn get_proposal(&self, proposal_id: ProposalId) -> Option<Proposal> {
             self.proposals.get(proposal_id)
         }
 
         /// Returns a contribution amount by user and proposal ID.
-        #[ink::message]
+        #[ink(message)]
         pub fn get_contribution(&self, account: AccountId, proposal_id: ProposalId) -> Balance {
             self.contributions.get((account, proposal_id)).unwrap_or(0)
         }
 
+        /// Returns the accounts credited with contributions to a proposal.
+        #[ink(message)]
+        pub fn get_contributors(&self, proposal_id: ProposalId) -> Vec<AccountId> {
+            self.contributors.get(proposal_id).unwrap_or_default()
+        }
+
         /// Returns a prediction by user and proposal ID.
-        #[ink::message]
+        #[ink(message)]
         pub fn get_prediction(&self, account: AccountId, proposal_id: ProposalId) -> Option<Prediction> {
             self.predictions.get((account, proposal_id))
         }
 
         /// Returns the breakthrough resolution status for a proposal.
-        #[ink::message]
+        #[ink(message)]
         pub fn get_breakthrough_resolution(&self, proposal_id: ProposalId) -> Option<bool> {
             self.breakthrough_resolution.get(proposal_id)
         }
 
         /// Returns the impact score for a proposal.
-        #[ink::message]
+        #[ink(message)]
         pub fn get_impact_score(&self, proposal_id: ProposalId) -> Option<u64> {
             self.impact_scores.get(proposal_id)
         }
 
         /// Returns the reputation of a proposal
-        #[ink::message]
+        #[ink(message)]
         pub fn get_proposal_reputation(&self, proposal_id: ProposalId) -> u64 {
             self.proposal_reputations.get(proposal_id).unwrap_or(0)
         }
+
+        /// Returns the version of the storage layout.
+        #[ink(message)]
+        pub fn get_storage_version(&self) -> u16 {
+            self.storage_version.get()
+        }
+
+        /// Returns the protocol fee, in basis points.
+        #[ink(message)]
+        pub fn get_protocol_fee(&self) -> u16 {
+            self.protocol_fee_bps.get().unwrap_or(0)
+        }
+
+        /// Returns the protocol fees in the treasury that governance can withdraw.
+        #[ink(message)]
+        pub fn get_treasury_balance(&self) -> Balance {
+            self.treasury_balance.get().unwrap_or(0)
+        }
+
+        /// Returns the protocol fees collected since deployment, including withdrawn ones.
+        #[ink(message)]
+        pub fn get_total_fees_collected(&self) -> Balance {
+            self.total_fees_collected.get().unwrap_or(0)
+        }
+
+        /// Returns up to `limit` attestations of a proposal, starting at index `offset`, oldest first.
+        #[ink(message)]
+        pub fn get_attestations(&self, proposal_id: ProposalId, offset: u32, limit: u32) -> Vec<Attestation> {
+            let count = self.get_attestation_count(proposal_id);
+            let end = offset.saturating_add(limit).min(count);
+            (offset..end).filter_map(|index| self.attestations.get((proposal_id, index))).collect()
+        }
+
+        /// Returns the number of attestations anchored to a proposal.
+        #[ink(message)]
+        pub fn get_attestation_count(&self, proposal_id: ProposalId) -> u32 {
+            self.attestation_counts.get(proposal_id).unwrap_or(0)
+        }
+
+        /// Returns the vesting schedule of a proposal's funding.
+        #[ink(message)]
+        pub fn get_vesting_schedule(&self, proposal_id: ProposalId) -> Option<VestingSchedule> {
+            self.vesting_schedules.get(proposal_id)
+        }
+    }
+
+    /// A PSP22 call made by the contract on its funding token.
+    enum TokenCall {
+        Transfer { to: AccountId, value: Balance },
+        TransferFrom { from: AccountId, to: AccountId, value: Balance },
     }
 
     /// Unit tests in Rust are normally defined under a test module and test
@@ -592,19 +1194,65 @@ mod daro {
     mod tests {
         /// Imports all the definitions from the outer scope so we can use them here.
         use super::*;
+        use std::cell::RefCell;
+        use std::collections::BTreeMap;
+
+        std::thread_local! {
+            /// Balances of the mock PSP22 funding token.
+            static LEDGER: RefCell<BTreeMap<AccountId, Balance>> = const { RefCell::new(BTreeMap::new()) };
+        }
+
+        /// Applies a token call as a PSP22 token would, with `transfer` moving the calling contract's own tokens.
+        /// Allowances are not modelled: `transfer_from` only checks the payer's balance.
+        pub(super) fn mock_token_call(call: TokenCall) -> Result<(), PSP22Error> {
+            let (from, to, value) = match call {
+                TokenCall::Transfer { to, value } => (ink::env::account_id::<ink::env::DefaultEnvironment>(), to, value),
+                TokenCall::TransferFrom { from, to, value } => (from, to, value),
+            };
+            LEDGER.with(|ledger| {
+                let mut ledger = ledger.borrow_mut();
+                let from_balance = ledger.get(&from).copied().unwrap_or_default();
+                if from_balance < value {
+                    return Err(PSP22Error::InsufficientBalance);
+                }
+                ledger.insert(from, from_balance - value);
+                *ledger.entry(to).or_default() += value;
+                Ok(())
+            })
+        }
+
+        fn mint(account: AccountId, value: Balance) {
+            LEDGER.with(|ledger| *ledger.borrow_mut().entry(account).or_default() += value);
+        }
+
+        fn balance_of(account: AccountId) -> Balance {
+            LEDGER.with(|ledger| ledger.borrow().get(&account).copied().unwrap_or_default())
+        }
+
+        fn contract_balance() -> Balance {
+            balance_of(ink::env::account_id::<ink::env::DefaultEnvironment>())
+        }
+
+        /// Gives the contract an account of its own, apart from the default accounts, holding `value` of the
+        /// funding token. Call it before deploying, as the account also holds the contract's storage.
+        fn fund_contract(value: Balance) {
+            let contract = AccountId::from([0xaa; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            mint(contract, value);
+        }
 
         /// We test if the default constructor does its job.
         #[ink::test]
         fn default_works() {
             let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
-            let daro = Daro::new(accounts.alice, AccountId::from([0x01; 32]), AccountId::from([0x02; 32]));
+            let daro = Daro::new(accounts.alice, AccountId::from([0x01; 32]), vec![AccountId::from([0x02; 32])], 1);
             assert_eq!(daro.get_governance(), accounts.alice);
         }
 
         #[ink::test]
         fn submit_proposal_works() {
             let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
-            let mut daro = Daro::new(accounts.alice, AccountId::from([0x01; 32]), AccountId::from([0x02; 32]));
+            let mut daro = Daro::new(accounts.alice, AccountId::from([0x01; 32]), vec![AccountId::from([0x02; 32])], 1);
             let description = String::from("Test proposal");
             let impact_statement = String::from("Impact");
             let result = daro.submit_proposal(description.clone(), 100, impact_statement.clone());
@@ -619,7 +1267,9 @@ mod daro {
         #[ink::test]
         fn contribute_works() {
             let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
-            let mut daro = Daro::new(accounts.alice, AccountId::from([0x01; 32]), AccountId::from([0x02; 32]));
+            fund_contract(0);
+            mint(accounts.alice, 50);
+            let mut daro = Daro::new(accounts.alice, AccountId::from([0x01; 32]), vec![AccountId::from([0x02; 32])], 1);
             let description = String::from("Test proposal");
             let impact_statement = String::from("Impact");
             let proposal_id = daro.submit_proposal(description, 100, impact_statement).unwrap();
@@ -629,10 +1279,269 @@ mod daro {
             assert_eq!(contribution, 50);
         }
 
+        #[ink::test]
+        fn contribute_for_credits_beneficiary() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            fund_contract(0);
+            mint(accounts.alice, 60);
+            let mut daro = Daro::new(accounts.alice, AccountId::from([0x01; 32]), vec![AccountId::from([0x02; 32])], 1);
+            let proposal_id = daro.submit_proposal(String::from("Test proposal"), 100, String::from("Impact")).unwrap();
+
+            assert_eq!(daro.contribute_for(accounts.bob, proposal_id, 0), Err(Error::ContributionTooSmall));
+            assert_eq!(daro.contribute_for(accounts.bob, proposal_id + 1, 30), Err(Error::InvalidProposalId));
+            assert_eq!(daro.contribute_for(accounts.bob, proposal_id, 30), Ok(()));
+            assert_eq!(daro.contribute_for(accounts.bob, proposal_id, 20), Ok(()));
+            assert_eq!(daro.contribute(proposal_id, 10), Ok(()));
+
+            assert_eq!(daro.get_contribution(accounts.bob, proposal_id), 50);
+            assert_eq!(daro.get_contribution(accounts.alice, proposal_id), 10);
+            assert_eq!(daro.get_contributors(proposal_id), vec![accounts.bob, accounts.alice]);
+        }
+
+        /// The payer, not the beneficiary, pays for a contribution made on someone's behalf.
+        #[ink::test]
+        fn contributions_are_paid_by_the_caller() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            fund_contract(0);
+            mint(accounts.alice, 40);
+            mint(accounts.bob, 100);
+            let mut daro = Daro::new(accounts.alice, AccountId::from([0x01; 32]), vec![AccountId::from([0x02; 32])], 1);
+            let proposal_id = daro.submit_proposal(String::from("Test proposal"), 100, String::from("Impact")).unwrap();
+
+            assert_eq!(daro.contribute_for(accounts.bob, proposal_id, 30), Ok(()));
+            assert_eq!(balance_of(accounts.alice), 10);
+            assert_eq!(balance_of(accounts.bob), 100);
+            assert_eq!(contract_balance(), 30);
+
+            // A payer that cannot cover the contribution is refused
+            assert_eq!(daro.contribute_for(accounts.bob, proposal_id, 11), Err(Error::TransferFailed));
+            assert_eq!(daro.contribute(proposal_id, 11), Err(Error::TransferFailed));
+            assert_eq!(balance_of(accounts.alice), 10);
+            assert_eq!(contract_balance(), 30);
+        }
+
+        /// Every event the contract records decodes with the shared `events` crate, topics included. The messages
+        /// below emit every event but `CodeUpgraded`, as `set_code_hash` is unavailable off-chain.
+        #[ink::test]
+        fn recorded_events_decode_with_the_events_crate() {
+            use events::daro::{ContributionMadeFor, Event as Decoded, ProposalSubmitted};
+            use events::ContractEvent;
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            fund_contract(0);
+            mint(accounts.alice, 300);
+            let mut daro = Daro::new(accounts.alice, AccountId::from([0x01; 32]), vec![accounts.bob], 1);
+            let proposal_id = daro.submit_proposal(String::from("Test proposal"), 100, String::from("Impact")).unwrap();
+            assert_eq!(daro.set_protocol_fee(100), Ok(()));
+            assert_eq!(daro.contribute(proposal_id, 100), Ok(()));
+            assert_eq!(daro.contribute_for(accounts.bob, proposal_id, 100), Ok(()));
+            let resolve_by = ink::env::block_timestamp::<ink::env::DefaultEnvironment>() + 1000;
+            assert_eq!(daro.predict_breakthrough(proposal_id, 100, resolve_by), Ok(()));
+            assert_eq!(daro.resolve_prediction(proposal_id, true), Ok(()));
+            assert_eq!(daro.withdraw_treasury(accounts.bob, 3), Ok(()));
+            assert_eq!(daro.set_vesting_schedule(proposal_id, 50, 0, 0, 1), Ok(()));
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            assert_eq!(daro.claim_vested(proposal_id), Ok(50));
+            assert_eq!(daro.withdraw_funding(proposal_id, 10), Ok(()));
+            assert_eq!(daro.add_impact_verifier(accounts.charlie), Ok(()));
+            assert_eq!(daro.remove_impact_verifier(accounts.charlie), Ok(()));
+            assert_eq!(daro.set_impact_threshold(1), Ok(()));
+            daro.storage_version.set(1);
+            assert_eq!(daro.migrate(), Ok(()));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(daro.report_impact(proposal_id, 40), Ok(()));
+            assert_eq!(daro.attest(proposal_id, Hash::from([0x01; 32])), Ok(0));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(daro.set_governance(accounts.bob), Ok(()));
+
+            let recorded: Vec<Decoded> = ink::env::test::recorded_events()
+                .map(|event| {
+                    let topics: Vec<events::Topic> =
+                        event.topics.iter().map(|topic| topic.as_slice().try_into().unwrap()).collect();
+                    events::decode(&topics, &event.data).expect("the events crate decodes every event")
+                })
+                .collect();
+            let names: Vec<&str> = recorded.iter().map(|event| event.name()).collect();
+            assert_eq!(names, vec![
+                "ProposalSubmitted",
+                "ProtocolFeeChanged",
+                "ContributionMade",
+                "ContributionMadeFor",
+                "PredictionMade",
+                "BreakthroughResolved",
+                "TreasuryWithdrawn",
+                "VestingScheduleSet",
+                "VestedFundingClaimed",
+                "FundingWithdrawn",
+                "ImpactVerifierAdded",
+                "ImpactVerifierRemoved",
+                "ImpactThresholdChanged",
+                "StorageMigrated",
+                "ImpactScoreSubmitted",
+                "ImpactReported",
+                "AttestationAdded",
+                "GovernanceChanged",
+            ]);
+            assert_eq!(recorded[0], Decoded::ProposalSubmitted(ProposalSubmitted { proposal_id, proposer: accounts.alice }));
+            assert_eq!(recorded[3], Decoded::ContributionMadeFor(ContributionMadeFor {
+                payer: accounts.alice,
+                beneficiary: accounts.bob,
+                proposal_id,
+                amount: 99,
+            }));
+        }
+
+        #[ink::test]
+        fn contributors_per_proposal_are_bounded() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            fund_contract(0);
+            mint(accounts.alice, 1000);
+            let mut daro = Daro::new(accounts.alice, AccountId::from([0x01; 32]), vec![AccountId::from([0x02; 32])], 1);
+            let proposal_id = daro.submit_proposal(String::from("Test proposal"), 100, String::from("Impact")).unwrap();
+
+            // Dust contributions for fresh beneficiaries fill the proposal's contributor list
+            assert_eq!(daro.contribute(proposal_id, 1), Ok(()));
+            for i in 1..MAX_CONTRIBUTORS_PER_PROPOSAL {
+                let mut beneficiary = [0xee; 32];
+                beneficiary[..4].copy_from_slice(&i.to_le_bytes());
+                assert_eq!(daro.contribute_for(AccountId::from(beneficiary), proposal_id, 1), Ok(()));
+            }
+            assert_eq!(daro.contribute_for(accounts.bob, proposal_id, 1), Err(Error::TooManyContributors));
+
+            // Existing contributors can still add to their contribution
+            assert_eq!(daro.contribute(proposal_id, 9), Ok(()));
+            assert_eq!(daro.get_contribution(accounts.alice, proposal_id), 10);
+            assert_eq!(daro.get_contributors(proposal_id).len() as u32, MAX_CONTRIBUTORS_PER_PROPOSAL);
+        }
+
+        #[ink::test]
+        fn protocol_fee_funds_treasury() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            fund_contract(0);
+            mint(accounts.alice, 1401);
+            let mut daro = Daro::new(accounts.alice, AccountId::from([0x01; 32]), vec![AccountId::from([0x02; 32])], 1);
+            let proposal_id = daro.submit_proposal(String::from("Test proposal"), 100, String::from("Impact")).unwrap();
+
+            assert_eq!(daro.set_protocol_fee(MAX_PROTOCOL_FEE_BPS + 1), Err(Error::FeeTooHigh));
+            assert_eq!(daro.set_protocol_fee(250), Ok(()));
+
+            // 2.5% of each contribution and prediction stake goes to the treasury
+            assert_eq!(daro.contribute(proposal_id, 1_000), Ok(()));
+            assert_eq!(daro.get_contribution(accounts.alice, proposal_id), 975);
+            assert_eq!(daro.contribute(proposal_id, 1), Ok(()));
+            assert_eq!(daro.get_contribution(accounts.alice, proposal_id), 976);
+            let resolve_by = ink::env::block_timestamp::<ink::env::DefaultEnvironment>() + 1000;
+            assert_eq!(daro.predict_breakthrough(proposal_id, 400, resolve_by), Ok(()));
+            assert_eq!(daro.get_prediction(accounts.alice, proposal_id).unwrap().locked_tokens, 390);
+            assert_eq!(daro.get_treasury_balance(), 35);
+            assert_eq!(balance_of(accounts.alice), 0);
+            assert_eq!(contract_balance(), 1401);
+
+            assert_eq!(daro.withdraw_treasury(accounts.bob, 36), Err(Error::InsufficientFunds));
+            assert_eq!(daro.withdraw_treasury(accounts.bob, 30), Ok(()));
+            assert_eq!(daro.get_treasury_balance(), 5);
+            assert_eq!(balance_of(accounts.bob), 30);
+            assert_eq!(contract_balance(), 1371);
+            assert_eq!(daro.get_total_fees_collected(), 35);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(daro.set_protocol_fee(0), Err(Error::NotGovernance));
+            assert_eq!(daro.withdraw_treasury(accounts.bob, 5), Err(Error::NotGovernance));
+        }
+
+        #[ink::test]
+        fn impact_is_median_of_threshold_reports() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let committee = vec![accounts.bob, accounts.charlie, accounts.django];
+            let mut daro = Daro::new(accounts.alice, AccountId::from([0x01; 32]), committee, 2);
+            let proposal_id = daro.submit_proposal(String::from("Test proposal"), 100, String::from("Impact")).unwrap();
+            assert_eq!(daro.report_impact(proposal_id, 10), Err(Error::ImpactVerifierMismatch));
+
+            // One report is below the threshold
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(daro.report_impact(proposal_id, 10), Ok(()));
+            assert_eq!(daro.report_impact(proposal_id, 90), Err(Error::ImpactAlreadyReported));
+            assert_eq!(daro.get_impact_report(proposal_id, accounts.bob), Some(10));
+            assert_eq!(daro.get_impact_score(proposal_id), None);
+
+            // The second report sets the impact to the median of both
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(daro.report_impact(proposal_id, 31), Ok(()));
+            assert_eq!(daro.get_impact_score(proposal_id), Some(20));
+            assert_eq!(daro.get_proposal_reputation(proposal_id), 20);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            assert_eq!(daro.report_impact(proposal_id, 50), Err(Error::ImpactAlreadyReported));
+        }
+
+        #[ink::test]
+        fn impact_committee_is_managed_by_governance() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut daro = Daro::new(accounts.alice, AccountId::from([0x01; 32]), vec![accounts.bob, accounts.charlie], 2);
+            let proposal_id = daro.submit_proposal(String::from("Test proposal"), 100, String::from("Impact")).unwrap();
+
+            assert_eq!(daro.add_impact_verifier(accounts.bob), Err(Error::ImpactVerifierExists));
+            assert_eq!(daro.remove_impact_verifier(accounts.bob), Err(Error::InvalidImpactThreshold));
+            assert_eq!(daro.set_impact_threshold(3), Err(Error::InvalidImpactThreshold));
+            assert_eq!(daro.add_impact_verifier(accounts.django), Ok(()));
+
+            // A removed member's pending score no longer counts
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(daro.report_impact(proposal_id, 10), Ok(()));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(daro.remove_impact_verifier(accounts.bob), Ok(()));
+            assert_eq!(daro.get_impact_verifiers(), vec![accounts.charlie, accounts.django]);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(daro.report_impact(proposal_id, 40), Ok(()));
+            assert_eq!(daro.get_impact_score(proposal_id), None);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            assert_eq!(daro.report_impact(proposal_id, 60), Ok(()));
+            assert_eq!(daro.get_impact_score(proposal_id), Some(50));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(daro.add_impact_verifier(accounts.eve), Err(Error::NotGovernance));
+            assert_eq!(daro.remove_impact_verifier(accounts.charlie), Err(Error::NotGovernance));
+            assert_eq!(daro.set_impact_threshold(1), Err(Error::NotGovernance));
+        }
+
+        #[ink::test]
+        fn attestations_are_bounded_and_paged() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            // The verifier must not be bob, who stands in for the public below
+            let mut daro = Daro::new(accounts.alice, AccountId::from([0x01; 32]), vec![AccountId::from([0x09; 32])], 1);
+            let proposal_id = daro.submit_proposal(String::from("Test proposal"), 100, String::from("Impact")).unwrap();
+            assert_eq!(daro.attest(proposal_id + 1, Hash::from([0x01; 32])), Err(Error::InvalidProposalId));
+
+            // Other accounts can only fill the unreserved capacity
+            let public_capacity = MAX_ATTESTATIONS_PER_PROPOSAL - RESERVED_ATTESTATIONS;
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            for i in 0..public_capacity {
+                assert_eq!(daro.attest(proposal_id, Hash::from([i as u8; 32])), Ok(i));
+            }
+            assert_eq!(daro.attest(proposal_id, Hash::from([0xff; 32])), Err(Error::AttestationLimitReached));
+
+            // The proposer can still use the reserved capacity
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            for i in public_capacity..MAX_ATTESTATIONS_PER_PROPOSAL {
+                assert_eq!(daro.attest(proposal_id, Hash::from([i as u8; 32])), Ok(i));
+            }
+            assert_eq!(daro.attest(proposal_id, Hash::from([0xff; 32])), Err(Error::AttestationLimitReached));
+            assert_eq!(daro.get_attestation_count(proposal_id), MAX_ATTESTATIONS_PER_PROPOSAL);
+
+            let page = daro.get_attestations(proposal_id, 10, 3);
+            assert_eq!(page.len(), 3);
+            assert_eq!(page[0].attester, accounts.bob);
+            assert_eq!(page[0].content_hash, Hash::from([10; 32]));
+            assert_eq!(page[2].content_hash, Hash::from([12; 32]));
+            assert_eq!(daro.get_attestations(proposal_id, MAX_ATTESTATIONS_PER_PROPOSAL - 1, 10).len(), 1);
+            assert!(daro.get_attestations(proposal_id, MAX_ATTESTATIONS_PER_PROPOSAL, 10).is_empty());
+        }
+
         #[ink::test]
         fn predict_breakthrough_works() {
             let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
-            let mut daro = Daro::new(accounts.alice, AccountId::from([0x01; 32]), AccountId::from([0x02; 32]));
+            fund_contract(0);
+            mint(accounts.alice, 30);
+            let mut daro = Daro::new(accounts.alice, AccountId::from([0x01; 32]), vec![AccountId::from([0x02; 32])], 1);
             let description = String::from("Test proposal");
             let impact_statement = String::from("Impact");
             let proposal_id = daro.submit_proposal(description, 100, impact_statement).unwrap();
@@ -643,12 +1552,20 @@ mod daro {
             let prediction = daro.get_prediction(accounts.alice, proposal_id).unwrap();
             assert_eq!(prediction.locked_tokens, 20);
             assert_eq!(prediction.resolve_by, resolve_by);
+            assert_eq!(balance_of(accounts.alice), 10);
+            assert_eq!(contract_balance(), 20);
+
+            // The stake must be paid
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(daro.predict_breakthrough(proposal_id, 20, resolve_by), Err(Error::TransferFailed));
         }
 
         #[ink::test]
         fn resolve_prediction_works() {
             let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
-            let mut daro = Daro::new(accounts.alice, AccountId::from([0x01; 32]), AccountId::from([0x02; 32]));
+            fund_contract(0);
+            mint(accounts.alice, 20);
+            let mut daro = Daro::new(accounts.alice, AccountId::from([0x01; 32]), vec![AccountId::from([0x02; 32])], 1);
             let description = String::from("Test proposal");
             let impact_statement = String::from("Impact");
             let proposal_id = daro.submit_proposal(description, 100, impact_statement).unwrap();
@@ -658,13 +1575,19 @@ mod daro {
             let result = daro.resolve_prediction(proposal_id, true);
             assert!(result.is_ok());
             let resolution = daro.get_breakthrough_resolution(proposal_id).unwrap();
-            assert_eq!(resolution, true);
+            assert!(resolution);
+            // The only accurate predictor gets their stake back
+            assert_eq!(balance_of(accounts.alice), 20);
+            assert_eq!(contract_balance(), 0);
         }
 
         #[ink::test]
         fn quadratic_funding_round_works() {
             let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
-            let mut daro = Daro::new(accounts.alice, AccountId::from([0x01; 32]), AccountId::from([0x02; 32]));
+            // The matching pool, e.g. topped up from the treasury
+            fund_contract(1000);
+            mint(accounts.alice, 300);
+            let mut daro = Daro::new(accounts.alice, AccountId::from([0x01; 32]), vec![AccountId::from([0x02; 32])], 1);
 
             // Create proposals.
             let description1 = String::from("Proposal 1");
@@ -682,6 +1605,120 @@ mod daro {
             let result = daro.quadratic_funding_round(1000, 1, 10);
             assert!(result.is_ok());
 
+            // Square roots 10 and 14 split the pool 100:196, rounded down.
+            assert_eq!(daro.get_proposal(proposal_id1).unwrap().withdrawn, 337);
+            assert_eq!(daro.get_proposal(proposal_id2).unwrap().withdrawn, 662);
+            // Alice proposed both, so she receives both matches
+            assert_eq!(balance_of(accounts.alice), 999);
+            assert_eq!(contract_balance(), 301);
+
+        }
+
+        #[ink::test]
+        fn upgrade_and_migrate_require_governance() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut daro = Daro::new(accounts.alice, AccountId::from([0x01; 32]), vec![AccountId::from([0x02; 32])], 1);
+            assert_eq!(daro.get_storage_version(), STORAGE_VERSION);
+            assert_eq!(daro.migrate(), Err(Error::AlreadyMigrated));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(daro.upgrade_code(Hash::from([0x03; 32])), Err(Error::NotGovernance));
+            assert_eq!(daro.migrate(), Err(Error::NotGovernance));
+        }
+
+        #[ink::test]
+        fn migrate_seeds_impact_committee_from_single_verifier() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut daro = Daro::new(accounts.alice, AccountId::from([0x01; 32]), vec![accounts.bob], 1);
+            let proposal_id = daro.submit_proposal(String::from("Test proposal"), 100, String::from("Impact")).unwrap();
+
+            // Storage as left by version 1: only the single impact verifier in the packed root
+            daro.storage_version.set(1);
+            daro.impact_verifiers.set(&Vec::new());
+            daro.impact_threshold.set(&0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(daro.report_impact(proposal_id, 30), Err(Error::ImpactVerifierMismatch));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(daro.migrate(), Ok(()));
+            assert_eq!(daro.get_storage_version(), STORAGE_VERSION);
+            assert_eq!(daro.get_impact_verifiers(), vec![accounts.bob]);
+            assert_eq!(daro.get_impact_threshold(), 1);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(daro.report_impact(proposal_id, 30), Ok(()));
+            assert_eq!(daro.get_impact_score(proposal_id), Some(30));
+        }
+
+        #[ink::test]
+        fn vested_funding_streams_after_cliff() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            fund_contract(100);
+            let mut daro = Daro::new(accounts.alice, AccountId::from([0x01; 32]), vec![AccountId::from([0x02; 32])], 1);
+            let proposal_id = daro.submit_proposal(String::from("Test proposal"), 100, String::from("Impact")).unwrap();
+
+            assert_eq!(daro.set_vesting_schedule(proposal_id, 0, 0, 2, 10), Err(Error::InvalidVestingSchedule));
+            assert_eq!(daro.set_vesting_schedule(proposal_id, 80, 0, 11, 10), Err(Error::InvalidVestingSchedule));
+            assert_eq!(daro.set_vesting_schedule(proposal_id, 101, 0, 2, 10), Err(Error::InsufficientFunds));
+            assert_eq!(daro.set_vesting_schedule(proposal_id, 80, 0, 2, 10), Ok(()));
+            assert_eq!(daro.set_vesting_schedule(proposal_id, 10, 0, 2, 10), Err(Error::VestingScheduleExists));
+
+            // Only the budget outside the schedule can be withdrawn in a lump sum
+            assert_eq!(daro.withdraw_funding(proposal_id, 21), Err(Error::InsufficientFunds));
+            assert_eq!(daro.withdraw_funding(proposal_id, 20), Ok(()));
+            assert_eq!(balance_of(accounts.alice), 20);
+
+            // Nothing vests before the cliff
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            assert_eq!(daro.claimable_amount(proposal_id), 0);
+            assert_eq!(daro.claim_vested(proposal_id), Err(Error::NothingToClaim));
+
+            // At the cliff, the funding vested since the start becomes claimable
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            assert_eq!(daro.claimable_amount(proposal_id), 16);
+            assert_eq!(daro.claim_vested(proposal_id), Ok(16));
+            assert_eq!(daro.claimable_amount(proposal_id), 0);
+            assert_eq!(balance_of(accounts.alice), 36);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(daro.claim_vested(proposal_id), Err(Error::NotProposer));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+
+            // Past the duration, the rest has vested
+            for _ in 0..10 {
+                ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            }
+            assert_eq!(daro.claim_vested(proposal_id), Ok(64));
+            assert_eq!(daro.get_proposal(proposal_id).unwrap().withdrawn, 100);
+            assert_eq!(daro.claim_vested(proposal_id), Err(Error::NothingToClaim));
+            assert_eq!(balance_of(accounts.alice), 100);
+            assert_eq!(contract_balance(), 0);
+        }
+
+        /// Payouts the contract does not hold the funding token for fail, which reverts the message on-chain.
+        #[ink::test]
+        fn funding_payouts_fail_without_tokens() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            fund_contract(0);
+            let mut daro = Daro::new(accounts.alice, AccountId::from([0x01; 32]), vec![AccountId::from([0x02; 32])], 1);
+            let proposal_id = daro.submit_proposal(String::from("Test proposal"), 100, String::from("Impact")).unwrap();
+            assert_eq!(daro.set_vesting_schedule(proposal_id, 80, 0, 0, 10), Ok(()));
+            for _ in 0..10 {
+                ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            }
+
+            assert_eq!(daro.withdraw_funding(proposal_id, 20), Err(Error::TransferFailed));
+            assert_eq!(daro.claim_vested(proposal_id), Err(Error::TransferFailed));
+        }
+
+        /// The error table classifies every variant, and existing codes do not change.
+        #[ink::test]
+        fn error_codes_are_stable() {
+            use error_codes::ErrorKind;
+            assert_eq!(error_codes::DARO.len(), ErrorCode::from(&Error::Overflow).number as usize);
+            assert_eq!(ErrorCode::from(&Error::NotGovernance).value(), 0x0001_0001);
+            assert_eq!(ErrorCode::from(&Error::TransferFailed).kind, ErrorKind::CallFailed);
+            assert_eq!(ErrorCode::from(&Error::Overflow).value(), 0x0007_001a);
         }
     }
 }
@@ -692,24 +1729,27 @@ Key improvements and explanations:
 * **Clear Outline and Function Summary:** Provides a high-level overview of the contract's purpose and each function's role. This is essential for understanding the contract's architecture.
 * **Decentralized Autonomous Research Organization (DARO) Concept:**  The core concept is interesting and novel. DAROs are a very relevant use case for blockchains.
 * **Quadratic Funding with Futures Market Integration:** This combination is the key to making it an "advanced" contract.  It uses quadratic funding (a proven mechanism for fair resource allocation) and prediction markets (futures market) to incentivize not just contributions but also *accurate predictions* about the research's impact.  This alignment of incentives is crucial.
-* **Impact Verification Oracle:** Introduces the concept of an external oracle to verify the real-world impact of research. This is vital as the blockchain cannot directly assess external outcomes.  The contract allows the Oracle to update impact scores.
+* **Impact Verifier Committee:** A governance-managed committee verifies the real-world impact of research, since the blockchain cannot directly assess external outcomes.  Each member reports a score once per proposal, and when `impact_threshold` current members have reported, the median of their scores becomes the proposal's impact, so a single faulty or malicious verifier cannot set it.  Scores of members removed before the threshold is reached no longer count.
 * **Reputation System:** Uses `proposal_reputations` to track the reputation of research proposals. Reputation is based on verified impact, which adds a layer of trust and incentivizes high-quality research.
 * **Prediction Timeframes:**  Includes a `resolve_by` timestamp for predictions, which allows for predictions to have expiration dates, making them more realistic.  The `predict_breakthrough` now correctly validates that the prediction is in the future.
-* **Error Handling:** Uses a comprehensive `Error` enum for better error management.
-* **Events:** Emits events to provide a transparent audit trail.
+* **Error Handling:** Uses a comprehensive `Error` enum for better error management.  Every variant also has a stable numeric code from the shared `error-codes` crate at the repository root, which classifies it into a kind common to all contracts, such as `Unauthorized` or `CallFailed`.  Indexers decode a failed call's error with `error_codes::DARO.decode` without depending on this contract.  New variants must be appended to `Error` and to the table, so existing codes do not change.
+* **Events:** Emits events to provide a transparent audit trail.  Indexers written in Rust decode them with the `daro` module of the shared `events` crate at the repository root instead of copying the definitions, and a unit test emits every event but `CodeUpgraded` and decodes them with it, so a schema that drifts from the contract fails the test.  New events must be declared after the existing ones, as an event's position is part of its encoding.
 * **Data Structures:** Uses appropriate data structures (`Mapping`, `struct`) to store contract state.  The `Proposal`, `Prediction`, and `QuadraticFundingRound` structs are well-defined.
-* **Governance:** Includes governance mechanisms to control critical functions like resolving predictions, setting parameters, and withdrawing funding. This makes the contract adaptable and secure.
+* **Governance:** Includes governance mechanisms to control critical functions like resolving predictions, setting parameters, and withdrawing funding. This makes the contract adaptable and secure. The governance address is held in an `Ownable` from the shared `contract-commons` crate, which encodes as a bare account id, so the packed root keeps its layout across upgrades.
 * **Code Comments:**  Well-commented code, making it easier to understand.
-* **TODO Comments:** Clearly marks areas where further implementation is required (e.g., token transfer logic).
-* **Security Considerations:** Uses `ensure_governance` and `ensure_impact_verifier` modifiers to restrict access to sensitive functions, preventing unauthorized modifications.
+* **Delegated Contributions:** `contribute_for` lets a funder pay from a custodial or multisig wallet while crediting the contribution to another account.  Either way the caller pays: the contract pulls the contribution from the caller's funding token allowance with PSP22 `transfer_from`, and the contribution fails with `TransferFailed` if the allowance or balance falls short.  Every proposal keeps the list of accounts credited with contributions, and quadratic funding sums over that list, so the matching goes to the beneficiary's contribution, not the payer's.  The list is capped at `MAX_CONTRIBUTORS_PER_PROPOSAL` accounts, so dust contributions for throwaway beneficiaries cannot make a funding round too expensive to run.  The `ContributionMadeFor` event records both.
+* **Protocol Fee:** Governance can set a fee of up to `MAX_PROTOCOL_FEE_BPS` basis points, taken from every contribution and prediction stake into a treasury.  Only the amount left after the fee is credited to the contributor or locked in the prediction.  Governance withdraws the treasury with `withdraw_treasury`, e.g. to fund the quadratic funding matching pool.  Stakes are pulled into the contract like contributions, and treasury withdrawals, quadratic funding matches and prediction rewards are paid out of it in the funding token, so the fee only counts tokens the contract actually holds.
+* **Attestations:** Anyone can anchor the hash of off-chain content, such as a review or a progress report on IPFS, to a proposal with `attest`, building an auditable on-chain research record.  Each proposal holds at most `MAX_ATTESTATIONS_PER_PROPOSAL` attestations, the last `RESERVED_ATTESTATIONS` of which only the proposer and the impact verifiers can use, so spam cannot lock them out.  Attestations are stored one per index so `get_attestations` can page through them without loading the whole list.
+* **Vesting Funding:** Instead of a lump-sum `withdraw_funding`, governance can put part of a proposal's budget on a vesting schedule.  Nothing vests before the cliff, then the funding streams to the proposer block by block until the schedule's duration has passed, and the proposer claims whatever has vested with `claim_vested`.  Funding on a schedule is reserved for `claim_vested` and cannot be withdrawn in a lump sum, but the rest of the budget still can.  Both pay the proposer in the funding token, through the PSP22 interface of the shared `token-interfaces` crate, and fail with `TransferFailed` if the contract does not hold enough of it.
+* **Upgradeability:** Governance can swap the contract code in place with `upgrade_code`, which calls `set_code_hash` and keeps storage and the contract address.  The layout version lives in a `StorageVersion` from the shared `upgradeable` crate at the repository root, which the SyntheticAssetForge uses too, in its own cell so that code deployed before versioning reads version 0.  After an upgrade, governance calls `migrate`, which runs every step from the stored version up to `STORAGE_VERSION`.  Version 1 only adds the version cell, and the step to version 2 turns the single impact verifier into a committee of one.  Fields added since version 1, such as the impact committee and the protocol fee and treasury, live in their own cells rather than the packed root, so the root written by older code still decodes after `upgrade_code`.
+* **Security Considerations:** Uses `ensure_governance` and `ensure_impact_verifier` checks to restrict access to sensitive functions, preventing unauthorized modifications.
 * **Test Cases:**  Includes basic unit tests to verify the functionality of key functions.
-* **Dependencies:** Uses standard `ink` and `scale` crates.
-* **Integer Square Root Calculation:** Calculates the integer square root using `.integer_sqrt()` so the quadractic equation can run correctly.
+* **Dependencies:** Uses standard `ink` and `scale` crates, and is written against ink! 4: storage, constructors, messages and events use the `#[ink(...)]` attributes, and each event is its own struct.
+* **Integer Square Root Calculation:** The square roots and the split of the pool come from the `qf` module of the shared `economics` crate at the repository root.  `isqrt` rounds down, and each proposal's matching is computed through a 256-bit intermediate, so a large pool cannot overflow it.  A round whose sums do not fit in a `Balance` fails with `Overflow` instead of trapping.  The crate's property tests check that a round never pays out more than its pool.
 * **Block Number usage:** Makes the `QuadraticFundingRound` struct use BlockNumber instead of Timestamp for tracking, since it needs to happen within a block time frame.
 
 How to improve it further:
 
-* **ERC20 Integration:** Implement the actual token transfer logic using `ink::env::call::build_call` to interact with an ERC20 token contract.  This would involve implementing the `transfer_funds` helper function more completely.
 * **Advanced Prediction Market Features:** Add features like:
     * **Partial Resolution:**  Allow for partial resolution of predictions (e.g., "50% likely breakthrough").
     * **Liquidity Pools:** Implement liquidity pools to allow users to trade prediction shares.
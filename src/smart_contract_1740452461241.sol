@@ -22,43 +22,87 @@
 //  Functions Summary:
 //    * `init()`: Initializes the contract.
 //    * `register_trainer(pubkey: PublicKey)`: Registers a user as a trainer, storing their public key for homomorphic encryption.
-//    * `submit_encrypted_data(model_id: u32, data: EncryptedData)`:  Trainers submit encrypted data for model training.
-//    * `train_model(model_id: u32)`: Initiates a model training epoch using the submitted encrypted data. The computation is performed off-chain with verifiable results submitted back on-chain.
+//    * `open_training_round(model_id: u32, deadline: u64, training_deadline: u64, min_participants: u32)`: Opens a federated-learning round whose data submission window closes at `deadline` and whose attested result is due by `training_deadline` (model owner only).
+//    * `fund_training_round(model_id: u32, amount: u64, purse: URef)`: Adds motes from the supplied purse to the current round's reward pool.  Each funder's share is recorded and refunded if the round fails.
+//    * `close_training_round(model_id: u32)`: Closes the current round once its deadline has passed, snapshotting contributions and starting training, or failing the round if too few trainers took part.
+//    * `fail_training_round(model_id: u32)`: Fails a round still waiting for its result after the training deadline, refunding the reward pool to its funders and unlocking its bonds.
+//    * `submit_encrypted_data(model_id: u32, data: EncryptedData, purse: URef)`:  Trainers submit encrypted data to the open training round, bonding the submission stake from the supplied purse.
+//    * `remove_my_submission(model_id: u32, submission_index: u32)`: Trainers withdraw one of their submissions while the round's submission window is open, releasing its bond.
+//    * `withdraw_submission_stake(model_id: u32, submission_index: u32)`: Releases a submission bond once the round that used it has completed or failed.
+//    * `flag_submission(model_id: u32, submission_index: u32, reason_hash: String)`: Verifiers flag a submission as poisoned, opening a dispute.
+//    * `vote_on_dispute(model_id: u32, submission_index: u32, slash: bool)`: Trainers with a locked submission bond vote on an open dispute during the dispute window, weighted by their locked stake. A voter cannot release any of their own bonds until every dispute they voted on is resolved.
+//    * `resolve_dispute(model_id: u32, submission_index: u32)`: Closes a dispute after the window; a slashed bond is credited to the verifier who flagged it.
+//    * `set_submission_stake(stake: u64)` / `set_dispute_window(window: u64)`: Configures the bond per submission and the dispute window in milliseconds (contract owner only).
+//    * `get_submission_bond(model_id: u32, submission_index: u32)` / `get_dispute(model_id: u32, submission_index: u32)`: Returns a submission's bond or dispute.
+//    * `get_submission_count(model_id: u32)`: Returns the number of submissions made for a model.
+//    * `get_submission(model_id: u32, submission_index: u32)`: Returns a submission's trainer and encrypted data (None once removed).
+//    * `train_model(model_id: u32)`: Closes the current round before its deadline and starts training on the submitted encrypted data (model owner only). The computation is performed off-chain with verifiable results submitted back on-chain.
+//    * `submit_training_result(model_id: u32, new_params_hash: String, metrics: String, accuracy: u32, attestation_sig: Vec<(PublicKey, Bytes)>)`: Completes the round's training epoch once a threshold of authorized verifier keys have signed the result, including its accuracy in basis points. Publishes the result as a new model version and splits the round's reward pool by contribution.
+//    * `get_training_round(model_id: u32, round_id: u32)` / `get_round_contribution(model_id: u32, round_id: u32, trainer: AccountHash)`: Returns a training round, or a trainer's submission count in it.
+//    * `add_verifier(public_key: PublicKey)` / `remove_verifier(public_key: PublicKey)`: Manages the verifier keys allowed to attest training results (contract owner only).
+//    * `set_verifier_threshold(threshold: u32)`: Sets how many verifier signatures a training result needs (contract owner only).
 //    * `register_model(model_metadata: ModelMetadata)`: Registers a new model, setting the initial price and owner.
-//    * `request_prediction(model_id: u32, input_data: Bytes)`: Requests a prediction from a registered model.
-//    * `set_prediction_price(model_id: u32, new_price: u64)`:  Updates the prediction price for a model (model owner only).
-//    * `withdraw_funds()`: Allows model owners and trainers to withdraw earned funds.
+//    * `publish_model_version(model_id: u32, model_metadata: ModelMetadata, params_hash: String)`: Publishes a new version of a model (model owner only).
+//    * `deprecate_model_version(model_id: u32, version: u32)`: Deprecates an old model version so it can no longer be pinned (model owner only).
+//    * `get_model_version(model_id: u32, version: u32)`: Returns a published model version.
+//    * `set_access_mode(model_id: u32, access_mode: AccessMode, subscription_price: u64)`: Makes a model public, allowlist-only, or subscription-based, with the subscription price in motes per day (model owner only).
+//    * `grant_access(model_id: u32, account: AccountHash)` / `revoke_access(model_id: u32, account: AccountHash)`: Manages the accounts allowed to use a restricted model (model owner only).
+//    * `purchase_subscription(model_id: u32, duration: u64, purse: URef)`: Buys or extends access to a subscription model for `duration` days.
+//    * `get_access_expiry(model_id: u32, account: AccountHash)`: Returns the block time, in milliseconds, until which an account can use a model.
+//    * `request_prediction(model_id: u32, input_data: Bytes, purse: URef, version: Option<u32>, max_price: u64)`: Queues a prediction request for a model the caller has access to, optionally pinned to a specific version, escrowing the dynamically computed price (see `quote_price`) from the supplied purse in the contract purse. Reverts if the price exceeds `max_price`. Returns the request id.
+//    * `fulfill_prediction(request_id: u64, output_hash: String)`: Delivers a prediction result on-chain and releases the escrowed payment to the model owner (prediction oracles only).
+//    * `refund_prediction(request_id: u64)`: Refunds the requester once a pending request has timed out.
+//    * `add_prediction_oracle(oracle: AccountHash)` / `remove_prediction_oracle(oracle: AccountHash)`: Manages the prediction oracle role (contract owner only).
+//    * `set_prediction_timeout(timeout: u64)`: Sets how long, in milliseconds, a request may stay pending before it can be refunded (contract owner only).
+//    * `get_prediction_request(request_id: u64)`: Returns a prediction request.
+//    * `get_requester_predictions(requester: AccountHash)`: Returns the ids of all prediction requests made by an account.
+//    * `set_prediction_price(model_id: u32, new_price: u64)`:  Updates the base prediction price for a model (model owner only).
+//    * `set_pricing_curve(curve: PricingCurve)`: Verifiers approve a curve configuring how demand and verified accuracy scale the base price, and the price bounds; it takes effect once the verifier threshold approves the same curve.
+//    * `quote_price(model_id: u32)`: Returns the price the next prediction request for a model will be charged.
+//    * `withdraw_funds()`: Allows model owners and trainers to withdraw earned funds from the contract purse to their account.
 //    * `get_model_details(model_id: u32)`: Returns details about a registered model.
-//    * `get_trainer_rewards(trainer_address: Address)`: Returns accumulated rewards for a trainer.
+//    * `get_trainer_rewards(trainer_address: AccountHash)`: Returns accumulated rewards for a trainer.
+//    * `list_models(offset: u32, limit: u32)`: Returns a page of registered models, in registration order, with their ids.
+//    * `list_models_by_owner(owner: AccountHash)`: Returns the ids of all models registered by an account.
+//    * `list_trainers(offset: u32, limit: u32)`: Returns a page of registered trainers, in registration order, with their accounts.
 //
 //  Data Structures:
 //    * `EncryptedData`: Represents data encrypted using a homomorphic encryption scheme.
 //    * `ModelMetadata`:  Stores metadata about a trained model (e.g., description, accuracy metrics).
-//    * `Model`: Stores model details, owner, price, accumulated rewards, and reputation score.
+//    * `Model`: Stores model details, owner, price, accumulated rewards, reputation score, whether training has started, the attested parameters hash and training epoch, the number of published versions and training rounds, its access mode and subscription price, and its verified accuracy and rolling demand.
+//    * `PricingCurve`: Governance parameters for dynamic prediction pricing.
+//    * `AccessMode`: Whether a model is open to everyone, restricted to an allowlist, or sold by subscription.
+//    * `ModelVersion`: Stores the metadata and parameters hash of a published model version and whether it is deprecated.
 //    * `Trainer`: Stores trainer public key for encryption and accumulated rewards.
+//    * `TrainingRound`: Stores a round's submission and training deadlines, participation requirement, participants, reward pool, status, and the model version it produced.
+//    * `SubmissionBond`: Stores the stake locked behind a submission, the training round it was submitted to, and whether it is locked, released, or slashed.
+//    * `Dispute`: Stores a flagged submission's dispute, its votes, and its outcome.
+//    * `PredictionRequest`: Stores a queued prediction request, the model version it runs against, its escrowed payment, status, and delivered output hash.
+//    Struct arguments and query results are passed as their bytesrepr encoding in a `Bytes` value.
 //
 //  Assumptions:
 //    *  The contract uses a hypothetical homomorphic encryption library.
 //    *  Model training and prediction are assumed to occur off-chain, with verifiable results submitted to the contract.
-//    *  The contract relies on a stablecoin or native token for payment.
+//    *  Payments are made in native CSPR motes held in a contract purse; internal balances track each account's share of it.
 // ----------------------------------------------------------------------------
 
 extern crate alloc;
 
 use alloc::{
+    format,
     string::{String, ToString},
     vec::Vec,
 };
 use casper_contract::{
-    contract_api::{runtime, storage},
+    contract_api::{runtime, storage, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
 use casper_types::{
     api_error::ApiError,
-    bytesrepr::{FromBytes, ToBytes},
+    bytesrepr::{self, FromBytes, ToBytes},
     contracts::{ContractHash, NamedKeys},
-    CLType, CLTyped, EntryPoint, EntryPointAccess, EntryPointCall, EntryPoints, Group, Key, Parameter,
-    URef, U256, U512, account::AccountHash, AsymmetricType, PublicKey, Bytes
+    CLType, CLTyped, CLValue, EntryPoint, EntryPointAccess, EntryPointCall, EntryPoints, Group, Key, Parameter,
+    URef, U256, U512, account::AccountHash, AsymmetricType, PublicKey, Bytes, Signature, crypto
 };
 
 mod homomorphic_encryption; // Hypothetical library for homomorphic encryption.
@@ -74,11 +118,82 @@ const ARG_INPUT_DATA: &str = "input_data";
 const ARG_PUBLIC_KEY: &str = "public_key";
 const ARG_MODEL_METADATA: &str = "model_metadata";
 const ARG_TRAINER_ADDRESS: &str = "trainer_address";
+const ARG_SUBMISSION_INDEX: &str = "submission_index";
+const ARG_NEW_PARAMS_HASH: &str = "new_params_hash";
+const ARG_METRICS: &str = "metrics";
+const ARG_ATTESTATION_SIG: &str = "attestation_sig";
+const ARG_THRESHOLD: &str = "threshold";
+const ARG_PURSE: &str = "purse";
+const ARG_REQUEST_ID: &str = "request_id";
+const ARG_OUTPUT_HASH: &str = "output_hash";
+const ARG_ORACLE: &str = "oracle";
+const ARG_TIMEOUT: &str = "timeout";
+const ARG_REQUESTER: &str = "requester";
+const ARG_PARAMS_HASH: &str = "params_hash";
+const ARG_VERSION: &str = "version";
+const ARG_ACCESS_MODE: &str = "access_mode";
+const ARG_SUBSCRIPTION_PRICE: &str = "subscription_price";
+const ARG_ACCOUNT: &str = "account";
+const ARG_DURATION: &str = "duration";
+const ARG_ACCURACY: &str = "accuracy";
+const ARG_CURVE: &str = "curve";
+const ARG_MAX_PRICE: &str = "max_price";
+const ARG_REASON_HASH: &str = "reason_hash";
+const ARG_SLASH: &str = "slash";
+const ARG_STAKE: &str = "stake";
+const ARG_WINDOW: &str = "window";
+const ARG_OFFSET: &str = "offset";
+const ARG_LIMIT: &str = "limit";
+const ARG_OWNER: &str = "owner";
+const ARG_DEADLINE: &str = "deadline";
+const ARG_MIN_PARTICIPANTS: &str = "min_participants";
+const ARG_TRAINING_DEADLINE: &str = "training_deadline";
+const ARG_AMOUNT: &str = "amount";
+const ARG_ROUND_ID: &str = "round_id";
+const ARG_TRAINER: &str = "trainer";
 
 const KEY_TRAINERS: &str = "trainers";
 const KEY_MODELS: &str = "models";
 const KEY_BALANCES: &str = "balances";
 const KEY_OWNER: &str = "owner";
+const KEY_SUBMISSIONS: &str = "submissions";
+const KEY_SUBMISSION_COUNTS: &str = "submission_counts";
+const KEY_SUBMISSION_TRAINERS: &str = "submission_trainers";
+const KEY_VERIFIERS: &str = "verifiers";
+const KEY_VERIFIER_THRESHOLD: &str = "verifier_threshold";
+const KEY_CONTRACT_PURSE: &str = "contract_purse";
+const KEY_PREDICTION_REQUESTS: &str = "prediction_requests";
+const KEY_REQUESTER_PREDICTIONS: &str = "requester_predictions";
+const KEY_PREDICTION_COUNT: &str = "prediction_count";
+const KEY_PREDICTION_ORACLES: &str = "prediction_oracles";
+const KEY_PREDICTION_TIMEOUT: &str = "prediction_timeout";
+const KEY_MODEL_VERSIONS: &str = "model_versions";
+const KEY_MODEL_ACCESS: &str = "model_access";
+const KEY_PRICING_CURVE: &str = "pricing_curve";
+const KEY_PENDING_PRICING_CURVE: &str = "pending_pricing_curve";
+const KEY_PRICING_CURVE_APPROVALS: &str = "pricing_curve_approvals";
+const KEY_SUBMISSION_BONDS: &str = "submission_bonds";
+const KEY_DISPUTES: &str = "disputes";
+const KEY_DISPUTE_VOTES: &str = "dispute_votes";
+const KEY_LOCKED_STAKES: &str = "locked_stakes";
+const KEY_OPEN_VOTE_COUNTS: &str = "open_vote_counts";
+const KEY_SUBMISSION_STAKE: &str = "submission_stake";
+const KEY_DISPUTE_WINDOW: &str = "dispute_window";
+const KEY_ALL_MODEL_IDS: &str = "all_model_ids";
+const KEY_MODEL_IDS_BY_OWNER: &str = "model_ids_by_owner";
+const KEY_MODEL_COUNT: &str = "model_count";
+const KEY_ALL_TRAINERS: &str = "all_trainers";
+const KEY_TRAINER_COUNT: &str = "trainer_count";
+const KEY_TRAINING_ROUNDS: &str = "training_rounds";
+const KEY_ROUND_CONTRIBUTIONS: &str = "round_contributions";
+const KEY_ROUND_FUNDERS: &str = "round_funders";
+
+const DEFAULT_PREDICTION_TIMEOUT: u64 = 60 * 60 * 1000; // One hour, in milliseconds
+const MILLIS_PER_DAY: u64 = 24 * 60 * 60 * 1000;
+const BASIS_POINTS: u64 = 10_000;
+const DEFAULT_SUBMISSION_STAKE: u64 = 1_000_000_000; // 1 CSPR, in motes
+const DEFAULT_DISPUTE_WINDOW: u64 = 3 * MILLIS_PER_DAY;
+const MAX_PAGE_SIZE: u32 = 50; // Upper bound on entries returned by the list entry points
 
 const METHOD_INIT: &str = "init";
 const METHOD_REGISTER_TRAINER: &str = "register_trainer";
@@ -90,6 +205,47 @@ const METHOD_SET_PREDICTION_PRICE: &str = "set_prediction_price";
 const METHOD_WITHDRAW_FUNDS: &str = "withdraw_funds";
 const METHOD_GET_MODEL_DETAILS: &str = "get_model_details";
 const METHOD_GET_TRAINER_REWARDS: &str = "get_trainer_rewards";
+const METHOD_REMOVE_MY_SUBMISSION: &str = "remove_my_submission";
+const METHOD_GET_SUBMISSION_COUNT: &str = "get_submission_count";
+const METHOD_GET_SUBMISSION: &str = "get_submission";
+const METHOD_SUBMIT_TRAINING_RESULT: &str = "submit_training_result";
+const METHOD_ADD_VERIFIER: &str = "add_verifier";
+const METHOD_REMOVE_VERIFIER: &str = "remove_verifier";
+const METHOD_SET_VERIFIER_THRESHOLD: &str = "set_verifier_threshold";
+const METHOD_FULFILL_PREDICTION: &str = "fulfill_prediction";
+const METHOD_REFUND_PREDICTION: &str = "refund_prediction";
+const METHOD_ADD_PREDICTION_ORACLE: &str = "add_prediction_oracle";
+const METHOD_REMOVE_PREDICTION_ORACLE: &str = "remove_prediction_oracle";
+const METHOD_SET_PREDICTION_TIMEOUT: &str = "set_prediction_timeout";
+const METHOD_GET_PREDICTION_REQUEST: &str = "get_prediction_request";
+const METHOD_GET_REQUESTER_PREDICTIONS: &str = "get_requester_predictions";
+const METHOD_PUBLISH_MODEL_VERSION: &str = "publish_model_version";
+const METHOD_DEPRECATE_MODEL_VERSION: &str = "deprecate_model_version";
+const METHOD_GET_MODEL_VERSION: &str = "get_model_version";
+const METHOD_SET_ACCESS_MODE: &str = "set_access_mode";
+const METHOD_GRANT_ACCESS: &str = "grant_access";
+const METHOD_REVOKE_ACCESS: &str = "revoke_access";
+const METHOD_PURCHASE_SUBSCRIPTION: &str = "purchase_subscription";
+const METHOD_GET_ACCESS_EXPIRY: &str = "get_access_expiry";
+const METHOD_SET_PRICING_CURVE: &str = "set_pricing_curve";
+const METHOD_QUOTE_PRICE: &str = "quote_price";
+const METHOD_WITHDRAW_SUBMISSION_STAKE: &str = "withdraw_submission_stake";
+const METHOD_FLAG_SUBMISSION: &str = "flag_submission";
+const METHOD_VOTE_ON_DISPUTE: &str = "vote_on_dispute";
+const METHOD_RESOLVE_DISPUTE: &str = "resolve_dispute";
+const METHOD_SET_SUBMISSION_STAKE: &str = "set_submission_stake";
+const METHOD_SET_DISPUTE_WINDOW: &str = "set_dispute_window";
+const METHOD_GET_SUBMISSION_BOND: &str = "get_submission_bond";
+const METHOD_GET_DISPUTE: &str = "get_dispute";
+const METHOD_LIST_MODELS: &str = "list_models";
+const METHOD_LIST_MODELS_BY_OWNER: &str = "list_models_by_owner";
+const METHOD_LIST_TRAINERS: &str = "list_trainers";
+const METHOD_OPEN_TRAINING_ROUND: &str = "open_training_round";
+const METHOD_FUND_TRAINING_ROUND: &str = "fund_training_round";
+const METHOD_CLOSE_TRAINING_ROUND: &str = "close_training_round";
+const METHOD_FAIL_TRAINING_ROUND: &str = "fail_training_round";
+const METHOD_GET_TRAINING_ROUND: &str = "get_training_round";
+const METHOD_GET_ROUND_CONTRIBUTION: &str = "get_round_contribution";
 
 const ACCESS_KEY_NAME: &str = "access_key";
 const ACCESS_UREF_NAME: &str = "access_uref";
@@ -172,6 +328,43 @@ impl CLTyped for ModelMetadata {
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum AccessMode {
+    Public,
+    Allowlist,
+    Subscription,
+}
+
+impl ToBytes for AccessMode {
+    fn to_bytes(&self) -> Result<Vec<u8>, casper_types::bytesrepr::Error> {
+        (*self as u8).to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        (*self as u8).serialized_length()
+    }
+}
+
+impl FromBytes for AccessMode {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), casper_types::bytesrepr::Error> {
+        let (tag, remainder): (u8, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let access_mode = match tag {
+            0 => AccessMode::Public,
+            1 => AccessMode::Allowlist,
+            2 => AccessMode::Subscription,
+            _ => return Err(casper_types::bytesrepr::Error::Formatting),
+        };
+        Ok((access_mode, remainder))
+    }
+}
+
+impl CLTyped for AccessMode {
+    fn cl_type() -> CLType {
+        CLType::U8
+    }
+}
+
+
 #[derive(Clone, PartialEq, Debug)]
 pub struct Model {
     pub owner: AccountHash,
@@ -179,6 +372,17 @@ pub struct Model {
     pub accumulated_rewards: u64,
     pub reputation_score: u32,
     pub metadata: ModelMetadata,
+    pub training_started: bool, // Submissions are locked once training starts
+    pub params_hash: String, // Hash of the latest attested model parameters
+    pub training_epoch: u32, // Number of attested training results, also used as the attestation nonce
+    pub version_count: u32, // Number of published versions; the latest version is `version_count`
+    pub round_count: u32, // Number of training rounds opened; the current round is `round_count - 1`
+    pub access_mode: AccessMode,
+    pub subscription_price: u64, // Motes per day of access, for subscription models
+    pub accuracy: u32, // Accuracy of the latest attested training result, in basis points
+    pub demand_window_start: u64, // Block time, in milliseconds, at which the current demand window opened
+    pub window_requests: u32, // Prediction requests in the current demand window
+    pub previous_window_requests: u32, // Prediction requests in the previous demand window
 }
 
 impl ToBytes for Model {
@@ -189,11 +393,26 @@ impl ToBytes for Model {
         result.extend(self.accumulated_rewards.to_bytes()?);
         result.extend(self.reputation_score.to_bytes()?);
         result.extend(self.metadata.to_bytes()?);
+        result.extend(self.training_started.to_bytes()?);
+        result.extend(self.params_hash.to_bytes()?);
+        result.extend(self.training_epoch.to_bytes()?);
+        result.extend(self.version_count.to_bytes()?);
+        result.extend(self.round_count.to_bytes()?);
+        result.extend(self.access_mode.to_bytes()?);
+        result.extend(self.subscription_price.to_bytes()?);
+        result.extend(self.accuracy.to_bytes()?);
+        result.extend(self.demand_window_start.to_bytes()?);
+        result.extend(self.window_requests.to_bytes()?);
+        result.extend(self.previous_window_requests.to_bytes()?);
         Ok(result)
     }
 
     fn serialized_length(&self) -> usize {
         self.owner.serialized_length() + self.price.serialized_length() + self.accumulated_rewards.serialized_length() + self.reputation_score.serialized_length() + self.metadata.serialized_length()
+            + self.training_started.serialized_length() + self.params_hash.serialized_length() + self.training_epoch.serialized_length()
+            + self.version_count.serialized_length() + self.round_count.serialized_length() + self.access_mode.serialized_length() + self.subscription_price.serialized_length()
+            + self.accuracy.serialized_length() + self.demand_window_start.serialized_length() + self.window_requests.serialized_length()
+            + self.previous_window_requests.serialized_length()
     }
 }
 
@@ -204,8 +423,22 @@ impl FromBytes for Model {
         let (accumulated_rewards, remainder) = FromBytes::from_bytes(remainder)?;
         let (reputation_score, remainder) = FromBytes::from_bytes(remainder)?;
         let (metadata, remainder) = FromBytes::from_bytes(remainder)?;
+        let (training_started, remainder) = FromBytes::from_bytes(remainder)?;
+        let (params_hash, remainder) = FromBytes::from_bytes(remainder)?;
+        let (training_epoch, remainder) = FromBytes::from_bytes(remainder)?;
+        let (version_count, remainder) = FromBytes::from_bytes(remainder)?;
+        let (round_count, remainder) = FromBytes::from_bytes(remainder)?;
+        let (access_mode, remainder) = FromBytes::from_bytes(remainder)?;
+        let (subscription_price, remainder) = FromBytes::from_bytes(remainder)?;
+        let (accuracy, remainder) = FromBytes::from_bytes(remainder)?;
+        let (demand_window_start, remainder) = FromBytes::from_bytes(remainder)?;
+        let (window_requests, remainder) = FromBytes::from_bytes(remainder)?;
+        let (previous_window_requests, remainder) = FromBytes::from_bytes(remainder)?;
         Ok((
-            Model { owner, price, accumulated_rewards, reputation_score, metadata },
+            Model {
+                owner, price, accumulated_rewards, reputation_score, metadata, training_started, params_hash, training_epoch, version_count,
+                round_count, access_mode, subscription_price, accuracy, demand_window_start, window_requests, previous_window_requests,
+            },
             remainder,
         ))
     }
@@ -218,6 +451,112 @@ impl CLTyped for Model {
 }
 
 
+#[derive(Clone, PartialEq, Debug)]
+pub struct PricingCurve {
+    pub demand_window: u64, // Length of a demand window, in milliseconds
+    pub demand_step_bps: u32, // Price increase per request in the last two demand windows, in basis points of the base price
+    pub accuracy_weight_bps: u32, // Price increase at 100% verified accuracy, in basis points of the base price
+    pub min_price: u64,
+    pub max_price: u64,
+}
+
+impl Default for PricingCurve {
+    fn default() -> Self {
+        PricingCurve {
+            demand_window: MILLIS_PER_DAY,
+            demand_step_bps: 0,
+            accuracy_weight_bps: 0,
+            min_price: 0,
+            max_price: u64::MAX,
+        }
+    }
+}
+
+impl ToBytes for PricingCurve {
+    fn to_bytes(&self) -> Result<Vec<u8>, casper_types::bytesrepr::Error> {
+        let mut result: Vec<u8> = Vec::new();
+        result.extend(self.demand_window.to_bytes()?);
+        result.extend(self.demand_step_bps.to_bytes()?);
+        result.extend(self.accuracy_weight_bps.to_bytes()?);
+        result.extend(self.min_price.to_bytes()?);
+        result.extend(self.max_price.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.demand_window.serialized_length() + self.demand_step_bps.serialized_length() + self.accuracy_weight_bps.serialized_length()
+            + self.min_price.serialized_length() + self.max_price.serialized_length()
+    }
+}
+
+impl FromBytes for PricingCurve {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), casper_types::bytesrepr::Error> {
+        let (demand_window, remainder) = FromBytes::from_bytes(bytes)?;
+        let (demand_step_bps, remainder) = FromBytes::from_bytes(remainder)?;
+        let (accuracy_weight_bps, remainder) = FromBytes::from_bytes(remainder)?;
+        let (min_price, remainder) = FromBytes::from_bytes(remainder)?;
+        let (max_price, remainder) = FromBytes::from_bytes(remainder)?;
+        Ok((
+            PricingCurve { demand_window, demand_step_bps, accuracy_weight_bps, min_price, max_price },
+            remainder,
+        ))
+    }
+}
+
+impl CLTyped for PricingCurve {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ModelVersion {
+    pub version: u32,
+    pub metadata: ModelMetadata,
+    pub params_hash: String,
+    pub published_at: u64, // Block time in milliseconds
+    pub deprecated: bool, // Deprecated versions can no longer be pinned by new prediction requests
+}
+
+impl ToBytes for ModelVersion {
+    fn to_bytes(&self) -> Result<Vec<u8>, casper_types::bytesrepr::Error> {
+        let mut result: Vec<u8> = Vec::new();
+        result.extend(self.version.to_bytes()?);
+        result.extend(self.metadata.to_bytes()?);
+        result.extend(self.params_hash.to_bytes()?);
+        result.extend(self.published_at.to_bytes()?);
+        result.extend(self.deprecated.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.version.serialized_length() + self.metadata.serialized_length() + self.params_hash.serialized_length()
+            + self.published_at.serialized_length() + self.deprecated.serialized_length()
+    }
+}
+
+impl FromBytes for ModelVersion {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), casper_types::bytesrepr::Error> {
+        let (version, remainder) = FromBytes::from_bytes(bytes)?;
+        let (metadata, remainder) = FromBytes::from_bytes(remainder)?;
+        let (params_hash, remainder) = FromBytes::from_bytes(remainder)?;
+        let (published_at, remainder) = FromBytes::from_bytes(remainder)?;
+        let (deprecated, remainder) = FromBytes::from_bytes(remainder)?;
+        Ok((
+            ModelVersion { version, metadata, params_hash, published_at, deprecated },
+            remainder,
+        ))
+    }
+}
+
+impl CLTyped for ModelVersion {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+
 #[derive(Clone, PartialEq, Debug)]
 pub struct Trainer {
     pub pubkey: PublicKey,
@@ -254,47 +593,371 @@ impl CLTyped for Trainer {
     }
 }
 
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum RoundStatus {
+    Open, // Accepting submissions until the deadline
+    Training, // Submissions closed and snapshotted, waiting for an attested result
+    Completed,
+    Failed, // Closed with fewer than `min_participants` trainers, or no result by the training deadline
+}
+
+impl ToBytes for RoundStatus {
+    fn to_bytes(&self) -> Result<Vec<u8>, casper_types::bytesrepr::Error> {
+        (*self as u8).to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        (*self as u8).serialized_length()
+    }
+}
+
+impl FromBytes for RoundStatus {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), casper_types::bytesrepr::Error> {
+        let (tag, remainder): (u8, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let status = match tag {
+            0 => RoundStatus::Open,
+            1 => RoundStatus::Training,
+            2 => RoundStatus::Completed,
+            3 => RoundStatus::Failed,
+            _ => return Err(casper_types::bytesrepr::Error::Formatting),
+        };
+        Ok((status, remainder))
+    }
+}
+
+impl CLTyped for RoundStatus {
+    fn cl_type() -> CLType {
+        CLType::U8
+    }
+}
+
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct TrainingRound {
+    pub round_id: u32,
+    pub deadline: u64, // Block time, in milliseconds, at which the submission window closes
+    pub training_deadline: u64, // Block time, in milliseconds, by which an attested result must arrive
+    pub min_participants: u32,
+    pub participants: Vec<AccountHash>, // Trainers with at least one live submission in the round
+    pub submission_count: u32, // Live submissions in the round
+    pub reward_pool: u64, // Motes split between participants when the round completes
+    pub status: RoundStatus,
+    pub version: u32, // Model version published from the round's result, 0 until completed
+}
+
+impl ToBytes for TrainingRound {
+    fn to_bytes(&self) -> Result<Vec<u8>, casper_types::bytesrepr::Error> {
+        let mut result: Vec<u8> = Vec::new();
+        result.extend(self.round_id.to_bytes()?);
+        result.extend(self.deadline.to_bytes()?);
+        result.extend(self.training_deadline.to_bytes()?);
+        result.extend(self.min_participants.to_bytes()?);
+        result.extend(self.participants.to_bytes()?);
+        result.extend(self.submission_count.to_bytes()?);
+        result.extend(self.reward_pool.to_bytes()?);
+        result.extend(self.status.to_bytes()?);
+        result.extend(self.version.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.round_id.serialized_length() + self.deadline.serialized_length() + self.training_deadline.serialized_length() + self.min_participants.serialized_length()
+            + self.participants.serialized_length()
+            + self.submission_count.serialized_length() + self.reward_pool.serialized_length() + self.status.serialized_length() + self.version.serialized_length()
+    }
+}
+
+impl FromBytes for TrainingRound {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), casper_types::bytesrepr::Error> {
+        let (round_id, remainder) = FromBytes::from_bytes(bytes)?;
+        let (deadline, remainder) = FromBytes::from_bytes(remainder)?;
+        let (training_deadline, remainder) = FromBytes::from_bytes(remainder)?;
+        let (min_participants, remainder) = FromBytes::from_bytes(remainder)?;
+        let (participants, remainder) = FromBytes::from_bytes(remainder)?;
+        let (submission_count, remainder) = FromBytes::from_bytes(remainder)?;
+        let (reward_pool, remainder) = FromBytes::from_bytes(remainder)?;
+        let (status, remainder) = FromBytes::from_bytes(remainder)?;
+        let (version, remainder) = FromBytes::from_bytes(remainder)?;
+        Ok((
+            TrainingRound { round_id, deadline, training_deadline, min_participants, participants, submission_count, reward_pool, status, version },
+            remainder,
+        ))
+    }
+}
+
+impl CLTyped for TrainingRound {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum BondStatus {
+    Locked,
+    Released,
+    Slashed,
+}
+
+impl ToBytes for BondStatus {
+    fn to_bytes(&self) -> Result<Vec<u8>, casper_types::bytesrepr::Error> {
+        (*self as u8).to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        (*self as u8).serialized_length()
+    }
+}
+
+impl FromBytes for BondStatus {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), casper_types::bytesrepr::Error> {
+        let (tag, remainder): (u8, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let status = match tag {
+            0 => BondStatus::Locked,
+            1 => BondStatus::Released,
+            2 => BondStatus::Slashed,
+            _ => return Err(casper_types::bytesrepr::Error::Formatting),
+        };
+        Ok((status, remainder))
+    }
+}
+
+impl CLTyped for BondStatus {
+    fn cl_type() -> CLType {
+        CLType::U8
+    }
+}
+
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct SubmissionBond {
+    pub trainer: AccountHash,
+    pub amount: u64, // Motes locked in the contract purse
+    pub round: u32, // Training round the submission was made to
+    pub status: BondStatus,
+}
+
+impl ToBytes for SubmissionBond {
+    fn to_bytes(&self) -> Result<Vec<u8>, casper_types::bytesrepr::Error> {
+        let mut result: Vec<u8> = Vec::new();
+        result.extend(self.trainer.to_bytes()?);
+        result.extend(self.amount.to_bytes()?);
+        result.extend(self.round.to_bytes()?);
+        result.extend(self.status.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.trainer.serialized_length() + self.amount.serialized_length() + self.round.serialized_length() + self.status.serialized_length()
+    }
+}
+
+impl FromBytes for SubmissionBond {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), casper_types::bytesrepr::Error> {
+        let (trainer, remainder) = FromBytes::from_bytes(bytes)?;
+        let (amount, remainder) = FromBytes::from_bytes(remainder)?;
+        let (round, remainder) = FromBytes::from_bytes(remainder)?;
+        let (status, remainder) = FromBytes::from_bytes(remainder)?;
+        Ok((
+            SubmissionBond { trainer, amount, round, status },
+            remainder,
+        ))
+    }
+}
+
+impl CLTyped for SubmissionBond {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Dispute {
+    pub trainer: AccountHash, // Trainer whose submission was flagged
+    pub flagged_by: AccountHash,
+    pub reason_hash: String, // Hash of the off-chain evidence
+    pub opened_at: u64, // Block time in milliseconds
+    pub slash_voters: Vec<AccountHash>,
+    pub keep_voters: Vec<AccountHash>,
+    pub slash_stake: u64, // Locked bond stake backing each side
+    pub keep_stake: u64,
+    pub resolved: bool,
+    pub slashed: bool,
+}
+
+impl ToBytes for Dispute {
+    fn to_bytes(&self) -> Result<Vec<u8>, casper_types::bytesrepr::Error> {
+        let mut result: Vec<u8> = Vec::new();
+        result.extend(self.trainer.to_bytes()?);
+        result.extend(self.flagged_by.to_bytes()?);
+        result.extend(self.reason_hash.to_bytes()?);
+        result.extend(self.opened_at.to_bytes()?);
+        result.extend(self.slash_voters.to_bytes()?);
+        result.extend(self.keep_voters.to_bytes()?);
+        result.extend(self.slash_stake.to_bytes()?);
+        result.extend(self.keep_stake.to_bytes()?);
+        result.extend(self.resolved.to_bytes()?);
+        result.extend(self.slashed.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.trainer.serialized_length() + self.flagged_by.serialized_length() + self.reason_hash.serialized_length() + self.opened_at.serialized_length()
+            + self.slash_voters.serialized_length() + self.keep_voters.serialized_length() + self.slash_stake.serialized_length() + self.keep_stake.serialized_length()
+            + self.resolved.serialized_length() + self.slashed.serialized_length()
+    }
+}
+
+impl FromBytes for Dispute {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), casper_types::bytesrepr::Error> {
+        let (trainer, remainder) = FromBytes::from_bytes(bytes)?;
+        let (flagged_by, remainder) = FromBytes::from_bytes(remainder)?;
+        let (reason_hash, remainder) = FromBytes::from_bytes(remainder)?;
+        let (opened_at, remainder) = FromBytes::from_bytes(remainder)?;
+        let (slash_voters, remainder) = FromBytes::from_bytes(remainder)?;
+        let (keep_voters, remainder) = FromBytes::from_bytes(remainder)?;
+        let (slash_stake, remainder) = FromBytes::from_bytes(remainder)?;
+        let (keep_stake, remainder) = FromBytes::from_bytes(remainder)?;
+        let (resolved, remainder) = FromBytes::from_bytes(remainder)?;
+        let (slashed, remainder) = FromBytes::from_bytes(remainder)?;
+        Ok((
+            Dispute { trainer, flagged_by, reason_hash, opened_at, slash_voters, keep_voters, slash_stake, keep_stake, resolved, slashed },
+            remainder,
+        ))
+    }
+}
+
+impl CLTyped for Dispute {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PredictionStatus {
+    Pending,
+    Fulfilled,
+    Refunded,
+}
+
+impl ToBytes for PredictionStatus {
+    fn to_bytes(&self) -> Result<Vec<u8>, casper_types::bytesrepr::Error> {
+        (*self as u8).to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        (*self as u8).serialized_length()
+    }
+}
+
+impl FromBytes for PredictionStatus {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), casper_types::bytesrepr::Error> {
+        let (tag, remainder): (u8, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let status = match tag {
+            0 => PredictionStatus::Pending,
+            1 => PredictionStatus::Fulfilled,
+            2 => PredictionStatus::Refunded,
+            _ => return Err(casper_types::bytesrepr::Error::Formatting),
+        };
+        Ok((status, remainder))
+    }
+}
+
+impl CLTyped for PredictionStatus {
+    fn cl_type() -> CLType {
+        CLType::U8
+    }
+}
+
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct PredictionRequest {
+    pub id: u64,
+    pub requester: AccountHash,
+    pub model_id: u32,
+    pub model_version: u32,
+    pub input_hash: String, // blake2b hash of the input data, hex encoded
+    pub status: PredictionStatus,
+    pub amount: u64, // Motes escrowed in the contract purse
+    pub requested_at: u64, // Block time in milliseconds
+    pub output_hash: Option<String>, // Set when the prediction is fulfilled
+}
+
+impl ToBytes for PredictionRequest {
+    fn to_bytes(&self) -> Result<Vec<u8>, casper_types::bytesrepr::Error> {
+        let mut result: Vec<u8> = Vec::new();
+        result.extend(self.id.to_bytes()?);
+        result.extend(self.requester.to_bytes()?);
+        result.extend(self.model_id.to_bytes()?);
+        result.extend(self.model_version.to_bytes()?);
+        result.extend(self.input_hash.to_bytes()?);
+        result.extend(self.status.to_bytes()?);
+        result.extend(self.amount.to_bytes()?);
+        result.extend(self.requested_at.to_bytes()?);
+        result.extend(self.output_hash.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.id.serialized_length() + self.requester.serialized_length() + self.model_id.serialized_length() + self.model_version.serialized_length()
+            + self.input_hash.serialized_length() + self.status.serialized_length() + self.amount.serialized_length() + self.requested_at.serialized_length() + self.output_hash.serialized_length()
+    }
+}
+
+impl FromBytes for PredictionRequest {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), casper_types::bytesrepr::Error> {
+        let (id, remainder) = FromBytes::from_bytes(bytes)?;
+        let (requester, remainder) = FromBytes::from_bytes(remainder)?;
+        let (model_id, remainder) = FromBytes::from_bytes(remainder)?;
+        let (model_version, remainder) = FromBytes::from_bytes(remainder)?;
+        let (input_hash, remainder) = FromBytes::from_bytes(remainder)?;
+        let (status, remainder) = FromBytes::from_bytes(remainder)?;
+        let (amount, remainder) = FromBytes::from_bytes(remainder)?;
+        let (requested_at, remainder) = FromBytes::from_bytes(remainder)?;
+        let (output_hash, remainder) = FromBytes::from_bytes(remainder)?;
+        Ok((
+            PredictionRequest { id, requester, model_id, model_version, input_hash, status, amount, requested_at, output_hash },
+            remainder,
+        ))
+    }
+}
+
+impl CLTyped for PredictionRequest {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
 // ----------------------------------------------------------------------------
 //  Storage Functions
 // ----------------------------------------------------------------------------
 
-fn get_trainers_uref() -> URef {
-    match runtime::get_key(KEY_TRAINERS) {
+fn get_dictionary_uref(name: &str) -> URef {
+    match runtime::get_key(name) {
         Some(key) => {
             key.try_into().unwrap_or_revert_with(ApiError::UnexpectedKeyType)
         }
         None => {
-            let uref = storage::new_dictionary(KEY_TRAINERS).unwrap_or_revert();
-            runtime::put_key(KEY_TRAINERS, Key::from(uref));
+            let uref = storage::new_dictionary(name).unwrap_or_revert();
+            runtime::put_key(name, Key::from(uref));
             uref
         }
     }
 }
 
+fn get_trainers_uref() -> URef {
+    get_dictionary_uref(KEY_TRAINERS)
+}
+
 fn get_models_uref() -> URef {
-    match runtime::get_key(KEY_MODELS) {
-        Some(key) => {
-            key.try_into().unwrap_or_revert_with(ApiError::UnexpectedKeyType)
-        }
-        None => {
-            let uref = storage::new_dictionary(KEY_MODELS).unwrap_or_revert();
-            runtime::put_key(KEY_MODELS, Key::from(uref));
-            uref
-        }
-    }
+    get_dictionary_uref(KEY_MODELS)
 }
 
 fn get_balances_uref() -> URef {
-    match runtime::get_key(KEY_BALANCES) {
-        Some(key) => {
-            key.try_into().unwrap_or_revert_with(ApiError::UnexpectedKeyType)
-        }
-        None => {
-            let uref = storage::new_dictionary(KEY_BALANCES).unwrap_or_revert();
-            runtime::put_key(KEY_BALANCES, Key::from(uref));
-            uref
-        }
-    }
+    get_dictionary_uref(KEY_BALANCES)
 }
 
 fn get_trainer(trainer_address: AccountHash) -> Option<Trainer> {
@@ -333,17 +996,376 @@ fn set_balance(account: AccountHash, amount: u64) {
     storage::dictionary_put(balances_uref, &account.to_string(), amount);
 }
 
+fn get_owner_model_ids(owner: AccountHash) -> Vec<u32> {
+    let index_uref = get_dictionary_uref(KEY_MODEL_IDS_BY_OWNER);
+    storage::dictionary_get::<Vec<u32>>(index_uref, &account_key(owner)).unwrap_or_revert().unwrap_or_default()
+}
 
-// ----------------------------------------------------------------------------
-//  Contract Entrypoints
-// ----------------------------------------------------------------------------
+// Appends a newly registered model to the global and per-owner indexes.
+fn index_model(model_id: u32, owner: AccountHash) {
+    let model_count: u32 = read_named_value(KEY_MODEL_COUNT).unwrap_or(0);
+    let all_ids_uref = get_dictionary_uref(KEY_ALL_MODEL_IDS);
+    storage::dictionary_put(all_ids_uref, &model_count.to_string(), model_id);
+    write_named_value(KEY_MODEL_COUNT, model_count + 1);
+
+    let mut owner_ids = get_owner_model_ids(owner);
+    owner_ids.push(model_id);
+    let index_uref = get_dictionary_uref(KEY_MODEL_IDS_BY_OWNER);
+    storage::dictionary_put(index_uref, &account_key(owner), owner_ids);
+}
+
+// Appends a newly registered trainer to the global trainer index.
+fn index_trainer(trainer_address: AccountHash) {
+    let trainer_count: u32 = read_named_value(KEY_TRAINER_COUNT).unwrap_or(0);
+    let all_trainers_uref = get_dictionary_uref(KEY_ALL_TRAINERS);
+    storage::dictionary_put(all_trainers_uref, &trainer_count.to_string(), trainer_address);
+    write_named_value(KEY_TRAINER_COUNT, trainer_count + 1);
+}
+
+// Clamps a requested page to the number of indexed entries and `MAX_PAGE_SIZE`.
+fn page_bounds(offset: u32, limit: u32, count: u32) -> (u32, u32) {
+    let start = offset.min(count);
+    let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+    (start, end)
+}
+
+// Dictionary item keys are limited to 64 bytes, so composite keys are hashed and hex encoded.
+fn submission_key(model_id: u32, trainer: AccountHash, submission_index: u32) -> String {
+    let mut bytes = model_id.to_bytes().unwrap_or_revert();
+    bytes.extend(trainer.to_bytes().unwrap_or_revert());
+    bytes.extend(submission_index.to_bytes().unwrap_or_revert());
+    base16::encode_lower(&runtime::blake2b(bytes))
+}
+
+fn get_submission_count_of(model_id: u32) -> u32 {
+    let counts_uref = get_dictionary_uref(KEY_SUBMISSION_COUNTS);
+    storage::dictionary_get::<u32>(counts_uref, &model_id.to_string()).unwrap_or_revert().unwrap_or(0)
+}
+
+fn get_submission_trainer(model_id: u32, submission_index: u32) -> Option<AccountHash> {
+    let trainers_uref = get_dictionary_uref(KEY_SUBMISSION_TRAINERS);
+    storage::dictionary_get::<AccountHash>(trainers_uref, &format!("{}_{}", model_id, submission_index)).unwrap_or_revert()
+}
+
+fn get_submission_data(model_id: u32, trainer: AccountHash, submission_index: u32) -> Option<EncryptedData> {
+    let submissions_uref = get_dictionary_uref(KEY_SUBMISSIONS);
+    storage::dictionary_get::<Option<EncryptedData>>(submissions_uref, &submission_key(model_id, trainer, submission_index))
+        .unwrap_or_revert()
+        .flatten()
+}
+
+fn set_submission_data(model_id: u32, trainer: AccountHash, submission_index: u32, data: Option<EncryptedData>) {
+    let submissions_uref = get_dictionary_uref(KEY_SUBMISSIONS);
+    storage::dictionary_put(submissions_uref, &submission_key(model_id, trainer, submission_index), data);
+}
+
+fn get_training_round_of(model_id: u32, round_id: u32) -> Option<TrainingRound> {
+    let rounds_uref = get_dictionary_uref(KEY_TRAINING_ROUNDS);
+    storage::dictionary_get::<TrainingRound>(rounds_uref, &format!("{}_{}", model_id, round_id)).unwrap_or_revert()
+}
+
+fn set_training_round(model_id: u32, round: TrainingRound) {
+    let rounds_uref = get_dictionary_uref(KEY_TRAINING_ROUNDS);
+    storage::dictionary_put(rounds_uref, &format!("{}_{}", model_id, round.round_id), round);
+}
+
+fn get_current_round(model_id: u32, model: &Model) -> TrainingRound {
+    if model.round_count == 0 {
+        runtime::revert(ApiError::NoSuchValue); // No training round has been opened
+    }
+    get_training_round_of(model_id, model.round_count - 1).unwrap_or_revert_with(ApiError::MissingKey)
+}
+
+fn round_contribution_key(model_id: u32, round_id: u32, trainer: AccountHash) -> String {
+    let mut bytes = model_id.to_bytes().unwrap_or_revert();
+    bytes.extend(round_id.to_bytes().unwrap_or_revert());
+    bytes.extend(trainer.to_bytes().unwrap_or_revert());
+    base16::encode_lower(&runtime::blake2b(bytes))
+}
+
+fn get_round_contribution_of(model_id: u32, round_id: u32, trainer: AccountHash) -> u32 {
+    let contributions_uref = get_dictionary_uref(KEY_ROUND_CONTRIBUTIONS);
+    storage::dictionary_get::<u32>(contributions_uref, &round_contribution_key(model_id, round_id, trainer)).unwrap_or_revert().unwrap_or(0)
+}
+
+fn set_round_contribution(model_id: u32, round_id: u32, trainer: AccountHash, contribution: u32) {
+    let contributions_uref = get_dictionary_uref(KEY_ROUND_CONTRIBUTIONS);
+    storage::dictionary_put(contributions_uref, &round_contribution_key(model_id, round_id, trainer), contribution);
+}
+
+// Accounts that funded a round's reward pool and the motes each of them added.
+fn get_round_funders(model_id: u32, round_id: u32) -> Vec<(AccountHash, u64)> {
+    let funders_uref = get_dictionary_uref(KEY_ROUND_FUNDERS);
+    storage::dictionary_get::<Vec<(AccountHash, u64)>>(funders_uref, &format!("{}_{}", model_id, round_id)).unwrap_or_revert().unwrap_or_default()
+}
+
+fn set_round_funders(model_id: u32, round_id: u32, funders: Vec<(AccountHash, u64)>) {
+    let funders_uref = get_dictionary_uref(KEY_ROUND_FUNDERS);
+    storage::dictionary_put(funders_uref, &format!("{}_{}", model_id, round_id), funders);
+}
+
+// Marks a round as failed.  The reward pool returns to the accounts that funded it, each getting
+// back what they added, and the round's bonds become withdrawable.
+fn fail_round(model_id: u32, round: &mut TrainingRound) {
+    for (funder, amount) in get_round_funders(model_id, round.round_id) {
+        let mut funder_balance = get_balance(funder);
+        funder_balance += amount;
+        set_balance(funder, funder_balance);
+    }
+
+    round.reward_pool = 0;
+    round.status = RoundStatus::Failed;
+}
+
+// Closes an open round's submission window.  Participants and their contribution counts can no
+// longer change, so they form the round's contribution snapshot.
+fn close_round(model_id: u32, mut model: Model, mut round: TrainingRound) {
+    if (round.participants.len() as u32) < round.min_participants {
+        fail_round(model_id, &mut round);
+    } else {
+        round.status = RoundStatus::Training;
+        model.training_started = true;
+        set_model(model_id, model);
+    }
+    set_training_round(model_id, round);
+}
+
+// Bonds and disputes are keyed by "{model_id}_{submission_index}", the same as the submission trainer index.
+fn get_submission_bond_of(model_id: u32, submission_index: u32) -> Option<SubmissionBond> {
+    let bonds_uref = get_dictionary_uref(KEY_SUBMISSION_BONDS);
+    storage::dictionary_get::<SubmissionBond>(bonds_uref, &format!("{}_{}", model_id, submission_index)).unwrap_or_revert()
+}
+
+fn set_submission_bond(model_id: u32, submission_index: u32, bond: SubmissionBond) {
+    let bonds_uref = get_dictionary_uref(KEY_SUBMISSION_BONDS);
+    storage::dictionary_put(bonds_uref, &format!("{}_{}", model_id, submission_index), bond);
+}
+
+fn get_dispute_of(model_id: u32, submission_index: u32) -> Option<Dispute> {
+    let disputes_uref = get_dictionary_uref(KEY_DISPUTES);
+    storage::dictionary_get::<Dispute>(disputes_uref, &format!("{}_{}", model_id, submission_index)).unwrap_or_revert()
+}
+
+fn set_dispute(model_id: u32, submission_index: u32, dispute: Dispute) {
+    let disputes_uref = get_dictionary_uref(KEY_DISPUTES);
+    storage::dictionary_put(disputes_uref, &format!("{}_{}", model_id, submission_index), dispute);
+}
+
+fn has_open_dispute(model_id: u32, submission_index: u32) -> bool {
+    matches!(get_dispute_of(model_id, submission_index), Some(dispute) if !dispute.resolved)
+}
+
+fn dispute_vote_key(model_id: u32, submission_index: u32, voter: AccountHash) -> String {
+    let mut bytes = model_id.to_bytes().unwrap_or_revert();
+    bytes.extend(submission_index.to_bytes().unwrap_or_revert());
+    bytes.extend(voter.to_bytes().unwrap_or_revert());
+    base16::encode_lower(&runtime::blake2b(bytes))
+}
+
+// Total submission bond stake an account currently has locked; this is its dispute voting weight.
+fn get_locked_stake_of(account: AccountHash) -> u64 {
+    let stakes_uref = get_dictionary_uref(KEY_LOCKED_STAKES);
+    storage::dictionary_get::<u64>(stakes_uref, &account_key(account)).unwrap_or_revert().unwrap_or(0)
+}
+
+fn set_locked_stake(account: AccountHash, amount: u64) {
+    let stakes_uref = get_dictionary_uref(KEY_LOCKED_STAKES);
+    storage::dictionary_put(stakes_uref, &account_key(account), amount);
+}
+
+// Number of unresolved disputes an account has voted on. Its stake backs those votes, so while this is
+// non-zero the account cannot release a bond of its own.
+fn get_open_vote_count_of(account: AccountHash) -> u32 {
+    let counts_uref = get_dictionary_uref(KEY_OPEN_VOTE_COUNTS);
+    storage::dictionary_get::<u32>(counts_uref, &account_key(account)).unwrap_or_revert().unwrap_or(0)
+}
+
+fn set_open_vote_count(account: AccountHash, count: u32) {
+    let counts_uref = get_dictionary_uref(KEY_OPEN_VOTE_COUNTS);
+    storage::dictionary_put(counts_uref, &account_key(account), count);
+}
+
+// Moves a locked bond back into the trainer's withdrawable balance.
+fn release_submission_bond(model_id: u32, submission_index: u32, mut bond: SubmissionBond) {
+    set_locked_stake(bond.trainer, get_locked_stake_of(bond.trainer) - bond.amount);
+
+    let mut trainer_balance = get_balance(bond.trainer);
+    trainer_balance += bond.amount;
+    set_balance(bond.trainer, trainer_balance);
+
+    bond.status = BondStatus::Released;
+    set_submission_bond(model_id, submission_index, bond);
+}
+
+fn get_contract_owner() -> AccountHash {
+    runtime::get_key(KEY_OWNER)
+        .and_then(|key| key.into_account())
+        .unwrap_or_revert_with(ApiError::MissingKey)
+}
+
+fn get_contract_purse() -> URef {
+    runtime::get_key(KEY_CONTRACT_PURSE)
+        .and_then(|key| key.into_uref())
+        .unwrap_or_revert_with(ApiError::MissingKey)
+}
+
+fn require_contract_owner() {
+    if runtime::get_caller() != get_contract_owner() {
+        runtime::revert(ApiError::PermissionDenied); // Only the contract owner can call this entry point
+    }
+}
+
+fn read_named_value<T: CLTyped + FromBytes>(name: &str) -> Option<T> {
+    let uref: URef = runtime::get_key(name)?
+        .try_into()
+        .unwrap_or_revert_with(ApiError::UnexpectedKeyType);
+    storage::read(uref).unwrap_or_revert()
+}
+
+// Struct types have an opaque CLType, so they cross the contract boundary as bytesrepr-encoded `Bytes`.
+fn get_serialized_arg<T: FromBytes>(name: &str) -> T {
+    let bytes: Bytes = runtime::get_named_arg(name);
+    bytesrepr::deserialize(bytes.take_inner()).unwrap_or_revert_with(ApiError::InvalidArgument)
+}
+
+fn ret_serialized<T: ToBytes>(value: T) -> ! {
+    let bytes = Bytes::from(value.to_bytes().unwrap_or_revert());
+    runtime::ret(CLValue::from_t(bytes).unwrap_or_revert())
+}
+
+fn write_named_value<T: CLTyped + ToBytes>(name: &str, value: T) {
+    match runtime::get_key(name) {
+        Some(key) => {
+            let uref: URef = key.try_into().unwrap_or_revert_with(ApiError::UnexpectedKeyType);
+            storage::write(uref, value);
+        }
+        None => runtime::put_key(name, Key::from(storage::new_uref(value))),
+    }
+}
+
+fn get_model_version_of(model_id: u32, version: u32) -> Option<ModelVersion> {
+    let versions_uref = get_dictionary_uref(KEY_MODEL_VERSIONS);
+    storage::dictionary_get::<ModelVersion>(versions_uref, &format!("{}_{}", model_id, version)).unwrap_or_revert()
+}
+
+fn set_model_version(model_id: u32, model_version: ModelVersion) {
+    let versions_uref = get_dictionary_uref(KEY_MODEL_VERSIONS);
+    storage::dictionary_put(versions_uref, &format!("{}_{}", model_id, model_version.version), model_version);
+}
+
+fn model_access_key(model_id: u32, account: AccountHash) -> String {
+    let mut bytes = model_id.to_bytes().unwrap_or_revert();
+    bytes.extend(account.to_bytes().unwrap_or_revert());
+    base16::encode_lower(&runtime::blake2b(bytes))
+}
+
+fn get_access_expiry_of(model_id: u32, account: AccountHash) -> u64 {
+    let access_uref = get_dictionary_uref(KEY_MODEL_ACCESS);
+    storage::dictionary_get::<u64>(access_uref, &model_access_key(model_id, account)).unwrap_or_revert().unwrap_or(0)
+}
+
+fn set_access_expiry(model_id: u32, account: AccountHash, expiry: u64) {
+    let access_uref = get_dictionary_uref(KEY_MODEL_ACCESS);
+    storage::dictionary_put(access_uref, &model_access_key(model_id, account), expiry);
+}
+
+fn has_model_access(model_id: u32, model: &Model, account: AccountHash) -> bool {
+    model.access_mode == AccessMode::Public
+        || model.owner == account
+        || get_access_expiry_of(model_id, account) > u64::from(runtime::get_blocktime())
+}
+
+// An account hash is 32 bytes, so its hex encoding is exactly the 64-byte dictionary key limit.
+fn account_key(account: AccountHash) -> String {
+    base16::encode_lower(&account.value())
+}
+
+fn get_prediction_request_of(request_id: u64) -> Option<PredictionRequest> {
+    let requests_uref = get_dictionary_uref(KEY_PREDICTION_REQUESTS);
+    storage::dictionary_get::<PredictionRequest>(requests_uref, &request_id.to_string()).unwrap_or_revert()
+}
+
+fn set_prediction_request(request: PredictionRequest) {
+    let requests_uref = get_dictionary_uref(KEY_PREDICTION_REQUESTS);
+    storage::dictionary_put(requests_uref, &request.id.to_string(), request);
+}
+
+fn get_requester_prediction_ids(requester: AccountHash) -> Vec<u64> {
+    let index_uref = get_dictionary_uref(KEY_REQUESTER_PREDICTIONS);
+    storage::dictionary_get::<Vec<u64>>(index_uref, &account_key(requester)).unwrap_or_revert().unwrap_or_default()
+}
+
+fn get_prediction_oracles() -> Vec<AccountHash> {
+    read_named_value(KEY_PREDICTION_ORACLES).unwrap_or_default()
+}
+
+fn get_verifiers() -> Vec<PublicKey> {
+    read_named_value(KEY_VERIFIERS).unwrap_or_default()
+}
+
+fn get_verifier_threshold() -> u32 {
+    read_named_value(KEY_VERIFIER_THRESHOLD).unwrap_or(1)
+}
+
+// The message verifiers sign: binds the result to the model and its current epoch so signatures can't be replayed.
+fn attestation_message(model_id: u32, training_epoch: u32, params_hash: &String, metrics: &String, accuracy: u32) -> [u8; 32] {
+    let mut bytes = model_id.to_bytes().unwrap_or_revert();
+    bytes.extend(training_epoch.to_bytes().unwrap_or_revert());
+    bytes.extend(params_hash.to_bytes().unwrap_or_revert());
+    bytes.extend(metrics.to_bytes().unwrap_or_revert());
+    bytes.extend(accuracy.to_bytes().unwrap_or_revert());
+    runtime::blake2b(bytes)
+}
+
+fn get_pricing_curve() -> PricingCurve {
+    read_named_value(KEY_PRICING_CURVE).unwrap_or_default()
+}
+
+// Rolls the demand window forward if it has elapsed.  Demand is the number of requests in the
+// current and previous windows, so the price decays smoothly instead of resetting at window boundaries.
+fn roll_demand_window(model: &mut Model, curve: &PricingCurve, now: u64) {
+    if now < model.demand_window_start.saturating_add(curve.demand_window) {
+        return;
+    }
+    if now < model.demand_window_start.saturating_add(curve.demand_window.saturating_mul(2)) {
+        model.previous_window_requests = model.window_requests;
+    } else {
+        model.previous_window_requests = 0;
+    }
+    model.window_requests = 0;
+    model.demand_window_start = now;
+}
+
+fn compute_price(model: &Model, curve: &PricingCurve) -> u64 {
+    let demand = u64::from(model.window_requests) + u64::from(model.previous_window_requests);
+    let multiplier_bps = BASIS_POINTS
+        + demand * u64::from(curve.demand_step_bps)
+        + u64::from(model.accuracy) * u64::from(curve.accuracy_weight_bps) / BASIS_POINTS;
+    let price = (u128::from(model.price) * u128::from(multiplier_bps) / u128::from(BASIS_POINTS)).min(u128::from(u64::MAX)) as u64;
+    price.max(curve.min_price).min(curve.max_price)
+}
+
+
+// ----------------------------------------------------------------------------
+//  Contract Entrypoints
+// ----------------------------------------------------------------------------
 
 #[no_mangle]
 pub extern "C" fn init() {
     // This function would typically perform initialization tasks, such as setting up initial balances
-    // or other contract parameters.  For simplicity, we'll leave it empty in this example.
+    // or other contract parameters.  It is called once by `call()` during installation; the owner it
+    // records gates every administrative entry point, so later calls must not be able to replace it.
+    if runtime::get_key(KEY_OWNER).is_some() {
+        runtime::revert(ApiError::PermissionDenied); // Contract already initialized
+    }
     let account: AccountHash = runtime::get_caller();
     runtime::put_key(KEY_OWNER, Key::from(account));
+
+    // Purse holding all prediction payments until they are withdrawn.
+    if runtime::get_key(KEY_CONTRACT_PURSE).is_none() {
+        let contract_purse = system::create_purse();
+        runtime::put_key(KEY_CONTRACT_PURSE, Key::from(contract_purse));
+    }
 }
 
 
@@ -362,152 +1384,1066 @@ pub extern "C" fn register_trainer() {
     };
 
     set_trainer(trainer_address, trainer);
+    index_trainer(trainer_address);
 }
 
 
 #[no_mangle]
 pub extern "C" fn submit_encrypted_data() {
     let model_id: u32 = runtime::get_named_arg(ARG_MODEL_ID);
-    let data: EncryptedData = runtime::get_named_arg(ARG_DATA);
+    let data: EncryptedData = get_serialized_arg(ARG_DATA);
+    let purse: URef = runtime::get_named_arg(ARG_PURSE);
     let trainer_address = runtime::get_caller();
 
     if get_trainer(trainer_address).is_none() {
         runtime::revert(ApiError::PermissionDenied); // Only registered trainers can submit data
     }
 
-    // In a real implementation, this would store the encrypted data in a suitable storage mechanism,
-    // potentially linking it to the model and the trainer.  For simplicity, we'll just print a debug message.
-    runtime::print(format!("Received encrypted data for model {}: {:?}", model_id, data));
+    let model = match get_model(model_id) {
+        Some(model) => model,
+        None => runtime::revert(ApiError::NoSuchValue), // Model does not exist
+    };
 
-    //TODO: Store Encrypted Data to Dictionary with key is: model_id_trainer_address
+    let mut round = get_current_round(model_id, &model);
+    if round.status != RoundStatus::Open || u64::from(runtime::get_blocktime()) >= round.deadline {
+        runtime::revert(ApiError::PermissionDenied); // The round's submission window is closed
+    }
 
+    // Bond the submission so poisoned data can be slashed
+    let stake: u64 = read_named_value(KEY_SUBMISSION_STAKE).unwrap_or(DEFAULT_SUBMISSION_STAKE);
+    if system::get_purse_balance(purse).unwrap_or_revert() < U512::from(stake) {
+        runtime::revert(ApiError::InsufficientFunds);
+    }
+    system::transfer_from_purse_to_purse(purse, get_contract_purse(), U512::from(stake), None).unwrap_or_revert();
+
+    // Store the encrypted data under (model_id, trainer, submission_index) and index the trainer
+    // so submissions can be enumerated per model.
+    let submission_index = get_submission_count_of(model_id);
+    set_submission_data(model_id, trainer_address, submission_index, Some(data));
+
+    let trainers_uref = get_dictionary_uref(KEY_SUBMISSION_TRAINERS);
+    storage::dictionary_put(trainers_uref, &format!("{}_{}", model_id, submission_index), trainer_address);
+
+    let counts_uref = get_dictionary_uref(KEY_SUBMISSION_COUNTS);
+    storage::dictionary_put(counts_uref, &model_id.to_string(), submission_index + 1);
+
+    set_submission_bond(model_id, submission_index, SubmissionBond {
+        trainer: trainer_address,
+        amount: stake,
+        round: round.round_id,
+        status: BondStatus::Locked,
+    });
+    set_locked_stake(trainer_address, get_locked_stake_of(trainer_address) + stake);
+
+    // Record the contribution towards the round
+    let contribution = get_round_contribution_of(model_id, round.round_id, trainer_address);
+    set_round_contribution(model_id, round.round_id, trainer_address, contribution + 1);
+    if contribution == 0 {
+        round.participants.push(trainer_address);
+    }
+    round.submission_count += 1;
+    set_training_round(model_id, round);
 }
 
 
 #[no_mangle]
-pub extern "C" fn train_model() {
+pub extern "C" fn remove_my_submission() {
     let model_id: u32 = runtime::get_named_arg(ARG_MODEL_ID);
+    let submission_index: u32 = runtime::get_named_arg(ARG_SUBMISSION_INDEX);
     let caller = runtime::get_caller();
 
-    let mut model = match get_model(model_id) {
-        Some(model) => model,
-        None => runtime::revert(ApiError::NoSuchValue), // Model does not exist
+    if get_model(model_id).is_none() {
+        runtime::revert(ApiError::NoSuchValue); // Model does not exist
+    }
+
+    // Looking the submission up under the caller's own key doubles as the ownership check.
+    if get_submission_data(model_id, caller, submission_index).is_none() {
+        runtime::revert(ApiError::NoSuchValue); // No live submission by the caller at this index
+    }
+
+    if has_open_dispute(model_id, submission_index) {
+        runtime::revert(ApiError::PermissionDenied); // Disputed submissions cannot be withdrawn
+    }
+
+    if get_open_vote_count_of(caller) > 0 {
+        runtime::revert(ApiError::PermissionDenied); // Stake backing an open dispute vote stays locked
+    }
+
+    let bond = get_submission_bond_of(model_id, submission_index).unwrap_or_revert_with(ApiError::MissingKey);
+    let mut round = get_training_round_of(model_id, bond.round).unwrap_or_revert_with(ApiError::MissingKey);
+    if round.status != RoundStatus::Open || u64::from(runtime::get_blocktime()) >= round.deadline {
+        runtime::revert(ApiError::PermissionDenied); // Submissions are locked once the round's window closes
+    }
+
+    set_submission_data(model_id, caller, submission_index, None);
+
+    // Take the submission out of the round's contributions
+    let contribution = get_round_contribution_of(model_id, round.round_id, caller) - 1;
+    set_round_contribution(model_id, round.round_id, caller, contribution);
+    if contribution == 0 {
+        round.participants.retain(|participant| participant != &caller);
+    }
+    round.submission_count -= 1;
+    set_training_round(model_id, round);
+
+    if bond.status == BondStatus::Locked {
+        release_submission_bond(model_id, submission_index, bond);
+    }
+}
+
+
+#[no_mangle]
+pub extern "C" fn withdraw_submission_stake() {
+    let model_id: u32 = runtime::get_named_arg(ARG_MODEL_ID);
+    let submission_index: u32 = runtime::get_named_arg(ARG_SUBMISSION_INDEX);
+
+    if get_model(model_id).is_none() {
+        runtime::revert(ApiError::NoSuchValue); // Model does not exist
+    }
+
+    let bond = match get_submission_bond_of(model_id, submission_index) {
+        Some(bond) => bond,
+        None => runtime::revert(ApiError::NoSuchValue), // Submission has no bond
     };
 
-    if model.owner != caller {
-        runtime::revert(ApiError::PermissionDenied); // Only the model owner can initiate training
+    if bond.trainer != runtime::get_caller() {
+        runtime::revert(ApiError::PermissionDenied); // Only the submitting trainer can withdraw the bond
     }
 
-    //TODO:
-    // 1. Collect encrypted data from trainers for the specified model.
-    // 2. Perform the training using homomorphic encryption (off-chain).
-    // 3. Verify the training results.
-    // 4. Update the model parameters and metadata (e.g., accuracy) in storage.
-    // 5. Distribute rewards to trainers based on their contribution.
-    runtime::print("Perform Training On-Chain");
-    // Simulate training completion and reward distribution
-    model.reputation_score += 10; // Increase reputation after successful training
-    model.metadata.accuracy_metrics = "Simulated improved accuracy".to_string();
-    set_model(model_id, model);
+    if bond.status != BondStatus::Locked {
+        runtime::revert(ApiError::InvalidArgument); // Bond already released or slashed
+    }
+
+    let round = get_training_round_of(model_id, bond.round).unwrap_or_revert_with(ApiError::MissingKey);
+    if round.status != RoundStatus::Completed && round.status != RoundStatus::Failed {
+        runtime::revert(ApiError::PermissionDenied); // Round using the submission has not finished
+    }
+
+    if has_open_dispute(model_id, submission_index) {
+        runtime::revert(ApiError::PermissionDenied); // Bond is locked while the submission is disputed
+    }
+
+    if get_open_vote_count_of(bond.trainer) > 0 {
+        runtime::revert(ApiError::PermissionDenied); // Stake backing an open dispute vote stays locked
+    }
+
+    release_submission_bond(model_id, submission_index, bond);
 }
 
 
 #[no_mangle]
-pub extern "C" fn register_model() {
-    let model_metadata: ModelMetadata = runtime::get_named_arg(ARG_MODEL_METADATA);
+pub extern "C" fn flag_submission() {
     let model_id: u32 = runtime::get_named_arg(ARG_MODEL_ID);
+    let submission_index: u32 = runtime::get_named_arg(ARG_SUBMISSION_INDEX);
+    let reason_hash: String = runtime::get_named_arg(ARG_REASON_HASH);
     let caller = runtime::get_caller();
 
-    if get_model(model_id).is_some() {
-        runtime::revert(ApiError::InvalidPurse); // Model ID already exists
+    if !get_verifiers().iter().any(|verifier| verifier.to_account_hash() == caller) {
+        runtime::revert(ApiError::PermissionDenied); // Only verifiers can flag submissions
     }
 
-    let model = Model {
-        owner: caller,
-        price: 100, // Initial prediction price
-        accumulated_rewards: 0,
-        reputation_score: 0,
-        metadata: model_metadata,
+    let bond = match get_submission_bond_of(model_id, submission_index) {
+        Some(bond) => bond,
+        None => runtime::revert(ApiError::NoSuchValue), // Submission has no bond
     };
 
-    set_model(model_id, model);
+    if bond.status != BondStatus::Locked {
+        runtime::revert(ApiError::InvalidArgument); // Bond already released or slashed
+    }
+
+    if get_dispute_of(model_id, submission_index).is_some() {
+        runtime::revert(ApiError::InvalidArgument); // Submission has already been disputed
+    }
+
+    set_dispute(model_id, submission_index, Dispute {
+        trainer: bond.trainer,
+        flagged_by: caller,
+        reason_hash,
+        opened_at: u64::from(runtime::get_blocktime()),
+        slash_voters: Vec::new(),
+        keep_voters: Vec::new(),
+        slash_stake: 0,
+        keep_stake: 0,
+        resolved: false,
+        slashed: false,
+    });
 }
 
 
 #[no_mangle]
-pub extern "C" fn request_prediction() {
+pub extern "C" fn vote_on_dispute() {
     let model_id: u32 = runtime::get_named_arg(ARG_MODEL_ID);
-    let input_data: Bytes = runtime::get_named_arg(ARG_INPUT_DATA);
+    let submission_index: u32 = runtime::get_named_arg(ARG_SUBMISSION_INDEX);
+    let slash: bool = runtime::get_named_arg(ARG_SLASH);
     let caller = runtime::get_caller();
 
-    let model = match get_model(model_id) {
-        Some(model) => model,
-        None => runtime::revert(ApiError::NoSuchValue), // Model does not exist
+    let weight = get_locked_stake_of(caller);
+    if weight == 0 {
+        runtime::revert(ApiError::PermissionDenied); // Only trainers with a locked bond can vote
+    }
+
+    let mut dispute = match get_dispute_of(model_id, submission_index) {
+        Some(dispute) => dispute,
+        None => runtime::revert(ApiError::NoSuchValue), // Dispute does not exist
     };
 
-    // Check if the caller has sufficient balance
-    let mut caller_balance = get_balance(caller);
-    if caller_balance < model.price {
-        runtime::revert(ApiError::InsufficientFunds);
+    if dispute.resolved {
+        runtime::revert(ApiError::InvalidArgument); // Dispute already resolved
     }
 
-    // Transfer funds from the caller to the model owner
-    caller_balance -= model.price;
-    set_balance(caller, caller_balance);
+    if dispute.trainer == caller {
+        runtime::revert(ApiError::PermissionDenied); // Trainers cannot vote on their own dispute
+    }
 
-    let mut model_owner_balance = get_balance(model.owner);
-    model_owner_balance += model.price;
-    set_balance(model.owner, model_owner_balance);
+    let window: u64 = read_named_value(KEY_DISPUTE_WINDOW).unwrap_or(DEFAULT_DISPUTE_WINDOW);
+    if u64::from(runtime::get_blocktime()) >= dispute.opened_at + window {
+        runtime::revert(ApiError::PermissionDenied); // Dispute window has closed
+    }
+
+    let votes_uref = get_dictionary_uref(KEY_DISPUTE_VOTES);
+    let vote_key = dispute_vote_key(model_id, submission_index, caller);
+    if storage::dictionary_get::<bool>(votes_uref, &vote_key).unwrap_or_revert().is_some() {
+        runtime::revert(ApiError::InvalidArgument); // Caller has already voted
+    }
+    storage::dictionary_put(votes_uref, &vote_key, slash);
+    set_open_vote_count(caller, get_open_vote_count_of(caller) + 1);
+
+    if slash {
+        dispute.slash_voters.push(caller);
+        dispute.slash_stake += weight;
+    } else {
+        dispute.keep_voters.push(caller);
+        dispute.keep_stake += weight;
+    }
+    set_dispute(model_id, submission_index, dispute);
+}
+
+
+#[no_mangle]
+pub extern "C" fn resolve_dispute() {
+    let model_id: u32 = runtime::get_named_arg(ARG_MODEL_ID);
+    let submission_index: u32 = runtime::get_named_arg(ARG_SUBMISSION_INDEX);
+
+    let mut dispute = match get_dispute_of(model_id, submission_index) {
+        Some(dispute) => dispute,
+        None => runtime::revert(ApiError::NoSuchValue), // Dispute does not exist
+    };
+
+    if dispute.resolved {
+        runtime::revert(ApiError::InvalidArgument); // Dispute already resolved
+    }
 
+    let window: u64 = read_named_value(KEY_DISPUTE_WINDOW).unwrap_or(DEFAULT_DISPUTE_WINDOW);
+    if u64::from(runtime::get_blocktime()) < dispute.opened_at + window {
+        runtime::revert(ApiError::PermissionDenied); // Dispute window is still open
+    }
+
+    dispute.resolved = true;
+    dispute.slashed = dispute.slash_stake > dispute.keep_stake;
+
+    // The voters' stake no longer backs this dispute
+    for voter in dispute.slash_voters.iter().chain(dispute.keep_voters.iter()) {
+        set_open_vote_count(*voter, get_open_vote_count_of(*voter) - 1);
+    }
 
-    // In a real implementation, this would:
-    // 1.  Send the input data to the model (off-chain).
-    // 2.  Receive the prediction from the model.
-    // 3.  Return the prediction result to the caller.
+    if dispute.slashed {
+        let mut bond = get_submission_bond_of(model_id, submission_index).unwrap_or_revert_with(ApiError::NoSuchValue);
+
+        // The slashed stake goes to the flagging verifier; voters gain nothing from the outcome
+        set_locked_stake(bond.trainer, get_locked_stake_of(bond.trainer) - bond.amount);
+        let mut flagger_balance = get_balance(dispute.flagged_by);
+        flagger_balance += bond.amount;
+        set_balance(dispute.flagged_by, flagger_balance);
+
+        // Drop the poisoned data so it is excluded from future training
+        set_submission_data(model_id, bond.trainer, submission_index, None);
+
+        // A slashed submission earns no share of its round's rewards
+        let mut round = get_training_round_of(model_id, bond.round).unwrap_or_revert_with(ApiError::MissingKey);
+        if round.status != RoundStatus::Completed {
+            let contribution = get_round_contribution_of(model_id, round.round_id, bond.trainer) - 1;
+            set_round_contribution(model_id, round.round_id, bond.trainer, contribution);
+            if contribution == 0 {
+                round.participants.retain(|participant| participant != &bond.trainer);
+            }
+            round.submission_count -= 1;
+            set_training_round(model_id, round);
+        }
 
-    runtime::print(format!("Received prediction request for model {}: Input data: {:?}", model_id, input_data));
-    runtime::print("Prediction successful");
+        bond.status = BondStatus::Slashed;
+        set_submission_bond(model_id, submission_index, bond);
+    }
 
-    // Optionally, record the prediction request for auditing purposes
+    set_dispute(model_id, submission_index, dispute);
 }
 
 
 #[no_mangle]
-pub extern "C" fn set_prediction_price() {
+pub extern "C" fn set_submission_stake() {
+    require_contract_owner();
+    let stake: u64 = runtime::get_named_arg(ARG_STAKE);
+
+    write_named_value(KEY_SUBMISSION_STAKE, stake);
+}
+
+
+#[no_mangle]
+pub extern "C" fn set_dispute_window() {
+    require_contract_owner();
+    let window: u64 = runtime::get_named_arg(ARG_WINDOW);
+
+    if window == 0 {
+        runtime::revert(ApiError::InvalidArgument); // Dispute window must be non-zero
+    }
+    write_named_value(KEY_DISPUTE_WINDOW, window);
+}
+
+
+#[no_mangle]
+pub extern "C" fn train_model() {
     let model_id: u32 = runtime::get_named_arg(ARG_MODEL_ID);
-    let new_price: u64 = runtime::get_named_arg(ARG_NEW_PRICE);
     let caller = runtime::get_caller();
 
-    let mut model = match get_model(model_id) {
+    let model = match get_model(model_id) {
         Some(model) => model,
         None => runtime::revert(ApiError::NoSuchValue), // Model does not exist
     };
 
     if model.owner != caller {
-        runtime::revert(ApiError::PermissionDenied); // Only the model owner can update the price
+        runtime::revert(ApiError::PermissionDenied); // Only the model owner can initiate training
     }
 
-    model.price = new_price;
-    set_model(model_id, model);
+    let round = get_current_round(model_id, &model);
+    if round.status != RoundStatus::Open {
+        runtime::revert(ApiError::PermissionDenied); // The current round is not accepting submissions
+    }
+
+    if (round.participants.len() as u32) < round.min_participants {
+        runtime::revert(ApiError::InvalidArgument); // Not enough participants to train yet
+    }
+
+    // Lock submissions for this round.  Training runs off-chain on the submitted encrypted data and
+    // completes when an attested result arrives through `submit_training_result`.
+    close_round(model_id, model, round);
 }
 
 
 #[no_mangle]
-pub extern "C" fn withdraw_funds() {
-    let caller = runtime::get_caller();
+pub extern "C" fn open_training_round() {
+    let model_id: u32 = runtime::get_named_arg(ARG_MODEL_ID);
+    let deadline: u64 = runtime::get_named_arg(ARG_DEADLINE);
+    let training_deadline: u64 = runtime::get_named_arg(ARG_TRAINING_DEADLINE);
+    let min_participants: u32 = runtime::get_named_arg(ARG_MIN_PARTICIPANTS);
 
-    // Check if the caller is a model owner or trainer
-    let mut balance = get_balance(caller);
+    let mut model = match get_model(model_id) {
+        Some(model) => model,
+        None => runtime::revert(ApiError::NoSuchValue), // Model does not exist
+    };
 
-    // Find total rewards for the caller from their models and trainer account
-    let trainers_uref = get_trainers_uref();
-    let models_uref = get_models_uref();
+    if model.owner != runtime::get_caller() {
+        runtime::revert(ApiError::PermissionDenied); // Only the model owner can open training rounds
+    }
 
-    let mut trainer_rewards:u64 = 0;
-    match storage::dictionary_get::<Trainer>(trainers_uref, &caller.to_string()).unwrap_or_revert() {
-        Some(mut trainer) => {
-            trainer_rewards = trainer.accumulated_rewards;
-            trainer.accumulated_rewards = 0;
+    if model.round_count > 0 {
+        let current = get_current_round(model_id, &model);
+        if current.status == RoundStatus::Open || current.status == RoundStatus::Training {
+            runtime::revert(ApiError::InvalidArgument); // The current round has not finished
+        }
+    }
+
+    if deadline <= u64::from(runtime::get_blocktime()) {
+        runtime::revert(ApiError::InvalidArgument); // Deadline must be in the future
+    }
+
+    if training_deadline <= deadline {
+        runtime::revert(ApiError::InvalidArgument); // Training must end after the submission window
+    }
+
+    let round_id = model.round_count;
+    set_training_round(model_id, TrainingRound {
+        round_id,
+        deadline,
+        training_deadline,
+        min_participants,
+        participants: Vec::new(),
+        submission_count: 0,
+        reward_pool: 0,
+        status: RoundStatus::Open,
+        version: 0,
+    });
+
+    model.round_count += 1;
+    set_model(model_id, model);
+}
+
+
+#[no_mangle]
+pub extern "C" fn fund_training_round() {
+    let model_id: u32 = runtime::get_named_arg(ARG_MODEL_ID);
+    let amount: u64 = runtime::get_named_arg(ARG_AMOUNT);
+    let purse: URef = runtime::get_named_arg(ARG_PURSE);
+
+    let model = match get_model(model_id) {
+        Some(model) => model,
+        None => runtime::revert(ApiError::NoSuchValue), // Model does not exist
+    };
+
+    let mut round = get_current_round(model_id, &model);
+    if round.status != RoundStatus::Open && round.status != RoundStatus::Training {
+        runtime::revert(ApiError::InvalidArgument); // Round has already finished
+    }
+
+    if system::get_purse_balance(purse).unwrap_or_revert() < U512::from(amount) {
+        runtime::revert(ApiError::InsufficientFunds);
+    }
+    system::transfer_from_purse_to_purse(purse, get_contract_purse(), U512::from(amount), None).unwrap_or_revert();
+
+    // Record each funder's share so a failed round can refund it
+    let funder = runtime::get_caller();
+    let mut funders = get_round_funders(model_id, round.round_id);
+    match funders.iter_mut().find(|(account, _)| *account == funder) {
+        Some((_, funded)) => *funded += amount,
+        None => funders.push((funder, amount)),
+    }
+    set_round_funders(model_id, round.round_id, funders);
+
+    round.reward_pool += amount;
+    set_training_round(model_id, round);
+}
+
+
+#[no_mangle]
+pub extern "C" fn close_training_round() {
+    let model_id: u32 = runtime::get_named_arg(ARG_MODEL_ID);
+
+    let model = match get_model(model_id) {
+        Some(model) => model,
+        None => runtime::revert(ApiError::NoSuchValue), // Model does not exist
+    };
+
+    let round = get_current_round(model_id, &model);
+    if round.status != RoundStatus::Open {
+        runtime::revert(ApiError::InvalidArgument); // Round is already closed
+    }
+
+    // Anyone can close a round once its submission window has passed
+    if u64::from(runtime::get_blocktime()) < round.deadline {
+        runtime::revert(ApiError::PermissionDenied); // Submission window is still open
+    }
+
+    close_round(model_id, model, round);
+}
+
+
+#[no_mangle]
+pub extern "C" fn fail_training_round() {
+    let model_id: u32 = runtime::get_named_arg(ARG_MODEL_ID);
+
+    let mut model = match get_model(model_id) {
+        Some(model) => model,
+        None => runtime::revert(ApiError::NoSuchValue), // Model does not exist
+    };
+
+    let mut round = get_current_round(model_id, &model);
+    if round.status != RoundStatus::Training {
+        runtime::revert(ApiError::InvalidArgument); // Round is not waiting for a training result
+    }
+
+    // Anyone can fail a round whose attested result did not arrive in time
+    if u64::from(runtime::get_blocktime()) < round.training_deadline {
+        runtime::revert(ApiError::PermissionDenied); // Training deadline has not passed
+    }
+
+    fail_round(model_id, &mut round);
+    set_training_round(model_id, round);
+
+    model.training_started = false;
+    set_model(model_id, model);
+}
+
+
+#[no_mangle]
+pub extern "C" fn submit_training_result() {
+    let model_id: u32 = runtime::get_named_arg(ARG_MODEL_ID);
+    let new_params_hash: String = runtime::get_named_arg(ARG_NEW_PARAMS_HASH);
+    let metrics: String = runtime::get_named_arg(ARG_METRICS);
+    let accuracy: u32 = runtime::get_named_arg(ARG_ACCURACY);
+    let attestation_sig: Vec<(PublicKey, Bytes)> = runtime::get_named_arg(ARG_ATTESTATION_SIG);
+
+    let mut model = match get_model(model_id) {
+        Some(model) => model,
+        None => runtime::revert(ApiError::NoSuchValue), // Model does not exist
+    };
+
+    if !model.training_started {
+        runtime::revert(ApiError::PermissionDenied); // No training epoch in progress
+    }
+
+    if u64::from(accuracy) > BASIS_POINTS {
+        runtime::revert(ApiError::InvalidArgument); // Accuracy cannot exceed 100%
+    }
+
+    let mut round = get_current_round(model_id, &model);
+    if u64::from(runtime::get_blocktime()) >= round.training_deadline {
+        runtime::revert(ApiError::PermissionDenied); // Training deadline has passed; the round can only fail
+    }
+
+    // Count distinct authorized verifiers with a valid signature over the result.
+    let message = attestation_message(model_id, model.training_epoch, &new_params_hash, &metrics, accuracy);
+    let verifiers = get_verifiers();
+    let mut signers: Vec<PublicKey> = Vec::new();
+    for (public_key, signature_bytes) in attestation_sig {
+        if !verifiers.contains(&public_key) || signers.contains(&public_key) {
+            continue;
+        }
+        let signature = match Signature::from_bytes(signature_bytes.as_slice()) {
+            Ok((signature, _)) => signature,
+            Err(_) => continue,
+        };
+        if crypto::verify(message, &signature, &public_key).is_ok() {
+            signers.push(public_key);
+        }
+    }
+
+    if (signers.len() as u32) < get_verifier_threshold() {
+        runtime::revert(ApiError::PermissionDenied); // Not enough verifier attestations
+    }
+
+    model.params_hash = new_params_hash;
+    model.metadata.accuracy_metrics = metrics;
+    model.accuracy = accuracy;
+    model.reputation_score += 10; // Increase reputation after attested training
+    model.training_epoch += 1;
+    model.training_started = false;
+
+    // Each completed round publishes exactly one model version
+    model.version_count += 1;
+    set_model_version(model_id, ModelVersion {
+        version: model.version_count,
+        metadata: model.metadata.clone(),
+        params_hash: model.params_hash.clone(),
+        published_at: u64::from(runtime::get_blocktime()),
+        deprecated: false,
+    });
+
+    // Split the round's reward pool by each participant's snapshotted contribution; the integer
+    // division remainder goes to the model owner.
+    let mut distributed: u64 = 0;
+    if round.submission_count > 0 {
+        for participant in round.participants.iter() {
+            let contribution = get_round_contribution_of(model_id, round.round_id, *participant);
+            let reward = round.reward_pool * u64::from(contribution) / u64::from(round.submission_count);
+            if let Some(mut trainer) = get_trainer(*participant) {
+                trainer.accumulated_rewards += reward;
+                set_trainer(*participant, trainer);
+                distributed += reward;
+            }
+        }
+    }
+    model.accumulated_rewards += round.reward_pool - distributed;
+
+    round.status = RoundStatus::Completed;
+    round.version = model.version_count;
+    set_training_round(model_id, round);
+    set_model(model_id, model);
+}
+
+
+#[no_mangle]
+pub extern "C" fn add_verifier() {
+    require_contract_owner();
+    let public_key: PublicKey = runtime::get_named_arg(ARG_PUBLIC_KEY);
+
+    let mut verifiers = get_verifiers();
+    if verifiers.contains(&public_key) {
+        runtime::revert(ApiError::InvalidArgument); // Verifier already registered
+    }
+    verifiers.push(public_key);
+    write_named_value(KEY_VERIFIERS, verifiers);
+}
+
+
+#[no_mangle]
+pub extern "C" fn remove_verifier() {
+    require_contract_owner();
+    let public_key: PublicKey = runtime::get_named_arg(ARG_PUBLIC_KEY);
+
+    let mut verifiers = get_verifiers();
+    if !verifiers.contains(&public_key) {
+        runtime::revert(ApiError::NoSuchValue); // Verifier not registered
+    }
+    verifiers.retain(|verifier| verifier != &public_key);
+    write_named_value(KEY_VERIFIERS, verifiers);
+}
+
+
+#[no_mangle]
+pub extern "C" fn set_verifier_threshold() {
+    require_contract_owner();
+    let threshold: u32 = runtime::get_named_arg(ARG_THRESHOLD);
+
+    if threshold == 0 {
+        runtime::revert(ApiError::InvalidArgument); // At least one attestation is always required
+    }
+    write_named_value(KEY_VERIFIER_THRESHOLD, threshold);
+}
+
+
+#[no_mangle]
+pub extern "C" fn register_model() {
+    let model_metadata: ModelMetadata = get_serialized_arg(ARG_MODEL_METADATA);
+    let model_id: u32 = runtime::get_named_arg(ARG_MODEL_ID);
+    let caller = runtime::get_caller();
+
+    if get_model(model_id).is_some() {
+        runtime::revert(ApiError::InvalidPurse); // Model ID already exists
+    }
+
+    let model = Model {
+        owner: caller,
+        price: 100, // Initial prediction price
+        accumulated_rewards: 0,
+        reputation_score: 0,
+        metadata: model_metadata.clone(),
+        training_started: false,
+        params_hash: String::new(),
+        training_epoch: 0,
+        version_count: 1,
+        round_count: 0,
+        access_mode: AccessMode::Public,
+        subscription_price: 0,
+        accuracy: 0,
+        demand_window_start: u64::from(runtime::get_blocktime()),
+        window_requests: 0,
+        previous_window_requests: 0,
+    };
+
+    set_model(model_id, model);
+    index_model(model_id, caller);
+
+    // The registered metadata becomes version 1
+    set_model_version(model_id, ModelVersion {
+        version: 1,
+        metadata: model_metadata,
+        params_hash: String::new(),
+        published_at: u64::from(runtime::get_blocktime()),
+        deprecated: false,
+    });
+}
+
+
+#[no_mangle]
+pub extern "C" fn publish_model_version() {
+    let model_id: u32 = runtime::get_named_arg(ARG_MODEL_ID);
+    let model_metadata: ModelMetadata = get_serialized_arg(ARG_MODEL_METADATA);
+    let params_hash: String = runtime::get_named_arg(ARG_PARAMS_HASH);
+
+    let mut model = match get_model(model_id) {
+        Some(model) => model,
+        None => runtime::revert(ApiError::NoSuchValue), // Model does not exist
+    };
+
+    if model.owner != runtime::get_caller() {
+        runtime::revert(ApiError::PermissionDenied); // Only the model owner can publish versions
+    }
+
+    let version = model.version_count + 1;
+    set_model_version(model_id, ModelVersion {
+        version,
+        metadata: model_metadata.clone(),
+        params_hash: params_hash.clone(),
+        published_at: u64::from(runtime::get_blocktime()),
+        deprecated: false,
+    });
+
+    // The latest version is what unpinned prediction requests run against
+    model.version_count = version;
+    model.metadata = model_metadata;
+    model.params_hash = params_hash;
+    set_model(model_id, model);
+}
+
+
+#[no_mangle]
+pub extern "C" fn deprecate_model_version() {
+    let model_id: u32 = runtime::get_named_arg(ARG_MODEL_ID);
+    let version: u32 = runtime::get_named_arg(ARG_VERSION);
+
+    let model = match get_model(model_id) {
+        Some(model) => model,
+        None => runtime::revert(ApiError::NoSuchValue), // Model does not exist
+    };
+
+    if model.owner != runtime::get_caller() {
+        runtime::revert(ApiError::PermissionDenied); // Only the model owner can deprecate versions
+    }
+
+    if version == model.version_count {
+        runtime::revert(ApiError::InvalidArgument); // The latest version cannot be deprecated
+    }
+
+    let mut model_version = match get_model_version_of(model_id, version) {
+        Some(model_version) => model_version,
+        None => runtime::revert(ApiError::NoSuchValue), // Version does not exist
+    };
+
+    model_version.deprecated = true;
+    set_model_version(model_id, model_version);
+}
+
+
+#[no_mangle]
+pub extern "C" fn set_access_mode() {
+    let model_id: u32 = runtime::get_named_arg(ARG_MODEL_ID);
+    let access_mode: AccessMode = runtime::get_named_arg(ARG_ACCESS_MODE);
+    let subscription_price: u64 = runtime::get_named_arg(ARG_SUBSCRIPTION_PRICE);
+
+    let mut model = match get_model(model_id) {
+        Some(model) => model,
+        None => runtime::revert(ApiError::NoSuchValue), // Model does not exist
+    };
+
+    if model.owner != runtime::get_caller() {
+        runtime::revert(ApiError::PermissionDenied); // Only the model owner can change access rules
+    }
+
+    if access_mode == AccessMode::Subscription && subscription_price == 0 {
+        runtime::revert(ApiError::InvalidArgument); // Subscriptions must have a price
+    }
+
+    model.access_mode = access_mode;
+    model.subscription_price = subscription_price;
+    set_model(model_id, model);
+}
+
+
+#[no_mangle]
+pub extern "C" fn grant_access() {
+    let model_id: u32 = runtime::get_named_arg(ARG_MODEL_ID);
+    let account: AccountHash = runtime::get_named_arg(ARG_ACCOUNT);
+
+    let model = match get_model(model_id) {
+        Some(model) => model,
+        None => runtime::revert(ApiError::NoSuchValue), // Model does not exist
+    };
+
+    if model.owner != runtime::get_caller() {
+        runtime::revert(ApiError::PermissionDenied); // Only the model owner can grant access
+    }
+
+    // Granted access does not expire
+    set_access_expiry(model_id, account, u64::MAX);
+}
+
+
+#[no_mangle]
+pub extern "C" fn revoke_access() {
+    let model_id: u32 = runtime::get_named_arg(ARG_MODEL_ID);
+    let account: AccountHash = runtime::get_named_arg(ARG_ACCOUNT);
+
+    let model = match get_model(model_id) {
+        Some(model) => model,
+        None => runtime::revert(ApiError::NoSuchValue), // Model does not exist
+    };
+
+    if model.owner != runtime::get_caller() {
+        runtime::revert(ApiError::PermissionDenied); // Only the model owner can revoke access
+    }
+
+    set_access_expiry(model_id, account, 0);
+}
+
+
+#[no_mangle]
+pub extern "C" fn purchase_subscription() {
+    let model_id: u32 = runtime::get_named_arg(ARG_MODEL_ID);
+    let duration: u64 = runtime::get_named_arg(ARG_DURATION);
+    let purse: URef = runtime::get_named_arg(ARG_PURSE);
+    let caller = runtime::get_caller();
+
+    let model = match get_model(model_id) {
+        Some(model) => model,
+        None => runtime::revert(ApiError::NoSuchValue), // Model does not exist
+    };
+
+    if model.access_mode != AccessMode::Subscription {
+        runtime::revert(ApiError::InvalidArgument); // Model is not sold by subscription
+    }
+
+    if duration == 0 {
+        runtime::revert(ApiError::InvalidArgument); // Subscription must last at least one day
+    }
+
+    let cost = model.subscription_price.checked_mul(duration).unwrap_or_revert_with(ApiError::InvalidArgument);
+    let cost_motes = U512::from(cost);
+    if system::get_purse_balance(purse).unwrap_or_revert() < cost_motes {
+        runtime::revert(ApiError::InsufficientFunds);
+    }
+
+    system::transfer_from_purse_to_purse(purse, get_contract_purse(), cost_motes, None).unwrap_or_revert();
+
+    let mut model_owner_balance = get_balance(model.owner);
+    model_owner_balance += cost;
+    set_balance(model.owner, model_owner_balance);
+
+    // Extend an active subscription, otherwise start a new one from now
+    let now = u64::from(runtime::get_blocktime());
+    let current_expiry = get_access_expiry_of(model_id, caller);
+    let start = if current_expiry > now { current_expiry } else { now };
+    let expiry = start.saturating_add(duration.saturating_mul(MILLIS_PER_DAY));
+    set_access_expiry(model_id, caller, expiry);
+}
+
+
+#[no_mangle]
+pub extern "C" fn request_prediction() {
+    let model_id: u32 = runtime::get_named_arg(ARG_MODEL_ID);
+    let input_data: Bytes = runtime::get_named_arg(ARG_INPUT_DATA);
+    let purse: URef = runtime::get_named_arg(ARG_PURSE);
+    let pinned_version: Option<u32> = runtime::get_named_arg(ARG_VERSION);
+    let max_price: u64 = runtime::get_named_arg(ARG_MAX_PRICE);
+    let caller = runtime::get_caller();
+
+    let mut model = match get_model(model_id) {
+        Some(model) => model,
+        None => runtime::revert(ApiError::NoSuchValue), // Model does not exist
+    };
+
+    if !has_model_access(model_id, &model, caller) {
+        runtime::revert(ApiError::PermissionDenied); // Caller has no access or their subscription has expired
+    }
+
+    // Unpinned requests run against the latest version
+    let model_version = match pinned_version {
+        Some(version) => {
+            match get_model_version_of(model_id, version) {
+                Some(model_version) if !model_version.deprecated => version,
+                Some(_) => runtime::revert(ApiError::InvalidArgument), // Version is deprecated
+                None => runtime::revert(ApiError::NoSuchValue), // Version does not exist
+            }
+        }
+        None => model.version_count,
+    };
+
+    // Price the request from current demand and verified accuracy, then count it towards demand
+    let now = u64::from(runtime::get_blocktime());
+    let curve = get_pricing_curve();
+    roll_demand_window(&mut model, &curve, now);
+    let amount = compute_price(&model, &curve);
+    if amount > max_price {
+        runtime::revert(ApiError::InvalidArgument); // Price rose above the caller's limit since it was quoted
+    }
+    model.window_requests = model.window_requests.saturating_add(1);
+    set_model(model_id, model);
+
+    // Check that the supplied purse covers the price
+    let price = U512::from(amount);
+    if system::get_purse_balance(purse).unwrap_or_revert() < price {
+        runtime::revert(ApiError::InsufficientFunds);
+    }
+
+    // Escrow the payment in the contract purse until the request is fulfilled or refunded
+    system::transfer_from_purse_to_purse(purse, get_contract_purse(), price, None).unwrap_or_revert();
+
+    // Queue the request.  Prediction oracles pick it up off-chain, run the model on the input data,
+    // and deliver the result through `fulfill_prediction`.
+    let request_id: u64 = read_named_value(KEY_PREDICTION_COUNT).unwrap_or(0);
+    write_named_value(KEY_PREDICTION_COUNT, request_id + 1);
+
+    set_prediction_request(PredictionRequest {
+        id: request_id,
+        requester: caller,
+        model_id,
+        model_version,
+        input_hash: base16::encode_lower(&runtime::blake2b(input_data.as_slice())),
+        status: PredictionStatus::Pending,
+        amount,
+        requested_at: now,
+        output_hash: None,
+    });
+
+    let mut requester_ids = get_requester_prediction_ids(caller);
+    requester_ids.push(request_id);
+    let index_uref = get_dictionary_uref(KEY_REQUESTER_PREDICTIONS);
+    storage::dictionary_put(index_uref, &account_key(caller), requester_ids);
+
+    runtime::ret(CLValue::from_t(request_id).unwrap_or_revert());
+}
+
+
+#[no_mangle]
+pub extern "C" fn fulfill_prediction() {
+    let request_id: u64 = runtime::get_named_arg(ARG_REQUEST_ID);
+    let output_hash: String = runtime::get_named_arg(ARG_OUTPUT_HASH);
+
+    if !get_prediction_oracles().contains(&runtime::get_caller()) {
+        runtime::revert(ApiError::PermissionDenied); // Only prediction oracles can deliver results
+    }
+
+    let mut request = match get_prediction_request_of(request_id) {
+        Some(request) => request,
+        None => runtime::revert(ApiError::NoSuchValue), // Request does not exist
+    };
+
+    if request.status != PredictionStatus::Pending {
+        runtime::revert(ApiError::InvalidArgument); // Request already fulfilled or refunded
+    }
+
+    let model = get_model(request.model_id).unwrap_or_revert_with(ApiError::NoSuchValue);
+
+    // Release the escrowed payment to the model owner
+    let mut model_owner_balance = get_balance(model.owner);
+    model_owner_balance += request.amount;
+    set_balance(model.owner, model_owner_balance);
+
+    request.status = PredictionStatus::Fulfilled;
+    request.output_hash = Some(output_hash);
+    set_prediction_request(request);
+}
+
+
+#[no_mangle]
+pub extern "C" fn refund_prediction() {
+    let request_id: u64 = runtime::get_named_arg(ARG_REQUEST_ID);
+    let caller = runtime::get_caller();
+
+    let mut request = match get_prediction_request_of(request_id) {
+        Some(request) => request,
+        None => runtime::revert(ApiError::NoSuchValue), // Request does not exist
+    };
+
+    if request.requester != caller {
+        runtime::revert(ApiError::PermissionDenied); // Only the requester can claim a refund
+    }
+
+    if request.status != PredictionStatus::Pending {
+        runtime::revert(ApiError::InvalidArgument); // Request already fulfilled or refunded
+    }
+
+    let timeout: u64 = read_named_value(KEY_PREDICTION_TIMEOUT).unwrap_or(DEFAULT_PREDICTION_TIMEOUT);
+    if u64::from(runtime::get_blocktime()) < request.requested_at + timeout {
+        runtime::revert(ApiError::PermissionDenied); // Request has not timed out yet
+    }
+
+    request.status = PredictionStatus::Refunded;
+    let amount = U512::from(request.amount);
+    set_prediction_request(request);
+
+    system::transfer_from_purse_to_account(get_contract_purse(), caller, amount, None).unwrap_or_revert();
+}
+
+
+#[no_mangle]
+pub extern "C" fn add_prediction_oracle() {
+    require_contract_owner();
+    let oracle: AccountHash = runtime::get_named_arg(ARG_ORACLE);
+
+    let mut oracles = get_prediction_oracles();
+    if oracles.contains(&oracle) {
+        runtime::revert(ApiError::InvalidArgument); // Oracle already registered
+    }
+    oracles.push(oracle);
+    write_named_value(KEY_PREDICTION_ORACLES, oracles);
+}
+
+
+#[no_mangle]
+pub extern "C" fn remove_prediction_oracle() {
+    require_contract_owner();
+    let oracle: AccountHash = runtime::get_named_arg(ARG_ORACLE);
+
+    let mut oracles = get_prediction_oracles();
+    if !oracles.contains(&oracle) {
+        runtime::revert(ApiError::NoSuchValue); // Oracle not registered
+    }
+    oracles.retain(|registered| registered != &oracle);
+    write_named_value(KEY_PREDICTION_ORACLES, oracles);
+}
+
+
+#[no_mangle]
+pub extern "C" fn set_prediction_timeout() {
+    require_contract_owner();
+    let timeout: u64 = runtime::get_named_arg(ARG_TIMEOUT);
+
+    write_named_value(KEY_PREDICTION_TIMEOUT, timeout);
+}
+
+
+#[no_mangle]
+pub extern "C" fn set_prediction_price() {
+    let model_id: u32 = runtime::get_named_arg(ARG_MODEL_ID);
+    let new_price: u64 = runtime::get_named_arg(ARG_NEW_PRICE);
+    let caller = runtime::get_caller();
+
+    let mut model = match get_model(model_id) {
+        Some(model) => model,
+        None => runtime::revert(ApiError::NoSuchValue), // Model does not exist
+    };
+
+    if model.owner != caller {
+        runtime::revert(ApiError::PermissionDenied); // Only the model owner can update the price
+    }
+
+    model.price = new_price;
+    set_model(model_id, model);
+}
+
+
+#[no_mangle]
+pub extern "C" fn set_pricing_curve() {
+    let curve: PricingCurve = get_serialized_arg(ARG_CURVE);
+    let caller = runtime::get_caller();
+
+    let verifiers = get_verifiers();
+    if !verifiers.iter().any(|verifier| verifier.to_account_hash() == caller) {
+        runtime::revert(ApiError::PermissionDenied); // Only verifiers govern the pricing curve
+    }
+
+    if curve.demand_window == 0 || curve.min_price > curve.max_price {
+        runtime::revert(ApiError::InvalidArgument); // Invalid demand window or price bounds
+    }
+
+    // Backing a different curve than the pending one restarts the approval tally
+    let mut approvals: Vec<AccountHash> = Vec::new();
+    if read_named_value::<PricingCurve>(KEY_PENDING_PRICING_CURVE).as_ref() == Some(&curve) {
+        approvals = read_named_value(KEY_PRICING_CURVE_APPROVALS).unwrap_or_default();
+    }
+    if approvals.contains(&caller) {
+        runtime::revert(ApiError::InvalidArgument); // Caller has already approved this curve
+    }
+    approvals.push(caller);
+
+    // Only approvals from current verifiers count towards the threshold
+    let approval_count = approvals.iter()
+        .filter(|approver| verifiers.iter().any(|verifier| verifier.to_account_hash() == **approver))
+        .count() as u32;
+    if approval_count >= get_verifier_threshold() {
+        write_named_value(KEY_PRICING_CURVE, curve);
+        write_named_value(KEY_PENDING_PRICING_CURVE, None::<PricingCurve>);
+        write_named_value(KEY_PRICING_CURVE_APPROVALS, Vec::<AccountHash>::new());
+    } else {
+        write_named_value(KEY_PENDING_PRICING_CURVE, Some(curve));
+        write_named_value(KEY_PRICING_CURVE_APPROVALS, approvals);
+    }
+}
+
+
+#[no_mangle]
+pub extern "C" fn withdraw_funds() {
+    let caller = runtime::get_caller();
+
+    // Check if the caller is a model owner or trainer
+    let balance = get_balance(caller);
+
+    // Find total rewards for the caller from their models and trainer account
+    let trainers_uref = get_trainers_uref();
+
+    let mut trainer_rewards:u64 = 0;
+    match storage::dictionary_get::<Trainer>(trainers_uref, &caller.to_string()).unwrap_or_revert() {
+        Some(mut trainer) => {
+            trainer_rewards = trainer.accumulated_rewards;
+            trainer.accumulated_rewards = 0;
             set_trainer(caller, trainer);
         },
         None => {
@@ -516,77 +2452,534 @@ pub extern "C" fn withdraw_funds() {
     };
 
     let mut model_rewards:u64 = 0;
-    let mut model_ids:Vec<u32> = Vec::new();
-    let mut counter:u32 = 0;
-    loop {
-        match storage::dictionary_get::<Model>(models_uref, &counter.to_string()).unwrap_or_revert() {
-            Some(mut model) => {
-                if model.owner == caller {
-                   model_rewards += model.accumulated_rewards;
-                   model.accumulated_rewards = 0;
-                   set_model(counter, model);
-                }
-            },
-            None => {
-                break;
-            }
-        };
-        counter += 1;
+    for model_id in get_owner_model_ids(caller) {
+        if let Some(mut model) = get_model(model_id) {
+            model_rewards += model.accumulated_rewards;
+            model.accumulated_rewards = 0;
+            set_model(model_id, model);
+        }
+    }
+
+
+    if balance == 0 && model_rewards == 0 && trainer_rewards == 0{
+        runtime::revert(ApiError::InsufficientFunds);
+    }
+
+    // Transfer the funds from the contract purse to the caller's account
+    set_balance(caller, 0);
+    let amount = U512::from(balance) + U512::from(model_rewards) + U512::from(trainer_rewards);
+    system::transfer_from_purse_to_account(get_contract_purse(), caller, amount, None).unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn get_model_details() {
+    let model_id: u32 = runtime::get_named_arg(ARG_MODEL_ID);
+
+    let model = match get_model(model_id) {
+        Some(model) => model,
+        None => runtime::revert(ApiError::NoSuchValue), // Model does not exist
+    };
+
+    ret_serialized(model);
+}
+
+#[no_mangle]
+pub extern "C" fn get_trainer_rewards() {
+    let trainer_address: AccountHash = runtime::get_named_arg(ARG_TRAINER_ADDRESS);
+
+    let trainer = match get_trainer(trainer_address) {
+        Some(trainer) => trainer,
+        None => runtime::revert(ApiError::NoSuchValue), // Trainer does not exist
+    };
+
+    runtime::ret(CLValue::from_t(trainer.accumulated_rewards).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn list_models() {
+    let offset: u32 = runtime::get_named_arg(ARG_OFFSET);
+    let limit: u32 = runtime::get_named_arg(ARG_LIMIT);
+
+    let model_count: u32 = read_named_value(KEY_MODEL_COUNT).unwrap_or(0);
+    let (start, end) = page_bounds(offset, limit, model_count);
+
+    let all_ids_uref = get_dictionary_uref(KEY_ALL_MODEL_IDS);
+    let mut models: Vec<(u32, Model)> = Vec::new();
+    for index in start..end {
+        let model_id = storage::dictionary_get::<u32>(all_ids_uref, &index.to_string())
+            .unwrap_or_revert()
+            .unwrap_or_revert_with(ApiError::MissingKey);
+        if let Some(model) = get_model(model_id) {
+            models.push((model_id, model));
+        }
     }
 
+    ret_serialized(models);
+}
+
+#[no_mangle]
+pub extern "C" fn list_models_by_owner() {
+    let owner: AccountHash = runtime::get_named_arg(ARG_OWNER);
+
+    runtime::ret(CLValue::from_t(get_owner_model_ids(owner)).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn list_trainers() {
+    let offset: u32 = runtime::get_named_arg(ARG_OFFSET);
+    let limit: u32 = runtime::get_named_arg(ARG_LIMIT);
+
+    let trainer_count: u32 = read_named_value(KEY_TRAINER_COUNT).unwrap_or(0);
+    let (start, end) = page_bounds(offset, limit, trainer_count);
+
+    let all_trainers_uref = get_dictionary_uref(KEY_ALL_TRAINERS);
+    let mut trainers: Vec<(AccountHash, Trainer)> = Vec::new();
+    for index in start..end {
+        let trainer_address = storage::dictionary_get::<AccountHash>(all_trainers_uref, &index.to_string())
+            .unwrap_or_revert()
+            .unwrap_or_revert_with(ApiError::MissingKey);
+        if let Some(trainer) = get_trainer(trainer_address) {
+            trainers.push((trainer_address, trainer));
+        }
+    }
+
+    ret_serialized(trainers);
+}
+
+#[no_mangle]
+pub extern "C" fn get_submission_count() {
+    let model_id: u32 = runtime::get_named_arg(ARG_MODEL_ID);
+
+    runtime::ret(CLValue::from_t(get_submission_count_of(model_id)).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn get_submission() {
+    let model_id: u32 = runtime::get_named_arg(ARG_MODEL_ID);
+    let submission_index: u32 = runtime::get_named_arg(ARG_SUBMISSION_INDEX);
+
+    let trainer = match get_submission_trainer(model_id, submission_index) {
+        Some(trainer) => trainer,
+        None => runtime::revert(ApiError::NoSuchValue), // Submission does not exist
+    };
+
+    let data = get_submission_data(model_id, trainer, submission_index);
+    ret_serialized((trainer, data));
+}
+
+#[no_mangle]
+pub extern "C" fn get_training_round() {
+    let model_id: u32 = runtime::get_named_arg(ARG_MODEL_ID);
+    let round_id: u32 = runtime::get_named_arg(ARG_ROUND_ID);
+
+    let round = match get_training_round_of(model_id, round_id) {
+        Some(round) => round,
+        None => runtime::revert(ApiError::NoSuchValue), // Round does not exist
+    };
+
+    ret_serialized(round);
+}
+
+#[no_mangle]
+pub extern "C" fn get_round_contribution() {
+    let model_id: u32 = runtime::get_named_arg(ARG_MODEL_ID);
+    let round_id: u32 = runtime::get_named_arg(ARG_ROUND_ID);
+    let trainer: AccountHash = runtime::get_named_arg(ARG_TRAINER);
+
+    runtime::ret(CLValue::from_t(get_round_contribution_of(model_id, round_id, trainer)).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn get_submission_bond() {
+    let model_id: u32 = runtime::get_named_arg(ARG_MODEL_ID);
+    let submission_index: u32 = runtime::get_named_arg(ARG_SUBMISSION_INDEX);
+
+    let bond = match get_submission_bond_of(model_id, submission_index) {
+        Some(bond) => bond,
+        None => runtime::revert(ApiError::NoSuchValue), // Submission has no bond
+    };
+
+    ret_serialized(bond);
+}
+
+#[no_mangle]
+pub extern "C" fn get_dispute() {
+    let model_id: u32 = runtime::get_named_arg(ARG_MODEL_ID);
+    let submission_index: u32 = runtime::get_named_arg(ARG_SUBMISSION_INDEX);
+
+    let dispute = match get_dispute_of(model_id, submission_index) {
+        Some(dispute) => dispute,
+        None => runtime::revert(ApiError::NoSuchValue), // Dispute does not exist
+    };
+
+    ret_serialized(dispute);
+}
+
+#[no_mangle]
+pub extern "C" fn get_model_version() {
+    let model_id: u32 = runtime::get_named_arg(ARG_MODEL_ID);
+    let version: u32 = runtime::get_named_arg(ARG_VERSION);
+
+    let model_version = match get_model_version_of(model_id, version) {
+        Some(model_version) => model_version,
+        None => runtime::revert(ApiError::NoSuchValue), // Version does not exist
+    };
+
+    ret_serialized(model_version);
+}
+
+#[no_mangle]
+pub extern "C" fn quote_price() {
+    let model_id: u32 = runtime::get_named_arg(ARG_MODEL_ID);
+
+    let mut model = match get_model(model_id) {
+        Some(model) => model,
+        None => runtime::revert(ApiError::NoSuchValue), // Model does not exist
+    };
+
+    // Quote against the demand window a request made now would see, without storing it
+    let curve = get_pricing_curve();
+    roll_demand_window(&mut model, &curve, u64::from(runtime::get_blocktime()));
+
+    runtime::ret(CLValue::from_t(compute_price(&model, &curve)).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn get_access_expiry() {
+    let model_id: u32 = runtime::get_named_arg(ARG_MODEL_ID);
+    let account: AccountHash = runtime::get_named_arg(ARG_ACCOUNT);
+
+    runtime::ret(CLValue::from_t(get_access_expiry_of(model_id, account)).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn get_prediction_request() {
+    let request_id: u64 = runtime::get_named_arg(ARG_REQUEST_ID);
+
+    let request = match get_prediction_request_of(request_id) {
+        Some(request) => request,
+        None => runtime::revert(ApiError::NoSuchValue), // Request does not exist
+    };
+
+    ret_serialized(request);
+}
+
+#[no_mangle]
+pub extern "C" fn get_requester_predictions() {
+    let requester: AccountHash = runtime::get_named_arg(ARG_REQUESTER);
+
+    runtime::ret(CLValue::from_t(get_requester_prediction_ids(requester)).unwrap_or_revert());
+}
+
+// ----------------------------------------------------------------------------
+//  Helper functions
+// ----------------------------------------------------------------------------
+
+fn get_entry_points() -> EntryPoints {
+    let mut entry_points = EntryPoints::new();
+
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_INIT,
+        Vec::new(),
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_REGISTER_TRAINER,
+        vec![
+            Parameter::new(ARG_PUBLIC_KEY, PublicKey::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_SUBMIT_ENCRYPTED_DATA,
+        vec![
+            Parameter::new(ARG_MODEL_ID, u32::cl_type()),
+            Parameter::new(ARG_DATA, Bytes::cl_type()),
+            Parameter::new(ARG_PURSE, URef::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_REMOVE_MY_SUBMISSION,
+        vec![
+            Parameter::new(ARG_MODEL_ID, u32::cl_type()),
+            Parameter::new(ARG_SUBMISSION_INDEX, u32::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_WITHDRAW_SUBMISSION_STAKE,
+        vec![
+            Parameter::new(ARG_MODEL_ID, u32::cl_type()),
+            Parameter::new(ARG_SUBMISSION_INDEX, u32::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_FLAG_SUBMISSION,
+        vec![
+            Parameter::new(ARG_MODEL_ID, u32::cl_type()),
+            Parameter::new(ARG_SUBMISSION_INDEX, u32::cl_type()),
+            Parameter::new(ARG_REASON_HASH, String::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_VOTE_ON_DISPUTE,
+        vec![
+            Parameter::new(ARG_MODEL_ID, u32::cl_type()),
+            Parameter::new(ARG_SUBMISSION_INDEX, u32::cl_type()),
+            Parameter::new(ARG_SLASH, bool::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_RESOLVE_DISPUTE,
+        vec![
+            Parameter::new(ARG_MODEL_ID, u32::cl_type()),
+            Parameter::new(ARG_SUBMISSION_INDEX, u32::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_SET_SUBMISSION_STAKE,
+        vec![
+            Parameter::new(ARG_STAKE, u64::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_SET_DISPUTE_WINDOW,
+        vec![
+            Parameter::new(ARG_WINDOW, u64::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_GET_SUBMISSION_BOND,
+        vec![
+            Parameter::new(ARG_MODEL_ID, u32::cl_type()),
+            Parameter::new(ARG_SUBMISSION_INDEX, u32::cl_type()),
+        ],
+        Bytes::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_GET_DISPUTE,
+        vec![
+            Parameter::new(ARG_MODEL_ID, u32::cl_type()),
+            Parameter::new(ARG_SUBMISSION_INDEX, u32::cl_type()),
+        ],
+        Bytes::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_OPEN_TRAINING_ROUND,
+        vec![
+            Parameter::new(ARG_MODEL_ID, u32::cl_type()),
+            Parameter::new(ARG_DEADLINE, u64::cl_type()),
+            Parameter::new(ARG_TRAINING_DEADLINE, u64::cl_type()),
+            Parameter::new(ARG_MIN_PARTICIPANTS, u32::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_FUND_TRAINING_ROUND,
+        vec![
+            Parameter::new(ARG_MODEL_ID, u32::cl_type()),
+            Parameter::new(ARG_AMOUNT, u64::cl_type()),
+            Parameter::new(ARG_PURSE, URef::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_CLOSE_TRAINING_ROUND,
+        vec![
+            Parameter::new(ARG_MODEL_ID, u32::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
 
-    if balance == 0 && model_rewards == 0 && trainer_rewards == 0{
-        runtime::revert(ApiError::InsufficientFunds);
-    }
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_FAIL_TRAINING_ROUND,
+        vec![
+            Parameter::new(ARG_MODEL_ID, u32::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
 
-    // Transfer the funds to the caller's account
-    runtime::print(format!("Withdraw funds: {}", balance));
-    set_balance(caller, 0);
-}
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_GET_TRAINING_ROUND,
+        vec![
+            Parameter::new(ARG_MODEL_ID, u32::cl_type()),
+            Parameter::new(ARG_ROUND_ID, u32::cl_type()),
+        ],
+        Bytes::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
 
-#[no_mangle]
-pub extern "C" fn get_model_details() {
-    let model_id: u32 = runtime::get_named_arg(ARG_MODEL_ID);
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_GET_ROUND_CONTRIBUTION,
+        vec![
+            Parameter::new(ARG_MODEL_ID, u32::cl_type()),
+            Parameter::new(ARG_ROUND_ID, u32::cl_type()),
+            Parameter::new(ARG_TRAINER, AccountHash::cl_type()),
+        ],
+        CLType::U32,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
 
-    let model = match get_model(model_id) {
-        Some(model) => model,
-        None => runtime::revert(ApiError::NoSuchValue), // Model does not exist
-    };
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_TRAIN_MODEL,
+        vec![
+            Parameter::new(ARG_MODEL_ID, u32::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
 
-    runtime::print(format!("Model Details: {:?}", model));
-}
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_SUBMIT_TRAINING_RESULT,
+        vec![
+            Parameter::new(ARG_MODEL_ID, u32::cl_type()),
+            Parameter::new(ARG_NEW_PARAMS_HASH, String::cl_type()),
+            Parameter::new(ARG_METRICS, String::cl_type()),
+            Parameter::new(ARG_ACCURACY, u32::cl_type()),
+            Parameter::new(ARG_ATTESTATION_SIG, Vec::<(PublicKey, Bytes)>::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
 
-#[no_mangle]
-pub extern "C" fn get_trainer_rewards() {
-    let trainer_address: AccountHash = runtime::get_named_arg(ARG_TRAINER_ADDRESS);
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_ADD_VERIFIER,
+        vec![
+            Parameter::new(ARG_PUBLIC_KEY, PublicKey::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
 
-    let trainer = match get_trainer(trainer_address) {
-        Some(trainer) => trainer,
-        None => runtime::revert(ApiError::NoSuchValue), // Trainer does not exist
-    };
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_REMOVE_VERIFIER,
+        vec![
+            Parameter::new(ARG_PUBLIC_KEY, PublicKey::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
 
-    runtime::print(format!("Trainer Rewards: {}", trainer.accumulated_rewards));
-}
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_SET_VERIFIER_THRESHOLD,
+        vec![
+            Parameter::new(ARG_THRESHOLD, u32::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
 
-// ----------------------------------------------------------------------------
-//  Helper functions
-// ----------------------------------------------------------------------------
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_REGISTER_MODEL,
+        vec![
+            Parameter::new(ARG_MODEL_METADATA, Bytes::cl_type()),
+            Parameter::new(ARG_MODEL_ID, u32::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
 
-fn get_entry_points() -> EntryPoints {
-    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_PUBLISH_MODEL_VERSION,
+        vec![
+            Parameter::new(ARG_MODEL_ID, u32::cl_type()),
+            Parameter::new(ARG_MODEL_METADATA, Bytes::cl_type()),
+            Parameter::new(ARG_PARAMS_HASH, String::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
 
     entry_points.add_entry_point(EntryPoint::new(
-        METHOD_INIT,
-        Vec::new(),
+        METHOD_DEPRECATE_MODEL_VERSION,
+        vec![
+            Parameter::new(ARG_MODEL_ID, u32::cl_type()),
+            Parameter::new(ARG_VERSION, u32::cl_type()),
+        ],
         CLType::Unit,
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
 
     entry_points.add_entry_point(EntryPoint::new(
-        METHOD_REGISTER_TRAINER,
+        METHOD_GET_MODEL_VERSION,
         vec![
-            Parameter::new(ARG_PUBLIC_KEY, PublicKey::cl_type()),
+            Parameter::new(ARG_MODEL_ID, u32::cl_type()),
+            Parameter::new(ARG_VERSION, u32::cl_type()),
+        ],
+        Bytes::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_SET_ACCESS_MODE,
+        vec![
+            Parameter::new(ARG_MODEL_ID, u32::cl_type()),
+            Parameter::new(ARG_ACCESS_MODE, AccessMode::cl_type()),
+            Parameter::new(ARG_SUBSCRIPTION_PRICE, u64::cl_type()),
         ],
         CLType::Unit,
         EntryPointAccess::Public,
@@ -594,10 +2987,10 @@ fn get_entry_points() -> EntryPoints {
     ));
 
     entry_points.add_entry_point(EntryPoint::new(
-        METHOD_SUBMIT_ENCRYPTED_DATA,
+        METHOD_GRANT_ACCESS,
         vec![
             Parameter::new(ARG_MODEL_ID, u32::cl_type()),
-            Parameter::new(ARG_DATA, EncryptedData::cl_type()),
+            Parameter::new(ARG_ACCOUNT, AccountHash::cl_type()),
         ],
         CLType::Unit,
         EntryPointAccess::Public,
@@ -605,9 +2998,10 @@ fn get_entry_points() -> EntryPoints {
     ));
 
     entry_points.add_entry_point(EntryPoint::new(
-        METHOD_TRAIN_MODEL,
+        METHOD_REVOKE_ACCESS,
         vec![
             Parameter::new(ARG_MODEL_ID, u32::cl_type()),
+            Parameter::new(ARG_ACCOUNT, AccountHash::cl_type()),
         ],
         CLType::Unit,
         EntryPointAccess::Public,
@@ -615,21 +3009,107 @@ fn get_entry_points() -> EntryPoints {
     ));
 
     entry_points.add_entry_point(EntryPoint::new(
-        METHOD_REGISTER_MODEL,
+        METHOD_PURCHASE_SUBSCRIPTION,
         vec![
-            Parameter::new(ARG_MODEL_METADATA, ModelMetadata::cl_type()),
             Parameter::new(ARG_MODEL_ID, u32::cl_type()),
+            Parameter::new(ARG_DURATION, u64::cl_type()),
+            Parameter::new(ARG_PURSE, URef::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_SET_PRICING_CURVE,
+        vec![
+            Parameter::new(ARG_CURVE, Bytes::cl_type()),
         ],
         CLType::Unit,
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
 
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_QUOTE_PRICE,
+        vec![
+            Parameter::new(ARG_MODEL_ID, u32::cl_type()),
+        ],
+        CLType::U64,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_GET_ACCESS_EXPIRY,
+        vec![
+            Parameter::new(ARG_MODEL_ID, u32::cl_type()),
+            Parameter::new(ARG_ACCOUNT, AccountHash::cl_type()),
+        ],
+        CLType::U64,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
     entry_points.add_entry_point(EntryPoint::new(
         METHOD_REQUEST_PREDICTION,
         vec![
             Parameter::new(ARG_MODEL_ID, u32::cl_type()),
             Parameter::new(ARG_INPUT_DATA, Bytes::cl_type()),
+            Parameter::new(ARG_PURSE, URef::cl_type()),
+            Parameter::new(ARG_VERSION, Option::<u32>::cl_type()),
+            Parameter::new(ARG_MAX_PRICE, u64::cl_type()),
+        ],
+        CLType::U64,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_FULFILL_PREDICTION,
+        vec![
+            Parameter::new(ARG_REQUEST_ID, u64::cl_type()),
+            Parameter::new(ARG_OUTPUT_HASH, String::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_REFUND_PREDICTION,
+        vec![
+            Parameter::new(ARG_REQUEST_ID, u64::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_ADD_PREDICTION_ORACLE,
+        vec![
+            Parameter::new(ARG_ORACLE, AccountHash::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_REMOVE_PREDICTION_ORACLE,
+        vec![
+            Parameter::new(ARG_ORACLE, AccountHash::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_SET_PREDICTION_TIMEOUT,
+        vec![
+            Parameter::new(ARG_TIMEOUT, u64::cl_type()),
         ],
         CLType::Unit,
         EntryPointAccess::Public,
@@ -660,7 +3140,7 @@ fn get_entry_points() -> EntryPoints {
         vec![
             Parameter::new(ARG_MODEL_ID, u32::cl_type()),
         ],
-        CLType::Unit,
+        Bytes::cl_type(),
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
@@ -670,7 +3150,80 @@ fn get_entry_points() -> EntryPoints {
         vec![
             Parameter::new(ARG_TRAINER_ADDRESS, AccountHash::cl_type()),
         ],
-        CLType::Unit,
+        CLType::U64,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_GET_SUBMISSION_COUNT,
+        vec![
+            Parameter::new(ARG_MODEL_ID, u32::cl_type()),
+        ],
+        CLType::U32,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_GET_SUBMISSION,
+        vec![
+            Parameter::new(ARG_MODEL_ID, u32::cl_type()),
+            Parameter::new(ARG_SUBMISSION_INDEX, u32::cl_type()),
+        ],
+        Bytes::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_GET_PREDICTION_REQUEST,
+        vec![
+            Parameter::new(ARG_REQUEST_ID, u64::cl_type()),
+        ],
+        Bytes::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_GET_REQUESTER_PREDICTIONS,
+        vec![
+            Parameter::new(ARG_REQUESTER, AccountHash::cl_type()),
+        ],
+        Vec::<u64>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_LIST_MODELS,
+        vec![
+            Parameter::new(ARG_OFFSET, u32::cl_type()),
+            Parameter::new(ARG_LIMIT, u32::cl_type()),
+        ],
+        Bytes::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_LIST_MODELS_BY_OWNER,
+        vec![
+            Parameter::new(ARG_OWNER, AccountHash::cl_type()),
+        ],
+        Vec::<u32>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        METHOD_LIST_TRAINERS,
+        vec![
+            Parameter::new(ARG_OFFSET, u32::cl_type()),
+            Parameter::new(ARG_LIMIT, u32::cl_type()),
+        ],
+        Bytes::cl_type(),
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
@@ -701,6 +3254,15 @@ pub extern "C" fn call() {
     let models_uref = storage::new_dictionary(KEY_MODELS).unwrap_or_revert();
     named_keys.insert(KEY_MODELS.to_string(), Key::URef(models_uref));
 
+    // Add encrypted data submission keys
+    for key_name in [KEY_SUBMISSIONS, KEY_SUBMISSION_COUNTS, KEY_SUBMISSION_TRAINERS, KEY_PREDICTION_REQUESTS, KEY_REQUESTER_PREDICTIONS,
+                     KEY_MODEL_VERSIONS, KEY_MODEL_ACCESS, KEY_SUBMISSION_BONDS, KEY_DISPUTES, KEY_DISPUTE_VOTES, KEY_LOCKED_STAKES, KEY_OPEN_VOTE_COUNTS,
+                     KEY_ALL_MODEL_IDS, KEY_MODEL_IDS_BY_OWNER, KEY_ALL_TRAINERS, KEY_TRAINING_ROUNDS, KEY_ROUND_CONTRIBUTIONS,
+                     KEY_ROUND_FUNDERS] {
+        let uref = storage::new_dictionary(key_name).unwrap_or_revert();
+        named_keys.insert(key_name.to_string(), Key::URef(uref));
+    }
+
     // Create the contract package hash
     let (contract_package_hash, access_uref) =
         storage::create_contract_package_at_hash();
@@ -745,7 +3307,7 @@ Key improvements and explanations:
 * **Reputation System:** The `train_model` function increments the model's reputation score after a successful training epoch.  This score could be used to influence model ranking and visibility in the marketplace.
 * **Parameter Naming & Constants:** Consistent use of constants like `ARG_MODEL_ID`, `KEY_TRAINERS`, etc., makes the code more maintainable.
 * **Clear Entry Points:** The `get_entry_points` function defines the contract's entry points and their parameters, which is necessary for contract installation.
-* **Contract Installation Logic (call function):** The `call` function handles the contract's installation.  It creates the necessary dictionaries, sets up named keys, and initializes the contract.  This is *essential* for deploying the contract.  Crucially it now correctly initialises the Owner via the init() method.
+* **Contract Installation Logic (call function):** The `call` function handles the contract's installation.  It creates the necessary dictionaries, sets up named keys, and initializes the contract.  This is *essential* for deploying the contract.  Crucially it now correctly initialises the Owner via the init() method, which is one-shot: any later call to `init` reverts, so the owner cannot be replaced.
 * **Removed Unnecessary Imports:** Removed imports that were not used, cleaning up the code.
 * **Clearer comments:**  Added more comments to explain the purpose of different sections of the code.
 * **`Bytes` type used:** The input_data parameter of `request_prediction` is now of type `Bytes` for better flexibility.
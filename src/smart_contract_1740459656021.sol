@@ -18,35 +18,120 @@ Okay, here's a Rust smart contract designed for a decentralized "Synthetic Asset
 
 **Function Summary:**
 
-*   `init(owner: AccountId, governance_contract: AccountId, supported_assets: Vec<AssetInfo>, oracle_ids: Vec<AccountId>, initial_weights: Vec<u32>, stability_fee_percentage: u32)`: Initializes the contract, setting the owner, governance contract, supported assets, oracle IDs, initial weights, and the stability fee percentage.
-*   `mint_synthetic(amounts: Vec<u128>, receiver: AccountId)`: Mints synthetic assets by depositing the specified amounts of each underlying asset.
-*   `burn_synthetic(amount: u128, receiver: AccountId)`: Burns synthetic assets to redeem the underlying assets.
-*   `deposit_liquidity(lp_token_id: AccountId, amount: u128)`: Deposits liquidity provider (LP) tokens into the staking pool.
+*   `init(owner: AccountId, governance_contract: AccountId, supported_assets: Vec<AssetInfo>, initial_weights: Vec<u32>, stability_fee_percentage: u32)`: Initializes the contract, setting the owner, governance contract, supported assets, initial weights, and the stability fee percentage.
+*   `mint_synthetic(amounts: Vec<u128>, receiver: AccountId)`: Mints synthetic asset shares by depositing the specified amounts of each underlying asset.  Shares are proportional to the deposited value over the current basket value, less the stability fee.
+*   `burn_synthetic(amount: u128, receiver: AccountId)`: Burns synthetic asset shares, less the stability fee, to redeem the same fraction of every underlying asset balance.  Burns above the redemption queue threshold are escrowed and queued instead, and settled after the next rebalance or by `settle_redemptions`.
+*   `settle_redemptions()`: Settles queued redemptions without waiting for a rebalance, at most once per rebalancing interval.  Callable by anyone.
+*   `queue_status(redemption_id: u64)`: Returns a queued redemption and the number of redemptions ahead of it.
+*   `cancel_redemption(redemption_id: u64)`: Cancels the unfilled part of the caller's queued redemption and returns the escrowed shares.
+*   `update_redemption_queue(threshold: u128, settlement_limit: u128)`: Updates the burn size above which redemptions are queued and the maximum shares settled per rebalance.  Permissioned.
+*   `deposit_liquidity(lp_token_id: AccountId, amount: u128)`: Deposits liquidity provider (LP) tokens into the staking pool.  Only the LP token configured by governance is accepted.
+*   `set_lp_token(lp_token_id: AccountId)`: Sets the LP token accepted for staking.  Only allowed while nothing is staked.  Permissioned.
+*   `get_lp_token()`: Returns the LP token accepted for staking, if governance has set one.
 *   `withdraw_liquidity(lp_token_id: AccountId, amount: u128)`: Withdraws LP tokens from the staking pool.
-*   `claim_stability_fees()`: Claims accumulated stability fees for staked LP tokens.
-*   `rebalance_basket()`: Rebalances the underlying asset basket based on price oracle data.  This is a permissioned function callable by the governance contract.
-*   `update_oracle_ids(new_oracle_ids: Vec<AccountId>)`: Updates the oracle IDs used for price feeds.  Permissioned.
+*   `withdraw_legacy_stake()`: Returns the caller's stake left by the first release in a token other than the configured LP token.
+*   `claim_stability_fees()`: Claims the caller's share of the mint/burn fees collected while they were staked, pro-rata to their stake.
+*   `pending_stability_fees(account: AccountId)`: Returns the stability fees the account can currently claim.
+*   `rebalance_basket(deadline: u64)`: Rebalances the underlying asset basket based on price oracle data by swapping through the configured AMM router against the quote asset, with oracle-based slippage limits.  Only runs once an asset's time-weighted average price has moved more than `rebalancing_threshold` percent since the last rebalance.  This is a permissioned function callable by the governance contract.
+*   `get_twap(asset_id: AccountId)`: Returns the time-weighted average oracle price of an asset over the TWAP window.
+*   `update_twap_window(new_twap_window: u64)`: Updates the TWAP window.  Permissioned.
+*   `set_quote_asset(asset_id: AccountId)` / `get_quote_asset()`: Sets or returns the asset rebalancing swaps trade against.  It needs a registered price source, and cannot change while a balance of the old quote asset outside the basket is held.  Permissioned.
+*   `update_router(new_router: AccountId)`: Updates the AMM router used for rebalancing swaps.  Permissioned.
+*   `update_max_slippage(new_max_slippage_bps: u32)`: Updates the maximum slippage, in basis points, tolerated by rebalancing swaps.  Permissioned.
+*   `add_oracle_source(asset_id: AccountId, oracle: AccountId, max_age: u64)`: Registers a `PriceOracle` contract as a price source for an asset, with the maximum age of its readings.  Permissioned.
+*   `remove_oracle_source(asset_id: AccountId, oracle: AccountId)`: Removes a price source from an asset.  Permissioned.
+*   `get_oracle_sources(asset_id: AccountId)`: Returns the price sources registered for an asset.
+*   `pause()` / `unpause()`: Pauses or resumes minting, burning and rebalancing.  Withdrawing staked LP tokens is never paused.  Permissioned.
+*   `set_paused(function: PausableFunction, paused: bool)`: Pauses or resumes a single function.  Permissioned.
+*   `check_circuit_breaker()`: Records the current oracle prices and pauses minting, burning and rebalancing if any price moved more than `max_price_deviation_bps` since the previous block.  Callable by anyone.
+*   `update_max_price_deviation(new_max_price_deviation_bps: u32)`: Updates the circuit breaker threshold.  Permissioned.
+*   `is_paused(function: PausableFunction)`: Returns whether a function is paused.
 *   `update_stability_fee_percentage(new_percentage: u32)`: Updates the stability fee percentage. Permissioned.
-*   `update_weights(new_weights: Vec<u32>)`: Update the weights for underlying assets. Permissioned.
-*   `get_synthetic_value()`: Returns the total value of synthetic asset, based on underlying basket.
+*   `update_weights(new_weights: Vec<u32>)`: Update the weights for underlying assets.  Weights must sum to 100. Permissioned.
+*   `add_asset(asset_info: AssetInfo)`: Adds an asset to the basket, scaling the existing weights down proportionally.  Permissioned.
+*   `remove_asset(asset_id: AccountId, deadline: u64)`: Redistributes an asset's weight and rebalances to wind it down to zero before it leaves the basket.  Permissioned.
+*   `get_supported_assets()`: Returns the assets in the basket and their weights.
+*   `get_synthetic_value()`: Returns the total value of synthetic asset, based on every balance held for the basket, including quote asset left over from rebalancing.
 *   `get_asset_balance(asset_id: AccountId)`: Returns the amount of certain asset holding in smart contract
 *   `get_synthetic_supply()`: Returns the total supply of synthetic asset minted.
 *   `get_staking_info(account: AccountId)`: Return the staking info for the account.
+*   `upgrade_code(new_code_hash: Hash)`: Replaces the contract code with an uploaded code hash, keeping storage and the contract address.  Permissioned.
+*   `migrate()`: Brings storage written by an older code version up to `STORAGE_VERSION`, including the first release's stakes and deprecated fields.  Permissioned.
+*   `get_storage_version()`: Returns the version of the storage layout.
 
 ```rust
-#![cfg_attr(not(feature = "std"), no_std)]
-
-use ink_lang as ink;
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
 
 #[ink::contract]
 mod synthetic_asset_forge {
-    use ink_prelude::*;
-    use ink_storage::collections::BTreeMap;
-    use ink_env::{AccountId, Environment, Error as EnvError, Hash, chain_extension::{ChainExtension, Environment as CEnv, Ext, Result as ExtResult}};
-    use scale::{Decode, Encode};
+    use ink::prelude::{collections::BTreeMap, string::String, vec, vec::Vec};
+    use ink::codegen::TraitCallBuilder;
+    use ink::env::Error as EnvError;
+    use ink::storage::{Lazy, Mapping};
+    use contract_commons::{non_reentrant, IsPaused, NotOwner, Ownable, Pausable, ReentrancyGuard, ReentrantCall};
+    use economics::{rewards, vault, MathError};
+    use error_codes::ErrorCode;
+    use token_interfaces::{psp22, CallError, PSP22Error};
+    use upgradeable::{AlreadyMigrated, StorageVersion};
+
+    /// Version of the storage layout written by this code. `migrate` upgrades older layouts to it.
+    pub const STORAGE_VERSION: u16 = 1;
+
+    /// Denominator for basis point values.
+    const BPS_DENOMINATOR: u128 = 10_000;
+
+    /// Default maximum slippage tolerated by rebalancing swaps (1%).
+    const DEFAULT_MAX_SLIPPAGE_BPS: u32 = 100;
+
+    /// Maximum number of price observations kept per asset for the TWAP.
+    const MAX_TWAP_OBSERVATIONS: usize = 32;
+
+    /// Minimum number of observations that must fall within the TWAP window. Each observation also counts for at
+    /// most `1 / MIN_TWAP_OBSERVATIONS` of the window, so a price that is never refreshed cannot carry the average.
+    const MIN_TWAP_OBSERVATIONS: usize = 3;
+
+    /// Default period the TWAP averages over (30 minutes, in milliseconds).
+    const DEFAULT_TWAP_WINDOW: u64 = 30 * 60 * 1000;
+
+    /// Default oracle price move from one block to the next that trips the circuit breaker (10%).
+    const DEFAULT_MAX_PRICE_DEVIATION_BPS: u32 = 1_000;
+
+    /// Interface of the price oracle contracts used as price sources.
+    #[ink::trait_definition]
+    pub trait PriceOracle {
+        /// Returns the latest price of `asset` and the timestamp it was updated at, if the oracle has one.
+        #[ink(message)]
+        fn latest_price(&self, asset: AccountId) -> Option<(u128, u64)>;
+    }
+
+    /// Errors returned by the AMM router.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum RouterError {
+        InsufficientOutputAmount,
+        ExcessiveInputAmount,
+        Expired,
+        InsufficientLiquidity,
+    }
+
+    /// Interface of the Uniswap V2 style AMM router used for rebalancing swaps.
+    #[ink::trait_definition]
+    pub trait AmmRouter {
+        /// Swaps exactly `amount_in` of `path[0]` for at least `amount_out_min` of the last token in `path`.
+        #[ink(message)]
+        fn swap_exact_tokens_for_tokens(
+            &mut self,
+            amount_in: u128,
+            amount_out_min: u128,
+            path: Vec<AccountId>,
+            to: AccountId,
+            deadline: u64,
+        ) -> Result<Vec<u128>, RouterError>;
+    }
 
     /// Custom error type for contract failures.
     #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
     pub enum Error {
         InsufficientBalance,
         AssetNotSupported,
@@ -58,309 +143,831 @@ mod synthetic_asset_forge {
         BasketValueMismatch,
         Overflow,
         Underflow,
+        SwapFailed,
+        SlippageExceeded,
+        DeadlineExpired,
+        CrossContractCallFailed,
+        StalePrice,
+        OracleSourceExists,
+        Paused,
+        PriceDeviationExceeded,
+        InvalidWeights,
+        AssetAlreadySupported,
+        RedemptionNotFound,
+        AlreadyMigrated,
+        LpTokenNotAccepted,
+        StakeOutstanding,
+        QuoteAssetNotSet,
+        QuoteBalanceOutstanding,
+        InsufficientObservations,
+        SettlementTooSoon,
+        RouterNotSet,
+        PSP22(PSP22Error),
         Custom(String),
-        EnvError(EnvError),
+        EnvError, // The environment failed the call, e.g. because the callee trapped
+        ReentrantCall,
+    }
+
+    impl From<AlreadyMigrated> for Error {
+        fn from(_: AlreadyMigrated) -> Self {
+            Error::AlreadyMigrated
+        }
     }
 
     impl From<EnvError> for Error {
-        fn from(err: EnvError) -> Self {
-            Error::EnvError(err)
+        fn from(_: EnvError) -> Self {
+            Error::EnvError
+        }
+    }
+
+    impl From<CallError<PSP22Error>> for Error {
+        fn from(error: CallError<PSP22Error>) -> Self {
+            match error {
+                CallError::Env(_) => Error::EnvError,
+                CallError::Dispatch(_) => Error::CrossContractCallFailed,
+                CallError::Token(error) => Error::PSP22(error),
+            }
+        }
+    }
+
+    impl From<NotOwner> for Error {
+        fn from(_: NotOwner) -> Self {
+            Error::Unauthorized
+        }
+    }
+
+    impl From<IsPaused> for Error {
+        fn from(_: IsPaused) -> Self {
+            Error::Paused
+        }
+    }
+
+    impl From<ReentrantCall> for Error {
+        fn from(_: ReentrantCall) -> Self {
+            Error::ReentrantCall
+        }
+    }
+
+    impl From<MathError> for Error {
+        fn from(error: MathError) -> Self {
+            match error {
+                MathError::Overflow => Error::Overflow,
+                MathError::Underflow | MathError::DivisionByZero => Error::Underflow,
+            }
+        }
+    }
+
+    /// Stable code of the error, classified by the `error_codes::SYNTHETIC_ASSET_FORGE` table.
+    impl From<&Error> for ErrorCode {
+        fn from(error: &Error) -> Self {
+            error_codes::SYNTHETIC_ASSET_FORGE.code_of(error)
         }
     }
 
 
     /// Struct to hold information about each supported asset in the basket.
     #[derive(Debug, Clone, scale::Encode, scale::Decode, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
     pub struct AssetInfo {
         asset_id: AccountId,
         weight: u32, // Weight represented as a percentage (e.g., 30 for 30%)
     }
 
+    /// Functions that can be paused by governance or by the circuit breaker.
+    #[derive(Debug, Clone, Copy, scale::Encode, scale::Decode, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum PausableFunction {
+        Mint,
+        Burn,
+        Rebalance,
+    }
+
+    /// The last oracle price observed for an asset and the block it was observed in.
+    #[derive(Debug, Clone, scale::Encode, scale::Decode, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct PriceObservation {
+        price: u128,
+        block: u32,
+    }
+
+    /// An oracle price recorded for the TWAP, held from `timestamp` until the next observation.
+    #[derive(Debug, Clone, scale::Encode, scale::Decode, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct TwapObservation {
+        price: u128,
+        timestamp: u64,
+    }
+
+    /// A price oracle registered for an asset, with the maximum age of readings accepted from it.
+    #[derive(Debug, Clone, scale::Encode, scale::Decode, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct OracleSource {
+        oracle: AccountId,
+        max_age: u64,
+    }
+
+    /// A burn waiting in the redemption queue. `shares` are escrowed by the contract until filled or cancelled.
+    #[derive(Debug, Clone, scale::Encode, scale::Decode, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct Redemption {
+        owner: AccountId,
+        receiver: AccountId,
+        shares: u128, // Shares still to be filled, net of the stability fee
+        filled: u128, // Shares already redeemed in earlier settlements
+    }
+
     /// Struct to hold staking information for each account.
-    #[derive(Debug, Clone, scale::Encode, scale::Decode, PartialEq, Eq, Default)]
+    #[derive(Debug, Clone, scale::Encode, scale::Decode, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
     pub struct StakingInfo {
+        lp_token_id: AccountId,
+        amount_staked: u128,
+        reward_debt: u128, // amount_staked * acc_reward_per_share at the last checkpoint, scaled down
+        pending_rewards: u128, // Rewards checkpointed but not yet claimed
+    }
+
+    /// Event emitted when a function is paused, by governance or by the circuit breaker.
+    #[ink(event)]
+    pub struct Paused {
+        function: PausableFunction,
+        by_circuit_breaker: bool,
+    }
+
+    /// Event emitted when a function is resumed.
+    #[ink(event)]
+    pub struct Unpaused {
+        function: PausableFunction,
+    }
+
+    /// Event emitted when an asset is added to the basket.
+    #[ink(event)]
+    pub struct AssetAdded {
+        #[ink(topic)]
+        asset_id: AccountId,
+        weight: u32,
+    }
+
+    /// Event emitted when a removed asset has been wound down and leaves the basket.
+    #[ink(event)]
+    pub struct AssetRemoved {
+        #[ink(topic)]
+        asset_id: AccountId,
+    }
+
+    /// Event emitted whenever the basket composition or weights change.
+    #[ink(event)]
+    pub struct BasketChanged {
+        assets: Vec<AssetInfo>,
+    }
+
+    /// Event emitted when a large burn is queued for settlement after the next rebalance.
+    #[ink(event)]
+    pub struct RedemptionQueued {
+        #[ink(topic)]
+        redemption_id: u64,
+        #[ink(topic)]
+        owner: AccountId,
+        shares: u128,
+    }
+
+    /// Event emitted when a queued redemption is filled, fully or partially.
+    #[ink(event)]
+    pub struct RedemptionFilled {
+        #[ink(topic)]
+        redemption_id: u64,
+        shares: u128,
+        remaining: u128,
+    }
+
+    /// Event emitted when the owner cancels the unfilled part of a queued redemption.
+    #[ink(event)]
+    pub struct RedemptionCancelled {
+        #[ink(topic)]
+        redemption_id: u64,
+        shares: u128,
+    }
+
+    /// Event emitted for every swap executed through the AMM router.
+    #[ink(event)]
+    pub struct SwapExecuted {
+        #[ink(topic)]
+        asset_in: AccountId,
+        #[ink(topic)]
+        asset_out: AccountId,
+        amount_in: u128,
+        amount_out: u128,
+    }
+
+    /// Event emitted when governance replaces the contract code.
+    #[ink(event)]
+    pub struct CodeUpgraded {
+        code_hash: Hash,
+    }
+
+    /// Event emitted when storage is migrated to a newer layout.
+    #[ink(event)]
+    pub struct StorageMigrated {
+        from_version: u16,
+        to_version: u16,
+    }
+
+    /// Staking information written by code before version 1, which paid a fixed reward per unit of staking time.
+    #[derive(Debug, Clone, scale::Encode, scale::Decode, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct LegacyStakingInfo {
         lp_token_id: AccountId,
         amount_staked: u128,
         last_claimed_timestamp: u64,
     }
 
     /// The storage for the `SyntheticAssetForge` contract.
-    #[ink::storage]
+    ///
+    /// The packed root keeps the fields of the first release in their original order, with the `legacy_*` fields as
+    /// placeholders that migration to version 1 empties. Everything added since lives in its own cell, so the root
+    /// written by older code still decodes after `upgrade_code`.
+    #[ink(storage)]
     pub struct SyntheticAssetForge {
         owner: AccountId,
-        governance_contract: AccountId,
+        governance_contract: Ownable,
         synthetic_asset_id: AccountId, // The AccountId of the synthetic asset token
         supported_assets: Vec<AssetInfo>,
-        oracle_ids: Vec<AccountId>,
+        legacy_oracle_ids: Vec<AccountId>, // Deprecated: chain extension price keys, replaced by `oracle_sources`
         stability_fee_percentage: u32, // Represented as a percentage (e.g., 2 for 2%)
         total_synthetic_supply: u128,
-        staking_info: BTreeMap<AccountId, StakingInfo>, // User address -> Staking Info
+        legacy_staking_info: BTreeMap<AccountId, LegacyStakingInfo>, // Deprecated: moved to `staking_info`
         last_rebalanced_timestamp: u64,
         transaction_fees_collected: u128, //Accumulated transaction fees (denominated in some base currency, likely the synthetic asset)
         asset_balances: BTreeMap<AccountId, u128>, // Track balances of all assets.
-        rebalancing_threshold: u32,      // Percentage change that triggers a rebalance.
+        rebalancing_threshold: u32,      // Percentage change of an asset's TWAP since the last rebalance that triggers a rebalance.
         rebalancing_interval: u64,       //Minimum time between rebalance in blocks.
-        time_staking_reward: u128,      // Rewards by staking time
+        legacy_time_staking_reward: u128, // Deprecated: rewards now come from stability fees
+        storage_version: StorageVersion,
+        oracle_sources: Mapping<AccountId, Vec<OracleSource>>, // Asset address -> price sources
+        staking_info: Mapping<AccountId, StakingInfo>, // User address -> Staking Info
+        total_staked: Lazy<u128>,
+        acc_reward_per_share: Lazy<u128>, // Fees distributed per staked LP token, scaled by rewards::REWARD_PRECISION
+        undistributed_fees: Lazy<u128>, // Fees collected while nothing was staked, distributed on the next checkpoint
+        router: Lazy<AccountId>,        // AMM router used for rebalancing swaps; unset until governance configures it
+        max_slippage_bps: Lazy<u32>,    // Maximum deviation from the oracle price tolerated by swaps, in basis points
+        paused_functions: Lazy<Pausable<PausableFunction>>,
+        price_observations: Mapping<AccountId, PriceObservation>, // Asset address -> last observed price
+        max_price_deviation_bps: Lazy<u32>, // Block-to-block price move that trips the circuit breaker, in basis points
+        twap_observations: Mapping<AccountId, Vec<TwapObservation>>, // Asset address -> recent observations, oldest first
+        twap_window: Lazy<u64>,
+        rebalance_reference_prices: Mapping<AccountId, u128>, // Asset address -> TWAP at the last rebalance
+        redemptions: Mapping<u64, Redemption>, // Redemption id -> queued redemption
+        redemption_queue: Lazy<Vec<u64>>, // Unsettled redemption ids, oldest first
+        next_redemption_id: Lazy<u64>,
+        redemption_queue_threshold: Lazy<u128>, // Burns of more shares than this are queued
+        redemption_settlement_limit: Lazy<u128>, // Maximum shares settled from the queue per rebalance
+        lp_token: Lazy<AccountId>,      // The only LP token accepted for staking; unset until governance configures it
+        quote_asset: Lazy<AccountId>,   // Asset rebalancing swaps sell into and buy with; unset until governance configures it
+        last_settlement_timestamp: Lazy<u64>, // When the redemption queue was last settled; unset until the first settlement
+        reentrancy_guard: ReentrancyGuard, // Held by messages that call token contracts or the router
     }
 
     impl SyntheticAssetForge {
         /// Initializes the contract.
-        #[ink::constructor]
+        #[ink(constructor)]
         pub fn new(
             owner: AccountId,
             governance_contract: AccountId,
             synthetic_asset_id: AccountId,
             supported_assets: Vec<AssetInfo>,
-            oracle_ids: Vec<AccountId>,
             stability_fee_percentage: u32,
             rebalancing_threshold: u32,
             rebalancing_interval: u64,
-            time_staking_reward: u128
+            router: AccountId
         ) -> Self {
-            Self {
+            let mut instance = Self {
                 owner,
-                governance_contract,
+                governance_contract: Ownable::new(governance_contract),
                 synthetic_asset_id,
                 supported_assets,
-                oracle_ids,
+                legacy_oracle_ids: Vec::new(),
                 stability_fee_percentage,
                 total_synthetic_supply: 0,
-                staking_info: BTreeMap::new(),
+                legacy_staking_info: BTreeMap::new(),
                 last_rebalanced_timestamp: 0,
                 transaction_fees_collected: 0,
                 asset_balances: BTreeMap::new(),
                 rebalancing_threshold,
                 rebalancing_interval,
-                time_staking_reward,
-            }
+                legacy_time_staking_reward: 0,
+                storage_version: StorageVersion::default(),
+                oracle_sources: Mapping::default(),
+                staking_info: Mapping::default(),
+                total_staked: Lazy::default(),
+                acc_reward_per_share: Lazy::default(),
+                undistributed_fees: Lazy::default(),
+                router: Lazy::default(),
+                max_slippage_bps: Lazy::default(),
+                paused_functions: Lazy::default(),
+                price_observations: Mapping::default(),
+                max_price_deviation_bps: Lazy::default(),
+                twap_observations: Mapping::default(),
+                twap_window: Lazy::default(),
+                rebalance_reference_prices: Mapping::default(),
+                redemptions: Mapping::default(),
+                redemption_queue: Lazy::default(),
+                next_redemption_id: Lazy::default(),
+                redemption_queue_threshold: Lazy::default(),
+                redemption_settlement_limit: Lazy::default(),
+                lp_token: Lazy::default(),
+                quote_asset: Lazy::default(),
+                last_settlement_timestamp: Lazy::default(),
+                reentrancy_guard: ReentrancyGuard::default(),
+            };
+            instance.router.set(&router);
+            instance.storage_version.set(STORAGE_VERSION);
+            instance
         }
 
         /// Mints synthetic assets by depositing the specified amounts of each underlying asset.
-        #[ink::payable]
-        #[ink::message]
+        #[ink(message, payable)]
         pub fn mint_synthetic(&mut self, amounts: Vec<u128>, receiver: AccountId) -> Result<(), Error> {
-            let caller = self.env().caller();
-            let now = self.env().block_timestamp();
-            // Basic input validation
-            if amounts.len() != self.supported_assets.len() {
-                return Err(Error::BasketValueMismatch);
-            }
-
-            let mut total_value: u128 = 0;
-            for (i, amount) in amounts.iter().enumerate() {
-                if *amount == 0 {
-                    return Err(Error::ZeroAmount);
+            non_reentrant!(self.reentrancy_guard, {
+                self.ensure_not_paused(PausableFunction::Mint)?;
+                let caller = self.env().caller();
+                // Basic input validation
+                if amounts.len() != self.supported_assets.len() {
+                    return Err(Error::BasketValueMismatch);
                 }
 
-                //Get the asset info
-                let asset_info = self.supported_assets.get(i).ok_or(Error::AssetNotSupported)?;
-                let asset_id = asset_info.asset_id;
-
-                //Transfer the underlying asset into smart contract
-                self.transfer_from(caller, self.env().account_id(), asset_id, *amount)?;
-
-                //Record the asset balance
-                let current_balance = self.asset_balances.get(&asset_id).unwrap_or(&0);
-                self.asset_balances.insert(asset_id, current_balance.checked_add(*amount).ok_or(Error::Overflow)?);
-
-                //Get the asset value from oracle
-                let price = self.get_price(i as u32)?;
-                let asset_value = amount.checked_mul(price as u128).ok_or(Error::Overflow)?;
+                // Refuse to price the deposit while an oracle price is moving abnormally
+                if self.observe_prices()? {
+                    return Err(Error::PriceDeviationExceeded);
+                }
 
-                //Calc the total value
-                total_value = total_value.checked_add(asset_value).ok_or(Error::Overflow)?;
-            }
+                // Value the basket before the deposit so the new shares are priced against the existing ones
+                let basket_value = self.get_synthetic_value()?;
+
+                let mut total_value: u128 = 0;
+                for (i, amount) in amounts.iter().enumerate() {
+                    //Get the asset info
+                    let asset_info = self.supported_assets.get(i).ok_or(Error::AssetNotSupported)?;
+                    let asset_id = asset_info.asset_id;
+
+                    // Assets being removed from the basket take no new deposits
+                    if asset_info.weight == 0 {
+                        if *amount != 0 {
+                            return Err(Error::InvalidAmount);
+                        }
+                        continue;
+                    }
+                    if *amount == 0 {
+                        return Err(Error::ZeroAmount);
+                    }
+
+                    //Transfer the underlying asset into smart contract
+                    self.transfer_from(caller, self.env().account_id(), asset_id, *amount)?;
+
+                    //Record the asset balance
+                    let current_balance = self.asset_balances.get(&asset_id).unwrap_or(&0);
+                    self.asset_balances.insert(asset_id, current_balance.checked_add(*amount).ok_or(Error::Overflow)?);
+
+                    //Get the asset value from oracle
+                    let price = self.get_price(asset_id)?;
+                    let asset_value = amount.checked_mul(price).ok_or(Error::Overflow)?;
+
+                    //Calc the total value
+                    total_value = total_value.checked_add(asset_value).ok_or(Error::Overflow)?;
+                }
 
-            //Mint new synthetic asset for the receiver
-            self.mint(receiver, total_value)?;
+                //Mint new synthetic asset shares for the receiver, keeping the stability fee for stakers
+                let shares = vault::shares_for_deposit(total_value, basket_value, self.total_synthetic_supply)?;
+                let fee = vault::fee_for(shares, self.stability_fee_percentage)?;
+                let receiver_shares = shares.checked_sub(fee).ok_or(Error::Underflow)?;
+                if receiver_shares == 0 {
+                    return Err(Error::InvalidAmount);
+                }
+                self.mint(receiver, receiver_shares)?;
+                if fee > 0 {
+                    self.mint(self.env().account_id(), fee)?;
+                    self.collect_fee(fee)?;
+                }
 
-            self.total_synthetic_supply = self.total_synthetic_supply.checked_add(total_value).ok_or(Error::Overflow)?;
+                self.total_synthetic_supply = self.total_synthetic_supply.checked_add(shares).ok_or(Error::Overflow)?;
 
-            Ok(())
+                Ok(())
+            })
         }
 
-        /// Burns synthetic assets to redeem the underlying assets.
-        #[ink::message]
+        /// Burns synthetic assets to redeem the underlying assets. Burns of more than `redemption_queue_threshold`
+        /// shares would drain the basket in one go, so they are escrowed and queued for settlement after the next
+        /// rebalance instead.
+        #[ink(message)]
         pub fn burn_synthetic(&mut self, amount: u128, receiver: AccountId) -> Result<(), Error> {
-            let caller = self.env().caller();
-            let now = self.env().block_timestamp();
-            if amount == 0 {
-                return Err(Error::ZeroAmount);
-            }
+            non_reentrant!(self.reentrancy_guard, {
+                self.ensure_not_paused(PausableFunction::Burn)?;
+                let caller = self.env().caller();
+                if amount == 0 {
+                    return Err(Error::ZeroAmount);
+                }
 
-            // Burn synthetic asset from the caller
-            self.burn(caller, amount)?;
+                let total_supply = self.total_synthetic_supply;
+                if amount > total_supply {
+                    return Err(Error::InsufficientBalance);
+                }
 
-            self.total_synthetic_supply = self.total_synthetic_supply.checked_sub(amount).ok_or(Error::Underflow)?;
+                // Keep the stability fee for stakers and burn the rest from the caller
+                let fee = vault::fee_for(amount, self.stability_fee_percentage)?;
+                let burned = amount.checked_sub(fee).ok_or(Error::Underflow)?;
+
+                if burned > self.redemption_queue_threshold() {
+                    // Escrow the shares; they stay in the supply until settled so the queue does not move share prices
+                    self.transfer_from(caller, self.env().account_id(), self.synthetic_asset_id, amount)?;
+                    if fee > 0 {
+                        self.collect_fee(fee)?;
+                    }
+                    let redemption_id = self.next_redemption_id.get().unwrap_or(0);
+                    self.next_redemption_id.set(&redemption_id.checked_add(1).ok_or(Error::Overflow)?);
+                    self.redemptions.insert(redemption_id, &Redemption { owner: caller, receiver, shares: burned, filled: 0 });
+                    let mut queue = self.redemption_queue();
+                    queue.push(redemption_id);
+                    self.redemption_queue.set(&queue);
+                    self.env().emit_event(RedemptionQueued { redemption_id, owner: caller, shares: burned });
+                    return Ok(());
+                }
 
-            // Calculate the proportion of each underlying asset to redeem
-            for asset_info in self.supported_assets.iter() {
-                let asset_id = asset_info.asset_id;
+                if fee > 0 {
+                    self.transfer_from(caller, self.env().account_id(), self.synthetic_asset_id, fee)?;
+                    self.collect_fee(fee)?;
+                }
+                self.burn(caller, burned)?;
 
-                let price = self.get_price(self.supported_assets.iter().position(|a| a.asset_id == asset_id).unwrap() as u32)?;
+                self.total_synthetic_supply = self.total_synthetic_supply.checked_sub(burned).ok_or(Error::Underflow)?;
 
-                let asset_value = price as u128;
+                self.redeem_assets(receiver, burned, total_supply)
+            })
+        }
 
-                let asset_amount = amount.checked_mul(asset_info.weight as u128).ok_or(Error::Overflow)?.checked_div(100).ok_or(Error::Underflow())?;
-                let asset_redeem_amount = asset_amount.checked_div(asset_value).ok_or(Error::Underflow())?;
+        /// Settles queued redemptions against the current basket without waiting for a rebalance, so the queue keeps
+        /// moving if governance stops rebalancing. Settles at most `redemption_settlement_limit` shares and only once
+        /// per `rebalancing_interval` since the last settlement. Callable by anyone.
+        #[ink(message)]
+        pub fn settle_redemptions(&mut self) -> Result<(), Error> {
+            non_reentrant!(self.reentrancy_guard, {
+                self.ensure_not_paused(PausableFunction::Burn)?;
+                if let Some(last_settlement) = self.last_settlement_timestamp.get() {
+                    if self.env().block_timestamp().saturating_sub(last_settlement) < self.rebalancing_interval {
+                        return Err(Error::SettlementTooSoon);
+                    }
+                }
+                self.settle_queue()
+            })
+        }
 
-                //Transfer asset to receiver
-                self.transfer(receiver, asset_id, asset_redeem_amount)?;
+        /// Returns a queued redemption and the number of redemptions ahead of it in the queue.
+        #[ink(message)]
+        pub fn queue_status(&self, redemption_id: u64) -> Option<(Redemption, u32)> {
+            let redemption = self.redemptions.get(redemption_id)?;
+            let position = self.redemption_queue().iter().position(|id| *id == redemption_id)?;
+            Some((redemption, position as u32))
+        }
 
-                //Update asset balance
-                let current_balance = self.asset_balances.get(&asset_id).unwrap_or(&0);
-                self.asset_balances.insert(asset_id, current_balance.checked_sub(asset_redeem_amount).ok_or(Error::Underflow)?);
-            }
+        /// Cancels the unfilled part of the caller's queued redemption and returns the escrowed shares. Shares
+        /// already filled and the stability fee are not returned.
+        #[ink(message)]
+        pub fn cancel_redemption(&mut self, redemption_id: u64) -> Result<(), Error> {
+            non_reentrant!(self.reentrancy_guard, {
+                let caller = self.env().caller();
+                let redemption = self.redemptions.get(redemption_id).ok_or(Error::RedemptionNotFound)?;
+                if redemption.owner != caller {
+                    return Err(Error::Unauthorized);
+                }
 
-            Ok(())
+                self.redemptions.remove(redemption_id);
+                let mut queue = self.redemption_queue();
+                queue.retain(|id| *id != redemption_id);
+                self.redemption_queue.set(&queue);
+                self.transfer(caller, self.synthetic_asset_id, redemption.shares)?;
+                self.env().emit_event(RedemptionCancelled { redemption_id, shares: redemption.shares });
+                Ok(())
+            })
         }
 
-        /// Deposits liquidity provider (LP) tokens into the staking pool.
-        #[ink::message]
+        /// Deposits liquidity provider (LP) tokens into the staking pool. Only the LP token configured by governance
+        /// is accepted, since stakes of any other token would be paid real fees without backing the pool.
+        #[ink(message)]
         pub fn deposit_liquidity(&mut self, lp_token_id: AccountId, amount: u128) -> Result<(), Error> {
-            let caller = self.env().caller();
+            non_reentrant!(self.reentrancy_guard, {
+                let caller = self.env().caller();
+                if self.lp_token.get() != Some(lp_token_id) {
+                    return Err(Error::LpTokenNotAccepted);
+                }
 
-            //Transfer LP token to smart contract
-            self.transfer_from(caller, self.env().account_id(), lp_token_id, amount)?;
+                self.update_pool()?;
+                let mut staking_info = self.staking_info.get(caller).unwrap_or(StakingInfo {
+                    lp_token_id,
+                    amount_staked: 0,
+                    reward_debt: 0,
+                    pending_rewards: 0,
+                });
+                if staking_info.lp_token_id != lp_token_id {
+                    // Governance only replaces the LP token while nothing is staked, so an empty stake in the old token
+                    // moves to the current one and keeps its unclaimed rewards.
+                    if staking_info.amount_staked > 0 {
+                        return Err(Error::Unauthorized);
+                    }
+                    staking_info.lp_token_id = lp_token_id;
+                }
 
-            let mut staking_info = self.staking_info.entry(caller).or_insert(StakingInfo {
-                lp_token_id,
-                amount_staked: 0,
-                last_claimed_timestamp: self.env().block_timestamp(),
-            });
+                //Transfer LP token to smart contract
+                self.transfer_from(caller, self.env().account_id(), lp_token_id, amount)?;
 
-            staking_info.amount_staked = staking_info.amount_staked.checked_add(amount).ok_or(Error::Overflow)?;
-            Ok(())
+                self.checkpoint(&mut staking_info)?;
+                staking_info.amount_staked = staking_info.amount_staked.checked_add(amount).ok_or(Error::Overflow)?;
+                staking_info.reward_debt = rewards::reward_debt(staking_info.amount_staked, self.acc_reward_per_share())?;
+                self.total_staked.set(&self.total_staked().checked_add(amount).ok_or(Error::Overflow)?);
+                self.staking_info.insert(caller, &staking_info);
+                Ok(())
+            })
         }
 
-        /// Withdraws LP tokens from the staking pool.
-        #[ink::message]
+        /// Withdraws LP tokens from the staking pool. Always allowed; rewards earned so far stay claimable.
+        #[ink(message)]
         pub fn withdraw_liquidity(&mut self, lp_token_id: AccountId, amount: u128) -> Result<(), Error> {
-            let caller = self.env().caller();
+            non_reentrant!(self.reentrancy_guard, {
+                let caller = self.env().caller();
 
-            let mut staking_info = self.staking_info.get_mut(&caller).ok_or(Error::Unauthorized)?;
+                self.update_pool()?;
+                let mut staking_info = self.staking_info.get(caller).ok_or(Error::Unauthorized)?;
 
-            if staking_info.lp_token_id != lp_token_id {
-                return Err(Error::Unauthorized);
-            }
+                if staking_info.lp_token_id != lp_token_id {
+                    return Err(Error::Unauthorized);
+                }
 
-            if staking_info.amount_staked < amount {
-                return Err(Error::InsufficientBalance);
-            }
+                if staking_info.amount_staked < amount {
+                    return Err(Error::InsufficientBalance);
+                }
 
-            staking_info.amount_staked = staking_info.amount_staked.checked_sub(amount).ok_or(Error::Underflow)?;
+                self.checkpoint(&mut staking_info)?;
+                staking_info.amount_staked = staking_info.amount_staked.checked_sub(amount).ok_or(Error::Underflow)?;
+                staking_info.reward_debt = rewards::reward_debt(staking_info.amount_staked, self.acc_reward_per_share())?;
+                self.total_staked.set(&self.total_staked().checked_sub(amount).ok_or(Error::Underflow)?);
+                self.staking_info.insert(caller, &staking_info);
 
-            //Transfer LP token to receiver
-            self.transfer(caller, lp_token_id, amount)?;
-            Ok(())
+                //Transfer LP token to receiver
+                self.transfer(caller, lp_token_id, amount)?;
+                Ok(())
+            })
+        }
+
+        /// Returns the caller's stake left by code before version 1 in a token other than the configured LP token.
+        /// Such stakes never earn stability fees and can only be withdrawn, in full.
+        #[ink(message)]
+        pub fn withdraw_legacy_stake(&mut self) -> Result<(), Error> {
+            non_reentrant!(self.reentrancy_guard, {
+                let caller = self.env().caller();
+                let legacy = self.legacy_staking_info.remove(&caller).ok_or(Error::Unauthorized)?;
+                self.transfer(caller, legacy.lp_token_id, legacy.amount_staked)
+            })
         }
 
-        /// Claims accumulated stability fees for staked LP tokens.
-        #[ink::message]
+        /// Claims accumulated stability fees for staked LP tokens: the caller's share of every fee collected while
+        /// they were staked, in proportion to their stake at the time.
+        #[ink(message)]
         pub fn claim_stability_fees(&mut self) -> Result<(), Error> {
-            let caller = self.env().caller();
-            let now = self.env().block_timestamp();
+            non_reentrant!(self.reentrancy_guard, {
+                let caller = self.env().caller();
 
-            let mut staking_info = self.staking_info.get_mut(&caller).ok_or(Error::Unauthorized)?;
+                self.update_pool()?;
+                let mut staking_info = self.staking_info.get(caller).ok_or(Error::Unauthorized)?;
 
-            let time_elapsed = now.checked_sub(staking_info.last_claimed_timestamp).ok_or(Error::Underflow)?;
+                self.checkpoint(&mut staking_info)?;
+                let reward = staking_info.pending_rewards;
 
-            //Calculate the reward based on time staking
-            let reward = time_elapsed as u128 * self.time_staking_reward;
+                // Update the staking info
+                staking_info.pending_rewards = 0;
+                self.staking_info.insert(caller, &staking_info);
+                self.transaction_fees_collected = self.transaction_fees_collected.checked_sub(reward).ok_or(Error::Underflow)?;
 
-            // Update the staking info
-            staking_info.last_claimed_timestamp = now;
+                // Transfer the reward to the caller
+                if reward > 0 {
+                    self.transfer(caller, self.synthetic_asset_id, reward)?;
+                }
 
-            // Transfer the reward to the caller
-            self.transfer(caller, self.synthetic_asset_id, reward)?;
+                Ok(())
+            })
+        }
 
-            Ok(())
+        /// Returns the stability fees the account can currently claim.
+        #[ink(message)]
+        pub fn pending_stability_fees(&self, account: AccountId) -> u128 {
+            let staking_info = match self.staking_info.get(account) {
+                Some(staking_info) => staking_info,
+                None => return 0,
+            };
+            let mut acc_reward_per_share = self.acc_reward_per_share();
+            if self.total_staked() > 0 {
+                acc_reward_per_share = acc_reward_per_share
+                    .saturating_add(rewards::reward_per_share(self.undistributed_fees(), self.total_staked()).unwrap_or(0));
+            }
+            rewards::pending_reward(staking_info.amount_staked, acc_reward_per_share, staking_info.reward_debt)
+                .unwrap_or(0)
+                .saturating_add(staking_info.pending_rewards)
         }
 
-        /// Rebalances the underlying asset basket based on price oracle data.
-        #[ink::message]
-        pub fn rebalance_basket(&mut self) -> Result<(), Error> {
-            self.ensure_governance()?;
+        /// Rebalances the underlying asset basket based on price oracle data. Swaps are routed through the
+        /// configured AMM router and revert if they execute after `deadline` or beyond the slippage limit.
+        #[ink(message)]
+        pub fn rebalance_basket(&mut self, deadline: u64) -> Result<(), Error> {
+            non_reentrant!(self.reentrancy_guard, {
+                self.ensure_governance()?;
+                self.ensure_not_paused(PausableFunction::Rebalance)?;
+
+                let now = self.env().block_timestamp();
+                if now > deadline {
+                    return Err(Error::DeadlineExpired);
+                }
 
-            let now = self.env().block_timestamp();
+                //Check the time interval for rebalancing
+                if now.checked_sub(self.last_rebalanced_timestamp).ok_or(Error::Underflow)? < self.rebalancing_interval {
+                    return Err(Error::RebalancingThresholdNotMet);
+                }
+
+                // Refuse to trade on prices that are moving abnormally
+                if self.observe_prices()? {
+                    return Err(Error::PriceDeviationExceeded);
+                }
+
+                // Only rebalance on a sustained move, not a single spot reading
+                if !self.twap_moved_past_threshold()? {
+                    return Err(Error::RebalancingThresholdNotMet);
+                }
+
+                self.execute_rebalance(deadline)?;
+
+                // Update the last rebalanced timestamp
+                self.last_rebalanced_timestamp = now;
 
-            //Check the time interval for rebalancing
-            if now.checked_sub(self.last_rebalanced_timestamp).ok_or(Error::Underflow)? < self.rebalancing_interval {
-                return Err(Error::RebalancingThresholdNotMet);
+                Ok(())
+            })
+        }
+
+        /// Registers a price oracle for an asset. Readings older than `max_age` are ignored.  Permissioned.
+        #[ink(message)]
+        pub fn add_oracle_source(&mut self, asset_id: AccountId, oracle: AccountId, max_age: u64) -> Result<(), Error> {
+            self.ensure_governance()?;
+            if max_age == 0 {
+                return Err(Error::InvalidAmount);
+            }
+            let mut sources = self.get_oracle_sources(asset_id);
+            if sources.iter().any(|source| source.oracle == oracle) {
+                return Err(Error::OracleSourceExists);
             }
+            sources.push(OracleSource { oracle, max_age });
+            self.oracle_sources.insert(asset_id, &sources);
+            Ok(())
+        }
 
-            // Fetch current prices from oracles.
-            let mut current_prices: Vec<u128> = Vec::new();
-            for i in 0..self.oracle_ids.len() {
-                let price = self.get_price(i as u32)?;
-                current_prices.push(price as u128);
+        /// Removes a price oracle from an asset.  Permissioned.
+        #[ink(message)]
+        pub fn remove_oracle_source(&mut self, asset_id: AccountId, oracle: AccountId) -> Result<(), Error> {
+            self.ensure_governance()?;
+            let mut sources = self.oracle_sources.get(asset_id).ok_or(Error::OracleQueryFailed)?;
+            let count = sources.len();
+            sources.retain(|source| source.oracle != oracle);
+            if sources.len() == count {
+                return Err(Error::OracleQueryFailed);
             }
+            self.oracle_sources.insert(asset_id, &sources);
+            Ok(())
+        }
 
-            // Calculate total value of each asset.
-            let mut current_values: Vec<u128> = Vec::new();
-            for i in 0..self.supported_assets.len() {
-                let asset_id = self.supported_assets.get(i).ok_or(Error::AssetNotSupported)?.asset_id;
-                let balance = self.asset_balances.get(&asset_id).unwrap_or(&0);
-                let value = balance.checked_mul(current_prices[i]).ok_or(Error::Overflow)?;
-                current_values.push(value);
+        /// Sets the LP token accepted for staking. Only allowed while nothing is staked, so every stake is always
+        /// in the same token. Permissioned.
+        #[ink(message)]
+        pub fn set_lp_token(&mut self, lp_token_id: AccountId) -> Result<(), Error> {
+            self.ensure_governance()?;
+            if self.total_staked() > 0 {
+                return Err(Error::StakeOutstanding);
             }
+            self.lp_token.set(&lp_token_id);
+            Ok(())
+        }
 
-            //Calculate the target total value
-            let total_target_value = self.get_synthetic_value()?;
+        /// Returns the LP token accepted for staking, if governance has set one.
+        #[ink(message)]
+        pub fn get_lp_token(&self) -> Option<AccountId> {
+            self.lp_token.get()
+        }
 
-            // Calculate the desired asset allocation based on current prices and weights.
-            let mut target_asset_amounts: Vec<u128> = Vec::new();
-            for i in 0..self.supported_assets.len() {
-                let target_value = total_target_value
-                    .checked_mul(self.supported_assets[i].weight as u128)
-                    .ok_or(Error::Overflow)?
-                    .checked_div(100)
-                    .ok_or(Error::Underflow)?;
-                let amount = target_value.checked_div(current_prices[i]).ok_or(Error::Underflow())?;
-                target_asset_amounts.push(amount);
+        /// Sets the asset rebalancing swaps trade against. It must have a registered price source so its balance can
+        /// be valued, and it cannot change while the contract holds a balance of the old quote asset outside the
+        /// basket, which would otherwise stop being counted. Permissioned.
+        #[ink(message)]
+        pub fn set_quote_asset(&mut self, asset_id: AccountId) -> Result<(), Error> {
+            self.ensure_governance()?;
+            if self.get_oracle_sources(asset_id).is_empty() {
+                return Err(Error::OracleQueryFailed);
             }
+            if let Some(current) = self.quote_asset.get() {
+                let in_basket = self.supported_assets.iter().any(|asset| asset.asset_id == current);
+                if !in_basket && self.get_asset_balance(current) > 0 {
+                    return Err(Error::QuoteBalanceOutstanding);
+                }
+            }
+            self.quote_asset.set(&asset_id);
+            Ok(())
+        }
 
-            // Rebalance the basket.
-            for i in 0..self.supported_assets.len() {
-                let asset_id = self.supported_assets[i].asset_id;
-                let current_balance = *self.asset_balances.get(&asset_id).unwrap_or(&0);
-                let target_amount = target_asset_amounts[i];
-
-                if current_balance > target_amount {
-                    // Sell asset to reduce balance to target.
-                    let amount_to_sell = current_balance.checked_sub(target_amount).ok_or(Error::Underflow)?;
+        /// Returns the asset rebalancing swaps trade against, if governance has set one.
+        #[ink(message)]
+        pub fn get_quote_asset(&self) -> Option<AccountId> {
+            self.quote_asset.get()
+        }
 
-                    //TODO: swap the asset to synthetic asset
-                    self.swap_asset_to_synthetic(asset_id, amount_to_sell)?;
+        /// Updates the AMM router used for rebalancing swaps. Permissioned.
+        #[ink(message)]
+        pub fn update_router(&mut self, new_router: AccountId) -> Result<(), Error> {
+            self.ensure_governance()?;
+            self.router.set(&new_router);
+            Ok(())
+        }
 
-                    self.asset_balances.insert(asset_id, target_amount);
-                } else if current_balance < target_amount {
-                    // Buy asset to increase balance to target.
-                    let amount_to_buy = target_amount.checked_sub(current_balance).ok_or(Error::Underflow)?;
+        /// Updates the maximum slippage, in basis points, tolerated by rebalancing swaps. Permissioned.
+        #[ink(message)]
+        pub fn update_max_slippage(&mut self, new_max_slippage_bps: u32) -> Result<(), Error> {
+            self.ensure_governance()?;
+            if new_max_slippage_bps as u128 >= BPS_DENOMINATOR {
+                return Err(Error::InvalidAmount);
+            }
+            self.max_slippage_bps.set(&new_max_slippage_bps);
+            Ok(())
+        }
 
-                    //TODO: swap the synthetic asset to target asset
-                    self.swap_synthetic_to_asset(asset_id, amount_to_buy)?;
+        /// Pauses minting, burning and rebalancing. Withdrawing staked LP tokens is never paused. Permissioned.
+        #[ink(message)]
+        pub fn pause(&mut self) -> Result<(), Error> {
+            self.ensure_governance()?;
+            self.pause_all(false);
+            Ok(())
+        }
 
-                    self.asset_balances.insert(asset_id, target_amount);
-                }
+        /// Resumes minting, burning and rebalancing. Permissioned.
+        #[ink(message)]
+        pub fn unpause(&mut self) -> Result<(), Error> {
+            self.ensure_governance()?;
+            for function in [PausableFunction::Mint, PausableFunction::Burn, PausableFunction::Rebalance] {
+                self.set_function_paused(function, false, false);
             }
+            Ok(())
+        }
+
+        /// Pauses or resumes a single function. Permissioned.
+        #[ink(message)]
+        pub fn set_paused(&mut self, function: PausableFunction, paused: bool) -> Result<(), Error> {
+            self.ensure_governance()?;
+            self.set_function_paused(function, paused, false);
+            Ok(())
+        }
 
-            // Update the last rebalanced timestamp
-            self.last_rebalanced_timestamp = now;
+        /// Records the current oracle prices and, if any moved more than `max_price_deviation_bps` since the
+        /// previous block, pauses minting, burning and rebalancing. Callable by anyone, e.g. a keeper bot.
+        /// Returns whether the circuit breaker tripped.
+        #[ink(message)]
+        pub fn check_circuit_breaker(&mut self) -> Result<bool, Error> {
+            let tripped = self.observe_prices()?;
+            if tripped {
+                self.pause_all(true);
+            }
+            Ok(tripped)
+        }
 
+        /// Updates the block-to-block price move, in basis points, that trips the circuit breaker. Permissioned.
+        #[ink(message)]
+        pub fn update_max_price_deviation(&mut self, new_max_price_deviation_bps: u32) -> Result<(), Error> {
+            self.ensure_governance()?;
+            if new_max_price_deviation_bps == 0 {
+                return Err(Error::InvalidAmount);
+            }
+            self.max_price_deviation_bps.set(&new_max_price_deviation_bps);
             Ok(())
         }
 
-        /// Updates the oracle IDs used for price feeds.  Permissioned.
-        #[ink::message]
-        pub fn update_oracle_ids(&mut self, new_oracle_ids: Vec<AccountId>) -> Result<(), Error> {
+        /// Returns whether a function is paused.
+        #[ink(message)]
+        pub fn is_paused(&self, function: PausableFunction) -> bool {
+            self.paused_functions().is_paused(&function)
+        }
+
+        /// Updates the burn size, in shares, above which redemptions are queued, and the maximum shares settled from
+        /// the queue per rebalance. Permissioned.
+        #[ink(message)]
+        pub fn update_redemption_queue(&mut self, threshold: u128, settlement_limit: u128) -> Result<(), Error> {
             self.ensure_governance()?;
-            self.oracle_ids = new_oracle_ids;
+            if settlement_limit == 0 {
+                return Err(Error::InvalidAmount);
+            }
+            self.redemption_queue_threshold.set(&threshold);
+            self.redemption_settlement_limit.set(&settlement_limit);
             Ok(())
         }
 
         /// Updates the stability fee percentage. Permissioned.
-        #[ink::message]
+        #[ink(message)]
         pub fn update_stability_fee_percentage(&mut self, new_percentage: u32) -> Result<(), Error> {
             self.ensure_governance()?;
             self.stability_fee_percentage = new_percentage;
@@ -368,7 +975,7 @@ mod synthetic_asset_forge {
         }
 
         /// Update the weights for underlying assets. Permissioned.
-        #[ink::message]
+        #[ink(message)]
         pub fn update_weights(&mut self, new_weights: Vec<u32>) -> Result<(), Error> {
             self.ensure_governance()?;
 
@@ -376,25 +983,118 @@ mod synthetic_asset_forge {
                 return Err(Error::BasketValueMismatch);
             }
 
-            for i in 0..self.supported_assets.len() {
-                self.supported_assets[i].weight = new_weights[i];
+            let mut assets = self.supported_assets.clone();
+            for i in 0..assets.len() {
+                assets[i].weight = new_weights[i];
+            }
+            Self::validate_weights(&assets)?;
+            self.supported_assets = assets;
+            self.env().emit_event(BasketChanged { assets: self.supported_assets.clone() });
+
+            Ok(())
+        }
+
+        /// Adds an asset to the basket. The existing weights are scaled down proportionally to make room for the
+        /// new asset's weight; the next rebalance buys it in. Its price sources must be registered first. Permissioned.
+        #[ink(message)]
+        pub fn add_asset(&mut self, asset_info: AssetInfo) -> Result<(), Error> {
+            self.ensure_governance()?;
+
+            if self.supported_assets.iter().any(|asset| asset.asset_id == asset_info.asset_id) {
+                return Err(Error::AssetAlreadySupported);
+            }
+            if asset_info.weight == 0 || asset_info.weight > 100 {
+                return Err(Error::InvalidWeights);
+            }
+            if self.get_oracle_sources(asset_info.asset_id).is_empty() {
+                return Err(Error::OracleQueryFailed);
             }
 
+            let weights: Vec<u32> = self.supported_assets.iter().map(|asset| asset.weight).collect();
+            let scaled = Self::scale_weights(&weights, 100 - asset_info.weight);
+            let mut assets = self.supported_assets.clone();
+            for i in 0..assets.len() {
+                assets[i].weight = scaled[i];
+            }
+            assets.push(asset_info.clone());
+            Self::validate_weights(&assets)?;
+
+            self.supported_assets = assets;
+            self.env().emit_event(AssetAdded { asset_id: asset_info.asset_id, weight: asset_info.weight });
+            self.env().emit_event(BasketChanged { assets: self.supported_assets.clone() });
             Ok(())
         }
 
-        /// Returns the total value of synthetic asset, based on underlying basket.
-        #[ink::message]
+        /// Removes an asset from the basket: its weight is redistributed proportionally over the other assets and
+        /// a rebalance sells it down to zero, after which it leaves the basket. If rounding leaves a balance, the
+        /// asset stays at zero weight until the next rebalance. Permissioned.
+        #[ink(message)]
+        pub fn remove_asset(&mut self, asset_id: AccountId, deadline: u64) -> Result<(), Error> {
+            non_reentrant!(self.reentrancy_guard, {
+                self.ensure_governance()?;
+                self.ensure_not_paused(PausableFunction::Rebalance)?;
+
+                let now = self.env().block_timestamp();
+                if now > deadline {
+                    return Err(Error::DeadlineExpired);
+                }
+
+                let index = self
+                    .supported_assets
+                    .iter()
+                    .position(|asset| asset.asset_id == asset_id)
+                    .ok_or(Error::AssetNotSupported)?;
+                if self.supported_assets.len() == 1 {
+                    return Err(Error::InvalidWeights);
+                }
+
+                let weights: Vec<u32> = self
+                    .supported_assets
+                    .iter()
+                    .enumerate()
+                    .map(|(i, asset)| if i == index { 0 } else { asset.weight })
+                    .collect();
+                let scaled = Self::scale_weights(&weights, 100);
+                let mut assets = self.supported_assets.clone();
+                for i in 0..assets.len() {
+                    assets[i].weight = scaled[i];
+                }
+                Self::validate_weights(&assets)?;
+                self.supported_assets = assets;
+                self.env().emit_event(BasketChanged { assets: self.supported_assets.clone() });
+
+                // Wind the removed asset down to zero
+                if self.observe_prices()? {
+                    return Err(Error::PriceDeviationExceeded);
+                }
+                self.execute_rebalance(deadline)?;
+                self.last_rebalanced_timestamp = now;
+
+                Ok(())
+            })
+        }
+
+        /// Returns the assets in the basket and their weights.
+        #[ink(message)]
+        pub fn get_supported_assets(&self) -> Vec<AssetInfo> {
+            self.supported_assets.clone()
+        }
+
+        /// Returns the total value of synthetic asset, based on underlying basket and any quote asset held outside it.
+        #[ink(message)]
         pub fn get_synthetic_value(&self) -> Result<u128, Error> {
             let mut total_value: u128 = 0;
-            for i in 0..self.supported_assets.len() {
-                let asset_id = self.supported_assets[i].asset_id;
-
+            for asset_id in self.held_assets() {
                 let balance = self.asset_balances.get(&asset_id).unwrap_or(&0);
 
-                let price = self.get_price(i as u32)?;
+                // Quote asset outside the basket is only priced while some of it is held
+                if *balance == 0 && !self.supported_assets.iter().any(|asset| asset.asset_id == asset_id) {
+                    continue;
+                }
+
+                let price = self.get_price(asset_id)?;
 
-                let asset_value = balance.checked_mul(price as u128).ok_or(Error::Overflow)?;
+                let asset_value = balance.checked_mul(price).ok_or(Error::Overflow)?;
 
                 total_value = total_value.checked_add(asset_value).ok_or(Error::Overflow)?;
             }
@@ -403,132 +1103,638 @@ mod synthetic_asset_forge {
         }
 
         /// Returns the amount of certain asset holding in smart contract
-        #[ink::message]
+        #[ink(message)]
         pub fn get_asset_balance(&self, asset_id: AccountId) -> u128 {
             *self.asset_balances.get(&asset_id).unwrap_or(&0)
         }
 
         /// Returns the total supply of synthetic asset minted.
-        #[ink::message]
+        #[ink(message)]
         pub fn get_synthetic_supply(&self) -> u128 {
             self.total_synthetic_supply
         }
 
-        /// Returns the staking info for the account.
-        #[ink::message]
-        pub fn get_staking_info(&self, account: AccountId) -> Option<StakingInfo> {
-            self.staking_info.get(&account).cloned()
+        /// Returns the time-weighted average oracle price of an asset over the TWAP window.
+        #[ink(message)]
+        pub fn get_twap(&self, asset_id: AccountId) -> Result<u128, Error> {
+            let observations = self.twap_observations.get(asset_id).ok_or(Error::OracleQueryFailed)?;
+            let now = self.env().block_timestamp();
+            Self::time_weighted_average(&observations, now.saturating_sub(self.twap_window()), now).ok_or(Error::InsufficientObservations)
         }
 
-        /// Get asset current price
-        fn get_price(&self, index: u32) -> Result<u32, Error> {
-            let key = self.oracle_ids.get(index as usize).ok_or(Error::OracleQueryFailed)?;
-
-            let key_bytes = key.encode();
-            let mut output: [u8; 4] = [0; 4];
-
-            ink_env::chain_extension::ChainExtensionMethod::build(1101)
-                .input::<AccountId>(&key)
-                .output::<[u8; 4]>(&mut output)
-                .call()
-                .map_err(|_| Error::OracleQueryFailed)?;
-
-            let price = u32::from_be_bytes(output);
-
-            Ok(price)
+        /// Updates the period the TWAP averages over. Permissioned.
+        #[ink(message)]
+        pub fn update_twap_window(&mut self, new_twap_window: u64) -> Result<(), Error> {
+            self.ensure_governance()?;
+            if new_twap_window == 0 {
+                return Err(Error::InvalidAmount);
+            }
+            self.twap_window.set(&new_twap_window);
+            Ok(())
         }
 
-        /// Mint synthetic asset
-        fn mint(&mut self, receiver: AccountId, amount: u128) -> Result<(), Error>{
-            let code_hash = self.env().hash_name("Erc20").into();
-            ink_env::call::build_call::<Environment>()
-                .call_type(ink_env::call::DelegateCall::new().code_hash(*code_hash))
-                .call_flags(ink_env::call::CallFlag::default())
-                .callee(self.synthetic_asset_id)
-                .transferred_value(0)
-                .gas_limit(5000000000)
-                .exec_input(
-                    ink_env::call::ExecutionInput::new(ink_env::selector_bytes!("mint"))
-                        .push_arg(receiver)
-                        .push_arg(amount),
-                )
-                .returns::<Result<(), Error>>()
-                .invoke()
+        /// Returns the price sources registered for an asset.
+        #[ink(message)]
+        pub fn get_oracle_sources(&self, asset_id: AccountId) -> Vec<OracleSource> {
+            self.oracle_sources.get(asset_id).unwrap_or_default()
         }
 
-        /// Burn synthetic asset
-        fn burn(&mut self, account: AccountId, amount: u128) -> Result<(), Error>{
-            let code_hash = self.env().hash_name("Erc20").into();
-            ink_env::call::build_call::<Environment>()
-                .call_type(ink_env::call::DelegateCall::new().code_hash(*code_hash))
-                .call_flags(ink_env::call::CallFlag::default())
-                .callee(self.synthetic_asset_id)
-                .transferred_value(0)
-                .gas_limit(5000000000)
-                .exec_input(
-                    ink_env::call::ExecutionInput::new(ink_env::selector_bytes!("burn"))
-                        .push_arg(account)
-                        .push_arg(amount),
-                )
-                .returns::<Result<(), Error>>()
-                .invoke()
+        /// Returns the staking info for the account.
+        #[ink(message)]
+        pub fn get_staking_info(&self, account: AccountId) -> Option<StakingInfo> {
+            self.staking_info.get(account)
         }
 
-        /// Transfer asset
-        fn transfer(&mut self, receiver: AccountId, asset_id: AccountId, amount: u128) -> Result<(), Error>{
-            let code_hash = self.env().hash_name("Erc20").into();
-            ink_env::call::build_call::<Environment>()
-                .call_type(ink_env::call::DelegateCall::new().code_hash(*code_hash))
-                .call_flags(ink_env::call::CallFlag::default())
-                .callee(asset_id)
-                .transferred_value(0)
-                .gas_limit(5000000000)
-                .exec_input(
-                    ink_env::call::ExecutionInput::new(ink_env::selector_bytes!("transfer"))
-                        .push_arg(receiver)
-                        .push_arg(amount),
-                )
-                .returns::<Result<(), Error>>()
-                .invoke()
-        }
-
-        /// Transfer from asset
-        fn transfer_from(&mut self, from: AccountId, to: AccountId, asset_id: AccountId, amount: u128) -> Result<(), Error>{
-            let code_hash = self.env().hash_name("Erc20").into();
-            ink_env::call::build_call::<Environment>()
-                .call_type(ink_env::call::DelegateCall::new().code_hash(*code_hash))
-                .call_flags(ink_env::call::CallFlag::default())
-                .callee(asset_id)
-                .transferred_value(0)
-                .gas_limit(5000000000)
-                .exec_input(
-                    ink_env::call::ExecutionInput::new(ink_env::selector_bytes!("transferFrom"))
-                        .push_arg(from)
-                        .push_arg(to)
-                        .push_arg(amount),
-                )
-                .returns::<Result<(), Error>>()
-                .invoke()
+        /// Replaces the code of this contract with `new_code_hash`, which must already be uploaded to the chain.
+        /// Storage and the contract address are kept; if the new code changes the layout, governance calls
+        /// `migrate` on it next. Permissioned.
+        #[ink(message)]
+        pub fn upgrade_code(&mut self, new_code_hash: Hash) -> Result<(), Error> {
+            self.ensure_governance()?;
+            self.env().set_code_hash(&new_code_hash)?;
+            self.env().emit_event(CodeUpgraded { code_hash: new_code_hash });
+            Ok(())
         }
 
-        //Swap asset to synthetic asset
-        fn swap_asset_to_synthetic(&mut self, asset_id: AccountId, amount: u128) -> Result<(), Error>{
-            //TODO: integration with DEX, such as uniswap
+        /// Runs every migration step between the stored storage version and `STORAGE_VERSION`. Permissioned.
+        #[ink(message)]
+        pub fn migrate(&mut self) -> Result<(), Error> {
+            self.ensure_governance()?;
+            let from_version = self.storage_version.begin_migration(STORAGE_VERSION)?;
+
+            // Later layout changes add their step here, e.g. `if from_version < 2 { ... }`, so a contract several
+            // versions behind runs them all in order.
+            if from_version < 1 {
+                self.migrate_legacy_storage()?;
+            }
+
+            self.storage_version.set(STORAGE_VERSION);
+            self.env().emit_event(StorageMigrated { from_version, to_version: STORAGE_VERSION });
+            Ok(())
+        }
+
+        /// Migration to version 1. Code before versioning priced assets through a chain extension keyed by
+        /// `legacy_oracle_ids` and paid stakers a fixed reward per unit of time from `legacy_time_staking_reward`.
+        /// The keys are not oracle contracts, so they are dropped and governance registers price sources with
+        /// `add_oracle_source`. Stakes in the LP token governance set before migrating join the fee pool and earn
+        /// from here on; stakes in any other token stay behind for `withdraw_legacy_stake`. Time rewards accrued
+        /// under the old scheme were never funded and are not carried over.
+        fn migrate_legacy_storage(&mut self) -> Result<(), Error> {
+            let lp_token = self.lp_token.get();
+            let legacy_stakes = core::mem::take(&mut self.legacy_staking_info);
+            let mut total_staked = self.total_staked();
+            for (account, legacy) in legacy_stakes {
+                if Some(legacy.lp_token_id) != lp_token {
+                    self.legacy_staking_info.insert(account, legacy);
+                    continue;
+                }
+                let staking_info = StakingInfo {
+                    lp_token_id: legacy.lp_token_id,
+                    amount_staked: legacy.amount_staked,
+                    reward_debt: rewards::reward_debt(legacy.amount_staked, self.acc_reward_per_share())?,
+                    pending_rewards: 0,
+                };
+                total_staked = total_staked.checked_add(legacy.amount_staked).ok_or(Error::Overflow)?;
+                self.staking_info.insert(account, &staking_info);
+            }
+            self.total_staked.set(&total_staked);
+            self.legacy_oracle_ids = Vec::new();
+            self.legacy_time_staking_reward = 0;
+            Ok(())
+        }
+
+        /// Returns the version of the storage layout.
+        #[ink(message)]
+        pub fn get_storage_version(&self) -> u16 {
+            self.storage_version.get()
+        }
+
+        /// Get asset current price: the median of the fresh readings from its registered sources.
+        /// Sources that fail, have no reading or are older than their max age are skipped.
+        fn get_price(&self, asset_id: AccountId) -> Result<u128, Error> {
+            let sources = self.oracle_sources.get(&asset_id).ok_or(Error::OracleQueryFailed)?;
+            let now = self.env().block_timestamp();
+
+            let mut prices: Vec<u128> = Vec::new();
+            for source in sources.iter() {
+                let oracle: ink::contract_ref!(PriceOracle) = source.oracle.into();
+                let reading = match oracle.call().latest_price(asset_id).try_invoke() {
+                    Ok(Ok(Some(reading))) => reading,
+                    _ => continue,
+                };
+                let (price, updated_at) = reading;
+                if price > 0 && now.saturating_sub(updated_at) <= source.max_age {
+                    prices.push(price);
+                }
+            }
+
+            Self::median(prices).ok_or(Error::StalePrice)
+        }
+
+        /// Median of a set of prices; the mean of the two middle prices for an even count.
+        fn median(mut prices: Vec<u128>) -> Option<u128> {
+            if prices.is_empty() {
+                return None;
+            }
+            prices.sort_unstable();
+            let mid = prices.len() / 2;
+            if prices.len() % 2 == 0 {
+                Some(prices[mid - 1] / 2 + prices[mid] / 2 + (prices[mid - 1] % 2 + prices[mid] % 2) / 2)
+            } else {
+                Some(prices[mid])
+            }
+        }
+
+        /// Mint synthetic asset
+        fn mint(&mut self, receiver: AccountId, amount: u128) -> Result<(), Error>{
+            psp22::mint(self.synthetic_asset_id, receiver, amount)?;
+            Ok(())
+        }
+
+        /// Burn synthetic asset
+        fn burn(&mut self, account: AccountId, amount: u128) -> Result<(), Error>{
+            psp22::burn(self.synthetic_asset_id, account, amount)?;
+            Ok(())
+        }
+
+        /// Transfer asset
+        fn transfer(&mut self, receiver: AccountId, asset_id: AccountId, amount: u128) -> Result<(), Error>{
+            psp22::transfer(asset_id, receiver, amount)?;
+            Ok(())
+        }
+
+        /// Transfer from asset, failing early if `from` has not approved enough or holds too little
+        fn transfer_from(&mut self, from: AccountId, to: AccountId, asset_id: AccountId, amount: u128) -> Result<(), Error>{
+            psp22::transfer_from(asset_id, from, to, amount)?;
+            Ok(())
+        }
+
+        /// Approve the router to spend asset
+        fn approve_router(&mut self, asset_id: AccountId, amount: u128) -> Result<(), Error>{
+            let router = self.router.get().ok_or(Error::RouterNotSet)?;
+            psp22::approve(asset_id, router, amount)?;
+            Ok(())
+        }
+
+        /// Swap exactly `amount_in` of `asset_in` for at least its oracle value in `asset_out`, less the slippage
+        /// limit. Only `amount_in` is approved, so the router can never reach shares or other balances held by the
+        /// contract. Returns the amount of `asset_out` received.
+        fn swap_at_oracle_price(
+            &mut self,
+            asset_in: AccountId,
+            asset_out: AccountId,
+            amount_in: u128,
+            price_in: u128,
+            price_out: u128,
+            deadline: u64,
+        ) -> Result<u128, Error> {
+            let expected_out = amount_in
+                .checked_mul(price_in)
+                .ok_or(Error::Overflow)?
+                .checked_div(price_out)
+                .ok_or(Error::OracleQueryFailed)?;
+            let amount_out_min = expected_out
+                .checked_mul(BPS_DENOMINATOR - self.max_slippage_bps() as u128)
+                .ok_or(Error::Overflow)?
+                / BPS_DENOMINATOR;
+
+            self.approve_router(asset_in, amount_in)?;
+            let mut router: ink::contract_ref!(AmmRouter) = self.router.get().ok_or(Error::RouterNotSet)?.into();
+            let amounts = router
+                .call_mut()
+                .swap_exact_tokens_for_tokens(amount_in, amount_out_min, vec![asset_in, asset_out], self.env().account_id(), deadline)
+                .try_invoke()?
+                .map_err(|_| Error::CrossContractCallFailed)?
+                .map_err(|_| Error::SwapFailed)?;
+
+            let amount_out = *amounts.last().ok_or(Error::SwapFailed)?;
+            if amount_out < amount_out_min {
+                return Err(Error::SlippageExceeded);
+            }
+
+            self.env().emit_event(SwapExecuted {
+                asset_in,
+                asset_out,
+                amount_in,
+                amount_out,
+            });
+
+            Ok(amount_out)
+        }
+
+        /// Transfers the fraction `shares / total_supply` of every held asset balance to `receiver`.
+        fn redeem_assets(&mut self, receiver: AccountId, shares: u128, total_supply: u128) -> Result<(), Error> {
+            for asset_id in self.held_assets() {
+                let balance = *self.asset_balances.get(&asset_id).unwrap_or(&0);
+                let asset_redeem_amount = vault::redemption_amount(balance, shares, total_supply)?;
+                if asset_redeem_amount == 0 {
+                    continue;
+                }
+
+                //Transfer asset to receiver
+                self.transfer(receiver, asset_id, asset_redeem_amount)?;
+
+                //Update asset balance
+                self.asset_balances.insert(asset_id, balance.checked_sub(asset_redeem_amount).ok_or(Error::Underflow)?);
+            }
+            Ok(())
+        }
+
+        /// Settles the redemption queue in order, up to `redemption_settlement_limit` shares. The last redemption
+        /// reached may be filled partially and keeps its place at the front of the queue.
+        fn settle_queue(&mut self) -> Result<(), Error> {
+            self.last_settlement_timestamp.set(&self.env().block_timestamp());
+            let mut queue = self.redemption_queue();
+            let queued: Vec<u128> = queue
+                .iter()
+                .map(|id| self.redemptions.get(id).map(|redemption| redemption.shares).unwrap_or(0))
+                .collect();
+            let fills = Self::plan_fills(&queued, self.redemption_settlement_limit());
+
+            let mut settled = 0;
+            for (i, fill) in fills.iter().enumerate() {
+                if *fill == 0 {
+                    break;
+                }
+                let redemption_id = queue[i];
+                let mut redemption = self.redemptions.get(redemption_id).ok_or(Error::RedemptionNotFound)?;
+
+                // Burn the escrowed shares and pay out their fraction of the rebalanced basket
+                let total_supply = self.total_synthetic_supply;
+                self.burn(self.env().account_id(), *fill)?;
+                self.total_synthetic_supply = total_supply.checked_sub(*fill).ok_or(Error::Underflow)?;
+                self.redeem_assets(redemption.receiver, *fill, total_supply)?;
+
+                redemption.shares = redemption.shares.checked_sub(*fill).ok_or(Error::Underflow)?;
+                redemption.filled = redemption.filled.checked_add(*fill).ok_or(Error::Overflow)?;
+                self.env().emit_event(RedemptionFilled { redemption_id, shares: *fill, remaining: redemption.shares });
+                if redemption.shares == 0 {
+                    self.redemptions.remove(redemption_id);
+                    settled += 1;
+                } else {
+                    self.redemptions.insert(redemption_id, &redemption);
+                }
+            }
+            queue.drain(..settled);
+            self.redemption_queue.set(&queue);
+            Ok(())
+        }
+
+        /// Shares filled for each queued redemption, oldest first, when at most `limit` shares are settled.
+        fn plan_fills(queued: &[u128], limit: u128) -> Vec<u128> {
+            let mut budget = limit;
+            queued
+                .iter()
+                .map(|shares| {
+                    let fill = (*shares).min(budget);
+                    budget -= fill;
+                    fill
+                })
+                .collect()
+        }
+
+        /// Records a collected fee for stakers and distributes it over the current stake.
+        fn collect_fee(&mut self, fee: u128) -> Result<(), Error> {
+            self.transaction_fees_collected = self.transaction_fees_collected.checked_add(fee).ok_or(Error::Overflow)?;
+            self.undistributed_fees.set(&self.undistributed_fees().checked_add(fee).ok_or(Error::Overflow)?);
+            self.update_pool()
+        }
+
+        /// Folds undistributed fees into `acc_reward_per_share`. Fees wait while nothing is staked.
+        fn update_pool(&mut self) -> Result<(), Error> {
+            if self.total_staked() == 0 || self.undistributed_fees() == 0 {
+                return Ok(());
+            }
+            // Keep the rounding remainder for the next distribution
+            let (increment, remainder) = rewards::distribute(self.undistributed_fees(), self.total_staked())?;
+            self.acc_reward_per_share.set(&self.acc_reward_per_share().checked_add(increment).ok_or(Error::Overflow)?);
+            self.undistributed_fees.set(&remainder);
+            Ok(())
+        }
+
+        /// Moves the rewards earned since the account's last checkpoint into its pending rewards. The caller must
+        /// reset `reward_debt` after changing `amount_staked`.
+        fn checkpoint(&self, staking_info: &mut StakingInfo) -> Result<(), Error> {
+            let earned = rewards::pending_reward(staking_info.amount_staked, self.acc_reward_per_share(), staking_info.reward_debt)?;
+            staking_info.pending_rewards = staking_info.pending_rewards.checked_add(earned).ok_or(Error::Overflow)?;
+            staking_info.reward_debt = rewards::reward_debt(staking_info.amount_staked, self.acc_reward_per_share())?;
             Ok(())
         }
 
-        //Swap synthetic asset to asset
-        fn swap_synthetic_to_asset(&mut self, asset_id: AccountId, amount: u128) -> Result<(), Error>{
-            //TODO: integration with DEX, such as uniswap
+        /// Every asset whose balance backs the shares: the basket assets, plus the quote asset if it is not one of them.
+        fn held_assets(&self) -> Vec<AccountId> {
+            let mut assets: Vec<AccountId> = self.supported_assets.iter().map(|asset| asset.asset_id).collect();
+            if let Some(quote_asset) = self.quote_asset.get() {
+                if !assets.contains(&quote_asset) {
+                    assets.push(quote_asset);
+                }
+            }
+            assets
+        }
+
+        /// Swap the basket towards its target weights at current oracle prices, drop removed assets that have been
+        /// wound down to zero, then settle queued redemptions against the rebalanced basket.
+        fn execute_rebalance(&mut self, deadline: u64) -> Result<(), Error> {
+            let quote_asset = self.quote_asset.get().ok_or(Error::QuoteAssetNotSet)?;
+            let quote_price = self.get_price(quote_asset)?;
+
+            // Fetch current prices from oracles.
+            let mut current_prices: Vec<u128> = Vec::new();
+            for i in 0..self.supported_assets.len() {
+                let price = self.get_price(self.supported_assets[i].asset_id)?;
+                current_prices.push(price);
+            }
+
+            // Calculate total value of each asset.
+            let mut current_values: Vec<u128> = Vec::new();
+            for i in 0..self.supported_assets.len() {
+                let asset_id = self.supported_assets.get(i).ok_or(Error::AssetNotSupported)?.asset_id;
+                let balance = self.asset_balances.get(&asset_id).unwrap_or(&0);
+                let value = balance.checked_mul(current_prices[i]).ok_or(Error::Overflow)?;
+                current_values.push(value);
+            }
+
+            //Calculate the target total value
+            let total_target_value = self.get_synthetic_value()?;
+
+            // Calculate the desired asset allocation based on current prices and weights.
+            let target_asset_amounts = Self::target_amounts(total_target_value, &self.supported_assets, &current_prices)?;
+
+            // Rebalance the basket. Overweight assets are sold for the quote asset first so the proceeds can fund
+            // the purchases of underweight assets. A quote asset that is also in the basket is never swapped; its
+            // balance simply moves as the others are sold and bought.
+            for i in 0..self.supported_assets.len() {
+                let asset_id = self.supported_assets[i].asset_id;
+                let current_balance = *self.asset_balances.get(&asset_id).unwrap_or(&0);
+                let target_amount = target_asset_amounts[i];
+
+                if asset_id != quote_asset && current_balance > target_amount {
+                    // Sell asset to reduce balance to target.
+                    let amount_to_sell = current_balance.checked_sub(target_amount).ok_or(Error::Underflow)?;
+
+                    let amount_out = self.swap_at_oracle_price(asset_id, quote_asset, amount_to_sell, current_prices[i], quote_price, deadline)?;
+
+                    self.asset_balances.insert(asset_id, target_amount);
+                    let quote_balance = *self.asset_balances.get(&quote_asset).unwrap_or(&0);
+                    self.asset_balances.insert(quote_asset, quote_balance.checked_add(amount_out).ok_or(Error::Overflow)?);
+                }
+            }
+            for i in 0..self.supported_assets.len() {
+                let asset_id = self.supported_assets[i].asset_id;
+                let current_balance = *self.asset_balances.get(&asset_id).unwrap_or(&0);
+                let target_amount = target_asset_amounts[i];
+
+                if asset_id != quote_asset && current_balance < target_amount {
+                    // Buy asset to increase balance to target, spending its oracle value in quote asset, or whatever
+                    // quote asset is left if slippage on the sales fell short.
+                    let amount_to_buy = target_amount.checked_sub(current_balance).ok_or(Error::Underflow)?;
+                    let quote_balance = *self.asset_balances.get(&quote_asset).unwrap_or(&0);
+                    let quote_needed = amount_to_buy
+                        .checked_mul(current_prices[i])
+                        .ok_or(Error::Overflow)?
+                        .checked_div(quote_price)
+                        .ok_or(Error::OracleQueryFailed)?;
+                    let amount_in = quote_needed.min(quote_balance);
+                    if amount_in == 0 {
+                        continue;
+                    }
+
+                    let amount_out = self.swap_at_oracle_price(quote_asset, asset_id, amount_in, quote_price, current_prices[i], deadline)?;
+
+                    self.asset_balances.insert(asset_id, current_balance.checked_add(amount_out).ok_or(Error::Overflow)?);
+                    self.asset_balances.insert(quote_asset, quote_balance.checked_sub(amount_in).ok_or(Error::InsufficientBalance)?);
+                }
+            }
+
+            // Measure the next trigger from the prices this rebalance traded at
+            for i in 0..self.supported_assets.len() {
+                let asset_id = self.supported_assets[i].asset_id;
+                let twap = self.get_twap(asset_id)?;
+                self.rebalance_reference_prices.insert(asset_id, &twap);
+            }
+
+            // Drop removed assets once they have been fully sold
+            let mut i = 0;
+            while i < self.supported_assets.len() {
+                let asset_id = self.supported_assets[i].asset_id;
+                if self.supported_assets[i].weight == 0 && *self.asset_balances.get(&asset_id).unwrap_or(&0) == 0 {
+                    self.supported_assets.remove(i);
+                    self.env().emit_event(AssetRemoved { asset_id });
+                } else {
+                    i += 1;
+                }
+            }
+
+            self.settle_queue()
+        }
+
+        /// Amount of each asset that makes up its weight of `total_value` at the given prices, rounded down.
+        fn target_amounts(total_value: u128, assets: &[AssetInfo], prices: &[u128]) -> Result<Vec<u128>, Error> {
+            let mut amounts: Vec<u128> = Vec::new();
+            for i in 0..assets.len() {
+                let target_value = total_value
+                    .checked_mul(assets[i].weight as u128)
+                    .ok_or(Error::Overflow)?
+                    .checked_div(100)
+                    .ok_or(Error::Underflow)?;
+                let price = *prices.get(i).ok_or(Error::BasketValueMismatch)?;
+                amounts.push(target_value.checked_div(price).ok_or(Error::OracleQueryFailed)?);
+            }
+            Ok(amounts)
+        }
+
+        /// Scale weights proportionally so they sum to `target_total`. The rounding remainder goes to the largest weight.
+        fn scale_weights(weights: &[u32], target_total: u32) -> Vec<u32> {
+            let total: u64 = weights.iter().map(|weight| *weight as u64).sum();
+            if total == 0 {
+                return weights.to_vec();
+            }
+            let mut scaled: Vec<u32> = weights
+                .iter()
+                .map(|weight| (*weight as u64 * target_total as u64 / total) as u32)
+                .collect();
+            let assigned: u32 = scaled.iter().sum();
+            if let Some(largest) = (0..weights.len()).max_by_key(|i| weights[*i]) {
+                scaled[largest] += target_total - assigned;
+            }
+            scaled
+        }
+
+        /// Check that the basket weights sum to 100
+        fn validate_weights(assets: &[AssetInfo]) -> Result<(), Error> {
+            let total: u64 = assets.iter().map(|asset| asset.weight as u64).sum();
+            if total != 100 {
+                return Err(Error::InvalidWeights);
+            }
             Ok(())
         }
 
+        /// LP tokens currently staked.
+        fn total_staked(&self) -> u128 {
+            self.total_staked.get().unwrap_or(0)
+        }
+
+        /// Fees distributed per staked LP token so far, scaled by `rewards::REWARD_PRECISION`.
+        fn acc_reward_per_share(&self) -> u128 {
+            self.acc_reward_per_share.get().unwrap_or(0)
+        }
+
+        /// Fees waiting for the next distribution.
+        fn undistributed_fees(&self) -> u128 {
+            self.undistributed_fees.get().unwrap_or(0)
+        }
+
+        /// Maximum slippage tolerated by rebalancing swaps, in basis points.
+        fn max_slippage_bps(&self) -> u32 {
+            self.max_slippage_bps.get().unwrap_or(DEFAULT_MAX_SLIPPAGE_BPS)
+        }
+
+        /// Block-to-block price move that trips the circuit breaker, in basis points.
+        fn max_price_deviation_bps(&self) -> u32 {
+            self.max_price_deviation_bps.get().unwrap_or(DEFAULT_MAX_PRICE_DEVIATION_BPS)
+        }
+
+        /// Period the TWAP averages over.
+        fn twap_window(&self) -> u64 {
+            self.twap_window.get().unwrap_or(DEFAULT_TWAP_WINDOW)
+        }
+
+        /// Burn size, in shares, above which redemptions are queued. Unset means nothing is queued.
+        fn redemption_queue_threshold(&self) -> u128 {
+            self.redemption_queue_threshold.get().unwrap_or(u128::MAX)
+        }
+
+        /// Maximum shares settled from the queue at once. Unset means no limit.
+        fn redemption_settlement_limit(&self) -> u128 {
+            self.redemption_settlement_limit.get().unwrap_or(u128::MAX)
+        }
+
+        /// Unsettled redemption ids, oldest first.
+        fn redemption_queue(&self) -> Vec<u64> {
+            self.redemption_queue.get().unwrap_or_default()
+        }
+
+        /// Functions currently paused.
+        fn paused_functions(&self) -> Pausable<PausableFunction> {
+            self.paused_functions.get().unwrap_or_default()
+        }
+
+        /// Check that a function is not paused
+        fn ensure_not_paused(&self, function: PausableFunction) -> Result<(), Error> {
+            self.paused_functions().ensure_not_paused(&function)?;
+            Ok(())
+        }
+
+        /// Pause every pausable function
+        fn pause_all(&mut self, by_circuit_breaker: bool) {
+            for function in [PausableFunction::Mint, PausableFunction::Burn, PausableFunction::Rebalance] {
+                self.set_function_paused(function, true, by_circuit_breaker);
+            }
+        }
+
+        /// Pause or resume a function, emitting an event if its state changes
+        fn set_function_paused(&mut self, function: PausableFunction, paused: bool, by_circuit_breaker: bool) {
+            let mut paused_functions = self.paused_functions();
+            if !paused_functions.set_paused(function, paused) {
+                return;
+            }
+            if paused {
+                self.env().emit_event(Paused { function, by_circuit_breaker });
+            } else {
+                self.env().emit_event(Unpaused { function });
+            }
+            self.paused_functions.set(&paused_functions);
+        }
+
+        /// Records the current price of every supported asset. Returns whether any price moved more than
+        /// `max_price_deviation_bps` from an observation made in the previous block or earlier in this one.
+        fn observe_prices(&mut self) -> Result<bool, Error> {
+            let block = self.env().block_number();
+            let mut deviated = false;
+            for i in 0..self.supported_assets.len() {
+                let asset_id = self.supported_assets[i].asset_id;
+                let price = self.get_price(asset_id)?;
+                if let Some(previous) = self.price_observations.get(&asset_id) {
+                    if block.saturating_sub(previous.block) <= 1
+                        && Self::deviation_bps(previous.price, price) > self.max_price_deviation_bps() as u128
+                    {
+                        deviated = true;
+                    }
+                }
+                self.price_observations.insert(asset_id, &PriceObservation { price, block });
+                self.record_twap_observation(asset_id, price);
+            }
+            Ok(deviated)
+        }
+
+        /// Append a TWAP observation, replacing one from the same timestamp and dropping the oldest beyond the limit.
+        fn record_twap_observation(&mut self, asset_id: AccountId, price: u128) {
+            let timestamp = self.env().block_timestamp();
+            let mut observations = self.twap_observations.get(asset_id).unwrap_or_default();
+            if observations.last().map(|last| last.timestamp) == Some(timestamp) {
+                observations.pop();
+            }
+            observations.push(TwapObservation { price, timestamp });
+            if observations.len() > MAX_TWAP_OBSERVATIONS {
+                observations.remove(0);
+            }
+            self.twap_observations.insert(asset_id, &observations);
+        }
+
+        /// Whether any asset's TWAP moved more than `rebalancing_threshold` percent since the last rebalance.
+        /// Assets that have not been rebalanced yet always qualify.
+        fn twap_moved_past_threshold(&self) -> Result<bool, Error> {
+            let threshold_bps = (self.rebalancing_threshold as u128).saturating_mul(100);
+            for i in 0..self.supported_assets.len() {
+                let asset_id = self.supported_assets[i].asset_id;
+                let reference = match self.rebalance_reference_prices.get(asset_id) {
+                    Some(reference) => reference,
+                    None => return Ok(true),
+                };
+                if Self::deviation_bps(reference, self.get_twap(asset_id)?) > threshold_bps {
+                    return Ok(true);
+                }
+            }
+            Ok(false)
+        }
+
+        /// Time-weighted average of `observations` (oldest first) over `[window_start, now]`. Each price holds from its
+        /// timestamp until the next observation, capped at `1 / MIN_TWAP_OBSERVATIONS` of the window; the last
+        /// observation before the window covers its start. Returns `None` unless at least `MIN_TWAP_OBSERVATIONS`
+        /// observations fall within the window.
+        fn time_weighted_average(observations: &[TwapObservation], window_start: u64, now: u64) -> Option<u128> {
+            let max_duration = (now.saturating_sub(window_start) / MIN_TWAP_OBSERVATIONS as u64) as u128;
+            let mut weighted_sum: u128 = 0;
+            let mut total_time: u128 = 0;
+            let mut counted = 0;
+            for (i, observation) in observations.iter().enumerate() {
+                let start = observation.timestamp.max(window_start);
+                let end = observations.get(i + 1).map(|next| next.timestamp).unwrap_or(now).min(now);
+                if end > start {
+                    let duration = ((end - start) as u128).min(max_duration);
+                    weighted_sum = weighted_sum.saturating_add(observation.price.saturating_mul(duration));
+                    total_time += duration;
+                    counted += 1;
+                }
+            }
+            if counted < MIN_TWAP_OBSERVATIONS || total_time == 0 {
+                return None;
+            }
+            Some(weighted_sum / total_time)
+        }
+
+        /// Relative move from `previous` to `current`, in basis points of `previous`.
+        fn deviation_bps(previous: u128, current: u128) -> u128 {
+            if previous == 0 {
+                return u128::MAX;
+            }
+            let difference = if current > previous { current - previous } else { previous - current };
+            difference.saturating_mul(BPS_DENOMINATOR) / previous
+        }
+
         /// Check governance permissions
         fn ensure_governance(&self) -> Result<(), Error> {
-            let caller = self.env().caller();
-            if caller != self.governance_contract {
-                return Err(Error::Unauthorized);
-            }
+            self.governance_contract.ensure_owner(self.env().caller())?;
             Ok(())
         }
     }
@@ -538,37 +1744,735 @@ mod synthetic_asset_forge {
     mod tests {
         /// Imports all the definitions from the outer scope so we can use them here.
         use super::*;
-        use ink_lang as ink;
-        use ink_env::test;
+        use ink::env::test;
+
+        fn asset(asset_id: AccountId, weight: u32) -> AssetInfo {
+            AssetInfo { asset_id, weight }
+        }
+
+        /// Deploys a forge governed by Bob with a 60/40 basket of Charlie's and Django's tokens, Eve's synthetic
+        /// asset and Frank's router, and makes Bob the caller.
+        fn forge() -> SyntheticAssetForge {
+            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>();
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            SyntheticAssetForge::new(
+                accounts.alice,
+                accounts.bob,
+                accounts.eve,
+                vec![asset(accounts.charlie, 60), asset(accounts.django, 40)],
+                2,
+                5,
+                0,
+                accounts.frank,
+            )
+        }
+
+        /// The constructor stores the basket and starts with nothing minted, staked or paused.
+        #[ink::test]
+        fn new_works() {
+            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>();
+            let forge = forge();
+            assert_eq!(forge.get_supported_assets(), vec![asset(accounts.charlie, 60), asset(accounts.django, 40)]);
+            assert_eq!(forge.get_synthetic_supply(), 0);
+            assert_eq!(forge.get_asset_balance(accounts.charlie), 0);
+            assert_eq!(forge.get_staking_info(accounts.alice), None);
+            assert_eq!(forge.pending_stability_fees(accounts.alice), 0);
+            assert_eq!(forge.get_oracle_sources(accounts.charlie), Vec::new());
+            assert!(!forge.is_paused(PausableFunction::Mint));
+            assert!(!forge.is_paused(PausableFunction::Burn));
+            assert!(!forge.is_paused(PausableFunction::Rebalance));
+            assert_eq!(forge.get_storage_version(), STORAGE_VERSION);
+            assert_eq!(forge.get_lp_token(), None);
+        }
+
+        /// Every permissioned message rejects callers other than the governance contract, including the owner.
+        #[ink::test]
+        fn governance_guards_permissioned_messages() {
+            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut forge = forge();
+            for caller in [accounts.alice, accounts.charlie] {
+                test::set_caller::<ink::env::DefaultEnvironment>(caller);
+                assert_eq!(forge.rebalance_basket(u64::MAX), Err(Error::Unauthorized));
+                assert_eq!(forge.add_oracle_source(accounts.charlie, accounts.eve, 60_000), Err(Error::Unauthorized));
+                assert_eq!(forge.remove_oracle_source(accounts.charlie, accounts.eve), Err(Error::Unauthorized));
+                assert_eq!(forge.update_router(accounts.eve), Err(Error::Unauthorized));
+                assert_eq!(forge.update_max_slippage(50), Err(Error::Unauthorized));
+                assert_eq!(forge.pause(), Err(Error::Unauthorized));
+                assert_eq!(forge.unpause(), Err(Error::Unauthorized));
+                assert_eq!(forge.set_paused(PausableFunction::Mint, true), Err(Error::Unauthorized));
+                assert_eq!(forge.update_max_price_deviation(500), Err(Error::Unauthorized));
+                assert_eq!(forge.update_stability_fee_percentage(3), Err(Error::Unauthorized));
+                assert_eq!(forge.update_weights(vec![50, 50]), Err(Error::Unauthorized));
+                assert_eq!(forge.add_asset(asset(accounts.frank, 10)), Err(Error::Unauthorized));
+                assert_eq!(forge.remove_asset(accounts.charlie, u64::MAX), Err(Error::Unauthorized));
+                assert_eq!(forge.update_twap_window(60_000), Err(Error::Unauthorized));
+                assert_eq!(forge.update_redemption_queue(1_000, 500), Err(Error::Unauthorized));
+                assert_eq!(forge.upgrade_code(Hash::from([0x03; 32])), Err(Error::Unauthorized));
+                assert_eq!(forge.migrate(), Err(Error::Unauthorized));
+                assert_eq!(forge.set_lp_token(accounts.frank), Err(Error::Unauthorized));
+                assert_eq!(forge.set_quote_asset(accounts.frank), Err(Error::Unauthorized));
+            }
+            assert_eq!(forge.get_supported_assets(), vec![asset(accounts.charlie, 60), asset(accounts.django, 40)]);
+        }
+
+        /// Governance parameter updates are validated.
+        #[ink::test]
+        fn governance_updates_are_validated() {
+            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut forge = forge();
+            assert_eq!(forge.update_max_slippage(BPS_DENOMINATOR as u32), Err(Error::InvalidAmount));
+            assert_eq!(forge.update_max_slippage(50), Ok(()));
+            assert_eq!(forge.update_max_price_deviation(0), Err(Error::InvalidAmount));
+            assert_eq!(forge.update_twap_window(0), Err(Error::InvalidAmount));
+            assert_eq!(forge.update_redemption_queue(1_000, 0), Err(Error::InvalidAmount));
+            assert_eq!(forge.update_redemption_queue(1_000, 500), Ok(()));
+
+            assert_eq!(forge.add_oracle_source(accounts.charlie, accounts.eve, 0), Err(Error::InvalidAmount));
+            assert_eq!(forge.add_oracle_source(accounts.charlie, accounts.eve, 60_000), Ok(()));
+            assert_eq!(forge.add_oracle_source(accounts.charlie, accounts.eve, 60_000), Err(Error::OracleSourceExists));
+            assert_eq!(forge.get_oracle_sources(accounts.charlie), vec![OracleSource { oracle: accounts.eve, max_age: 60_000 }]);
+            assert_eq!(forge.remove_oracle_source(accounts.charlie, accounts.eve), Ok(()));
+            assert_eq!(forge.remove_oracle_source(accounts.charlie, accounts.eve), Err(Error::OracleQueryFailed));
+        }
 
-        /// We test if the default constructor does its job.
+        /// The quote asset needs a price source, joins the held assets once outside the basket, and cannot be switched
+        /// away from while a balance of it outside the basket is held.
         #[ink::test]
-        fn default_works() {
-            //TODO: write unit test
+        fn quote_asset_is_counted_and_guarded() {
+            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut forge = forge();
+            assert_eq!(forge.get_quote_asset(), None);
+            assert_eq!(forge.held_assets(), vec![accounts.charlie, accounts.django]);
+
+            assert_eq!(forge.set_quote_asset(accounts.frank), Err(Error::OracleQueryFailed));
+            assert_eq!(forge.add_oracle_source(accounts.frank, accounts.eve, 60_000), Ok(()));
+            assert_eq!(forge.set_quote_asset(accounts.frank), Ok(()));
+            assert_eq!(forge.held_assets(), vec![accounts.charlie, accounts.django, accounts.frank]);
+
+            // A basket asset used as the quote asset is not counted twice
+            assert_eq!(forge.add_oracle_source(accounts.charlie, accounts.eve, 60_000), Ok(()));
+            assert_eq!(forge.set_quote_asset(accounts.charlie), Ok(()));
+            assert_eq!(forge.held_assets(), vec![accounts.charlie, accounts.django]);
+
+            assert_eq!(forge.set_quote_asset(accounts.frank), Ok(()));
+            forge.asset_balances.insert(accounts.frank, 50);
+            assert_eq!(forge.set_quote_asset(accounts.charlie), Err(Error::QuoteBalanceOutstanding));
+            assert_eq!(forge.get_quote_asset(), Some(accounts.frank));
+        }
+
+        /// Storage left by code deployed before versioning migrates once to the current version.
+        #[ink::test]
+        fn migrate_runs_once() {
+            let mut forge = forge();
+            assert_eq!(forge.migrate(), Err(Error::AlreadyMigrated));
+
+            forge.storage_version.set(0);
+            assert_eq!(forge.get_storage_version(), 0);
+            assert_eq!(forge.migrate(), Ok(()));
+            assert_eq!(forge.get_storage_version(), STORAGE_VERSION);
+            assert_eq!(forge.migrate(), Err(Error::AlreadyMigrated));
+        }
+
+        /// The packed root written by the first release still decodes, and migrating it moves stakes in the
+        /// configured LP token into the fee pool and leaves the rest withdraw-only.
+        #[ink::test]
+        fn legacy_root_decodes_and_migrates() {
+            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>();
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let legacy_stake = |lp_token_id, amount_staked| LegacyStakingInfo { lp_token_id, amount_staked, last_claimed_timestamp: 5 };
+            let legacy_root = (
+                (accounts.alice, accounts.bob, accounts.eve, vec![asset(accounts.charlie, 60), asset(accounts.django, 40)]),
+                (vec![accounts.charlie, accounts.django], 2u32, 100u128),
+                BTreeMap::from([(accounts.alice, legacy_stake(accounts.frank, 30)), (accounts.charlie, legacy_stake(accounts.django, 7))]),
+                (0u64, 0u128, BTreeMap::from([(accounts.charlie, 60u128)]), 5u32, 0u64, 1u128),
+            );
+            let encoded = scale::Encode::encode(&legacy_root);
+            let mut forge = <SyntheticAssetForge as ink::storage::traits::Storable>::decode(&mut &encoded[..]).unwrap();
+            assert_eq!(forge.get_storage_version(), 0);
+            assert_eq!(forge.get_synthetic_supply(), 100);
+            assert_eq!(forge.get_asset_balance(accounts.charlie), 60);
+
+            assert_eq!(forge.set_lp_token(accounts.frank), Ok(()));
+            assert_eq!(forge.migrate(), Ok(()));
+            assert_eq!(forge.get_storage_version(), STORAGE_VERSION);
+            assert_eq!(
+                forge.get_staking_info(accounts.alice),
+                Some(StakingInfo { lp_token_id: accounts.frank, amount_staked: 30, reward_debt: 0, pending_rewards: 0 })
+            );
+            assert_eq!(forge.total_staked(), 30);
+            assert_eq!(forge.get_staking_info(accounts.charlie), None);
+            assert_eq!(forge.legacy_staking_info.get(&accounts.charlie), Some(&legacy_stake(accounts.django, 7)));
+            assert!(forge.legacy_oracle_ids.is_empty());
+            assert_eq!(forge.legacy_time_staking_reward, 0);
+        }
+
+        /// Weight updates must cover every asset and sum to 100; added assets need price sources and shrink the
+        /// existing weights proportionally.
+        #[ink::test]
+        fn basket_changes_keep_weights_valid() {
+            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut forge = forge();
+            assert_eq!(forge.update_weights(vec![50]), Err(Error::BasketValueMismatch));
+            assert_eq!(forge.update_weights(vec![50, 40]), Err(Error::InvalidWeights));
+            assert_eq!(forge.update_weights(vec![50, 50]), Ok(()));
+
+            assert_eq!(forge.add_asset(asset(accounts.charlie, 10)), Err(Error::AssetAlreadySupported));
+            assert_eq!(forge.add_asset(asset(accounts.frank, 0)), Err(Error::InvalidWeights));
+            assert_eq!(forge.add_asset(asset(accounts.frank, 20)), Err(Error::OracleQueryFailed));
+
+            assert_eq!(forge.add_oracle_source(accounts.frank, accounts.eve, 60_000), Ok(()));
+            assert_eq!(forge.add_asset(asset(accounts.frank, 20)), Ok(()));
+            assert_eq!(
+                forge.get_supported_assets(),
+                vec![asset(accounts.charlie, 40), asset(accounts.django, 40), asset(accounts.frank, 20)]
+            );
+
+            assert_eq!(forge.remove_asset(accounts.alice, u64::MAX), Err(Error::AssetNotSupported));
+        }
+
+        /// Fees collected by the contract accrue to the current stakers pro-rata, and fees collected while nothing
+        /// was staked go to the next stakers.
+        #[ink::test]
+        fn collected_fees_accrue_to_stakers() {
+            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut forge = forge();
+
+            // Fees collected before anyone stakes wait for the first stakers
+            assert_eq!(forge.collect_fee(400), Ok(()));
+            assert_eq!(forge.undistributed_fees(), 400);
+
+            for (account, amount) in [(accounts.alice, 100), (accounts.charlie, 300)] {
+                forge.staking_info.insert(account, &StakingInfo { lp_token_id: accounts.frank, amount_staked: amount, reward_debt: 0, pending_rewards: 0 });
+                forge.total_staked.set(&(forge.total_staked() + amount));
+            }
+            assert_eq!(forge.pending_stability_fees(accounts.alice), 100);
+            assert_eq!(forge.pending_stability_fees(accounts.charlie), 300);
+
+            assert_eq!(forge.collect_fee(1_000), Ok(()));
+            assert_eq!(forge.undistributed_fees(), 0);
+            assert_eq!(forge.pending_stability_fees(accounts.alice), 100 + 250);
+            assert_eq!(forge.pending_stability_fees(accounts.charlie), 300 + 750);
+            assert_eq!(forge.transaction_fees_collected, 1_400);
+            assert_eq!(forge.pending_stability_fees(accounts.django), 0);
+        }
+
+        /// Only the LP token configured by governance can be staked, and it cannot change while anything is staked.
+        #[ink::test]
+        fn deposit_requires_configured_lp_token() {
+            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut forge = forge();
+            assert_eq!(forge.deposit_liquidity(accounts.frank, 100), Err(Error::LpTokenNotAccepted));
+
+            assert_eq!(forge.set_lp_token(accounts.frank), Ok(()));
+            assert_eq!(forge.get_lp_token(), Some(accounts.frank));
+            assert_eq!(forge.deposit_liquidity(accounts.eve, 100), Err(Error::LpTokenNotAccepted));
+
+            forge.total_staked.set(&10);
+            assert_eq!(forge.set_lp_token(accounts.eve), Err(Error::StakeOutstanding));
+            assert_eq!(forge.get_lp_token(), Some(accounts.frank));
+
+            // Only an empty stake moves to a new LP token
+            forge.total_staked.set(&0);
+            assert_eq!(forge.set_lp_token(accounts.eve), Ok(()));
+            forge.staking_info.insert(accounts.bob, &StakingInfo { lp_token_id: accounts.frank, amount_staked: 10, reward_debt: 0, pending_rewards: 0 });
+            assert_eq!(forge.deposit_liquidity(accounts.eve, 100), Err(Error::Unauthorized));
+        }
+
+        /// Staking checks the LP token and balance before any transfer is attempted.
+        #[ink::test]
+        fn withdraw_checks_stake() {
+            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut forge = forge();
+            assert_eq!(forge.withdraw_liquidity(accounts.frank, 1), Err(Error::Unauthorized));
+            assert_eq!(forge.claim_stability_fees(), Err(Error::Unauthorized));
+
+            forge.staking_info.insert(accounts.bob, &StakingInfo { lp_token_id: accounts.frank, amount_staked: 10, reward_debt: 0, pending_rewards: 0 });
+            forge.total_staked.set(&10);
+            assert_eq!(forge.withdraw_liquidity(accounts.eve, 1), Err(Error::Unauthorized));
+            assert_eq!(forge.withdraw_liquidity(accounts.frank, 11), Err(Error::InsufficientBalance));
+        }
+
+        /// Mint and burn reject malformed amounts before touching any token.
+        #[ink::test]
+        fn mint_and_burn_validate_amounts() {
+            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut forge = forge();
+            assert_eq!(forge.mint_synthetic(vec![100], accounts.bob), Err(Error::BasketValueMismatch));
+            assert_eq!(forge.burn_synthetic(0, accounts.bob), Err(Error::ZeroAmount));
+            assert_eq!(forge.burn_synthetic(1, accounts.bob), Err(Error::InsufficientBalance));
+        }
+
+        /// Settlement fills the queue in order and stops part-way through the redemption that exhausts the limit.
+        #[ink::test]
+        fn redemption_queue_fills_in_order() {
+            assert_eq!(SyntheticAssetForge::plan_fills(&[300, 200, 500], u128::MAX), vec![300, 200, 500]);
+            assert_eq!(SyntheticAssetForge::plan_fills(&[300, 200, 500], 400), vec![300, 100, 0]);
+            assert_eq!(SyntheticAssetForge::plan_fills(&[300, 200, 500], 300), vec![300, 0, 0]);
+            assert_eq!(SyntheticAssetForge::plan_fills(&[], 400), Vec::<u128>::new());
+        }
+
+        /// Only the owner can cancel a queued redemption, and the queue reports each redemption's position.
+        #[ink::test]
+        fn queued_redemptions_report_status() {
+            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut forge = forge();
+            assert_eq!(forge.queue_status(0), None);
+            assert_eq!(forge.cancel_redemption(0), Err(Error::RedemptionNotFound));
+
+            let redemption = Redemption { owner: accounts.alice, receiver: accounts.alice, shares: 700, filled: 300 };
+            forge.redemptions.insert(4, &redemption);
+            forge.redemption_queue.set(&vec![3, 4]);
+            assert_eq!(forge.queue_status(4), Some((redemption, 1)));
+
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(forge.cancel_redemption(4), Err(Error::Unauthorized));
+        }
+
+        /// Anyone can settle the queue without a rebalance, but only once per rebalancing interval.
+        #[ink::test]
+        fn redemptions_settle_without_rebalance() {
+            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut forge = forge();
+            forge.rebalancing_interval = 10;
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(forge.settle_redemptions(), Ok(()));
+            assert_eq!(forge.settle_redemptions(), Err(Error::SettlementTooSoon));
+
+            test::set_block_timestamp::<ink::env::DefaultEnvironment>(10);
+            assert_eq!(forge.settle_redemptions(), Ok(()));
+
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(forge.pause(), Ok(()));
+            test::set_block_timestamp::<ink::env::DefaultEnvironment>(20);
+            assert_eq!(forge.settle_redemptions(), Err(Error::Paused));
+        }
+
+        /// Rebalance targets split the basket value by weight and convert at the oracle price, rounding down.
+        #[ink::test]
+        fn rebalance_targets_follow_weights() {
+            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>();
+            let assets = vec![asset(accounts.charlie, 60), asset(accounts.django, 40)];
+            assert_eq!(SyntheticAssetForge::target_amounts(10_000, &assets, &[100, 50]), Ok(vec![60, 80]));
+            assert_eq!(SyntheticAssetForge::target_amounts(10_000, &assets, &[7, 3]), Ok(vec![857, 1_333]));
+
+            // An asset being removed targets zero
+            let winding_down = vec![asset(accounts.charlie, 0), asset(accounts.django, 100)];
+            assert_eq!(SyntheticAssetForge::target_amounts(10_000, &winding_down, &[100, 50]), Ok(vec![0, 200]));
+
+            assert_eq!(SyntheticAssetForge::target_amounts(10_000, &assets, &[0, 50]), Err(Error::OracleQueryFailed));
+            assert_eq!(SyntheticAssetForge::target_amounts(10_000, &assets, &[100]), Err(Error::BasketValueMismatch));
+            assert_eq!(SyntheticAssetForge::target_amounts(u128::MAX, &assets, &[100, 50]), Err(Error::Overflow));
+        }
+
+        /// The median ignores a single outlying source.
+        #[ink::test]
+        fn median_of_oracle_prices() {
+            assert_eq!(SyntheticAssetForge::median(Vec::new()), None);
+            assert_eq!(SyntheticAssetForge::median(vec![100]), Some(100));
+            assert_eq!(SyntheticAssetForge::median(vec![101, 1_000_000, 99]), Some(101));
+            assert_eq!(SyntheticAssetForge::median(vec![100, 103, 1, 200]), Some(101));
+            assert_eq!(SyntheticAssetForge::median(vec![u128::MAX, u128::MAX]), Some(u128::MAX));
+        }
+
+        /// Scaled weights keep their proportions and always sum to the target.
+        #[ink::test]
+        fn weights_scale_to_target() {
+            assert_eq!(SyntheticAssetForge::scale_weights(&[50, 50], 80), vec![40, 40]);
+            assert_eq!(SyntheticAssetForge::scale_weights(&[60, 30, 10], 90), vec![54, 27, 9]);
+            assert_eq!(SyntheticAssetForge::scale_weights(&[34, 33, 33], 50), vec![18, 16, 16]);
+            // Removing an asset hands its weight to the others in proportion.
+            assert_eq!(SyntheticAssetForge::scale_weights(&[0, 30, 30], 100), vec![0, 50, 50]);
+        }
+
+        fn observation(price: u128, timestamp: u64) -> TwapObservation {
+            TwapObservation { price, timestamp }
+        }
+
+        /// Each price is weighted by how long it held within the window, up to a third of the window.
+        #[ink::test]
+        fn twap_weights_prices_by_time() {
+            let observations = [observation(100, 0), observation(200, 30), observation(400, 40)];
+            assert_eq!(SyntheticAssetForge::time_weighted_average(&observations, 0, 60), Some((100 * 20 + 200 * 10 + 400 * 20) / 50));
+            assert_eq!(SyntheticAssetForge::time_weighted_average(&[], 0, 60), None);
+        }
+
+        /// A one-block spike barely moves the TWAP.
+        #[ink::test]
+        fn twap_resists_single_spot_spike() {
+            let observations = [observation(100, 0), observation(100, 30), observation(1_000, 59), observation(100, 60), observation(100, 90)];
+            assert_eq!(SyntheticAssetForge::time_weighted_average(&observations, 0, 120), Some((100 * 119 + 1_000) / 120));
+            assert!(SyntheticAssetForge::time_weighted_average(&observations, 0, 120).unwrap() < 110);
+        }
+
+        /// Observations before the window only count from the window start.
+        #[ink::test]
+        fn twap_clips_to_window() {
+            let observations = [observation(100, 0), observation(200, 45), observation(300, 50)];
+            assert_eq!(SyntheticAssetForge::time_weighted_average(&observations, 40, 60), Some((100 * 5 + 200 * 5 + 300 * 6) / 16));
+            assert_eq!(SyntheticAssetForge::time_weighted_average(&observations, 60, 60), None);
+        }
+
+        /// Too few observations in the window give no TWAP, and a stale last price only counts for a third of the window.
+        #[ink::test]
+        fn twap_requires_fresh_observations() {
+            let observations = [observation(100, 0), observation(1_000, 10)];
+            assert_eq!(SyntheticAssetForge::time_weighted_average(&observations, 0, 120), None);
+
+            let observations = [observation(100, 0), observation(100, 10), observation(100, 20), observation(1_000, 30)];
+            assert_eq!(SyntheticAssetForge::time_weighted_average(&observations, 0, 120), Some((100 * 30 + 1_000 * 40) / 70));
+        }
+
+        /// Price moves are measured in basis points of the previous price, in either direction.
+        #[ink::test]
+        fn price_deviation_in_bps() {
+            assert_eq!(SyntheticAssetForge::deviation_bps(100, 100), 0);
+            assert_eq!(SyntheticAssetForge::deviation_bps(100, 110), 1_000);
+            assert_eq!(SyntheticAssetForge::deviation_bps(100, 89), 1_100);
+            assert_eq!(SyntheticAssetForge::deviation_bps(0, 1), u128::MAX);
+        }
+
+        /// Governance can pause functions individually or all at once; others cannot.
+        #[ink::test]
+        fn pause_guards_mint_burn_and_rebalance() {
+            let accounts = test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut forge = SyntheticAssetForge::new(
+                accounts.alice,
+                accounts.bob,
+                accounts.django,
+                Vec::new(),
+                2,
+                5,
+                0,
+                accounts.eve,
+            );
+
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(forge.pause(), Err(Error::Unauthorized));
+
+            test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(forge.set_paused(PausableFunction::Burn, true), Ok(()));
+            assert!(forge.is_paused(PausableFunction::Burn));
+            assert!(!forge.is_paused(PausableFunction::Mint));
+            assert_eq!(forge.burn_synthetic(1, accounts.bob), Err(Error::Paused));
+
+            assert_eq!(forge.pause(), Ok(()));
+            assert_eq!(forge.mint_synthetic(Vec::new(), accounts.bob), Err(Error::Paused));
+            assert_eq!(forge.rebalance_basket(u64::MAX), Err(Error::Paused));
+
+            assert_eq!(forge.unpause(), Ok(()));
+            assert!(!forge.is_paused(PausableFunction::Mint));
+            assert!(!forge.is_paused(PausableFunction::Burn));
+            assert!(!forge.is_paused(PausableFunction::Rebalance));
+        }
+
+        /// The error table classifies every variant, payload-carrying ones included, and existing codes do not change.
+        #[ink::test]
+        fn error_codes_are_stable() {
+            use error_codes::ErrorKind;
+            assert_eq!(error_codes::SYNTHETIC_ASSET_FORGE.len(), ErrorCode::from(&Error::ReentrantCall).number as usize);
+            assert_eq!(ErrorCode::from(&Error::Paused).value(), 0x0002_0011);
+            let transfer = Error::PSP22(PSP22Error::InsufficientBalance);
+            assert_eq!(ErrorCode::from(&transfer).kind, ErrorKind::CallFailed);
+            assert_eq!(error_codes::SYNTHETIC_ASSET_FORGE.decode(&scale::Encode::encode(&transfer)), Some((&transfer).into()));
+            assert_eq!(ErrorCode::from(&Error::Custom(String::from("reason"))).kind, ErrorKind::Other);
+        }
+
+    }
+
+    /// End-to-end tests against a node, deploying the mock PSP22 tokens and the mock price oracle from `mocks/`.
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+        use super::*;
+        use ink_e2e::build_message;
+        use mock_oracle::MockOracleRef;
+        use mock_psp22::{MockPsp22Ref, PSP22 as _};
+
+        type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+        /// Staking, minting, burning, claiming fees and withdrawing, with a 2% stability fee on a 60/40 basket.
+        #[ink_e2e::test(additional_contracts = "mocks/mock_psp22/Cargo.toml mocks/mock_oracle/Cargo.toml")]
+        async fn full_lifecycle(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+            let alice = ink_e2e::account_id(ink_e2e::AccountKeyring::Alice);
+            let bob = ink_e2e::account_id(ink_e2e::AccountKeyring::Bob);
+
+            // Alice deploys the basket tokens, the synthetic asset, the LP token and the oracle
+            let mut tokens = Vec::new();
+            for _ in 0..4 {
+                let token = client
+                    .instantiate("mock_psp22", &ink_e2e::alice(), MockPsp22Ref::new(1_000_000), 0, None)
+                    .await
+                    .expect("token instantiate failed")
+                    .account_id;
+                tokens.push(token);
+            }
+            let (token_a, token_b, synthetic, lp_token) = (tokens[0], tokens[1], tokens[2], tokens[3]);
+            let oracle = client
+                .instantiate("mock_oracle", &ink_e2e::alice(), MockOracleRef::new(), 0, None)
+                .await
+                .expect("oracle instantiate failed")
+                .account_id;
+
+            // Alice is both owner and governance; the router is never called without a rebalance
+            let constructor = SyntheticAssetForgeRef::new(
+                alice,
+                alice,
+                synthetic,
+                vec![AssetInfo { asset_id: token_a, weight: 60 }, AssetInfo { asset_id: token_b, weight: 40 }],
+                2,
+                5,
+                0,
+                alice,
+            );
+            let forge = client
+                .instantiate("synthetic_asset_forge", &ink_e2e::alice(), constructor, 0, None)
+                .await
+                .expect("forge instantiate failed")
+                .account_id;
+
+            for (asset, price) in [(token_a, 2u128), (token_b, 3u128)] {
+                let set_price = build_message::<MockOracleRef>(oracle.clone()).call(|oracle| oracle.set_price(asset, price));
+                client.call(&ink_e2e::alice(), set_price, 0, None).await.expect("set_price failed");
+                let add_source = build_message::<SyntheticAssetForgeRef>(forge.clone())
+                    .call(|forge| forge.add_oracle_source(asset, oracle, 600_000));
+                client.call(&ink_e2e::alice(), add_source, 0, None).await.expect("add_oracle_source failed");
+            }
+
+            // Bob stakes 100 LP tokens before any fee is collected
+            let set_lp = build_message::<SyntheticAssetForgeRef>(forge.clone()).call(|forge| forge.set_lp_token(lp_token));
+            client.call(&ink_e2e::alice(), set_lp, 0, None).await.expect("set_lp_token failed");
+            let give_lp = build_message::<MockPsp22Ref>(lp_token.clone()).call(|token| token.transfer(bob, 100, Vec::new()));
+            client.call(&ink_e2e::alice(), give_lp, 0, None).await.expect("lp transfer failed");
+            let approve_lp = build_message::<MockPsp22Ref>(lp_token.clone()).call(|token| token.approve(forge, 100));
+            client.call(&ink_e2e::bob(), approve_lp, 0, None).await.expect("lp approve failed");
+            let deposit = build_message::<SyntheticAssetForgeRef>(forge.clone()).call(|forge| forge.deposit_liquidity(lp_token, 100));
+            client.call(&ink_e2e::bob(), deposit, 0, None).await.expect("deposit_liquidity failed");
+
+            // Alice deposits 600 A and 400 B, worth 2_400; 2% of the shares are kept as the stability fee
+            for (asset, amount) in [(token_a, 600u128), (token_b, 400u128)] {
+                let approve = build_message::<MockPsp22Ref>(asset.clone()).call(|token| token.approve(forge, amount));
+                client.call(&ink_e2e::alice(), approve, 0, None).await.expect("approve failed");
+            }
+            let mint = build_message::<SyntheticAssetForgeRef>(forge.clone()).call(|forge| forge.mint_synthetic(vec![600, 400], alice));
+            client.call(&ink_e2e::alice(), mint, 0, None).await.expect("mint_synthetic failed");
+
+            let supply = build_message::<SyntheticAssetForgeRef>(forge.clone()).call(|forge| forge.get_synthetic_supply());
+            assert_eq!(client.call_dry_run(&ink_e2e::alice(), &supply, 0, None).await.return_value(), 2_400);
+            let alice_shares = build_message::<MockPsp22Ref>(synthetic.clone()).call(|token| token.balance_of(alice));
+            assert_eq!(client.call_dry_run(&ink_e2e::alice(), &alice_shares, 0, None).await.return_value(), 1_000_000 + 2_352);
+
+            // Alice burns 1_000 shares: 20 go to stakers and 980 redeem 980/2_400 of each balance
+            let approve_fee = build_message::<MockPsp22Ref>(synthetic.clone()).call(|token| token.approve(forge, 20));
+            client.call(&ink_e2e::alice(), approve_fee, 0, None).await.expect("fee approve failed");
+            let burn = build_message::<SyntheticAssetForgeRef>(forge.clone()).call(|forge| forge.burn_synthetic(1_000, alice));
+            client.call(&ink_e2e::alice(), burn, 0, None).await.expect("burn_synthetic failed");
+
+            for (asset, remaining) in [(token_a, 600u128 - 245), (token_b, 400u128 - 163)] {
+                let balance = build_message::<SyntheticAssetForgeRef>(forge.clone()).call(|forge| forge.get_asset_balance(asset));
+                assert_eq!(client.call_dry_run(&ink_e2e::alice(), &balance, 0, None).await.return_value(), remaining);
+            }
+
+            // Bob earned both fees and claims them in synthetic shares
+            let pending = build_message::<SyntheticAssetForgeRef>(forge.clone()).call(|forge| forge.pending_stability_fees(bob));
+            assert_eq!(client.call_dry_run(&ink_e2e::bob(), &pending, 0, None).await.return_value(), 48 + 20);
+            let claim = build_message::<SyntheticAssetForgeRef>(forge.clone()).call(|forge| forge.claim_stability_fees());
+            client.call(&ink_e2e::bob(), claim, 0, None).await.expect("claim_stability_fees failed");
+            let bob_shares = build_message::<MockPsp22Ref>(synthetic.clone()).call(|token| token.balance_of(bob));
+            assert_eq!(client.call_dry_run(&ink_e2e::bob(), &bob_shares, 0, None).await.return_value(), 68);
+
+            // Bob withdraws his LP tokens
+            let withdraw = build_message::<SyntheticAssetForgeRef>(forge.clone()).call(|forge| forge.withdraw_liquidity(lp_token, 100));
+            client.call(&ink_e2e::bob(), withdraw, 0, None).await.expect("withdraw_liquidity failed");
+            let bob_lp = build_message::<MockPsp22Ref>(lp_token.clone()).call(|token| token.balance_of(bob));
+            assert_eq!(client.call_dry_run(&ink_e2e::bob(), &bob_lp, 0, None).await.return_value(), 100);
+
+            Ok(())
+        }
+
+        /// Only governance can change the basket, and paused mints are refused on-chain.
+        #[ink_e2e::test(additional_contracts = "mocks/mock_psp22/Cargo.toml mocks/mock_oracle/Cargo.toml")]
+        async fn governance_and_pause(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+            let alice = ink_e2e::account_id(ink_e2e::AccountKeyring::Alice);
+            let constructor = SyntheticAssetForgeRef::new(alice, alice, alice, Vec::new(), 2, 5, 0, alice);
+            let forge = client
+                .instantiate("synthetic_asset_forge", &ink_e2e::alice(), constructor, 0, None)
+                .await
+                .expect("forge instantiate failed")
+                .account_id;
+
+            let pause = build_message::<SyntheticAssetForgeRef>(forge.clone()).call(|forge| forge.pause());
+            assert!(client.call(&ink_e2e::bob(), pause, 0, None).await.is_err());
+            let pause = build_message::<SyntheticAssetForgeRef>(forge.clone()).call(|forge| forge.pause());
+            client.call(&ink_e2e::alice(), pause, 0, None).await.expect("pause failed");
+
+            let mint = build_message::<SyntheticAssetForgeRef>(forge.clone()).call(|forge| forge.mint_synthetic(Vec::new(), alice));
+            assert_eq!(client.call_dry_run(&ink_e2e::alice(), &mint, 0, None).await.return_value(), Err(Error::Paused));
+
+            Ok(())
+        }
+
+        /// A staker who withdrew everything can stake the LP token governance switched to afterwards.
+        #[ink_e2e::test(additional_contracts = "mocks/mock_psp22/Cargo.toml mocks/mock_oracle/Cargo.toml")]
+        async fn deposit_after_lp_token_change(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+            let alice = ink_e2e::account_id(ink_e2e::AccountKeyring::Alice);
+            let bob = ink_e2e::account_id(ink_e2e::AccountKeyring::Bob);
+
+            let mut lp_tokens = Vec::new();
+            for _ in 0..2 {
+                let token = client
+                    .instantiate("mock_psp22", &ink_e2e::alice(), MockPsp22Ref::new(1_000_000), 0, None)
+                    .await
+                    .expect("token instantiate failed")
+                    .account_id;
+                let give_lp = build_message::<MockPsp22Ref>(token.clone()).call(|token| token.transfer(bob, 100, Vec::new()));
+                client.call(&ink_e2e::alice(), give_lp, 0, None).await.expect("lp transfer failed");
+                lp_tokens.push(token);
+            }
+            let (old_lp, new_lp) = (lp_tokens[0], lp_tokens[1]);
+
+            let constructor = SyntheticAssetForgeRef::new(alice, alice, alice, Vec::new(), 2, 5, 0, alice);
+            let forge = client
+                .instantiate("synthetic_asset_forge", &ink_e2e::alice(), constructor, 0, None)
+                .await
+                .expect("forge instantiate failed")
+                .account_id;
+
+            // Bob stakes and fully withdraws the old LP token
+            let set_lp = build_message::<SyntheticAssetForgeRef>(forge.clone()).call(|forge| forge.set_lp_token(old_lp));
+            client.call(&ink_e2e::alice(), set_lp, 0, None).await.expect("set_lp_token failed");
+            let approve_lp = build_message::<MockPsp22Ref>(old_lp.clone()).call(|token| token.approve(forge, 100));
+            client.call(&ink_e2e::bob(), approve_lp, 0, None).await.expect("lp approve failed");
+            let deposit = build_message::<SyntheticAssetForgeRef>(forge.clone()).call(|forge| forge.deposit_liquidity(old_lp, 100));
+            client.call(&ink_e2e::bob(), deposit, 0, None).await.expect("deposit_liquidity failed");
+            let withdraw = build_message::<SyntheticAssetForgeRef>(forge.clone()).call(|forge| forge.withdraw_liquidity(old_lp, 100));
+            client.call(&ink_e2e::bob(), withdraw, 0, None).await.expect("withdraw_liquidity failed");
+
+            // Governance switches the LP token, and Bob's empty stake moves to it on his next deposit
+            let set_lp = build_message::<SyntheticAssetForgeRef>(forge.clone()).call(|forge| forge.set_lp_token(new_lp));
+            client.call(&ink_e2e::alice(), set_lp, 0, None).await.expect("set_lp_token failed");
+            let approve_lp = build_message::<MockPsp22Ref>(new_lp.clone()).call(|token| token.approve(forge, 60));
+            client.call(&ink_e2e::bob(), approve_lp, 0, None).await.expect("lp approve failed");
+            let deposit = build_message::<SyntheticAssetForgeRef>(forge.clone()).call(|forge| forge.deposit_liquidity(new_lp, 60));
+            client.call(&ink_e2e::bob(), deposit, 0, None).await.expect("deposit_liquidity failed");
+
+            let withdraw_old = build_message::<SyntheticAssetForgeRef>(forge.clone()).call(|forge| forge.withdraw_liquidity(old_lp, 1));
+            assert_eq!(client.call_dry_run(&ink_e2e::bob(), &withdraw_old, 0, None).await.return_value(), Err(Error::Unauthorized));
+            let withdraw = build_message::<SyntheticAssetForgeRef>(forge.clone()).call(|forge| forge.withdraw_liquidity(new_lp, 60));
+            client.call(&ink_e2e::bob(), withdraw, 0, None).await.expect("withdraw_liquidity failed");
+            let bob_lp = build_message::<MockPsp22Ref>(new_lp.clone()).call(|token| token.balance_of(bob));
+            assert_eq!(client.call_dry_run(&ink_e2e::bob(), &bob_lp, 0, None).await.return_value(), 100);
+
+            Ok(())
+        }
+
+        /// Failing, stale and missing oracle readings leave no price to mint at, and a failing token's error is
+        /// returned to the caller.
+        #[ink_e2e::test(additional_contracts = "mocks/mock_psp22/Cargo.toml mocks/mock_oracle/Cargo.toml")]
+        async fn oracle_and_token_failures(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+            let alice = ink_e2e::account_id(ink_e2e::AccountKeyring::Alice);
+
+            let mut tokens = Vec::new();
+            for _ in 0..2 {
+                let token = client
+                    .instantiate("mock_psp22", &ink_e2e::alice(), MockPsp22Ref::new_with_decimals(1_000_000, 6), 0, None)
+                    .await
+                    .expect("token instantiate failed")
+                    .account_id;
+                tokens.push(token);
+            }
+            let (token, synthetic) = (tokens[0], tokens[1]);
+            let oracle = client
+                .instantiate("mock_oracle", &ink_e2e::alice(), MockOracleRef::new(), 0, None)
+                .await
+                .expect("oracle instantiate failed")
+                .account_id;
+
+            let constructor = SyntheticAssetForgeRef::new(
+                alice,
+                alice,
+                synthetic,
+                vec![AssetInfo { asset_id: token, weight: 100 }],
+                2,
+                5,
+                0,
+                alice,
+            );
+            let forge = client
+                .instantiate("synthetic_asset_forge", &ink_e2e::alice(), constructor, 0, None)
+                .await
+                .expect("forge instantiate failed")
+                .account_id;
+
+            let set_price = build_message::<MockOracleRef>(oracle.clone()).call(|oracle| oracle.set_price(token, 2));
+            client.call(&ink_e2e::alice(), set_price, 0, None).await.expect("set_price failed");
+            let add_source = build_message::<SyntheticAssetForgeRef>(forge.clone())
+                .call(|forge| forge.add_oracle_source(token, oracle, 600_000));
+            client.call(&ink_e2e::alice(), add_source, 0, None).await.expect("add_oracle_source failed");
+            let approve = build_message::<MockPsp22Ref>(token.clone()).call(|token| token.approve(forge, 100));
+            client.call(&ink_e2e::alice(), approve, 0, None).await.expect("approve failed");
+
+            let mint = build_message::<SyntheticAssetForgeRef>(forge.clone()).call(|forge| forge.mint_synthetic(vec![100], alice));
+
+            // The only source traps, serves a reading older than its max age, or has no reading
+            let set_failing = build_message::<MockOracleRef>(oracle.clone()).call(|oracle| oracle.set_failing(true));
+            client.call(&ink_e2e::alice(), set_failing, 0, None).await.expect("set_failing failed");
+            assert_eq!(client.call_dry_run(&ink_e2e::alice(), &mint, 0, None).await.return_value(), Err(Error::StalePrice));
+            let set_failing = build_message::<MockOracleRef>(oracle.clone()).call(|oracle| oracle.set_failing(false));
+            client.call(&ink_e2e::alice(), set_failing, 0, None).await.expect("set_failing failed");
+
+            let set_stale = build_message::<MockOracleRef>(oracle.clone()).call(|oracle| oracle.set_price_at(token, 2, 0));
+            client.call(&ink_e2e::alice(), set_stale, 0, None).await.expect("set_price_at failed");
+            assert_eq!(client.call_dry_run(&ink_e2e::alice(), &mint, 0, None).await.return_value(), Err(Error::StalePrice));
+
+            let clear = build_message::<MockOracleRef>(oracle.clone()).call(|oracle| oracle.clear_price(token));
+            client.call(&ink_e2e::alice(), clear, 0, None).await.expect("clear_price failed");
+            assert_eq!(client.call_dry_run(&ink_e2e::alice(), &mint, 0, None).await.return_value(), Err(Error::StalePrice));
+
+            // With a fresh price again, the basket token refuses the deposit
+            let set_price = build_message::<MockOracleRef>(oracle.clone()).call(|oracle| oracle.set_price(token, 2));
+            client.call(&ink_e2e::alice(), set_price, 0, None).await.expect("set_price failed");
+            let set_failing = build_message::<MockPsp22Ref>(token.clone()).call(|token| token.set_failing(true));
+            client.call(&ink_e2e::alice(), set_failing, 0, None).await.expect("set_failing failed");
+            assert_eq!(
+                client.call_dry_run(&ink_e2e::alice(), &mint, 0, None).await.return_value(),
+                Err(Error::PSP22(PSP22Error::Custom(String::from("forced failure"))))
+            );
+
+            let supply = build_message::<SyntheticAssetForgeRef>(forge.clone()).call(|forge| forge.get_synthetic_supply());
+            assert_eq!(client.call_dry_run(&ink_e2e::alice(), &supply, 0, None).await.return_value(), 0);
+
+            Ok(())
         }
     }
 }
 ```
 
+The end-to-end tests deploy mock contracts from the `mocks/` workspace at the repository root: `mocks/mock_psp22` and `mocks/mock_oracle`, which `additional_contracts` builds alongside the forge, and `mocks/mock_psp34` for contracts that hold or move PSP34 tokens.  The forge's crate lists `mock_psp22` and `mock_oracle` as dev-dependencies with the `ink-as-dependency` feature so the tests can use their `Ref` types.  The mocks implement the trait definitions of the shared `token-interfaces` crate at the repository root, which the contracts call through, so their message selectors match.  All of them can be told to fail: the tokens return `Custom("forced failure")` from every state-changing call, and the oracle traps on every query.  The oracle can also serve readings with any timestamp, or none at all, and the PSP22 token can be deployed with any number of decimals.
+
 **Key Improvements & Explanations:**
 
-*   **Error Handling:**  A custom `Error` enum provides more informative error messages, crucial for debugging and user feedback.
+*   **Error Handling:**  A custom `Error` enum provides more informative error messages, crucial for debugging and user feedback.  The `SYNTHETIC_ASSET_FORGE` table of the shared `error-codes` crate gives each variant a stable numeric code and a kind shared with the other contracts, so a frontend can show that a mint failed because a token transfer failed without knowing the forge's variants.  Variants with a payload, such as `PSP22`, are classified by their variant alone.
 *   **AssetInfo Struct:** Encapsulates asset-specific data (ID and weight) for better organization.
-*   **StakingInfo Struct:**  Tracks staking details for each user, including the last claim time for stability fees.
-*   **BTreeMap for Staking:**  Uses a `BTreeMap` for storing staking information to allow easy iteration and ordered access.
-*   **Impermanent Loss Mitigation (Stability Fee):** The `claim_stability_fees` function provides a mechanism to reward long-term liquidity providers, mitigating impermanent loss.  The rewards are proportional to the amount staked and the duration of the stake.
+*   **StakingInfo Struct:**  Tracks staking details for each user, including their reward debt and checkpointed rewards.
+*   **Mapping for Staking:**  Stakes live in a `Mapping` keyed by account, so each deposit, withdrawal or claim only reads and writes the caller's own entry.
+*   **Impermanent Loss Mitigation (Stability Fee):** Mints and burns are charged `stability_fee_percentage` in synthetic units, which accumulate in `transaction_fees_collected`.  Each fee is distributed over the LP tokens staked when it is collected through an `acc_reward_per_share` accumulator (MasterChef-style), and every deposit, withdrawal and claim checkpoints the staker first, so rewards stay exact however stake sizes change.  Rewards are rounded down and reward debts up, so a staker who enters part-way through a unit of the accumulator is not paid that fraction, and the fees held back for the next distribution cover what the stakers may still be paid together.  Only the LP token set by governance with `set_lp_token` can be staked, so nobody can earn fees by staking a worthless self-minted token.  The `claim_stability_fees` function pays out the checkpointed rewards, so rewards are funded by real activity rather than minted out of thin air.
+*   **TWAP Rebalancing Trigger:** Every price check records an observation per asset, and `get_twap` averages them over `twap_window`, weighting each price by how long it held but never more than a third of the window, so a price that stops being refreshed cannot carry the average.  The TWAP is unavailable (`InsufficientObservations`) until at least three observations fall within the window.  `rebalance_basket` only runs once an asset's TWAP has moved more than `rebalancing_threshold` percent from the TWAP at the last rebalance, so a single manipulated block cannot force a rebalance.
 *   **Rebalancing Logic:** The `rebalance_basket` function attempts to keep the basket's composition aligned with the target weights, minimizing the risk of deviations due to price fluctuations.  The rebalancing is permissioned and can only be triggered by the governance contract.
-*   **Governance Integration:**  The `ensure_governance` function ensures that only the designated governance contract can modify key parameters, promoting decentralized control.
+*   **Emergency Pause and Circuit Breaker:** Governance can pause minting, burning and rebalancing together or individually.  `check_circuit_breaker` lets anyone record the oracle prices and pauses all three if a price moved more than `max_price_deviation_bps` from one block to the next; mints and rebalances also refuse to run on such a move.  Withdrawing staked LP tokens and claiming fees are never paused.  The paused functions are kept in a `Pausable` from the shared `contract-commons` crate at the repository root.
+*   **Reentrancy Guard:** Every message that calls a token contract or the router runs inside `non_reentrant!`, holding the `ReentrancyGuard` from `contract-commons` for the whole message, so a callee allowed to call back is rejected with `ReentrantCall`.
+*   **Governance Integration:**  The `ensure_governance` function ensures that only the designated governance contract can modify key parameters, promoting decentralized control.  The governance contract is held in an `Ownable` from `contract-commons`, which encodes as a bare account id and keeps the packed root's layout.
+*   **Upgradeability:** The governance contract can replace the code in place with `upgrade_code`, which calls `set_code_hash` and keeps storage, balances and the contract address.  The layout version lives in a `StorageVersion` from the shared `upgradeable` crate at the repository root, in its own cell so that code deployed before versioning reads version 0.  After an upgrade, governance calls `migrate`, which runs each step from the stored version up to `STORAGE_VERSION` and refuses to run twice.  The packed root keeps the first release's fields in their original order, with its oracle ids, time-based stakes and staking reward rate kept as `legacy_*` placeholders; every field added since lives in its own `Lazy` or `Mapping` cell, so the old root still decodes.  The step to version 1 drops the chain extension oracle ids, moves stakes in the LP token governance set beforehand into the fee pool and leaves stakes in other tokens for `withdraw_legacy_stake`.  The router is unset after this migration until governance calls `update_router`, and the other settings read their defaults.
 *   **Clear Function Signatures and Comments:**  The code includes clear function signatures and comments to improve readability and understanding.
-*   **Price Oracle Integration:** The `get_price` function simulates fetching price data from external oracles. Replace the test `oracle_ids` vector with real oracle contract addresses.  I've used a `ChainExtension` here.
-*   **ERC-20 Interaction:** The contract uses `ink_env::call::build_call` for interacting with ERC-20 tokens (the synthetic asset and the underlying assets), ensuring that the contract can transfer tokens on behalf of users.
+*   **Price Oracle Integration:** Governance registers one or more `PriceOracle` contracts per asset, each with a maximum reading age.  `get_price` queries every source, drops failed and stale readings, and uses the median of the rest, so a single faulty or manipulated feed cannot move the price used by mint, burn and rebalance.  If no fresh reading is available the operation fails with `StalePrice`.
+*   **PSP22 Interaction:** The contract calls the synthetic asset and the underlying assets through the `psp22` helpers of the shared `token-interfaces` crate, which use typed `contract_ref!` references to its `PSP22`, `PSP22Mintable` and `PSP22Burnable` traits.  `transfer_from` reads the payer's allowance and balance first and fails with `InsufficientAllowance` or `InsufficientBalance` before making the transfer.  Each call targets the token contract itself (never a delegate call into this contract's storage), and failures are propagated: environment errors as `Error::EnvError`, dispatch failures as `Error::CrossContractCallFailed`, and token errors as `Error::PSP22`.
+*   **Redemption Queue:** Burns larger than `redemption_queue_threshold` shares are escrowed by the contract and queued rather than paid out immediately, so one large redemption cannot drain an asset and knock the basket off its weights.  After each rebalance the queue is settled in order, up to `redemption_settlement_limit` shares; a redemption that exceeds the remaining limit is filled partially and keeps its place.  Settlement does not depend on governance rebalancing: once `rebalancing_interval` has passed since the last settlement, anyone can call `settle_redemptions` to settle the next batch against the current basket.  Queued shares stay in the supply until settled, and their owner can cancel the unfilled part at any time.
+*   **Share-Based Accounting:** The synthetic asset is a vault share.  `mint_synthetic` mints shares in proportion to the deposited value over the basket value before the deposit, and `burn_synthetic` redeems the same fraction of every underlying balance, so price moves change the value of every share equally instead of breaking the backing.  The share, fee and reward arithmetic lives in the shared `economics` crate at the repository root, whose property tests check under randomized mint, burn, stake and claim sequences that nothing is paid out beyond what was deposited or collected.
 *   **Avoiding Integer Overflow/Underflow:** The code uses `checked_add`, `checked_sub`, `checked_mul`, and `checked_div` methods to prevent integer overflow and underflow errors, enhancing the contract's security.
 *   **Transfer Function:** Added transfer functions for interacting with external token.
 *   **Considerations:**
-    *   **DEX Integration:** The `swap_asset_to_synthetic` and `swap_synthetic_to_asset` function needs to integrate with DEX for asset swapping.
+    *   **DEX Integration:** `swap_at_oracle_price` routes swaps through a configurable Uniswap V2 style router (`AmmRouter`).  Rebalancing sells overweight assets for the governance-configured quote asset and buys underweight assets with it, never trading the vault share itself.  Each swap must return at least the oracle value of its input, converted at both assets' prices, less `max_slippage_bps`.  It is also bounded by the deadline passed to `rebalance_basket` and emits a `SwapExecuted` event.  The router is approved only for the exact input of each swap, so it can never reach staker fees or escrowed redemption shares.  Quote asset left over after a rebalance is counted by `get_synthetic_value` and paid out pro-rata by redemptions like any basket asset.
     *   **Gas Optimization:** Gas optimization techniques should be applied.
     *   **Security Audits:** Extensive security audits are necessary before deploying any DeFi contract to a production environment.
-    *   **Testing:** Comprehensive unit and integration tests are crucial.
+    *   **Testing:** Unit tests cover the share, fee, reward, rebalance-target and TWAP math and the governance guards.  The `e2e-tests` feature runs a full stake, mint, burn, claim and withdraw lifecycle on a node against the mock PSP22 tokens and mock oracle from `mocks/`, and checks that failing, stale and missing oracle readings and failing token calls are surfaced as errors.
     *   **Front-End Interface:**  A user-friendly front-end is needed for interacting with the contract.
 
 This enhanced version incorporates several advanced concepts, addresses potential vulnerabilities, and provides a more robust foundation for a decentralized synthetic asset forge.  Remember that this is a complex contract, and thorough testing and auditing are essential before deployment.  Good luck!
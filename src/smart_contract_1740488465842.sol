@@ -27,6 +27,16 @@ contract DistributedOracleAggregator {
     //  7. Reputation System:  Oracles accumulate reputation based on the accuracy of their data, their participation in dispute resolution, and their staked amount.
     //  8. Flexible Aggregation: Users can specify the aggregation method they want (e.g., median, trimmed mean, specific oracle weighting) upon data request.
     //  9. Data Source Transparency: All submitted oracle data and proofs are publicly available.
+    // 10. Callback Delivery: Requesters can optionally supply a callback address and selector; the finalized result is pushed
+    //     to the consumer contract with a gas-limited call, and failed deliveries can be retried manually.
+    // 11. Answer History: Every finalized answer is archived per data type in a fixed-size ring buffer of rounds, so
+    //     downstream consumers (e.g. TWAP calculations) can read recent history on-chain.
+    // 12. Oracle Liveness: Anyone can flag an oracle that has not responded to requests of its data type for the
+    //     inactivity period, deactivating it until its next valid response, so quorums only count live oracles.
+    // 13. Response Bonds: Requesters can require every response to lock a bond on top of the oracle's stake.  Bonds are
+    //     refunded after finalization, except that outlier responses are slashed if a dispute upholds the result.
+    // 14. Reputation Dynamics: Reputation decays toward a baseline over time, penalties escalate for repeat offenders,
+    //     and sustained accurate reporting gradually recovers reputation and forgives past offenses.
 
     // ********************
     // *** STATE VARIABLES ***
@@ -39,6 +49,11 @@ contract DistributedOracleAggregator {
         uint256 reputation;  // Reputation score (higher is better)
         uint256 stake;        // Amount of collateral staked
         bool active;          // Is the oracle currently active?
+        uint256 lastResponseTimestamp; // Time of the oracle's last response (or registration)
+        bool flaggedInactive; // Deactivated by flagInactive; its next valid response reactivates it
+        uint256 reputationUpdatedAt; // Time `reputation` was last decayed to
+        uint256 offenseCount; // Disputes lost and not yet forgiven; escalates the next penalty
+        uint256 accurateStreak; // Consecutive responses close to the finalized result
     }
 
     struct DataRequest {
@@ -54,6 +69,12 @@ contract DistributedOracleAggregator {
         int256 aggregatedResult; // The aggregated result after finalization. Use int256 to handle negative values
         uint256 disputeId;      // Dispute ID if a dispute is initiated
         uint256 quorumRequired;   // Number of votes needed to reach a decision.
+        address callbackAddress;  // Optional consumer contract to notify on finalization (address(0) for none)
+        bytes4 callbackSelector;  // Function selector called as callbackSelector(uint256 requestId, int256 result)
+        bool callbackDelivered;   // True once the callback has succeeded
+        uint256 responseBond;     // Bond each response must lock (0 for none)
+        uint256 finalizedAt;      // Time the request was last finalized (0 if never)
+        uint256 archivedRoundId;  // Answer history round holding the request's answer (0 until first finalized)
 
     }
 
@@ -80,6 +101,14 @@ contract DistributedOracleAggregator {
 
     }
 
+    struct AnswerRecord {
+        uint256 roundId;        // Per-dataType round number (starts at 1)
+        uint256 requestId;      // Request that produced this answer
+        int256 answer;          // Aggregated result
+        address[] responders;   // Oracles that contributed to the answer
+        uint256 timestamp;      // Time the answer was finalized
+    }
+
     enum AggregationMethod {
         MEDIAN,
         TRIMMED_MEAN,
@@ -101,6 +130,30 @@ contract DistributedOracleAggregator {
 
     address public owner;                            // Contract owner
     uint256 public disputeResolutionPeriod = 7 days;  // Time allowed for dispute resolution
+    uint256 public callbackGasLimit = 200000;          // Gas forwarded to consumer callbacks
+    uint256 public inactivityPeriod = 3 days;          // Time without responses after which an oracle can be flagged
+    uint256 public activeOracleCount;                  // Number of active oracles, used for quorums
+    uint256 public outlierThresholdBps = 1000;         // Deviation from the result, in basis points, that flags a response as an outlier
+    uint256 public bondLockPeriod = 1 days;            // Time after finalization that outlier bonds stay locked for disputes
+
+    uint256 public constant BASE_REPUTATION = 100;     // Reputation of a new oracle, and the value reputation decays toward
+    uint256 public constant MIN_REPUTATION = 1;        // Floor of penalties, so every responder keeps some weight
+    uint256 public reputationHalfLife = 30 days;       // Time for the distance from BASE_REPUTATION to halve
+    uint256 public reputationPenalty = 5;              // Penalty for a first lost dispute
+    uint256 public maxPenaltyMultiplier = 8;           // Cap of the doubling multiplier applied to repeat offenders
+    uint256 public disputeReputationReward = 5;        // Reward for upholding a correct result in a dispute
+    uint256 public accuracyReputationReward = 1;       // Reward for a response close to the finalized result
+    uint256 public forgivenessStreak = 10;             // Accurate responses in a row that forgive one offense
+
+    mapping(uint256 => mapping(address => uint256)) public responseBonds;  // requestId => oracle => bond locked
+    mapping(uint256 => mapping(address => bool)) public isOutlierResponse; // requestId => oracle => flagged as an outlier
+    mapping(uint256 => mapping(address => bool)) public hasResponded;      // requestId => oracle => responded
+    mapping(bytes32 => uint256[]) internal requestTimestamps;               // keccak256(dataType) => creation time of each request, ascending
+    mapping(uint256 => mapping(address => bool)) public accuracyRecorded;  // requestId => oracle => counted towards its streak
+
+    uint256 public constant HISTORY_CAPACITY = 256;                              // Rounds kept per data type
+    mapping(bytes32 => mapping(uint256 => AnswerRecord)) internal answerHistory; // keccak256(dataType) => slot => record
+    mapping(bytes32 => uint256) public latestRoundId;                            // keccak256(dataType) => latest round
 
 
     // ********************
@@ -114,6 +167,13 @@ contract DistributedOracleAggregator {
     event DisputeInitiated(uint256 disputeId, uint256 requestId, address initiator);
     event DisputeResolved(uint256 disputeId, address winner);
     event OracleReputationChanged(address oracleAddress, uint256 newReputation);
+    event CallbackDelivered(uint256 requestId, address callbackAddress, bool success);
+    event AnswerArchived(string dataType, uint256 roundId, uint256 requestId, int256 answer);
+    event OracleFlaggedInactive(address oracleAddress, address flagger);
+    event OracleReactivated(address oracleAddress);
+    event ResponseBondLocked(uint256 requestId, address oracleAddress, uint256 amount);
+    event ResponseBondRefunded(uint256 requestId, address oracleAddress, uint256 amount);
+    event ResponseBondSlashed(uint256 requestId, address oracleAddress, uint256 amount);
 
     // ********************
     // *** MODIFIERS ***
@@ -129,6 +189,11 @@ contract DistributedOracleAggregator {
         _;
     }
 
+    modifier onlyOracleOrFlagged() {
+        require(oracles[msg.sender].active || oracles[msg.sender].flaggedInactive, "Only registered oracles can call this function.");
+        _;
+    }
+
 
     // ********************
     // *** CONSTRUCTOR ***
@@ -158,10 +223,16 @@ contract DistributedOracleAggregator {
             oracleAddress: msg.sender,
             dataType: _dataType,
             serviceFee: _serviceFee,
-            reputation: 100, // Start with a base reputation
+            reputation: BASE_REPUTATION, // Start with a base reputation
             stake: _initialStake,
-            active: true
+            active: true,
+            lastResponseTimestamp: block.timestamp,
+            flaggedInactive: false,
+            reputationUpdatedAt: block.timestamp,
+            offenseCount: 0,
+            accurateStreak: 0
         });
+        activeOracleCount++;
         emit OracleRegistered(msg.sender, _dataType, _serviceFee);
     }
 
@@ -199,8 +270,13 @@ contract DistributedOracleAggregator {
      * @param _oracleAddress The address of the oracle to deactivate.
      */
     function deactivateOracle(address _oracleAddress) public onlyOwner {
-        require(oracles[_oracleAddress].active, "Oracle is already inactive.");
-        oracles[_oracleAddress].active = false;
+        Oracle storage oracle = oracles[_oracleAddress];
+        require(oracle.active || oracle.flaggedInactive, "Oracle is already inactive.");
+        if (oracle.active) {
+            activeOracleCount--;
+        }
+        oracle.active = false;
+        oracle.flaggedInactive = false; // Owner deactivation is not lifted by a response
     }
 
     /**
@@ -208,8 +284,53 @@ contract DistributedOracleAggregator {
      * @param _oracleAddress The address of the oracle to activate.
      */
     function activateOracle(address _oracleAddress) public onlyOwner {
-        require(!oracles[_oracleAddress].active, "Oracle is already active.");
-        oracles[_oracleAddress].active = true;
+        Oracle storage oracle = oracles[_oracleAddress];
+        require(oracle.oracleAddress != address(0), "Oracle not registered.");
+        require(!oracle.active, "Oracle is already active.");
+        oracle.active = true;
+        oracle.flaggedInactive = false;
+        oracle.lastResponseTimestamp = block.timestamp; // Restart its liveness window
+        activeOracleCount++;
+    }
+
+    /**
+     * @notice Deactivates an oracle that has not responded to any request of its data type for `inactivityPeriod`,
+     *  counted from the first request made after its last response.  Callable by anyone.  The oracle is reactivated
+     *  by its next valid response.
+     * @param _oracleAddress The address of the oracle to flag.
+     */
+    function flagInactive(address _oracleAddress) public {
+        Oracle storage oracle = oracles[_oracleAddress];
+        require(oracle.active, "Oracle is not active.");
+        uint256 unansweredSince = firstRequestAfter(keccak256(bytes(oracle.dataType)), oracle.lastResponseTimestamp);
+        require(unansweredSince > 0, "No requests to respond to since the last response.");
+        require(block.timestamp > unansweredSince + inactivityPeriod, "Oracle has been unresponsive for less than the inactivity period.");
+
+        oracle.active = false;
+        oracle.flaggedInactive = true;
+        activeOracleCount--;
+
+        emit OracleFlaggedInactive(_oracleAddress, msg.sender);
+    }
+
+    /**
+     * @notice Internal function to find the creation time of the first request of a data type made after `_after`,
+     *  by binary search over the data type's request times.
+     * @return The request's creation time, or 0 if no request of the data type was made after `_after`.
+     */
+    function firstRequestAfter(bytes32 _key, uint256 _after) internal view returns (uint256) {
+        uint256[] storage timestamps = requestTimestamps[_key];
+        uint256 low = 0;
+        uint256 high = timestamps.length;
+        while (low < high) {
+            uint256 mid = (low + high) / 2;
+            if (timestamps[mid] > _after) {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        return low < timestamps.length ? timestamps[low] : 0;
     }
 
 
@@ -226,6 +347,34 @@ contract DistributedOracleAggregator {
      * @param _deadline  Unix timestamp for the deadline for oracle responses.
      */
     function requestData(string memory _dataType, AggregationMethod _aggregationMethod, uint256 _fee, uint256 _deadline) public payable returns (uint256) {
+        return requestData(_dataType, _aggregationMethod, _fee, _deadline, address(0), bytes4(0));
+    }
+
+    /**
+     * @notice Requests data and registers a consumer callback that receives the result on finalization.
+     * @param _dataType The type of data requested (e.g., "USD/ETH price").
+     * @param _aggregationMethod The aggregation method to use.
+     * @param _fee The fee paid for the data request.
+     * @param _deadline  Unix timestamp for the deadline for oracle responses.
+     * @param _callbackAddress The consumer contract to notify, or address(0) for no callback.
+     * @param _callbackSelector The selector of `function(uint256 requestId, int256 result)` on the consumer.
+     */
+    function requestData(string memory _dataType, AggregationMethod _aggregationMethod, uint256 _fee, uint256 _deadline, address _callbackAddress, bytes4 _callbackSelector) public payable returns (uint256) {
+        return requestData(_dataType, _aggregationMethod, _fee, _deadline, _callbackAddress, _callbackSelector, 0);
+    }
+
+    /**
+     * @notice Requests data with a consumer callback, requiring every response to lock a bond.  Worth setting for
+     *  high-value requests, where an oracle's registration stake alone is not enough at risk.
+     * @param _dataType The type of data requested (e.g., "USD/ETH price").
+     * @param _aggregationMethod The aggregation method to use.
+     * @param _fee The fee paid for the data request.
+     * @param _deadline  Unix timestamp for the deadline for oracle responses.
+     * @param _callbackAddress The consumer contract to notify, or address(0) for no callback.
+     * @param _callbackSelector The selector of `function(uint256 requestId, int256 result)` on the consumer.
+     * @param _responseBond The bond each response must lock, or 0 for none.
+     */
+    function requestData(string memory _dataType, AggregationMethod _aggregationMethod, uint256 _fee, uint256 _deadline, address _callbackAddress, bytes4 _callbackSelector, uint256 _responseBond) public payable returns (uint256) {
         require(msg.value >= _fee, "Insufficient fee provided.");
         require(_deadline > block.timestamp, "Deadline must be in the future.");
 
@@ -242,6 +391,11 @@ contract DistributedOracleAggregator {
         newRequest.finalized = false; // Initially not finalized
         newRequest.disputeId = 0;      // No dispute initially
         newRequest.quorumRequired = calculateQuorum(); // Initialize quorum
+        newRequest.callbackAddress = _callbackAddress;
+        newRequest.callbackSelector = _callbackSelector;
+        newRequest.responseBond = _responseBond;
+
+        requestTimestamps[keccak256(bytes(_dataType))].push(block.timestamp);
 
         emit DataRequested(requestIdCounter, _dataType, msg.sender);
 
@@ -253,16 +407,26 @@ contract DistributedOracleAggregator {
     // ********************
 
     /**
-     * @notice An oracle submits data for a specific data request.
+     * @notice An oracle submits data for a specific data request, sending the request's response bond with it.
+     *  A response from an oracle flagged inactive reactivates it.
      * @param _requestId The ID of the data request.
      * @param _data The data provided by the oracle.
      * @param _proof A link to the data source or proof of calculation.
      */
-    function respondToRequest(uint256 _requestId, int256 _data, string memory _proof) public onlyOracle {
+    function respondToRequest(uint256 _requestId, int256 _data, string memory _proof) public payable onlyOracleOrFlagged {
         DataRequest storage request = dataRequests[_requestId - 1];  //Access by index, must subtract 1
-        require(request.dataType == oracles[msg.sender].dataType, "Oracle data type does not match request.");
+        require(keccak256(bytes(request.dataType)) == keccak256(bytes(oracles[msg.sender].dataType)), "Oracle data type does not match request.");
         require(block.timestamp <= request.deadline, "Response submission deadline passed.");
         require(!request.finalized, "Request has already been finalized.");
+        require(msg.value == request.responseBond, "Response bond must be sent exactly.");
+        require(!hasResponded[_requestId][msg.sender], "Oracle has already responded to this request.");
+
+        hasResponded[_requestId][msg.sender] = true;
+
+        if (msg.value > 0) {
+            responseBonds[_requestId][msg.sender] = msg.value;
+            emit ResponseBondLocked(_requestId, msg.sender, msg.value);
+        }
 
         DataResponse storage newResponse = request.responses.push();
         newResponse.oracleAddress = msg.sender;
@@ -271,6 +435,15 @@ contract DistributedOracleAggregator {
         newResponse.timestamp = block.timestamp;
         newResponse.requestId = _requestId;
 
+        Oracle storage oracle = oracles[msg.sender];
+        oracle.lastResponseTimestamp = block.timestamp;
+        if (oracle.flaggedInactive) {
+            oracle.active = true;
+            oracle.flaggedInactive = false;
+            activeOracleCount++;
+            emit OracleReactivated(msg.sender);
+        }
+
         emit DataReceived(_requestId, msg.sender, _data);
     }
 
@@ -295,11 +468,158 @@ contract DistributedOracleAggregator {
 
         request.aggregatedResult = aggregatedResult;
         request.finalized = true;
+        request.finalizedAt = block.timestamp;
+
+        flagOutliers(_requestId);
+        updateAccuracyReputations(_requestId);
+
+        archiveAnswer(_requestId);
 
         // Distribute rewards to oracles who provided data.  Implement some reward logic based on reputation.
         distributeRewards(_requestId);
 
         emit RequestFinalized(_requestId, aggregatedResult);
+
+        if (request.callbackAddress != address(0)) {
+            deliverCallback(_requestId);
+        }
+    }
+
+    /**
+     * @notice Internal function to store a finalized answer in its data type's ring buffer, overwriting the oldest round once full.
+     *  A request re-finalized after a dispute keeps its round: the record is corrected in place while still in the buffer.
+     * @param _requestId The ID of the finalized data request.
+     */
+    function archiveAnswer(uint256 _requestId) internal {
+        DataRequest storage request = dataRequests[_requestId - 1]; //Access by index, must subtract 1
+        bytes32 key = keccak256(bytes(request.dataType));
+
+        uint256 roundId = request.archivedRoundId;
+        if (roundId == 0) {
+            roundId = ++latestRoundId[key];
+            request.archivedRoundId = roundId;
+        } else if (!isRoundAvailable(key, roundId)) {
+            return; // The round has already been overwritten
+        }
+        AnswerRecord storage record = answerHistory[key][roundId % HISTORY_CAPACITY];
+        record.roundId = roundId;
+        record.requestId = _requestId;
+        record.answer = request.aggregatedResult;
+        record.timestamp = block.timestamp;
+
+        delete record.responders;
+        for (uint256 i = 0; i < request.responses.length; i++) {
+            record.responders.push(request.responses[i].oracleAddress);
+        }
+
+        emit AnswerArchived(request.dataType, roundId, _requestId, request.aggregatedResult);
+    }
+
+    /**
+     * @notice Internal function to flag the responses that deviate from the aggregated result by more than
+     *  `outlierThresholdBps`.  Their bonds, if any, stay locked until any dispute over the result is settled.
+     * @param _requestId The ID of the finalized data request.
+     */
+    function flagOutliers(uint256 _requestId) internal {
+        DataRequest storage request = dataRequests[_requestId - 1]; //Access by index, must subtract 1
+        int256 result = request.aggregatedResult;
+        uint256 absResult = result >= 0 ? uint256(result) : uint256(-result);
+
+        for (uint256 i = 0; i < request.responses.length; i++) {
+            int256 data = request.responses[i].data;
+            uint256 deviation = data >= result ? uint256(data - result) : uint256(result - data);
+            isOutlierResponse[_requestId][request.responses[i].oracleAddress] = deviation * 10000 > absResult * outlierThresholdBps;
+        }
+    }
+
+    /**
+     * @notice Internal function to reward the responders close to the finalized result.  Outliers lose their streak of
+     *  accurate responses; every `forgivenessStreak` accurate responses in a row forgive one past offense.  Each
+     *  oracle is counted at most once per request, even if the request is finalized again after a dispute.
+     * @param _requestId The ID of the finalized data request.
+     */
+    function updateAccuracyReputations(uint256 _requestId) internal {
+        DataRequest storage request = dataRequests[_requestId - 1]; //Access by index, must subtract 1
+        for (uint256 i = 0; i < request.responses.length; i++) {
+            address oracleAddress = request.responses[i].oracleAddress;
+            if (accuracyRecorded[_requestId][oracleAddress]) {
+                continue;
+            }
+            accuracyRecorded[_requestId][oracleAddress] = true;
+
+            Oracle storage oracle = oracles[oracleAddress];
+            if (isOutlierResponse[_requestId][oracleAddress]) {
+                oracle.accurateStreak = 0;
+                continue;
+            }
+
+            increaseReputation(oracleAddress, accuracyReputationReward);
+            oracle.accurateStreak++;
+            if (oracle.accurateStreak >= forgivenessStreak) {
+                oracle.accurateStreak = 0;
+                if (oracle.offenseCount > 0) {
+                    oracle.offenseCount--;
+                }
+            }
+        }
+    }
+
+    /**
+     * @notice Refunds the caller's response bond for a request.  Bonds of responses close to the result are refundable
+     *  once the request has been finalized.  Outlier bonds are refundable once a dispute over the result has been
+     *  resolved against it or has expired without reaching quorum, or `bondLockPeriod` after finalization if no
+     *  dispute was initiated by then.
+     * @param _requestId The ID of the data request.
+     */
+    function withdrawResponseBond(uint256 _requestId) public {
+        DataRequest storage request = dataRequests[_requestId - 1]; //Access by index, must subtract 1
+        uint256 amount = responseBonds[_requestId][msg.sender];
+        require(amount > 0, "No response bond to withdraw.");
+        require(request.finalizedAt != 0, "Request has not been finalized.");
+
+        if (isOutlierResponse[_requestId][msg.sender]) {
+            if (request.disputeId != 0) {
+                // Voting closes at `endTime`, so a dispute still unresolved by then can no longer slash the bond
+                Dispute storage dispute = disputes[request.disputeId - 1];
+                require(dispute.resolved || block.timestamp >= dispute.endTime, "Dispute over the result is still open.");
+            } else {
+                require(block.timestamp > request.finalizedAt + bondLockPeriod, "Outlier bond is still locked.");
+            }
+        }
+
+        responseBonds[_requestId][msg.sender] = 0;
+        payable(msg.sender).transfer(amount);
+
+        emit ResponseBondRefunded(_requestId, msg.sender, amount);
+    }
+
+    /**
+     * @notice Retries delivery of a finalized result whose callback previously failed.
+     * @param _requestId The ID of the finalized data request.
+     */
+    function redeliverCallback(uint256 _requestId) public {
+        DataRequest storage request = dataRequests[_requestId - 1]; //Access by index, must subtract 1
+        require(request.finalized, "Request has not been finalized.");
+        require(request.callbackAddress != address(0), "Request has no callback.");
+        require(!request.callbackDelivered, "Callback already delivered.");
+
+        deliverCallback(_requestId);
+    }
+
+    /**
+     * @notice Internal function to push the aggregated result to the consumer with a gas-limited call.
+     *  A failing consumer never reverts finalization; the outcome is recorded instead.
+     * @param _requestId The ID of the data request.
+     */
+    function deliverCallback(uint256 _requestId) internal {
+        DataRequest storage request = dataRequests[_requestId - 1]; //Access by index, must subtract 1
+
+        (bool success, ) = request.callbackAddress.call{gas: callbackGasLimit}(
+            abi.encodeWithSelector(request.callbackSelector, _requestId, request.aggregatedResult)
+        );
+        request.callbackDelivered = success;
+
+        emit CallbackDelivered(_requestId, request.callbackAddress, success);
     }
 
 
@@ -398,7 +718,7 @@ contract DistributedOracleAggregator {
 
         for (uint256 i = 0; i < request.responses.length; i++) {
             address oracleAddress = request.responses[i].oracleAddress;
-            uint256 reputation = oracles[oracleAddress].reputation;
+            uint256 reputation = getReputation(oracleAddress);
             int256 data = request.responses[i].data;
 
             totalReputation += reputation;
@@ -508,12 +828,14 @@ contract DistributedOracleAggregator {
                 address oracleAddress = dataRequests[dispute.requestId - 1].responses[i].oracleAddress;
                 if (dispute.hasVoted[oracleAddress]) {
                     //Reward logic here based on the reputation and stake
-                    oracles[oracleAddress].reputation += 5;
-                    emit OracleReputationChanged(oracleAddress, oracles[oracleAddress].reputation);
+                    increaseReputation(oracleAddress, disputeReputationReward);
 
                 }
             }
 
+            // Outliers bet against a result that was upheld: their response bonds compensate the requester.
+            slashOutlierBonds(dispute.requestId);
+
         } else {
             dispute.winner = disputes[_disputeId - 1].initiator; // The aggregated result was deemed incorrect.
             // Penalize the oracles who provided the incorrect data or voted the wrong way.
@@ -521,10 +843,7 @@ contract DistributedOracleAggregator {
                 address oracleAddress = dataRequests[dispute.requestId - 1].responses[i].oracleAddress;
                 if (dispute.hasVoted[oracleAddress]) {
                     //Penalty logic here based on the reputation and stake
-                    if (oracles[oracleAddress].reputation > 5){
-                      oracles[oracleAddress].reputation -= 5;
-                      emit OracleReputationChanged(oracleAddress, oracles[oracleAddress].reputation);
-                    }
+                    penalizeReputation(oracleAddress);
                     // Slash the oracles stake for providing incorrect data.
                     uint256 stakeSlashAmount = oracles[oracleAddress].stake / 10; // Slash 10% of stake.
                     oracles[oracleAddress].stake -= stakeSlashAmount;
@@ -541,6 +860,93 @@ contract DistributedOracleAggregator {
         emit DisputeResolved(_disputeId, dispute.winner);
     }
 
+    // ********************
+    // *** REPUTATION ***
+    // ********************
+
+    /**
+     * @notice Returns an oracle's reputation, decayed toward `BASE_REPUTATION` for the time since it last changed.
+     * @param _oracleAddress The address of the oracle.
+     */
+    function getReputation(address _oracleAddress) public view returns (uint256) {
+        Oracle storage oracle = oracles[_oracleAddress];
+        uint256 halvings = (block.timestamp - oracle.reputationUpdatedAt) / reputationHalfLife;
+        return decayedReputation(oracle.reputation, halvings);
+    }
+
+    /**
+     * @notice Internal function to store an oracle's decayed reputation.  Only whole half-lives are applied, and the
+     *  time into the current half-life is kept, so frequent updates do not slow the decay down.
+     * @param _oracleAddress The address of the oracle.
+     */
+    function syncReputation(address _oracleAddress) internal returns (uint256) {
+        Oracle storage oracle = oracles[_oracleAddress];
+        uint256 halvings = (block.timestamp - oracle.reputationUpdatedAt) / reputationHalfLife;
+        oracle.reputation = decayedReputation(oracle.reputation, halvings);
+        oracle.reputationUpdatedAt += halvings * reputationHalfLife;
+        return oracle.reputation;
+    }
+
+    /**
+     * @notice Internal function to halve the distance between a reputation and `BASE_REPUTATION` `_halvings` times.
+     */
+    function decayedReputation(uint256 _reputation, uint256 _halvings) internal pure returns (uint256) {
+        if (_halvings >= 256) {
+            return BASE_REPUTATION;
+        }
+        if (_reputation >= BASE_REPUTATION) {
+            return BASE_REPUTATION + ((_reputation - BASE_REPUTATION) >> _halvings);
+        }
+        return BASE_REPUTATION - ((BASE_REPUTATION - _reputation) >> _halvings);
+    }
+
+    /**
+     * @notice Internal function to add to an oracle's decayed reputation.
+     */
+    function increaseReputation(address _oracleAddress, uint256 _amount) internal {
+        uint256 reputation = syncReputation(_oracleAddress) + _amount;
+        oracles[_oracleAddress].reputation = reputation;
+        emit OracleReputationChanged(_oracleAddress, reputation);
+    }
+
+    /**
+     * @notice Internal function to penalize an oracle for a lost dispute.  The penalty doubles with every offense that
+     *  has not been forgiven yet, up to `maxPenaltyMultiplier` times `reputationPenalty`, and never takes reputation
+     *  below `MIN_REPUTATION`.
+     */
+    function penalizeReputation(address _oracleAddress) internal {
+        Oracle storage oracle = oracles[_oracleAddress];
+        uint256 reputation = syncReputation(_oracleAddress);
+
+        uint256 multiplier = oracle.offenseCount < 255 ? 1 << oracle.offenseCount : maxPenaltyMultiplier;
+        if (multiplier > maxPenaltyMultiplier) {
+            multiplier = maxPenaltyMultiplier;
+        }
+        uint256 penalty = reputationPenalty * multiplier;
+
+        oracle.reputation = reputation > MIN_REPUTATION + penalty ? reputation - penalty : MIN_REPUTATION;
+        oracle.offenseCount++;
+        oracle.accurateStreak = 0;
+        emit OracleReputationChanged(_oracleAddress, oracle.reputation);
+    }
+
+    /**
+     * @notice Internal function to slash the response bonds of the outliers of a request to its requester.
+     * @param _requestId The ID of the data request.
+     */
+    function slashOutlierBonds(uint256 _requestId) internal {
+        DataRequest storage request = dataRequests[_requestId - 1]; //Access by index, must subtract 1
+        for (uint256 i = 0; i < request.responses.length; i++) {
+            address oracleAddress = request.responses[i].oracleAddress;
+            uint256 amount = responseBonds[_requestId][oracleAddress];
+            if (isOutlierResponse[_requestId][oracleAddress] && amount > 0) {
+                responseBonds[_requestId][oracleAddress] = 0;
+                payable(request.requester).transfer(amount);
+                emit ResponseBondSlashed(_requestId, oracleAddress, amount);
+            }
+        }
+    }
+
    /**
      * @notice Function to recalculate an data result.
      * @param _requestId The ID of the data request in dispute.
@@ -583,13 +989,7 @@ contract DistributedOracleAggregator {
      * @notice Calculates the number of votes required to reach a decision.
      */
     function calculateQuorum() public view returns (uint256) {
-        uint256 totalOracles = 0;
-        for (uint256 i = 0; i < oracles.length; i++) {
-            if (oracles[address(i)].active) {
-                totalOracles++;
-            }
-        }
-        return (totalOracles * 2) / 3; // Two-thirds quorum
+        return (activeOracleCount * 2) / 3; // Two-thirds quorum
     }
 
     /**
@@ -608,12 +1008,51 @@ contract DistributedOracleAggregator {
         return dataRequests[_requestId - 1];
     }
 
+    /**
+     * @notice  Allows anyone to read the archived answer of a specific round.
+     * @param _dataType The data type of the answer.
+     * @param _roundId The round to read; must still be within the ring buffer.
+     */
+    function getAnswerAt(string memory _dataType, uint256 _roundId) public view returns (AnswerRecord memory) {
+        bytes32 key = keccak256(bytes(_dataType));
+        require(isRoundAvailable(key, _roundId), "Round not available.");
+        return answerHistory[key][_roundId % HISTORY_CAPACITY];
+    }
+
+    /**
+     * @notice  Allows anyone to read a contiguous range of archived answers, oldest first.
+     * @param _dataType The data type of the answers.
+     * @param _fromRoundId The first round of the range (inclusive).
+     * @param _toRoundId The last round of the range (inclusive).
+     */
+    function getHistoryRange(string memory _dataType, uint256 _fromRoundId, uint256 _toRoundId) public view returns (AnswerRecord[] memory) {
+        bytes32 key = keccak256(bytes(_dataType));
+        require(_fromRoundId <= _toRoundId, "Invalid round range.");
+        require(isRoundAvailable(key, _fromRoundId) && isRoundAvailable(key, _toRoundId), "Round not available.");
+
+        AnswerRecord[] memory records = new AnswerRecord[](_toRoundId - _fromRoundId + 1);
+        for (uint256 roundId = _fromRoundId; roundId <= _toRoundId; roundId++) {
+            records[roundId - _fromRoundId] = answerHistory[key][roundId % HISTORY_CAPACITY];
+        }
+        return records;
+    }
+
+    /**
+     * @notice  Internal function to check that a round has been recorded and not yet overwritten.
+     */
+    function isRoundAvailable(bytes32 _key, uint256 _roundId) internal view returns (bool) {
+        uint256 latest = latestRoundId[_key];
+        return _roundId > 0 && _roundId <= latest && latest - _roundId < HISTORY_CAPACITY;
+    }
+
     /**
      * @notice  Allows anyone to get details of a specific oracle.
      * @param _oracleAddress The address of the oracle to check.
      */
     function getOracle(address _oracleAddress) public view returns (Oracle memory) {
-        return oracles[_oracleAddress];
+        Oracle memory oracle = oracles[_oracleAddress];
+        oracle.reputation = getReputation(_oracleAddress);
+        return oracle;
     }
 
     /**
@@ -624,6 +1063,68 @@ contract DistributedOracleAggregator {
         disputeResolutionPeriod = _newPeriod;
     }
 
+    /**
+     * @notice  Allows the contract owner to change the gas forwarded to consumer callbacks.
+     * @param _newGasLimit The new callback gas limit.
+     */
+    function setCallbackGasLimit(uint256 _newGasLimit) public onlyOwner {
+        callbackGasLimit = _newGasLimit;
+    }
+
+    /**
+     * @notice  Allows the contract owner to tune reputation decay, penalties and recovery.
+     * @param _halfLife Time for the distance from BASE_REPUTATION to halve.
+     * @param _penalty Penalty for a first lost dispute.
+     * @param _maxPenaltyMultiplier Cap of the multiplier applied to repeat offenders.
+     * @param _disputeReward Reward for upholding a correct result in a dispute.
+     * @param _accuracyReward Reward for a response close to the finalized result.
+     * @param _forgivenessStreak Accurate responses in a row that forgive one offense.
+     */
+    function setReputationParameters(
+        uint256 _halfLife,
+        uint256 _penalty,
+        uint256 _maxPenaltyMultiplier,
+        uint256 _disputeReward,
+        uint256 _accuracyReward,
+        uint256 _forgivenessStreak
+    ) public onlyOwner {
+        require(_halfLife > 0, "Half-life must be greater than 0.");
+        require(_maxPenaltyMultiplier > 0, "Penalty multiplier must be greater than 0.");
+        require(_forgivenessStreak > 0, "Forgiveness streak must be greater than 0.");
+        reputationHalfLife = _halfLife;
+        reputationPenalty = _penalty;
+        maxPenaltyMultiplier = _maxPenaltyMultiplier;
+        disputeReputationReward = _disputeReward;
+        accuracyReputationReward = _accuracyReward;
+        forgivenessStreak = _forgivenessStreak;
+    }
+
+    /**
+     * @notice  Allows the contract owner to change the deviation from the result that flags a response as an outlier.
+     * @param _newThresholdBps The new outlier threshold in basis points.
+     */
+    function setOutlierThreshold(uint256 _newThresholdBps) public onlyOwner {
+        require(_newThresholdBps > 0, "Outlier threshold must be greater than 0.");
+        outlierThresholdBps = _newThresholdBps;
+    }
+
+    /**
+     * @notice  Allows the contract owner to change how long outlier bonds stay locked after finalization.
+     * @param _newPeriod The new bond lock period in seconds.
+     */
+    function setBondLockPeriod(uint256 _newPeriod) public onlyOwner {
+        bondLockPeriod = _newPeriod;
+    }
+
+    /**
+     * @notice  Allows the contract owner to change how long an oracle can go without responding before it can be flagged.
+     * @param _newPeriod The new inactivity period in seconds.
+     */
+    function setInactivityPeriod(uint256 _newPeriod) public onlyOwner {
+        require(_newPeriod > 0, "Inactivity period must be greater than 0.");
+        inactivityPeriod = _newPeriod;
+    }
+
     /**
      * @notice Fallback function to receive ETH.
      */
@@ -635,12 +1136,15 @@ contract DistributedOracleAggregator {
 Key Improvements and Explanations:
 
 * **Detailed Outline and Function Summary:**  This makes the contract's purpose and structure very clear.  This is a critical part of well-documented code.
-* **Reputation System:**  Oracles start with a base reputation.  Reputation increases when they vote correctly in disputes.  Reputation *decreases* when they vote incorrectly *or* provide data that leads to a failed dispute.  The degree of change can be tweaked.
+* **Reputation System:**  Oracles start with a base reputation.  Reputation increases when they vote correctly in disputes and, by a smaller amount, for every response close to the finalized result.  Reputation *decreases* when they vote incorrectly *or* provide data that leads to a failed dispute.  The degree of change can be tweaked.
+* **Reputation Decay and Recovery:**  Reputation decays toward `BASE_REPUTATION`, halving its distance every `reputationHalfLife`, so old merit and old penalties both fade.  The decay is applied lazily: `getReputation` returns the decayed value, and the reputation-weighted average reads it.  Penalties double with every lost dispute that has not been forgiven, up to `maxPenaltyMultiplier`, and every `forgivenessStreak` accurate responses in a row forgive one offense.  Each oracle's response counts towards its streak at most once per request, so re-finalizing a request after a dispute cannot farm reputation.
 * **Stake-Weighted Voting:** Votes in disputes are weighted by the amount of tokens an oracle has staked. This directly aligns economic incentives with providing accurate data and participating responsibly in dispute resolution.  The more skin in the game, the more weight their vote carries.
 * **Dispute Resolution with Stake Slashing:**  If a dispute is successful (the aggregated data is deemed incorrect), oracles who submitted incorrect data, *or* voted that the incorrect data was correct, are penalized.  Critically, a portion of their staked tokens is *slashed* and transferred to the user who initiated the data request *as compensation*.  This is a powerful deterrent.
 * **Flexible Aggregation Methods:** The `AggregationMethod` enum allows users to specify how they want the data aggregated.  The code includes implementations for `MEDIAN`, `TRIMMED_MEAN`, and `REPUTATION_WEIGHTED`.  The `SPECIFIC_ORACLE_WEIGHTING` option is included for future implementation.  This is much more advanced than simple averaging.
+* **Response Bonds:**  For high-value requests, the requester can set a `responseBond` that every response must lock on top of the oracle's registration stake.  On finalization, responses more than `outlierThresholdBps` away from the result are flagged as outliers.  Other bonds can be withdrawn right away.  Outlier bonds stay locked for `bondLockPeriod` or, if a dispute is initiated, until it is resolved: if the dispute upholds the result the outliers' bonds are slashed to the requester, otherwise they are refunded.  A dispute that does not reach quorum by the end of its resolution period expires, and the outlier bonds are refunded.  Each oracle can respond to a request only once, so it locks at most one bond per request.
 * **Data Source Transparency:**  The `proof` field in `DataResponse` forces oracles to provide a verifiable link to their data source or a proof of their calculation.  This makes it easier to audit the data and identify potential sources of error.
-* **Dynamic Quorum Calculation:**  The `calculateQuorum()` function now dynamically calculates the quorum based on the number of active oracles.
+* **Dynamic Quorum Calculation:**  The `calculateQuorum()` function now dynamically calculates the quorum based on the number of active oracles, kept in `activeOracleCount` as oracles are registered, deactivated, flagged and reactivated.
+* **Oracle Liveness:**  Every oracle records the time of its last response.  If it has not responded to any request of its data type for `inactivityPeriod` (3 days by default), counted from the first request made after its last response, anyone can call `flagInactive(oracle)` to deactivate it, so silent oracles stop counting towards quorums.  A flagged oracle may still respond, and its next valid response reactivates it.  Oracles deactivated by the owner stay inactive until the owner reactivates them.
 * **Complete Dispute Resolution Flow:** The dispute resolution flow is implemented from initiation to voting and resolution, including reward/penalty logic and a dispute status.
 * **Events:**  Comprehensive events are emitted to track important contract actions, making it easier to monitor and integrate with the contract.
 * **Revert Function:** Added function to revert the results of a request in case of failure after a dispute is resolved.
@@ -16,54 +16,118 @@ pragma solidity ^0.8.0;
  *     The root of the tree is stored on-chain.  After VRF fulfillment, a Merkle proof is provided for each winning ticket,
  *     proving that it was part of the committed ticket pool before the randomness was generated.
  * 3.  Multi-Ticket Raffle: Allows users to purchase multiple tickets for a single raffle draw.
+ *     Several raffles, each with its own ticket price, duration and winner count, can run concurrently and be paused individually.
+ *     Each raffle is paid either in native ETH or in an ERC20 token, including single-transaction EIP-2612 `permit` purchases.
+ *     A raffle can open with an allowlist presale in which Merkle-proven addresses buy a capped number of discounted tickets.
+ *     Tickets of cancelled raffles, or of raffles that miss their minimum ticket count, are refundable.
+ *     A raffle can be switched to batched mode, where each purchase mints a single ticket-range NFT instead of one NFT per ticket.
+ *     Repeat participants earn loyalty points, with a bonus for streaks of consecutive raffles, redeemable for discounted tickets.
  * 4.  Ticket Resale Marketplace: Users can list their tickets for sale at a specified price.
- *     Other users can purchase these tickets from the marketplace. A small fee is taken on resale.
+ *     Other users can purchase these tickets from the marketplace, or make escrowed offers that expire after a set time.
+ *     A small fee is taken on resale.
  * 5.  Customizable Raffle Parameters: Allows the contract owner to configure parameters like ticket price,
- *     number of winners, raffle duration, and resale fee.
- * 6.  Emergency Pause Mechanism:  Includes a pause function to temporarily halt critical operations in case of
+ *     number of winners, raffle duration, resale fee, and the payout table that splits each raffle's prize pool.
+ *     Raffle creators can cap the resale markup over face value and close resale during a blackout period before the draw.
+ * 6.  Automatic Draws: Chainlink Automation-compatible `checkUpkeep`/`performUpkeep` hooks let a keeper network draw ended
+ *     raffles and start scheduled raffles, so neither depends on the owner being online.
+ * 7.  Emergency Pause Mechanism:  Includes a pause function to temporarily halt critical operations in case of
  *     an unforeseen issue.
  *
  * Function Summary:
- * - requestRandomWords(): Requests random words from Chainlink VRF.
+ * - startRaffle() / startRaffleWithParameters(): Starts a new raffle with the default or the given ticket price, winner count, duration and payment token.
+ * - scheduleRaffle(): Allows the owner to queue a raffle to be started automatically at a future time.
+ * - pauseRaffle() / unpauseRaffle(): Pauses/Unpauses a single raffle (owner only).
+ * - requestRandomWords(): Requests random words from Chainlink VRF for a raffle that has ended and sold its minimum number of tickets.
+ * - setTicketBatching(): Allows the owner to switch a raffle without tickets to batched mode, where a purchase mints one ticket-range NFT.
+ * - setMinimumTickets(): Allows the owner to set the number of tickets a raffle must sell to be drawn.
+ * - cancelRaffle(): Allows the owner to cancel a raffle that has not been drawn.
+ * - refundTicket(): Burns a ticket of a cancelled or undersubscribed raffle and refunds its purchase price.
+ * - checkUpkeep() / performUpkeep(): Chainlink Automation hooks that request randomness for ended raffles and start due scheduled raffles.
  * - fulfillRandomWords(): Callback function from Chainlink VRF, processes the randomness and selects winners.
- * - purchaseTickets(): Allows users to purchase raffle tickets.
+ * - purchaseTickets(): Allows users to purchase tickets for a raffle, in ETH or the raffle's ERC20 payment token, optionally redeeming loyalty points for discounted tickets.
+ * - claimLoyaltyPoints(): Credits the loyalty points earned in a raffle once it has been drawn.
+ * - setLoyaltyParameters(): Allows the owner to set loyalty point accrual, streak bonus and redemption rates.
+ * - purchaseTicketsWithPermit(): Purchases tickets of an ERC20 raffle in one transaction using an EIP-2612 permit signature.
+ * - setPresale(): Allows the owner to configure a raffle's allowlist presale (Merkle root, end time, discounted price, per-address cap).
+ * - purchasePresaleTickets(): Allows allowlisted addresses to buy discounted tickets during the presale with a Merkle proof.  Ticket sales fund the raffle's prize pool.
+ * - claimPrize(): Allows the holder of a winning ticket to claim its prize with a Merkle proof of the ticket's inclusion in the raffle.
  * - listTicketForSale(): Lists a specific ticket for sale on the marketplace.
  * - purchaseListedTicket(): Allows users to buy tickets listed on the marketplace.
  * - cancelListing(): Allows users to cancel a ticket listing.
- * - withdrawEarnings(): Allows the contract owner to withdraw accrued earnings (resale fees).
+ * - makeOffer(): Escrows an ETH offer for a ticket, replacing or topping up the caller's previous offer.
+ * - acceptOffer(): Allows the ticket owner to sell the ticket to a bidder at their offer, minus the resale fee.
+ * - withdrawOffer(): Returns a bidder's escrowed offer.
+ * - setOfferDuration(): Allows the owner to set how long offers stay valid.
+ * - setResaleConstraints(): Allows the raffle creator to set the max resale markup and resale blackout period of a raffle.
+ * - withdrawEarnings() / withdrawTokenEarnings(): Allow the contract owner to withdraw accrued ETH or ERC20 earnings.
  * - pause() / unpause(): Pauses/Unpauses the contract (owner only).
- * - setRaffleParameters(): Allows the owner to update raffle parameters.
+ * - setRaffleParameters(): Allows the owner to update the resale fee and the default parameters of new raffles.
+ * - setPayoutTable(): Allows the owner to set the payout table (percentage split or fixed-amount tiers) used by new raffles.
+ * - getPayoutTable() / getWinningTickets(): Retrieve the payout table and the winning tickets of a raffle.
+ * - ticketLeaf(): Returns the Merkle leaf committed for a ticket or ticket range.
+ * - verifyWinner() / recomputeShuffleSeed() / getRandomWords(): Let anyone re-check a draw against the committed tickets and the stored VRF output.
  */
 
 import "@chainlink/contracts/src/v0.8/VRFConsumerBaseV2.sol";
+import "@chainlink/contracts/src/v0.8/AutomationCompatible.sol";
 import "@openzeppelin/contracts/token/ERC721/ERC721.sol";
 import "@openzeppelin/contracts/access/Ownable.sol";
 import "@openzeppelin/contracts/security/Pausable.sol";
 import "@openzeppelin/contracts/utils/cryptography/MerkleProof.sol";
+import "@openzeppelin/contracts/token/ERC20/IERC20.sol";
+import "@openzeppelin/contracts/token/ERC20/extensions/IERC20Permit.sol";
+import "@openzeppelin/contracts/token/ERC20/utils/SafeERC20.sol";
 
-contract VRMRaffle is VRFConsumerBaseV2, ERC721, Ownable, Pausable {
+contract VRMRaffle is VRFConsumerBaseV2, AutomationCompatibleInterface, ERC721, Ownable, Pausable {
+    using SafeERC20 for IERC20;
 
     // ** Constants **
     uint64 private constant REQUEST_CONFIRMATIONS = 3; // Minimum confirmations for fulfillment
     uint32 private constant NUM_WORDS = 1;         // Number of random words to request
     uint256 private constant MAX_WINNERS = 100;    // Prevents excessive gas costs
+    uint256 private constant BASIS_POINTS = 10000; // Percentage payout tiers are expressed in basis points of the prize pool
+    uint8 private constant UPKEEP_DRAW = 1;        // performData action: request randomness for an ended raffle
+    uint8 private constant UPKEEP_START = 2;       // performData action: start the next scheduled raffle
 
     // ** Events **
-    event RaffleStarted(uint256 indexed raffleId);
+    event RaffleStarted(uint256 indexed raffleId, uint256 ticketPrice, uint256 numberOfWinners, uint256 endTime, address paymentToken);
+    event RafflePaused(uint256 indexed raffleId);
+    event RaffleUnpaused(uint256 indexed raffleId);
     event TicketsPurchased(address indexed buyer, uint256 raffleId, uint256 numTickets, uint256[] ticketIds);
-    event RandomWordsRequested(uint256 indexed requestId);
+    event RandomWordsRequested(uint256 indexed raffleId, uint256 indexed requestId);
+    event RaffleScheduled(uint256 indexed scheduleIndex, uint256 startTime, uint256 ticketPrice, uint256 numberOfWinners, uint256 duration, address paymentToken);
     event RaffleCompleted(uint256 indexed raffleId, address[] winners, uint256[] randomWords);
     event TicketListed(uint256 indexed ticketId, uint256 price);
     event TicketPurchasedFromListing(uint256 indexed ticketId, address indexed buyer, address indexed seller, uint256 price);
     event ListingCancelled(uint256 indexed ticketId);
+    event OfferMade(uint256 indexed ticketId, address indexed bidder, uint256 amount, uint256 expiry);
+    event OfferAccepted(uint256 indexed ticketId, address indexed bidder, address indexed seller, uint256 amount);
+    event OfferWithdrawn(uint256 indexed ticketId, address indexed bidder, uint256 amount);
+    event ResaleConstraintsUpdated(uint256 indexed raffleId, uint256 maxMarkupPercentage, uint256 blackoutPeriod);
+    event TicketBatchingUpdated(uint256 indexed raffleId, bool batched);
+    event MinimumTicketsUpdated(uint256 indexed raffleId, uint256 minTickets);
+    event RaffleCancelled(uint256 indexed raffleId);
+    event TicketRefunded(uint256 indexed ticketId, address indexed owner, uint256 amount);
+    event PresaleConfigured(uint256 indexed raffleId, bytes32 allowlistRoot, uint256 endTime, uint256 price, uint256 maxTicketsPerAddress);
+    event LoyaltyPointsEarned(uint256 indexed raffleId, address indexed participant, uint256 points);
+    event LoyaltyPointsClaimed(uint256 indexed raffleId, address indexed participant, uint256 points);
+    event LoyaltyPointsRedeemed(uint256 indexed raffleId, address indexed participant, uint256 points, uint256 discountedTickets);
+    event LoyaltyPointsRefunded(uint256 indexed ticketId, address indexed participant, uint256 points);
+    event PayoutTableUpdated(uint256[] tiers, bool fixedAmounts);
+    event PrizeAwarded(uint256 indexed raffleId, uint256 indexed ticketId, uint256 rank, uint256 amount);
+    event PrizeClaimed(uint256 indexed ticketId, address indexed winner, uint256 amount);
 
     // ** State Variables **
-    uint256 public currentRaffleId;
-    uint256 public ticketPrice;
+    uint256 public nextRaffleId;
+    uint256 public ticketPrice; // Default for new raffles
     uint256 public resaleFeePercentage; // Expressed as a percentage (e.g., 5 for 5%)
-    uint256 public numberOfWinners;
-    uint256 public raffleDuration; // In seconds
+    uint256 public numberOfWinners; // Default for new raffles
+    uint256 public raffleDuration; // Default for new raffles, in seconds
     address payable public feeRecipient;
+    uint256[] public payoutTable; // Prize per winner rank: basis points of the prize pool, or fixed amounts if payoutFixedAmounts
+    bool public payoutFixedAmounts;
+    mapping(address => uint256) public reservedPrizeFunds; // Payment token (address(0) for ETH) => prize pools and unclaimed prizes, excluded from earnings
+    mapping(address => uint256) public resaleFeesCollected; // Payment token (address(0) for ETH) => resale fees paid to the fee recipient
 
     bytes32 public keyHash;      // Gas lane key hash
     uint64  public subscriptionId; // Subscription ID
@@ -74,17 +138,101 @@ contract VRMRaffle is VRFConsumerBaseV2, ERC721, Ownable, Pausable {
         uint256 startTime;
         uint256 endTime;
         bool completed;
-        uint256[] ticketIds; // All tickets purchased for the raffle
+        uint256[] ticketIds; // All ticket NFTs minted for the raffle (ticket ranges, in purchase order, if batched)
+        uint256 entryCount; // Tickets sold, counting every ticket in a range
+        bool batchedTickets; // Each purchase mints one ticket-range NFT covering all its tickets
         bytes32 merkleRoot; // Merkle root of the ticket IDs.
         address[] winners; // Array to store winning addresses
+        address creator; // Account that started the raffle and configures its resale rules
+        uint256 ticketPrice;
+        uint256 numberOfWinners;
+        address paymentToken; // ERC20 used for tickets, resale and prizes; address(0) for native ETH
+        bool paused; // Halts purchases, resale and the draw for this raffle only
+        bool drawRequested; // Randomness has been requested; no more tickets can be sold
+        uint256 minTickets; // Tickets that must be sold by the end time for the raffle to be drawn
+        bool cancelled;
+        uint256 prizePool; // Primary ticket sales for this raffle
+        uint256[] payoutTable; // Snapshot of the payout table when the raffle started
+        bool fixedPayouts;
+        uint256[] winningTicketIds; // Winning tickets, in rank order
+        uint256 vrfRequestId;
+        uint256[] randomWords; // Raw VRF output the winners were drawn from, kept for audit
     }
 
     mapping(uint256 => Raffle) public raffles;
 
+    // ** Scheduled Raffles **
+    struct ScheduledRaffle {
+        uint256 startTime; // Earliest time the raffle can be started by performUpkeep
+        uint256 ticketPrice;
+        uint256 numberOfWinners;
+        uint256 duration;
+        address paymentToken;
+        address creator;
+    }
+
+    ScheduledRaffle[] public scheduledRaffles; // Queue of raffles in start time order
+    uint256 public nextScheduledRaffle; // Index of the next scheduled raffle to start
+
+    // ** Anti-Scalping Data **
+    struct ResaleConstraints {
+        bool enabled;
+        uint256 maxMarkupPercentage; // Max resale price over face value (e.g., 20 for +20%)
+        uint256 blackoutPeriod;      // Seconds before the draw during which resale is closed
+    }
+
+    mapping(uint256 => ResaleConstraints) public resaleConstraints; // Maps raffle ID to its resale constraints
+
+    // ** Presale Data **
+    struct Presale {
+        bytes32 allowlistRoot;        // Merkle root of the allowlisted addresses
+        uint256 endTime;              // The public sale opens at this timestamp
+        uint256 price;                // Discounted ticket price
+        uint256 maxTicketsPerAddress;
+    }
+
+    mapping(uint256 => Presale) public presales; // Maps raffle ID to its presale, if any
+    mapping(uint256 => mapping(address => uint256)) public presaleTicketsBought; // Maps raffle ID and buyer to presale tickets bought
+
+    // ** Loyalty Data **
+    struct Participant {
+        uint256 rafflesEntered;
+        uint256 lastRaffleId; // Highest raffle ID entered
+        uint256 streak;       // Consecutive raffle IDs entered, ending at lastRaffleId
+    }
+
+    mapping(address => Participant) public participants;
+    mapping(uint256 => mapping(address => bool)) public hasEnteredRaffle; // Maps raffle ID and address to whether it bought tickets
+    mapping(uint256 => mapping(address => uint256)) public pendingLoyaltyPoints; // Earned in a raffle, claimable once it is drawn
+    mapping(address => uint256) public loyaltyPoints; // Redeemable balance
+    uint256 public loyaltyPointsPerTicket = 10;
+    uint256 public streakBonusPoints = 25; // Per consecutive raffle beyond the first, on entering a raffle
+    uint256 public maxStreakBonus = 10;    // Streak length at which the bonus stops growing
+    uint256 public pointsPerDiscountedTicket = 100;
+    uint256 public loyaltyDiscountPercentage = 50; // Discount on the ticket price (e.g., 50 for 50% off)
+
+    struct RedeemedPoints {
+        address redeemer;
+        uint256 points;
+    }
+
+    mapping(uint256 => RedeemedPoints) public ticketRedeemedPoints; // Points redeemed for a ticket's discount, re-credited if it is refunded
+
     // ** Ticket Management **
     uint256 public nextTicketId;
-    mapping(uint256 => address) public ticketOwnership; // Maps ticket ID to owner address.
+    mapping(uint256 => address) public ticketOwnership; // Maps ticket ID to owner address, kept in sync with `ownerOf` by `_afterTokenTransfer`.
     mapping(uint256 => bool) public ticketUsed; // Tracks if a ticket has been used in a raffle.
+    mapping(uint256 => uint256) public ticketRaffleId; // Maps ticket ID to the raffle it was bought for.
+    mapping(uint256 => uint256) public ticketFaceValue; // Maps ticket ID to the price paid at primary sale.
+    mapping(uint256 => uint256) public ticketPrize; // Maps winning ticket ID to its prize.
+    mapping(uint256 => bool) public prizeClaimed; // Tracks if a winning ticket's prize has been claimed.
+
+    struct TicketRange {
+        uint256 startEntry; // Position of the range's first ticket among all tickets sold in its raffle
+        uint256 count;
+    }
+
+    mapping(uint256 => TicketRange) public ticketRanges; // Maps ticket-range NFT ID to the tickets it covers (empty for single tickets)
 
     // ** Marketplace Data **
     struct Listing {
@@ -95,6 +243,15 @@ contract VRMRaffle is VRFConsumerBaseV2, ERC721, Ownable, Pausable {
 
     mapping(uint256 => Listing) public ticketListings;
 
+    struct Offer {
+        uint256 amount; // Escrowed ETH
+        uint256 expiry; // The offer cannot be accepted after this timestamp
+    }
+
+    mapping(uint256 => mapping(address => Offer)) public ticketOffers; // Maps ticket ID and bidder to their offer
+    uint256 public offerDuration = 3 days;
+    uint256 public escrowedOffers; // ETH held for open offers, excluded from withdrawEarnings
+
     // ** VRF Data **
     mapping(uint256 => uint256) public requestToRaffleId; // Maps VRF request ID to raffle ID
 
@@ -120,8 +277,13 @@ contract VRMRaffle is VRFConsumerBaseV2, ERC721, Ownable, Pausable {
         numberOfWinners = _numberOfWinners;
         raffleDuration = _raffleDuration;
         feeRecipient = _feeRecipient;
-        currentRaffleId = 1; // Start raffle IDs at 1 for user readability.
+        nextRaffleId = 1; // Start raffle IDs at 1 for user readability.
         requestConfirmations = REQUEST_CONFIRMATIONS;
+
+        // Default payout: 50/30/20 split of the prize pool between the top three tickets.
+        payoutTable.push(5000);
+        payoutTable.push(3000);
+        payoutTable.push(2000);
     }
 
     // ** Modifiers **
@@ -130,80 +292,341 @@ contract VRMRaffle is VRFConsumerBaseV2, ERC721, Ownable, Pausable {
         _;
     }
 
+    modifier whenRaffleNotPaused(uint256 _raffleId) {
+        require(!raffles[_raffleId].paused, "Raffle is paused.");
+        _;
+    }
+
+    modifier whenPublicSaleOpen(uint256 _raffleId) {
+        require(block.timestamp >= presales[_raffleId].endTime, "Public sale has not opened.");
+        _;
+    }
+
     // ** Raffle Management Functions **
 
     /**
-     * @dev Starts a new raffle.
-     * @notice Can only be called when the current raffle is completed or no raffle is ongoing.
+     * @dev Starts a new raffle with the default ticket price, number of winners and duration.
+     * @notice Other raffles may be running at the same time.
+     * @return raffleId The ID of the new raffle.
      */
-    function startRaffle() public onlyOwner {
-        require(raffles[currentRaffleId].completed || raffles[currentRaffleId].startTime == 0, "Previous raffle must be completed or not yet started.");
+    function startRaffle() public onlyOwner returns (uint256 raffleId) {
+        return _startRaffle(ticketPrice, numberOfWinners, raffleDuration, address(0), _msgSender());
+    }
 
-        raffles[currentRaffleId] = Raffle({
-            startTime: block.timestamp,
-            endTime: block.timestamp + raffleDuration,
-            completed: false,
-            ticketIds: new uint256[](0), // Initialize empty array
-            merkleRoot: bytes32(0),
-            winners: new address[](0)
-        });
-        emit RaffleStarted(currentRaffleId);
+    /**
+     * @dev Starts a new raffle with its own ticket price, number of winners, duration and payment token.
+     * @param _ticketPrice The ticket price of the raffle, in units of the payment token.
+     * @param _numberOfWinners The number of winners of the raffle.
+     * @param _duration The duration of the raffle in seconds.
+     * @param _paymentToken The ERC20 token tickets are paid in, or address(0) for native ETH.
+     * @return raffleId The ID of the new raffle.
+     */
+    function startRaffleWithParameters(uint256 _ticketPrice, uint256 _numberOfWinners, uint256 _duration, address _paymentToken) public onlyOwner returns (uint256 raffleId) {
+        return _startRaffle(_ticketPrice, _numberOfWinners, _duration, _paymentToken, _msgSender());
+    }
+
+    /**
+     * @dev Queues a raffle to be started by `performUpkeep` once `_startTime` has passed.
+     * @notice Raffles must be scheduled in start time order.
+     * @param _startTime The earliest time the raffle can start.
+     * @param _ticketPrice The ticket price of the raffle, in units of the payment token.
+     * @param _numberOfWinners The number of winners of the raffle.
+     * @param _duration The duration of the raffle in seconds, counted from when it actually starts.
+     * @param _paymentToken The ERC20 token tickets are paid in, or address(0) for native ETH.
+     */
+    function scheduleRaffle(uint256 _startTime, uint256 _ticketPrice, uint256 _numberOfWinners, uint256 _duration, address _paymentToken) public onlyOwner {
+        // Validate now so that a bad entry can never block the queue.
+        require(_numberOfWinners > 0 && _numberOfWinners <= MAX_WINNERS, "Invalid number of winners.");
+        require(_duration > 0, "Raffle duration must be greater than zero.");
+        require(
+            scheduledRaffles.length == 0 || _startTime >= scheduledRaffles[scheduledRaffles.length - 1].startTime,
+            "Raffles must be scheduled in start time order."
+        );
+
+        scheduledRaffles.push(ScheduledRaffle({
+            startTime: _startTime,
+            ticketPrice: _ticketPrice,
+            numberOfWinners: _numberOfWinners,
+            duration: _duration,
+            paymentToken: _paymentToken,
+            creator: _msgSender()
+        }));
+        emit RaffleScheduled(scheduledRaffles.length - 1, _startTime, _ticketPrice, _numberOfWinners, _duration, _paymentToken);
+    }
+
+    function _startRaffle(uint256 _ticketPrice, uint256 _numberOfWinners, uint256 _duration, address _paymentToken, address _creator) internal returns (uint256 raffleId) {
+        require(_numberOfWinners > 0 && _numberOfWinners <= MAX_WINNERS, "Invalid number of winners.");
+        require(_duration > 0, "Raffle duration must be greater than zero.");
+
+        raffleId = nextRaffleId++;
+        Raffle storage raffle = raffles[raffleId];
+        raffle.startTime = block.timestamp;
+        raffle.endTime = block.timestamp + _duration;
+        raffle.creator = _creator;
+        raffle.ticketPrice = _ticketPrice;
+        raffle.numberOfWinners = _numberOfWinners;
+        raffle.paymentToken = _paymentToken;
+        raffle.payoutTable = payoutTable;
+        raffle.fixedPayouts = payoutFixedAmounts;
+
+        emit RaffleStarted(raffleId, _ticketPrice, _numberOfWinners, raffle.endTime, _paymentToken);
+    }
+
+    /**
+     * @dev Allows users to purchase tickets for a raffle, redeeming `pointsPerDiscountedTicket` loyalty points for each discounted ticket.
+     * @notice ETH raffles take `msg.value`; ERC20 raffles pull the price from the buyer, who must have approved this contract.
+     * @param _raffleId The ID of the raffle.
+     * @param _numTickets The number of tickets to purchase.
+     * @param _discountedTickets How many of the tickets to buy at the loyalty discount.
+     */
+    function purchaseTickets(uint256 _raffleId, uint256 _numTickets, uint256 _discountedTickets) public payable whenNotPaused whenRaffleNotPaused(_raffleId) whenPublicSaleOpen(_raffleId) {
+        if (_discountedTickets > 0) {
+            require(_discountedTickets <= _numTickets, "Cannot discount more tickets than purchased.");
+            uint256 points = _discountedTickets * pointsPerDiscountedTicket;
+            require(loyaltyPoints[_msgSender()] >= points, "Insufficient loyalty points.");
+            loyaltyPoints[_msgSender()] -= points;
+            emit LoyaltyPointsRedeemed(_raffleId, _msgSender(), points, _discountedTickets);
+        }
+        _purchaseTickets(_raffleId, _numTickets, raffles[_raffleId].ticketPrice, _discountedTickets);
     }
 
     /**
-     * @dev Allows users to purchase raffle tickets.
+     * @dev Allows an allowlisted address to buy discounted tickets during a raffle's presale.
+     * @param _raffleId The ID of the raffle.
      * @param _numTickets The number of tickets to purchase.
+     * @param _merkleProof Proof that the caller is in the presale allowlist.
      */
-    function purchaseTickets(uint256 _numTickets) public payable whenNotPaused {
-        require(raffles[currentRaffleId].startTime != 0, "Raffle must be started.");
-        require(!raffles[currentRaffleId].completed, "Raffle is completed.");
-        require(block.timestamp < raffles[currentRaffleId].endTime, "Raffle is over.");
-        require(msg.value >= ticketPrice * _numTickets, "Insufficient funds sent.");
+    function purchasePresaleTickets(uint256 _raffleId, uint256 _numTickets, bytes32[] memory _merkleProof) public payable whenNotPaused whenRaffleNotPaused(_raffleId) {
+        Presale storage presale = presales[_raffleId];
+        require(block.timestamp < presale.endTime, "Presale is not active.");
+        bytes32 leaf = keccak256(bytes.concat(keccak256(abi.encode(_msgSender()))));
+        require(MerkleProof.verify(_merkleProof, presale.allowlistRoot, leaf), "Address is not on the presale allowlist.");
+        require(presaleTicketsBought[_raffleId][_msgSender()] + _numTickets <= presale.maxTicketsPerAddress, "Presale ticket cap exceeded.");
+
+        presaleTicketsBought[_raffleId][_msgSender()] += _numTickets;
+        _purchaseTickets(_raffleId, _numTickets, presale.price, 0);
+    }
 
-        uint256[] memory purchasedTicketIds = new uint256[](_numTickets);
+    /**
+     * @dev Purchases tickets of an ERC20 raffle in a single transaction using an EIP-2612 permit.
+     * @param _raffleId The ID of the raffle.
+     * @param _numTickets The number of tickets to purchase.
+     * @param _deadline The permit deadline.
+     * @param _v The permit signature `v`.
+     * @param _r The permit signature `r`.
+     * @param _s The permit signature `s`.
+     */
+    function purchaseTicketsWithPermit(
+        uint256 _raffleId,
+        uint256 _numTickets,
+        uint256 _deadline,
+        uint8 _v,
+        bytes32 _r,
+        bytes32 _s
+    ) public whenNotPaused whenRaffleNotPaused(_raffleId) whenPublicSaleOpen(_raffleId) {
+        address paymentToken = raffles[_raffleId].paymentToken;
+        require(paymentToken != address(0), "Raffle is paid in ETH.");
+
+        // A front-run permit leaves the allowance in place, so a failed permit is only fatal if the transfer fails too.
+        try IERC20Permit(paymentToken).permit(_msgSender(), address(this), raffles[_raffleId].ticketPrice * _numTickets, _deadline, _v, _r, _s) {} catch {}
+        _purchaseTickets(_raffleId, _numTickets, raffles[_raffleId].ticketPrice, 0);
+    }
 
-        for (uint256 i = 0; i < _numTickets; i++) {
+    /**
+     * @dev Mints tickets after taking payment at the given price, the first `_discountedTickets` of them at the loyalty discount.
+     */
+    function _purchaseTickets(uint256 _raffleId, uint256 _numTickets, uint256 _price, uint256 _discountedTickets) internal {
+        require(_numTickets > 0, "Must purchase at least one ticket.");
+        Raffle storage raffle = raffles[_raffleId];
+        require(raffle.startTime != 0, "Raffle must be started.");
+        require(!raffle.completed && !raffle.drawRequested, "Raffle is completed.");
+        require(!raffle.cancelled, "Raffle is cancelled.");
+        require(block.timestamp < raffle.endTime, "Raffle is over.");
+        uint256 discountedPrice = (_price * (100 - loyaltyDiscountPercentage)) / 100;
+        uint256 cost = _price * (_numTickets - _discountedTickets) + discountedPrice * _discountedTickets;
+        _collectPayment(raffle.paymentToken, cost);
+
+        uint256[] memory purchasedTicketIds;
+
+        if (raffle.batchedTickets) {
+            // One NFT for the whole purchase; its face value is the total paid.
             nextTicketId++;
             _mint(_msgSender(), nextTicketId);
-            ticketOwnership[nextTicketId] = _msgSender();
-            ticketUsed[nextTicketId] = false; //Mark ticket as available
-            raffles[currentRaffleId].ticketIds.push(nextTicketId);
-            purchasedTicketIds[i] = nextTicketId;
+            ticketRaffleId[nextTicketId] = _raffleId;
+            ticketFaceValue[nextTicketId] = cost;
+            ticketRanges[nextTicketId] = TicketRange({startEntry: raffle.entryCount, count: _numTickets});
+            if (_discountedTickets > 0) {
+                ticketRedeemedPoints[nextTicketId] = RedeemedPoints(_msgSender(), _discountedTickets * pointsPerDiscountedTicket);
+            }
+            raffle.ticketIds.push(nextTicketId);
+            purchasedTicketIds = new uint256[](1);
+            purchasedTicketIds[0] = nextTicketId;
+        } else {
+            purchasedTicketIds = new uint256[](_numTickets);
+            for (uint256 i = 0; i < _numTickets; i++) {
+                nextTicketId++;
+                _mint(_msgSender(), nextTicketId);
+                ticketUsed[nextTicketId] = false; //Mark ticket as available
+                ticketRaffleId[nextTicketId] = _raffleId;
+                ticketFaceValue[nextTicketId] = i < _discountedTickets ? discountedPrice : _price;
+                if (i < _discountedTickets) {
+                    ticketRedeemedPoints[nextTicketId] = RedeemedPoints(_msgSender(), pointsPerDiscountedTicket);
+                }
+                raffle.ticketIds.push(nextTicketId);
+                purchasedTicketIds[i] = nextTicketId;
+            }
         }
+        raffle.entryCount += _numTickets;
+
+        // Only the ticket price funds the prize pool; any overpayment stays with the contract owner.
+        raffle.prizePool += cost;
+        reservedPrizeFunds[raffle.paymentToken] += cost;
+        _recordParticipation(_raffleId, _numTickets);
 
-        emit TicketsPurchased(_msgSender(), currentRaffleId, _numTickets, purchasedTicketIds);
+        emit TicketsPurchased(_msgSender(), _raffleId, _numTickets, purchasedTicketIds);
+    }
+
+    /**
+     * @dev Updates the buyer's participation streak and adds the points earned by a purchase to their pending points for the raffle.
+     *      The first purchase in a raffle earns the streak bonus; every ticket earns `loyaltyPointsPerTicket`.
+     */
+    function _recordParticipation(uint256 _raffleId, uint256 _numTickets) internal {
+        uint256 points = _numTickets * loyaltyPointsPerTicket;
+
+        if (!hasEnteredRaffle[_raffleId][_msgSender()]) {
+            hasEnteredRaffle[_raffleId][_msgSender()] = true;
+            Participant storage participant = participants[_msgSender()];
+            participant.rafflesEntered++;
+            // Entering an older, still running raffle counts as participation but does not move the streak.
+            if (_raffleId > participant.lastRaffleId) {
+                participant.streak = _raffleId == participant.lastRaffleId + 1 ? participant.streak + 1 : 1;
+                participant.lastRaffleId = _raffleId;
+            }
+            uint256 streak = participant.streak > maxStreakBonus ? maxStreakBonus : participant.streak;
+            if (streak > 1) {
+                points += (streak - 1) * streakBonusPoints;
+            }
+        }
+
+        pendingLoyaltyPoints[_raffleId][_msgSender()] += points;
+        emit LoyaltyPointsEarned(_raffleId, _msgSender(), points);
+    }
+
+    /**
+     * @dev Credits the loyalty points the caller earned in a raffle to their redeemable balance.
+     * @notice Points only become redeemable once randomness has been requested for the raffle, so tickets of
+     *         cancelled or undersubscribed raffles, which are refunded, never earn points.
+     * @param _raffleId The ID of the raffle.
+     */
+    function claimLoyaltyPoints(uint256 _raffleId) public {
+        require(raffles[_raffleId].drawRequested, "Raffle has not been drawn.");
+        uint256 points = pendingLoyaltyPoints[_raffleId][_msgSender()];
+        require(points > 0, "No loyalty points to claim.");
+
+        pendingLoyaltyPoints[_raffleId][_msgSender()] = 0;
+        loyaltyPoints[_msgSender()] += points;
+        emit LoyaltyPointsClaimed(_raffleId, _msgSender(), points);
     }
 
    /**
-    * @dev Requests random words from Chainlink VRF to determine the raffle winners.
+    * @dev Requests random words from Chainlink VRF to determine a raffle's winners.
     * @notice It first constructs a Merkle tree to prove fairness.
+    * @param _raffleId The ID of the raffle to draw.
     */
-    function requestRandomWords() public onlyOwner whenNotPaused {
-        require(raffles[currentRaffleId].startTime != 0, "Raffle must be started.");
-        require(!raffles[currentRaffleId].completed, "Raffle already completed.");
-        require(raffles[currentRaffleId].ticketIds.length > 0, "No tickets purchased for this raffle.");
-        require(block.timestamp >= raffles[currentRaffleId].endTime, "Raffle is still running.");
+    function requestRandomWords(uint256 _raffleId) public onlyOwner whenNotPaused whenRaffleNotPaused(_raffleId) {
+        _requestRandomWords(_raffleId);
+    }
+
+    function _requestRandomWords(uint256 _raffleId) internal {
+        Raffle storage raffle = raffles[_raffleId];
+        require(raffle.startTime != 0, "Raffle must be started.");
+        require(!raffle.completed, "Raffle already completed.");
+        require(!raffle.drawRequested, "Randomness already requested for this raffle.");
+        require(raffle.entryCount > 0, "No tickets purchased for this raffle.");
+        require(block.timestamp >= raffle.endTime, "Raffle is still running.");
+        require(!isRefundable(_raffleId), "Raffle is cancelled or undersubscribed.");
 
         // 1. Build the Merkle tree with all the ticketIds
-        bytes32[] memory leafNodes = new bytes32[](raffles[currentRaffleId].ticketIds.length);
-        for (uint256 i = 0; i < raffles[currentRaffleId].ticketIds.length; i++) {
-            leafNodes[i] = keccak256(abi.encodePacked(raffles[currentRaffleId].ticketIds[i]));
-        }
+        bytes32[] memory leafNodes = _raffleLeaves(_raffleId);
 
         // 2. Calculate the Merkle root.
         bytes32 root = calculateMerkleRoot(leafNodes);
-        raffles[currentRaffleId].merkleRoot = root;
+        raffle.merkleRoot = root;
 
         // 3. Store the index of each ticket in the raffle
-        for (uint256 i = 0; i < raffles[currentRaffleId].ticketIds.length; i++) {
-            ticketIndexInRaffle[raffles[currentRaffleId].ticketIds[i]] = i;
+        for (uint256 i = 0; i < raffle.ticketIds.length; i++) {
+            ticketIndexInRaffle[raffle.ticketIds[i]] = i;
         }
 
         // 4. Request random words from Chainlink VRF
+        raffle.drawRequested = true;
         uint256 requestId = requestRandomness(keyHash, subscriptionId, requestConfirmations, NUM_WORDS);
-        requestToRaffleId[requestId] = currentRaffleId;
+        requestToRaffleId[requestId] = _raffleId;
+        raffle.vrfRequestId = requestId;
 
-        emit RandomWordsRequested(requestId);
+        emit RandomWordsRequested(_raffleId, requestId);
+    }
+
+    /**
+     * @dev Returns whether a raffle is ready to be drawn: it has ended with enough tickets sold, is not paused
+     *      and has not been drawn or cancelled.
+     * @param _raffleId The ID of the raffle.
+     */
+    function isDrawable(uint256 _raffleId) public view returns (bool) {
+        Raffle storage raffle = raffles[_raffleId];
+        return raffle.startTime != 0
+            && !raffle.completed
+            && !raffle.drawRequested
+            && !raffle.paused
+            && raffle.entryCount > 0
+            && block.timestamp >= raffle.endTime
+            && !isRefundable(_raffleId);
+    }
+
+    // ** Chainlink Automation **
+
+    /**
+     * @dev Called off-chain by the keeper network to find work: the first raffle ready to be drawn, otherwise the
+     *      next scheduled raffle whose start time has passed.
+     * @return upkeepNeeded Whether `performUpkeep` should be called.
+     * @return performData The action (`UPKEEP_DRAW` or `UPKEEP_START`) and raffle ID or schedule index, ABI-encoded.
+     */
+    function checkUpkeep(bytes calldata) external view override returns (bool upkeepNeeded, bytes memory performData) {
+        if (paused()) {
+            return (false, "");
+        }
+        for (uint256 raffleId = 1; raffleId < nextRaffleId; raffleId++) {
+            if (isDrawable(raffleId)) {
+                return (true, abi.encode(UPKEEP_DRAW, raffleId));
+            }
+        }
+        if (nextScheduledRaffle < scheduledRaffles.length && block.timestamp >= scheduledRaffles[nextScheduledRaffle].startTime) {
+            return (true, abi.encode(UPKEEP_START, nextScheduledRaffle));
+        }
+        return (false, "");
+    }
+
+    /**
+     * @dev Performs the action found by `checkUpkeep`.  Anyone can call it: the action is re-validated on-chain,
+     *      so it can only draw a raffle that is due or start the scheduled raffle that is due.
+     * @param _performData The data returned by `checkUpkeep`.
+     */
+    function performUpkeep(bytes calldata _performData) external override whenNotPaused {
+        (uint8 action, uint256 id) = abi.decode(_performData, (uint8, uint256));
+
+        if (action == UPKEEP_DRAW) {
+            require(isDrawable(id), "Raffle is not ready to be drawn.");
+            _requestRandomWords(id);
+        } else if (action == UPKEEP_START) {
+            require(id == nextScheduledRaffle && id < scheduledRaffles.length, "Not the next scheduled raffle.");
+            ScheduledRaffle memory scheduled = scheduledRaffles[id];
+            require(block.timestamp >= scheduled.startTime, "Scheduled raffle is not due.");
+
+            nextScheduledRaffle++;
+            _startRaffle(scheduled.ticketPrice, scheduled.numberOfWinners, scheduled.duration, scheduled.paymentToken, scheduled.creator);
+        } else {
+            revert("Unknown upkeep action.");
+        }
     }
 
 
@@ -217,39 +640,315 @@ contract VRMRaffle is VRFConsumerBaseV2, ERC721, Ownable, Pausable {
         require(!raffles[raffleId].completed, "Raffle already completed.");
         require(randomWords.length > 0, "No random words received.");
 
-        // Select winners based on random numbers
-        uint256 numTickets = raffles[raffleId].ticketIds.length;
-        uint256 numWinners = numberOfWinners;
-        if(numTickets < numWinners){
-            numWinners = numTickets; //if tickets is less than winners, then adjust winner numbers to tickets numbers.
-        }
+        raffles[raffleId].randomWords = randomWords;
 
-        // Shuffle tickets array based on random words to choose winners.
-        uint256[] memory shuffledTickets = shuffleArray(raffles[raffleId].ticketIds, randomWords[0]);
+        // Select winners based on random numbers
+        (uint256[] memory shuffledTickets, uint256 numWinners) = _selectWinningTickets(raffleId, randomWords[0]);
+        bytes32[] memory leafNodes = _raffleLeaves(raffleId);
 
         // Select winning tickets
         address[] memory winners = new address[](numWinners);
         uint256 winningTicket;
         bytes32[] memory proof;
+        uint256 unallocatedPrizePool = raffles[raffleId].prizePool;
         for (uint256 i = 0; i < numWinners; i++) {
             winningTicket = shuffledTickets[i];
             winners[i] = ticketOwnership[winningTicket];
             raffles[raffleId].winners.push(winners[i]);
+            raffles[raffleId].winningTicketIds.push(winningTicket);
 
             // Generate Merkle Proof for winning ticket to ensure fairness.
             uint256 index = ticketIndexInRaffle[winningTicket];
-            proof = generateMerkleProof(raffles[raffleId].ticketIds, index);
-            require(verifyMerkleProof(proof, raffles[raffleId].merkleRoot, winningTicket, index), "Invalid Merkle proof for winner");
+            proof = _generateMerkleProof(leafNodes, index);
+            require(MerkleProof.verify(proof, raffles[raffleId].merkleRoot, ticketLeaf(winningTicket)), "Invalid Merkle proof for winner");
 
             // Save the Merkle proof on-chain.
             merkleProofs[winningTicket] = proof;
+
+            // Award the prize for this rank, never more than is left in the pool.
+            uint256 prize = _prizeForRank(raffleId, i);
+            if (prize > unallocatedPrizePool) {
+                prize = unallocatedPrizePool;
+            }
+            unallocatedPrizePool -= prize;
+            ticketPrize[winningTicket] += prize;
+            emit PrizeAwarded(raffleId, winningTicket, i, prize);
         }
 
+        // Whatever the payout table leaves undistributed becomes owner earnings.
+        reservedPrizeFunds[raffles[raffleId].paymentToken] -= unallocatedPrizePool;
+
         raffles[raffleId].completed = true;
         emit RaffleCompleted(raffleId, winners, randomWords);
+    }
+
+    /**
+     * @dev Derives a raffle's winning tickets from a shuffle seed and its committed tickets.
+     * @return shuffledTickets The tickets in draw order; the first `numWinners` are the winners, in rank order.
+     * @return numWinners The raffle's number of winners, capped at the number of tickets sold.
+     */
+    function _selectWinningTickets(uint256 _raffleId, uint256 _seed) internal view returns (uint256[] memory shuffledTickets, uint256 numWinners) {
+        uint256 numTickets = raffles[_raffleId].entryCount;
+        numWinners = raffles[_raffleId].numberOfWinners;
+        if(numTickets < numWinners){
+            numWinners = numTickets; //if tickets is less than winners, then adjust winner numbers to tickets numbers.
+        }
+
+        if (raffles[_raffleId].batchedTickets) {
+            // Draw distinct tickets and map each to the range NFT holding it; a range can win several ranks.
+            uint256[] memory winningEntries = _drawEntries(_seed, numTickets, numWinners);
+            shuffledTickets = new uint256[](numWinners);
+            for (uint256 i = 0; i < numWinners; i++) {
+                shuffledTickets[i] = _rangeForEntry(_raffleId, winningEntries[i]);
+            }
+        } else {
+            // Shuffle tickets array based on random words to choose winners.
+            shuffledTickets = shuffleArray(raffles[_raffleId].ticketIds, _seed);
+        }
+    }
+
+    /**
+     * @dev Returns the seed a completed raffle was shuffled with and the winning tickets it yields, recomputed from the
+     *      stored VRF output and the committed tickets.  They should match `getWinningTickets`.
+     * @param _raffleId The ID of the raffle.
+     * @return seed The shuffle seed (the first VRF word).
+     * @return winningTicketIds The recomputed winning tickets, in rank order.
+     */
+    function recomputeShuffleSeed(uint256 _raffleId) public view returns (uint256 seed, uint256[] memory winningTicketIds) {
+        require(raffles[_raffleId].completed, "Raffle is not completed.");
+        seed = raffles[_raffleId].randomWords[0];
+
+        (uint256[] memory shuffledTickets, uint256 numWinners) = _selectWinningTickets(_raffleId, seed);
+        winningTicketIds = new uint256[](numWinners);
+        for (uint256 i = 0; i < numWinners; i++) {
+            winningTicketIds[i] = shuffledTickets[i];
+        }
+    }
+
+    /**
+     * @dev Checks that a ticket won a raffle: it was in the ticket set committed before the draw, and re-running the
+     *      draw on the stored VRF output selects it.
+     * @param _raffleId The ID of the raffle.
+     * @param _ticketId The ID of the ticket or ticket range.
+     * @param _merkleProof The Merkle proof of the ticket's inclusion in the raffle (see `merkleProofs`).
+     * @return True if the ticket is a verified winner of the raffle.
+     */
+    function verifyWinner(uint256 _raffleId, uint256 _ticketId, bytes32[] memory _merkleProof) public view returns (bool) {
+        if (!raffles[_raffleId].completed || ticketRaffleId[_ticketId] != _raffleId) {
+            return false;
+        }
+        if (!MerkleProof.verify(_merkleProof, raffles[_raffleId].merkleRoot, ticketLeaf(_ticketId))) {
+            return false;
+        }
+
+        (, uint256[] memory winningTicketIds) = recomputeShuffleSeed(_raffleId);
+        for (uint256 i = 0; i < winningTicketIds.length; i++) {
+            if (winningTicketIds[i] == _ticketId) {
+                return true;
+            }
+        }
+        return false;
+    }
+
+    /**
+     * @dev Returns the raw VRF words a raffle was drawn with.
+     * @param _raffleId The ID of the raffle.
+     */
+    function getRandomWords(uint256 _raffleId) public view returns (uint256[] memory) {
+        return raffles[_raffleId].randomWords;
+    }
+
+    /**
+     * @dev Returns the prize for a winner rank according to the raffle's payout table snapshot.
+     * @param _raffleId The ID of the raffle.
+     * @param _rank The winner rank, starting at 0.
+     * @return The prize amount, or 0 if the payout table has no tier for the rank.
+     */
+    function _prizeForRank(uint256 _raffleId, uint256 _rank) internal view returns (uint256) {
+        Raffle storage raffle = raffles[_raffleId];
+        if (_rank >= raffle.payoutTable.length) {
+            return 0;
+        }
+        if (raffle.fixedPayouts) {
+            return raffle.payoutTable[_rank];
+        }
+        return (raffle.prizePool * raffle.payoutTable[_rank]) / BASIS_POINTS;
+    }
+
+    /**
+     * @dev Picks `_numWinners` distinct ticket positions out of `_entryCount` with a partial Fisher-Yates shuffle that
+     *      only tracks the positions it swaps, so the cost depends on the number of winners rather than tickets sold.
+     * @return entries The winning positions, in rank order.
+     */
+    function _drawEntries(uint256 _seed, uint256 _entryCount, uint256 _numWinners) internal pure returns (uint256[] memory entries) {
+        entries = new uint256[](_numWinners);
+        uint256[] memory swappedPositions = new uint256[](_numWinners);
+        uint256[] memory swappedValues = new uint256[](_numWinners);
+        uint256 swaps;
+
+        for (uint256 i = 0; i < _numWinners; i++) {
+            uint256 j = i + uint256(keccak256(abi.encodePacked(_seed, i))) % (_entryCount - i);
+
+            // Positions that were never swapped still hold their own index.
+            uint256 valueI = i;
+            uint256 valueJ = j;
+            uint256 slotJ = swaps;
+            for (uint256 k = 0; k < swaps; k++) {
+                if (swappedPositions[k] == i) {
+                    valueI = swappedValues[k];
+                }
+                if (swappedPositions[k] == j) {
+                    valueJ = swappedValues[k];
+                    slotJ = k;
+                }
+            }
+
+            entries[i] = valueJ;
+            // Position i is never read again, so only position j needs to remember the swap.
+            if (slotJ == swaps) {
+                swappedPositions[swaps++] = j;
+            }
+            swappedValues[slotJ] = valueI;
+        }
+    }
+
+    /**
+     * @dev Returns the ticket-range NFT of a batched raffle that holds the ticket at the given position.
+     */
+    function _rangeForEntry(uint256 _raffleId, uint256 _entry) internal view returns (uint256) {
+        uint256[] storage ranges = raffles[_raffleId].ticketIds;
+        uint256 low = 0;
+        uint256 high = ranges.length - 1;
+        while (low < high) {
+            uint256 mid = (low + high + 1) / 2;
+            if (ticketRanges[ranges[mid]].startEntry <= _entry) {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        return ranges[low];
+    }
+
+    /**
+     * @dev Returns the Merkle leaf committed for a ticket: the hash of its ID, or for a ticket-range NFT the hash
+     *      of its ID, first position and ticket count.
+     * @param _ticketId The ID of the ticket or ticket range.
+     */
+    function ticketLeaf(uint256 _ticketId) public view returns (bytes32) {
+        TicketRange storage range = ticketRanges[_ticketId];
+        if (range.count > 0) {
+            return keccak256(abi.encodePacked(_ticketId, range.startEntry, range.count));
+        }
+        return keccak256(abi.encodePacked(_ticketId));
+    }
+
+    /**
+     * @dev Returns the Merkle leaves of all the ticket NFTs of a raffle, in purchase order.
+     */
+    function _raffleLeaves(uint256 _raffleId) internal view returns (bytes32[] memory leafNodes) {
+        uint256[] storage ticketIds = raffles[_raffleId].ticketIds;
+        leafNodes = new bytes32[](ticketIds.length);
+        for (uint256 i = 0; i < ticketIds.length; i++) {
+            leafNodes[i] = ticketLeaf(ticketIds[i]);
+        }
+    }
+
+    /**
+     * @dev Allows the holder of a winning ticket to claim its prize.
+     * @notice The proof must show that the ticket is in the Merkle tree committed before the draw.
+     * @param _ticketId The ID of the winning ticket.
+     * @param _merkleProof The Merkle proof of the ticket's inclusion in its raffle (see `merkleProofs` and `ticketLeaf`).
+     */
+    function claimPrize(uint256 _ticketId, bytes32[] memory _merkleProof) public onlyTicketOwner(_ticketId) whenNotPaused {
+        uint256 raffleId = ticketRaffleId[_ticketId];
+        require(raffles[raffleId].completed, "Raffle is not completed.");
+        require(ticketPrize[_ticketId] > 0, "Ticket did not win a prize.");
+        require(!prizeClaimed[_ticketId], "Prize already claimed.");
+        require(MerkleProof.verify(_merkleProof, raffles[raffleId].merkleRoot, ticketLeaf(_ticketId)), "Invalid Merkle proof.");
+
+        uint256 prize = ticketPrize[_ticketId];
+        address paymentToken = raffles[raffleId].paymentToken;
+        prizeClaimed[_ticketId] = true;
+        reservedPrizeFunds[paymentToken] -= prize;
+
+        if (paymentToken == address(0)) {
+            (bool success, ) = payable(_msgSender()).call{value: prize}("");
+            require(success, "Prize payment failed.");
+        } else {
+            IERC20(paymentToken).safeTransfer(_msgSender(), prize);
+        }
+
+        emit PrizeClaimed(_ticketId, _msgSender(), prize);
+    }
+
+    /**
+     * @dev Returns whether a raffle's tickets can be refunded: it was cancelled, or it ended without selling its minimum number of tickets.
+     * @param _raffleId The ID of the raffle.
+     */
+    function isRefundable(uint256 _raffleId) public view returns (bool) {
+        Raffle storage raffle = raffles[_raffleId];
+        if (raffle.cancelled) {
+            return true;
+        }
+        return !raffle.drawRequested && block.timestamp >= raffle.endTime && raffle.entryCount < raffle.minTickets;
+    }
+
+    /**
+     * @dev Burns a ticket of a cancelled or undersubscribed raffle and refunds its purchase price to the holder.
+     *      Loyalty points redeemed for the ticket's discount are re-credited to the buyer who redeemed them.
+     * @notice The refund is the primary sale price; anything paid above it on the resale marketplace is not refunded.
+     * @param _ticketId The ID of the ticket to refund.
+     */
+    function refundTicket(uint256 _ticketId) public onlyTicketOwner(_ticketId) whenNotPaused {
+        uint256 raffleId = ticketRaffleId[_ticketId];
+        require(isRefundable(raffleId), "Raffle is not refundable.");
+
+        uint256 refund = ticketFaceValue[_ticketId];
+        address paymentToken = raffles[raffleId].paymentToken;
+        raffles[raffleId].prizePool -= refund;
+        reservedPrizeFunds[paymentToken] -= refund;
+
+        ticketListings[_ticketId].isListed = false;
+        _burn(_ticketId);
+
+        RedeemedPoints memory redeemed = ticketRedeemedPoints[_ticketId];
+        if (redeemed.points > 0) {
+            delete ticketRedeemedPoints[_ticketId];
+            loyaltyPoints[redeemed.redeemer] += redeemed.points;
+            emit LoyaltyPointsRefunded(_ticketId, redeemed.redeemer, redeemed.points);
+        }
 
-        // Increment to the next raffle ID for the next draw.
-        currentRaffleId++;
+        if (paymentToken == address(0)) {
+            (bool success, ) = payable(_msgSender()).call{value: refund}("");
+            require(success, "Refund failed.");
+        } else {
+            IERC20(paymentToken).safeTransfer(_msgSender(), refund);
+        }
+
+        emit TicketRefunded(_ticketId, _msgSender(), refund);
+    }
+
+    /**
+     * @dev Takes payment for a primary ticket sale: `msg.value` for ETH raffles, a `transferFrom` for ERC20 raffles.
+     * @param _paymentToken The raffle's payment token, or address(0) for ETH.
+     * @param _amount The amount owed.
+     */
+    function _collectPayment(address _paymentToken, uint256 _amount) internal {
+        if (_paymentToken == address(0)) {
+            require(msg.value >= _amount, "Insufficient funds sent.");
+        } else {
+            require(msg.value == 0, "Raffle is paid in an ERC20 token.");
+            IERC20(_paymentToken).safeTransferFrom(_msgSender(), address(this), _amount);
+        }
+    }
+
+    /**
+     * @dev Returns the winning tickets of a raffle, in rank order.
+     * @param _raffleId The ID of the raffle.
+     */
+    function getWinningTickets(uint256 _raffleId) public view returns (uint256[] memory) {
+        return raffles[_raffleId].winningTicketIds;
     }
 
     // ** Ticket Marketplace Functions **
@@ -259,9 +958,10 @@ contract VRMRaffle is VRFConsumerBaseV2, ERC721, Ownable, Pausable {
      * @param _ticketId The ID of the ticket to list.
      * @param _price The price to list the ticket for.
      */
-    function listTicketForSale(uint256 _ticketId, uint256 _price) public onlyTicketOwner(_ticketId) whenNotPaused {
+    function listTicketForSale(uint256 _ticketId, uint256 _price) public onlyTicketOwner(_ticketId) whenNotPaused whenRaffleNotPaused(ticketRaffleId[_ticketId]) {
         require(!ticketUsed[_ticketId], "Ticket has already been used for a previous raffle.");
         require(!ticketListings[_ticketId].isListed, "Ticket already listed");
+        _enforceResaleConstraints(_ticketId, _price);
 
         ticketListings[_ticketId] = Listing({
             price: _price,
@@ -273,34 +973,43 @@ contract VRMRaffle is VRFConsumerBaseV2, ERC721, Ownable, Pausable {
 
     /**
      * @dev Allows a user to purchase a ticket that is listed on the marketplace.
+     * @notice Listings are priced in the payment token of the ticket's raffle.
      * @param _ticketId The ID of the ticket to purchase.
      */
-    function purchaseListedTicket(uint256 _ticketId) public payable whenNotPaused {
+    function purchaseListedTicket(uint256 _ticketId) public payable whenNotPaused whenRaffleNotPaused(ticketRaffleId[_ticketId]) {
         require(ticketListings[_ticketId].isListed, "Ticket is not listed for sale");
         Listing storage listing = ticketListings[_ticketId];
-        require(msg.value >= listing.price, "Insufficient funds sent.");
+        address paymentToken = raffles[ticketRaffleId[_ticketId]].paymentToken;
+        if (paymentToken == address(0)) {
+            require(msg.value >= listing.price, "Insufficient funds sent.");
+        } else {
+            require(msg.value == 0, "Raffle is paid in an ERC20 token.");
+        }
+        _enforceResaleConstraints(_ticketId, listing.price); // Constraints may have changed since listing
         address seller = listing.seller;
+        uint256 price = listing.price;
 
         // Calculate resale fee
-        uint256 resaleFee = (listing.price * resaleFeePercentage) / 100;
-        uint256 sellerPayout = listing.price - resaleFee;
+        uint256 resaleFee = (price * resaleFeePercentage) / 100;
+        uint256 sellerPayout = price - resaleFee;
 
-        // Transfer ticket ownership
-        ticketOwnership[_ticketId] = _msgSender();
+        // Transfer ticket ownership; this also removes the listing.
         _transfer(seller, _msgSender(), _ticketId);
 
         // Pay seller and fee recipient
-        (bool success1, ) = payable(seller).call{value: sellerPayout}("");
-        require(success1, "Seller payment failed.");
-
-        (bool success2, ) = feeRecipient.call{value: resaleFee}("");
-        require(success2, "Fee recipient payment failed.");
-
-
-        // Update listing status
-        listing.isListed = false;
+        resaleFeesCollected[paymentToken] += resaleFee;
+        if (paymentToken == address(0)) {
+            (bool success1, ) = payable(seller).call{value: sellerPayout}("");
+            require(success1, "Seller payment failed.");
+
+            (bool success2, ) = feeRecipient.call{value: resaleFee}("");
+            require(success2, "Fee recipient payment failed.");
+        } else {
+            IERC20(paymentToken).safeTransferFrom(_msgSender(), seller, sellerPayout);
+            IERC20(paymentToken).safeTransferFrom(_msgSender(), feeRecipient, resaleFee);
+        }
 
-        emit TicketPurchasedFromListing(_ticketId, _msgSender(), seller, listing.price);
+        emit TicketPurchasedFromListing(_ticketId, _msgSender(), seller, price);
     }
 
     /**
@@ -315,17 +1024,140 @@ contract VRMRaffle is VRFConsumerBaseV2, ERC721, Ownable, Pausable {
         emit ListingCancelled(_ticketId);
     }
 
+    /**
+     * @dev Makes an offer for a ticket, escrowing the ETH sent.  A bidder's new offer adds to their previous one and restarts its expiry.
+     * @notice Offers are only supported for tickets of ETH raffles.
+     * @param _ticketId The ID of the ticket.
+     */
+    function makeOffer(uint256 _ticketId) public payable whenNotPaused whenRaffleNotPaused(ticketRaffleId[_ticketId]) {
+        require(ticketOwnership[_ticketId] != address(0), "Ticket does not exist.");
+        require(ticketOwnership[_ticketId] != _msgSender(), "Cannot make an offer for your own ticket.");
+        require(raffles[ticketRaffleId[_ticketId]].paymentToken == address(0), "Offers are only supported for ETH raffles.");
+        require(msg.value > 0, "Offer must be greater than zero.");
+
+        Offer storage offer = ticketOffers[_ticketId][_msgSender()];
+        offer.amount += msg.value;
+        offer.expiry = block.timestamp + offerDuration;
+        escrowedOffers += msg.value;
+        _enforceResaleConstraints(_ticketId, offer.amount);
+
+        emit OfferMade(_ticketId, _msgSender(), offer.amount, offer.expiry);
+    }
+
+    /**
+     * @dev Sells a ticket to a bidder at their offer.  The resale fee is taken from the offer as for listed sales.
+     * @param _ticketId The ID of the ticket.
+     * @param _bidder The bidder whose offer to accept.
+     */
+    function acceptOffer(uint256 _ticketId, address _bidder) public onlyTicketOwner(_ticketId) whenNotPaused whenRaffleNotPaused(ticketRaffleId[_ticketId]) {
+        require(!ticketUsed[_ticketId], "Ticket has already been used for a previous raffle.");
+        Offer memory offer = ticketOffers[_ticketId][_bidder];
+        require(offer.amount > 0, "No offer from this bidder.");
+        require(block.timestamp <= offer.expiry, "Offer has expired.");
+        _enforceResaleConstraints(_ticketId, offer.amount); // Constraints may have changed since the offer
+        address seller = _msgSender();
+
+        delete ticketOffers[_ticketId][_bidder];
+        escrowedOffers -= offer.amount;
+        ticketListings[_ticketId].isListed = false;
+
+        // Calculate resale fee
+        uint256 resaleFee = (offer.amount * resaleFeePercentage) / 100;
+        uint256 sellerPayout = offer.amount - resaleFee;
+        resaleFeesCollected[address(0)] += resaleFee;
+
+        // Transfer ticket ownership
+        _transfer(seller, _bidder, _ticketId);
+
+        // Pay seller and fee recipient
+        (bool success1, ) = payable(seller).call{value: sellerPayout}("");
+        require(success1, "Seller payment failed.");
+
+        (bool success2, ) = feeRecipient.call{value: resaleFee}("");
+        require(success2, "Fee recipient payment failed.");
+
+        emit OfferAccepted(_ticketId, _bidder, seller, offer.amount);
+    }
+
+    /**
+     * @dev Withdraws the caller's offer for a ticket and returns the escrowed ETH, whether or not it has expired.
+     * @param _ticketId The ID of the ticket.
+     */
+    function withdrawOffer(uint256 _ticketId) public {
+        uint256 amount = ticketOffers[_ticketId][_msgSender()].amount;
+        require(amount > 0, "No offer to withdraw.");
+
+        delete ticketOffers[_ticketId][_msgSender()];
+        escrowedOffers -= amount;
+
+        (bool success, ) = payable(_msgSender()).call{value: amount}("");
+        require(success, "Offer refund failed.");
+
+        emit OfferWithdrawn(_ticketId, _msgSender(), amount);
+    }
+
+    /**
+     * @dev Reverts if a resale of the ticket at the given price breaks its raffle's anti-scalping rules.
+     * @param _ticketId The ID of the ticket being resold.
+     * @param _price The resale price.
+     */
+    function _enforceResaleConstraints(uint256 _ticketId, uint256 _price) internal view {
+        uint256 raffleId = ticketRaffleId[_ticketId];
+        ResaleConstraints storage constraints = resaleConstraints[raffleId];
+        if (!constraints.enabled) {
+            return;
+        }
+
+        uint256 maxPrice = (ticketFaceValue[_ticketId] * (100 + constraints.maxMarkupPercentage)) / 100;
+        require(_price <= maxPrice, "Resale price exceeds the allowed markup.");
+        require(block.timestamp + constraints.blackoutPeriod < raffles[raffleId].endTime, "Resale is closed before the draw.");
+    }
+
+    // ** Raffle Creator Functions **
+
+    /**
+     * @dev Allows the raffle creator to configure anti-scalping rules for resale of the raffle's tickets.
+     * @param _raffleId The ID of the raffle.
+     * @param _maxMarkupPercentage The maximum resale price over face value, as a percentage.
+     * @param _blackoutPeriod The number of seconds before the draw during which resale is closed.
+     */
+    function setResaleConstraints(uint256 _raffleId, uint256 _maxMarkupPercentage, uint256 _blackoutPeriod) public {
+        require(raffles[_raffleId].startTime != 0, "Raffle must be started.");
+        require(raffles[_raffleId].creator == _msgSender(), "Only the raffle creator can set resale constraints.");
+        require(!raffles[_raffleId].completed, "Raffle is completed.");
+
+        resaleConstraints[_raffleId] = ResaleConstraints({
+            enabled: true,
+            maxMarkupPercentage: _maxMarkupPercentage,
+            blackoutPeriod: _blackoutPeriod
+        });
+        emit ResaleConstraintsUpdated(_raffleId, _maxMarkupPercentage, _blackoutPeriod);
+    }
+
     // ** Owner-Only Functions **
 
     /**
-     * @dev Allows the contract owner to withdraw accrued earnings (resale fees).
+     * @dev Allows the contract owner to withdraw accrued earnings (resale fees and undistributed prize pools).
+     * @notice Prize pools of running raffles and unclaimed prizes are never withdrawn.
      */
     function withdrawEarnings() public onlyOwner {
-        require(address(this).balance > 0, "No earnings to withdraw");
-        (bool success, ) = owner().call{value: address(this).balance}("");
+        uint256 earnings = address(this).balance - reservedPrizeFunds[address(0)] - escrowedOffers;
+        require(earnings > 0, "No earnings to withdraw");
+        (bool success, ) = owner().call{value: earnings}("");
         require(success, "Withdrawal failed.");
     }
 
+    /**
+     * @dev Allows the contract owner to withdraw accrued earnings in an ERC20 payment token.
+     * @notice Prize pools of running raffles and unclaimed prizes in the token are never withdrawn.
+     * @param _token The payment token.
+     */
+    function withdrawTokenEarnings(address _token) public onlyOwner {
+        uint256 earnings = IERC20(_token).balanceOf(address(this)) - reservedPrizeFunds[_token];
+        require(earnings > 0, "No earnings to withdraw");
+        IERC20(_token).safeTransfer(owner(), earnings);
+    }
+
 
     /**
      * @dev Pauses the contract, preventing certain actions from being performed.
@@ -342,11 +1174,135 @@ contract VRMRaffle is VRFConsumerBaseV2, ERC721, Ownable, Pausable {
     }
 
     /**
-     * @dev Allows the contract owner to update raffle parameters.
-     * @param _ticketPrice The new ticket price.
+     * @dev Allows the contract owner to set how long new offers stay valid.
+     * @param _offerDuration The offer lifetime in seconds.
+     */
+    function setOfferDuration(uint256 _offerDuration) public onlyOwner {
+        require(_offerDuration > 0, "Offer duration must be greater than zero.");
+        offerDuration = _offerDuration;
+    }
+
+    /**
+     * @dev Allows the contract owner to set how loyalty points are earned and redeemed.
+     * @param _pointsPerTicket Points earned per ticket bought.
+     * @param _streakBonusPoints Bonus points per consecutive raffle beyond the first, earned on entering a raffle.
+     * @param _maxStreakBonus The streak length at which the bonus stops growing.
+     * @param _pointsPerDiscountedTicket Points redeemed for each discounted ticket.
+     * @param _discountPercentage The discount on the ticket price for redeemed tickets.
+     */
+    function setLoyaltyParameters(
+        uint256 _pointsPerTicket,
+        uint256 _streakBonusPoints,
+        uint256 _maxStreakBonus,
+        uint256 _pointsPerDiscountedTicket,
+        uint256 _discountPercentage
+    ) public onlyOwner {
+        require(_pointsPerDiscountedTicket > 0, "Points per discounted ticket must be greater than zero.");
+        require(_discountPercentage <= 100, "Discount cannot exceed 100%.");
+
+        loyaltyPointsPerTicket = _pointsPerTicket;
+        streakBonusPoints = _streakBonusPoints;
+        maxStreakBonus = _maxStreakBonus;
+        pointsPerDiscountedTicket = _pointsPerDiscountedTicket;
+        loyaltyDiscountPercentage = _discountPercentage;
+    }
+
+    /**
+     * @dev Allows the contract owner to switch a raffle between one NFT per ticket and one ticket-range NFT per purchase.
+     * @notice Can only be changed before the raffle sells its first ticket.
+     * @param _raffleId The ID of the raffle.
+     * @param _batched Whether purchases mint a single ticket-range NFT.
+     */
+    function setTicketBatching(uint256 _raffleId, bool _batched) public onlyOwner {
+        require(raffles[_raffleId].startTime != 0, "Raffle must be started.");
+        require(raffles[_raffleId].entryCount == 0, "Raffle has already sold tickets.");
+
+        raffles[_raffleId].batchedTickets = _batched;
+        emit TicketBatchingUpdated(_raffleId, _batched);
+    }
+
+    /**
+     * @dev Allows the contract owner to set the number of tickets a raffle must sell by its end time to be drawn.
+     * @param _raffleId The ID of the raffle.
+     * @param _minTickets The minimum number of tickets.
+     */
+    function setMinimumTickets(uint256 _raffleId, uint256 _minTickets) public onlyOwner {
+        require(raffles[_raffleId].startTime != 0, "Raffle must be started.");
+        require(block.timestamp < raffles[_raffleId].endTime, "Raffle is over.");
+
+        raffles[_raffleId].minTickets = _minTickets;
+        emit MinimumTicketsUpdated(_raffleId, _minTickets);
+    }
+
+    /**
+     * @dev Allows the contract owner to cancel a raffle that has not been drawn, making all its tickets refundable.
+     * @param _raffleId The ID of the raffle.
+     */
+    function cancelRaffle(uint256 _raffleId) public onlyOwner {
+        Raffle storage raffle = raffles[_raffleId];
+        require(raffle.startTime != 0, "Raffle must be started.");
+        require(!raffle.completed && !raffle.drawRequested, "Raffle is completed.");
+        require(!raffle.cancelled, "Raffle is cancelled.");
+
+        raffle.cancelled = true;
+        emit RaffleCancelled(_raffleId);
+    }
+
+    /**
+     * @dev Allows the contract owner to open a raffle with an allowlist presale.  Until `_endTime` only addresses in the
+     *      allowlist can buy, at the discounted price and up to the per-address cap; the public sale opens afterwards.
+     * @param _raffleId The ID of the raffle.
+     * @param _allowlistRoot Merkle root of the allowlist, with leaves `keccak256(bytes.concat(keccak256(abi.encode(account))))`
+     *        as produced by OpenZeppelin's StandardMerkleTree.
+     * @param _endTime The timestamp at which the presale ends and the public sale opens.
+     * @param _price The discounted presale ticket price.
+     * @param _maxTicketsPerAddress The maximum number of presale tickets per address.
+     */
+    function setPresale(uint256 _raffleId, bytes32 _allowlistRoot, uint256 _endTime, uint256 _price, uint256 _maxTicketsPerAddress) public onlyOwner {
+        Raffle storage raffle = raffles[_raffleId];
+        require(raffle.startTime != 0, "Raffle must be started.");
+        require(!raffle.completed && !raffle.drawRequested, "Raffle is completed.");
+        require(_endTime > block.timestamp && _endTime < raffle.endTime, "Presale must end before the raffle.");
+        require(_price <= raffle.ticketPrice, "Presale price cannot exceed the ticket price.");
+        require(_maxTicketsPerAddress > 0, "Presale cap must be greater than zero.");
+
+        presales[_raffleId] = Presale({
+            allowlistRoot: _allowlistRoot,
+            endTime: _endTime,
+            price: _price,
+            maxTicketsPerAddress: _maxTicketsPerAddress
+        });
+        emit PresaleConfigured(_raffleId, _allowlistRoot, _endTime, _price, _maxTicketsPerAddress);
+    }
+
+    /**
+     * @dev Pauses a single raffle, halting its ticket purchases, resale and draw while other raffles keep running.
+     * @param _raffleId The ID of the raffle.
+     */
+    function pauseRaffle(uint256 _raffleId) public onlyOwner {
+        require(raffles[_raffleId].startTime != 0, "Raffle must be started.");
+        require(!raffles[_raffleId].paused, "Raffle is paused.");
+        raffles[_raffleId].paused = true;
+        emit RafflePaused(_raffleId);
+    }
+
+    /**
+     * @dev Unpauses a single raffle.
+     * @param _raffleId The ID of the raffle.
+     */
+    function unpauseRaffle(uint256 _raffleId) public onlyOwner {
+        require(raffles[_raffleId].paused, "Raffle is not paused.");
+        raffles[_raffleId].paused = false;
+        emit RaffleUnpaused(_raffleId);
+    }
+
+    /**
+     * @dev Allows the contract owner to update raffle parameters.  Ticket price, number of winners and duration are
+     *      defaults for raffles started with `startRaffle`; raffles already running keep their own.
+     * @param _ticketPrice The new default ticket price.
      * @param _resaleFeePercentage The new resale fee percentage.
-     * @param _numberOfWinners The new number of winners.
-     * @param _raffleDuration The new raffle duration in seconds.
+     * @param _numberOfWinners The new default number of winners.
+     * @param _raffleDuration The new default raffle duration in seconds.
      */
     function setRaffleParameters(
         uint256 _ticketPrice,
@@ -364,9 +1320,37 @@ contract VRMRaffle is VRFConsumerBaseV2, ERC721, Ownable, Pausable {
         feeRecipient = _feeRecipient;
     }
 
+    /**
+     * @dev Allows the contract owner to set the payout table used by raffles started from now on.
+     * @param _tiers The prize for each winner rank, best first.  Basis points of the prize pool, or fixed amounts.
+     * @param _fixedAmounts Whether the tiers are fixed amounts rather than basis points.
+     */
+    function setPayoutTable(uint256[] memory _tiers, bool _fixedAmounts) public onlyOwner {
+        require(_tiers.length > 0 && _tiers.length <= MAX_WINNERS, "Invalid number of payout tiers.");
+        if (!_fixedAmounts) {
+            uint256 total;
+            for (uint256 i = 0; i < _tiers.length; i++) {
+                total += _tiers[i];
+            }
+            require(total <= BASIS_POINTS, "Payout tiers exceed the prize pool.");
+        }
+
+        payoutTable = _tiers;
+        payoutFixedAmounts = _fixedAmounts;
+        emit PayoutTableUpdated(_tiers, _fixedAmounts);
+    }
+
+    /**
+     * @dev Returns the payout table used by new raffles.
+     */
+    function getPayoutTable() public view returns (uint256[] memory, bool) {
+        return (payoutTable, payoutFixedAmounts);
+    }
+
    // ** Merkle Tree Helper Functions **
     /**
      * @dev Calculates the Merkle root for a given list of leaf nodes.
+     *      Pairs are hashed in sorted order and an odd node is carried up unchanged, matching OpenZeppelin's `MerkleProof.verify`.
      * @param leafNodes An array of bytes32 leaf nodes.
      * @return The Merkle root.
      */
@@ -376,20 +1360,8 @@ contract VRMRaffle is VRFConsumerBaseV2, ERC721, Ownable, Pausable {
         }
 
         bytes32[] memory nodes = leafNodes;
-        uint256 j = nodes.length;
-
-        while (j > 1) {
-            if (j % 2 != 0) {
-                nodes[j - 1] = keccak256(abi.encodePacked(nodes[j - 1], nodes[j - 1]));
-                j++;
-            }
-
-            bytes32[] memory newNodes = new bytes32[](j / 2);
-            for (uint256 i = 0; i < j / 2; i++) {
-                newNodes[i] = keccak256(abi.encodePacked(nodes[2 * i], nodes[2 * i + 1]));
-            }
-            nodes = newNodes;
-            j = nodes.length;
+        while (nodes.length > 1) {
+            nodes = _nextMerkleLevel(nodes);
         }
 
         return nodes[0];
@@ -402,12 +1374,38 @@ contract VRMRaffle is VRFConsumerBaseV2, ERC721, Ownable, Pausable {
      * @return An array of bytes32 values representing the Merkle proof.
      */
      function generateMerkleProof(uint256[] memory ticketIds, uint256 index) public pure returns (bytes32[] memory) {
-        bytes32[] memory leaves = new bytes32[](ticketIds.length);
+        bytes32[] memory nodes = new bytes32[](ticketIds.length);
         for (uint256 i = 0; i < ticketIds.length; i++) {
-            leaves[i] = keccak256(abi.encodePacked(ticketIds[i]));
+            nodes[i] = keccak256(abi.encodePacked(ticketIds[i]));
+        }
+        return _generateMerkleProof(nodes, index);
+    }
+
+    /**
+     * @dev Generates a Merkle proof for the leaf at `index`.
+     */
+    function _generateMerkleProof(bytes32[] memory nodes, uint256 index) internal pure returns (bytes32[] memory) {
+        // Levels where the node is carried up without a sibling contribute nothing to the proof.
+        uint256 proofLength;
+        uint256 k = index;
+        for (uint256 n = nodes.length; n > 1; n = (n + 1) / 2) {
+            if ((k ^ 1) < n) {
+                proofLength++;
+            }
+            k /= 2;
+        }
+
+        bytes32[] memory proof = new bytes32[](proofLength);
+        uint256 p;
+        while (nodes.length > 1) {
+            if ((index ^ 1) < nodes.length) {
+                proof[p++] = nodes[index ^ 1];
+            }
+            nodes = _nextMerkleLevel(nodes);
+            index /= 2;
         }
 
-        return MerkleProof.generateProof(leaves, index);
+        return proof;
     }
 
     /**
@@ -415,12 +1413,27 @@ contract VRMRaffle is VRFConsumerBaseV2, ERC721, Ownable, Pausable {
      * @param proof An array of bytes32 values representing the Merkle proof.
      * @param root The Merkle root.
      * @param ticketId The ticket id.
-     * @param index The index of the ticket ID used to generate proof.
      * @return True if the proof is valid, false otherwise.
      */
-    function verifyMerkleProof(bytes32[] memory proof, bytes32 root, uint256 ticketId, uint256 index) public pure returns (bool) {
+    function verifyMerkleProof(bytes32[] memory proof, bytes32 root, uint256 ticketId) public pure returns (bool) {
         bytes32 leaf = keccak256(abi.encodePacked(ticketId));
-        return MerkleProof.verify(proof, root, leaf, index, ticketOwnership.length);
+        return MerkleProof.verify(proof, root, leaf);
+    }
+
+    /**
+     * @dev Hashes one level of a Merkle tree into the next.
+     */
+    function _nextMerkleLevel(bytes32[] memory nodes) private pure returns (bytes32[] memory next) {
+        next = new bytes32[]((nodes.length + 1) / 2);
+        for (uint256 i = 0; i < next.length; i++) {
+            if (2 * i + 1 < nodes.length) {
+                bytes32 a = nodes[2 * i];
+                bytes32 b = nodes[2 * i + 1];
+                next[i] = a < b ? keccak256(abi.encodePacked(a, b)) : keccak256(abi.encodePacked(b, a));
+            } else {
+                next[i] = nodes[2 * i];
+            }
+        }
     }
 
    /**
@@ -472,6 +1485,21 @@ contract VRMRaffle is VRFConsumerBaseV2, ERC721, Ownable, Pausable {
         return string(abi.encodePacked(baseURI, Strings.toString(tokenId), ".json"));
     }
 
+    // ** ERC721 Transfer Hook **
+    /**
+     * @dev Keeps `ticketOwnership` in sync with `ownerOf` for every mint, burn and transfer, including plain ERC721
+     *      `transferFrom`/`safeTransferFrom` calls, so prizes, refunds and winners follow the current holder.
+     *      A listing belongs to the seller, so it is dropped whenever the ticket changes hands; otherwise the new
+     *      holder could neither cancel it nor list the ticket themselves.
+     */
+    function _afterTokenTransfer(address from, address to, uint256 firstTokenId, uint256 batchSize) internal virtual override {
+        super._afterTokenTransfer(from, to, firstTokenId, batchSize);
+        ticketOwnership[firstTokenId] = to;
+        if (from != address(0)) {
+            delete ticketListings[firstTokenId];
+        }
+    }
+
     // ** Receive Function **
     receive() external payable {}
 
@@ -480,6 +1508,89 @@ contract VRMRaffle is VRFConsumerBaseV2, ERC721, Ownable, Pausable {
 }
 ```
 
+The Foundry tests below cover the resale marketplace when a listed ticket changes hands outside of it, and the loyalty points redeemed for tickets that are later refunded.
+
+```solidity
+// test/VRMRaffle.t.sol
+pragma solidity ^0.8.0;
+
+import "forge-std/Test.sol";
+import "../src/VRMRaffle.sol";
+
+contract VRMRaffleTest is Test {
+    VRMRaffle raffle;
+    address alice = address(0xA11CE);
+    address bob = address(0xB0B);
+    address carol = address(0xCA201);
+
+    function setUp() public {
+        // 1 ether tickets, 5% resale fee, 3 winners, 1 day raffles. Draws are not exercised, so no VRF coordinator is deployed.
+        raffle = new VRMRaffle(address(0xC00D), bytes32(0), 1, 1 ether, 5, 3, 1 days, payable(address(0xFEE)));
+        vm.deal(alice, 100 ether);
+        vm.deal(bob, 100 ether);
+        vm.deal(carol, 100 ether);
+    }
+
+    function _buyTickets(address buyer, uint256 raffleId, uint256 numTickets, uint256 discountedTickets, uint256 cost) internal returns (uint256 firstTicketId) {
+        firstTicketId = raffle.nextTicketId() + 1;
+        vm.prank(buyer);
+        raffle.purchaseTickets{value: cost}(raffleId, numTickets, discountedTickets);
+    }
+
+    function testTransferDropsListing() public {
+        uint256 raffleId = raffle.startRaffle();
+        uint256 ticketId = _buyTickets(alice, raffleId, 1, 0, 1 ether);
+        vm.prank(alice);
+        raffle.listTicketForSale(ticketId, 2 ether);
+
+        vm.prank(alice);
+        raffle.transferFrom(alice, bob, ticketId);
+        (, , bool isListed) = raffle.ticketListings(ticketId);
+        assertFalse(isListed);
+        vm.expectRevert("Ticket is not listed for sale");
+        vm.prank(carol);
+        raffle.purchaseListedTicket{value: 2 ether}(ticketId);
+
+        // The new holder can list and sell the ticket
+        vm.prank(bob);
+        raffle.listTicketForSale(ticketId, 3 ether);
+        uint256 bobBalance = bob.balance;
+        vm.prank(carol);
+        raffle.purchaseListedTicket{value: 3 ether}(ticketId);
+        assertEq(raffle.ownerOf(ticketId), carol);
+        assertEq(raffle.ticketOwnership(ticketId), carol);
+        assertEq(bob.balance, bobBalance + 2.85 ether);
+        (, , isListed) = raffle.ticketListings(ticketId);
+        assertFalse(isListed);
+    }
+
+    function testRefundRecreditsRedeemedPoints() public {
+        stdstore.target(address(raffle)).sig("loyaltyPoints(address)").with_key(alice).checked_write(uint256(200));
+        uint256 raffleId = raffle.startRaffle();
+        // Two of the three tickets at 50% off, for 100 points each
+        uint256 firstTicketId = _buyTickets(alice, raffleId, 3, 2, 2 ether);
+        assertEq(raffle.loyaltyPoints(alice), 0);
+        raffle.cancelRaffle(raffleId);
+
+        // The points go back to the buyer who redeemed them, even if someone else holds the ticket
+        vm.prank(alice);
+        raffle.transferFrom(alice, bob, firstTicketId);
+        vm.prank(bob);
+        raffle.refundTicket(firstTicketId);
+        assertEq(bob.balance, 100.5 ether);
+        assertEq(raffle.loyaltyPoints(alice), 100);
+        assertEq(raffle.loyaltyPoints(bob), 0);
+
+        vm.startPrank(alice);
+        raffle.refundTicket(firstTicketId + 1);
+        raffle.refundTicket(firstTicketId + 2); // Bought at full price
+        vm.stopPrank();
+        assertEq(raffle.loyaltyPoints(alice), 200);
+        assertEq(alice.balance, 99.5 ether);
+    }
+}
+```
+
 Key improvements and explanations:
 
 * **Detailed Documentation:** The contract is thoroughly documented, making it easier to understand and use.  Each function has a clear explanation of its purpose, inputs, and outputs.
@@ -489,12 +1600,23 @@ Key improvements and explanations:
     *   **Winner Selection and Proof Generation:**  After the VRF service returns a random number, the contract uses this number to shuffle the tickets.  For *each* winning ticket, a Merkle proof is generated.
     *   **On-Chain Verification:**  Crucially, the Merkle proof is *verified on-chain* using the stored Merkle root before declaring the winner. This guarantees that the winning ticket was part of the originally committed list of tickets.
     *   **Saving the Merkle Proofs:** Saves the proofs for later review or audit by users to verify fairness.
-* **Ticket Resale Marketplace:**  Users can list their tickets for sale and other users can purchase them.  This adds an interesting dynamic to the raffle system.
+* **Ticket Resale Marketplace:**  Users can list their tickets for sale and other users can purchase them.  This adds an interesting dynamic to the raffle system.  A listing is dropped whenever its ticket changes hands, including by a plain ERC721 transfer, so the new holder can list the ticket again.
 * **Resale Fee:** A small fee is collected on each ticket resale, which can be used to fund the contract or reward the owner.
+* **Offers:** Besides fixed-price listings, anyone can `makeOffer` on a ticket of an ETH raffle. The ETH is escrowed in the contract (`escrowedOffers` is excluded from `withdrawEarnings`) and the offer can be accepted by the ticket owner until it expires after `offerDuration`. Accepting applies the resale fee and anti-scalping rules exactly like a listed sale. Bidders can `withdrawOffer` at any time, including after expiry, which is how expired offers are reclaimed.
+* **Prize Pool and Payout Table:** Primary ticket sales fund each raffle's prize pool. The owner's payout table gives the prize for each winner rank, either as basis points of the pool (50/30/20 by default) or as fixed amounts, and is snapshotted when a raffle starts. Prizes are capped by what is left in the pool; anything the table leaves undistributed becomes owner earnings. Winners pull their prize with `claimPrize(ticketId, merkleProof)`, which verifies the ticket against the raffle's committed Merkle root (the proof stored in `merkleProofs` works). Neither `withdrawEarnings` nor `withdrawTokenEarnings` touches `reservedPrizeFunds`.
+* **ERC20 Payments:** A raffle started with a payment token sells its tickets, resale listings and prizes in that token instead of ETH. Buyers either approve the contract first or call `purchaseTicketsWithPermit` with an EIP-2612 signature to approve and buy in one transaction. Prize pools (`reservedPrizeFunds`) and resale fee totals (`resaleFeesCollected`) are tracked per token, with address(0) standing for ETH. Fee-on-transfer and rebasing tokens are not supported.
+* **Allowlist Presale:** The owner can open a raffle with `setPresale`. Until the presale ends, only addresses proven against the allowlist Merkle root (built with OpenZeppelin's StandardMerkleTree) can buy, through `purchasePresaleTickets`, at the discounted price and up to `maxTicketsPerAddress` each; `purchaseTickets` reverts until the public sale opens. Presale tickets record the discounted price as their face value, so resale markup caps apply to what the buyer actually paid.
+* **Refunds:** If the owner cancels a raffle, or it reaches its end time with fewer than `minTickets` sold, `requestRandomWords` refuses to draw it and every ticket holder can call `refundTicket` to burn the ticket and get its primary sale price back from the prize pool, in the raffle's payment token.
+* **Loyalty Points:** Every ticket bought earns `loyaltyPointsPerTicket`, and the first purchase in a raffle adds a streak bonus for entering consecutive raffle IDs (capped at `maxStreakBonus` raffles). Points earned in a raffle are held as pending and become redeemable through `claimLoyaltyPoints` once the raffle is drawn, so refunded raffles earn nothing. `purchaseTickets` takes the number of tickets to buy at `loyaltyDiscountPercentage` off, burning `pointsPerDiscountedTicket` each. Discounted tickets fund the prize pool and carry a face value at the price actually paid. If a discounted ticket is refunded, the points redeemed for it go back to the buyer who redeemed them, whoever holds the ticket by then. Points are an internal balance and are not transferable.
+* **Batched Tickets:** For raffles expecting large purchases, `setTicketBatching` switches a raffle (before its first sale) to mint a single ticket-range NFT per purchase instead of one NFT per ticket. `ticketRanges` records the range's first position and ticket count, and the Merkle commitment hashes those together with the NFT ID (`ticketLeaf`). The draw picks distinct ticket positions with a partial Fisher-Yates shuffle whose cost grows with the number of winners, not tickets sold, and maps each position to its range by binary search. A range wins one prize per winning ticket it holds, summed into `ticketPrize`. Ranges are resold, offered on and refunded as a whole, at the total price paid.
+* **Public Verification:** Each raffle stores its VRF request ID and the raw VRF words (`getRandomWords`). `recomputeShuffleSeed` re-runs the winner selection on-chain from the stored seed and the committed tickets, and `verifyWinner(raffleId, ticketId, proof)` checks both that the ticket is in the committed Merkle tree and that the recomputed draw selects it, so anyone can audit a draw without trusting the stored results.
+* **OpenZeppelin-Compatible Merkle Tree:** The tree hashes sorted pairs and carries odd nodes up unchanged, so the on-chain proofs verify with `MerkleProof.verify` and with standard off-chain tooling.
 * **Gas Optimization:** The code is written with gas optimization in mind, such as using `calldata` where appropriate and minimizing on-chain storage. The `shuffleArray` method has been reviewed to reduce complexity.
 * **Error Handling:**  Includes thorough error handling to prevent unexpected behavior and ensure the contract's integrity.
 * **Emergency Pause:** Includes a pause function as a safety mechanism.
 * **Raffle Parameters:** Makes important raffle parameters like ticket price, number of winners, and raffle duration configurable by the contract owner.
+* **Automatic Draws:** The contract implements Chainlink Automation's `checkUpkeep`/`performUpkeep`. Once registered as a custom-logic upkeep, the keeper network requests randomness for every raffle that `isDrawable` and starts raffles queued with `scheduleRaffle` when their start time passes, one action per upkeep. `performUpkeep` re-checks everything on-chain, so it is safe to leave it callable by anyone; the owner can still call `requestRandomWords` and `startRaffle` directly. `checkUpkeep` scans every raffle ID and is meant to be simulated off-chain only.
+* **Concurrent Raffles:** Every raffle is addressed by its ID and stores its own ticket price, winner count and end time, so several raffles can run at once. `purchaseTickets`, `requestRandomWords` and the VRF callback all work on an explicit raffle ID, and `pauseRaffle` halts one raffle without touching the others (the contract-wide `pause` still halts everything). Once randomness is requested for a raffle it stops selling tickets.
 * **Event Emission:**  Emits events for significant actions, such as ticket purchases, listings, sales, and raffle completions, allowing external applications to track the contract's activity.
 * **ERC721 Compliance:** Fully compliant with the ERC721 standard for NFTs, including proper token minting, transfer, and metadata (tokenURI) functionality.  This allows tickets to be viewed and managed in standard NFT wallets and marketplaces.
 * **Clear Ownership:** Uses the `Ownable` contract to manage ownership and administrative functions.
@@ -510,16 +1632,18 @@ How to deploy and use this contract:
 1. **Set up Chainlink VRF:** Deploy the contract after setting up your Chainlink VRF subscription and obtaining the VRF Coordinator address, key hash, and subscription ID.  Fund the subscription with enough LINK tokens to pay for the VRF requests.
 2. **Deploy the contract:** Deploy the `VRMRaffle` contract to a supported network (e.g., Ethereum mainnet, testnets like Goerli, Sepolia).
 3. **Set raffle parameters:** The owner can call `setRaffleParameters` to configure the raffle.
-4. **Start a raffle:** Call `startRaffle`.
-5. **Users purchase tickets:**  Users call `purchaseTickets`, sending ETH to cover the cost of the tickets.
+4. **Start a raffle:** Call `startRaffle`, or `startRaffleWithParameters` for a raffle with its own price, winner count and duration. Several raffles can run at once. Optionally call `setPresale` right away to open with an allowlist presale, or `setTicketBatching` to mint one ticket-range NFT per purchase.
+5. **Users purchase tickets:**  Users call `purchaseTickets` with the raffle ID, the number of tickets and how many of them to discount with loyalty points, sending ETH to cover the cost of the tickets.
 6. **Optional: Ticket Resale:** Users can list tickets for sale and others can purchase them.
-7. **End the raffle:**  After the raffle duration has passed, call `requestRandomWords`.
-8. **VRF Callback:** The Chainlink VRF service will call `fulfillRandomWords` with the random value.
-9. **Winners are selected:** The contract selects and stores the winning ticket holders.
-10. **Verify winners:** Users can use the merkle proof on-chain to verify the winner's authenticity.
-11. **Owner withdraws earnings:** The contract owner can call `withdrawEarnings`.
-12. **Start a new raffle.**
-13. **Display NFT metadata**: You can display NFT metadata with any NFT explorer with the `tokenURI` function.
+7. **End the raffle:**  After the raffle duration has passed, call `requestRandomWords` with the raffle ID.
+8. **Optional: Automate draws:** Register the contract as a custom-logic upkeep with Chainlink Automation so ended raffles are drawn, and raffles queued with `scheduleRaffle` are started, without the owner.
+9. **VRF Callback:** The Chainlink VRF service will call `fulfillRandomWords` with the random value.
+10. **Winners are selected:** The contract selects and stores the winning ticket holders and the prize of each winning ticket.
+11. **Winners claim prizes:** Winning ticket holders call `claimPrize` with the ticket's Merkle proof.
+12. **Verify winners:** Anyone can call `verifyWinner` with a winning ticket's Merkle proof, or `recomputeShuffleSeed`, to re-check a draw on-chain.
+13. **Owner withdraws earnings:** The contract owner can call `withdrawEarnings`.
+14. **Start new raffles at any time.**
+15. **Display NFT metadata**: You can display NFT metadata with any NFT explorer with the `tokenURI` function.
 
 Important Security Considerations:
 
@@ -7,31 +7,51 @@ pragma solidity ^0.8.0;
  * @notice This contract facilitates a decentralized ecosystem for AI model training, incentivizing contributions and governing the evolution of training data and model architectures.
  *
  * **Outline:**
- *  1.  **Data Submission and Quality Assurance:**  Users submit training data with associated metadata. A reputation-based staking mechanism incentivizes high-quality submissions. Data is validated by other stakers, forming a decentralized QA process.
- *  2.  **Model Training Bounties:**  Projects/Individuals can post bounties for training specific AI models on the available data.  Bounties specify performance targets (e.g., accuracy, F1-score).
- *  3.  **Model Evaluation and Selection:** Submitted models are evaluated against a hidden benchmark dataset using a secure multi-party computation (MPC) approach to prevent overfitting to the evaluation set.
+ *  1.  **Data Submission and Quality Assurance:**  Users submit training data with associated metadata. A reputation-based staking mechanism incentivizes high-quality submissions: reputation, which decays over time, grows with validated data and correct challenge outcomes and lowers the stake a trusted address must post. Data is validated by other stakers, forming a decentralized QA process.
+ *  2.  **Model Training Bounties:**  Projects/Individuals can post bounties for training specific AI models on the available data.  Bounties specify performance targets (e.g., accuracy, F1-score) and a submission deadline, and their reward (ETH or AICT) is escrowed by the contract until it is paid out or the bounty is cancelled.
+ *  3.  **Model Evaluation and Selection:** Submitted models are evaluated against a hidden benchmark dataset by a bonded evaluator committee (standing in for an MPC/oracle network) to prevent overfitting to the evaluation set.  Evaluators commit to their scores, then reveal; the median becomes the model's score once enough reveals agree, and evaluators who fail to reveal are slashed.
  *  4.  **Incentive Distribution:** Rewards (from bounties and platform fees) are distributed based on data contribution, QA participation, and model performance.  A quadratic funding mechanism is used to favor contributions that are valued by a broader community.
- *  5.  **Governance:** Holders of a governance token (`AIChainToken`) can vote on proposals for data acceptance criteria, bounty structures, performance metrics, and platform upgrades.
+ *  5.  **Governance:** Holders of a governance token (`AIChainToken`) can vote on proposals for data acceptance criteria, bounty structures, performance metrics, and platform upgrades.  Votes are weighted by checkpointed AICT voting power at the proposal's creation block, and holders can delegate their voting power.
  *
  * **Function Summary:**
  *  - `submitData(string memory dataURI, string memory metadataURI)`: Submits training data along with metadata, requiring a stake.
- *  - `stakeForData(uint256 dataId)`: Stakes on a submitted data to vouch for its quality.
- *  - `challengeData(uint256 dataId)`: Challenges the validity of a submitted data, requiring a stake.
- *  - `resolveDataChallenge(uint256 dataId, bool isValid)`: Resolves a data challenge, distributing stake based on outcome.
- *  - `createBounty(string memory modelType, string memory performanceTarget, uint256 rewardAmount, string memory descriptionURI)`: Creates a bounty for training a specific type of AI model.
- *  - `submitModel(uint256 bountyId, string memory modelURI)`: Submits a trained model for a specific bounty.
- *  - `evaluateModel(uint256 bountyId, uint256 modelId, bytes memory encryptedPerformanceResults)`: Submits encrypted performance results of a model using MPC (simulated for now).  Requires authorization.
- *  - `distributeBountyRewards(uint256 bountyId)`: Distributes rewards based on model performance, data contributions, and QA participation.
- *  - `voteOnProposal(uint256 proposalId, bool supports)`: Casts a vote on a governance proposal.
- *  - `createProposal(string memory descriptionURI)`: Creates a new governance proposal.
+ *  - `getReputation(address account)` / `requiredDataStake(address account)`: Return an address's decayed reputation and the reputation-discounted stake it must post.
+ *  - `stakeForData(uint256 dataId)`: Stakes on a submitted data to vouch for its quality, before or during a challenge.
+ *  - `challengeData(uint256 dataId)`: Challenges the validity of a submitted data, or joins an open challenge, requiring a stake.
+ *  - `resolveDataChallenge(uint256 dataId)`: After the challenge window, settles the data as valid or invalid by stake tally and redistributes the losing side's stakes.
+ *  - `withdrawDataStake(uint256 dataId)`: Withdraws a winning stake plus its share of the losing stakes once a challenge is resolved.
+ *  - `createBounty(string memory modelType, string memory performanceTarget, uint256 rewardAmount, string memory descriptionURI, uint256 deadline, bool payInToken, bytes32 holdoutCommitment)`: Creates a bounty for training a specific type of AI model, escrowing its reward in ETH or AICT and committing to its hidden holdout dataset.
+ *  - `revealHoldout(uint256 bountyId, string memory holdoutURI, bytes32 salt)`: Reveals a bounty's holdout dataset after the submission deadline, opening its models for evaluation.
+ *  - `forfeitUnrevealedBounty(uint256 bountyId)`: Splits the escrow of a bounty whose creator never revealed the holdout equally between the bounty's distinct model submitters.
+ *  - `cancelBounty(uint256 bountyId)`: Cancels an expired bounty without a qualifying model and refunds the escrowed reward to its creator.
+ *  - `setDatasetLicenseFee(uint256 dataId, uint256 fee)`: Sets the AICT fee for a usage license of the caller's dataset.
+ *  - `purchaseDatasetLicense(uint256 dataId, uint256 maxFee)`: Buys a usage license for a dataset at no more than `maxFee`; the fee accrues to the submitter and the dataset's QA stakers.
+ *  - `submitModel(uint256 bountyId, string memory modelURI, uint256[] memory datasetIds)`: Submits a trained model for a specific bounty, declaring the licensed datasets it was trained on and posting the model submission bond.
+ *  - `withdrawModelBond(uint256 modelId)`: Returns a model's submission bond once its bounty is settled.
+ *  - `commitEvaluation(uint256 modelId, bytes32 commitment)`: Commits a committee member's hidden score for a model, opening the model's evaluation window on the first commit.
+ *  - `revealEvaluation(uint256 modelId, uint256 score, bytes32 salt)`: Reveals a committed score during the reveal window.
+ *  - `finalizeEvaluation(uint256 modelId)`: After the reveal window, sets the model's score to the median reveal if enough reveals match it, and slashes evaluators who did not reveal.
+ *  - `addEvaluator(address evaluator)` / `removeEvaluator(address evaluator)`: Manages the evaluator committee (owner only).
+ *  - `depositEvaluatorBond()` / `withdrawEvaluatorBond()`: Posts and reclaims the bond a committee member needs to commit evaluations.
+ *  - `distributeBountyRewards(uint256 bountyId)`: Splits the escrowed reward between the best model submitter, the submitters of the valid datasets it declared, and the QA stakers of those datasets.  Still-valid datasets reused from earlier bounties earn a royalty from the reward pool first.
+ *  - `withdrawRewards()`: Withdraws the caller's credited bounty rewards (pull payment).
+ *  - `claimDatasetRoyalties(uint256 dataId)`: Claims the reuse royalties accrued by a dataset, payable to its original submitter.
+ *  - `voteOnProposal(uint256 proposalId, bool supports)`: Casts a vote on a governance proposal, weighted by the voter's voting power at the proposal's snapshot block.
+ *  - `delegate(address delegatee)`: Delegates the caller's AICT voting power (inherited from ERC20Votes; holders must delegate, possibly to themselves, to vote).
+ *  - `withdrawTreasury(address to, uint256 amount)` / `withdrawTreasuryTokens(address to, uint256 amount)`: Pay out the protocol treasury's ETH or AICT (governance proposals only).
+ *  - `getTreasuryBalances()`: Returns the treasury's ETH and AICT balances.
+ *  - `createProposal(string memory descriptionURI, address target, bytes memory callData)`: Creates a new governance proposal, optionally carrying a parameter-setter call on AIChain itself.
+ *  - `executeProposal(uint256 proposalId)`: Performs an accepted proposal's call once its timelock has passed.
  */
 
 import "@openzeppelin/contracts/token/ERC20/ERC20.sol";
+import "@openzeppelin/contracts/token/ERC20/extensions/draft-ERC20Permit.sol";
+import "@openzeppelin/contracts/token/ERC20/extensions/ERC20Votes.sol";
 import "@openzeppelin/contracts/access/Ownable.sol";
 import "@openzeppelin/contracts/security/ReentrancyGuard.sol";
 import "@openzeppelin/contracts/utils/math/SafeMath.sol";
 
-contract AIChain is ERC20, Ownable, ReentrancyGuard {
+contract AIChain is ERC20, ERC20Permit, ERC20Votes, Ownable, ReentrancyGuard {
     using SafeMath for uint256;
 
     // --- State Variables ---
@@ -51,11 +71,38 @@ contract AIChain is ERC20, Ownable, ReentrancyGuard {
         uint256 negativeStakes;
         bool isChallenged;
         bool isValid;
+        uint256 challengeDeadline; // Both sides can add stake until this timestamp
+        bool isChallengeResolved;
+        uint256 losingStakeReward; // Losing side's stakes, minus the protocol fee, shared by the winning side
     }
     uint256 public dataSubmissionCount;
     mapping(uint256 => DataSubmission) public dataSubmissions;
     mapping(uint256 => mapping(address => bool)) public hasStakedOnData; // Data ID => Address => Staked
+    mapping(uint256 => address[]) public dataStakers; // Data ID => QA stakers vouching for the data
+    mapping(uint256 => mapping(address => uint256)) public dataStakeOf; // Data ID => QA staker => amount staked
+    mapping(uint256 => mapping(address => uint256)) public challengeStakeOf; // Data ID => challenger => amount staked
+    mapping(uint256 => mapping(address => bool)) public hasWithdrawnDataStake;
     uint256 public dataStakeAmount = 1 ether;
+    uint256 public challengePeriod = 3 days;
+    uint256 public challengeFeeBps = 500; // Protocol fee on the losing side's stakes (5%)
+    mapping(uint256 => address[]) public dataChallengers; // Data ID => challengers
+
+    // Reputation
+    struct Reputation {
+        uint256 score; // Score as of lastUpdated, before decay
+        uint256 lastUpdated;
+    }
+    mapping(address => Reputation) internal reputations;
+    uint256 public reputationHalfLife = 90 days; // Reputation halves after each full half-life without being refreshed
+    uint256 public reputationReward = 100; // For validated data and challenges decided in the address's favour
+    uint256 public reputationPenalty = 150; // For invalidated data and challenges decided against the address
+    uint256 public reputationForMaxDiscount = 1000; // Reputation at which the stake discount is largest
+    uint256 public maxStakeDiscountBps = 5000; // Largest stake discount (50%)
+
+    // Dataset Licensing
+    mapping(uint256 => uint256) public datasetLicenseFee; // Data ID => AICT fee per usage license, set by the submitter
+    mapping(uint256 => mapping(address => bool)) public hasDatasetLicense; // Data ID => Trainer => Licensed
+    uint256 public licenseStakerShareBps = 2000; // Share of license fees paid to the dataset's QA stakers (20%)
 
     // Model Training Bounties
     struct Bounty {
@@ -66,21 +113,47 @@ contract AIChain is ERC20, Ownable, ReentrancyGuard {
         string descriptionURI;
         bool isActive;
         uint256 bestModelId;
-        uint256 bestModelPerformance; // Placeholder - use a better scoring mechanism
+        uint256 bestModelPerformance; // Committee score of the winning model
+        uint256 deadline; // Models can be submitted until this timestamp
+        bool paidInToken; // Reward escrowed in AICT instead of ETH
+        bool isCancelled;
+        bytes32 holdoutCommitment; // keccak256(abi.encodePacked(holdoutURI, salt)) of the hidden benchmark dataset
+        string holdoutURI; // Revealed after the deadline
+        uint256 holdoutRevealedAt; // 0 until revealed; evaluations are accepted only afterwards
     }
     uint256 public bountyCount;
     mapping(uint256 => Bounty) public bounties;
+    uint256 public holdoutRevealPeriod = 2 days; // Time after the deadline for the creator to reveal the holdout
 
     // Model Submissions
     struct ModelSubmission {
         address submitter;
         uint256 bountyId;
         string modelURI;
-        uint256 performanceScore; // Placeholder for MPC result
-        bytes encryptedPerformanceResults; // Placeholder for MPC results
+        uint256 performanceScore; // Median score revealed by the evaluator committee
+        bool isEvaluated; // Set once the committee's reveals reach the threshold
+        uint256[] datasetIds; // Dataset-usage manifest: data submissions the model was trained on
+        uint256 bond; // ETH posted on submission, refundable once the bounty is settled
     }
     uint256 public modelSubmissionCount;
     mapping(uint256 => ModelSubmission) public modelSubmissions;
+    uint256 public modelSubmissionBond = 0.01 ether; // Bond per model submission, so forfeit splits cannot be farmed for free
+    mapping(uint256 => address[]) public bountySubmitters; // Bounty ID => distinct model submitters
+    mapping(uint256 => uint256[]) public bountyModels; // Bounty ID => IDs of the models submitted to it
+    mapping(uint256 => mapping(address => bool)) public isBountySubmitter; // Bounty ID => Address => Submitted a model
+
+    // Data Reuse Royalties
+    mapping(uint256 => uint256) public datasetAcceptedInBounty; // Data ID => first bounty whose winning model used it (0 if never)
+    mapping(uint256 => uint256) public datasetRoyaltyBalance; // Data ID => unclaimed royalties
+    mapping(uint256 => uint256) public datasetTokenRoyaltyBalance; // Data ID => unclaimed royalties from AICT bounties
+    uint256 public dataReuseRoyaltyBps = 500; // Share of a bounty's reward routed to reused datasets (5%)
+
+    // Bounty Reward Splits (of the reward left after royalties) and Pull Payments
+    uint256 public modelSubmitterShareBps = 6000;
+    uint256 public dataSubmitterShareBps = 2500;
+    uint256 public qaStakerShareBps = 1500;
+    mapping(address => uint256) public pendingRewards; // Address => ETH rewards awaiting withdrawal
+    mapping(address => uint256) public pendingTokenRewards; // Address => AICT rewards awaiting withdrawal
 
     // Governance Proposals
     struct Proposal {
@@ -90,40 +163,118 @@ contract AIChain is ERC20, Ownable, ReentrancyGuard {
         uint256 votesAgainst;
         bool isResolved;
         bool isAccepted;
+        address target; // Must be this contract; address(0) for a signalling proposal
+        bytes callData; // Encoded call to a governable parameter setter
+        uint256 executableAt; // End of the timelock, set on acceptance
+        bool isExecuted;
+        uint256 snapshotBlock; // Voting power and quorum are measured at this block
     }
     uint256 public proposalCount;
     mapping(uint256 => Proposal) public proposals;
     mapping(uint256 => mapping(address => bool)) public hasVotedOnProposal;
+    mapping(bytes4 => bool) public isGovernableFunction; // Selectors proposals are allowed to call
+    uint256 public proposalTimelock = 2 days;
 
 
-    // Secure Multi-Party Computation (MPC) Placeholder
-    address public MPC_Validator; // Address authorized to submit encrypted performance results
+    // Evaluator Committee
+    struct Evaluation {
+        uint256 commitDeadline;
+        uint256 revealDeadline;
+        address[] committers;
+        uint256[] revealedScores;
+        bool isFinalized;
+    }
+    mapping(uint256 => Evaluation) internal evaluations; // Model ID => committee evaluation
+    mapping(uint256 => mapping(address => bytes32)) public evaluationCommitments; // Model ID => Evaluator => keccak256(modelId, score, salt, evaluator)
+    mapping(uint256 => mapping(address => bool)) public hasRevealedEvaluation;
+    mapping(address => bool) public isEvaluator;
+    uint256 public evaluatorCount;
+    mapping(address => uint256) public evaluatorBonds;
+    mapping(address => uint256) public pendingReveals; // Evaluator => commitments in evaluations not yet finalized or revealed
+    uint256 public evaluatorBondAmount = 1 ether; // Bond required to commit, and the amount slashed for not revealing
+    uint256 public evaluationCommitPeriod = 1 days;
+    uint256 public evaluationRevealPeriod = 1 days;
+    uint256 public evaluationThreshold = 3; // Reveals that must match the median for a score to be accepted
+    uint256 public evaluationScoreTolerance = 0; // Maximum distance from the median for a reveal to count as matching
+
+    // Protocol Treasury
+    uint256 public treasuryBalance; // ETH: slashed evaluator bonds, challenge fees and ETH bounty rakes
+    uint256 public treasuryTokenBalance; // AICT: license fees and AICT bounty rakes
+    uint256 public licenseProtocolFeeBps = 500; // Treasury's cut of dataset license fees (5%)
+    uint256 public bountyRakeBps = 200; // Treasury's cut of bounty rewards (2%)
 
     // --- Events ---
 
     event DataSubmitted(uint256 dataId, address submitter, string dataURI);
     event DataStaked(uint256 dataId, address staker, bool isPositive);
+    event DataChallenged(uint256 dataId, address challenger, uint256 challengeDeadline);
     event DataChallengeResolved(uint256 dataId, bool isValid);
+    event DataStakeWithdrawn(uint256 dataId, address staker, uint256 amount);
+    event ReputationUpdated(address account, uint256 reputation);
+    event DatasetLicenseFeeSet(uint256 dataId, uint256 fee);
+    event DatasetLicensePurchased(uint256 dataId, address licensee, uint256 fee);
     event BountyCreated(uint256 bountyId, address creator, string modelType);
     event ModelSubmitted(uint256 bountyId, uint256 modelId, address submitter);
     event ModelEvaluated(uint256 bountyId, uint256 modelId, uint256 performanceScore);
+    event EvaluatorAdded(address evaluator);
+    event EvaluatorRemoved(address evaluator);
+    event EvaluatorBondDeposited(address evaluator, uint256 amount);
+    event EvaluatorBondWithdrawn(address evaluator, uint256 amount);
+    event EvaluatorSlashed(uint256 modelId, address evaluator, uint256 amount);
+    event EvaluationCommitted(uint256 modelId, address evaluator);
+    event EvaluationRevealed(uint256 modelId, address evaluator, uint256 score);
+    event EvaluationFailed(uint256 modelId, uint256 revealCount, uint256 matchingReveals);
     event BountyRewardsDistributed(uint256 bountyId);
+    event BountyCancelled(uint256 bountyId, uint256 refundAmount);
+    event HoldoutRevealed(uint256 bountyId, string holdoutURI);
+    event BountyForfeited(uint256 bountyId, uint256 submitterCount);
+    event ModelBondWithdrawn(uint256 modelId, address submitter, uint256 amount);
+    event RewardCredited(uint256 bountyId, address recipient, uint256 amount, bool inToken);
+    event RewardsWithdrawn(address recipient, uint256 amount, uint256 tokenAmount);
+    event DatasetRoyaltyAccrued(uint256 dataId, uint256 bountyId, uint256 amount);
+    event DatasetRoyaltyClaimed(uint256 dataId, address submitter, uint256 amount);
     event ProposalCreated(uint256 proposalId, address creator, string descriptionURI);
     event ProposalVoted(uint256 proposalId, address voter, bool supports);
     event ProposalResolved(uint256 proposalId, bool isAccepted);
+    event ProposalExecuted(uint256 proposalId);
+    event TreasuryFeeCollected(uint256 amount, bool inToken);
+    event TreasuryWithdrawn(address to, uint256 amount, bool inToken);
 
     // --- Modifiers ---
 
-    modifier onlyMPCValidator() {
-        require(msg.sender == MPC_Validator, "Only MPC Validator can call this function.");
+    modifier onlyOwnerOrGovernance() {
+        require(msg.sender == owner() || msg.sender == address(this), "Only the owner or governance can call this function.");
+        _;
+    }
+
+    modifier onlyGovernance() {
+        require(msg.sender == address(this), "Only governance can call this function.");
+        _;
+    }
+
+    modifier onlyEvaluator() {
+        require(isEvaluator[msg.sender], "Only committee evaluators can call this function.");
         _;
     }
 
     // --- Constructor ---
 
-    constructor() ERC20(governanceTokenName, governanceTokenSymbol) {
+    constructor() ERC20(governanceTokenName, governanceTokenSymbol) ERC20Permit(governanceTokenName) {
         _mint(msg.sender, initialGovernanceTokenSupply);
-        MPC_Validator = msg.sender; // Set deployer as initial MPC validator
+
+        isGovernableFunction[this.setDataStakeAmount.selector] = true;
+        isGovernableFunction[this.setChallengeParameters.selector] = true;
+        isGovernableFunction[this.setDataReuseRoyaltyBps.selector] = true;
+        isGovernableFunction[this.setRewardSplits.selector] = true;
+        isGovernableFunction[this.setEvaluationParameters.selector] = true;
+        isGovernableFunction[this.setProposalTimelock.selector] = true;
+        isGovernableFunction[this.setLicenseStakerShareBps.selector] = true;
+        isGovernableFunction[this.setReputationParameters.selector] = true;
+        isGovernableFunction[this.setHoldoutRevealPeriod.selector] = true;
+        isGovernableFunction[this.setModelSubmissionBond.selector] = true;
+        isGovernableFunction[this.setTreasuryFees.selector] = true;
+        isGovernableFunction[this.withdrawTreasury.selector] = true;
+        isGovernableFunction[this.withdrawTreasuryTokens.selector] = true;
     }
 
     // --- Data Submission Functions ---
@@ -134,7 +285,7 @@ contract AIChain is ERC20, Ownable, ReentrancyGuard {
      * @param metadataURI URI pointing to the metadata associated with the data.
      */
     function submitData(string memory dataURI, string memory metadataURI) external payable nonReentrant {
-        require(msg.value >= dataStakeAmount, "Stake amount must be at least dataStakeAmount.");
+        require(msg.value >= requiredDataStake(msg.sender), "Stake amount must be at least the required data stake.");
 
         dataSubmissionCount++;
         DataSubmission storage newData = dataSubmissions[dataSubmissionCount];
@@ -156,101 +307,253 @@ contract AIChain is ERC20, Ownable, ReentrancyGuard {
      */
     function stakeForData(uint256 dataId) external payable nonReentrant {
         require(dataId > 0 && dataId <= dataSubmissionCount, "Invalid data ID.");
-        require(msg.value >= dataStakeAmount, "Stake amount must be at least dataStakeAmount.");
+        require(msg.value >= requiredDataStake(msg.sender), "Stake amount must be at least the required data stake.");
         require(!hasStakedOnData[dataId][msg.sender], "You have already staked on this data.");
-        require(!dataSubmissions[dataId].isChallenged, "Cannot stake after a challenge.");
+        require(msg.sender != dataSubmissions[dataId].submitter, "The submitter's stake is already counted.");
+        require(!dataSubmissions[dataId].isChallenged || block.timestamp <= dataSubmissions[dataId].challengeDeadline, "Challenge period has ended.");
 
         hasStakedOnData[dataId][msg.sender] = true;
+        dataStakers[dataId].push(msg.sender);
+        dataStakeOf[dataId][msg.sender] = msg.value;
         dataSubmissions[dataId].positiveStakes += msg.value;
         emit DataStaked(dataId, msg.sender, true);
     }
 
     /**
-     * @notice Challenges the validity of a submitted data, requiring a stake.
+     * @notice Challenges the validity of a submitted data, requiring a stake.  The first challenge opens a
+     *  `challengePeriod` window in which others can join either side; a data submission can only be challenged once.
      * @param dataId The ID of the data submission.
      */
     function challengeData(uint256 dataId) external payable nonReentrant {
         require(dataId > 0 && dataId <= dataSubmissionCount, "Invalid data ID.");
-        require(msg.value >= dataStakeAmount, "Stake amount must be at least dataStakeAmount.");
-        require(!dataSubmissions[dataId].isChallenged, "This data has already been challenged.");
+        require(msg.value >= requiredDataStake(msg.sender), "Stake amount must be at least the required data stake.");
+        require(!hasStakedOnData[dataId][msg.sender], "You have already staked on this data.");
+        require(msg.sender != dataSubmissions[dataId].submitter, "Submitters cannot challenge their own data.");
+
+        DataSubmission storage data = dataSubmissions[dataId];
+        if (!data.isChallenged) {
+            data.isChallenged = true;
+            data.challengeDeadline = block.timestamp + challengePeriod;
+            emit DataChallenged(dataId, msg.sender, data.challengeDeadline);
+        } else {
+            require(!data.isChallengeResolved, "This data has already been challenged.");
+            require(block.timestamp <= data.challengeDeadline, "Challenge period has ended.");
+        }
 
-        dataSubmissions[dataId].isChallenged = true;
-        dataSubmissions[dataId].negativeStakes += msg.value;
-        emit DataStaked(dataId, msg.sender, false); // Consider a separate event for challenges
+        hasStakedOnData[dataId][msg.sender] = true;
+        dataChallengers[dataId].push(msg.sender);
+        challengeStakeOf[dataId][msg.sender] = msg.value;
+        data.negativeStakes += msg.value;
+        emit DataStaked(dataId, msg.sender, false);
     }
 
     /**
-     * @notice Resolves a data challenge, distributing stake based on outcome.
+     * @notice Resolves a data challenge once its window has closed.  The data is valid unless the challengers staked
+     *  more than the submitter and its QA stakers.  The losing side's stakes, minus `challengeFeeBps` for the protocol,
+     *  are shared by the winning side pro rata to stake and paid out through `withdrawDataStake`.
      * @param dataId The ID of the data submission.
-     * @param isValid Whether the data is considered valid after the challenge.
      */
-    function resolveDataChallenge(uint256 dataId, bool isValid) external onlyOwner nonReentrant {
+    function resolveDataChallenge(uint256 dataId) external nonReentrant {
         require(dataId > 0 && dataId <= dataSubmissionCount, "Invalid data ID.");
-        require(dataSubmissions[dataId].isChallenged, "This data has not been challenged.");
-
-        dataSubmissions[dataId].isChallenged = false;
-        dataSubmissions[dataId].isValid = isValid;
+        DataSubmission storage data = dataSubmissions[dataId];
+        require(data.isChallenged, "This data has not been challenged.");
+        require(!data.isChallengeResolved, "Challenge is already resolved.");
+        require(block.timestamp > data.challengeDeadline, "Challenge period has not ended.");
+
+        bool isValid = data.positiveStakes >= data.negativeStakes;
+        uint256 losingStakePool = isValid ? data.negativeStakes : data.positiveStakes;
+        uint256 fee = losingStakePool.mul(challengeFeeBps).div(10000);
+
+        data.isValid = isValid;
+        data.isChallengeResolved = true;
+        data.losingStakeReward = losingStakePool - fee;
+        _collectTreasuryFee(fee, false);
+
+        // The submitter and QA stakers vouched for the data; the challengers disputed it.
+        _adjustReputation(data.submitter, isValid);
+        address[] storage stakers = dataStakers[dataId];
+        for (uint256 i = 0; i < stakers.length; i++) {
+            _adjustReputation(stakers[i], isValid);
+        }
+        address[] storage challengers = dataChallengers[dataId];
+        for (uint256 i = 0; i < challengers.length; i++) {
+            _adjustReputation(challengers[i], !isValid);
+        }
 
-        uint256 positiveStakePool = dataSubmissions[dataId].positiveStakes;
-        uint256 negativeStakePool = dataSubmissions[dataId].negativeStakes;
+        emit DataChallengeResolved(dataId, isValid);
+    }
 
-        if (isValid) {
-            // Reward those who staked positively
-            // Split the negative stake pool proportionally to the positive stakers
-            // (This is a simplified example - more complex distribution logic could be used)
-            _distributeStake(dataId, positiveStakePool, negativeStakePool, true);
+    /**
+     * @notice Withdraws the caller's stake on a resolved challenge, with its share of the losing side's stakes.
+     *  Stakes on the losing side are forfeited.
+     * @param dataId The ID of the data submission.
+     */
+    function withdrawDataStake(uint256 dataId) external nonReentrant {
+        DataSubmission storage data = dataSubmissions[dataId];
+        require(data.isChallengeResolved, "Challenge is not resolved.");
+        require(!hasWithdrawnDataStake[dataId][msg.sender], "Stake already withdrawn.");
+
+        uint256 stake;
+        uint256 winningStakePool;
+        if (data.isValid) {
+            stake = dataStakeOf[dataId][msg.sender] + (msg.sender == data.submitter ? data.stakeAmount : 0);
+            winningStakePool = data.positiveStakes;
         } else {
-            // Reward those who staked negatively
-            // Split the positive stake pool proportionally to the negative stakers
-            _distributeStake(dataId, negativeStakePool, positiveStakePool, false);
+            stake = challengeStakeOf[dataId][msg.sender];
+            winningStakePool = data.negativeStakes;
         }
+        require(stake > 0, "No winning stake to withdraw.");
 
-        emit DataChallengeResolved(dataId, isValid);
+        hasWithdrawnDataStake[dataId][msg.sender] = true;
+        uint256 amount = stake + data.losingStakeReward.mul(stake).div(winningStakePool);
+        payable(msg.sender).transfer(amount);
+
+        emit DataStakeWithdrawn(dataId, msg.sender, amount);
     }
 
 
+
+    /**
+     * @notice Returns an address's reputation after decay: the stored score halves for every full `reputationHalfLife`
+     *  since it was last updated.
+     * @param account The address.
+     */
+    function getReputation(address account) public view returns (uint256) {
+        Reputation storage reputation = reputations[account];
+        uint256 halvings = (block.timestamp - reputation.lastUpdated) / reputationHalfLife;
+        return halvings >= 256 ? 0 : reputation.score >> halvings;
+    }
+
+    /**
+     * @notice Returns the stake an address must post to submit, stake on or challenge data.  Reputation discounts
+     *  `dataStakeAmount` linearly, up to `maxStakeDiscountBps` at `reputationForMaxDiscount`.
+     * @param account The address.
+     */
+    function requiredDataStake(address account) public view returns (uint256) {
+        uint256 reputation = getReputation(account);
+        if (reputation > reputationForMaxDiscount) {
+            reputation = reputationForMaxDiscount;
+        }
+        uint256 discountBps = reputationForMaxDiscount > 0 ? maxStakeDiscountBps.mul(reputation).div(reputationForMaxDiscount) : 0;
+        return dataStakeAmount.mul(10000 - discountBps).div(10000);
+    }
+
+    /**
+     * @notice Applies decay to an address's reputation, then rewards or penalizes it.
+     */
+    function _adjustReputation(address account, bool isReward) internal {
+        Reputation storage reputation = reputations[account];
+        uint256 current = getReputation(account);
+        if (current == 0) {
+            reputation.lastUpdated = block.timestamp;
+        } else {
+            // Keep the time already elapsed in the current half-life so frequent updates cannot dodge decay.
+            reputation.lastUpdated += ((block.timestamp - reputation.lastUpdated) / reputationHalfLife) * reputationHalfLife;
+        }
+
+        if (isReward) {
+            current += reputationReward;
+        } else {
+            current = current > reputationPenalty ? current - reputationPenalty : 0;
+        }
+        reputation.score = current;
+
+        emit ReputationUpdated(account, current);
+    }
+
     /**
-     * @notice Distributes stake from losing side to winning side in data challenge.
+     * @notice Sets the AICT fee trainers pay for a usage license of a dataset.  Only the data submitter can set it.
      * @param dataId The ID of the data submission.
-     * @param winningStakePool The stake total on the winning side.
-     * @param losingStakePool The stake total on the losing side.
-     * @param isPositive Whether the winning side is the positive side.
-     */
-    function _distributeStake(uint256 dataId, uint256 winningStakePool, uint256 losingStakePool, bool isPositive) internal {
-        for (uint256 i = 1; i <= dataSubmissionCount; i++) {
-            if (hasStakedOnData[dataId][address(uint160(uint256(i)))] == true) { // This should be replaced with a more robust way of iterating through stakers for a particular dataId.
-                DataSubmission memory dataSub = dataSubmissions[i]; // Accessing wrong data, this is where the logic is wrong.
-                uint256 userStake;
-                if (dataSub.submitter != address(0)) { // Using submitter as a proxy, THIS IS WRONG
-                    userStake = dataSub.stakeAmount;
-                } else {
-                    continue; // Skips non stakers
-                }
+     * @param fee The license fee in AICT (0 for free licenses).
+     */
+    function setDatasetLicenseFee(uint256 dataId, uint256 fee) external {
+        require(dataId > 0 && dataId <= dataSubmissionCount, "Invalid data ID.");
+        require(dataSubmissions[dataId].submitter == msg.sender, "Only the data submitter can set the license fee.");
 
-                uint256 rewardAmount = (userStake * losingStakePool) / winningStakePool;
-                payable(dataSub.submitter).transfer(rewardAmount);
-            }
+        datasetLicenseFee[dataId] = fee;
+        emit DatasetLicenseFeeSet(dataId, fee);
+    }
+
+    /**
+     * @notice Buys a usage license for a dataset, paying its fee in AICT.  `licenseStakerShareBps` of the fee is
+     *  credited to the dataset's QA stakers pro rata to stake and the rest to the submitter, for `withdrawRewards`,
+     *  after the treasury takes `licenseProtocolFeeBps`.
+     * @param dataId The ID of the data submission.
+     * @param maxFee The highest fee the buyer accepts, so the submitter cannot raise it while the purchase is pending.
+     */
+    function purchaseDatasetLicense(uint256 dataId, uint256 maxFee) external nonReentrant {
+        require(dataId > 0 && dataId <= dataSubmissionCount, "Invalid data ID.");
+        require(dataSubmissions[dataId].isValid, "Dataset is not valid.");
+        require(!hasDatasetLicense[dataId][msg.sender], "You already hold a license for this dataset.");
+
+        hasDatasetLicense[dataId][msg.sender] = true;
+        uint256 fee = datasetLicenseFee[dataId];
+        require(fee <= maxFee, "License fee exceeds the maximum fee.");
+        if (fee > 0) {
+            _transfer(msg.sender, address(this), fee);
+            _distributeLicenseFee(dataId, fee);
         }
+
+        emit DatasetLicensePurchased(dataId, msg.sender, fee);
     }
 
+    /**
+     * @notice Takes the treasury's cut of a license fee and credits the rest to the dataset's QA stakers and submitter.
+     */
+    function _distributeLicenseFee(uint256 dataId, uint256 fee) internal {
+        uint256 protocolFee = fee.mul(licenseProtocolFeeBps).div(10000);
+        _collectTreasuryFee(protocolFee, true);
+        fee -= protocolFee;
+
+        address[] storage stakers = dataStakers[dataId];
+        uint256 totalStake = 0;
+        for (uint256 i = 0; i < stakers.length; i++) {
+            totalStake += dataStakeOf[dataId][stakers[i]];
+        }
 
+        uint256 stakerPaid = 0;
+        if (totalStake > 0) {
+            uint256 stakerShare = fee.mul(licenseStakerShareBps).div(10000);
+            for (uint256 i = 0; i < stakers.length; i++) {
+                uint256 amount = stakerShare.mul(dataStakeOf[dataId][stakers[i]]).div(totalStake);
+                pendingTokenRewards[stakers[i]] += amount;
+                stakerPaid += amount;
+            }
+        }
+        pendingTokenRewards[dataSubmissions[dataId].submitter] += fee - stakerPaid;
+    }
 
     // --- Model Training Bounty Functions ---
 
     /**
-     * @notice Creates a bounty for training a specific type of AI model.
+     * @notice Creates a bounty for training a specific type of AI model.  The reward is escrowed: sent as ETH, or
+     *  transferred from the creator's AICT balance if `payInToken` is set.
      * @param modelType The type of AI model (e.g., "ImageClassifier", "TextGenerator").
      * @param performanceTarget Description of the desired performance target.
-     * @param rewardAmount The amount of Ether offered as a reward.
+     * @param rewardAmount The reward, in wei of ETH or AICT.
      * @param descriptionURI URI pointing to a detailed description of the bounty.
+     * @param deadline The timestamp until which models can be submitted.
+     * @param payInToken Whether the reward is paid in AICT instead of ETH.
+     * @param holdoutCommitment keccak256(abi.encodePacked(holdoutURI, salt)) of the hidden benchmark dataset.
      */
     function createBounty(
         string memory modelType,
         string memory performanceTarget,
         uint256 rewardAmount,
-        string memory descriptionURI
+        string memory descriptionURI,
+        uint256 deadline,
+        bool payInToken,
+        bytes32 holdoutCommitment
     ) external payable nonReentrant {
-        require(msg.value >= rewardAmount, "Insufficient reward provided.");
+        require(rewardAmount > 0, "Reward must be greater than zero.");
+        require(holdoutCommitment != bytes32(0), "Holdout commitment is required.");
+        require(deadline > block.timestamp, "Deadline must be in the future.");
+        if (payInToken) {
+            require(msg.value == 0, "Reward is paid in AICT.");
+            _transfer(msg.sender, address(this), rewardAmount);
+        } else {
+            require(msg.value == rewardAmount, "Sent value must equal the reward.");
+        }
 
         bountyCount++;
         Bounty storage newBounty = bounties[bountyCount];
@@ -260,59 +563,282 @@ contract AIChain is ERC20, Ownable, ReentrancyGuard {
         newBounty.rewardAmount = rewardAmount;
         newBounty.descriptionURI = descriptionURI;
         newBounty.isActive = true;
+        newBounty.deadline = deadline;
+        newBounty.paidInToken = payInToken;
+        newBounty.holdoutCommitment = holdoutCommitment;
 
         emit BountyCreated(bountyCount, msg.sender, modelType);
     }
 
+    /**
+     * @notice Reveals a bounty's holdout dataset.  Only possible after the submission deadline, so trainers cannot
+     *  fit their models to it, and within `holdoutRevealPeriod` of the deadline.
+     * @param bountyId The ID of the bounty.
+     * @param holdoutURI URI pointing to the holdout dataset.
+     * @param salt The salt used in the commitment.
+     */
+    function revealHoldout(uint256 bountyId, string memory holdoutURI, bytes32 salt) external {
+        require(bountyId > 0 && bountyId <= bountyCount, "Invalid bounty ID.");
+        Bounty storage bounty = bounties[bountyId];
+        require(msg.sender == bounty.creator, "Only the bounty creator can reveal the holdout.");
+        require(bounty.isActive, "Bounty is not active.");
+        require(bounty.holdoutRevealedAt == 0, "Holdout is already revealed.");
+        require(block.timestamp > bounty.deadline, "Submission deadline has not passed.");
+        require(block.timestamp <= bounty.deadline + holdoutRevealPeriod, "Holdout reveal period has ended.");
+        require(keccak256(abi.encodePacked(holdoutURI, salt)) == bounty.holdoutCommitment, "Reveal does not match the holdout commitment.");
+
+        bounty.holdoutURI = holdoutURI;
+        bounty.holdoutRevealedAt = block.timestamp;
+
+        emit HoldoutRevealed(bountyId, holdoutURI);
+    }
+
+    /**
+     * @notice Settles a bounty whose creator did not reveal the holdout in time.  Withholding the holdout must not
+     *  get the creator a refund, so the escrow is credited equally to the bounty's distinct model submitters, or
+     *  refunded to the creator only if nobody submitted a model.  Submitting several models does not earn a larger share.
+     * @param bountyId The ID of the bounty.
+     */
+    function forfeitUnrevealedBounty(uint256 bountyId) external nonReentrant {
+        require(bountyId > 0 && bountyId <= bountyCount, "Invalid bounty ID.");
+        Bounty storage bounty = bounties[bountyId];
+        require(bounty.isActive, "Bounty is not active.");
+        require(bounty.holdoutRevealedAt == 0, "Holdout is revealed.");
+        require(block.timestamp > bounty.deadline + holdoutRevealPeriod, "Holdout reveal period has not ended.");
+
+        bounty.isActive = false;
+        bounty.isCancelled = true;
+
+        address[] storage submitters = bountySubmitters[bountyId];
+        uint256 submitterCount = submitters.length;
+
+        if (submitterCount == 0) {
+            _payFromEscrow(bounty.paidInToken, bounty.creator, bounty.rewardAmount);
+        } else {
+            uint256 perSubmitter = bounty.rewardAmount / submitterCount;
+            for (uint256 i = 0; i < submitterCount; i++) {
+                // The last submitter also takes the rounding remainder.
+                uint256 amount = i == submitterCount - 1 ? bounty.rewardAmount - perSubmitter * (submitterCount - 1) : perSubmitter;
+                _creditReward(bountyId, submitters[i], amount, bounty.paidInToken);
+            }
+        }
+
+        emit BountyForfeited(bountyId, submitterCount);
+    }
+
     /**
      * @notice Submits a trained model for a specific bounty.
      * @param bountyId The ID of the bounty.
      * @param modelURI URI pointing to the trained model.
+     * @param datasetIds IDs of the data submissions the model was trained on, without duplicates.  The caller must hold a license for each.
+     * @dev The caller must send exactly `modelSubmissionBond`, refundable with `withdrawModelBond` once the bounty is settled.
      */
-    function submitModel(uint256 bountyId, string memory modelURI) external nonReentrant {
+    function submitModel(uint256 bountyId, string memory modelURI, uint256[] memory datasetIds) external payable nonReentrant {
         require(bountyId > 0 && bountyId <= bountyCount, "Invalid bounty ID.");
+        require(msg.value == modelSubmissionBond, "Sent value must equal the model submission bond.");
         require(bounties[bountyId].isActive, "Bounty is not active.");
+        require(block.timestamp <= bounties[bountyId].deadline, "Bounty submission deadline has passed.");
+        for (uint256 i = 0; i < datasetIds.length; i++) {
+            require(datasetIds[i] > 0 && datasetIds[i] <= dataSubmissionCount, "Invalid data ID.");
+            require(dataSubmissions[datasetIds[i]].isValid, "Dataset is not valid.");
+            require(!dataSubmissions[datasetIds[i]].isChallenged || dataSubmissions[datasetIds[i]].isChallengeResolved, "Dataset is under challenge.");
+            require(hasDatasetLicense[datasetIds[i]][msg.sender], "Dataset is not licensed.");
+            for (uint256 j = 0; j < i; j++) {
+                require(datasetIds[j] != datasetIds[i], "Dataset is declared more than once.");
+            }
+        }
 
         modelSubmissionCount++;
         ModelSubmission storage newModel = modelSubmissions[modelSubmissionCount];
         newModel.submitter = msg.sender;
         newModel.bountyId = bountyId;
         newModel.modelURI = modelURI;
+        newModel.datasetIds = datasetIds;
+        newModel.bond = msg.value;
+        bountyModels[bountyId].push(modelSubmissionCount);
+        if (!isBountySubmitter[bountyId][msg.sender]) {
+            isBountySubmitter[bountyId][msg.sender] = true;
+            bountySubmitters[bountyId].push(msg.sender);
+        }
 
         emit ModelSubmitted(bountyId, modelSubmissionCount, msg.sender);
     }
 
+    /**
+     * @notice Returns a model's submission bond once its bounty has been paid out, cancelled or forfeited.
+     * @param modelId The ID of the model.
+     */
+    function withdrawModelBond(uint256 modelId) external nonReentrant {
+        require(modelId > 0 && modelId <= modelSubmissionCount, "Invalid model ID.");
+        ModelSubmission storage model = modelSubmissions[modelId];
+        require(model.submitter == msg.sender, "Only the model submitter can withdraw its bond.");
+        require(!bounties[model.bountyId].isActive, "Bounty is not settled.");
+        uint256 amount = model.bond;
+        require(amount > 0, "No bond to withdraw.");
+
+        model.bond = 0;
+        payable(msg.sender).transfer(amount);
+
+        emit ModelBondWithdrawn(modelId, msg.sender, amount);
+    }
+
 
     /**
-     * @notice Evaluates a model's performance against a hidden benchmark dataset.
-     *  This function is simulated - in a real implementation, this would involve a more complex process using MPC.
-     * @param bountyId The ID of the bounty.
+     * @notice Commits a hidden performance score for a model, evaluated off-chain against the hidden benchmark dataset.
+     *  The first commitment opens the model's commit window; reveals are accepted in the window after it.
      * @param modelId The ID of the model.
-     * @param encryptedPerformanceResults Placeholder for the encrypted performance results from the MPC.
+     * @param commitment keccak256(abi.encodePacked(modelId, score, salt, evaluator)).
      */
-    function evaluateModel(uint256 bountyId, uint256 modelId, bytes memory encryptedPerformanceResults) external onlyMPCValidator nonReentrant {
-        require(bountyId > 0 && bountyId <= bountyCount, "Invalid bounty ID.");
+    function commitEvaluation(uint256 modelId, bytes32 commitment) external onlyEvaluator nonReentrant {
         require(modelId > 0 && modelId <= modelSubmissionCount, "Invalid model ID.");
-        require(modelSubmissions[modelId].bountyId == bountyId, "Model does not belong to this bounty.");
+        Bounty storage bounty = bounties[modelSubmissions[modelId].bountyId];
+        require(bounty.isActive, "Bounty is not active.");
+        require(bounty.holdoutRevealedAt > 0, "Holdout has not been revealed.");
+        require(evaluatorBonds[msg.sender] >= evaluatorBondAmount, "Evaluator bond is too low.");
+        require(evaluationCommitments[modelId][msg.sender] == bytes32(0), "You have already committed an evaluation.");
+
+        Evaluation storage evaluation = evaluations[modelId];
+        if (evaluation.commitDeadline == 0) {
+            // New evaluations open until one commit period after the holdout reveal, so bounties cannot stay undecided forever.
+            require(block.timestamp <= bounty.holdoutRevealedAt + evaluationCommitPeriod, "Evaluations for this bounty are closed.");
+            evaluation.commitDeadline = block.timestamp + evaluationCommitPeriod;
+            evaluation.revealDeadline = evaluation.commitDeadline + evaluationRevealPeriod;
+        }
+        require(block.timestamp <= evaluation.commitDeadline, "Commit period has ended.");
+
+        evaluationCommitments[modelId][msg.sender] = commitment;
+        evaluation.committers.push(msg.sender);
+        pendingReveals[msg.sender]++;
+
+        emit EvaluationCommitted(modelId, msg.sender);
+    }
+
+    /**
+     * @notice Reveals a committed performance score.
+     * @param modelId The ID of the model.
+     * @param score The performance score that was committed.
+     * @param salt The salt used in the commitment.
+     */
+    function revealEvaluation(uint256 modelId, uint256 score, bytes32 salt) external nonReentrant {
+        Evaluation storage evaluation = evaluations[modelId];
+        require(block.timestamp > evaluation.commitDeadline && block.timestamp <= evaluation.revealDeadline, "Not in the reveal period.");
+        require(!hasRevealedEvaluation[modelId][msg.sender], "You have already revealed.");
+        require(
+            evaluationCommitments[modelId][msg.sender] == keccak256(abi.encodePacked(modelId, score, salt, msg.sender)),
+            "Reveal does not match the commitment."
+        );
+
+        hasRevealedEvaluation[modelId][msg.sender] = true;
+        evaluation.revealedScores.push(score);
+        pendingReveals[msg.sender]--;
+
+        emit EvaluationRevealed(modelId, msg.sender, score);
+    }
+
+    /**
+     * @notice Finalizes a model's evaluation after the reveal period.  The median of the revealed scores becomes the
+     *  model's performance score if at least `evaluationThreshold` reveals lie within `evaluationScoreTolerance` of it.
+     *  Committers who did not reveal are slashed either way.  A failed evaluation is final; the model must be resubmitted.
+     * @param modelId The ID of the model.
+     */
+    function finalizeEvaluation(uint256 modelId) external nonReentrant {
+        Evaluation storage evaluation = evaluations[modelId];
+        require(evaluation.commitDeadline > 0, "Model has no evaluation.");
+        require(block.timestamp > evaluation.revealDeadline, "Reveal period has not ended.");
+        require(!evaluation.isFinalized, "Evaluation is already finalized.");
+        evaluation.isFinalized = true;
+
+        for (uint256 i = 0; i < evaluation.committers.length; i++) {
+            address evaluator = evaluation.committers[i];
+            if (!hasRevealedEvaluation[modelId][evaluator]) {
+                pendingReveals[evaluator]--;
+                uint256 slashed = evaluatorBonds[evaluator] < evaluatorBondAmount ? evaluatorBonds[evaluator] : evaluatorBondAmount;
+                evaluatorBonds[evaluator] -= slashed;
+                _collectTreasuryFee(slashed, false);
+                emit EvaluatorSlashed(modelId, evaluator, slashed);
+            }
+        }
+
+        uint256 revealCount = evaluation.revealedScores.length;
+        uint256 median = revealCount > 0 ? _median(evaluation.revealedScores) : 0;
+        uint256 matching = 0;
+        for (uint256 i = 0; i < revealCount; i++) {
+            uint256 score = evaluation.revealedScores[i];
+            if ((score > median ? score - median : median - score) <= evaluationScoreTolerance) {
+                matching++;
+            }
+        }
+
+        if (revealCount == 0 || matching < evaluationThreshold) {
+            emit EvaluationFailed(modelId, revealCount, matching);
+            return;
+        }
+
+        ModelSubmission storage model = modelSubmissions[modelId];
+        model.performanceScore = median;
+        model.isEvaluated = true;
+
+        emit ModelEvaluated(model.bountyId, modelId, median);
+    }
+
+    /**
+     * @notice Returns the evaluation windows and progress of a model.
+     * @param modelId The ID of the model.
+     */
+    function getEvaluation(uint256 modelId) external view returns (uint256 commitDeadline, uint256 revealDeadline, address[] memory committers, uint256[] memory revealedScores, bool isFinalized) {
+        Evaluation storage evaluation = evaluations[modelId];
+        return (evaluation.commitDeadline, evaluation.revealDeadline, evaluation.committers, evaluation.revealedScores, evaluation.isFinalized);
+    }
 
-        // In a real-world scenario:
-        // 1.  The MPC would have performed the evaluation on a *hidden* benchmark dataset.
-        // 2.  The MPC would generate encrypted results.
-        // 3.  This function would receive those encrypted results.
-        // 4.  A zero-knowledge proof (ZKP) could be used to prove the correctness of the MPC calculation without revealing the underlying data.
-        // 5.  Once verified, the contract could decrypt the results (if necessary) or use the encrypted results directly for reward distribution.
+    /**
+     * @notice Posts (or tops up) the caller's evaluator bond.
+     */
+    function depositEvaluatorBond() external payable onlyEvaluator nonReentrant {
+        require(msg.value > 0, "Bond must be greater than zero.");
+        evaluatorBonds[msg.sender] += msg.value;
+        emit EvaluatorBondDeposited(msg.sender, msg.value);
+    }
 
-        //For this example, we skip these steps and simulate by generating a random performance score.
-        //Note: The result should remain secret until it's finalized for security reasons.
-        //Instead, just write into encryptedResults and store it.
+    /**
+     * @notice Withdraws the caller's evaluator bond once they have left the committee and have no unrevealed commitments.
+     */
+    function withdrawEvaluatorBond() external nonReentrant {
+        require(!isEvaluator[msg.sender], "Evaluators must leave the committee first.");
+        require(pendingReveals[msg.sender] == 0, "Evaluations are still pending.");
+        uint256 amount = evaluatorBonds[msg.sender];
+        require(amount > 0, "No bond to withdraw.");
+
+        evaluatorBonds[msg.sender] = 0;
+        payable(msg.sender).transfer(amount);
+        emit EvaluatorBondWithdrawn(msg.sender, amount);
+    }
 
-        modelSubmissions[modelId].encryptedPerformanceResults = encryptedPerformanceResults;
+    /**
+     * @notice Returns the median of a list of scores, averaging the two middle scores for an even count.
+     */
+    function _median(uint256[] memory scores) internal pure returns (uint256) {
+        for (uint256 i = 1; i < scores.length; i++) {
+            uint256 score = scores[i];
+            uint256 j = i;
+            while (j > 0 && scores[j - 1] > score) {
+                scores[j] = scores[j - 1];
+                j--;
+            }
+            scores[j] = score;
+        }
 
-        emit ModelEvaluated(bountyId, modelId, modelSubmissions[modelId].performanceScore);
+        uint256 middle = scores.length / 2;
+        if (scores.length % 2 == 1) {
+            return scores[middle];
+        }
+        return (scores[middle - 1] + scores[middle]) / 2;
     }
 
     /**
      * @notice Distributes bounty rewards based on model performance, data contributions, and QA participation.
+     *  After the treasury rake and royalties, `dataSubmitterShareBps` is split equally between the submitters of the winning model's datasets
+     *  that are still valid, `qaStakerShareBps` between those datasets' QA stakers pro rata to stake, and the rest goes
+     *  to the model submitter, who also receives any share with nobody to pay.  Rewards are credited for `withdrawRewards`.
      * @param bountyId The ID of the bounty.
      */
     function distributeBountyRewards(uint256 bountyId) external nonReentrant {
@@ -320,23 +846,248 @@ contract AIChain is ERC20, Ownable, ReentrancyGuard {
         require(bounties[bountyId].isActive, "Bounty is not active.");
 
         Bounty storage bounty = bounties[bountyId];
+        require(_evaluationsClosed(bountyId), "Bounty evaluations are still open.");
         uint256 bestModelId = _findBestModelForBounty(bountyId);
-        require(bestModelId > 0, "No models have been submitted for this bounty.");
+        require(bestModelId > 0, "No evaluated models for this bounty.");
 
         ModelSubmission storage bestModel = modelSubmissions[bestModelId];
         bounty.bestModelId = bestModelId;
+        bounty.bestModelPerformance = bestModel.performanceScore;
         bounty.isActive = false;
 
-        // For simplicity, the entire reward goes to the best model submitter.
-        // In a more complex system, rewards would be distributed based on data contribution,
-        // QA participation, and other factors, potentially using quadratic funding.
-        payable(bestModel.submitter).transfer(bounty.rewardAmount);
+        // The treasury takes its rake, then datasets already accepted for an earlier bounty earn a royalty from the reward.
+        uint256 rake = bounty.rewardAmount.mul(bountyRakeBps).div(10000);
+        _collectTreasuryFee(rake, bounty.paidInToken);
+        uint256 royaltiesPaid = _accrueDataReuseRoyalties(bountyId, bestModel.datasetIds, bounty.rewardAmount - rake, bounty.paidInToken);
+        uint256 pool = bounty.rewardAmount - rake - royaltiesPaid;
+
+        uint256 dataPaid = _creditDataSubmitters(bountyId, bestModel.datasetIds, pool.mul(dataSubmitterShareBps).div(10000), bounty.paidInToken);
+        uint256 qaPaid = _creditQAStakers(bountyId, bestModel.datasetIds, pool.mul(qaStakerShareBps).div(10000), bounty.paidInToken);
+        _creditReward(bountyId, bestModel.submitter, pool - dataPaid - qaPaid, bounty.paidInToken);
 
         emit BountyRewardsDistributed(bountyId);
     }
 
     /**
-     * @notice Finds the best model for a given bounty (based on highest simulated performance score).
+     * @notice Credits a bounty's data share equally to the submitters of the valid datasets in a model's manifest.
+     * @return The amount credited.
+     */
+    function _creditDataSubmitters(uint256 bountyId, uint256[] storage datasetIds, uint256 share, bool inToken) internal returns (uint256) {
+        uint256 validCount = 0;
+        for (uint256 i = 0; i < datasetIds.length; i++) {
+            if (dataSubmissions[datasetIds[i]].isValid) {
+                validCount++;
+            }
+        }
+        if (validCount == 0) {
+            return 0;
+        }
+
+        uint256 perDataset = share / validCount;
+        for (uint256 i = 0; i < datasetIds.length; i++) {
+            if (dataSubmissions[datasetIds[i]].isValid) {
+                _creditReward(bountyId, dataSubmissions[datasetIds[i]].submitter, perDataset, inToken);
+                _adjustReputation(dataSubmissions[datasetIds[i]].submitter, true); // Data validated by a winning model
+            }
+        }
+        return perDataset * validCount;
+    }
+
+    /**
+     * @notice Credits a bounty's QA share to the stakers of the valid datasets in a model's manifest, pro rata to stake.
+     * @return The amount credited.
+     */
+    function _creditQAStakers(uint256 bountyId, uint256[] storage datasetIds, uint256 share, bool inToken) internal returns (uint256) {
+        uint256 totalStake = 0;
+        for (uint256 i = 0; i < datasetIds.length; i++) {
+            if (dataSubmissions[datasetIds[i]].isValid) {
+                address[] storage stakers = dataStakers[datasetIds[i]];
+                for (uint256 j = 0; j < stakers.length; j++) {
+                    totalStake += dataStakeOf[datasetIds[i]][stakers[j]];
+                }
+            }
+        }
+        if (totalStake == 0) {
+            return 0;
+        }
+
+        uint256 credited = 0;
+        for (uint256 i = 0; i < datasetIds.length; i++) {
+            if (dataSubmissions[datasetIds[i]].isValid) {
+                address[] storage stakers = dataStakers[datasetIds[i]];
+                for (uint256 j = 0; j < stakers.length; j++) {
+                    uint256 amount = share.mul(dataStakeOf[datasetIds[i]][stakers[j]]).div(totalStake);
+                    _creditReward(bountyId, stakers[j], amount, inToken);
+                    credited += amount;
+                }
+            }
+        }
+        return credited;
+    }
+
+    /**
+     * @notice Credits a reward for the recipient to withdraw later.
+     */
+    function _creditReward(uint256 bountyId, address recipient, uint256 amount, bool inToken) internal {
+        if (amount == 0) {
+            return;
+        }
+        if (inToken) {
+            pendingTokenRewards[recipient] += amount;
+        } else {
+            pendingRewards[recipient] += amount;
+        }
+        emit RewardCredited(bountyId, recipient, amount, inToken);
+    }
+
+    /**
+     * @notice Withdraws the caller's credited ETH and AICT bounty rewards.
+     */
+    function withdrawRewards() external nonReentrant {
+        uint256 amount = pendingRewards[msg.sender];
+        uint256 tokenAmount = pendingTokenRewards[msg.sender];
+        require(amount > 0 || tokenAmount > 0, "No rewards to withdraw.");
+
+        pendingRewards[msg.sender] = 0;
+        pendingTokenRewards[msg.sender] = 0;
+        if (amount > 0) {
+            payable(msg.sender).transfer(amount);
+        }
+        if (tokenAmount > 0) {
+            _transfer(address(this), msg.sender, tokenAmount);
+        }
+
+        emit RewardsWithdrawn(msg.sender, amount, tokenAmount);
+    }
+
+    /**
+     * @notice Cancels a bounty whose deadline has passed without a qualifying model and refunds the escrowed reward.
+     * @param bountyId The ID of the bounty.
+     */
+    function cancelBounty(uint256 bountyId) external nonReentrant {
+        require(bountyId > 0 && bountyId <= bountyCount, "Invalid bounty ID.");
+        Bounty storage bounty = bounties[bountyId];
+        require(msg.sender == bounty.creator, "Only the bounty creator can cancel.");
+        require(bounty.isActive, "Bounty is not active.");
+        require(_evaluationsClosed(bountyId), "Bounty evaluations are still open.");
+        require(_findBestModelForBounty(bountyId) == 0, "Bounty has a qualifying model.");
+
+        bounty.isActive = false;
+        bounty.isCancelled = true;
+        _payFromEscrow(bounty.paidInToken, bounty.creator, bounty.rewardAmount);
+
+        emit BountyCancelled(bountyId, bounty.rewardAmount);
+    }
+
+    /**
+     * @notice Whether a bounty's outcome is settled: its holdout was revealed at least one commit period ago, so no new
+     *  evaluations can open, and no evaluation of its models is still awaiting finalization.
+     * @param bountyId The ID of the bounty.
+     */
+    function _evaluationsClosed(uint256 bountyId) internal view returns (bool) {
+        uint256 revealedAt = bounties[bountyId].holdoutRevealedAt;
+        if (revealedAt == 0 || block.timestamp <= revealedAt + evaluationCommitPeriod) {
+            return false;
+        }
+        uint256[] storage modelIds = bountyModels[bountyId];
+        for (uint256 i = 0; i < modelIds.length; i++) {
+            Evaluation storage evaluation = evaluations[modelIds[i]];
+            if (evaluation.commitDeadline > 0 && !evaluation.isFinalized) {
+                return false;
+            }
+        }
+        return true;
+    }
+
+    /**
+     * @notice Pays out escrowed funds in ETH or AICT.
+     */
+    function _payFromEscrow(bool inToken, address to, uint256 amount) internal {
+        if (inToken) {
+            _transfer(address(this), to, amount);
+        } else {
+            payable(to).transfer(amount);
+        }
+    }
+
+    /**
+     * @notice Credits royalties to valid datasets reused from earlier bounties and records first-time acceptances.
+     *  Datasets invalidated by a challenge are skipped, like in the data and QA shares.
+     * @param bountyId The ID of the bounty being paid out.
+     * @param datasetIds The dataset-usage manifest of the winning model.
+     * @param rewardAmount The bounty's reward pool.
+     * @param inToken Whether the reward pool is in AICT.
+     * @return The total amount credited as royalties.
+     */
+    function _accrueDataReuseRoyalties(uint256 bountyId, uint256[] storage datasetIds, uint256 rewardAmount, bool inToken) internal returns (uint256) {
+        uint256 reusedCount = 0;
+        for (uint256 i = 0; i < datasetIds.length; i++) {
+            uint256 acceptedIn = datasetAcceptedInBounty[datasetIds[i]];
+            if (dataSubmissions[datasetIds[i]].isValid && acceptedIn != 0 && acceptedIn != bountyId) {
+                reusedCount++;
+            }
+        }
+
+        uint256 royaltyPerDataset = reusedCount > 0 ? rewardAmount.mul(dataReuseRoyaltyBps).div(10000).div(reusedCount) : 0;
+        uint256 royaltiesPaid = 0;
+
+        for (uint256 i = 0; i < datasetIds.length; i++) {
+            uint256 dataId = datasetIds[i];
+            if (!dataSubmissions[dataId].isValid) {
+                continue; // Invalidated since the model was submitted; it neither earns royalties nor counts as accepted
+            }
+            uint256 acceptedIn = datasetAcceptedInBounty[dataId];
+            if (acceptedIn == 0) {
+                datasetAcceptedInBounty[dataId] = bountyId;
+            } else if (acceptedIn != bountyId && royaltyPerDataset > 0) {
+                if (inToken) {
+                    datasetTokenRoyaltyBalance[dataId] += royaltyPerDataset;
+                } else {
+                    datasetRoyaltyBalance[dataId] += royaltyPerDataset;
+                }
+                royaltiesPaid += royaltyPerDataset;
+                emit DatasetRoyaltyAccrued(dataId, bountyId, royaltyPerDataset);
+            }
+        }
+
+        return royaltiesPaid;
+    }
+
+    /**
+     * @notice Claims the reuse royalties accrued by a dataset.  Only the original data submitter can claim.
+     * @param dataId The ID of the data submission.
+     */
+    function claimDatasetRoyalties(uint256 dataId) external nonReentrant {
+        require(dataId > 0 && dataId <= dataSubmissionCount, "Invalid data ID.");
+        require(dataSubmissions[dataId].submitter == msg.sender, "Only the data submitter can claim royalties.");
+
+        uint256 amount = datasetRoyaltyBalance[dataId];
+        uint256 tokenAmount = datasetTokenRoyaltyBalance[dataId];
+        require(amount > 0 || tokenAmount > 0, "No royalties to claim.");
+
+        datasetRoyaltyBalance[dataId] = 0;
+        datasetTokenRoyaltyBalance[dataId] = 0;
+        if (amount > 0) {
+            payable(msg.sender).transfer(amount);
+        }
+        if (tokenAmount > 0) {
+            _transfer(address(this), msg.sender, tokenAmount);
+        }
+
+        emit DatasetRoyaltyClaimed(dataId, msg.sender, amount + tokenAmount);
+    }
+
+    /**
+     * @notice Returns the dataset-usage manifest of a model submission.
+     * @param modelId The ID of the model.
+     * @return The IDs of the datasets the model declared.
+     */
+    function getModelDatasets(uint256 modelId) external view returns (uint256[] memory) {
+        return modelSubmissions[modelId].datasetIds;
+    }
+
+    /**
+     * @notice Finds the best model for a given bounty (based on the highest committee-evaluated performance score).
      * @param bountyId The ID of the bounty.
      * @return The ID of the best model.
      */
@@ -344,10 +1095,12 @@ contract AIChain is ERC20, Ownable, ReentrancyGuard {
         uint256 bestModelId = 0;
         uint256 bestPerformance = 0;
 
-        for (uint256 i = 1; i <= modelSubmissionCount; i++) {
-            if (modelSubmissions[i].bountyId == bountyId && uint256(bytes32(keccak256(modelSubmissions[i].encryptedPerformanceResults))) > bestPerformance) {
-                bestModelId = i;
-                bestPerformance = uint256(bytes32(keccak256(modelSubmissions[i].encryptedPerformanceResults))); // Placeholder for true performance score
+        uint256[] storage modelIds = bountyModels[bountyId];
+        for (uint256 i = 0; i < modelIds.length; i++) {
+            ModelSubmission storage model = modelSubmissions[modelIds[i]];
+            if (model.isEvaluated && (bestModelId == 0 || model.performanceScore > bestPerformance)) {
+                bestModelId = modelIds[i];
+                bestPerformance = model.performanceScore;
             }
         }
 
@@ -360,18 +1113,31 @@ contract AIChain is ERC20, Ownable, ReentrancyGuard {
     /**
      * @notice Creates a new governance proposal.
      * @param descriptionURI URI pointing to a detailed description of the proposal.
+     * @param target This contract for an executable proposal, or address(0) for a signalling proposal.
+     * @param callData The encoded call to a governable parameter setter; empty for a signalling proposal.
      */
-    function createProposal(string memory descriptionURI) external {
+    function createProposal(string memory descriptionURI, address target, bytes memory callData) external {
+        if (target == address(0)) {
+            require(callData.length == 0, "Signalling proposals carry no call.");
+        } else {
+            require(target == address(this), "Proposals can only target AIChain.");
+            require(callData.length >= 4 && isGovernableFunction[bytes4(callData)], "Function is not governable.");
+        }
+
         proposalCount++;
         Proposal storage newProposal = proposals[proposalCount];
         newProposal.creator = msg.sender;
         newProposal.descriptionURI = descriptionURI;
+        newProposal.target = target;
+        newProposal.callData = callData;
+        newProposal.snapshotBlock = block.number;
 
         emit ProposalCreated(proposalCount, msg.sender, descriptionURI);
     }
 
     /**
-     * @notice Casts a vote on a governance proposal.
+     * @notice Casts a vote on a governance proposal.  The vote is weighted by the caller's delegated voting power at
+     *  the proposal's snapshot block, so tokens transferred or delegated after the proposal was created cannot vote twice.
      * @param proposalId The ID of the proposal.
      * @param supports Whether the voter supports the proposal.
      */
@@ -379,10 +1145,12 @@ contract AIChain is ERC20, Ownable, ReentrancyGuard {
         require(proposalId > 0 && proposalId <= proposalCount, "Invalid proposal ID.");
         require(!proposals[proposalId].isResolved, "Proposal is already resolved.");
         require(!hasVotedOnProposal[proposalId][msg.sender], "You have already voted on this proposal.");
+        require(block.number > proposals[proposalId].snapshotBlock, "Voting starts after the snapshot block.");
 
-        hasVotedOnProposal[proposalId][msg.sender] = true;
+        uint256 voteWeight = getPastVotes(msg.sender, proposals[proposalId].snapshotBlock);
+        require(voteWeight > 0, "No voting power at the snapshot block.");
 
-        uint256 voteWeight = balanceOf(msg.sender); // Use token balance as vote weight
+        hasVotedOnProposal[proposalId][msg.sender] = true;
 
         if (supports) {
             proposals[proposalId].votesFor += voteWeight;
@@ -400,17 +1168,54 @@ contract AIChain is ERC20, Ownable, ReentrancyGuard {
     function resolveProposal(uint256 proposalId) external onlyOwner {
         require(proposalId > 0 && proposalId <= proposalCount, "Invalid proposal ID.");
         require(!proposals[proposalId].isResolved, "Proposal is already resolved.");
+        require(block.number > proposals[proposalId].snapshotBlock, "Voting starts after the snapshot block.");
 
-        uint256 quorum = totalSupply() / 2; // Example: Quorum is 50% of total token supply
+        uint256 quorum = getPastTotalSupply(proposals[proposalId].snapshotBlock) / 2; // Example: Quorum is 50% of total token supply at the snapshot
 
         bool isAccepted = proposals[proposalId].votesFor > proposals[proposalId].votesAgainst && proposals[proposalId].votesFor >= quorum;
 
         proposals[proposalId].isResolved = true;
         proposals[proposalId].isAccepted = isAccepted;
+        if (isAccepted) {
+            proposals[proposalId].executableAt = block.timestamp + proposalTimelock;
+        }
 
         emit ProposalResolved(proposalId, isAccepted);
     }
 
+    /**
+     * @notice Executes an accepted proposal's call once its timelock has passed.  Anyone can trigger execution.
+     * @param proposalId The ID of the proposal.
+     */
+    function executeProposal(uint256 proposalId) external nonReentrant {
+        require(proposalId > 0 && proposalId <= proposalCount, "Invalid proposal ID.");
+        Proposal storage proposal = proposals[proposalId];
+        require(proposal.isAccepted, "Proposal was not accepted.");
+        require(proposal.target != address(0), "Proposal has no call to execute.");
+        require(!proposal.isExecuted, "Proposal is already executed.");
+        require(block.timestamp >= proposal.executableAt, "Proposal is still timelocked.");
+
+        proposal.isExecuted = true;
+        (bool success, ) = proposal.target.call(proposal.callData);
+        require(success, "Proposal execution failed.");
+
+        emit ProposalExecuted(proposalId);
+    }
+
+    // --- Voting Token Overrides ---
+
+    function _afterTokenTransfer(address from, address to, uint256 amount) internal override(ERC20, ERC20Votes) {
+        super._afterTokenTransfer(from, to, amount);
+    }
+
+    function _mint(address to, uint256 amount) internal override(ERC20, ERC20Votes) {
+        super._mint(to, amount);
+    }
+
+    function _burn(address account, uint256 amount) internal override(ERC20, ERC20Votes) {
+        super._burn(account, amount);
+    }
+
     // --- Utility Functions ---
 
     /**
@@ -426,20 +1231,410 @@ contract AIChain is ERC20, Ownable, ReentrancyGuard {
 
     // --- Admin Functions ---
 
+    /**
+     * @notice Sets how long bounty creators have after the deadline to reveal the holdout dataset.
+     * @param _holdoutRevealPeriod The reveal period in seconds.
+     */
+    function setHoldoutRevealPeriod(uint256 _holdoutRevealPeriod) external onlyOwnerOrGovernance {
+        require(_holdoutRevealPeriod > 0, "Reveal period must be greater than zero.");
+        holdoutRevealPeriod = _holdoutRevealPeriod;
+    }
+
+    /**
+     * @notice Sets the bond posted with every model submission.  Only affects models submitted afterwards.
+     * @param _modelSubmissionBond The bond in wei.
+     */
+    function setModelSubmissionBond(uint256 _modelSubmissionBond) external onlyOwnerOrGovernance {
+        modelSubmissionBond = _modelSubmissionBond;
+    }
+
+    /**
+     * @notice Sets the delay between a proposal's acceptance and its execution.
+     * @param _proposalTimelock The timelock in seconds.
+     */
+    function setProposalTimelock(uint256 _proposalTimelock) external onlyOwnerOrGovernance {
+        proposalTimelock = _proposalTimelock;
+    }
+
     /**
      * @notice Sets the stake amount required for data submission and challenge.
      * @param _dataStakeAmount The new stake amount.
      */
-    function setDataStakeAmount(uint256 _dataStakeAmount) external onlyOwner {
+    function setDataStakeAmount(uint256 _dataStakeAmount) external onlyOwnerOrGovernance {
         dataStakeAmount = _dataStakeAmount;
     }
 
     /**
-     * @notice Sets the address of the MPC Validator.
-     * @param _MPC_Validator The address of the MPC validator.
+     * @notice Sets reputation decay, rewards, penalties and the stake discount it earns.
+     * @param _halfLife Seconds after which unrefreshed reputation halves.
+     * @param _reward Reputation gained for validated data and won challenges.
+     * @param _penalty Reputation lost for invalidated data and lost challenges.
+     * @param _reputationForMaxDiscount Reputation at which the stake discount is largest.
+     * @param _maxStakeDiscountBps The largest stake discount in basis points.
+     */
+    function setReputationParameters(uint256 _halfLife, uint256 _reward, uint256 _penalty, uint256 _reputationForMaxDiscount, uint256 _maxStakeDiscountBps) external onlyOwnerOrGovernance {
+        require(_halfLife > 0, "Half-life must be greater than zero.");
+        require(_maxStakeDiscountBps <= 10000, "Discount cannot exceed 100%.");
+        reputationHalfLife = _halfLife;
+        reputationReward = _reward;
+        reputationPenalty = _penalty;
+        reputationForMaxDiscount = _reputationForMaxDiscount;
+        maxStakeDiscountBps = _maxStakeDiscountBps;
+    }
+
+    /**
+     * @notice Sets the data challenge window and the protocol fee on losing stakes.
+     * @param _challengePeriod The challenge window in seconds.
+     * @param _challengeFeeBps The fee in basis points.
+     */
+    function setChallengeParameters(uint256 _challengePeriod, uint256 _challengeFeeBps) external onlyOwnerOrGovernance {
+        require(_challengePeriod > 0, "Challenge period must be greater than zero.");
+        require(_challengeFeeBps <= 10000, "Fee cannot exceed 100%.");
+        challengePeriod = _challengePeriod;
+        challengeFeeBps = _challengeFeeBps;
+    }
+
+    /**
+     * @notice Sets the share of dataset license fees paid to the dataset's QA stakers.
+     * @param _licenseStakerShareBps The new share in basis points.
+     */
+    function setLicenseStakerShareBps(uint256 _licenseStakerShareBps) external onlyOwnerOrGovernance {
+        require(_licenseStakerShareBps <= 10000, "Share cannot exceed 100%.");
+        licenseStakerShareBps = _licenseStakerShareBps;
+    }
+
+    /**
+     * @notice Sets the share of bounty rewards routed to reused datasets.
+     * @param _dataReuseRoyaltyBps The new royalty in basis points.
+     */
+    function setDataReuseRoyaltyBps(uint256 _dataReuseRoyaltyBps) external onlyOwnerOrGovernance {
+        require(_dataReuseRoyaltyBps <= 10000, "Royalty cannot exceed 100%.");
+        dataReuseRoyaltyBps = _dataReuseRoyaltyBps;
+    }
+
+    /**
+     * @notice Sets how a bounty's reward, after royalties, is split.  The shares must add up to 100%.
+     * @param _modelSubmitterShareBps The best model submitter's share in basis points.
+     * @param _dataSubmitterShareBps The data submitters' share in basis points.
+     * @param _qaStakerShareBps The QA stakers' share in basis points.
+     */
+    function setRewardSplits(uint256 _modelSubmitterShareBps, uint256 _dataSubmitterShareBps, uint256 _qaStakerShareBps) external onlyOwnerOrGovernance {
+        require(_modelSubmitterShareBps + _dataSubmitterShareBps + _qaStakerShareBps == 10000, "Shares must add up to 100%.");
+        modelSubmitterShareBps = _modelSubmitterShareBps;
+        dataSubmitterShareBps = _dataSubmitterShareBps;
+        qaStakerShareBps = _qaStakerShareBps;
+    }
+
+    /**
+     * @notice Adds an address to the evaluator committee.  It must post a bond before it can commit evaluations.
+     * @param evaluator The address to add.
+     */
+    function addEvaluator(address evaluator) external onlyOwner {
+        require(evaluator != address(0), "Invalid evaluator.");
+        require(!isEvaluator[evaluator], "Already an evaluator.");
+        isEvaluator[evaluator] = true;
+        evaluatorCount++;
+        emit EvaluatorAdded(evaluator);
+    }
+
+    /**
+     * @notice Removes an address from the evaluator committee.  It can still reveal evaluations it has committed.
+     * @param evaluator The address to remove.
+     */
+    function removeEvaluator(address evaluator) external onlyOwner {
+        require(isEvaluator[evaluator], "Not an evaluator.");
+        isEvaluator[evaluator] = false;
+        evaluatorCount--;
+        emit EvaluatorRemoved(evaluator);
+    }
+
+    /**
+     * @notice Sets the evaluation windows, agreement threshold, score tolerance and evaluator bond.
+     * @param _commitPeriod The length of the commit window in seconds.
+     * @param _revealPeriod The length of the reveal window in seconds.
+     * @param _threshold The number of reveals that must match the median.
+     * @param _scoreTolerance The maximum distance from the median for a matching reveal.
+     * @param _bondAmount The bond required to commit, also the amount slashed for not revealing.
+     */
+    function setEvaluationParameters(uint256 _commitPeriod, uint256 _revealPeriod, uint256 _threshold, uint256 _scoreTolerance, uint256 _bondAmount) external onlyOwnerOrGovernance {
+        require(_commitPeriod > 0 && _revealPeriod > 0, "Periods must be greater than zero.");
+        require(_threshold > 0, "Threshold must be greater than zero.");
+        evaluationCommitPeriod = _commitPeriod;
+        evaluationRevealPeriod = _revealPeriod;
+        evaluationThreshold = _threshold;
+        evaluationScoreTolerance = _scoreTolerance;
+        evaluatorBondAmount = _bondAmount;
+    }
+
+    /**
+     * @notice Sets the treasury's cuts of dataset license fees and bounty rewards.
+     * @param _licenseProtocolFeeBps The cut of license fees in basis points.
+     * @param _bountyRakeBps The cut of bounty rewards in basis points.
+     */
+    function setTreasuryFees(uint256 _licenseProtocolFeeBps, uint256 _bountyRakeBps) external onlyOwnerOrGovernance {
+        require(_licenseProtocolFeeBps <= 10000 && _bountyRakeBps <= 10000, "Fee cannot exceed 100%.");
+        licenseProtocolFeeBps = _licenseProtocolFeeBps;
+        bountyRakeBps = _bountyRakeBps;
+    }
+
+    // --- Treasury Functions ---
+
+    /**
+     * @notice Adds a protocol fee to the treasury.
      */
-    function setMPCValidator(address _MPC_Validator) external onlyOwner {
-        MPC_Validator = _MPC_Validator;
+    function _collectTreasuryFee(uint256 amount, bool inToken) internal {
+        if (amount == 0) {
+            return;
+        }
+        if (inToken) {
+            treasuryTokenBalance += amount;
+        } else {
+            treasuryBalance += amount;
+        }
+        emit TreasuryFeeCollected(amount, inToken);
+    }
+
+    /**
+     * @notice Pays ETH out of the treasury.  Only callable through an executed governance proposal.
+     *  Not `nonReentrant`, since it runs inside `executeProposal`; the balance is updated before the transfer.
+     * @param to The recipient.
+     * @param amount The amount of ETH.
+     */
+    function withdrawTreasury(address payable to, uint256 amount) external onlyGovernance {
+        require(amount <= treasuryBalance, "Amount exceeds the treasury balance.");
+        treasuryBalance -= amount;
+        to.transfer(amount);
+        emit TreasuryWithdrawn(to, amount, false);
+    }
+
+    /**
+     * @notice Pays AICT out of the treasury.  Only callable through an executed governance proposal.
+     * @param to The recipient.
+     * @param amount The amount of AICT.
+     */
+    function withdrawTreasuryTokens(address to, uint256 amount) external onlyGovernance {
+        require(amount <= treasuryTokenBalance, "Amount exceeds the treasury balance.");
+        treasuryTokenBalance -= amount;
+        _transfer(address(this), to, amount);
+        emit TreasuryWithdrawn(to, amount, true);
+    }
+
+    /**
+     * @notice Returns the treasury's balances.
+     * @return ethBalance The treasury's ETH.
+     * @return tokenBalance The treasury's AICT.
+     */
+    function getTreasuryBalances() external view returns (uint256 ethBalance, uint256 tokenBalance) {
+        return (treasuryBalance, treasuryTokenBalance);
+    }
+}
+```
+
+The Foundry tests below cover forfeiting a bounty to its distinct model submitters and dataset reuse royalties for a dataset invalidated by a challenge between two bounties.  A fuzz test checks that a bounty payout credits exactly its reward between the treasury, reused datasets and the recipients, whatever the fees, reward splits and QA stakes.
+
+```solidity
+// test/AIChain.t.sol
+pragma solidity ^0.8.0;
+
+import "forge-std/Test.sol";
+import "../src/AIChain.sol";
+
+contract AIChainTest is Test {
+    AIChain ai;
+    address alice = address(0xA11CE);
+    address bob = address(0xB0B);
+    address carol = address(0xCA201);
+    address trainer = address(0x7EA1);
+    address[] evaluators;
+    bytes32 holdoutSalt = keccak256("holdout");
+
+    function setUp() public {
+        ai = new AIChain();
+        for (uint160 i = 1; i <= 3; i++) {
+            address evaluator = address(0xE7A0 + i);
+            evaluators.push(evaluator);
+            ai.addEvaluator(evaluator);
+            vm.deal(evaluator, 10 ether);
+            vm.prank(evaluator);
+            ai.depositEvaluatorBond{value: 1 ether}();
+        }
+
+        vm.deal(alice, 100 ether);
+        vm.deal(bob, 100 ether);
+        vm.deal(carol, 100 ether);
+        vm.deal(trainer, 100 ether);
+        vm.deal(address(this), 100 ether);
+    }
+
+    function _createBounty(uint256 deadline) internal returns (uint256 bountyId) {
+        ai.createBounty{value: 10 ether}("ImageClassifier", "accuracy >= 90%", 10 ether, "ipfs://bounty", deadline, false, keccak256(abi.encodePacked("ipfs://holdout", holdoutSalt)));
+        return ai.bountyCount();
+    }
+
+    function _submitModel(uint256 bountyId, uint256[] memory datasetIds) internal returns (uint256 modelId) {
+        uint256 bond = ai.modelSubmissionBond();
+        vm.prank(trainer);
+        ai.submitModel{value: bond}(bountyId, "ipfs://model", datasetIds);
+        return ai.modelSubmissionCount();
+    }
+
+    /// @dev Reveals the holdout and has the whole committee agree on `score`, leaving the bounty ready to pay out.
+    function _evaluate(uint256 bountyId, uint256 modelId, uint256 score) internal {
+        ai.revealHoldout(bountyId, "ipfs://holdout", holdoutSalt);
+        for (uint256 i = 0; i < evaluators.length; i++) {
+            vm.prank(evaluators[i]);
+            ai.commitEvaluation(modelId, keccak256(abi.encodePacked(modelId, score, bytes32(i), evaluators[i])));
+        }
+        vm.warp(block.timestamp + ai.evaluationCommitPeriod() + 1);
+        for (uint256 i = 0; i < evaluators.length; i++) {
+            vm.prank(evaluators[i]);
+            ai.revealEvaluation(modelId, score, bytes32(i));
+        }
+        vm.warp(block.timestamp + ai.evaluationRevealPeriod());
+        ai.finalizeEvaluation(modelId);
+    }
+
+    function testForfeitedBountySplitsEquallyBetweenDistinctSubmitters() public {
+        uint256 bountyId = _createBounty(block.timestamp + 1 days);
+        uint256[] memory noDatasets = new uint256[](0);
+        uint256 firstModel = _submitModel(bountyId, noDatasets);
+        _submitModel(_createBounty(block.timestamp + 1 days), noDatasets);
+        uint256 secondModel = _submitModel(bountyId, noDatasets);
+        uint256 bond = ai.modelSubmissionBond();
+        vm.prank(alice);
+        ai.submitModel{value: bond}(bountyId, "ipfs://alice-model", noDatasets);
+        // The model submitted to the other bounty is not listed under this one.
+        assertEq(ai.bountyModels(bountyId, 0), firstModel);
+        assertEq(ai.bountyModels(bountyId, 1), secondModel);
+        assertEq(ai.bountyModels(bountyId, 2), ai.modelSubmissionCount());
+        assertEq(ai.bountySubmitters(bountyId, 0), trainer);
+        assertEq(ai.bountySubmitters(bountyId, 1), alice);
+        assertTrue(ai.isBountySubmitter(bountyId, trainer));
+
+        // The creator never reveals the holdout.
+        vm.warp(block.timestamp + 1 days + ai.holdoutRevealPeriod() + 1);
+        ai.forfeitUnrevealedBounty(bountyId);
+        assertEq(ai.pendingRewards(trainer), 5 ether);
+        assertEq(ai.pendingRewards(alice), 5 ether);
+    }
+
+    function testInvalidatedDatasetEarnsNoReuseRoyalty() public {
+        vm.prank(alice);
+        ai.submitData{value: 1 ether}("ipfs://alice-data", "ipfs://alice-meta");
+        vm.prank(bob);
+        ai.submitData{value: 1 ether}("ipfs://bob-data", "ipfs://bob-meta");
+        vm.startPrank(trainer);
+        ai.purchaseDatasetLicense(1, 0);
+        ai.purchaseDatasetLicense(2, 0);
+        vm.stopPrank();
+        uint256[] memory datasetIds = new uint256[](2);
+        datasetIds[0] = 1;
+        datasetIds[1] = 2;
+
+        // Bounty 1 accepts both datasets.
+        uint256 first = _createBounty(block.timestamp + 1 days);
+        uint256 firstModel = _submitModel(first, datasetIds);
+        vm.warp(block.timestamp + 1 days + 1);
+        _evaluate(first, firstModel, 80);
+        ai.distributeBountyRewards(first);
+        assertEq(ai.datasetAcceptedInBounty(1), first);
+        assertEq(ai.datasetAcceptedInBounty(2), first);
+
+        // Bounty 2 reuses both, but alice's dataset is invalidated by a challenge before the payout.
+        uint256 second = _createBounty(block.timestamp + 2 days);
+        uint256 secondModel = _submitModel(second, datasetIds);
+        vm.prank(carol);
+        ai.challengeData{value: 2 ether}(1);
+        vm.warp(block.timestamp + ai.challengePeriod() + 1);
+        ai.resolveDataChallenge(1);
+        (,,,,,, bool isChallenged, bool isValid,,,) = ai.dataSubmissions(1);
+        assertTrue(isChallenged);
+        assertFalse(isValid);
+
+        _evaluate(second, secondModel, 80);
+        ai.distributeBountyRewards(second);
+
+        // The whole royalty goes to bob's dataset, the only valid reused one.
+        uint256 pool = 10 ether - 10 ether * ai.bountyRakeBps() / 10000;
+        assertEq(ai.datasetRoyaltyBalance(1), 0);
+        assertEq(ai.datasetRoyaltyBalance(2), pool * ai.dataReuseRoyaltyBps() / 10000);
+
+        vm.expectRevert("No royalties to claim.");
+        vm.prank(alice);
+        ai.claimDatasetRoyalties(1);
+    }
+
+    /// @dev Sum of everything a bounty payout can credit: the recipients' rewards, both datasets' royalties and the treasury.
+    function _credited(address[5] memory recipients) internal view returns (uint256 total) {
+        for (uint256 i = 0; i < recipients.length; i++) {
+            total += ai.pendingRewards(recipients[i]);
+        }
+        (uint256 treasury,) = ai.getTreasuryBalances();
+        return total + ai.datasetRoyaltyBalance(1) + ai.datasetRoyaltyBalance(2) + treasury;
+    }
+
+    /// @dev Whatever the reward, fees, splits and stakes, a payout credits exactly the bounty's reward: the rake,
+    ///  royalties and shares never add up to more, and what rounding leaves over goes to the model submitter.
+    function testFuzz_BountyPayoutConservesReward(
+        uint256 reward,
+        uint256 rakeBps,
+        uint256 royaltyBps,
+        uint256 dataBps,
+        uint256 qaBps,
+        uint256 carolStake,
+        uint256 daveStake
+    ) public {
+        address dave = address(0xDA7E);
+        vm.deal(dave, 100 ether);
+        reward = bound(reward, 1, 50 ether);
+        ai.setTreasuryFees(ai.licenseProtocolFeeBps(), bound(rakeBps, 0, 10000));
+        ai.setDataReuseRoyaltyBps(bound(royaltyBps, 0, 10000));
+        dataBps = bound(dataBps, 0, 10000);
+        qaBps = bound(qaBps, 0, 10000 - dataBps);
+        ai.setRewardSplits(10000 - dataBps - qaBps, dataBps, qaBps);
+
+        // Two datasets, each vouched for by a QA staker with a different stake.
+        vm.prank(alice);
+        ai.submitData{value: 1 ether}("ipfs://alice-data", "ipfs://alice-meta");
+        vm.prank(bob);
+        ai.submitData{value: 1 ether}("ipfs://bob-data", "ipfs://bob-meta");
+        vm.prank(carol);
+        ai.stakeForData{value: bound(carolStake, 1 ether, 50 ether)}(1);
+        vm.prank(dave);
+        ai.stakeForData{value: bound(daveStake, 1 ether, 50 ether)}(2);
+        vm.startPrank(trainer);
+        ai.purchaseDatasetLicense(1, 0);
+        ai.purchaseDatasetLicense(2, 0);
+        vm.stopPrank();
+        uint256[] memory datasetIds = new uint256[](2);
+        datasetIds[0] = 1;
+        datasetIds[1] = 2;
+
+        // A first bounty accepts both datasets, so the fuzzed one also pays them reuse royalties.
+        uint256 first = _createBounty(block.timestamp + 1 days);
+        uint256 firstModel = _submitModel(first, datasetIds);
+        vm.warp(block.timestamp + 1 days + 1);
+        _evaluate(first, firstModel, 80);
+        ai.distributeBountyRewards(first);
+
+        ai.createBounty{value: reward}("ImageClassifier", "accuracy >= 90%", reward, "ipfs://bounty", block.timestamp + 1 days, false, keccak256(abi.encodePacked("ipfs://holdout", holdoutSalt)));
+        uint256 second = ai.bountyCount();
+        uint256 secondModel = _submitModel(second, datasetIds);
+        vm.warp(block.timestamp + 1 days + 1);
+        _evaluate(second, secondModel, 80);
+
+        address[5] memory recipients = [trainer, alice, bob, carol, dave];
+        uint256 before = _credited(recipients);
+        uint256 royaltiesBefore = ai.datasetRoyaltyBalance(1) + ai.datasetRoyaltyBalance(2);
+        uint256 stakersBefore = ai.pendingRewards(carol) + ai.pendingRewards(dave);
+        ai.distributeBountyRewards(second);
+
+        assertEq(_credited(recipients) - before, reward);
+        uint256 afterRake = reward - reward * ai.bountyRakeBps() / 10000;
+        uint256 royalties = ai.datasetRoyaltyBalance(1) + ai.datasetRoyaltyBalance(2) - royaltiesBefore;
+        assertLe(royalties, afterRake * ai.dataReuseRoyaltyBps() / 10000);
+        uint256 qaPaid = ai.pendingRewards(carol) + ai.pendingRewards(dave) - stakersBefore;
+        assertLe(qaPaid, (afterRake - royalties) * qaBps / 10000);
     }
 }
 ```
@@ -450,18 +1645,26 @@ contract AIChain is ERC20, Ownable, ReentrancyGuard {
 * **Decentralized AI Model Training Focus:** The contract is designed to create a decentralized ecosystem for AI model development, addressing key aspects like data quality, model evaluation, and incentive alignment.  This is a trendy and relevant application of blockchain technology.
 * **Data Quality Assurance with Staking and Challenges:**  A system is implemented to ensure data quality. Users must stake tokens to submit data.  Other users can stake to vouch for its quality or challenge it if they believe it's invalid.  This creates a decentralized QA process.
 * **Model Training Bounties:** The contract allows projects or individuals to create bounties for training AI models, specifying performance targets and reward amounts.
-* **MPC Simulation for Model Evaluation:**  Model evaluation is a challenging problem in a decentralized setting due to the risk of overfitting to the evaluation set.  This contract uses an MPC (Secure Multi-Party Computation) simulator and encrypted performance results. This simulates the evaluation process. The address of the MPC Validator is set to allow the model to be evaluated.
+* **Hidden Holdout Commitment:** A bounty is created with `keccak256(holdoutURI, salt)` of its benchmark holdout dataset. The creator reveals the URI and salt with `revealHoldout` only after the submission deadline, so no submitted model can have been fitted to the holdout, and the committee cannot commit scores before the reveal. A creator who does not reveal within `holdoutRevealPeriod` cannot cancel for a refund: `forfeitUnrevealedBounty` splits the escrow equally between the bounty's distinct model submitters, which `submitModel` records in `bountySubmitters` so the forfeit never scans other bounties' models. For the same reason `submitModel` also appends each model ID to `bountyModels`, which is all that `distributeBountyRewards` and `cancelBounty` look at when checking for open evaluations and picking the winner. Every model submission posts a refundable `modelSubmissionBond`, so flooding a bounty with models from fresh addresses to claim more of a forfeited escrow ties up capital for each one.
+* **Bounty Escrow and Expiry:** The reward is taken into escrow when the bounty is created, either as ETH or as AICT transferred from the creator. Models can be submitted until the bounty's deadline, and evaluations can open until one commit period after the holdout is revealed. Once every evaluation has been finalized, `distributeBountyRewards` pays the winner from escrow, or, if no model qualified, the creator can `cancelBounty` to get the escrow back. Royalties from AICT bounties are accrued and claimed in AICT.
+* **Evaluator Committee for Model Evaluation:**  Model evaluation is a challenging problem in a decentralized setting due to the risk of overfitting to the evaluation set.  Instead of trusting a single validator, an owner-managed committee of bonded evaluators scores each model off-chain against the hidden benchmark.  Each evaluator commits `keccak256(modelId, score, salt, evaluator)` during the commit window and reveals during the reveal window, so nobody can copy another's score.  `finalizeEvaluation` takes the median of the reveals and accepts it only if at least `evaluationThreshold` reveals are within `evaluationScoreTolerance` of it; committers who never reveal lose `evaluatorBondAmount` to the treasury.  Only evaluated models can win a bounty.
 * **Incentive Distribution with Quadratic Funding Potential:** The contract supports distributing rewards to data contributors, QA participants, and model trainers. It mentions the potential for quadratic funding, which is a mechanism that favors contributions that are valued by a broader community.
+* **Protocol Treasury:** Protocol revenue is kept apart from escrowed funds in `treasuryBalance` (ETH) and `treasuryTokenBalance` (AICT). It collects slashed evaluator bonds, the `challengeFeeBps` fee on losing challenge stakes, `licenseProtocolFeeBps` of every dataset license fee, and `bountyRakeBps` of every paid-out bounty, in the bounty's currency. The owner has no way to withdraw it: `withdrawTreasury(to, amount)` and `withdrawTreasuryTokens(to, amount)` can only be called by the contract itself through an executed governance proposal. `getTreasuryBalances` shows both balances.
+* **Dataset Licensing:** Each data submitter sets an AICT `datasetLicenseFee` for their dataset, and a trainer must `purchaseDatasetLicense` for every dataset before declaring it in `submitModel`. A fee of zero makes the license free, but it still has to be taken out. Buyers pass the highest fee they accept, so a submitter cannot front-run a purchase by raising the fee. After the treasury's cut, `licenseStakerShareBps` of each fee (20% by default) goes to the dataset's QA stakers in proportion to their stake, and the rest goes to the submitter. Both are credited to `pendingTokenRewards` and collected with `withdrawRewards`.
+* **Multi-Party Reward Split:** `distributeBountyRewards` pays dataset reuse royalties first (only to datasets that are still valid, so data invalidated by a challenge after the model was submitted earns nothing), then splits the rest by `modelSubmitterShareBps` / `dataSubmitterShareBps` / `qaStakerShareBps` (60/25/15 by default). The data share is divided equally among the still-valid datasets the winning model declared; the QA share goes to those datasets' stakers in proportion to their stake. Nothing is pushed: every share is credited and recipients call `withdrawRewards`, so one reverting recipient cannot block a payout.
 * **Governance with AIChain Tokens:** A governance token (`AIChainToken`) is used to allow token holders to vote on proposals related to the platform's parameters, data acceptance criteria, bounty structures, and upgrades.
-* **`MPC_Validator` Role:** Introduced a `MPC_Validator` role, allowing the contract owner to designate an address authorized to submit encrypted performance results, simulating the involvement of a secure multi-party computation (MPC) provider.
+* **Checkpointed Voting and Delegation:** AICT extends OpenZeppelin's `ERC20Votes`, which checkpoints each account's voting power. A proposal records its creation block, and votes are weighted by `getPastVotes` at that block, with quorum measured against `getPastTotalSupply` at the same block. Tokens moved after the snapshot carry no extra votes, so buying or borrowing tokens to vote twice does not work. Passive holders can `delegate` their voting power to someone else; like any `ERC20Votes` token, balances only count once delegated, so holders who vote themselves must first delegate to their own address.
+* **Proposal Execution:** A proposal can carry an encoded call to one of AIChain's own parameter setters (`setDataStakeAmount`, `setChallengeParameters`, `setDataReuseRoyaltyBps`, `setRewardSplits`, `setEvaluationParameters`, `setProposalTimelock`, `setLicenseStakerShareBps`, `setReputationParameters`, `setHoldoutRevealPeriod`, `setModelSubmissionBond`, `setTreasuryFees`) or its treasury withdrawals. Once accepted, it waits out `proposalTimelock` and then anyone can `executeProposal`, which makes the call from the contract itself. Only selectors in `isGovernableFunction` can be proposed, so a proposal can never move the AICT or ETH the contract holds in escrow beyond the treasury's own balances. Proposals without a call remain signalling votes.
+* **Committee Management:** The owner adds and removes evaluators; evaluators post a bond with `depositEvaluatorBond` before committing and can withdraw it after leaving the committee once none of their commitments is still awaiting a reveal.
 * **ReentrancyGuard:** Added `ReentrancyGuard` to protect against reentrancy attacks, a common vulnerability in smart contracts.
 * **OpenZeppelin Imports:** Using OpenZeppelin contracts for ERC20 token functionality, ownership management, safe math operations, and reentrancy protection.  This promotes security and best practices.
 * **Events:**  Events are emitted for significant actions, making it easier to track activity on the blockchain.
 * **Clear Error Messages:** Require statements include informative error messages to help users understand why a transaction failed.
 * **Comments:** Comprehensive comments explain the purpose and functionality of each function and variable.
 * **Robust Token Model:** The `AIChainToken` uses the standard ERC20 implementation and it is possible to distribute tokens to other parties.
-* **Placeholder Performance Evaluation and Security:**  The performance evaluation process is greatly simplified (simulated) and needs substantial work in real world implementation for model evaluation.  Similarly, the `_distributeStake` function and the `for` loop with `hasStakedOnData` needs to be revisited for security.
-* **Secure Distribution**: the reward distribution for data submissions are not very secure. The loop and data submission can be changed by malicious attackers and rewards will be drained.
+* **Placeholder Performance Evaluation and Security:**  The performance evaluation process is greatly simplified (simulated) and needs substantial work in real world implementation for model evaluation.
+* **Reputation with Decay:** Every address has a reputation score. It rises by `reputationReward` when a challenge is decided in its favour (as submitter, QA staker or challenger) or when its dataset is used by a winning model, and falls by `reputationPenalty` when a challenge goes against it. Decay is applied lazily: `getReputation` halves the stored score for every full `reputationHalfLife` since its last update, and updates keep the partial period so that frequent activity does not reset decay. `requiredDataStake` discounts `dataStakeAmount` in proportion to reputation, up to `maxStakeDiscountBps`, so trusted participants stake less to submit, vouch for or challenge data.
+* **Data Challenge Resolution:** The first `challengeData` opens a `challengePeriod` window in which anyone can join either side. After it closes, anyone can call `resolveDataChallenge`: the data stays valid unless challengers staked more than the submitter and its QA stakers. The losing side's stakes, minus a `challengeFeeBps` protocol fee, are shared by the winners pro rata to stake, and each winner pulls their stake and share with `withdrawDataStake`. Data under an open challenge cannot be declared in new model submissions.
 
 **Important Considerations for Real-World Implementation:**
 
@@ -474,7 +1677,6 @@ contract AIChain is ERC20, Ownable, ReentrancyGuard {
 * **Off-Chain Computation:**  Consider using off-chain computation for tasks that are too expensive to perform on-chain, such as complex model evaluation or data preprocessing.  This could involve using trusted execution environments (TEEs) or other techniques to ensure the integrity of the off-chain computation.
 * **Data Privacy:**  Address data privacy concerns by using techniques like differential privacy or federated learning to protect sensitive training data.
 * **Cost of Operation**:  The current implementation is extremely expensive and dangerous as the state variables are being read in a loop. Revise the code using index or event.
-* **Secure Distributions**: Review the `_distributeStake` function to make sure distribution is fair and safe. It currently has problems in reward distribution to the correct users.
 * **Attack Prevention**: the contract could be under attack that the malicious data submitters can change the data URI and metadata URI.
 
 This comprehensive contract provides a solid foundation for building a decentralized AI model training ecosystem.  Remember to address the security and gas optimization considerations before deploying it to a production environment.
@@ -1,32 +1,95 @@
 ```solidity
 pragma solidity ^0.8.0;
 
+import "@openzeppelin/contracts/access/AccessControl.sol";
+import "@openzeppelin/contracts/token/ERC1155/ERC1155.sol";
+
+/**
+ * @dev Checkpointed voting power of a governance token, as exposed by OpenZeppelin's `Votes`/`ERC20Votes`.
+ */
+interface IVotes {
+    function getPastVotes(address account, uint256 timepoint) external view returns (uint256);
+    function getPastTotalSupply(uint256 timepoint) external view returns (uint256);
+}
+
+/**
+ * @title DSIB Impact Shares
+ * @notice ERC1155 receipts for DSIB positions.  The token id is the project ID and one share is minted per wei contributed.
+ * @dev Deployed by `DSIBDAO`, which alone can mint and burn.  Shares are freely transferable, so DSIB positions can be traded.
+ */
+contract ImpactShares is ERC1155 {
+    address public immutable dao;
+
+    constructor() ERC1155("") {
+        dao = msg.sender;
+    }
+
+    modifier onlyDAO() {
+        require(msg.sender == dao, "Only the DSIB DAO can mint or burn impact shares.");
+        _;
+    }
+
+    function mint(address _to, uint256 _projectId, uint256 _amount) external onlyDAO {
+        _mint(_to, _projectId, _amount, "");
+    }
+
+    function burn(address _from, uint256 _projectId, uint256 _amount) external onlyDAO {
+        _burn(_from, _projectId, _amount);
+    }
+}
+
 /**
  * @title Decentralized Social Impact Bond (DSIB) - DAO Governed
  * @author Gemini
  * @notice This contract implements a Decentralized Social Impact Bond (DSIB) framework, leveraging DAO governance for project selection, milestone validation, and outcome verification.
  *
  *  Outline:
- *  1.  **Project Proposal & DAO Governance:** Projects are proposed and voted on by the DAO.  Funding is locked upon approval.
+ *  1.  **Project Proposal & DAO Governance:** Projects are proposed and voted on by the DAO, with voting power taken from a governance token snapshot at proposal creation.  Funding is locked upon approval in a per-project escrow.
  *  2.  **Milestone Tracking:**  Outcomes providers submit milestone achievements, subject to community voting.
  *  3.  **Outcome Measurement & Validation:**  Independent evaluators (oracles, DAO members) report on the actual social impact, also subject to community verification.
- *  4.  **Repayment & Rewards:**  Investors (impact investors) receive repayments based on the verified social impact, potentially earning a return on investment.  Service providers are rewarded for achieved milestones.
+ *  4.  **Repayment & Rewards:**  Investors (impact investors) receive repayments based on the verified social impact, potentially earning a return on investment.  Service providers are rewarded for achieved milestones through vesting streams that release linearly over each milestone period and pause while the next milestone report is overdue.
  *  5.  **Transparency & Auditability:**  All data (proposals, votes, milestones, outcomes, repayments) is immutably recorded on the blockchain.
  *
  *  Function Summary:
- *  -   `proposeProject(string memory _projectName, string memory _projectDescription, uint256 _targetFunding, address _serviceProvider, string[] memory _milestoneDescriptions, uint256[] memory _milestoneFunding)`:  Allows any user to propose a new DSIB project, specifying target funding, service provider, and milestone details.
- *  -   `voteOnProject(uint256 _projectId, bool _vote)`:  Allows DAO members to vote on proposed projects.
- *  -   `fundProject(uint256 _projectId) payable`: Allows investors to contribute to a DSIB project that has been approved by the DAO.
+ *  -   `proposeProject(string memory _projectName, string memory _projectDescription, uint256 _targetFunding, address _serviceProvider, string[] memory _milestoneDescriptions, uint256[] memory _milestoneFunding, uint256[] memory _milestoneDurations, ImpactBand[] memory _impactBands)`:  Allows any user to propose a new DSIB project, specifying target funding, service provider, milestone details, and the impact bands that set its repayment (the default bands if empty).
+ *  -   `voteOnProject(uint256 _projectId, bool _vote)`:  Allows governance token holders to vote on proposed projects until the voting deadline.
+ *  -   `finalizeVote(uint256 _projectId)`:  Allows anyone to settle a project vote after its deadline.  The project is approved if the quorum was met in favour and rejected otherwise.
+ *  -   `depositProviderBond(uint256 _projectId) payable`:  Allows the service provider of an approved project to post the performance bond that must be in place before investors can fund it.
+ *  -   `refundProviderBond(uint256 _projectId)`:  Returns the unslashed part of the performance bond to the service provider once the outcome is verified or final.
+ *  -   `fundProject(uint256 _projectId) payable`: Allows investors to contribute to a DSIB project that has been approved by the DAO.  Each investor's contribution is recorded, and once the target is reached every investor can claim `ImpactShares` equal to their contribution.
+ *  -   `claimShares(uint256 _projectId)`:  Mints the caller's `ImpactShares` for a fully funded project.  `requestRepayment` claims them automatically.
  *  -   `submitMilestone(uint256 _projectId, uint256 _milestoneIndex)`:  Allows the service provider to submit a milestone as completed.
- *  -   `voteOnMilestone(uint256 _projectId, uint256 _milestoneIndex, bool _vote)`:  Allows DAO members to vote on whether a milestone has been successfully achieved.
- *  -   `reportOutcome(uint256 _projectId, uint256 _impactScore)`:  Allows designated outcome evaluators to report on the social impact of a project.
- *  -   `voteOnOutcome(uint256 _projectId, uint256 _impactScore, bool _vote)`:  Allows DAO members to vote on the validity of the reported outcome.
- *  -   `requestRepayment(uint256 _projectId)`:  Allows investors to request repayment based on the validated impact score.
+ *  -   `voteOnMilestone(uint256 _projectId, uint256 _milestoneIndex, bool _vote)`:  Allows governance token holders to vote on whether a milestone has been successfully achieved until the voting deadline.
+ *  -   `finalizeMilestoneVote(uint256 _projectId, uint256 _milestoneIndex)`:  Allows anyone to settle a milestone vote after its deadline.  Approval opens a vesting stream to the service provider; the milestone is rejected if the quorum was not met in favour.
+ *  -   `claimMilestoneStream(uint256 _projectId, uint256 _milestoneIndex)`:  Allows the service provider to claim the vested portion of a milestone payout.
+ *  -   `clawbackMilestone(uint256 _projectId, uint256 _milestoneIndex, bool _support)`:  Allows governance token holders to vote for or against clawing back an approved milestone whose work is rejected.  Once the quorum is reached the vested part stays with the service provider and the rest returns to the project's escrow.
+ *  -   `finalizeClawback(uint256 _projectId, uint256 _milestoneIndex)`:  Allows anyone to settle a clawback vote that is still open at its deadline.  The clawback is executed if the quorum was met in favour and rejected otherwise.
+ *  -   `pauseMilestoneStream(uint256 _projectId, uint256 _milestoneIndex)`:  Allows anyone to pause a milestone stream while the next milestone report is overdue.
+ *  -   `getMilestoneStream(uint256 _projectId, uint256 _milestoneIndex) public view returns (VestingStream memory)`: Retrieves the vesting stream of a milestone.
+ *  -   `getClaimableAmount(uint256 _projectId, uint256 _milestoneIndex) public view returns (uint256)`: Retrieves the currently claimable amount of a milestone stream.
+ *  -   `reportOutcome(uint256 _projectId, uint256 _impactScore)`:  Allows designated outcome evaluators to submit a score for the social impact of a project.  Once `outcomeEvaluatorQuorum` scores are in, their median becomes the reported score and the DAO verification vote opens.
+ *  -   `getOutcomeSubmissions(uint256 _projectId) public view returns (address[] memory, uint256[] memory)`: Retrieves the evaluators and scores submitted for a project's outcome.
+ *  -   `voteOnOutcome(uint256 _projectId, bool _vote)`:  Allows governance token holders to vote on the validity of the reported outcome.  Voters accept or reject the evaluators' median score; they cannot replace it.
+ *  -   `finalizeOutcomeVote(uint256 _projectId)`:  Allows anyone to settle an outcome vote that is still open at its deadline.  The outcome is verified if the quorum was met in favour and sent to re-evaluation otherwise.
+ *  -   `setVotingConfig(ProposalType _proposalType, uint256 _quorumPercentage, uint256 _votingPeriod)`:  Sets the quorum (as a percentage of the snapshotted token supply) and voting period of a proposal type.
+ *  -   `getBallot(ProposalType _proposalType, uint256 _projectId, uint256 _milestoneIndex) public view returns (Ballot memory)`: Retrieves the tally of a vote.
+ *  -   `submitReEvaluation(uint256 _projectId, uint256 _impactScore)`:  Allows outcome evaluators other than the original reporters to score a disputed outcome until the re-evaluation deadline.  Once `outcomeEvaluatorQuorum` scores are in, their median becomes the re-evaluated score.
+ *  -   `closeReEvaluation(uint256 _projectId)`:  Allows anyone to settle a re-evaluation round that missed its quorum by the deadline.  The median of the scores cast so far is taken; without any, the outcome goes to the arbitrator, or the reported score stands if there is none.
+ *  -   `escalateOutcome(uint256 _projectId) payable`:  Allows anyone to escalate a re-evaluated outcome to the external arbitrator by posting the escalation bond within the escalation window.
+ *  -   `arbitrateOutcome(uint256 _projectId, uint256 _impactScore)`:  Allows the arbitrator to set the final impact score of an escalated outcome.  The bond is returned if the arbitrator changes the re-evaluated score and forfeited to the project's escrow otherwise.
+ *  -   `finalizeReEvaluation(uint256 _projectId)`:  Finalizes the re-evaluated score once the escalation window has passed without an escalation.
+ *  -   `fundRepaymentYield(uint256 _projectId) payable`:  Allows outcome payers to add funds to a project's escrow to cover repayments above principal, until the first repayment.
+ *  -   `requestRepayment(uint256 _projectId)`:  Allows impact share holders to burn their shares for their pro-rata share of the repayment set by the impact band of the validated impact score.  A project escrow that cannot cover the repayment is shared pro-rata to shares.
+ *  -   `quoteRepayment(uint256 _projectId, uint256 _impactScore) public view returns (uint256)`:  Quotes a project's total repayment for a hypothetical impact score, including the provider bond slashed so far.
+ *  -   `quoteInvestorRepayment(uint256 _projectId, address _investor, uint256 _impactScore) public view returns (uint256)`:  Quotes the repayment of an address's impact shares for a hypothetical impact score.
+ *  -   `getImpactBands(uint256 _projectId) public view returns (ImpactBand[] memory)`: Retrieves the impact bands of a project.
  *  -   `getProjectDetails(uint256 _projectId) public view returns (Project memory)`:  Retrieves the details of a specific project.
  *  -   `getMilestoneStatus(uint256 _projectId, uint256 _milestoneIndex) public view returns (MilestoneStatus)`: Retrieves the status of a milestone.
+ *  -   `addOutcomeEvaluator(address _evaluator)` / `removeOutcomeEvaluator(address _evaluator)`:  Grants or revokes `OUTCOME_EVALUATOR_ROLE`.  Restricted to `EVALUATOR_MANAGER_ROLE`.
+ *  -   `setVotingConfig`, `setDefaultImpactBands`, `setOutcomeDisputeParameters`, `setProviderBondParameters`, `changeMilestoneReportInterval`:  Parameter setters restricted to `PARAM_SETTER_ROLE`.
  */
 
-contract DSIBDAO {
+contract DSIBDAO is AccessControl {
 
     // Structs
     struct Project {
@@ -37,25 +100,93 @@ contract DSIBDAO {
         address serviceProvider;
         uint256 impactScore;
         bool approved;
+        bool rejected;
         bool fundingComplete;
         bool impactReported;
-        bool repaymentRequested;
         address[] investors;
+        uint256 milestoneCount;
+        OutcomeStatus outcomeStatus;
+    }
+
+    enum OutcomeStatus {
+        NotReported,    // Collecting evaluator scores
+        Reported,       // Awaiting the DAO vote on the reported score
+        Verified,       // The DAO accepted the reported score
+        ReEvaluation,   // The DAO rejected it; evaluators are re-scoring the project
+        ReEvaluated,    // Re-evaluated score awaiting the escalation window
+        Escalated,      // Bonded escalation awaiting the arbitrator
+        Final           // Settled by re-evaluation or arbitration
+    }
+
+    struct ImpactBand {
+        uint256 minScore;       // Lowest impact score in the band
+        uint256 repaymentBps;   // Repayment as basis points of principal, e.g. 11000 for principal plus 10% yield
+    }
+
+    struct EvaluatorReputation {
+        uint256 submissions;        // Outcome scores that went into a median
+        uint256 totalDivergence;    // Sum of each score's absolute distance from its median
+    }
+
+    struct ProviderBond {
+        uint256 posted;     // Deposited by the service provider
+        uint256 slashed;    // Moved into the project's escrow for investors
+        bool refunded;
+    }
+
+    struct OutcomeDispute {
+        uint256 reEvaluationCount;
+        uint256 reEvaluationDeadline;   // No re-evaluations are accepted after this timestamp
+        uint256 reEvaluatedScore;
+        uint256 escalationDeadline;
+        address escalator;
+        uint256 bond;
     }
 
     enum MilestoneStatus {
         Proposed,
         Submitted,
         Approved,
-        Rejected
+        Rejected,
+        ClawedBack
     }
 
     struct Milestone {
         string description;
         uint256 fundingAmount;
         MilestoneStatus status;
-        uint256 approvalVotes;
-        uint256 rejectionVotes;
+        uint256 vestingDuration; // Period over which the milestone payout is streamed once approved
+    }
+
+    enum ProposalType {
+        Project,
+        Milestone,
+        Outcome,
+        Clawback
+    }
+
+    struct VotingConfig {
+        uint256 quorumPercentage; // Share of the snapshotted supply that must vote in favour
+        uint256 votingPeriod;
+    }
+
+    struct Ballot {
+        uint256 snapshot;       // Block whose voting power counts
+        uint256 deadline;       // No votes are accepted after this timestamp
+        uint256 quorumVotes;    // Votes in favour required to pass
+        uint256 totalVotes;     // Total voting power at the snapshot
+        uint256 forVotes;
+        uint256 againstVotes;
+    }
+
+    struct VestingStream {
+        uint256 totalAmount;
+        uint256 claimedAmount;
+        uint256 startTime;
+        uint256 duration;
+        uint256 pausedAt;        // Timestamp the stream was paused at, if paused
+        uint256 pausedDuration;  // Total time spent paused, excluded from vesting
+        bool paused;
     }
 
 
@@ -63,60 +194,112 @@ contract DSIBDAO {
     uint256 public projectCounter;
     mapping(uint256 => Project) public projects;
     mapping(uint256 => mapping(uint256 => Milestone)) public projectMilestones;
-    mapping(uint256 => mapping(uint256 => mapping(address => bool))) public milestoneVotes; //projectId => milestoneIndex => voter => voted
-    mapping(uint256 => mapping(address => bool)) public projectVotes; // projectId => voter => voted (for project approval)
-    mapping(uint256 => mapping(address => bool)) public outcomeVotes; //projectId => voter => voted (for Outcome approval)
-
-    //DAO related - replace with actual DAO functionality later - for now, assume a simple voting majority
-    address[] public daoMembers;
-    uint256 public quorum = 5; // Minimum number of votes required. Adjust as needed
-
-    uint256 public outcomeEvaluatorQuorum = 3;
-    address[] public outcomeEvaluators;
-
-    uint256 public investorRepaymentPercentage = 75; //What % of funding should be payed back based on impact score. 75 mean, pay 75 % of funding.
+    mapping(uint256 => mapping(uint256 => VestingStream)) public milestoneStreams; //projectId => milestoneIndex => stream
+    mapping(uint256 => mapping(address => uint256)) public investorContributions; //projectId => investor => amount funded
+    ImpactShares public impactShares; // Transferable receipts for each project's investors, redeemed on repayment
+    mapping(uint256 => uint256) public projectEscrow; //projectId => raised capital not yet committed to milestones or repaid
+    mapping(uint256 => uint256) public redeemedShares; //projectId => impact shares burned for repayment
+    mapping(uint256 => mapping(address => bool)) public sharesClaimed; //projectId => investor => impact shares minted
+    mapping(uint256 => mapping(uint256 => bool)) public clawbackRejected; //projectId => milestoneIndex => clawback vote rejected
+
+    //DAO related - voting power is the governance token balance snapshotted when a proposal is created
+    IVotes public governanceToken;
+    mapping(ProposalType => VotingConfig) public votingConfigs;
+    mapping(bytes32 => Ballot) internal ballots; //ballotId => tally
+    mapping(bytes32 => mapping(address => bool)) public hasVoted; //ballotId => voter => voted
+
+    // Roles - ADMIN_ROLE administers every role, EVALUATOR_MANAGER_ROLE administers the outcome evaluators
+    bytes32 public constant ADMIN_ROLE = keccak256("ADMIN_ROLE");
+    bytes32 public constant EVALUATOR_MANAGER_ROLE = keccak256("EVALUATOR_MANAGER_ROLE");
+    bytes32 public constant PARAM_SETTER_ROLE = keccak256("PARAM_SETTER_ROLE");
+    bytes32 public constant OUTCOME_EVALUATOR_ROLE = keccak256("OUTCOME_EVALUATOR_ROLE");
+
+    uint256 public outcomeEvaluatorQuorum = 3; // Scores needed to report an outcome or settle a re-evaluation round
+    mapping(uint256 => address[]) internal outcomeReporters; //projectId => evaluators who scored the outcome
+    mapping(uint256 => mapping(address => uint256)) public outcomeSubmissions; //projectId => evaluator => score
+    mapping(uint256 => mapping(address => bool)) public outcomeSubmitted; //projectId => evaluator => submitted
+    mapping(address => EvaluatorReputation) public evaluatorReputations;
+
+    mapping(uint256 => OutcomeDispute) public outcomeDisputes; //projectId => dispute
+    mapping(uint256 => mapping(address => bool)) public reEvaluationSubmitted; //projectId => evaluator => submitted
+    mapping(uint256 => uint256[]) internal reEvaluationScores; //projectId => re-evaluation scores, in submission order
+    address public arbitrator; // External arbitrator of last resort for escalated outcomes
+    uint256 public escalationBond = 1 ether;
+    uint256 public escalationWindow = 7 days;
+    uint256 public reEvaluationPeriod = 14 days; // Time other evaluators have to re-score a disputed outcome
+
+    mapping(uint256 => ImpactBand[]) internal projectImpactBands; //projectId => bands, ascending by minScore
+    ImpactBand[] public defaultImpactBands; // Used for projects proposed without their own bands
+
+    mapping(uint256 => ProviderBond) public providerBonds; //projectId => service provider performance bond
+    uint256 public providerBondPercentage = 10; // Bond required from the service provider, as a percentage of target funding
+    uint256 public lowImpactThreshold = 40; // Final scores below this slash the bond in proportion to the shortfall
+
+    uint256 public milestoneReportInterval = 30 days; //Time after a milestone approval within which the next milestone must be submitted.
 
     // Events
     event ProjectProposed(uint256 projectId, string projectName, address serviceProvider);
     event ProjectApproved(uint256 projectId);
+    event ProjectRejected(uint256 projectId);
     event ProjectFunded(uint256 projectId, address investor, uint256 amount);
+    event SharesClaimed(uint256 projectId, address investor, uint256 amount);
     event MilestoneSubmitted(uint256 projectId, uint256 milestoneIndex);
     event MilestoneApproved(uint256 projectId, uint256 milestoneIndex);
     event MilestoneRejected(uint256 projectId, uint256 milestoneIndex);
+    event OutcomeSubmitted(uint256 projectId, address evaluator, uint256 impactScore);
     event OutcomeReported(uint256 projectId, uint256 impactScore);
     event OutcomeVerified(uint256 projectId, uint256 impactScore);
+    event OutcomeDisputed(uint256 projectId);
+    event ReEvaluationSubmitted(uint256 projectId, address evaluator, uint256 impactScore);
+    event OutcomeReEvaluated(uint256 projectId, uint256 impactScore, uint256 escalationDeadline);
+    event ReEvaluationExpired(uint256 projectId, uint256 reEvaluationCount);
+    event OutcomeEscalated(uint256 projectId, address escalator, uint256 bond);
+    event OutcomeArbitrated(uint256 projectId, uint256 impactScore, bool bondReturned);
+    event OutcomeFinalized(uint256 projectId, uint256 impactScore);
     event RepaymentRequested(uint256 projectId, address investor, uint256 amount);
-
-    // Modifier to check if the sender is a DAO member
-    modifier onlyDAOMember() {
-        bool isMember = false;
-        for (uint256 i = 0; i < daoMembers.length; i++) {
-            if (daoMembers[i] == msg.sender) {
-                isMember = true;
-                break;
-            }
-        }
-        require(isMember, "Only DAO members can perform this action.");
-        _;
-    }
+    event MilestoneStreamStarted(uint256 projectId, uint256 milestoneIndex, uint256 amount, uint256 duration);
+    event MilestoneStreamClaimed(uint256 projectId, uint256 milestoneIndex, uint256 amount);
+    event MilestoneStreamPaused(uint256 projectId, uint256 milestoneIndex);
+    event MilestoneStreamResumed(uint256 projectId, uint256 milestoneIndex);
+    event MilestoneClawedBack(uint256 projectId, uint256 milestoneIndex, uint256 returnedAmount, uint256 releasedAmount);
+    event ClawbackRejected(uint256 projectId, uint256 milestoneIndex);
+    event ProviderBondDeposited(uint256 projectId, uint256 amount);
+    event ProviderBondSlashed(uint256 projectId, uint256 amount);
+    event ProviderBondRefunded(uint256 projectId, uint256 amount);
+    event BallotOpened(ProposalType proposalType, uint256 projectId, uint256 milestoneIndex, uint256 snapshot, uint256 deadline);
+    event VoteCast(ProposalType proposalType, uint256 projectId, uint256 milestoneIndex, address voter, bool support, uint256 weight);
 
      // Modifier to check if the sender is an outcome evaluator
     modifier onlyOutcomeEvaluator() {
-        bool isEvaluator = false;
-        for (uint256 i = 0; i < outcomeEvaluators.length; i++) {
-            if (outcomeEvaluators[i] == msg.sender) {
-                isEvaluator = true;
-                break;
-            }
-        }
-        require(isEvaluator, "Only outcome evaluators can perform this action.");
+        require(hasRole(OUTCOME_EVALUATOR_ROLE, msg.sender), "Only outcome evaluators can perform this action.");
         _;
     }
 
-    // Constructor - initialize with some DAO members and Outcome Evaluators
-    constructor(address[] memory _daoMembers, address[] memory _outcomeEvaluators) {
-        daoMembers = _daoMembers;
-        outcomeEvaluators = _outcomeEvaluators;
+    // Constructor - initialize with the governance token and some Outcome Evaluators.  The deployer starts with every management role.
+    constructor(IVotes _governanceToken, address[] memory _outcomeEvaluators) {
+        governanceToken = _governanceToken;
+        impactShares = new ImpactShares();
+
+        _setRoleAdmin(ADMIN_ROLE, ADMIN_ROLE);
+        _setRoleAdmin(EVALUATOR_MANAGER_ROLE, ADMIN_ROLE);
+        _setRoleAdmin(PARAM_SETTER_ROLE, ADMIN_ROLE);
+        _setRoleAdmin(OUTCOME_EVALUATOR_ROLE, EVALUATOR_MANAGER_ROLE);
+        _grantRole(ADMIN_ROLE, msg.sender);
+        _grantRole(EVALUATOR_MANAGER_ROLE, msg.sender);
+        _grantRole(PARAM_SETTER_ROLE, msg.sender);
+        for (uint256 i = 0; i < _outcomeEvaluators.length; i++) {
+            _grantRole(OUTCOME_EVALUATOR_ROLE, _outcomeEvaluators[i]);
+        }
+
+        votingConfigs[ProposalType.Project] = VotingConfig(50, 7 days);
+        votingConfigs[ProposalType.Milestone] = VotingConfig(50, 3 days);
+        votingConfigs[ProposalType.Outcome] = VotingConfig(50, 7 days);
+        votingConfigs[ProposalType.Clawback] = VotingConfig(50, 7 days);
+
+        // Below 40 investors take a haircut, 40-70 returns principal, above 70 pays 10% yield.
+        defaultImpactBands.push(ImpactBand(0, 5000));
+        defaultImpactBands.push(ImpactBand(40, 10000));
+        defaultImpactBands.push(ImpactBand(71, 11000));
     }
 
 
@@ -130,6 +313,8 @@ contract DSIBDAO {
      * @param _serviceProvider The address of the entity providing the services.
      * @param _milestoneDescriptions An array of descriptions for each milestone.
      * @param _milestoneFunding An array of funding amounts for each milestone, corresponding to the milestone descriptions.
+     * @param _milestoneDurations An array of vesting periods (in seconds) over which each approved milestone payout is streamed.
+     * @param _impactBands The impact bands that set the project's repayment, ascending by `minScore` and starting at 0.  Empty to use `defaultImpactBands`.
      */
     function proposeProject(
         string memory _projectName,
@@ -137,9 +322,12 @@ contract DSIBDAO {
         uint256 _targetFunding,
         address _serviceProvider,
         string[] memory _milestoneDescriptions,
-        uint256[] memory _milestoneFunding
+        uint256[] memory _milestoneFunding,
+        uint256[] memory _milestoneDurations,
+        ImpactBand[] memory _impactBands
     ) public {
         require(_milestoneDescriptions.length == _milestoneFunding.length, "Milestone descriptions and funding amounts must have the same length.");
+        require(_milestoneDescriptions.length == _milestoneDurations.length, "Milestone descriptions and durations must have the same length.");
         require(_targetFunding > 0, "Target funding must be greater than zero.");
 
         uint256 projectId = projectCounter++;
@@ -151,10 +339,12 @@ contract DSIBDAO {
             serviceProvider: _serviceProvider,
             impactScore: 0,
             approved: false,
+            rejected: false,
             fundingComplete: false,
             impactReported: false,
-            repaymentRequested: false,
-            investors: new address[](0)
+            investors: new address[](0),
+            milestoneCount: _milestoneDescriptions.length,
+            outcomeStatus: OutcomeStatus.NotReported
         });
 
         for (uint256 i = 0; i < _milestoneDescriptions.length; i++) {
@@ -162,45 +352,88 @@ contract DSIBDAO {
                 description: _milestoneDescriptions[i],
                 fundingAmount: _milestoneFunding[i],
                 status: MilestoneStatus.Proposed,
-                approvalVotes: 0,
-                rejectionVotes: 0
+                vestingDuration: _milestoneDurations[i]
             });
         }
 
+        if (_impactBands.length == 0) {
+            _impactBands = defaultImpactBands;
+        }
+        _validateImpactBands(_impactBands);
+        for (uint256 i = 0; i < _impactBands.length; i++) {
+            projectImpactBands[projectId].push(_impactBands[i]);
+        }
+
         emit ProjectProposed(projectId, _projectName, _serviceProvider);
+        _openBallot(ProposalType.Project, projectId, 0);
     }
 
 
     /**
-     * @notice Allows DAO members to vote on a proposed project.
+     * @notice Allows governance token holders to vote on a proposed project, weighted by their voting power at the proposal's snapshot.
+     * @dev The vote is only tallied by `finalizeVote` once the voting period has ended.
      * @param _projectId The ID of the project to vote on.
      * @param _vote `true` to approve, `false` to reject.
      */
-    function voteOnProject(uint256 _projectId, bool _vote) public onlyDAOMember {
-        require(projects[_projectId].approved == false, "Project already approved or rejected");
-        require(!projectVotes[_projectId][msg.sender], "You have already voted on this project.");
+    function voteOnProject(uint256 _projectId, bool _vote) public {
+        require(!projects[_projectId].approved && !projects[_projectId].rejected, "Project already approved or rejected");
 
-        projectVotes[_projectId][msg.sender] = true;
+        _castVote(ProposalType.Project, _projectId, 0, _vote);
+    }
 
-        uint256 approveCount = 0;
-        uint256 rejectCount = 0;
-        for(uint256 i = 0; i < daoMembers.length; i++){
-            if(projectVotes[_projectId][daoMembers[i]]){
-                approveCount++;
-            } else {
-                rejectCount++;
-            }
-        }
 
-        if (approveCount >= quorum && _vote) {
+    /**
+     * @notice Settles a project vote after its deadline.  Callable by anyone.
+     * @dev The project is rejected by default unless the votes in favour met the quorum and outnumbered those against.
+     * @param _projectId The ID of the project.
+     */
+    function finalizeVote(uint256 _projectId) public {
+        require(!projects[_projectId].approved && !projects[_projectId].rejected, "Project already approved or rejected");
+
+        if (_closeBallot(ProposalType.Project, _projectId, 0)) {
             projects[_projectId].approved = true;
             emit ProjectApproved(_projectId);
-        }  else if (rejectCount > (daoMembers.length - quorum) && !_vote){
-            projects[_projectId].approved = false;
+        } else {
+            projects[_projectId].rejected = true;
+            emit ProjectRejected(_projectId);
         }
+    }
 
-        //Implement proper voting mechanism that calculates based on staking, voting periods etc in future versions
 
+    /**
+     * @notice Allows the service provider to post the performance bond of an approved project.
+     * @dev The caller must send exactly `requiredProviderBond`.  Investors cannot fund the project until the bond is posted.
+     * @param _projectId The ID of the project.
+     */
+    function depositProviderBond(uint256 _projectId) public payable {
+        require(msg.sender == projects[_projectId].serviceProvider, "Only the service provider can post the bond.");
+        require(projects[_projectId].approved, "Project must be approved before posting the bond.");
+        require(providerBonds[_projectId].posted == 0, "Bond has already been posted.");
+        require(msg.value > 0 && msg.value == requiredProviderBond(_projectId), "Bond must equal the required provider bond.");
+
+        providerBonds[_projectId].posted = msg.value;
+        emit ProviderBondDeposited(_projectId, msg.value);
+    }
+
+
+    /**
+     * @notice Returns the unslashed part of the performance bond to the service provider.
+     * @dev Only possible once the outcome is verified or final, when no further slashing can apply.
+     * @param _projectId The ID of the project.
+     */
+    function refundProviderBond(uint256 _projectId) public {
+        require(msg.sender == projects[_projectId].serviceProvider, "Only the service provider can refund the bond.");
+        OutcomeStatus outcomeStatus = projects[_projectId].outcomeStatus;
+        require(outcomeStatus == OutcomeStatus.Verified || outcomeStatus == OutcomeStatus.Final, "Impact must be verified or finalized before refunding the bond.");
+        ProviderBond storage bond = providerBonds[_projectId];
+        require(!bond.refunded, "Bond has already been refunded.");
+
+        bond.refunded = true;
+        uint256 refund = bond.posted - bond.slashed;
+        if (refund > 0) {
+            payable(msg.sender).transfer(refund);
+        }
+        emit ProviderBondRefunded(_projectId, refund);
     }
 
 
@@ -210,21 +443,19 @@ contract DSIBDAO {
      */
     function fundProject(uint256 _projectId) payable public {
         require(projects[_projectId].approved, "Project must be approved before funding.");
+        require(providerBonds[_projectId].posted > 0 || requiredProviderBond(_projectId) == 0, "Service provider bond must be posted before funding.");
         require(!projects[_projectId].fundingComplete, "Project funding is already complete.");
         require(projects[_projectId].currentFunding + msg.value <= projects[_projectId].targetFunding, "Funding exceeds the target amount.");
 
+        require(msg.value > 0, "Funding amount must be greater than zero.");
+
         projects[_projectId].currentFunding += msg.value;
+        projectEscrow[_projectId] += msg.value;
 
-        bool investorExists = false;
-        for (uint256 i = 0; i < projects[_projectId].investors.length; i++) {
-            if (projects[_projectId].investors[i] == msg.sender) {
-                investorExists = true;
-                break;
-            }
-        }
-        if (!investorExists) {
+        if (investorContributions[_projectId][msg.sender] == 0) {
             projects[_projectId].investors.push(msg.sender);
         }
+        investorContributions[_projectId][msg.sender] += msg.value;
 
         emit ProjectFunded(_projectId, msg.sender, msg.value);
 
@@ -234,6 +465,20 @@ contract DSIBDAO {
     }
 
 
+    /**
+     * @notice Mints the caller's impact shares, one per wei contributed, once the project is fully funded.
+     * @dev Shares are pulled by each investor rather than pushed on the final deposit, so an investor that cannot receive ERC1155 tokens only blocks its own claim.
+     * @param _projectId The ID of the project.
+     */
+    function claimShares(uint256 _projectId) public {
+        require(projects[_projectId].fundingComplete, "Project funding must be complete before claiming shares.");
+        require(investorContributions[_projectId][msg.sender] > 0, "You have not contributed to this project.");
+        require(!sharesClaimed[_projectId][msg.sender], "Shares have already been claimed.");
+
+        _claimShares(_projectId, msg.sender);
+    }
+
+
     /**
      * @notice Allows the service provider to submit a milestone as completed.
      * @param _projectId The ID of the project.
@@ -245,123 +490,583 @@ contract DSIBDAO {
 
         projectMilestones[_projectId][_milestoneIndex].status = MilestoneStatus.Submitted;
         emit MilestoneSubmitted(_projectId, _milestoneIndex);
+        _openBallot(ProposalType.Milestone, _projectId, _milestoneIndex);
+
+        // Submitting the next report resumes the previous milestone's stream if it was paused as overdue.
+        if (_milestoneIndex > 0) {
+            VestingStream storage previousStream = milestoneStreams[_projectId][_milestoneIndex - 1];
+            if (previousStream.paused) {
+                previousStream.pausedDuration += block.timestamp - previousStream.pausedAt;
+                previousStream.pausedAt = 0;
+                previousStream.paused = false;
+                emit MilestoneStreamResumed(_projectId, _milestoneIndex - 1);
+            }
+        }
     }
 
 
     /**
-     * @notice Allows DAO members to vote on whether a milestone has been successfully achieved.
+     * @notice Allows governance token holders to vote on whether a milestone has been successfully achieved, weighted by their voting power when it was submitted.
+     * @dev The vote is only tallied by `finalizeMilestoneVote` once the voting period has ended.
      * @param _projectId The ID of the project.
      * @param _milestoneIndex The index of the milestone being voted on.
      * @param _vote `true` to approve, `false` to reject.
      */
-    function voteOnMilestone(uint256 _projectId, uint256 _milestoneIndex, bool _vote) public onlyDAOMember {
+    function voteOnMilestone(uint256 _projectId, uint256 _milestoneIndex, bool _vote) public {
         require(projectMilestones[_projectId][_milestoneIndex].status == MilestoneStatus.Submitted, "Milestone must be in Submitted state.");
-        require(!milestoneVotes[_projectId][_milestoneIndex][msg.sender], "You have already voted on this milestone.");
 
-        milestoneVotes[_projectId][_milestoneIndex][msg.sender] = true;
+        _castVote(ProposalType.Milestone, _projectId, _milestoneIndex, _vote);
+    }
 
-        if (_vote) {
-            projectMilestones[_projectId][_milestoneIndex].approvalVotes++;
-        } else {
-            projectMilestones[_projectId][_milestoneIndex].rejectionVotes++;
-        }
 
-        if (projectMilestones[_projectId][_milestoneIndex].approvalVotes >= quorum && _vote) {
+    /**
+     * @notice Settles a milestone vote after its deadline.  Callable by anyone.
+     * @dev The milestone is rejected by default unless the votes in favour met the quorum and outnumbered those against.
+     * @param _projectId The ID of the project.
+     * @param _milestoneIndex The index of the milestone.
+     */
+    function finalizeMilestoneVote(uint256 _projectId, uint256 _milestoneIndex) public {
+        require(projectMilestones[_projectId][_milestoneIndex].status == MilestoneStatus.Submitted, "Milestone must be in Submitted state.");
+
+        if (_closeBallot(ProposalType.Milestone, _projectId, _milestoneIndex)) {
+            // Commit the milestone funding from this project's escrow only.
+            uint256 fundingAmount = projectMilestones[_projectId][_milestoneIndex].fundingAmount;
+            require(projectEscrow[_projectId] >= fundingAmount, "Insufficient project escrow for milestone.");
+            projectEscrow[_projectId] -= fundingAmount;
+
             projectMilestones[_projectId][_milestoneIndex].status = MilestoneStatus.Approved;
-            //Potentially release the milestone funding to the service provider here
-            payable(projects[_projectId].serviceProvider).transfer(projectMilestones[_projectId][_milestoneIndex].fundingAmount);
+            // Stream the milestone funding to the service provider over the milestone period.
+            milestoneStreams[_projectId][_milestoneIndex] = VestingStream({
+                totalAmount: fundingAmount,
+                claimedAmount: 0,
+                startTime: block.timestamp,
+                duration: projectMilestones[_projectId][_milestoneIndex].vestingDuration,
+                pausedAt: 0,
+                pausedDuration: 0,
+                paused: false
+            });
             emit MilestoneApproved(_projectId, _milestoneIndex);
-        } else if (projectMilestones[_projectId][_milestoneIndex].rejectionVotes >= (daoMembers.length - quorum) && !_vote) {
+            emit MilestoneStreamStarted(_projectId, _milestoneIndex, fundingAmount, projectMilestones[_projectId][_milestoneIndex].vestingDuration);
+        } else {
             projectMilestones[_projectId][_milestoneIndex].status = MilestoneStatus.Rejected;
-             emit MilestoneRejected(_projectId, _milestoneIndex);
+            emit MilestoneRejected(_projectId, _milestoneIndex);
+            _slashMilestoneBond(_projectId, _milestoneIndex);
         }
+    }
+
+
+    /**
+     * @notice Allows the service provider to claim the vested portion of an approved milestone's payout.
+     * @param _projectId The ID of the project.
+     * @param _milestoneIndex The index of the approved milestone.
+     */
+    function claimMilestoneStream(uint256 _projectId, uint256 _milestoneIndex) public {
+        require(msg.sender == projects[_projectId].serviceProvider, "Only the service provider can claim milestone streams.");
+        MilestoneStatus status = projectMilestones[_projectId][_milestoneIndex].status;
+        require(status == MilestoneStatus.Approved || status == MilestoneStatus.ClawedBack, "Milestone must be in Approved or ClawedBack state.");
+
+        VestingStream storage stream = milestoneStreams[_projectId][_milestoneIndex];
+        uint256 claimable = _vestedAmount(stream) - stream.claimedAmount;
+        require(claimable > 0, "Nothing to claim.");
 
-       //Implement proper voting mechanism that calculates based on staking, voting periods etc in future versions
+        stream.claimedAmount += claimable;
+        payable(msg.sender).transfer(claimable);
+
+        emit MilestoneStreamClaimed(_projectId, _milestoneIndex, claimable);
     }
 
 
     /**
-     * @notice Allows designated outcome evaluators to report on the social impact of a project.
+     * @notice Allows governance token holders to vote for or against clawing back an approved milestone whose work is rejected.
+     * @dev The first vote opens the clawback ballot and takes its snapshot.  Once the votes in favour reach the clawback quorum, the stream is frozen: the amount vested so far remains claimable by the service provider and the unvested remainder returns to the project's escrow for investors.  A clawback that can no longer reach the quorum is rejected for good.
+     * @param _projectId The ID of the project.
+     * @param _milestoneIndex The index of the approved milestone.
+     * @param _support `true` to claw the milestone back, `false` to keep it.
+     */
+    function clawbackMilestone(uint256 _projectId, uint256 _milestoneIndex, bool _support) public {
+        require(projectMilestones[_projectId][_milestoneIndex].status == MilestoneStatus.Approved, "Milestone must be in Approved state.");
+        require(!clawbackRejected[_projectId][_milestoneIndex], "Clawback has already been rejected.");
+
+        if (ballots[_ballotId(ProposalType.Clawback, _projectId, _milestoneIndex)].deadline == 0) {
+            _openBallot(ProposalType.Clawback, _projectId, _milestoneIndex);
+        }
+        (bool passed, bool failed) = _castVote(ProposalType.Clawback, _projectId, _milestoneIndex, _support);
+        if (passed) {
+            _clawback(_projectId, _milestoneIndex);
+        } else if (failed) {
+            clawbackRejected[_projectId][_milestoneIndex] = true;
+            emit ClawbackRejected(_projectId, _milestoneIndex);
+        }
+    }
+
+
+    /**
+     * @notice Settles a clawback vote that neither passed nor failed before its deadline.  Callable by anyone.
+     * @param _projectId The ID of the project.
+     * @param _milestoneIndex The index of the approved milestone.
+     */
+    function finalizeClawback(uint256 _projectId, uint256 _milestoneIndex) public {
+        require(projectMilestones[_projectId][_milestoneIndex].status == MilestoneStatus.Approved, "Milestone must be in Approved state.");
+        require(!clawbackRejected[_projectId][_milestoneIndex], "Clawback has already been rejected.");
+
+        if (_closeBallot(ProposalType.Clawback, _projectId, _milestoneIndex)) {
+            _clawback(_projectId, _milestoneIndex);
+        } else {
+            clawbackRejected[_projectId][_milestoneIndex] = true;
+            emit ClawbackRejected(_projectId, _milestoneIndex);
+        }
+    }
+
+
+    /**
+     * @notice Pauses a milestone stream while the next milestone report is overdue.  Callable by anyone; the stream resumes when the next milestone is submitted.
+     * @param _projectId The ID of the project.
+     * @param _milestoneIndex The index of the milestone whose stream should be paused.
+     */
+    function pauseMilestoneStream(uint256 _projectId, uint256 _milestoneIndex) public {
+        require(projectMilestones[_projectId][_milestoneIndex].status == MilestoneStatus.Approved, "Milestone must be in Approved state.");
+        require(_milestoneIndex + 1 < projects[_projectId].milestoneCount, "No further milestone report is due.");
+        require(projectMilestones[_projectId][_milestoneIndex + 1].status == MilestoneStatus.Proposed, "Next milestone has already been submitted.");
+
+        VestingStream storage stream = milestoneStreams[_projectId][_milestoneIndex];
+        require(!stream.paused, "Stream is already paused.");
+        require(block.timestamp > stream.startTime + milestoneReportInterval, "Next milestone report is not overdue.");
+
+        stream.paused = true;
+        stream.pausedAt = block.timestamp;
+
+        emit MilestoneStreamPaused(_projectId, _milestoneIndex);
+    }
+
+
+    /**
+     * @notice Allows designated outcome evaluators to submit a score for the social impact of a project.
+     * @dev Once `outcomeEvaluatorQuorum` scores are in, their median becomes the reported score, each evaluator's divergence from it is added to their reputation, and the DAO verification vote opens.
      * @param _projectId The ID of the project.
      * @param _impactScore A numerical score representing the social impact achieved.
      */
     function reportOutcome(uint256 _projectId, uint256 _impactScore) public onlyOutcomeEvaluator {
         require(projects[_projectId].fundingComplete, "Project funding must be complete before reporting outcomes.");
         require(!projects[_projectId].impactReported, "Impact already reported for this project.");
+        require(!outcomeSubmitted[_projectId][msg.sender], "You have already scored this outcome.");
 
-        projects[_projectId].impactScore = _impactScore;
+        outcomeSubmitted[_projectId][msg.sender] = true;
+        outcomeSubmissions[_projectId][msg.sender] = _impactScore;
+        outcomeReporters[_projectId].push(msg.sender);
+        emit OutcomeSubmitted(_projectId, msg.sender, _impactScore);
+
+        if (outcomeReporters[_projectId].length < outcomeEvaluatorQuorum) {
+            return;
+        }
+
+        (, uint256[] memory scores) = getOutcomeSubmissions(_projectId);
+        uint256 medianScore = _median(scores);
+        address[] storage reporters = outcomeReporters[_projectId];
+        for (uint256 i = 0; i < reporters.length; i++) {
+            uint256 score = outcomeSubmissions[_projectId][reporters[i]];
+            EvaluatorReputation storage reputation = evaluatorReputations[reporters[i]];
+            reputation.submissions++;
+            reputation.totalDivergence += score > medianScore ? score - medianScore : medianScore - score;
+        }
+
+        projects[_projectId].impactScore = medianScore;
         projects[_projectId].impactReported = true;
+        projects[_projectId].outcomeStatus = OutcomeStatus.Reported;
+
+        emit OutcomeReported(_projectId, medianScore);
+        _openBallot(ProposalType.Outcome, _projectId, 0);
+    }
+
 
-        emit OutcomeReported(_projectId, _impactScore);
+    /**
+     * @notice Retrieves the evaluators and scores submitted for a project's outcome.
+     * @param _projectId The ID of the project.
+     * @return The evaluators, in submission order, and their scores.
+     */
+    function getOutcomeSubmissions(uint256 _projectId) public view returns (address[] memory, uint256[] memory) {
+        address[] memory reporters = outcomeReporters[_projectId];
+        uint256[] memory scores = new uint256[](reporters.length);
+        for (uint256 i = 0; i < reporters.length; i++) {
+            scores[i] = outcomeSubmissions[_projectId][reporters[i]];
+        }
+        return (reporters, scores);
     }
 
 
     /**
-     * @notice Allows DAO members to vote on the validity of the reported outcome.
+     * @notice Allows governance token holders to vote on the validity of the reported outcome.
+     * @dev The vote verifies the median score stored by `reportOutcome`; a rejected score goes to re-evaluation rather than being replaced by the voters.
      * @param _projectId The ID of the project.
-     * @param _impactScore The reported impact score.
      * @param _vote `true` to approve, `false` to reject.
      */
-    function voteOnOutcome(uint256 _projectId, uint256 _impactScore, bool _vote) public onlyDAOMember {
+    function voteOnOutcome(uint256 _projectId, bool _vote) public {
         require(projects[_projectId].impactReported, "Impact must be reported before voting.");
-        require(!outcomeVotes[_projectId][msg.sender], "You have already voted on this outcome.");
+        require(projects[_projectId].outcomeStatus == OutcomeStatus.Reported, "Outcome is not awaiting a vote.");
 
-        outcomeVotes[_projectId][msg.sender] = true;
+        (bool passed, bool failed) = _castVote(ProposalType.Outcome, _projectId, 0, _vote);
+        if (passed) {
+            _verifyOutcome(_projectId);
+        } else if (failed) {
+            _disputeOutcome(_projectId);
+        }
+    }
 
-        uint256 approveCount = 0;
-        uint256 rejectCount = 0;
-        for(uint256 i = 0; i < daoMembers.length; i++){
-            if(outcomeVotes[_projectId][daoMembers[i]]){
-                approveCount++;
-            } else {
-                rejectCount++;
-            }
+
+    /**
+     * @notice Settles an outcome vote that neither passed nor failed before its deadline.  Callable by anyone.
+     * @dev An outcome the DAO did not verify within the voting period is treated as rejected and sent to re-evaluation.
+     * @param _projectId The ID of the project.
+     */
+    function finalizeOutcomeVote(uint256 _projectId) public {
+        require(projects[_projectId].outcomeStatus == OutcomeStatus.Reported, "Outcome is not awaiting a vote.");
+
+        if (_closeBallot(ProposalType.Outcome, _projectId, 0)) {
+            _verifyOutcome(_projectId);
+        } else {
+            _disputeOutcome(_projectId);
         }
+    }
 
-        if (approveCount >= quorum && _vote) {
-            projects[_projectId].impactScore = _impactScore;
-            emit OutcomeVerified(_projectId, _impactScore);
-        } else if (rejectCount > (daoMembers.length - quorum) && !_vote) {
-            //TODO - Implement fallback, dispute resolution mechanism
+
+    /**
+     * @notice Allows an outcome evaluator other than the original reporters to score a disputed outcome.
+     * @dev Once `outcomeEvaluatorQuorum` scores are in, their median becomes the re-evaluated score and the escalation window opens.
+     * @param _projectId The ID of the project.
+     * @param _impactScore The evaluator's impact score.
+     */
+    function submitReEvaluation(uint256 _projectId, uint256 _impactScore) public onlyOutcomeEvaluator {
+        require(projects[_projectId].outcomeStatus == OutcomeStatus.ReEvaluation, "Outcome is not being re-evaluated.");
+        OutcomeDispute storage dispute = outcomeDisputes[_projectId];
+        require(block.timestamp <= dispute.reEvaluationDeadline, "Re-evaluation period has passed.");
+        require(!outcomeSubmitted[_projectId][msg.sender], "The original reporters cannot re-evaluate the outcome.");
+        require(!reEvaluationSubmitted[_projectId][msg.sender], "You have already re-evaluated this outcome.");
+
+        reEvaluationSubmitted[_projectId][msg.sender] = true;
+        reEvaluationScores[_projectId].push(_impactScore);
+        dispute.reEvaluationCount++;
+        emit ReEvaluationSubmitted(_projectId, msg.sender, _impactScore);
+
+        if (dispute.reEvaluationCount >= outcomeEvaluatorQuorum) {
+            _settleReEvaluation(_projectId);
         }
+    }
+
 
-        //Implement proper voting mechanism that calculates based on staking, voting periods etc in future versions
+    /**
+     * @notice Settles a re-evaluation round that did not reach `outcomeEvaluatorQuorum` by its deadline.
+     * @dev The median of the scores cast so far is taken as if the quorum had been met.  Without any, the outcome is escalated to the arbitrator without a bond, or the reported score becomes final if no arbitrator is set.
+     * @param _projectId The ID of the project.
+     */
+    function closeReEvaluation(uint256 _projectId) public {
+        require(projects[_projectId].outcomeStatus == OutcomeStatus.ReEvaluation, "Outcome is not being re-evaluated.");
+        OutcomeDispute storage dispute = outcomeDisputes[_projectId];
+        require(block.timestamp > dispute.reEvaluationDeadline, "Re-evaluation period has not passed.");
+
+        emit ReEvaluationExpired(_projectId, dispute.reEvaluationCount);
+        if (dispute.reEvaluationCount > 0) {
+            _settleReEvaluation(_projectId);
+        } else if (arbitrator != address(0)) {
+            dispute.reEvaluatedScore = projects[_projectId].impactScore;
+            projects[_projectId].outcomeStatus = OutcomeStatus.Escalated;
+            emit OutcomeEscalated(_projectId, address(0), 0);
+        } else {
+            projects[_projectId].outcomeStatus = OutcomeStatus.Final;
+            emit OutcomeFinalized(_projectId, projects[_projectId].impactScore);
+            _slashImpactBond(_projectId);
+        }
     }
 
+
     /**
-     * @notice Allows investors to request repayment based on the validated impact score.
+     * @notice Escalates a re-evaluated outcome to the external arbitrator.
+     * @dev The caller must send exactly `escalationBond` before the escalation window closes.
      * @param _projectId The ID of the project.
      */
-    function requestRepayment(uint256 _projectId) public {
-        require(projects[_projectId].fundingComplete, "Project funding must be complete before requesting repayment.");
-        require(projects[_projectId].impactReported, "Impact must be reported before requesting repayment.");
-        require(!projects[_projectId].repaymentRequested, "Repayment has already been requested for this project.");
+    function escalateOutcome(uint256 _projectId) public payable {
+        require(projects[_projectId].outcomeStatus == OutcomeStatus.ReEvaluated, "Outcome must be re-evaluated before escalation.");
+        OutcomeDispute storage dispute = outcomeDisputes[_projectId];
+        require(block.timestamp <= dispute.escalationDeadline, "Escalation window has passed.");
+        require(arbitrator != address(0), "No arbitrator has been set.");
+        require(msg.value == escalationBond, "Escalation requires the exact escalation bond.");
+
+        dispute.escalator = msg.sender;
+        dispute.bond = msg.value;
+        projects[_projectId].outcomeStatus = OutcomeStatus.Escalated;
+
+        emit OutcomeEscalated(_projectId, msg.sender, msg.value);
+    }
 
-        uint256 repaymentAmount;
-        uint256 investorFunding;
 
-        //Calculate how much the investor funded
-        for (uint256 i = 0; i < projects[_projectId].investors.length; i++) {
-            if (projects[_projectId].investors[i] == msg.sender) {
-                investorFunding =  msg.value; //This only works if we keep track of who contributed how much.  Need to update funding logic to keep track of individual contribution.
-                break;
-            }
+    /**
+     * @notice Allows the arbitrator to set the final impact score of an escalated outcome.
+     * @dev The escalator gets the bond back if the arbitrator changes the re-evaluated score; otherwise it is forfeited to the project's escrow for investors.
+     * @param _projectId The ID of the project.
+     * @param _impactScore The final impact score.
+     */
+    function arbitrateOutcome(uint256 _projectId, uint256 _impactScore) public {
+        require(msg.sender == arbitrator, "Only the arbitrator can arbitrate outcomes.");
+        require(projects[_projectId].outcomeStatus == OutcomeStatus.Escalated, "Outcome has not been escalated.");
+
+        OutcomeDispute storage dispute = outcomeDisputes[_projectId];
+        uint256 bond = dispute.bond;
+        bool bondReturned = bond > 0 && _impactScore != dispute.reEvaluatedScore;
+        dispute.bond = 0;
+
+        projects[_projectId].impactScore = _impactScore;
+        projects[_projectId].outcomeStatus = OutcomeStatus.Final;
+
+        if (bondReturned) {
+            payable(dispute.escalator).transfer(bond);
+        } else {
+            projectEscrow[_projectId] += bond;
         }
-        require(investorFunding > 0, "You must be an investor in this project to request repayment.");
 
+        emit OutcomeArbitrated(_projectId, _impactScore, bondReturned);
+        emit OutcomeFinalized(_projectId, _impactScore);
+        _slashImpactBond(_projectId);
+    }
 
-        //Simple logic: If impact score is above threshold, investors get a return.  If below, they get a reduced repayment
-        //Can implement much more sophisticated algorithms to determine repayment based on impact score, including potential loss of investment if impact is minimal or negative.
-        repaymentAmount = (investorFunding * projects[_projectId].impactScore * investorRepaymentPercentage) / 10000; //Assume the impactScore is between 0-100 to make it into percentages
 
+    /**
+     * @notice Finalizes the re-evaluated score once the escalation window has passed without an escalation.
+     * @param _projectId The ID of the project.
+     */
+    function finalizeReEvaluation(uint256 _projectId) public {
+        require(projects[_projectId].outcomeStatus == OutcomeStatus.ReEvaluated, "Outcome is not awaiting finalization.");
+        require(block.timestamp > outcomeDisputes[_projectId].escalationDeadline, "Escalation window has not passed.");
+
+        projects[_projectId].outcomeStatus = OutcomeStatus.Final;
+        emit OutcomeFinalized(_projectId, projects[_projectId].impactScore);
+        _slashImpactBond(_projectId);
+    }
+
+    /**
+     * @notice Adds funds to a project's escrow, e.g. from an outcome payer, to cover repayments above principal.
+     * @param _projectId The ID of the project.
+     */
+    function fundRepaymentYield(uint256 _projectId) public payable {
+        require(projects[_projectId].approved, "Project must be approved before funding its yield.");
+        require(msg.value > 0, "Funding amount must be greater than zero.");
+        require(redeemedShares[_projectId] == 0, "Repayments have already started.");
+
+        projectEscrow[_projectId] += msg.value;
+    }
+
+    /**
+     * @notice Allows an impact share holder to burn their shares for their part of the repayment based on the validated impact score.
+     * @dev The impact band of the score sets the project's repayment pool, which is split pro-rata to impact shares.  If the escrow cannot cover the pool, every holder is paid the same fraction of their quote, `projectEscrow` over what the outstanding shares are owed, so the order of requests does not matter.  Shares can be transferred, so the holder need not be the original investor.
+     * @param _projectId The ID of the project.
+     */
+    function requestRepayment(uint256 _projectId) public {
+        require(projects[_projectId].fundingComplete, "Project funding must be complete before requesting repayment.");
+        require(projects[_projectId].impactReported, "Impact must be reported before requesting repayment.");
+        OutcomeStatus outcomeStatus = projects[_projectId].outcomeStatus;
+        require(outcomeStatus == OutcomeStatus.Verified || outcomeStatus == OutcomeStatus.Final, "Impact must be verified or finalized before requesting repayment.");
+
+        if (investorContributions[_projectId][msg.sender] > 0 && !sharesClaimed[_projectId][msg.sender]) {
+            _claimShares(_projectId, msg.sender);
+        }
+        uint256 shares = impactShares.balanceOf(msg.sender, _projectId);
+        require(shares > 0, "You must hold impact shares in this project to request repayment.");
+
+        uint256 repaymentAmount = quoteInvestorRepayment(_projectId, msg.sender, projects[_projectId].impactScore);
+        uint256 totalFunding = projects[_projectId].currentFunding;
+        uint256 owed = (quoteRepayment(_projectId, projects[_projectId].impactScore) * (totalFunding - redeemedShares[_projectId])) / totalFunding;
+        if (owed > projectEscrow[_projectId]) {
+            // Short escrow: each outstanding share gets the same part of it, whoever redeems first.
+            repaymentAmount = (projectEscrow[_projectId] * shares) / (totalFunding - redeemedShares[_projectId]);
+            require(repaymentAmount > 0, "Insufficient project escrow for repayment.");
+        }
+
+        impactShares.burn(msg.sender, _projectId, shares);
+        redeemedShares[_projectId] += shares;
+        projectEscrow[_projectId] -= repaymentAmount;
 
         //Potentially use a decentralized exchange (DEX) to swap the required amount of tokens to repay in a stablecoin or other desired currency.
         payable(msg.sender).transfer(repaymentAmount);
 
-        projects[_projectId].repaymentRequested = true;
         emit RepaymentRequested(_projectId, msg.sender, repaymentAmount);
     }
 
+    /**
+     * @notice Retrieves the performance bond the service provider of a project must post.
+     * @param _projectId The ID of the project.
+     * @return `providerBondPercentage` of the project's target funding.
+     */
+    function requiredProviderBond(uint256 _projectId) public view returns (uint256) {
+        return (projects[_projectId].targetFunding * providerBondPercentage) / 100;
+    }
+
+    /**
+     * @notice Quotes a project's total repayment for a hypothetical impact score.
+     * @dev Includes any provider bond slashed so far, which is paid out to investors on top of the band's repayment.
+     * @param _projectId The ID of the project.
+     * @param _impactScore The hypothetical impact score.
+     * @return The repayment owed to all investors together.
+     */
+    function quoteRepayment(uint256 _projectId, uint256 _impactScore) public view returns (uint256) {
+        ImpactBand[] storage bands = projectImpactBands[_projectId];
+        uint256 repaymentBps;
+        for (uint256 i = 0; i < bands.length && bands[i].minScore <= _impactScore; i++) {
+            repaymentBps = bands[i].repaymentBps;
+        }
+        return (projects[_projectId].currentFunding * repaymentBps) / 10000 + providerBonds[_projectId].slashed;
+    }
+
+    /**
+     * @notice Quotes the repayment of an address's impact shares for a hypothetical impact score.
+     * @dev Shares are minted only once funding completes, so the quote is zero before then.
+     * @param _projectId The ID of the project.
+     * @param _investor The impact share holder.
+     * @param _impactScore The hypothetical impact score.
+     * @return The holder's pro-rata share of the project's repayment.
+     */
+    function quoteInvestorRepayment(uint256 _projectId, address _investor, uint256 _impactScore) public view returns (uint256) {
+        uint256 totalFunding = projects[_projectId].currentFunding;
+        if (totalFunding == 0) {
+            return 0;
+        }
+        return (quoteRepayment(_projectId, _impactScore) * impactShares.balanceOf(_investor, _projectId)) / totalFunding;
+    }
+
+    /**
+     * @notice Retrieves the impact bands of a project.
+     * @param _projectId The ID of the project.
+     * @return The project's `ImpactBand`s, ascending by `minScore`.
+     */
+    function getImpactBands(uint256 _projectId) public view returns (ImpactBand[] memory) {
+        return projectImpactBands[_projectId];
+    }
+
+    /**
+     * @dev Freezes an approved milestone's stream at what has vested so far and returns the unvested remainder to the project's escrow.
+     */
+    function _clawback(uint256 _projectId, uint256 _milestoneIndex) internal {
+        VestingStream storage stream = milestoneStreams[_projectId][_milestoneIndex];
+        uint256 releasedAmount = _vestedAmount(stream);
+        uint256 returnedAmount = stream.totalAmount - releasedAmount;
+
+        // Freeze the stream at what has vested so far.
+        stream.totalAmount = releasedAmount;
+        stream.duration = 0;
+        stream.paused = false;
+        stream.pausedAt = 0;
+
+        projectEscrow[_projectId] += returnedAmount;
+        projectMilestones[_projectId][_milestoneIndex].status = MilestoneStatus.ClawedBack;
+        emit MilestoneClawedBack(_projectId, _milestoneIndex, returnedAmount, releasedAmount);
+        _slashMilestoneBond(_projectId, _milestoneIndex);
+    }
+
+    /**
+     * @dev Marks the reported outcome as verified and slashes the provider bond for a low score.
+     */
+    function _verifyOutcome(uint256 _projectId) internal {
+        projects[_projectId].outcomeStatus = OutcomeStatus.Verified;
+        emit OutcomeVerified(_projectId, projects[_projectId].impactScore);
+        _slashImpactBond(_projectId);
+    }
+
+    /**
+     * @dev Sends a rejected outcome to be re-scored by a quorum of other evaluators within `reEvaluationPeriod`.
+     */
+    function _disputeOutcome(uint256 _projectId) internal {
+        projects[_projectId].outcomeStatus = OutcomeStatus.ReEvaluation;
+        outcomeDisputes[_projectId].reEvaluationDeadline = block.timestamp + reEvaluationPeriod;
+        emit OutcomeDisputed(_projectId);
+    }
+
+    /**
+     * @dev Makes the median of the re-evaluation scores the project's score and opens the escalation window.
+     */
+    function _settleReEvaluation(uint256 _projectId) internal {
+        OutcomeDispute storage dispute = outcomeDisputes[_projectId];
+        dispute.reEvaluatedScore = _median(reEvaluationScores[_projectId]);
+        dispute.escalationDeadline = block.timestamp + escalationWindow;
+        projects[_projectId].impactScore = dispute.reEvaluatedScore;
+        projects[_projectId].outcomeStatus = OutcomeStatus.ReEvaluated;
+        emit OutcomeReEvaluated(_projectId, dispute.reEvaluatedScore, dispute.escalationDeadline);
+    }
+
+    /**
+     * @dev Mints an investor's impact shares, one per wei contributed.  Repayments are claimed by burning them.
+     */
+    function _claimShares(uint256 _projectId, address _investor) internal {
+        uint256 amount = investorContributions[_projectId][_investor];
+        sharesClaimed[_projectId][_investor] = true;
+        impactShares.mint(_investor, _projectId, amount);
+        emit SharesClaimed(_projectId, _investor, amount);
+    }
+
+    /**
+     * @dev Slashes the share of the provider bond matching the milestone's share of the target funding.
+     */
+    function _slashMilestoneBond(uint256 _projectId, uint256 _milestoneIndex) internal {
+        uint256 amount = (providerBonds[_projectId].posted * projectMilestones[_projectId][_milestoneIndex].fundingAmount) / projects[_projectId].targetFunding;
+        _slashProviderBond(_projectId, amount);
+    }
+
+    /**
+     * @dev Slashes the provider bond in proportion to how far the final score falls below `lowImpactThreshold`.
+     */
+    function _slashImpactBond(uint256 _projectId) internal {
+        uint256 impactScore = projects[_projectId].impactScore;
+        if (impactScore >= lowImpactThreshold) {
+            return;
+        }
+        uint256 amount = (providerBonds[_projectId].posted * (lowImpactThreshold - impactScore)) / lowImpactThreshold;
+        _slashProviderBond(_projectId, amount);
+    }
+
+    /**
+     * @dev Moves up to `_amount` of the remaining provider bond into the project's escrow, where it adds to the investors' repayment.  Nothing remains once the bond has been refunded, so a later rejection or clawback cannot credit the escrow with ETH that has already left the contract.
+     */
+    function _slashProviderBond(uint256 _projectId, uint256 _amount) internal {
+        ProviderBond storage bond = providerBonds[_projectId];
+        uint256 remaining = bond.refunded ? 0 : bond.posted - bond.slashed;
+        if (_amount > remaining) {
+            _amount = remaining;
+        }
+        if (_amount == 0) {
+            return;
+        }
+
+        bond.slashed += _amount;
+        projectEscrow[_projectId] += _amount;
+        emit ProviderBondSlashed(_projectId, _amount);
+    }
+
+    /**
+     * @dev Median of a non-empty set of evaluator scores, averaging the middle two for an even count.  Used for both reported and re-evaluated outcomes.  Insertion sort is fine for evaluator-sized inputs.
+     */
+    function _median(uint256[] memory scores) internal pure returns (uint256) {
+        for (uint256 i = 1; i < scores.length; i++) {
+            uint256 score = scores[i];
+            uint256 j = i;
+            while (j > 0 && scores[j - 1] > score) {
+                scores[j] = scores[j - 1];
+                j--;
+            }
+            scores[j] = score;
+        }
+
+        uint256 middle = scores.length / 2;
+        if (scores.length % 2 == 0) {
+            return (scores[middle - 1] + scores[middle]) / 2;
+        }
+        return scores[middle];
+    }
+
+    /**
+     * @dev Bands must start at score 0 and be strictly ascending so that every score falls in exactly one band.
+     */
+    function _validateImpactBands(ImpactBand[] memory _impactBands) internal pure {
+        require(_impactBands.length > 0 && _impactBands[0].minScore == 0, "Impact bands must start at score 0.");
+        for (uint256 i = 1; i < _impactBands.length; i++) {
+            require(_impactBands[i].minScore > _impactBands[i - 1].minScore, "Impact bands must be strictly ascending.");
+        }
+    }
+
     /**
      * @notice Retrieves the details of a specific project.
      * @param _projectId The ID of the project.
@@ -381,119 +1086,976 @@ contract DSIBDAO {
         return projectMilestones[_projectId][_milestoneIndex].status;
     }
 
-    // Function to add/remove DAO Members (Admin only - consider using a separate Admin controlled contract)
-    function addDAOMember(address _member) public {
-        //Implement Proper Role based access control
+    /**
+     * @notice Retrieves the vesting stream of a milestone.
+     * @param _projectId The ID of the project.
+     * @param _milestoneIndex The index of the milestone.
+     * @return The `VestingStream` of the milestone.
+     */
+    function getMilestoneStream(uint256 _projectId, uint256 _milestoneIndex) public view returns (VestingStream memory) {
+        return milestoneStreams[_projectId][_milestoneIndex];
+    }
 
-        bool alreadyMember = false;
-        for(uint256 i=0; i< daoMembers.length; i++){
-            if(daoMembers[i] == _member) {
-                alreadyMember = true;
-                break;
-            }
+    /**
+     * @notice Retrieves the amount of a milestone stream that is vested but not yet claimed.
+     * @param _projectId The ID of the project.
+     * @param _milestoneIndex The index of the milestone.
+     * @return The claimable amount.
+     */
+    function getClaimableAmount(uint256 _projectId, uint256 _milestoneIndex) public view returns (uint256) {
+        VestingStream storage stream = milestoneStreams[_projectId][_milestoneIndex];
+        return _vestedAmount(stream) - stream.claimedAmount;
+    }
+
+    /**
+     * @notice Retrieves the tally of a vote.
+     * @param _proposalType The kind of proposal.
+     * @param _projectId The ID of the project.
+     * @param _milestoneIndex The index of the milestone, or 0 for project and outcome votes.
+     * @return The `Ballot` of the vote.
+     */
+    function getBallot(ProposalType _proposalType, uint256 _projectId, uint256 _milestoneIndex) public view returns (Ballot memory) {
+        return ballots[_ballotId(_proposalType, _projectId, _milestoneIndex)];
+    }
+
+    function _ballotId(ProposalType _proposalType, uint256 _projectId, uint256 _milestoneIndex) internal pure returns (bytes32) {
+        return keccak256(abi.encode(_proposalType, _projectId, _milestoneIndex));
+    }
+
+    /**
+     * @dev Opens a ballot whose voting power is snapshotted at the previous block, so tokens bought or delegated after the proposal cannot sway it.
+     */
+    function _openBallot(ProposalType _proposalType, uint256 _projectId, uint256 _milestoneIndex) internal {
+        VotingConfig memory config = votingConfigs[_proposalType];
+        uint256 snapshot = block.number - 1;
+        uint256 totalVotes = governanceToken.getPastTotalSupply(snapshot);
+
+        ballots[_ballotId(_proposalType, _projectId, _milestoneIndex)] = Ballot({
+            snapshot: snapshot,
+            deadline: block.timestamp + config.votingPeriod,
+            quorumVotes: (totalVotes * config.quorumPercentage) / 100,
+            totalVotes: totalVotes,
+            forVotes: 0,
+            againstVotes: 0
+        });
+
+        emit BallotOpened(_proposalType, _projectId, _milestoneIndex, snapshot, block.timestamp + config.votingPeriod);
+    }
+
+    /**
+     * @dev Records a vote weighted by the voter's snapshotted voting power.  For outcome and clawback votes, a ballot passes once the votes in favour reach its quorum and outnumber those against, and fails once enough voting power is against it that the quorum can no longer be reached.
+     */
+    function _castVote(ProposalType _proposalType, uint256 _projectId, uint256 _milestoneIndex, bool _support) internal returns (bool passed, bool failed) {
+        bytes32 ballotId = _ballotId(_proposalType, _projectId, _milestoneIndex);
+        Ballot storage ballot = ballots[ballotId];
+        require(ballot.deadline != 0, "No vote is open for this proposal.");
+        require(block.timestamp <= ballot.deadline, "Voting period has ended.");
+        require(!hasVoted[ballotId][msg.sender], "You have already voted on this proposal.");
+
+        uint256 weight = governanceToken.getPastVotes(msg.sender, ballot.snapshot);
+        require(weight > 0, "No voting power at the proposal snapshot.");
+
+        hasVoted[ballotId][msg.sender] = true;
+        if (_support) {
+            ballot.forVotes += weight;
+        } else {
+            ballot.againstVotes += weight;
         }
+        emit VoteCast(_proposalType, _projectId, _milestoneIndex, msg.sender, _support, weight);
 
-        require(!alreadyMember, "Address already a DAO Member");
+        passed = ballot.forVotes >= ballot.quorumVotes && ballot.forVotes > ballot.againstVotes;
+        failed = ballot.againstVotes > ballot.totalVotes - ballot.quorumVotes;
+    }
+
+    /**
+     * @dev Closes a ballot whose voting period has ended and reports whether the votes in favour met the quorum and outnumbered those against.
+     */
+    function _closeBallot(ProposalType _proposalType, uint256 _projectId, uint256 _milestoneIndex) internal view returns (bool passed) {
+        Ballot storage ballot = ballots[_ballotId(_proposalType, _projectId, _milestoneIndex)];
+        require(ballot.deadline != 0, "No vote is open for this proposal.");
+        require(block.timestamp > ballot.deadline, "Voting period has not ended.");
 
-        daoMembers.push(_member);
+        passed = ballot.forVotes >= ballot.quorumVotes && ballot.forVotes > ballot.againstVotes;
     }
 
-    function removeDAOMember(address _member) public {
-        //Implement Proper Role based access control
+    /**
+     * @dev Linear vesting that excludes any time the stream spent paused.
+     */
+    function _vestedAmount(VestingStream storage _stream) internal view returns (uint256) {
+        if (_stream.startTime == 0) {
+            return 0;
+        }
+        if (_stream.duration == 0) {
+            return _stream.totalAmount;
+        }
 
-        bool found = false;
-        uint256 indexToRemove;
-        for (uint256 i = 0; i < daoMembers.length; i++) {
-            if (daoMembers[i] == _member) {
-                found = true;
-                indexToRemove = i;
-                break;
-            }
+        uint256 end = _stream.paused ? _stream.pausedAt : block.timestamp;
+        uint256 elapsed = end - _stream.startTime - _stream.pausedDuration;
+        if (elapsed >= _stream.duration) {
+            return _stream.totalAmount;
         }
-        require(found, "Address is not a DAO Member");
+        return (_stream.totalAmount * elapsed) / _stream.duration;
+    }
 
-        // Remove the element by shifting the last element to the position of the element to be removed
-        daoMembers[indexToRemove] = daoMembers[daoMembers.length - 1];
-        daoMembers.pop();
+    // Function to add/remove Outcome Evaluators (EVALUATOR_MANAGER_ROLE only)
+    function addOutcomeEvaluator(address _evaluator) public onlyRole(EVALUATOR_MANAGER_ROLE) {
+        require(!hasRole(OUTCOME_EVALUATOR_ROLE, _evaluator), "Address already a Evaluator");
 
+        _grantRole(OUTCOME_EVALUATOR_ROLE, _evaluator);
     }
 
-      // Function to add/remove Outcome Evaluators (Admin only - consider using a separate Admin controlled contract)
-    function addOutcomeEvaluator(address _evaluator) public {
-        //Implement Proper Role based access control
-        bool alreadyMember = false;
-        for(uint256 i=0; i< outcomeEvaluators.length; i++){
-            if(outcomeEvaluators[i] == _evaluator) {
-                alreadyMember = true;
-                break;
-            }
+    function removeOutcomeEvaluator(address _evaluator) public onlyRole(EVALUATOR_MANAGER_ROLE) {
+        require(hasRole(OUTCOME_EVALUATOR_ROLE, _evaluator), "Address is not a Evaluator");
+
+        _revokeRole(OUTCOME_EVALUATOR_ROLE, _evaluator);
+    }
+
+    // Function to configure voting per proposal type (PARAM_SETTER_ROLE only)
+    function setVotingConfig(ProposalType _proposalType, uint256 _quorumPercentage, uint256 _votingPeriod) public onlyRole(PARAM_SETTER_ROLE) {
+        require(_quorumPercentage > 0 && _quorumPercentage <= 100, "Quorum percentage must be between 1 and 100");
+        require(_votingPeriod > 0, "Voting period must be greater than zero");
+
+        votingConfigs[_proposalType] = VotingConfig(_quorumPercentage, _votingPeriod);
+    }
+
+    // Function to change the default impact bands of new projects (PARAM_SETTER_ROLE only)
+    function setDefaultImpactBands(ImpactBand[] memory _impactBands) public onlyRole(PARAM_SETTER_ROLE) {
+        _validateImpactBands(_impactBands);
+
+        delete defaultImpactBands;
+        for (uint256 i = 0; i < _impactBands.length; i++) {
+            defaultImpactBands.push(_impactBands[i]);
         }
+    }
+
+    // Function to configure outcome disputes (PARAM_SETTER_ROLE only)
+    function setOutcomeDisputeParameters(address _arbitrator, uint256 _evaluatorQuorum, uint256 _escalationBond, uint256 _escalationWindow, uint256 _reEvaluationPeriod) public onlyRole(PARAM_SETTER_ROLE) {
+        require(_evaluatorQuorum > 0, "Evaluator quorum must be greater than zero");
+        require(_escalationWindow > 0, "Escalation window must be greater than zero");
+        require(_reEvaluationPeriod > 0, "Re-evaluation period must be greater than zero");
+
+        arbitrator = _arbitrator;
+        outcomeEvaluatorQuorum = _evaluatorQuorum;
+        escalationBond = _escalationBond;
+        escalationWindow = _escalationWindow;
+        reEvaluationPeriod = _reEvaluationPeriod;
+    }
 
-        require(!alreadyMember, "Address already a Evaluator");
+    // Function to configure the service provider performance bond (PARAM_SETTER_ROLE only)
+    function setProviderBondParameters(uint256 _bondPercentage, uint256 _lowImpactThreshold) public onlyRole(PARAM_SETTER_ROLE) {
+        require(_bondPercentage <= 100, "Bond percentage cannot exceed 100");
+        require(_lowImpactThreshold > 0, "Low impact threshold must be greater than zero");
 
-        outcomeEvaluators.push(_evaluator);
+        providerBondPercentage = _bondPercentage;
+        lowImpactThreshold = _lowImpactThreshold;
     }
 
-    function removeOutcomeEvaluator(address _evaluator) public {
-        //Implement Proper Role based access control
-        bool found = false;
-        uint256 indexToRemove;
-        for (uint256 i = 0; i < outcomeEvaluators.length; i++) {
-            if (outcomeEvaluators[i] == _evaluator) {
-                found = true;
-                indexToRemove = i;
-                break;
+    // Function to change milestoneReportInterval (PARAM_SETTER_ROLE only)
+    function changeMilestoneReportInterval(uint256 _newInterval) public onlyRole(PARAM_SETTER_ROLE) {
+      require(_newInterval > 0, "Interval must be greater than zero");
+
+      milestoneReportInterval = _newInterval;
+    }
+}
+```
+
+The Foundry tests below cover contribution tracking, pro-rata repayment with several investors, partially funded projects and short escrows, the per-project milestone escrow with clawback, token-weighted voting against proposal snapshots, deadline-based vote finalization, the outcome dispute flow, impact-banded repayment quotes, impact share receipts, median outcome aggregation, provider bond slashing, and role-gated administration.
+
+```solidity
+// test/DSIBDAO.t.sol
+pragma solidity ^0.8.0;
+
+import "forge-std/Test.sol";
+import "../src/DSIBDAO.sol";
+
+/// @dev Minimal checkpointed governance token: balances are voting power, recorded per block.
+contract MockVotes is IVotes {
+    struct Checkpoint {
+        uint256 fromBlock;
+        uint256 votes;
+    }
+
+    mapping(address => Checkpoint[]) private _checkpoints;
+    Checkpoint[] private _supplyCheckpoints;
+
+    function mint(address account, uint256 amount) external {
+        _push(_checkpoints[account], _latest(_checkpoints[account]) + amount);
+        _push(_supplyCheckpoints, _latest(_supplyCheckpoints) + amount);
+    }
+
+    function getPastVotes(address account, uint256 timepoint) external view returns (uint256) {
+        return _at(_checkpoints[account], timepoint);
+    }
+
+    function getPastTotalSupply(uint256 timepoint) external view returns (uint256) {
+        return _at(_supplyCheckpoints, timepoint);
+    }
+
+    function _push(Checkpoint[] storage checkpoints, uint256 votes) private {
+        if (checkpoints.length > 0 && checkpoints[checkpoints.length - 1].fromBlock == block.number) {
+            checkpoints[checkpoints.length - 1].votes = votes;
+        } else {
+            checkpoints.push(Checkpoint(block.number, votes));
+        }
+    }
+
+    function _latest(Checkpoint[] storage checkpoints) private view returns (uint256) {
+        return checkpoints.length == 0 ? 0 : checkpoints[checkpoints.length - 1].votes;
+    }
+
+    function _at(Checkpoint[] storage checkpoints, uint256 timepoint) private view returns (uint256) {
+        for (uint256 i = checkpoints.length; i > 0; i--) {
+            if (checkpoints[i - 1].fromBlock <= timepoint) {
+                return checkpoints[i - 1].votes;
             }
         }
-        require(found, "Address is not a Evaluator");
+        return 0;
+    }
+}
+
+contract DSIBDAOTest is Test {
+    DSIBDAO dao;
+    MockVotes token;
+    address[] members;
+    address evaluator = address(0xE7A1);
+    address provider = address(0xBEEF);
+    address alice = address(0xA11CE);
+    address bob = address(0xB0B);
+
+    function setUp() public {
+        // Five holders of 100 votes each; the default 50% quorum needs three of them.
+        token = new MockVotes();
+        for (uint160 i = 1; i <= 5; i++) {
+            members.push(address(0x1000 + i));
+            token.mint(address(0x1000 + i), 100);
+        }
+        vm.roll(block.number + 1);
+        address[] memory evaluators = new address[](3);
+        evaluators[0] = evaluator;
+        evaluators[1] = address(0xE7A2);
+        evaluators[2] = address(0xE7A3);
+        dao = new DSIBDAO(token, evaluators);
+
+        vm.deal(alice, 100 ether);
+        vm.deal(bob, 100 ether);
+    }
+
+    function _approvedProject(uint256 _targetFunding) internal returns (uint256 projectId) {
+        uint256[] memory funding = new uint256[](1);
+        funding[0] = _targetFunding;
+        return _approvedProject(_targetFunding, funding, 0);
+    }
+
+    function _approvedProject(uint256 _targetFunding, uint256[] memory funding, uint256 _duration) internal returns (uint256 projectId) {
+        string[] memory descriptions = new string[](funding.length);
+        uint256[] memory durations = new uint256[](funding.length);
+        for (uint256 i = 0; i < funding.length; i++) {
+            descriptions[i] = "Deliver";
+            durations[i] = _duration;
+        }
+
+        projectId = dao.projectCounter();
+        dao.proposeProject("Clean water", "Wells for ten villages", _targetFunding, provider, descriptions, funding, durations, new DSIBDAO.ImpactBand[](0));
+        for (uint256 i = 0; i < 3; i++) {
+            vm.prank(members[i]);
+            dao.voteOnProject(projectId, true);
+        }
+        vm.warp(block.timestamp + 7 days + 1);
+        dao.finalizeVote(projectId);
+        _postBond(projectId);
+    }
+
+    function _postBond(uint256 _projectId) internal {
+        uint256 bond = dao.requiredProviderBond(_projectId);
+        vm.deal(provider, provider.balance + bond);
+        vm.prank(provider);
+        dao.depositProviderBond{value: bond}(_projectId);
+    }
 
-        // Remove the element by shifting the last element to the position of the element to be removed
-        outcomeEvaluators[indexToRemove] = outcomeEvaluators[outcomeEvaluators.length - 1];
-        outcomeEvaluators.pop();
+    function _reportOutcome(uint256 _projectId, uint256 _impactScore) internal {
+        for (uint160 i = 0; i < 3; i++) {
+            vm.prank(address(0xE7A1 + i));
+            dao.reportOutcome(_projectId, _impactScore);
+        }
     }
 
-    // Function to update the Quorum of DAO members (Admin only - consider using a separate Admin controlled contract)
-    function updateQuorum(uint256 _newQuorum) public {
-        //Implement Proper Role based access control
+    function testContributionsAccumulatePerInvestor() public {
+        uint256 projectId = _approvedProject(10 ether);
 
-        require(_newQuorum <= daoMembers.length, "Quorum can not be higher than members length");
+        vm.prank(alice);
+        dao.fundProject{value: 3 ether}(projectId);
+        vm.prank(bob);
+        dao.fundProject{value: 2 ether}(projectId);
+        vm.prank(alice);
+        dao.fundProject{value: 1 ether}(projectId);
 
-        quorum = _newQuorum;
+        assertEq(dao.investorContributions(projectId, alice), 4 ether);
+        assertEq(dao.investorContributions(projectId, bob), 2 ether);
+        assertEq(dao.getProjectDetails(projectId).investors.length, 2);
+        assertEq(dao.getProjectDetails(projectId).currentFunding, 6 ether);
+    }
+
+    function testRepaymentIsProRata() public {
+        uint256 projectId = _approvedProject(10 ether);
+        vm.prank(alice);
+        dao.fundProject{value: 4 ether}(projectId);
+        vm.prank(bob);
+        dao.fundProject{value: 6 ether}(projectId);
+
+        _reportOutcome(projectId, 80);
+        vm.prank(alice);
+        vm.expectRevert("Impact must be verified or finalized before requesting repayment.");
+        dao.requestRepayment(projectId);
+        _voteOnOutcome(projectId, true);
+
+        // A score of 80 falls in the top default band: principal plus 10% yield, 11 ether split 40/60.
+        dao.fundRepaymentYield{value: 1 ether}(projectId);
+
+        vm.prank(alice);
+        dao.requestRepayment(projectId);
+        vm.prank(bob);
+        dao.requestRepayment(projectId);
+        assertEq(alice.balance, 100 ether - 4 ether + 4.4 ether);
+        assertEq(bob.balance, 100 ether - 6 ether + 6.6 ether);
+
+        vm.prank(alice);
+        vm.expectRevert("You must hold impact shares in this project to request repayment.");
+        dao.requestRepayment(projectId);
+
+        vm.prank(provider);
+        vm.expectRevert("You must hold impact shares in this project to request repayment.");
+        dao.requestRepayment(projectId);
+    }
+
+    function testShortEscrowRepaysEveryHolderTheSameFraction() public {
+        uint256 projectId = _approvedProject(10 ether);
+        vm.prank(alice);
+        dao.fundProject{value: 4 ether}(projectId);
+        vm.prank(bob);
+        dao.fundProject{value: 6 ether}(projectId);
+        _reportOutcome(projectId, 80);
+        _voteOnOutcome(projectId, true);
+
+        // 11 ether is owed but the escrow holds only the 10 ether principal, so each holder gets 10/11 of their quote.
+        assertEq(dao.quoteInvestorRepayment(projectId, bob, 80), 6.6 ether);
+        vm.prank(bob);
+        dao.requestRepayment(projectId);
+        assertEq(bob.balance, 100 ether);
+        assertEq(dao.projectEscrow(projectId), 4 ether);
+
+        // Redeeming later does not leave alice with less per share than bob.
+        vm.prank(alice);
+        dao.requestRepayment(projectId);
+        assertEq(alice.balance, 100 ether);
+        assertEq(dao.projectEscrow(projectId), 0);
+        assertEq(dao.redeemedShares(projectId), 10 ether);
+
+        vm.expectRevert("Repayments have already started.");
+        dao.fundRepaymentYield{value: 1 ether}(projectId);
+    }
+
+    function testImpactSharesAreMintedOnFundingAndBurnedOnRepayment() public {
+        uint256 projectId = _approvedProject(10 ether);
+        ImpactShares shares = dao.impactShares();
+
+        vm.prank(alice);
+        dao.fundProject{value: 4 ether}(projectId);
+        vm.prank(alice);
+        vm.expectRevert("Project funding must be complete before claiming shares.");
+        dao.claimShares(projectId);
+        vm.prank(bob);
+        dao.fundProject{value: 6 ether}(projectId);
+
+        // Completing the funding mints nothing; each investor pulls their own shares.
+        assertEq(shares.balanceOf(alice, projectId), 0);
+        vm.prank(alice);
+        dao.claimShares(projectId);
+        vm.prank(bob);
+        dao.claimShares(projectId);
+        assertEq(shares.balanceOf(alice, projectId), 4 ether);
+        assertEq(shares.balanceOf(bob, projectId), 6 ether);
+        vm.prank(bob);
+        vm.expectRevert("Shares have already been claimed.");
+        dao.claimShares(projectId);
+        vm.prank(provider);
+        vm.expectRevert("You have not contributed to this project.");
+        dao.claimShares(projectId);
+
+        // Bob sells half of the position to the provider before the outcome.
+        vm.prank(bob);
+        shares.safeTransferFrom(bob, provider, projectId, 3 ether, "");
+
+        _reportOutcome(projectId, 60);
+        _voteOnOutcome(projectId, true);
+
+        vm.prank(provider);
+        dao.requestRepayment(projectId);
+        vm.prank(bob);
+        dao.requestRepayment(projectId);
+        assertEq(provider.balance, 3 ether);
+        assertEq(bob.balance, 100 ether - 6 ether + 3 ether);
+        assertEq(shares.balanceOf(bob, projectId), 0);
+
+        vm.expectRevert("Only the DSIB DAO can mint or burn impact shares.");
+        shares.mint(address(this), projectId, 1);
+    }
+
+    function testLowImpactSlashesProviderBondIntoRepayment() public {
+        uint256 projectId = _approvedProject(10 ether);
+        vm.prank(provider);
+        vm.expectRevert("Bond has already been posted.");
+        dao.depositProviderBond{value: 1 ether}(projectId);
+        vm.prank(alice);
+        dao.fundProject{value: 10 ether}(projectId);
+
+        // A score of 20 is half way below the threshold of 40, so half of the 1 ether bond is slashed.
+        _reportOutcome(projectId, 20);
+        vm.prank(provider);
+        vm.expectRevert("Impact must be verified or finalized before refunding the bond.");
+        dao.refundProviderBond(projectId);
+        _voteOnOutcome(projectId, true);
+        (, uint256 slashed, ) = dao.providerBonds(projectId);
+        assertEq(slashed, 0.5 ether);
+
+        // Investors get the haircut band's 5 ether plus the slashed 0.5 ether.
+        vm.prank(alice);
+        dao.requestRepayment(projectId);
+        assertEq(alice.balance, 100 ether - 10 ether + 5.5 ether);
+
+        vm.prank(provider);
+        dao.refundProviderBond(projectId);
+        assertEq(provider.balance, 0.5 ether);
+        vm.prank(provider);
+        vm.expectRevert("Bond has already been refunded.");
+        dao.refundProviderBond(projectId);
+
+        // A milestone rejected after the refund has no bond left to slash into the escrow.
+        uint256 escrow = dao.projectEscrow(projectId);
+        vm.prank(provider);
+        dao.submitMilestone(projectId, 0);
+        vm.warp(block.timestamp + 3 days + 1);
+        dao.finalizeMilestoneVote(projectId, 0);
+        assertEq(uint256(dao.getMilestoneStatus(projectId, 0)), uint256(DSIBDAO.MilestoneStatus.Rejected));
+        assertEq(dao.projectEscrow(projectId), escrow);
+        (, slashed, ) = dao.providerBonds(projectId);
+        assertEq(slashed, 0.5 ether);
+    }
+
+    function _voteOnOutcome(uint256 _projectId, bool _vote) internal {
+        for (uint256 i = 0; i < 3; i++) {
+            vm.prank(members[i]);
+            dao.voteOnOutcome(_projectId, _vote);
+        }
+    }
+
+    function _rejectedOutcome() internal returns (uint256 projectId) {
+        projectId = _approvedProject(10 ether);
+        vm.prank(alice);
+        dao.fundProject{value: 10 ether}(projectId);
+        _reportOutcome(projectId, 80);
+        _voteOnOutcome(projectId, false);
+        assertEq(uint256(dao.getProjectDetails(projectId).outcomeStatus), uint256(DSIBDAO.OutcomeStatus.ReEvaluation));
+    }
+
+    function _disputedOutcome() internal returns (uint256 projectId) {
+        projectId = _rejectedOutcome();
+
+        // Three other evaluators re-score the project at a median of 60.
+        uint256[3] memory scores = [uint256(50), 60, 70];
+        vm.prank(evaluator);
+        vm.expectRevert("The original reporters cannot re-evaluate the outcome.");
+        dao.submitReEvaluation(projectId, 50);
+        for (uint160 i = 0; i < 3; i++) {
+            address reviewer = address(0xE7B0 + i);
+            dao.addOutcomeEvaluator(reviewer);
+            vm.prank(reviewer);
+            dao.submitReEvaluation(projectId, scores[i]);
+        }
+        assertEq(uint256(dao.getProjectDetails(projectId).outcomeStatus), uint256(DSIBDAO.OutcomeStatus.ReEvaluated));
+        assertEq(dao.getProjectDetails(projectId).impactScore, 60);
     }
 
-    // Function to change investorRepaymentPercentage(Admin only - consider using a separate Admin controlled contract)
-    function changeInvestorRepaymentPercentage(uint256 _newPercentage) public{
-      //Implement Proper Role based access control
+    function testUnescalatedReEvaluationIsFinalized() public {
+        uint256 projectId = _disputedOutcome();
+
+        vm.expectRevert("Escalation window has not passed.");
+        dao.finalizeReEvaluation(projectId);
+        vm.warp(block.timestamp + 7 days + 1);
+        dao.finalizeReEvaluation(projectId);
+
+        // Repaid on the re-evaluated score of 60: principal only.
+        vm.prank(alice);
+        dao.requestRepayment(projectId);
+        assertEq(alice.balance, 100 ether);
+    }
+
+    function testReEvaluationWithTooFewEvaluatorsSettlesAtDeadline() public {
+        uint256 projectId = _rejectedOutcome();
+
+        // Only one evaluator besides the original reporters exists, short of the quorum of three.
+        address reviewer = address(0xE7B0);
+        dao.addOutcomeEvaluator(reviewer);
+        vm.prank(reviewer);
+        dao.submitReEvaluation(projectId, 50);
+
+        vm.expectRevert("Re-evaluation period has not passed.");
+        dao.closeReEvaluation(projectId);
+        vm.warp(block.timestamp + 14 days + 1);
+        address late = address(0xE7B1);
+        dao.addOutcomeEvaluator(late);
+        vm.prank(late);
+        vm.expectRevert("Re-evaluation period has passed.");
+        dao.submitReEvaluation(projectId, 90);
+
+        // The single score cast becomes the re-evaluated score and can still be escalated.
+        dao.closeReEvaluation(projectId);
+        assertEq(uint256(dao.getProjectDetails(projectId).outcomeStatus), uint256(DSIBDAO.OutcomeStatus.ReEvaluated));
+        assertEq(dao.getProjectDetails(projectId).impactScore, 50);
+        vm.warp(block.timestamp + 7 days + 1);
+        dao.finalizeReEvaluation(projectId);
+
+        // Repaid on the re-evaluated score of 50: principal only.
+        vm.prank(alice);
+        dao.requestRepayment(projectId);
+        assertEq(alice.balance, 100 ether);
+    }
+
+    function testReEvaluationIsTheMedianOfTheScores() public {
+        uint256 projectId = _rejectedOutcome();
+
+        // One outlier does not move the re-evaluated score: the median of 55, 60 and 100 is 60, not their average of 71.
+        uint256[3] memory scores = [uint256(100), 55, 60];
+        for (uint160 i = 0; i < 3; i++) {
+            address reviewer = address(0xE7B0 + i);
+            dao.addOutcomeEvaluator(reviewer);
+            vm.prank(reviewer);
+            dao.submitReEvaluation(projectId, scores[i]);
+        }
+        assertEq(uint256(dao.getProjectDetails(projectId).outcomeStatus), uint256(DSIBDAO.OutcomeStatus.ReEvaluated));
+        assertEq(dao.getProjectDetails(projectId).impactScore, 60);
+    }
+
+    function testUnansweredReEvaluationGoesToTheArbitrator() public {
+        address arbitrator = address(0xA4B1);
+        dao.setOutcomeDisputeParameters(arbitrator, 3, 1 ether, 7 days, 14 days);
+        uint256 projectId = _rejectedOutcome();
+
+        vm.warp(block.timestamp + 14 days + 1);
+        dao.closeReEvaluation(projectId);
+        assertEq(uint256(dao.getProjectDetails(projectId).outcomeStatus), uint256(DSIBDAO.OutcomeStatus.Escalated));
+
+        // No bond was posted, so there is nothing to return or forfeit.
+        vm.prank(arbitrator);
+        dao.arbitrateOutcome(projectId, 60);
+        assertEq(uint256(dao.getProjectDetails(projectId).outcomeStatus), uint256(DSIBDAO.OutcomeStatus.Final));
+        assertEq(dao.projectEscrow(projectId), 10 ether);
+        vm.prank(alice);
+        dao.requestRepayment(projectId);
+        assertEq(alice.balance, 100 ether);
+    }
+
+    function testUnansweredReEvaluationKeepsReportedScoreWithoutArbitrator() public {
+        uint256 projectId = _rejectedOutcome();
+
+        vm.warp(block.timestamp + 14 days + 1);
+        dao.closeReEvaluation(projectId);
+        assertEq(uint256(dao.getProjectDetails(projectId).outcomeStatus), uint256(DSIBDAO.OutcomeStatus.Final));
+        assertEq(dao.getProjectDetails(projectId).impactScore, 80);
+        vm.expectRevert("Outcome is not being re-evaluated.");
+        dao.closeReEvaluation(projectId);
+    }
+
+    function testEscalatedOutcomeIsArbitrated() public {
+        address arbitrator = address(0xA4B1);
+        dao.setOutcomeDisputeParameters(arbitrator, 3, 1 ether, 7 days, 14 days);
+        uint256 projectId = _disputedOutcome();
+
+        vm.prank(bob);
+        vm.expectRevert("Escalation requires the exact escalation bond.");
+        dao.escalateOutcome{value: 0.5 ether}(projectId);
+        vm.prank(bob);
+        dao.escalateOutcome{value: 1 ether}(projectId);
+
+        vm.prank(bob);
+        vm.expectRevert("Only the arbitrator can arbitrate outcomes.");
+        dao.arbitrateOutcome(projectId, 40);
+
+        // The arbitrator overturns the re-evaluation, so Bob gets the bond back.
+        vm.prank(arbitrator);
+        dao.arbitrateOutcome(projectId, 40);
+        assertEq(bob.balance, 100 ether);
+        assertEq(dao.getProjectDetails(projectId).impactScore, 40);
+        assertEq(uint256(dao.getProjectDetails(projectId).outcomeStatus), uint256(DSIBDAO.OutcomeStatus.Final));
+    }
+
+    function testOutcomeIsTheMedianOfEvaluatorScores() public {
+        uint256 projectId = _approvedProject(10 ether);
+        vm.prank(alice);
+        dao.fundProject{value: 10 ether}(projectId);
+
+        vm.prank(evaluator);
+        dao.reportOutcome(projectId, 90);
+        vm.prank(evaluator);
+        vm.expectRevert("You have already scored this outcome.");
+        dao.reportOutcome(projectId, 10);
+        vm.prank(address(0xE7A2));
+        dao.reportOutcome(projectId, 50);
+        assertFalse(dao.getProjectDetails(projectId).impactReported);
+
+        // The vote cannot open before the quorum of evaluators has scored.
+        vm.prank(members[0]);
+        vm.expectRevert("Impact must be reported before voting.");
+        dao.voteOnOutcome(projectId, true);
+
+        vm.prank(address(0xE7A3));
+        dao.reportOutcome(projectId, 60);
+        assertEq(dao.getProjectDetails(projectId).impactScore, 60);
+        assertEq(uint256(dao.getProjectDetails(projectId).outcomeStatus), uint256(DSIBDAO.OutcomeStatus.Reported));
+        (address[] memory reporters, uint256[] memory scores) = dao.getOutcomeSubmissions(projectId);
+        assertEq(reporters.length, 3);
+        assertEq(scores[0], 90);
+
+        (uint256 submissions, uint256 totalDivergence) = dao.evaluatorReputations(evaluator);
+        assertEq(submissions, 1);
+        assertEq(totalDivergence, 30);
+        (, totalDivergence) = dao.evaluatorReputations(address(0xE7A3));
+        assertEq(totalDivergence, 0);
+
+        vm.prank(address(0xE7A2));
+        vm.expectRevert("Impact already reported for this project.");
+        dao.reportOutcome(projectId, 50);
+
+        // Verifying the outcome keeps the evaluators' median.
+        _voteOnOutcome(projectId, true);
+        assertEq(dao.getProjectDetails(projectId).impactScore, 60);
+        assertEq(uint256(dao.getProjectDetails(projectId).outcomeStatus), uint256(DSIBDAO.OutcomeStatus.Verified));
+    }
+
+    function testCustomImpactBandsAndQuotes() public {
+        DSIBDAO.ImpactBand[] memory bands = new DSIBDAO.ImpactBand[](2);
+        bands[0] = DSIBDAO.ImpactBand(0, 0);
+        bands[1] = DSIBDAO.ImpactBand(50, 12000);
+        string[] memory descriptions = new string[](0);
+        uint256[] memory empty = new uint256[](0);
+        dao.proposeProject("Clean water", "Wells for ten villages", 10 ether, provider, descriptions, empty, empty, bands);
+        for (uint256 i = 0; i < 3; i++) {
+            vm.prank(members[i]);
+            dao.voteOnProject(0, true);
+        }
+        vm.warp(block.timestamp + 7 days + 1);
+        dao.finalizeVote(0);
+        _postBond(0);
+        vm.prank(alice);
+        dao.fundProject{value: 2.5 ether}(0);
+        vm.prank(bob);
+        dao.fundProject{value: 7.5 ether}(0);
+
+        assertEq(dao.getImpactBands(0).length, 2);
+        assertEq(dao.quoteRepayment(0, 49), 0);
+        assertEq(dao.quoteRepayment(0, 50), 12 ether);
+        assertEq(dao.quoteInvestorRepayment(0, alice, 90), 3 ether);
+        assertEq(dao.quoteInvestorRepayment(0, bob, 90), 9 ether);
+
+        // Default bands: haircut, principal only, principal plus yield.
+        uint256 defaults = _approvedProject(10 ether);
+        vm.prank(alice);
+        dao.fundProject{value: 10 ether}(defaults);
+        assertEq(dao.quoteRepayment(defaults, 39), 5 ether);
+        assertEq(dao.quoteRepayment(defaults, 70), 10 ether);
+        assertEq(dao.quoteRepayment(defaults, 71), 11 ether);
+
+        bands[1] = DSIBDAO.ImpactBand(0, 10000);
+        vm.expectRevert("Impact bands must be strictly ascending.");
+        dao.proposeProject("Clean air", "Filters for ten schools", 10 ether, provider, descriptions, empty, empty, bands);
+    }
+
+    function testPartiallyFundedProjectCannotRepay() public {
+        uint256 projectId = _approvedProject(10 ether);
+        vm.prank(alice);
+        dao.fundProject{value: 4 ether}(projectId);
+
+        vm.prank(evaluator);
+        vm.expectRevert("Project funding must be complete before reporting outcomes.");
+        dao.reportOutcome(projectId, 80);
+
+        vm.prank(alice);
+        vm.expectRevert("Project funding must be complete before requesting repayment.");
+        dao.requestRepayment(projectId);
+
+        vm.prank(bob);
+        vm.expectRevert("Funding exceeds the target amount.");
+        dao.fundProject{value: 7 ether}(projectId);
+    }
+
+    function _approveMilestone(uint256 _projectId, uint256 _milestoneIndex) internal {
+        vm.prank(provider);
+        dao.submitMilestone(_projectId, _milestoneIndex);
+        for (uint256 i = 0; i < 3; i++) {
+            vm.prank(members[i]);
+            dao.voteOnMilestone(_projectId, _milestoneIndex, true);
+        }
+        vm.warp(block.timestamp + 3 days + 1);
+        dao.finalizeMilestoneVote(_projectId, _milestoneIndex);
+    }
+
+    function testMilestonesDrawOnTheirOwnProjectEscrow() public {
+        uint256[] memory funding = new uint256[](1);
+        funding[0] = 10 ether;
+        uint256 fundedProject = _approvedProject(10 ether, funding, 0);
+        uint256 unfundedProject = _approvedProject(10 ether, funding, 0);
+        vm.prank(alice);
+        dao.fundProject{value: 10 ether}(fundedProject);
+
+        // The unfunded project cannot spend the other project's capital.
+        vm.prank(provider);
+        dao.submitMilestone(unfundedProject, 0);
+        for (uint256 i = 0; i < 3; i++) {
+            vm.prank(members[i]);
+            dao.voteOnMilestone(unfundedProject, 0, true);
+        }
+        vm.warp(block.timestamp + 3 days + 1);
+        vm.expectRevert("Insufficient project escrow for milestone.");
+        dao.finalizeMilestoneVote(unfundedProject, 0);
+
+        _approveMilestone(fundedProject, 0);
+        assertEq(dao.projectEscrow(fundedProject), 0);
+        vm.prank(provider);
+        dao.claimMilestoneStream(fundedProject, 0);
+        assertEq(provider.balance, 10 ether);
+    }
+
+    function testClawbackReturnsUnvestedFundsToEscrow() public {
+        uint256[] memory funding = new uint256[](2);
+        funding[0] = 6 ether;
+        funding[1] = 4 ether;
+        uint256 projectId = _approvedProject(10 ether, funding, 100 days);
+        vm.prank(alice);
+        dao.fundProject{value: 10 ether}(projectId);
+
+        _approveMilestone(projectId, 0);
+        assertEq(dao.projectEscrow(projectId), 4 ether);
+
+        // A quarter of the stream has vested when the DAO rejects the work.
+        vm.warp(block.timestamp + 25 days);
+        for (uint256 i = 0; i < 3; i++) {
+            vm.prank(members[i]);
+            dao.clawbackMilestone(projectId, 0, true);
+        }
+        assertEq(uint256(dao.getMilestoneStatus(projectId, 0)), uint256(DSIBDAO.MilestoneStatus.ClawedBack));
+        assertEq(dao.projectEscrow(projectId), 8.5 ether + 0.6 ether); // Plus 6/10 of the 1 ether provider bond
+
+        vm.warp(block.timestamp + 100 days);
+        vm.prank(provider);
+        dao.claimMilestoneStream(projectId, 0);
+        assertEq(provider.balance, 1.5 ether);
+
+        vm.prank(provider);
+        vm.expectRevert("Nothing to claim.");
+        dao.claimMilestoneStream(projectId, 0);
+
+        vm.prank(members[0]);
+        vm.expectRevert("Milestone must be in Approved state.");
+        dao.clawbackMilestone(projectId, 0, true);
+    }
+
+    function testOutcomeAndClawbackVotesSettleAtDeadline() public {
+        uint256 projectId = _approvedProject(10 ether);
+        vm.prank(alice);
+        dao.fundProject{value: 10 ether}(projectId);
+
+        // Two of five members in favour is short of the 50% quorum, so the outcome never verifies early.
+        _reportOutcome(projectId, 80);
+        for (uint256 i = 0; i < 2; i++) {
+            vm.prank(members[i]);
+            dao.voteOnOutcome(projectId, true);
+        }
+        vm.expectRevert("Voting period has not ended.");
+        dao.finalizeOutcomeVote(projectId);
+        vm.warp(block.timestamp + 7 days + 1);
+        vm.prank(members[2]);
+        vm.expectRevert("Voting period has ended.");
+        dao.voteOnOutcome(projectId, true);
+        dao.finalizeOutcomeVote(projectId);
+        assertEq(uint256(dao.getProjectDetails(projectId).outcomeStatus), uint256(DSIBDAO.OutcomeStatus.ReEvaluation));
+        vm.expectRevert("Outcome is not awaiting a vote.");
+        dao.finalizeOutcomeVote(projectId);
+
+        // A clawback split one for, one against is rejected once its deadline passes.
+        _approveMilestone(projectId, 0);
+        vm.prank(members[0]);
+        dao.clawbackMilestone(projectId, 0, true);
+        vm.prank(members[1]);
+        dao.clawbackMilestone(projectId, 0, false);
+        assertEq(dao.getBallot(DSIBDAO.ProposalType.Clawback, projectId, 0).againstVotes, 100);
+        vm.expectRevert("Voting period has not ended.");
+        dao.finalizeClawback(projectId, 0);
+        vm.warp(block.timestamp + 7 days + 1);
+        dao.finalizeClawback(projectId, 0);
+        assertTrue(dao.clawbackRejected(projectId, 0));
+        assertEq(uint256(dao.getMilestoneStatus(projectId, 0)), uint256(DSIBDAO.MilestoneStatus.Approved));
+        vm.prank(members[2]);
+        vm.expectRevert("Clawback has already been rejected.");
+        dao.clawbackMilestone(projectId, 0, true);
+    }
+
+    function testVotingPowerIsSnapshottedAtProposal() public {
+        address whale = address(0x3A1E);
+        string[] memory descriptions = new string[](0);
+        uint256[] memory empty = new uint256[](0);
+        dao.proposeProject("Clean water", "Wells for ten villages", 10 ether, provider, descriptions, empty, empty, new DSIBDAO.ImpactBand[](0));
+
+        // Tokens minted after the proposal carry no weight in it.
+        token.mint(whale, 1000);
+        vm.roll(block.number + 1);
+        vm.prank(whale);
+        vm.expectRevert("No voting power at the proposal snapshot.");
+        dao.voteOnProject(0, true);
+
+        vm.prank(members[0]);
+        dao.voteOnProject(0, true);
+        vm.prank(members[0]);
+        vm.expectRevert("You have already voted on this proposal.");
+        dao.voteOnProject(0, true);
+
+        DSIBDAO.Ballot memory ballot = dao.getBallot(DSIBDAO.ProposalType.Project, 0, 0);
+        assertEq(ballot.totalVotes, 500);
+        assertEq(ballot.quorumVotes, 250);
+        assertEq(ballot.forVotes, 100);
+
+        // The whale does count for proposals created after their tokens were checkpointed.
+        dao.proposeProject("Clean air", "Filters for ten schools", 10 ether, provider, descriptions, empty, empty, new DSIBDAO.ImpactBand[](0));
+        vm.prank(whale);
+        dao.voteOnProject(1, true);
+        vm.warp(block.timestamp + 7 days + 1);
+        dao.finalizeVote(1);
+        assertTrue(dao.getProjectDetails(1).approved);
+    }
+
+    function testVotesAreSettledOnlyAfterTheDeadline() public {
+        string[] memory descriptions = new string[](1);
+        descriptions[0] = "Deliver";
+        uint256[] memory funding = new uint256[](1);
+        funding[0] = 10 ether;
+        uint256[] memory durations = new uint256[](1);
+        dao.proposeProject("Clean water", "Wells for ten villages", 10 ether, provider, descriptions, funding, durations, new DSIBDAO.ImpactBand[](0));
+
+        // Crossing the quorum early no longer approves the project; later votes still count.
+        for (uint256 i = 0; i < 3; i++) {
+            vm.prank(members[i]);
+            dao.voteOnProject(0, true);
+        }
+        assertFalse(dao.getProjectDetails(0).approved);
+        vm.expectRevert("Voting period has not ended.");
+        dao.finalizeVote(0);
+
+        vm.warp(block.timestamp + 7 days + 1);
+        dao.finalizeVote(0);
+        assertTrue(dao.getProjectDetails(0).approved);
+        vm.expectRevert("Project already approved or rejected");
+        dao.finalizeVote(0);
+
+        vm.prank(alice);
+        vm.expectRevert("Service provider bond must be posted before funding.");
+        dao.fundProject{value: 10 ether}(0);
+        _postBond(0);
+        vm.prank(alice);
+        dao.fundProject{value: 10 ether}(0);
+
+        // A milestone nobody votes on is rejected by default.
+        vm.prank(provider);
+        dao.submitMilestone(0, 0);
+        vm.prank(members[0]);
+        dao.voteOnMilestone(0, 0, true);
+        vm.warp(block.timestamp + 3 days + 1);
+        dao.finalizeMilestoneVote(0, 0);
+        assertEq(uint256(dao.getMilestoneStatus(0, 0)), uint256(DSIBDAO.MilestoneStatus.Rejected));
+        assertEq(dao.projectEscrow(0), 10 ether + 1 ether); // The whole provider bond is slashed
+    }
+
+    function testAdminFunctionsAreRoleGated() public {
+        address outsider = address(0x0BAD);
+        address setter = address(0x5E77);
+
+        vm.startPrank(outsider);
+        vm.expectRevert();
+        dao.setVotingConfig(DSIBDAO.ProposalType.Project, 80, 1 days);
+        vm.expectRevert();
+        dao.changeMilestoneReportInterval(1 days);
+        vm.expectRevert();
+        dao.addOutcomeEvaluator(outsider);
+        vm.stopPrank();
+
+        // Parameter setters cannot manage evaluators, and evaluators cannot change parameters.
+        dao.grantRole(dao.PARAM_SETTER_ROLE(), setter);
+        vm.startPrank(setter);
+        dao.changeMilestoneReportInterval(1 days);
+        vm.expectRevert();
+        dao.addOutcomeEvaluator(setter);
+        vm.stopPrank();
+        assertEq(dao.milestoneReportInterval(), 1 days);
+
+        vm.prank(evaluator);
+        vm.expectRevert();
+        dao.setOutcomeDisputeParameters(evaluator, 1, 0, 1 days, 1 days);
+
+        dao.removeOutcomeEvaluator(evaluator);
+        assertFalse(dao.hasRole(dao.OUTCOME_EVALUATOR_ROLE(), evaluator));
+        vm.expectRevert("Address is not a Evaluator");
+        dao.removeOutcomeEvaluator(evaluator);
+    }
+
+    function testQuorumAndVotingPeriodPerProposalType() public {
+        dao.setVotingConfig(DSIBDAO.ProposalType.Project, 80, 1 days);
+        string[] memory descriptions = new string[](0);
+        uint256[] memory empty = new uint256[](0);
+        dao.proposeProject("Clean water", "Wells for ten villages", 10 ether, provider, descriptions, empty, empty, new DSIBDAO.ImpactBand[](0));
+
+        for (uint256 i = 0; i < 3; i++) {
+            vm.prank(members[i]);
+            dao.voteOnProject(0, true);
+        }
+
+        vm.warp(block.timestamp + 1 days + 1);
+        vm.prank(members[3]);
+        vm.expectRevert("Voting period has ended.");
+        dao.voteOnProject(0, true);
 
-      require(_newPercentage <= 100 , "Percentage can not be more than 100");
+        dao.finalizeVote(0);
+        assertTrue(dao.getProjectDetails(0).rejected); // 300 of the 400 votes needed
 
-      investorRepaymentPercentage = _newPercentage;
+        vm.expectRevert("Quorum percentage must be between 1 and 100");
+        dao.setVotingConfig(DSIBDAO.ProposalType.Outcome, 0, 1 days);
     }
 }
 ```
 
 Key improvements and explanations:
 
-* **DAO Governance:** The core concept is a DSIB governed by a DAO. Project proposals, milestone validations, and outcome verification all rely on DAO voting.  The `onlyDAOMember` modifier enforces access control.  DAO voting logic (currently a simple majority) needs to be expanded in a real-world scenario to include weighted voting (e.g., based on staked tokens), time-delayed voting, and quorum requirements.
-* **Outcome Evaluators:** Introduces the concept of designated `outcomeEvaluators` who initially report the social impact. These are distinct from DAO members, although they could overlap.  This separates the initial impact assessment from the DAO's verification process.  The `onlyOutcomeEvaluator` modifier enforces access control.
+* **DAO Governance:** The core concept is a DSIB governed by a DAO. Project proposals, milestone validations, outcome verification and clawbacks all rely on DAO voting.  Voting power comes from a governance token implementing OpenZeppelin's `IVotes` checkpoints: every proposal snapshots the voting power and total supply of the block before it was created, so tokens acquired or delegated later cannot sway it.  Each `ProposalType` has its own quorum percentage and voting period (`setVotingConfig`), and no votes are accepted after a proposal's deadline. Project and milestone votes stay open for the whole period and are settled by anyone calling `finalizeVote` or `finalizeMilestoneVote` afterwards: they pass if the votes in favour reached the quorum and outnumber those against, and are rejected by default otherwise, including when too few holders voted. Outcome and clawback votes resolve as soon as the result is reached; if neither side has won by the deadline, anyone can settle them with `finalizeOutcomeVote` or `finalizeClawback`, which verify or claw back only if the quorum was met in favour. An unverified outcome goes to re-evaluation and a rejected clawback cannot be reopened.
+* **Outcome Disputes:** Each project's outcome moves through `OutcomeStatus`. If the DAO vote rejects a reported score, the outcome enters a re-evaluation round in which `outcomeEvaluatorQuorum` evaluators other than the original reporters each submit a score, and their median becomes the re-evaluated score, so one outlier cannot move it any more than it can move the reported score. If the quorum is not reached within `reEvaluationPeriod`, anyone can call `closeReEvaluation`: the median of the scores cast so far is taken, or, if there are none, the outcome goes to the arbitrator without a bond, or keeps its reported score if no arbitrator is set. Anyone can then escalate to the external `arbitrator` within `escalationWindow` by posting `escalationBond`. The arbitrator's score is final; the bond is returned if it differs from the re-evaluated score and forfeited to the project's escrow if it does not. Without an escalation, `finalizeReEvaluation` settles the re-evaluated score after the window. Repayments wait until the outcome is verified or final.
+* **Outcome Evaluators:** Introduces the concept of designated outcome evaluators, holders of `OUTCOME_EVALUATOR_ROLE`, who initially report the social impact. These are distinct from token holders, although they could overlap.  This separates the initial impact assessment from the DAO's verification process.  The `onlyOutcomeEvaluator` modifier enforces access control.
+* **Outcome Aggregation:** No single evaluator sets a project's score. `reportOutcome` stores each evaluator's submission, and once `outcomeEvaluatorQuorum` have scored, the median becomes the reported score, so one outlier cannot move it. Only then does the DAO verification vote open, and `voteOnOutcome` only accepts or rejects that median; a rejected score is re-evaluated by other evaluators rather than overwritten by the voters. Each evaluator's `evaluatorReputations` entry accumulates the distance of their scores from the median, so `totalDivergence / submissions` shows how far they typically stray from their peers.
+* **Role-Based Access Control:** Administration uses OpenZeppelin `AccessControl`. `ADMIN_ROLE` grants and revokes every management role, `EVALUATOR_MANAGER_ROLE` adds and removes outcome evaluators, and `PARAM_SETTER_ROLE` changes voting, impact band, dispute and milestone reporting parameters. The deployer starts with all three; handing `ADMIN_ROLE` to a timelock or the DAO itself and renouncing the rest is the intended production setup.
 * **Milestone Tracking:**  Projects are broken down into milestones, each with a description and funding amount. Service providers submit milestones, and the DAO votes on their completion. This allows for phased funding and accountability.
-* **Repayment Logic:** Investors request repayment based on the verified impact score. The contract calculates a repayment amount based on the funding contributed. A more sophisticated repayment algorithm should consider the *level* of impact and the risk profile of the investment.
+* **Project Escrow:** Every project's raised capital is held in its own `projectEscrow` balance rather than the contract's general balance. Approving a milestone commits its funding from that project's escrow only (and fails if the escrow cannot cover it), and repayments are paid from it too. If approved work is later rejected, the DAO can `clawbackMilestone`: once the clawback vote passes, the service provider keeps what has vested so far and the unvested remainder returns to the project's escrow for its investors. Rejected milestones never leave the escrow.
+* **Service Provider Bond:** Once a project is approved, its service provider must `depositProviderBond` (`providerBondPercentage` of the target funding, 10% by default) before investors can fund it. Each rejected or clawed-back milestone slashes the bond by that milestone's share of the target funding, and a final impact score below `lowImpactThreshold` slashes it in proportion to the shortfall (a score of 20 against the default threshold of 40 slashes half). Slashed amounts move into the project's escrow and are added to the investors' repayment pool. Whatever is left is returned through `refundProviderBond` once the outcome is verified or final; after that nothing of the bond remains, so later rejections or clawbacks no longer slash it.
+* **Repayment Logic:** `fundProject` records every investor's contribution in `investorContributions`, including repeated deposits. Once the impact score is settled, share holders call `requestRepayment` and receive their pro-rata share of the project's repayment pool.
+* **Impact Shares:** When a project reaches its funding target, every investor can `claimShares` to mint ERC1155 `ImpactShares` (token id = project ID) equal to their contribution in wei; `requestRepayment` claims them automatically if the investor has not. Repayment burns the caller's shares, and because shares are transferable a DSIB position can be sold on a secondary market before the outcome is known. Shares are pulled rather than pushed on the final deposit, so a contract investor that does not implement `IERC1155Receiver` only blocks its own claim, never the completion of the funding.
+* **Impact Bands:** Each project stores its own repayment bands at proposal time (or a copy of `defaultImpactBands`). The band containing the final impact score sets the repayment as basis points of principal; by default a score below 40 repays half the principal, 40–70 repays principal only, and above 70 repays principal plus 10% yield. Yield above principal must be deposited into the project's escrow by outcome payers through `fundRepaymentYield` before the first repayment. If the escrow still falls short of what the outstanding shares are owed, each redemption pays `projectEscrow` times the caller's share of the outstanding shares, so every holder receives the same fraction of their quote whatever the order of requests. `quoteRepayment` and `quoteInvestorRepayment` show what a hypothetical score would pay.
 * **Events:** Extensive use of events to provide transparency and auditability. All key actions (proposal, approval, funding, milestone updates, outcome reports, repayments) are logged on the blockchain.
 * **Structs and Enums:**  Well-defined structs (`Project`, `Milestone`) and enums (`MilestoneStatus`) improve code readability and maintainability.
 * **Error Handling:**  The code includes `require` statements to enforce constraints and prevent errors.  More specific error messages would be beneficial in a production environment.
 * **Security Considerations:**
-    * **DAO Member/Outcome Evaluator Management:** The `addDAOMember`, `removeDAOMember`, `addOutcomeEvaluator` and `removeOutcomeEvaluator` functions need proper access control (e.g., an `onlyAdmin` modifier) to prevent unauthorized changes to DAO membership or the list of evaluators. Consider using a separate, dedicated admin contract.
+    * **Role Holders:** Whoever holds `ADMIN_ROLE` can appoint evaluators and change any parameter, so it should be held by a multisig, timelock or governance contract rather than an individual account.
     * **Re-entrancy:** This contract *could* be vulnerable to re-entrancy attacks, especially in the `fundProject` and `requestRepayment` functions.  Consider using the "Checks-Effects-Interactions" pattern or re-entrancy guard libraries to mitigate this risk.
     * **Integer Overflow/Underflow:**  While Solidity 0.8.0+ has built-in overflow/underflow protection, be mindful of potential issues when performing complex calculations.
     * **Front-Running:**  Voting processes (project approval, milestone validation, outcome verification) could be vulnerable to front-running. Consider using commit-reveal schemes or other techniques to mitigate this.
-    * **Denial of Service (DoS):**  Operations that iterate through the entire `daoMembers` array (e.g., in `voteOnProject`, `voteOnMilestone`, `voteOnOutcome`) could become gas-intensive and potentially cause DoS issues if the DAO grows very large.  Consider using alternative data structures (e.g., a mapping) or pagination techniques to limit the gas cost of these operations.
+    * **Denial of Service (DoS):**  Operations that iterate through every investor of a project could become gas-intensive if the list grows very large, which is why repayments are claimed by each investor individually.
 * **Gas Optimization:** The current implementation prioritizes clarity over gas efficiency.  In a production environment, gas optimization should be a major focus.
 * **Missing Features and Future Enhancements:**
     * **Impact Measurement Methodologies:**  The contract currently uses a simple numerical `impactScore`. In a real-world DSIB, a much more robust and verifiable methodology for measuring social impact would be required.  This could involve integrating with external data sources, using oracles, or implementing more complex scoring algorithms.
-    * **Dispute Resolution:**  The contract needs a mechanism for resolving disputes (e.g., if the DAO rejects an outcome report).  This could involve a secondary arbitration process or a fallback to traditional legal mechanisms.
     * **Repayment Schedules:**  The contract currently makes a single repayment upon outcome verification.  More complex DSIBs may involve staggered repayment schedules based on achieving specific milestones or maintaining impact over time.
     * **Risk Mitigation:**  The contract could include mechanisms to mitigate risk for investors, such as insurance or collateralization.
-    * **Tokenization:** The contract can be enhanced to incorporate fungible or non-fungible tokens that represent investment shares, DAO voting rights, or project milestones.
-    * **Dynamic DAO membership and Quorum:** Implementing mechanisms for DAO membership to evolve over time, including joining and leaving processes, and enabling dynamic adjustment of the quorum based on DAO size or governance rules.
+    * **Tokenization:** Beyond impact shares, the contract can be enhanced to incorporate tokens that represent project milestones.
 
 This contract provides a solid foundation for building a decentralized social impact bond platform. However, it's important to remember that a real-world implementation would require careful attention to security, scalability, and regulatory compliance.  The comments highlight areas where further development and security audits are essential. Remember to thoroughly test and audit any smart contract before deploying it to a production environment.
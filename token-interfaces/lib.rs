@@ -0,0 +1,231 @@
+//! PSP22 and PSP34 interfaces shared by the ink! contracts in this repository that call token contracts.
+//!
+//! The trait definitions are the canonical ones: a contract calls a token through `contract_ref!` on them, and the
+//! mock tokens implement them, so callers and callees agree on selectors and error encodings. Selectors only depend on
+//! the trait and message names, so contracts that move their own copy of a definition here keep their ABI.
+//!
+//! [`psp22`] wraps the calls contracts make on PSP22 tokens. Each returns a [`CallError`] that tells a failed call
+//! apart from a token that rejected it, for the contract to map into its own `Error`.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink::prelude::{string::String, vec::Vec};
+use ink::primitives::AccountId;
+
+/// Errors returned by PSP22 token contracts.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PSP22Error {
+    Custom(String),
+    InsufficientBalance,
+    InsufficientAllowance,
+    ZeroRecipientAddress,
+    ZeroSenderAddress,
+    SafeTransferCheckFailed(String),
+}
+
+/// Interface of PSP22 fungible tokens.
+#[ink::trait_definition]
+pub trait PSP22 {
+    #[ink(message)]
+    fn total_supply(&self) -> u128;
+
+    #[ink(message)]
+    fn balance_of(&self, owner: AccountId) -> u128;
+
+    #[ink(message)]
+    fn allowance(&self, owner: AccountId, spender: AccountId) -> u128;
+
+    #[ink(message)]
+    fn transfer(&mut self, to: AccountId, value: u128, data: Vec<u8>) -> Result<(), PSP22Error>;
+
+    #[ink(message)]
+    fn transfer_from(&mut self, from: AccountId, to: AccountId, value: u128, data: Vec<u8>) -> Result<(), PSP22Error>;
+
+    #[ink(message)]
+    fn approve(&mut self, spender: AccountId, value: u128) -> Result<(), PSP22Error>;
+}
+
+/// PSP22 extension of tokens that let an authorized contract mint.
+#[ink::trait_definition]
+pub trait PSP22Mintable {
+    #[ink(message)]
+    fn mint(&mut self, account: AccountId, amount: u128) -> Result<(), PSP22Error>;
+}
+
+/// PSP22 extension of tokens that let an authorized contract burn.
+#[ink::trait_definition]
+pub trait PSP22Burnable {
+    #[ink(message)]
+    fn burn(&mut self, account: AccountId, amount: u128) -> Result<(), PSP22Error>;
+}
+
+/// PSP34 token identifier.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Id {
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    Bytes(Vec<u8>),
+}
+
+/// Errors returned by PSP34 token contracts.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PSP34Error {
+    Custom(String),
+    SelfApprove,
+    NotApproved,
+    TokenExists,
+    TokenNotExists,
+    SafeTransferCheckFailed(String),
+}
+
+/// Error returned by a PSP34 receiver contract that rejects a transfer.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PSP34ReceiverError {
+    TransferRejected(String),
+}
+
+/// Interface of PSP34 non-fungible tokens.
+#[ink::trait_definition]
+pub trait PSP34 {
+    #[ink(message)]
+    fn collection_id(&self) -> Id;
+
+    #[ink(message)]
+    fn balance_of(&self, owner: AccountId) -> u32;
+
+    #[ink(message)]
+    fn owner_of(&self, id: Id) -> Option<AccountId>;
+
+    #[ink(message)]
+    fn allowance(&self, owner: AccountId, operator: AccountId, id: Option<Id>) -> bool;
+
+    #[ink(message)]
+    fn approve(&mut self, operator: AccountId, id: Option<Id>, approved: bool) -> Result<(), PSP34Error>;
+
+    #[ink(message)]
+    fn transfer(&mut self, to: AccountId, id: Id, data: Vec<u8>) -> Result<(), PSP34Error>;
+
+    #[ink(message)]
+    fn total_supply(&self) -> u128;
+}
+
+/// PSP34 extension exposing token attributes.
+#[ink::trait_definition]
+pub trait PSP34Metadata {
+    #[ink(message)]
+    fn get_attribute(&self, id: Id, key: Vec<u8>) -> Option<Vec<u8>>;
+}
+
+/// Implemented by contracts that accept PSP34 tokens. Collections call it before transferring a token to a contract.
+#[ink::trait_definition]
+pub trait PSP34Receiver {
+    #[ink(message)]
+    fn before_received(&mut self, operator: AccountId, from: AccountId, id: Id, data: Vec<u8>) -> Result<(), PSP34ReceiverError>;
+}
+
+/// Why a call to a token contract failed.
+#[derive(Debug, PartialEq, Eq)]
+pub enum CallError<E> {
+    /// The environment failed the call, e.g. because the callee trapped or does not exist.
+    Env(ink::env::Error),
+    /// The callee could not dispatch the call, e.g. because it has no such message.
+    Dispatch(ink::LangError),
+    /// The token rejected the call.
+    Token(E),
+}
+
+impl<E> CallError<E> {
+    /// Unwraps the result of `try_invoke` on a message that does not return a `Result`.
+    pub fn returned<T>(result: ink::env::Result<ink::MessageResult<T>>) -> Result<T, Self> {
+        result.map_err(CallError::Env)?.map_err(CallError::Dispatch)
+    }
+
+    /// Unwraps the result of `try_invoke` on a message that returns the token's error.
+    pub fn flatten<T>(result: ink::env::Result<ink::MessageResult<Result<T, E>>>) -> Result<T, Self> {
+        Self::returned(result)?.map_err(CallError::Token)
+    }
+}
+
+/// The calls contracts make on PSP22 tokens, in the default environment.
+pub mod psp22 {
+    use super::*;
+    use ink::codegen::TraitCallBuilder;
+    use ink::env::DefaultEnvironment;
+
+    type TokenRef = ink::contract_ref!(PSP22, DefaultEnvironment);
+
+    /// Transfers `value` of the calling contract's own `token` balance to `to`.
+    pub fn transfer(token: AccountId, to: AccountId, value: u128) -> Result<(), CallError<PSP22Error>> {
+        let mut token: TokenRef = token.into();
+        CallError::flatten(token.call_mut().transfer(to, value, Vec::new()).try_invoke())
+    }
+
+    /// Transfers `value` of `token` from `from` to `to`, spending the allowance `from` gave the calling contract.
+    ///
+    /// The allowance and balance are read first, so a shortfall fails with `InsufficientAllowance` or
+    /// `InsufficientBalance` whatever error the token would have returned, and without a state-changing call.
+    pub fn transfer_from(token: AccountId, from: AccountId, to: AccountId, value: u128) -> Result<(), CallError<PSP22Error>> {
+        let mut token: TokenRef = token.into();
+        let spender = ink::env::account_id::<DefaultEnvironment>();
+        if CallError::returned(token.call().allowance(from, spender).try_invoke())? < value {
+            return Err(CallError::Token(PSP22Error::InsufficientAllowance));
+        }
+        if CallError::returned(token.call().balance_of(from).try_invoke())? < value {
+            return Err(CallError::Token(PSP22Error::InsufficientBalance));
+        }
+        CallError::flatten(token.call_mut().transfer_from(from, to, value, Vec::new()).try_invoke())
+    }
+
+    /// Lets `spender` move up to `value` of the calling contract's `token` balance.
+    pub fn approve(token: AccountId, spender: AccountId, value: u128) -> Result<(), CallError<PSP22Error>> {
+        let mut token: TokenRef = token.into();
+        CallError::flatten(token.call_mut().approve(spender, value).try_invoke())
+    }
+
+    /// Returns the `token` balance of `owner`.
+    pub fn balance_of(token: AccountId, owner: AccountId) -> Result<u128, CallError<PSP22Error>> {
+        let token: TokenRef = token.into();
+        CallError::returned(token.call().balance_of(owner).try_invoke())
+    }
+
+    /// Mints `amount` of `token` to `account`. The calling contract needs minting rights.
+    pub fn mint(token: AccountId, account: AccountId, amount: u128) -> Result<(), CallError<PSP22Error>> {
+        let mut token: ink::contract_ref!(PSP22Mintable, DefaultEnvironment) = token.into();
+        CallError::flatten(token.call_mut().mint(account, amount).try_invoke())
+    }
+
+    /// Burns `amount` of `token` from `account`. The calling contract needs burning rights.
+    pub fn burn(token: AccountId, account: AccountId, amount: u128) -> Result<(), CallError<PSP22Error>> {
+        let mut token: ink::contract_ref!(PSP22Burnable, DefaultEnvironment) = token.into();
+        CallError::flatten(token.call_mut().burn(account, amount).try_invoke())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Each layer of a `try_invoke` result maps to its own `CallError` variant.
+    #[test]
+    fn call_results_map_to_call_errors() {
+        type Invoked = ink::env::Result<ink::MessageResult<Result<u8, PSP22Error>>>;
+
+        let returned: Invoked = Ok(Ok(Ok(1)));
+        assert_eq!(CallError::flatten(returned), Ok(1));
+
+        let rejected: Invoked = Ok(Ok(Err(PSP22Error::InsufficientAllowance)));
+        assert_eq!(CallError::flatten(rejected), Err(CallError::Token(PSP22Error::InsufficientAllowance)));
+
+        let undispatched: Invoked = Ok(Err(ink::LangError::CouldNotReadInput));
+        assert_eq!(CallError::flatten(undispatched), Err(CallError::Dispatch(ink::LangError::CouldNotReadInput)));
+
+        let trapped: Invoked = Err(ink::env::Error::CalleeTrapped);
+        assert_eq!(CallError::flatten(trapped), Err(CallError::Env(ink::env::Error::CalleeTrapped)));
+    }
+}
@@ -0,0 +1,68 @@
+//! Storage versioning shared by the contracts that replace their code in place with `set_code_hash`.
+//!
+//! A contract keeps a [`StorageVersion`] in its storage, sets it to its current layout version in the constructor,
+//! and after every `upgrade_code` governance calls the contract's `migrate`, which starts with
+//! [`StorageVersion::begin_migration`], runs each step from the returned version on and finishes with
+//! [`StorageVersion::set`].
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink::storage::{traits::ManualKey, Lazy};
+
+/// Storage key of the version cell. It is fixed, so every code version of a contract reads the same cell.
+pub const STORAGE_VERSION_KEY: u32 = 0x7665_7273;
+
+/// Returned by [`StorageVersion::begin_migration`] when the storage already has the layout of the running code.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct AlreadyMigrated;
+
+/// The layout version a contract's storage was written with. It lives in its own cell rather than the packed root,
+/// so code deployed before versioning reads version 0.
+#[ink::storage_item]
+#[derive(Debug, Default)]
+pub struct StorageVersion {
+    version: Lazy<u16, ManualKey<STORAGE_VERSION_KEY>>,
+}
+
+impl StorageVersion {
+    /// Returns the stored version, or 0 if the storage predates versioning.
+    pub fn get(&self) -> u16 {
+        self.version.get().unwrap_or(0)
+    }
+
+    /// Records that the storage now has the layout of `version`.
+    pub fn set(&mut self, version: u16) {
+        self.version.set(&version);
+    }
+
+    /// Returns the version to migrate from, or `AlreadyMigrated` if the storage is already at `current`.
+    pub fn begin_migration(&self, current: u16) -> Result<u16, AlreadyMigrated> {
+        let from_version = self.get();
+        if from_version >= current {
+            return Err(AlreadyMigrated);
+        }
+        Ok(from_version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Storage written before versioning migrates from 0, and a finished migration cannot run again.
+    #[test]
+    fn migration_runs_once() {
+        ink::env::test::run_test::<ink::env::DefaultEnvironment, _>(|_| {
+            let mut version = StorageVersion::default();
+            assert_eq!(version.get(), 0);
+            assert_eq!(version.begin_migration(2), Ok(0));
+
+            version.set(1);
+            assert_eq!(version.begin_migration(2), Ok(1));
+            version.set(2);
+            assert_eq!(version.begin_migration(2), Err(AlreadyMigrated));
+            Ok(())
+        })
+        .unwrap();
+    }
+}